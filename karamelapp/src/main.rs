@@ -3,53 +3,650 @@ use mimalloc::MiMalloc;
 #[global_allocator]
 static GLOBAL: MiMalloc = MiMalloc;
 extern crate karamellib;
-use clap::{Arg, App};
+use clap::{Arg, App, AppSettings, ArgMatches, SubCommand};
+use std::fs::{self, File};
+use std::env;
+use std::io::{self, BufRead, BufWriter, Read, Write};
+use std::process;
+use std::rc::Rc;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicPtr, Ordering};
 
 
-use karamellib::{constants::{KARAMEL_CONTACT_EMAIL, KARAMEL_HELP_ABOUT, KARAMEL_TITLE, KARAMEL_VERSION}, vm::executer::{ExecutionParameters, ExecutionSource}};
+use karamellib::{constants::{KARAMEL_CONTACT_EMAIL, KARAMEL_LANGUAGE_VARIABLE, KARAMEL_TITLE, KARAMEL_VERSION}, vm::executer::{ExecutionParameters, ExecutionSource, code_bytecode, code_compiler, code_listing}};
+use karamellib::error::diagnostic::Diagnostic;
+use karamellib::{error::generate_error_message, formatter::format_code};
+use karamellib::file::{FileSystem, OsFileSystem, read_file, read_encoded_file};
+use karamellib::file::encoding::{SourceEncoding, decode_source};
+use karamellib::lint::{lint_code, LintConfig, LintRule};
+use karamellib::transpiler::{transpile_code, TranspileTarget};
+use karamellib::vm::events::{JsonLinesEventStream, VmEventHook};
+use karamellib::vm::trace::TraceOutput;
+use karamellib::vm::testing::code_tests;
+use karamellib::vm::bench::{code_bench, BenchOptions, BenchReport};
+use karamellib::constants::KARAMEL_EVENT_BUFFER_SIZE;
+use karamellib::buildin::keywords::keyword_documentation;
+use karamellib::messages;
+use karamellib::compiler::KaramelCompilerContext;
+use karamellib::package::install_packages;
+
+fn format_command(matches: &ArgMatches) {
+    let file = matches.value_of("file").unwrap();
+    let source = read_source(file, source_encoding(matches));
+
+    let formatted = match format_code(source.as_str()) {
+        Ok(formatted) => formatted,
+        Err(error) => {
+            eprintln!("{}", generate_error_message(&source, &error));
+            process::exit(1);
+        }
+    };
+
+    /* Code that is read from the standard input is always written to the screen */
+    match matches.is_present("write") && file != "-" {
+        true => if let Err(error) = fs::write(file, formatted) {
+            eprintln!("{}", error);
+            process::exit(1);
+        },
+        false => print!("{}", formatted)
+    };
+}
+
+fn lint_command(matches: &ArgMatches) {
+    let file = matches.value_of("file").unwrap();
+    let source = read_source(file, source_encoding(matches));
+
+    let mut config = LintConfig::default();
+    for code in matches.values_of("disable").into_iter().flatten() {
+        match LintRule::from_code(code) {
+            Some(rule) => config.disable(rule),
+            None => {
+                eprintln!("{}", messages::format("lint.rule_not_found", &[("0", &code)]));
+                process::exit(1);
+            }
+        };
+    }
+
+    let warnings = match lint_code(source.as_str(), &config) {
+        Ok(warnings) => warnings,
+        Err(error) => {
+            eprintln!("{}", generate_error_message(&source, &error));
+            process::exit(1);
+        }
+    };
+
+    for warning in warnings.iter() {
+        match matches.is_present("json") {
+            true => println!("{}", Diagnostic::from(warning).to_json()),
+            false => println!("{}:{}:{} [{}] {}", file, warning.line + 1, warning.column + 1, warning.rule.code(), warning.message)
+        };
+    }
+
+    if !warnings.is_empty() {
+        process::exit(1);
+    }
+}
+
+fn test_command(matches: &ArgMatches) {
+    let file = matches.value_of("file").unwrap();
+    let source = read_source(file, source_encoding(matches));
+
+    let report = match code_tests(source.as_str()) {
+        Ok(report) => report,
+        Err(error) => {
+            eprintln!("{}", generate_error_message(&source, &error));
+            process::exit(1);
+        }
+    };
+
+    if report.results.is_empty() {
+        eprintln!("{}", messages::text("test.not_found"));
+        process::exit(1);
+    }
+
+    println!("{}", report.render());
+    if report.failed() > 0 {
+        process::exit(1);
+    }
+}
+
+fn bench_command(matches: &ArgMatches) {
+    let file = matches.value_of("file").unwrap();
+    let source = read_source(file, source_encoding(matches));
+
+    let baseline = matches.value_of("compare").map(|baseline_file| match read_file(baseline_file).ok().and_then(|text| BenchReport::from_json(&text)) {
+        Some(baseline) => baseline,
+        None => {
+            eprintln!("{}", messages::format("bench.baseline_invalid", &[("0", &baseline_file)]));
+            process::exit(1);
+        }
+    });
+
+    let defaults = BenchOptions::default();
+    let options = BenchOptions {
+        iterations: matches.value_of("iterations").map_or(defaults.iterations, |value| value.parse().unwrap()),
+        warmup: matches.value_of("warmup").map_or(defaults.warmup, |value| value.parse().unwrap()),
+        function: matches.value_of("function").map(str::to_string)
+    };
+
+    let report = match code_bench(source.as_str(), file, &options) {
+        Ok(report) => report,
+        Err(error) => {
+            eprintln!("{}", generate_error_message(&source, &error));
+            process::exit(1);
+        }
+    };
+
+    println!("{}", report.render(baseline.as_ref()));
+    if let Some(save_file) = matches.value_of("save") {
+        if let Err(error) = fs::write(save_file, report.to_json()) {
+            eprintln!("{}", error);
+            process::exit(1);
+        }
+    }
+}
+
+fn transpile_command(matches: &ArgMatches) {
+    let file = matches.value_of("file").unwrap();
+    let source = read_source(file, source_encoding(matches));
+
+    let target = TranspileTarget::from_name(matches.value_of("target").unwrap()).unwrap();
+    let transpiled = match transpile_code(source.as_str(), target) {
+        Ok(transpiled) => transpiled,
+        Err(error) => {
+            eprintln!("{}", generate_error_message(&source, &error));
+            process::exit(1);
+        }
+    };
+
+    match matches.value_of("output") {
+        Some(output) => if let Err(error) = fs::write(output, transpiled) {
+            eprintln!("{}", error);
+            process::exit(1);
+        },
+        None => print!("{}", transpiled)
+    };
+}
+
+/* Git dependencies are only downloaded by this command, programs use the repositories that are installed before */
+fn install_command(matches: &ArgMatches) {
+    let mut context = KaramelCompilerContext::new();
+    context.execution_path.path = matches.value_of("directory").unwrap().to_string();
+
+    if let Err(error) = install_packages(&mut context) {
+        eprintln!("{}", error);
+        process::exit(1);
+    }
+
+    let mut packages = context.packages.keys().collect::<Vec<_>>();
+    packages.sort();
+    for package in packages.iter() {
+        println!("{}", messages::format("cli.install.done", &[("0", package)]));
+    }
+}
+
+/* Without an output kind the file is only compiled, errors are written and nothing is printed for valid code */
+fn compile_command(file: &str, kind: Option<&str>, encoding: Option<SourceEncoding>) {
+    let source = read_source(file, encoding);
+
+    let output = match kind {
+        Some("listing") => code_listing(source.as_str()),
+        Some(_) => code_bytecode(source.as_str()),
+        None => code_compiler(source.as_str()).map(|_| String::new())
+    };
+
+    match output {
+        Ok(output) => print!("{}", output),
+        Err(error) => {
+            eprintln!("{}", generate_error_message(&source, &error));
+            process::exit(1);
+        }
+    };
+}
+
+fn run_command(matches: &ArgMatches, source: ExecutionSource, interrupt: Arc<AtomicBool>) {
+    let parameters = ExecutionParameters {
+        return_opcode: true,
+        return_output: true,
+        teaching_mode: matches.is_present("teaching"),
+        debug_assertions: matches.is_present("assumptions"),
+        type_guards: matches.is_present("type_guards"),
+        event_hook: event_hook(matches),
+        file_system: source_encoding(matches).map(|encoding| Rc::new(OsFileSystem::with_encoding(encoding)) as Rc<dyn FileSystem>),
+        gc_threshold: matches.value_of("gc_threshold").and_then(|value| value.parse().ok()),
+        max_call_depth: matches.value_of("call_depth").and_then(|value| value.parse().ok()),
+        interrupt: Some(interrupt),
+        trace: trace_output(matches),
+        ..ExecutionParameters::new(source)
+    };
+
+    let result = karamellib::vm::executer::code_executer(parameters);
+    if matches.is_present("json") {
+        for diagnostic in result.diagnostics().iter() {
+            println!("{}", diagnostic.to_json());
+        }
+    }
+
+    match result.executed {
+        true => println!("Success"),
+        false => println!("Fail")
+    };
+
+    /* Scripts can be used as tests and from the shell, failed executions end the process with an error code */
+    if !result.executed {
+        process::exit(1);
+    }
+}
+
+fn repl_command(teaching_mode: bool, interrupt: Arc<AtomicBool>) {
+    println!("{} {}", KARAMEL_TITLE, KARAMEL_VERSION);
+    println!("{}", messages::text("repl.welcome"));
+
+    let stdin = io::stdin();
+    let mut lines = stdin.lock().lines();
+    loop {
+        print!(">>> ");
+        let _ = io::stdout().flush();
+
+        let line = match lines.next() {
+            Some(Ok(line)) => line,
+            _ => break
+        };
+
+        let command = line.trim();
+        if command.is_empty() {
+            continue;
+        }
+
+        if let Some(keyword) = command.strip_prefix(":açıkla").or_else(|| command.strip_prefix(":acikla")) {
+            match keyword_documentation(keyword) {
+                Some(documentation) => println!("{}", documentation),
+                None => println!("{}", messages::format("repl.not_keyword", &[("0", &keyword.trim())]))
+            };
+            continue;
+        }
+
+        if command == ":çık" || command == ":cik" {
+            break;
+        }
+
+        /* Blocks continue until an empty line */
+        let mut code = line.clone();
+        if command.ends_with(':') {
+            loop {
+                print!("... ");
+                let _ = io::stdout().flush();
+                match lines.next() {
+                    Some(Ok(line)) if !line.trim().is_empty() => {
+                        code.push('\n');
+                        code.push_str(&line);
+                    },
+                    _ => break
+                };
+            }
+        }
+
+        interrupt.store(false, Ordering::SeqCst);
+        karamellib::vm::executer::code_executer(ExecutionParameters {
+            teaching_mode,
+            interrupt: Some(interrupt.clone()),
+            ..ExecutionParameters::new(ExecutionSource::Code(code))
+        });
+    }
+}
+
+fn event_hook(matches: &ArgMatches) -> Option<Box<dyn VmEventHook>> {
+    match matches.value_of("events")? {
+        "-" => Some(Box::new(JsonLinesEventStream::new(io::stdout(), KARAMEL_EVENT_BUFFER_SIZE))),
+        file => match File::create(file) {
+            Ok(file) => Some(Box::new(JsonLinesEventStream::new(file, KARAMEL_EVENT_BUFFER_SIZE))),
+            Err(error) => {
+                eprintln!("{}", error);
+                process::exit(1);
+            }
+        }
+    }
+}
+
+fn trace_output(matches: &ArgMatches) -> Option<TraceOutput> {
+    match matches.value_of("trace")? {
+        "-" => Some(TraceOutput::Writer(Box::new(io::stdout()))),
+        file => match File::create(file) {
+            Ok(file) => Some(TraceOutput::Writer(Box::new(BufWriter::new(file)))),
+            Err(error) => {
+                eprintln!("{}", error);
+                process::exit(1);
+            }
+        }
+    }
+}
+
+/* Interrupt flag of the running program. Signal handler can only use the static values. */
+static INTERRUPT: AtomicPtr<AtomicBool> = AtomicPtr::new(std::ptr::null_mut());
+
+#[cfg(unix)]
+extern "C" fn on_interrupt(_: libc::c_int) {
+    let interrupt = INTERRUPT.load(Ordering::SeqCst);
+    unsafe {
+        /* Second Ctrl+C closes the application if the program does not stop */
+        if interrupt.is_null() || (*interrupt).swap(true, Ordering::SeqCst) {
+            libc::_exit(130);
+        }
+    }
+}
+
+/* Ctrl+C stops the running program with an error instead of killing the application */
+fn interrupt_flag() -> Arc<AtomicBool> {
+    let interrupt = Arc::new(AtomicBool::new(false));
+    INTERRUPT.store(Arc::into_raw(interrupt.clone()) as *mut AtomicBool, Ordering::SeqCst);
+
+    #[cfg(unix)]
+    unsafe {
+        libc::signal(libc::SIGINT, on_interrupt as extern "C" fn(libc::c_int) as libc::sighandler_t);
+    }
+    interrupt
+}
+
+fn source_encoding(matches: &ArgMatches) -> Option<SourceEncoding> {
+    matches.value_of("encoding").and_then(SourceEncoding::from_name)
+}
+
+/* '-' reads the program from the standard input */
+fn read_source(file: &str, encoding: Option<SourceEncoding>) -> String {
+    let source = match file {
+        "-" => {
+            let mut code = Vec::new();
+            io::stdin().read_to_end(&mut code).map_err(|error| error.to_string())
+                .and_then(|_| decode_source(file, code, encoding).map_err(|error| error.to_string()))
+        },
+        _ => read_encoded_file(file, encoding).map_err(|error| error.to_string())
+    };
+
+    match source {
+        Ok(source) => source,
+        Err(error) => {
+            eprintln!("{}", error);
+            process::exit(1);
+        }
+    }
+}
+
+/* Files are executed with their paths, so their modules are searched next to them */
+fn execution_source(file: &str, encoding: Option<SourceEncoding>) -> ExecutionSource {
+    match file {
+        "-" => ExecutionSource::Code(read_source(file, encoding)),
+        _ => ExecutionSource::File(file.to_string())
+    }
+}
+
+/* Help texts are needed until the end of the program */
+fn help_text(key: &str) -> &'static str {
+    Box::leak(messages::text(key).into_boxed_str())
+}
+
+/* Options of the program execution, they are used with '--dosya' and the 'run' command */
+fn run_arguments<'a, 'b>() -> Vec<Arg<'a, 'b>> {
+    vec![
+        Arg::with_name("teaching")
+             .long("öğretim")
+             .help(help_text("cli.teaching")),
+        Arg::with_name("assumptions")
+             .long("varsay")
+             .help(help_text("cli.assumptions")),
+        Arg::with_name("type_guards")
+             .long("tür-kontrolü")
+             .help(help_text("cli.type_guards")),
+        Arg::with_name("gc_threshold")
+             .long("çöp-eşiği")
+             .value_name("SAYI")
+             .help(help_text("cli.gc_threshold"))
+             .validator(|value| value.parse::<usize>().map(|_| ()).map_err(|_| messages::format("cli.gc_threshold.invalid", &[("0", &value)])))
+             .takes_value(true),
+        Arg::with_name("call_depth")
+             .long("çağrı-derinliği")
+             .value_name("SAYI")
+             .help(help_text("cli.call_depth"))
+             .validator(|value| value.parse::<usize>().map(|_| ()).map_err(|_| messages::format("cli.call_depth.invalid", &[("0", &value)])))
+             .takes_value(true),
+        Arg::with_name("events")
+             .long("olaylar")
+             .value_name("FILE")
+             .help(help_text("cli.events"))
+             .takes_value(true),
+        Arg::with_name("trace")
+             .long("trace")
+             .value_name("FILE")
+             .help(help_text("cli.trace"))
+             .takes_value(true),
+        Arg::with_name("json")
+             .long("json")
+             .help(help_text("cli.json"))
+    ]
+}
 
 fn main() {
+    if let Ok(language) = env::var(KARAMEL_LANGUAGE_VARIABLE) {
+        messages::set_language(&language);
+    }
+
     let matches = App::new(KARAMEL_TITLE)
                           .version(KARAMEL_VERSION)
                           .author(KARAMEL_CONTACT_EMAIL)
-                          .about(KARAMEL_HELP_ABOUT)
+                          .about(help_text("cli.about"))
+                          /* 'karamelapp run.k' runs the script, the shebang line of the scripts calls the program like this */
+                          .setting(AppSettings::ArgsNegateSubcommands)
+                          .arg(Arg::with_name("encoding")
+                               .long("kodlama")
+                               .value_name("KODLAMA")
+                               .help(help_text("cli.encoding"))
+                               .possible_values(&["utf-8", "windows-1254", "iso-8859-9", "otomatik"])
+                               .global(true)
+                               .takes_value(true))
                           .arg(Arg::with_name("file")
                                .short("d")
                                .long("dosya")
                                .value_name("FILE")
-                               .help("Çalıştırılacak karamel dosyası")
+                               .help(help_text("cli.file"))
+                               .takes_value(true))
+                          .arg(Arg::with_name("script")
+                               .value_name("FILE")
+                               .help(help_text("cli.script"))
+                               .conflicts_with("file")
+                               .index(1))
+                          .args(&run_arguments())
+                          .arg(Arg::with_name("emit")
+                               .long("emit")
+                               .value_name("TÜR")
+                               .help(help_text("cli.emit"))
+                               .possible_values(&["bytecode", "listing"])
+                               .requires("file")
                                .takes_value(true))
+                          .subcommand(SubCommand::with_name("run")
+                               .alias("çalıştır")
+                               .about(help_text("cli.run"))
+                               .arg(Arg::with_name("file")
+                                    .value_name("FILE")
+                                    .help(help_text("cli.file"))
+                                    .required(true))
+                               .args(&run_arguments()))
+                          .subcommand(SubCommand::with_name("compile")
+                               .alias("derle")
+                               .about(help_text("cli.compile"))
+                               .arg(Arg::with_name("file")
+                                    .value_name("FILE")
+                                    .help(help_text("cli.compile.file"))
+                                    .required(true))
+                               .arg(Arg::with_name("emit")
+                                    .long("emit")
+                                    .value_name("TÜR")
+                                    .help(help_text("cli.compile.emit"))
+                                    .possible_values(&["bytecode", "listing"])
+                                    .takes_value(true)))
+                          .subcommand(SubCommand::with_name("format")
+                               .alias("biçimle")
+                               .about(help_text("cli.format"))
+                               .arg(Arg::with_name("file")
+                                    .value_name("FILE")
+                                    .help(help_text("cli.format.file"))
+                                    .required(true))
+                               .arg(Arg::with_name("write")
+                                    .short("y")
+                                    .long("yaz")
+                                    .help(help_text("cli.format.write"))))
+                          .subcommand(SubCommand::with_name("lint")
+                               .about(help_text("cli.lint"))
+                               .arg(Arg::with_name("file")
+                                    .value_name("FILE")
+                                    .help(help_text("cli.lint.file"))
+                                    .required(true))
+                               .arg(Arg::with_name("disable")
+                                    .short("k")
+                                    .long("kapat")
+                                    .value_name("KURALLAR")
+                                    .help(help_text("cli.lint.disable"))
+                                    .takes_value(true)
+                                    .use_delimiter(true))
+                               .arg(Arg::with_name("json")
+                                    .long("json")
+                                    .help(help_text("cli.lint.json"))))
+                          .subcommand(SubCommand::with_name("test")
+                               .alias("sına")
+                               .about(help_text("cli.test"))
+                               .arg(Arg::with_name("file")
+                                    .value_name("FILE")
+                                    .help(help_text("cli.test.file"))
+                                    .required(true)))
+                          .subcommand(SubCommand::with_name("bench")
+                               .alias("ölç")
+                               .about(help_text("cli.bench"))
+                               .arg(Arg::with_name("file")
+                                    .value_name("FILE")
+                                    .help(help_text("cli.bench.file"))
+                                    .required(true))
+                               .arg(Arg::with_name("iterations")
+                                    .short("t")
+                                    .long("tekrar")
+                                    .value_name("SAYI")
+                                    .help(help_text("cli.bench.iterations"))
+                                    .validator(|value| value.parse::<usize>().map(|_| ()).map_err(|_| messages::format("cli.bench.iterations.invalid", &[("0", &value)])))
+                                    .takes_value(true))
+                               .arg(Arg::with_name("warmup")
+                                    .short("ı")
+                                    .long("ısınma")
+                                    .value_name("SAYI")
+                                    .help(help_text("cli.bench.warmup"))
+                                    .validator(|value| value.parse::<usize>().map(|_| ()).map_err(|_| messages::format("cli.bench.warmup.invalid", &[("0", &value)])))
+                                    .takes_value(true))
+                               .arg(Arg::with_name("function")
+                                    .short("f")
+                                    .long("fonksiyon")
+                                    .value_name("İSİM")
+                                    .help(help_text("cli.bench.function"))
+                                    .takes_value(true))
+                               .arg(Arg::with_name("save")
+                                    .short("k")
+                                    .long("kaydet")
+                                    .value_name("DOSYA")
+                                    .help(help_text("cli.bench.save"))
+                                    .takes_value(true))
+                               .arg(Arg::with_name("compare")
+                                    .short("c")
+                                    .long("karşılaştır")
+                                    .value_name("DOSYA")
+                                    .help(help_text("cli.bench.compare"))
+                                    .takes_value(true)))
+                          .subcommand(SubCommand::with_name("transpile")
+                               .alias("çevir")
+                               .about(help_text("cli.transpile"))
+                               .arg(Arg::with_name("file")
+                                    .value_name("FILE")
+                                    .help(help_text("cli.transpile.file"))
+                                    .required(true))
+                               .arg(Arg::with_name("target")
+                                    .long("hedef")
+                                    .value_name("DİL")
+                                    .help(help_text("cli.transpile.target"))
+                                    .possible_values(&["javascript", "js", "python", "py"])
+                                    .default_value("javascript")
+                                    .takes_value(true))
+                               .arg(Arg::with_name("output")
+                                    .short("ç")
+                                    .long("çıktı")
+                                    .value_name("DOSYA")
+                                    .help(help_text("cli.transpile.output"))
+                                    .takes_value(true)))
+                          .subcommand(SubCommand::with_name("install")
+                               .alias("kur")
+                               .about(help_text("cli.install"))
+                               .arg(Arg::with_name("directory")
+                                    .value_name("DİZİN")
+                                    .help(help_text("cli.install.directory"))
+                                    .default_value(".")))
+                          .subcommand(SubCommand::with_name("repl")
+                               .about(help_text("cli.repl")))
                           .get_matches();
 
-    let parameters = match matches.value_of("file") {
-        Some(file) => ExecutionParameters {
-            source: ExecutionSource::File(file.to_string()),
-            return_opcode: true,
-            return_output: true,
-            dump_opcode: false,
-            dump_memory: false
-        },
-        None => ExecutionParameters {
-            source: ExecutionSource::Code(r#"
+    if let Some(matches) = matches.subcommand_matches("format") {
+        format_command(matches);
+        return;
+    }
+
+    if let Some(matches) = matches.subcommand_matches("test") {
+        test_command(matches);
+        return;
+    }
+
+    if let Some(matches) = matches.subcommand_matches("bench") {
+        bench_command(matches);
+        return;
+    }
+
+    if let Some(matches) = matches.subcommand_matches("transpile") {
+        transpile_command(matches);
+        return;
+    }
+
+    if let Some(matches) = matches.subcommand_matches("install") {
+        install_command(matches);
+        return;
+    }
+
+    if let Some(matches) = matches.subcommand_matches("lint") {
+        lint_command(matches);
+        return;
+    }
+
+    if let Some(matches) = matches.subcommand_matches("compile") {
+        compile_command(matches.value_of("file").unwrap(), matches.value_of("emit"), source_encoding(matches));
+        return;
+    }
+
+    if let Some(kind) = matches.value_of("emit") {
+        compile_command(matches.value_of("file").unwrap(), Some(kind), source_encoding(&matches));
+        return;
+    }
+
+    let interrupt = interrupt_flag();
+    if matches.subcommand_matches("repl").is_some() {
+        repl_command(matches.is_present("teaching"), interrupt);
+        return;
+    }
+
+    if let Some(matches) = matches.subcommand_matches("run") {
+        run_command(matches, execution_source(matches.value_of("file").unwrap(), source_encoding(matches)), interrupt);
+        return;
+    }
+
+    let source = match matches.value_of("file").or_else(|| matches.value_of("script")) {
+        Some(file) => execution_source(file, source_encoding(&matches)),
+        None => ExecutionSource::Code(r#"
 döngü i = 0, i < 10, i++:
     i mod 2 ise:
         gç::satıryaz('Mod 2 ', i.yazi())
     veya:
         gç::satıryaz('Mod 1 ', i.yazi())
            
-"#.to_string()),
-            return_opcode: true,
-            return_output: true,
-            dump_opcode: false,
-            dump_memory: false
-        }
-    };
-
-    
-    let result = karamellib::vm::executer::code_executer(parameters);
-    match result.executed {
-        true => println!("Success"),
-        false => println!("Fail")
+"#.to_string())
     };
+    run_command(&matches, source, interrupt);
 }
-
@@ -0,0 +1,89 @@
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::path::PathBuf;
+    use std::process::{Command, Output};
+
+    /* Every test has its own directory, the scripts are called with their relative paths like a shebang line does */
+    fn run_script(directory: &str, name: &str, code: &str) -> Output {
+        let directory: PathBuf = std::env::temp_dir().join(format!("karamelapp_{}_{}", directory, std::process::id()));
+        fs::create_dir_all(&directory).unwrap();
+        fs::write(directory.join(name), code).unwrap();
+
+        let output = Command::new(env!("CARGO_BIN_EXE_karamelapp"))
+            .arg(name)
+            .current_dir(&directory)
+            .output()
+            .unwrap();
+        fs::remove_dir_all(&directory).unwrap();
+        output
+    }
+
+    #[test]
+    fn test_script_named_like_subcommand() {
+        for name in ["test.k", "./test.k", "run.k", "lint.k", "rep.k"].iter() {
+            let output = run_script("subcommand", name, "#!/usr/bin/env karamelapp\ngç::satıryaz('çalıştı')");
+            let stdout = String::from_utf8_lossy(&output.stdout);
+            assert!(output.status.success(), "{}: {}", name, String::from_utf8_lossy(&output.stderr));
+            assert!(stdout.contains("çalıştı"), "{}: {}", name, stdout);
+        }
+    }
+
+    #[test]
+    fn test_failed_script_exit_code() {
+        let output = run_script("failed", "hata.k", "a = 'ab' * 1e19");
+        assert_eq!(output.status.code(), Some(1));
+
+        let output = run_script("syntax", "hata.k", "a = (1");
+        assert_eq!(output.status.code(), Some(1));
+    }
+
+    #[test]
+    fn test_invalid_call_depth() {
+        let output = Command::new(env!("CARGO_BIN_EXE_karamelapp")).args(&["--çağrı-derinliği", "derin", "-"]).env("KARAMEL_DIL", "tr").output().unwrap();
+        assert_eq!(output.status.code(), Some(1));
+        assert!(String::from_utf8_lossy(&output.stderr).contains("'derin' geçerli bir çağrı derinliği değil"), "{}", String::from_utf8_lossy(&output.stderr));
+    }
+
+    #[test]
+    fn test_invalid_bench_counts() {
+        for (option, message) in [("--tekrar", "'çok' geçerli bir tekrar sayısı değil"), ("--ısınma", "'çok' geçerli bir ısınma sayısı değil")].iter() {
+            let output = Command::new(env!("CARGO_BIN_EXE_karamelapp")).args(&["ölç", option, "çok", "-"]).env("KARAMEL_DIL", "tr").output().unwrap();
+            assert_eq!(output.status.code(), Some(1));
+            assert!(String::from_utf8_lossy(&output.stderr).contains(message), "{}", String::from_utf8_lossy(&output.stderr));
+        }
+    }
+
+    #[test]
+    fn test_help_output() {
+        let output = run_script("help", "yardım.k", "baz::yardım([1, 2])");
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+        assert!(stdout.contains("Tür: liste") && stdout.contains("uzunluk"), "{}", stdout);
+    }
+
+    #[test]
+    fn test_install_packages() {
+        let directory: PathBuf = std::env::temp_dir().join(format!("karamelapp_install_{}", std::process::id()));
+        fs::create_dir_all(directory.join("araç")).unwrap();
+        fs::write(directory.join("araç").join("baz.k"), "fonk selam():\n    döndür 'selam'").unwrap();
+        fs::write(directory.join("küfe.toml"), "[bağımlılıklar]\naraç = { yol = \"araç\" }\ngrafik = { git = \"https://ornek.org/grafik.git\" }").unwrap();
+        fs::write(directory.join("ana.k"), "kullan araç\ngç::satıryaz(araç::selam())").unwrap();
+
+        /* Running the program does not download the repository */
+        let output = Command::new(env!("CARGO_BIN_EXE_karamelapp")).arg("ana.k").current_dir(&directory).output().unwrap();
+        assert_eq!(output.status.code(), Some(1));
+        assert!(String::from_utf8_lossy(&output.stdout).contains("'grafik'"), "{}", String::from_utf8_lossy(&output.stdout));
+        assert!(!directory.join(".küfe").exists());
+        assert!(!directory.join("küfe.kilit").exists());
+
+        fs::write(directory.join("küfe.toml"), "[bağımlılıklar]\naraç = { yol = \"araç\" }").unwrap();
+        let output = Command::new(env!("CARGO_BIN_EXE_karamelapp")).arg("kur").current_dir(&directory).output().unwrap();
+        assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+        assert!(fs::read_to_string(directory.join("küfe.kilit")).unwrap().contains("[araç]"));
+
+        let output = Command::new(env!("CARGO_BIN_EXE_karamelapp")).arg("ana.k").current_dir(&directory).output().unwrap();
+        assert!(String::from_utf8_lossy(&output.stdout).contains("selam"), "{}", String::from_utf8_lossy(&output.stderr));
+        fs::remove_dir_all(&directory).unwrap();
+    }
+}
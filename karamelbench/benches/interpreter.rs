@@ -0,0 +1,68 @@
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+
+use karamelbench::{ARITHMETIC, BRANCHES, CONSTANT_ACCESS, DICT_ACCESS, FUNCTION_CALL, SOURCES, TEXT_BUILDER, TEXT_CONCAT};
+use karamellib::compiler::KaramelCompilerContext;
+use karamellib::parser::Parser;
+use karamellib::syntax::SyntaxParser;
+use karamellib::vm::executer::{code_executer, ExecutionParameters, ExecutionSource};
+
+fn execute(code: &str) {
+    let status = code_executer(ExecutionParameters {
+        return_output: true,
+        ..ExecutionParameters::new(ExecutionSource::Code(code.to_string()))
+    });
+    assert!(status.executed, "{:?}", status.error);
+}
+
+fn tokenizer(c: &mut Criterion) {
+    let mut group = c.benchmark_group("tokenizer");
+    for (name, code) in SOURCES.iter() {
+        group.throughput(Throughput::Bytes(code.len() as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(name), code, |b, code| b.iter(|| {
+            let mut parser = Parser::new(black_box(code));
+            parser.parse().unwrap();
+            parser.tokens().len()
+        }));
+    }
+    group.finish();
+}
+
+fn syntax_parser(c: &mut Criterion) {
+    let mut group = c.benchmark_group("syntax");
+    for (name, code) in SOURCES.iter() {
+        let mut parser = Parser::new(code);
+        parser.parse().unwrap();
+        let tokens = parser.tokens();
+
+        group.throughput(Throughput::Elements(tokens.len() as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(name), &tokens, |b, tokens| b.iter(|| {
+            SyntaxParser::new(black_box(tokens.clone())).parse().unwrap()
+        }));
+    }
+    group.finish();
+}
+
+fn constant_lookup(c: &mut Criterion) {
+    let context = KaramelCompilerContext::new();
+    let module_path = vec!["sabitler".to_string()];
+    let name = "pi".to_string();
+
+    c.bench_function("constant/lookup", |b| b.iter(|| {
+        context.get_constant(black_box(&name), black_box(&module_path)).unwrap()
+    }));
+}
+
+fn programs(c: &mut Criterion) {
+    let mut group = c.benchmark_group("program");
+    group.bench_function("aritmetik", |b| b.iter(|| execute(ARITHMETIC)));
+    group.bench_function("fonksiyon", |b| b.iter(|| execute(FUNCTION_CALL)));
+    group.bench_function("sozluk", |b| b.iter(|| execute(DICT_ACCESS)));
+    group.bench_function("sabit", |b| b.iter(|| execute(CONSTANT_ACCESS)));
+    group.bench_function("kosul", |b| b.iter(|| execute(BRANCHES)));
+    group.bench_function("yazi_birlestirme", |b| b.iter(|| execute(TEXT_CONCAT)));
+    group.bench_function("yazi_olusturucu", |b| b.iter(|| execute(TEXT_BUILDER)));
+    group.finish();
+}
+
+criterion_group!(benches, tokenizer, syntax_parser, constant_lookup, programs);
+criterion_main!(benches);
@@ -0,0 +1,29 @@
+//! Representative programs for the interpreter benchmarks in `benches/interpreter.rs`.
+
+/// Loop with number arithmetic and compound assignment
+pub static ARITHMETIC: &str = include_str!("../programs/aritmetik.k");
+
+/// Recursive function calls
+pub static FUNCTION_CALL: &str = include_str!("../programs/fonksiyon.k");
+
+/// Dictionary reads and indexer assignment in a loop
+pub static DICT_ACCESS: &str = include_str!("../programs/sozluk.k");
+
+/// Module constant lookups in a loop
+pub static CONSTANT_ACCESS: &str = include_str!("../programs/sabit.k");
+
+/// Conditions and jumps in a loop, mostly measures the opcode dispatch
+pub static BRANCHES: &str = include_str!("../programs/kosul.k");
+
+/// Text built with `+` in a loop, every step copies the whole text
+pub static TEXT_CONCAT: &str = include_str!("../programs/yazi_birlestirme.k");
+
+/// The same text built with `baz::yazı_oluşturucu`
+pub static TEXT_BUILDER: &str = include_str!("../programs/yazi_olusturucu.k");
+
+/// Bigger programs for the tokenizer and the syntax parser
+pub static SOURCES: &[(&str, &str)] = &[
+    ("not_hesaplama", include_str!("../../karamellib/tests/programs/not_hesaplama.k")),
+    ("liste_sozluk", include_str!("../../karamellib/tests/programs/liste_sozluk.k")),
+    ("yazi_islemleri", include_str!("../../karamellib/tests/programs/yazi_islemleri.k"))
+];
@@ -1,10 +1,16 @@
-use crate::compiler::{EMPTY_OBJECT, function::{FunctionParameter, FunctionReference, NativeCall, NativeCallResult}};
+use crate::compiler::{EMPTY_OBJECT, KaramelPrimative, DictKey, BoundFunction, function::{FunctionParameter, FunctionReference, FunctionType, NativeCall, NativeCallResult}};
+use crate::buildin::class::priority_queue::PriorityQueue;
+use crate::buildin::class::error::ErrorObject;
+use crate::buildin::class::frozen;
+use crate::buildin::class::bytes;
+use crate::buildin::keywords::keyword_documentation;
 use crate::types::VmObject;
-use crate::buildin::{Module, Class};
+use crate::buildin::{Module, Class, ClassProperty};
 use crate::compiler::GetType;
+use crate::compiler::semantics;
 use crate::error::KaramelErrorType;
-use crate::{n_parameter_expected};
-use std::{cell::RefCell, collections::HashMap};
+use crate::{n_parameter_expected, expected_parameter_type};
+use std::{cell::RefCell, collections::{BTreeMap, HashMap, VecDeque}};
 use std::rc::Rc;
 
 
@@ -58,9 +64,366 @@ impl BaseFunctionsModule  {
 
         let rc_module = Rc::new(module);
         rc_module.methods.borrow_mut().insert("tür_bilgisi".to_string(), FunctionReference::native_function(Self::type_info as NativeCall, "tür_bilgisi".to_string(), rc_module.clone()));
+        rc_module.methods.borrow_mut().insert("yığın".to_string(), FunctionReference::native_function(Self::stack as NativeCall, "yığın".to_string(), rc_module.clone()));
+        rc_module.methods.borrow_mut().insert("yigin".to_string(), FunctionReference::native_function(Self::stack as NativeCall, "yigin".to_string(), rc_module.clone()));
+        rc_module.methods.borrow_mut().insert("kuyruk".to_string(), FunctionReference::native_function(Self::queue as NativeCall, "kuyruk".to_string(), rc_module.clone()));
+        rc_module.methods.borrow_mut().insert("öncelik_kuyruğu".to_string(), FunctionReference::native_function(Self::priority_queue as NativeCall, "öncelik_kuyruğu".to_string(), rc_module.clone()));
+        rc_module.methods.borrow_mut().insert("oncelik_kuyrugu".to_string(), FunctionReference::native_function(Self::priority_queue as NativeCall, "oncelik_kuyrugu".to_string(), rc_module.clone()));
+        rc_module.methods.borrow_mut().insert("sıralı_sözlük".to_string(), FunctionReference::native_function(Self::sorted_dict as NativeCall, "sıralı_sözlük".to_string(), rc_module.clone()));
+        rc_module.methods.borrow_mut().insert("sirali_sozluk".to_string(), FunctionReference::native_function(Self::sorted_dict as NativeCall, "sirali_sozluk".to_string(), rc_module.clone()));
+        rc_module.methods.borrow_mut().insert("yazı_oluşturucu".to_string(), FunctionReference::native_function(Self::text_builder as NativeCall, "yazı_oluşturucu".to_string(), rc_module.clone()));
+        rc_module.methods.borrow_mut().insert("yazi_olusturucu".to_string(), FunctionReference::native_function(Self::text_builder as NativeCall, "yazi_olusturucu".to_string(), rc_module.clone()));
+        rc_module.methods.borrow_mut().insert("hata".to_string(), FunctionReference::native_function(Self::error as NativeCall, "hata".to_string(), rc_module.clone()));
+        rc_module.methods.borrow_mut().insert("anahtar_kelime".to_string(), FunctionReference::native_function(Self::keyword as NativeCall, "anahtar_kelime".to_string(), rc_module.clone()));
+        rc_module.methods.borrow_mut().insert("belge".to_string(), FunctionReference::native_function(Self::documentation as NativeCall, "belge".to_string(), rc_module.clone()));
+        rc_module.methods.borrow_mut().insert("yardım".to_string(), FunctionReference::native_function(Self::help as NativeCall, "yardım".to_string(), rc_module.clone()));
+        rc_module.methods.borrow_mut().insert("yardim".to_string(), FunctionReference::native_function(Self::help as NativeCall, "yardim".to_string(), rc_module.clone()));
+        rc_module.methods.borrow_mut().insert("türü".to_string(), FunctionReference::native_function(Self::type_name as NativeCall, "türü".to_string(), rc_module.clone()));
+        rc_module.methods.borrow_mut().insert("turu".to_string(), FunctionReference::native_function(Self::type_name as NativeCall, "turu".to_string(), rc_module.clone()));
+        rc_module.methods.borrow_mut().insert("sayımı".to_string(), FunctionReference::native_function(Self::is_number as NativeCall, "sayımı".to_string(), rc_module.clone()));
+        rc_module.methods.borrow_mut().insert("sayimi".to_string(), FunctionReference::native_function(Self::is_number as NativeCall, "sayimi".to_string(), rc_module.clone()));
+        rc_module.methods.borrow_mut().insert("yazımı".to_string(), FunctionReference::native_function(Self::is_text as NativeCall, "yazımı".to_string(), rc_module.clone()));
+        rc_module.methods.borrow_mut().insert("yazimi".to_string(), FunctionReference::native_function(Self::is_text as NativeCall, "yazimi".to_string(), rc_module.clone()));
+        rc_module.methods.borrow_mut().insert("listemi".to_string(), FunctionReference::native_function(Self::is_list as NativeCall, "listemi".to_string(), rc_module.clone()));
+        rc_module.methods.borrow_mut().insert("sözlükmü".to_string(), FunctionReference::native_function(Self::is_dict as NativeCall, "sözlükmü".to_string(), rc_module.clone()));
+        rc_module.methods.borrow_mut().insert("sozlukmu".to_string(), FunctionReference::native_function(Self::is_dict as NativeCall, "sozlukmu".to_string(), rc_module.clone()));
+        rc_module.methods.borrow_mut().insert("sayıya".to_string(), FunctionReference::native_function(Self::to_number as NativeCall, "sayıya".to_string(), rc_module.clone()));
+        rc_module.methods.borrow_mut().insert("sayiya".to_string(), FunctionReference::native_function(Self::to_number as NativeCall, "sayiya".to_string(), rc_module.clone()));
+        rc_module.methods.borrow_mut().insert("yazıya".to_string(), FunctionReference::native_function(Self::to_text as NativeCall, "yazıya".to_string(), rc_module.clone()));
+        rc_module.methods.borrow_mut().insert("yaziya".to_string(), FunctionReference::native_function(Self::to_text as NativeCall, "yaziya".to_string(), rc_module.clone()));
+        rc_module.methods.borrow_mut().insert("mantığa".to_string(), FunctionReference::native_function(Self::to_bool as NativeCall, "mantığa".to_string(), rc_module.clone()));
+        rc_module.methods.borrow_mut().insert("mantiga".to_string(), FunctionReference::native_function(Self::to_bool as NativeCall, "mantiga".to_string(), rc_module.clone()));
+        rc_module.methods.borrow_mut().insert("bayta".to_string(), FunctionReference::native_function(Self::to_bytes as NativeCall, "bayta".to_string(), rc_module.clone()));
+        rc_module.methods.borrow_mut().insert("onaltılıktan".to_string(), FunctionReference::native_function(Self::from_hex as NativeCall, "onaltılıktan".to_string(), rc_module.clone()));
+        rc_module.methods.borrow_mut().insert("onaltiliktan".to_string(), FunctionReference::native_function(Self::from_hex as NativeCall, "onaltiliktan".to_string(), rc_module.clone()));
+        rc_module.methods.borrow_mut().insert("base64ten".to_string(), FunctionReference::native_function(Self::from_base64 as NativeCall, "base64ten".to_string(), rc_module.clone()));
+        rc_module.methods.borrow_mut().insert("listeye".to_string(), FunctionReference::native_function(Self::to_list as NativeCall, "listeye".to_string(), rc_module.clone()));
+        rc_module.methods.borrow_mut().insert("derin_eşit".to_string(), FunctionReference::native_function(Self::deep_equal as NativeCall, "derin_eşit".to_string(), rc_module.clone()));
+        rc_module.methods.borrow_mut().insert("derin_esit".to_string(), FunctionReference::native_function(Self::deep_equal as NativeCall, "derin_esit".to_string(), rc_module.clone()));
+        rc_module.methods.borrow_mut().insert("karşılaştır".to_string(), FunctionReference::native_function(Self::compare as NativeCall, "karşılaştır".to_string(), rc_module.clone()));
+        rc_module.methods.borrow_mut().insert("karsilastir".to_string(), FunctionReference::native_function(Self::compare as NativeCall, "karsilastir".to_string(), rc_module.clone()));
+        rc_module.methods.borrow_mut().insert("kopyala".to_string(), FunctionReference::native_function(Self::copy as NativeCall, "kopyala".to_string(), rc_module.clone()));
+        rc_module.methods.borrow_mut().insert("dondurulmuş".to_string(), FunctionReference::native_function(Self::freeze as NativeCall, "dondurulmuş".to_string(), rc_module.clone()));
+        rc_module.methods.borrow_mut().insert("dondurulmus".to_string(), FunctionReference::native_function(Self::freeze as NativeCall, "dondurulmus".to_string(), rc_module.clone()));
+        rc_module.methods.borrow_mut().insert("bağla".to_string(), FunctionReference::native_function(Self::bind as NativeCall, "bağla".to_string(), rc_module.clone()));
+        rc_module.methods.borrow_mut().insert("bagla".to_string(), FunctionReference::native_function(Self::bind as NativeCall, "bagla".to_string(), rc_module.clone()));
         rc_module
     }
 
+    fn initial_items(function_name: &str, parameter: &FunctionParameter) -> Result<Vec<VmObject>, KaramelErrorType> {
+        match parameter.length() {
+            0 => Ok(Vec::new()),
+            1 => match &*parameter.iter().next().unwrap().deref() {
+                KaramelPrimative::List(list) => Ok(list.borrow().clone()),
+                _ => expected_parameter_type!(function_name.to_string(), "Liste".to_string())
+            },
+            _ => n_parameter_expected!(function_name.to_string(), 1, parameter.length())
+        }
+    }
+
+    pub fn stack(parameter: FunctionParameter) -> NativeCallResult {
+        let items = Self::initial_items("yığın", &parameter)?;
+        Ok(VmObject::native_convert(KaramelPrimative::Stack(RefCell::new(items))))
+    }
+
+    pub fn queue(parameter: FunctionParameter) -> NativeCallResult {
+        let items = Self::initial_items("kuyruk", &parameter)?;
+        Ok(VmObject::native_convert(KaramelPrimative::Queue(RefCell::new(VecDeque::from(items)))))
+    }
+
+    pub fn priority_queue(parameter: FunctionParameter) -> NativeCallResult {
+        let mut queue = PriorityQueue::new();
+        for item in Self::initial_items("öncelik_kuyruğu", &parameter)? {
+            match item.as_number() {
+                Some(priority) => queue.push(item, priority),
+                None => return expected_parameter_type!("öncelik_kuyruğu".to_string(), "Sayı".to_string())
+            }
+        }
+        Ok(VmObject::native_convert(KaramelPrimative::PriorityQueue(RefCell::new(queue))))
+    }
+
+    /// Items of the dict are copied, later changes to the dict are not seen in the sorted dict
+    pub fn sorted_dict(parameter: FunctionParameter) -> NativeCallResult {
+        let items = match parameter.length() {
+            0 => BTreeMap::new(),
+            1 => match &*parameter.iter().next().unwrap().deref() {
+                KaramelPrimative::Dict(items) => items.borrow().iter().map(|(key, item)| (key.clone(), *item)).collect(),
+                KaramelPrimative::SortedDict(items) => items.borrow().clone(),
+                _ => return expected_parameter_type!("sıralı_sözlük".to_string(), "Sözlük".to_string())
+            },
+            _ => return n_parameter_expected!("sıralı_sözlük".to_string(), 1, parameter.length())
+        };
+        Ok(VmObject::native_convert(KaramelPrimative::SortedDict(RefCell::new(items))))
+    }
+
+    pub fn text_builder(parameter: FunctionParameter) -> NativeCallResult {
+        match parameter.length() {
+            0 => Ok(VmObject::native_convert(KaramelPrimative::TextBuilder(RefCell::new(String::new())))),
+            1 => match &*parameter.iter().next().unwrap().deref() {
+                KaramelPrimative::Text(text) => Ok(VmObject::native_convert(KaramelPrimative::TextBuilder(RefCell::new(text.to_string())))),
+                _ => expected_parameter_type!("yazı_oluşturucu".to_string(), "Yazı".to_string())
+            },
+            _ => n_parameter_expected!("yazı_oluşturucu".to_string(), 1, parameter.length())
+        }
+    }
+
+    /// Creates error value with code, message and optional data
+    pub fn error(parameter: FunctionParameter) -> NativeCallResult {
+        if parameter.length() < 2 || parameter.length() > 3 {
+            return n_parameter_expected!("hata".to_string(), 3, parameter.length());
+        }
+
+        let mut arguments = parameter.iter();
+        let code = *arguments.next().unwrap();
+        let message = match &*arguments.next().unwrap().deref() {
+            KaramelPrimative::Text(message) => message.clone(),
+            _ => return expected_parameter_type!("hata".to_string(), "Yazı".to_string())
+        };
+        let data = arguments.next().copied().unwrap_or(EMPTY_OBJECT);
+
+        Ok(VmObject::native_convert(KaramelPrimative::Error(ErrorObject::new(code, message, data))))
+    }
+
+    pub fn keyword(parameter: FunctionParameter) -> NativeCallResult {
+        if parameter.length() != 1 {
+            return n_parameter_expected!("anahtar_kelime".to_string(), 1, parameter.length());
+        }
+
+        match &*parameter.iter().next().unwrap().deref() {
+            KaramelPrimative::Text(keyword) => match keyword_documentation(keyword) {
+                Some(documentation) => Ok(VmObject::native_convert(KaramelPrimative::Text(Rc::new(documentation)))),
+                None => Ok(EMPTY_OBJECT)
+            },
+            _ => expected_parameter_type!("anahtar_kelime".to_string(), "Yazı".to_string())
+        }
+    }
+
+    /// Returns the '##' comments of the function
+    pub fn documentation(parameter: FunctionParameter) -> NativeCallResult {
+        if parameter.length() != 1 {
+            return n_parameter_expected!("belge".to_string(), 1, parameter.length());
+        }
+
+        let function = match &*parameter.iter().next().unwrap().deref() {
+            KaramelPrimative::Function(reference, _) => reference.clone(),
+            KaramelPrimative::Bound(bound) => bound.function.clone(),
+            _ => return expected_parameter_type!("belge".to_string(), "Fonksiyon".to_string())
+        };
+
+        let documentation = function.documentation.borrow().clone();
+        match documentation {
+            Some(documentation) => Ok(VmObject::from(documentation)),
+            None => Ok(EMPTY_OBJECT)
+        }
+    }
+
+    /// Writes the type, functions and fields of the value. Functions are written with their arguments and '##' comments.
+    pub fn help(parameter: FunctionParameter) -> NativeCallResult {
+        if parameter.length() != 1 {
+            return n_parameter_expected!("yardım".to_string(), 1, parameter.length());
+        }
+
+        let value = parameter.iter().next().unwrap().deref();
+        let class = match &*value {
+            KaramelPrimative::Class(class) => Some(class.clone()),
+            _ => parameter.context().map(|context| context.get_class(&value))
+        };
+
+        let mut buffer = help_text(&value, class);
+        buffer.push_str("\r\n");
+        log::info!("{}", buffer);
+
+        parameter.write_to_stdout(&buffer);
+        Ok(EMPTY_OBJECT)
+    }
+
+    pub fn type_name(parameter: FunctionParameter) -> NativeCallResult {
+        if parameter.length() != 1 {
+            return n_parameter_expected!("türü".to_string(), 1, parameter.length());
+        }
+
+        Ok(VmObject::from(Rc::new(parameter.iter().next().unwrap().deref().get_type())))
+    }
+
+    /// Compares the type of the only parameter with the sample value
+    fn is_type(function_name: &str, parameter: &FunctionParameter, sample: KaramelPrimative) -> NativeCallResult {
+        if parameter.length() != 1 {
+            return n_parameter_expected!(function_name.to_string(), 1, parameter.length());
+        }
+
+        let value = parameter.iter().next().unwrap().deref();
+        Ok(VmObject::from(value.discriminant() == sample.discriminant()))
+    }
+
+    pub fn is_number(parameter: FunctionParameter) -> NativeCallResult {
+        Self::is_type("sayımı", &parameter, KaramelPrimative::Number(0.0))
+    }
+
+    pub fn is_text(parameter: FunctionParameter) -> NativeCallResult {
+        Self::is_type("yazımı", &parameter, KaramelPrimative::Text(Rc::new(String::new())))
+    }
+
+    pub fn is_list(parameter: FunctionParameter) -> NativeCallResult {
+        Self::is_type("listemi", &parameter, KaramelPrimative::List(RefCell::new(Vec::new())))
+    }
+
+    pub fn is_dict(parameter: FunctionParameter) -> NativeCallResult {
+        Self::is_type("sözlükmü", &parameter, KaramelPrimative::Dict(RefCell::new(HashMap::new())))
+    }
+
+    /// Converted value, or an error value that keeps the original value as data
+    fn convert(function_name: &str, target: &str, parameter: &FunctionParameter, converter: fn(&KaramelPrimative) -> Option<KaramelPrimative>) -> NativeCallResult {
+        if parameter.length() != 1 {
+            return n_parameter_expected!(function_name.to_string(), 1, parameter.length());
+        }
+
+        let value = *parameter.iter().next().unwrap();
+        let converted = converter(&value.deref());
+        match converted {
+            Some(converted) => Ok(VmObject::native_convert(converted)),
+            None => {
+                let mut error = ErrorObject::from_error(&KaramelErrorType::ConversionFailed { value: value.to_string(), target: target.to_string() });
+                error.data = value;
+                Ok(VmObject::native_convert(KaramelPrimative::Error(error)))
+            }
+        }
+    }
+
+    pub fn to_number(parameter: FunctionParameter) -> NativeCallResult {
+        Self::convert("sayıya", "sayı", &parameter, |value| match value {
+            KaramelPrimative::Number(number) => Some(KaramelPrimative::Number(*number)),
+            KaramelPrimative::Text(text) => text.trim().parse::<f64>().ok().map(KaramelPrimative::Number),
+            KaramelPrimative::Bool(value) => Some(KaramelPrimative::Number(if *value { 1.0 } else { 0.0 })),
+            _ => None
+        })
+    }
+
+    pub fn to_text(parameter: FunctionParameter) -> NativeCallResult {
+        Self::convert("yazıya", "yazı", &parameter, |value| match value {
+            KaramelPrimative::Text(text) => Some(KaramelPrimative::Text(text.clone())),
+            KaramelPrimative::TextBuilder(text) => Some(KaramelPrimative::Text(Rc::new(text.borrow().clone()))),
+            KaramelPrimative::Function(_, _) | KaramelPrimative::Class(_) => None,
+            value => Some(KaramelPrimative::Text(Rc::new(value.to_string())))
+        })
+    }
+
+    pub fn to_bool(parameter: FunctionParameter) -> NativeCallResult {
+        Self::convert("mantığa", "bool", &parameter, |value| match value {
+            KaramelPrimative::Bool(value) => Some(KaramelPrimative::Bool(*value)),
+            KaramelPrimative::Number(number) if *number == 1.0 => Some(KaramelPrimative::Bool(true)),
+            KaramelPrimative::Number(number) if *number == 0.0 => Some(KaramelPrimative::Bool(false)),
+            KaramelPrimative::Text(text) => match text.trim() {
+                "doğru" | "dogru" => Some(KaramelPrimative::Bool(true)),
+                "yanlış" | "yanlis" => Some(KaramelPrimative::Bool(false)),
+                _ => None
+            },
+            _ => None
+        })
+    }
+
+    /// Texts are converted to their UTF-8 bytes, lists should contain the numbers from 0 to 255
+    pub fn to_bytes(parameter: FunctionParameter) -> NativeCallResult {
+        Self::convert("bayta", "bayt", &parameter, |value| match value {
+            KaramelPrimative::Bytes(bytes) => Some(KaramelPrimative::Bytes(bytes.clone())),
+            KaramelPrimative::Text(text) => Some(KaramelPrimative::Bytes(Rc::new(text.as_bytes().to_vec()))),
+            KaramelPrimative::List(items) => items.borrow().iter().map(|item| match item.as_number() {
+                Some(number) if (0.0..=255.0).contains(&number) && number.fract() == 0.0 => Some(number as u8),
+                _ => None
+            }).collect::<Option<Vec<u8>>>().map(|bytes| KaramelPrimative::Bytes(Rc::new(bytes))),
+            _ => None
+        })
+    }
+
+    pub fn from_hex(parameter: FunctionParameter) -> NativeCallResult {
+        Self::convert("onaltılıktan", "bayt", &parameter, |value| match value {
+            KaramelPrimative::Text(text) => bytes::from_hex(text).map(|bytes| KaramelPrimative::Bytes(Rc::new(bytes))),
+            _ => None
+        })
+    }
+
+    pub fn from_base64(parameter: FunctionParameter) -> NativeCallResult {
+        Self::convert("base64ten", "bayt", &parameter, |value| match value {
+            KaramelPrimative::Text(text) => bytes::from_base64(text).map(|bytes| KaramelPrimative::Bytes(Rc::new(bytes))),
+            _ => None
+        })
+    }
+
+    pub fn to_list(parameter: FunctionParameter) -> NativeCallResult {
+        Self::convert("listeye", "liste", &parameter, |value| Self::list_items(value).map(|items| KaramelPrimative::List(RefCell::new(items))))
+    }
+
+    fn list_items(value: &KaramelPrimative) -> Option<Vec<VmObject>> {
+        let items = match value {
+            KaramelPrimative::List(items) | KaramelPrimative::Stack(items) => items.borrow().clone(),
+            KaramelPrimative::Queue(items) => items.borrow().iter().copied().collect(),
+            KaramelPrimative::PriorityQueue(queue) => queue.borrow().to_vec(),
+            KaramelPrimative::Frozen(inner) => return Self::list_items(&inner.deref()),
+            KaramelPrimative::Text(text) => text.chars().map(|character| VmObject::from(Rc::new(character.to_string()))).collect(),
+            KaramelPrimative::Dict(items) => {
+                let mut keys: Vec<DictKey> = items.borrow().keys().cloned().collect();
+                keys.sort();
+                keys.iter().map(DictKey::to_value).collect()
+            },
+            KaramelPrimative::SortedDict(items) => items.borrow().keys().map(DictKey::to_value).collect(),
+            KaramelPrimative::Bytes(bytes) => bytes.iter().map(|byte| VmObject::from(*byte as f64)).collect(),
+            _ => return None
+        };
+        Some(items)
+    }
+
+    pub fn deep_equal(parameter: FunctionParameter) -> NativeCallResult {
+        if parameter.length() != 2 {
+            return n_parameter_expected!("derin_eşit".to_string(), 2, parameter.length());
+        }
+
+        let mut iter = parameter.iter();
+        let (left, right) = (iter.next().unwrap().deref(), iter.next().unwrap().deref());
+        Ok(VmObject::from(semantics::deep_equal(&left, &right)))
+    }
+
+    /// -1, 0 or 1, the same order that is used by the sırala function of the lists
+    pub fn compare(parameter: FunctionParameter) -> NativeCallResult {
+        if parameter.length() != 2 {
+            return n_parameter_expected!("karşılaştır".to_string(), 2, parameter.length());
+        }
+
+        let mut iter = parameter.iter();
+        let (left, right) = (iter.next().unwrap().deref(), iter.next().unwrap().deref());
+        Ok(VmObject::from(semantics::compare(&left, &right) as i8 as f64))
+    }
+
+    /// Frozen copy of the lists and dicts, changing the frozen value or the values in it gives an error
+    pub fn freeze(parameter: FunctionParameter) -> NativeCallResult {
+        match parameter.length() {
+            1 => Ok(frozen::freeze(*parameter.iter().next().unwrap())?),
+            _ => n_parameter_expected!("dondurulmuş".to_string(), 1, parameter.length())
+        }
+    }
+
+    pub fn copy(parameter: FunctionParameter) -> NativeCallResult {
+        match parameter.length() {
+            1 => parameter.deep_copy(*parameter.iter().next().unwrap()),
+            _ => n_parameter_expected!("kopyala".to_string(), 1, parameter.length())
+        }
+    }
+
+    /// New function that calls the function with the given arguments first. Binding a bound function adds the arguments after the old ones.
+    pub fn bind(parameter: FunctionParameter) -> NativeCallResult {
+        if parameter.length() == 0 {
+            return n_parameter_expected!("bağla".to_string(), 1, parameter.length());
+        }
+
+        let mut iter = parameter.iter();
+        let (function, base, mut arguments) = match &*iter.next().unwrap().deref() {
+            KaramelPrimative::Function(reference, base) => (reference.clone(), *base, Vec::new()),
+            KaramelPrimative::Bound(bound) => (bound.function.clone(), bound.base, bound.arguments.clone()),
+            _ => return expected_parameter_type!("bağla".to_string(), "Fonksiyon".to_string())
+        };
+
+        arguments.extend(iter.copied());
+        Ok(VmObject::native_convert(KaramelPrimative::Bound(Rc::new(BoundFunction { function, base, arguments }))))
+    }
+
     pub fn type_info(parameter: FunctionParameter) -> NativeCallResult {        
         if parameter.length() > 1 {
             return n_parameter_expected!("tür_bilgisi".to_string(), 1);
@@ -72,3 +435,51 @@ impl BaseFunctionsModule  {
         }
     }
 }
+
+fn function_signature(reference: &FunctionReference) -> String {
+    let arguments = match reference.callback {
+        FunctionType::Native(_) => "...".to_string(),
+        FunctionType::Opcode => {
+            let types = reference.argument_types.borrow();
+            reference.arguments.iter().enumerate().map(|(index, argument)| match types.get(index) {
+                Some(Some(argument_type)) => format!("{}: {}", argument, argument_type),
+                _ => argument.to_string()
+            }).collect::<Vec<_>>().join(", ")
+        }
+    };
+    format!("{}({})", reference.name, arguments)
+}
+
+/// Text that is written by the 'yardım' function. Class is the class of the value, methods and fields are taken from it.
+pub fn help_text(value: &KaramelPrimative, class: Option<Rc<dyn Class>>) -> String {
+    let mut lines = vec![format!("Tür: {}", value.get_type())];
+
+    if let KaramelPrimative::Function(reference, _) = value {
+        lines.push(format!("Fonksiyon: {}", function_signature(reference)));
+        if let Some(documentation) = &*reference.documentation.borrow() {
+            lines.push(documentation.to_string());
+        }
+    }
+
+    if let Some(class) = class {
+        let mut methods = Vec::new();
+        let mut fields = Vec::new();
+        for (name, property) in class.properties() {
+            match property {
+                ClassProperty::Function(_) => methods.push(name.to_string()),
+                ClassProperty::Field(_) => fields.push(name.to_string())
+            }
+        }
+
+        methods.sort();
+        fields.sort();
+        if !methods.is_empty() {
+            lines.push(format!("Fonksiyonlar: {}", methods.join(", ")));
+        }
+        if !fields.is_empty() {
+            lines.push(format!("Özellikler: {}", fields.join(", ")));
+        }
+    }
+
+    lines.join("\r\n")
+}
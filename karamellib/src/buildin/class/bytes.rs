@@ -0,0 +1,193 @@
+use std::rc::Rc;
+
+use crate::{buildin::Class, compiler::function::{FunctionParameter, NativeCallResult}};
+use crate::compiler::value::EMPTY_OBJECT;
+use crate::buildin::class::baseclass::BasicInnerClass;
+use crate::buildin::class::error::ErrorObject;
+use crate::compiler::value::KaramelPrimative;
+use crate::error::KaramelErrorType;
+use crate::types::VmObject;
+use crate::{n_parameter_expected, expected_parameter_type};
+use crate::buildin::class::PRIMATIVE_CLASS_NAMES;
+
+static BASE64_CHARACTERS: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+pub fn get_primative_class() -> Rc<dyn Class> {
+    let mut opcode = BasicInnerClass::default();
+    opcode.set_name("bayt");
+
+    opcode.add_class_method("uzunluk", length);
+    opcode.add_class_method("parçagetir", substring);
+    opcode.add_class_method("parcagetir", substring);
+    opcode.add_class_method("yazı", to_text);
+    opcode.add_class_method("yazi", to_text);
+    opcode.add_class_method("onaltılık", hex);
+    opcode.add_class_method("onaltilik", hex);
+    opcode.add_class_method("base64", base64);
+    opcode.set_getter(getter);
+
+    PRIMATIVE_CLASS_NAMES.lock().unwrap().insert(opcode.get_class_name());
+    Rc::new(opcode)
+}
+
+/// Two lowercase hexadecimal digits for each byte
+pub fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+/// Spaces between the digits are skipped, 'ff 00' and 'FF00' give the same bytes
+pub fn from_hex(text: &str) -> Option<Vec<u8>> {
+    let digits = text.chars().filter(|ch| !ch.is_whitespace()).map(|ch| ch.to_digit(16)).collect::<Option<Vec<u32>>>()?;
+    match digits.len() % 2 {
+        0 => Some(digits.chunks(2).map(|pair| (pair[0] * 16 + pair[1]) as u8).collect()),
+        _ => None
+    }
+}
+
+/// Standard base64 with the '=' padding
+pub fn to_base64(bytes: &[u8]) -> String {
+    let mut text = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let bits = chunk.iter().enumerate().fold(0u32, |bits, (index, byte)| bits | (*byte as u32) << (16 - index * 8));
+        for index in 0..4 {
+            match index <= chunk.len() {
+                true => text.push(BASE64_CHARACTERS[(bits >> (18 - index * 6)) as usize & 63] as char),
+                false => text.push('=')
+            };
+        }
+    }
+    text
+}
+
+/// Padding can be left out, the other characters must be in the base64 alphabet
+pub fn from_base64(text: &str) -> Option<Vec<u8>> {
+    let text = text.trim().trim_end_matches('=');
+    let values = text.bytes().map(|ch| BASE64_CHARACTERS.iter().position(|item| *item == ch).map(|value| value as u32)).collect::<Option<Vec<u32>>>()?;
+    if values.len() % 4 == 1 {
+        return None;
+    }
+
+    let mut bytes = Vec::with_capacity(values.len() * 3 / 4);
+    for chunk in values.chunks(4) {
+        let bits = chunk.iter().enumerate().fold(0u32, |bits, (index, value)| bits | value << (18 - index * 6));
+        bytes.extend((0..chunk.len() - 1).map(|index| (bits >> (16 - index * 8)) as u8));
+    }
+    Some(bytes)
+}
+
+/* Failed conversions give an error value like 'baz::sayıya', they do not stop the program */
+fn conversion_error(value: VmObject, target: &str) -> VmObject {
+    let mut error = ErrorObject::from_error(&KaramelErrorType::ConversionFailed { value: value.to_string(), target: target.to_string() });
+    error.data = value;
+    VmObject::native_convert(KaramelPrimative::Error(error))
+}
+
+fn getter(source: VmObject, index: f64) -> NativeCallResult {
+    if let KaramelPrimative::Bytes(bytes) = &*source.deref() {
+        if index >= 0.0 {
+            return Ok(bytes.get(index as usize).map(|byte| VmObject::from(*byte as f64)).unwrap_or(EMPTY_OBJECT));
+        }
+    }
+    Ok(EMPTY_OBJECT)
+}
+
+fn length(parameter: FunctionParameter) -> NativeCallResult {
+    if let KaramelPrimative::Bytes(bytes) = &*parameter.source().unwrap().deref() {
+        return Ok(VmObject::from(bytes.len() as f64));
+    }
+    Ok(EMPTY_OBJECT)
+}
+
+/* Bytes from 'başlangıç' till 'bitiş', 'bitiş' is not included. Positions outside of the bytes are moved to the ends. */
+fn substring(parameter: FunctionParameter) -> NativeCallResult {
+    if let KaramelPrimative::Bytes(bytes) = &*parameter.source().unwrap().deref() {
+        return match parameter.length() {
+            2 => {
+                let mut iter = parameter.iter();
+                match (iter.next().unwrap().as_number(), iter.next().unwrap().as_number()) {
+                    (Some(start), Some(end)) => {
+                        let end = end.clamp(0.0, bytes.len() as f64) as usize;
+                        let start = start.clamp(0.0, end as f64) as usize;
+                        Ok(VmObject::native_convert(KaramelPrimative::Bytes(Rc::new(bytes[start..end].to_vec()))))
+                    },
+                    _ => expected_parameter_type!("parçagetir".to_string(), "Sayı".to_string())
+                }
+            },
+            _ => n_parameter_expected!("parçagetir".to_string(), 2, parameter.length())
+        };
+    }
+    Ok(EMPTY_OBJECT)
+}
+
+fn to_text(parameter: FunctionParameter) -> NativeCallResult {
+    if let KaramelPrimative::Bytes(bytes) = &*parameter.source().unwrap().deref() {
+        return Ok(match std::str::from_utf8(bytes) {
+            Ok(text) => VmObject::from(text.to_string()),
+            Err(_) => conversion_error(parameter.source().unwrap(), "yazı")
+        });
+    }
+    Ok(EMPTY_OBJECT)
+}
+
+fn hex(parameter: FunctionParameter) -> NativeCallResult {
+    if let KaramelPrimative::Bytes(bytes) = &*parameter.source().unwrap().deref() {
+        return Ok(VmObject::from(to_hex(bytes)));
+    }
+    Ok(EMPTY_OBJECT)
+}
+
+fn base64(parameter: FunctionParameter) -> NativeCallResult {
+    if let KaramelPrimative::Bytes(bytes) = &*parameter.source().unwrap().deref() {
+        return Ok(VmObject::from(to_base64(bytes)));
+    }
+    Ok(EMPTY_OBJECT)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::rc::Rc;
+    use std::cell::RefCell;
+    use crate::compiler::value::KaramelPrimative;
+    use super::*;
+
+    use crate::nativecall_test_with_params;
+    use crate::nativecall_test;
+    use crate::arc_number;
+    use crate::primative_text;
+    use crate::primative_number;
+
+    nativecall_test!{test_length_1, length, KaramelPrimative::Bytes(Rc::new(vec![1, 2, 3])), primative_number!(3)}
+    nativecall_test!{test_to_text_1, to_text, KaramelPrimative::Bytes(Rc::new("ığdır".as_bytes().to_vec())), primative_text!("ığdır")}
+    nativecall_test!{test_hex_1, hex, KaramelPrimative::Bytes(Rc::new(vec![0, 15, 255])), primative_text!("000fff")}
+    nativecall_test!{test_base64_1, base64, KaramelPrimative::Bytes(Rc::new(b"karamel".to_vec())), primative_text!("a2FyYW1lbA==")}
+    nativecall_test_with_params!{test_substring_1, substring, KaramelPrimative::Bytes(Rc::new(vec![1, 2, 3, 4])), [arc_number!(1), arc_number!(3)], KaramelPrimative::Bytes(Rc::new(vec![2, 3]))}
+    nativecall_test_with_params!{test_substring_2, substring, KaramelPrimative::Bytes(Rc::new(vec![1, 2, 3, 4])), [arc_number!(-2), arc_number!(10)], KaramelPrimative::Bytes(Rc::new(vec![1, 2, 3, 4]))}
+
+    #[test]
+    fn test_encodings() {
+        for source in ["", "a", "ab", "abc", "çağrı"].iter() {
+            assert_eq!(from_base64(&to_base64(source.as_bytes())).unwrap(), source.as_bytes());
+            assert_eq!(from_hex(&to_hex(source.as_bytes())).unwrap(), source.as_bytes());
+        }
+
+        assert_eq!(from_base64("YQ"), Some(b"a".to_vec()));
+        assert_eq!(from_base64("Y"), None);
+        assert_eq!(from_base64("Y*=="), None);
+        assert_eq!(from_hex("FF 0a"), Some(vec![255, 10]));
+        assert_eq!(from_hex("f"), None);
+        assert_eq!(from_hex("gg"), None);
+    }
+
+    #[test]
+    fn test_invalid_utf8() {
+        let stdout = Some(RefCell::new(String::new()));
+        let stderr = Some(RefCell::new(String::new()));
+        let bytes = VmObject::native_convert(KaramelPrimative::Bytes(Rc::new(vec![0xff, 0xfe])));
+
+        let result = to_text(FunctionParameter::new(&Vec::new(), Some(bytes), 0, 0, &stdout, &stderr)).unwrap();
+        assert!(matches!(&*result.deref(), KaramelPrimative::Error(_)));
+        assert_eq!(getter(bytes, 1.0).unwrap().as_number(), Some(254.0));
+        assert_eq!(getter(bytes, 2.0).unwrap(), EMPTY_OBJECT);
+    }
+}
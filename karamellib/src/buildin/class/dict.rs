@@ -5,9 +5,10 @@ use crate::{buildin::{Class, ClassConfig, ClassProperty}, compiler::{GetType, fu
 use crate::compiler::value::EMPTY_OBJECT;
 use crate::buildin::class::baseclass::BasicInnerClass;
 use crate::compiler::value::KaramelPrimative;
+use crate::compiler::DictKey;
 use crate::error::KaramelErrorType;
 use crate::types::VmObject;
-use crate::{n_parameter_expected, expected_parameter_type, arc_bool, primative_list};
+use crate::{n_parameter_expected, arc_bool, primative_list};
 
 use crate::buildin::class::PRIMATIVE_CLASS_NAMES;
 
@@ -35,6 +36,8 @@ impl DictClass {
         dict.add_class_method("temizle", clear);
         dict.add_class_method("sil", remove);
         dict.add_class_method("anahtarlar", keys);
+        dict.add_class_method("kopyala", copy);
+        dict.set_getter(getter);
 
         PRIMATIVE_CLASS_NAMES.lock().unwrap().insert(dict.get_type());
 
@@ -65,7 +68,7 @@ impl DictClass {
             None => match source {
                 Some(object) => {
                     match &*object.deref() {
-                        KaramelPrimative::Dict(dict) => match dict.borrow().get(&*field.clone()) {
+                        KaramelPrimative::Dict(dict) => match dict.borrow().get(&DictKey::from(field)) {
                             Some(data) => Some(ClassProperty::Field(data.deref())),
                             None => None
                         },
@@ -116,12 +119,8 @@ fn get(parameter: FunctionParameter) -> NativeCallResult {
         return match parameter.length() {
             0 =>  n_parameter_expected!("getir".to_string(), 1),
             1 => {
-                let key = match &*parameter.iter().next().unwrap().deref() {
-                    KaramelPrimative::Text(yazi) => yazi.clone(),
-                    _ => return expected_parameter_type!("anahtar".to_string(), "Yazı".to_string())
-                };
-                
-                return match dict.borrow().get(&*key) {
+                let key = DictKey::try_from_value(&parameter.iter().next().unwrap().deref())?;
+                return match dict.borrow().get(&key) {
                     Some(item) => Ok(*item),
                     _ => Ok(EMPTY_OBJECT)
                 };
@@ -132,6 +131,15 @@ fn get(parameter: FunctionParameter) -> NativeCallResult {
     Ok(EMPTY_OBJECT)
 }
 
+fn getter(source: VmObject, index: f64) -> NativeCallResult {
+    if let KaramelPrimative::Dict(dict) = &*source.deref() {
+        if let Some(key) = DictKey::from_value(&KaramelPrimative::Number(index)) {
+            return Ok(dict.borrow().get(&key).copied().unwrap_or(EMPTY_OBJECT));
+        }
+    }
+    Ok(EMPTY_OBJECT)
+}
+
 fn set(parameter: FunctionParameter) -> NativeCallResult {
     insert_or_update(parameter, "güncelle")
 }
@@ -146,13 +154,8 @@ fn insert_or_update(parameter: FunctionParameter, function_name: &str) -> Native
             0 =>  n_parameter_expected!(function_name.to_string(), 2),
             2 => {
                 let mut iter = parameter.iter();
-                let (position_object, item) = (&*iter.next().unwrap().deref(), &*iter.next().unwrap());
-
-                let position = match position_object {
-                    KaramelPrimative::Text(text) => text.clone(),
-                    _ => return expected_parameter_type!("anahtar".to_string(), "Yazı".to_string())
-                };
-                *dict.borrow_mut().entry((&position).to_string()).or_insert(*item) = *item;
+                let (key, item) = (DictKey::try_from_value(&iter.next().unwrap().deref())?, *iter.next().unwrap());
+                dict.borrow_mut().insert(key, item);
                 Ok(EMPTY_OBJECT)
             },
             _ => n_parameter_expected!(function_name.to_string(), 2, parameter.length())
@@ -169,6 +172,13 @@ fn length(parameter: FunctionParameter) -> NativeCallResult {
     Ok(EMPTY_OBJECT)
 }
 
+fn copy(parameter: FunctionParameter) -> NativeCallResult {
+    match parameter.length() {
+        0 => parameter.deep_copy(parameter.source().unwrap()),
+        _ => n_parameter_expected!("kopyala".to_string(), 0, parameter.length())
+    }
+}
+
 fn clear(parameter: FunctionParameter) -> NativeCallResult {
     if let KaramelPrimative::Dict(dict) = &*parameter.source().unwrap().deref() {
         dict.borrow_mut().clear();
@@ -181,12 +191,8 @@ fn remove(parameter: FunctionParameter) -> NativeCallResult {
         return match parameter.length() {
             0 => n_parameter_expected!("sil".to_string(), 1),
             1 => {
-                let key = match &*parameter.iter().next().unwrap().deref() {
-                    KaramelPrimative::Text(text) => text.clone(),
-                    _ => return expected_parameter_type!("anahtar".to_string(), "Yazı".to_string())
-                };
-                
-                Ok(match dict.borrow_mut().remove(&key.to_string()) {
+                let key = DictKey::try_from_value(&parameter.iter().next().unwrap().deref())?;
+                Ok(match dict.borrow_mut().remove(&key) {
                     Some(_) => arc_bool!(true),
                     None => arc_bool!(false)
                 })
@@ -199,11 +205,7 @@ fn remove(parameter: FunctionParameter) -> NativeCallResult {
 
 fn keys(parameter: FunctionParameter) -> NativeCallResult {
     if let KaramelPrimative::Dict(dict) = &*parameter.source().unwrap().deref() {
-        let mut keys = Vec::new();
-        for key in dict.borrow().keys() {
-            keys.push(VmObject::native_convert(KaramelPrimative::Text(Rc::new(key.to_string()))));
-        }
-
+        let keys = dict.borrow().keys().map(DictKey::to_value).collect::<Vec<_>>();
         return Ok(VmObject::native_convert(primative_list!(keys)));
     }
 
@@ -215,10 +217,8 @@ fn contains(parameter: FunctionParameter) -> NativeCallResult {
         return match parameter.length() {
             0 =>  n_parameter_expected!("içeriyormu".to_string(), 1),
             1 => {
-                match &*parameter.iter().next().unwrap().deref() {
-                    KaramelPrimative::Text(search) =>  Ok(VmObject::from(dict.borrow().contains_key(&**search))),
-                    _ => expected_parameter_type!("içeriyormu".to_string(), "Yazı".to_string())
-                }
+                let key = DictKey::try_from_value(&parameter.iter().next().unwrap().deref())?;
+                Ok(VmObject::from(dict.borrow().contains_key(&key)))
             },
             _ => n_parameter_expected!("içeriyormu".to_string(), 1, parameter.length())
         };
@@ -0,0 +1,130 @@
+use std::fmt;
+use std::rc::Rc;
+
+use strum::EnumMessage;
+
+use crate::{buildin::Class, compiler::function::{FunctionParameter, NativeCallResult}};
+use crate::compiler::value::EMPTY_OBJECT;
+use crate::buildin::class::baseclass::BasicInnerClass;
+use crate::compiler::value::KaramelPrimative;
+use crate::error::KaramelErrorType;
+use crate::types::VmObject;
+use crate::buildin::class::PRIMATIVE_CLASS_NAMES;
+
+/// Error value of the 'fırlat' and 'yakala' blocks. Runtime errors are converted to this value when they are caught.
+#[derive(Clone)]
+pub struct ErrorObject {
+    pub code: VmObject,
+    pub message: Rc<String>,
+    pub data: VmObject
+}
+
+impl ErrorObject {
+    pub fn new(code: VmObject, message: Rc<String>, data: VmObject) -> Self {
+        ErrorObject { code, message, data }
+    }
+
+    /// Runtime errors use their error number as code, so they can be compared with the documentation.
+    pub fn from_error(error: &KaramelErrorType) -> Self {
+        let code = match error.get_message().and_then(|code| code.parse::<f64>().ok()) {
+            Some(code) => VmObject::from(code),
+            None => EMPTY_OBJECT
+        };
+        ErrorObject::new(code, Rc::new(error.to_string()), EMPTY_OBJECT)
+    }
+
+    /// Thrown values are always errors. Text becomes the message, other values are kept as data.
+    pub fn from_value(value: VmObject) -> VmObject {
+        let error = match &*value.deref() {
+            KaramelPrimative::Error(_) => return value,
+            KaramelPrimative::Text(text) => ErrorObject::new(EMPTY_OBJECT, text.clone(), EMPTY_OBJECT),
+            primative => ErrorObject::new(EMPTY_OBJECT, Rc::new(primative.to_string()), value)
+        };
+        VmObject::native_convert(KaramelPrimative::Error(error))
+    }
+}
+
+impl fmt::Debug for ErrorObject {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "<Hata kod={} mesaj='{}'>", self.code.deref(), self.message)
+    }
+}
+
+impl PartialEq for ErrorObject {
+    fn eq(&self, other: &Self) -> bool {
+        self.code.deref() == other.code.deref() && self.message == other.message && self.data.deref() == other.data.deref()
+    }
+}
+
+pub fn get_primative_class() -> Rc<dyn Class> {
+    let mut opcode = BasicInnerClass::default();
+    opcode.set_name("Hata");
+
+    opcode.add_class_method("kod", code);
+    opcode.add_class_method("mesaj", message);
+    opcode.add_class_method("veri", data);
+
+    PRIMATIVE_CLASS_NAMES.lock().unwrap().insert(opcode.get_class_name());
+    Rc::new(opcode)
+}
+
+fn code(parameter: FunctionParameter) -> NativeCallResult {
+    if let KaramelPrimative::Error(error) = &*parameter.source().unwrap().deref() {
+        return Ok(error.code);
+    }
+    Ok(EMPTY_OBJECT)
+}
+
+fn message(parameter: FunctionParameter) -> NativeCallResult {
+    if let KaramelPrimative::Error(error) = &*parameter.source().unwrap().deref() {
+        return Ok(VmObject::from(error.message.clone()));
+    }
+    Ok(EMPTY_OBJECT)
+}
+
+fn data(parameter: FunctionParameter) -> NativeCallResult {
+    if let KaramelPrimative::Error(error) = &*parameter.source().unwrap().deref() {
+        return Ok(error.data);
+    }
+    Ok(EMPTY_OBJECT)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::rc::Rc;
+    use crate::compiler::value::KaramelPrimative;
+    use crate::error::KaramelErrorType;
+    use super::*;
+
+    use crate::nativecall_test;
+    use crate::arc_text;
+    use crate::primative_text;
+    use crate::arc_number;
+    use crate::primative_number;
+
+    nativecall_test!{test_code_1, code, KaramelPrimative::Error(ErrorObject::new(arc_number!(404), Rc::new("bulunamadı".to_string()), EMPTY_OBJECT)), primative_number!(404)}
+    nativecall_test!{test_message_1, message, KaramelPrimative::Error(ErrorObject::new(arc_number!(404), Rc::new("bulunamadı".to_string()), EMPTY_OBJECT)), primative_text!("bulunamadı")}
+    nativecall_test!{test_data_1, data, KaramelPrimative::Error(ErrorObject::new(EMPTY_OBJECT, Rc::new("".to_string()), arc_text!("veri"))), primative_text!("veri")}
+    nativecall_test!{test_data_2, data, KaramelPrimative::Error(ErrorObject::new(EMPTY_OBJECT, Rc::new("".to_string()), EMPTY_OBJECT)), KaramelPrimative::Empty}
+
+    #[test]
+    fn test_from_error() {
+        let error = ErrorObject::from_error(&KaramelErrorType::InvalidEscape("\\x".to_string()));
+        assert_eq!(*error.code.deref(), primative_number!(161));
+        assert_eq!(&*error.message, "'\\x' geçerli bir kaçış dizisi değil");
+    }
+
+    #[test]
+    fn test_from_value() {
+        match &*ErrorObject::from_value(arc_text!("olmadı")).deref() {
+            KaramelPrimative::Error(error) => assert_eq!(&*error.message, "olmadı"),
+            _ => assert!(false, "Yazı hataya dönüştürülmedi")
+        };
+
+        match &*ErrorObject::from_value(arc_number!(5)).deref() {
+            KaramelPrimative::Error(error) => assert_eq!(*error.data.deref(), primative_number!(5)),
+            _ => assert!(false, "Sayı hataya dönüştürülmedi")
+        };
+    }
+}
@@ -0,0 +1,285 @@
+use std::rc::Rc;
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+use crate::{buildin::{Class, ClassConfig, ClassProperty}, compiler::{GetType, function::{FunctionParameter, IndexerGetCall, IndexerSetCall, NativeCall, NativeCallResult, FunctionFlag}}};
+use crate::compiler::value::EMPTY_OBJECT;
+use crate::buildin::class::baseclass::BasicInnerClass;
+use crate::compiler::value::KaramelPrimative;
+use crate::compiler::DictKey;
+use crate::error::KaramelErrorType;
+use crate::types::VmObject;
+use crate::{n_parameter_expected, expected_parameter_type, arc_bool};
+
+use crate::buildin::class::PRIMATIVE_CLASS_NAMES;
+
+/// Frozen copy of the lists and dicts in the value. Other values can not contain themselves or are not changed by
+/// the frozen value's functions, they are kept as they are.
+pub fn freeze(value: VmObject) -> Result<VmObject, KaramelErrorType> {
+    freeze_items(value, &mut Vec::new())
+}
+
+fn freeze_items(value: VmObject, path: &mut Vec<*const KaramelPrimative>) -> Result<VmObject, KaramelErrorType> {
+    let source = value.deref();
+    if path.contains(&Rc::as_ptr(&source)) {
+        return Err(KaramelErrorType::GeneralError("Kendisini içeren değerler dondurulamaz".to_string()));
+    }
+
+    path.push(Rc::as_ptr(&source));
+    let inner = match &*source {
+        KaramelPrimative::List(items) => {
+            let items = items.borrow().clone();
+            KaramelPrimative::List(RefCell::new(items.into_iter().map(|item| freeze_items(item, path)).collect::<Result<_, _>>()?))
+        },
+        KaramelPrimative::Dict(items) => {
+            let items = items.borrow().clone();
+            KaramelPrimative::Dict(RefCell::new(items.into_iter().map(|(key, item)| Ok((key, freeze_items(item, path)?))).collect::<Result<_, KaramelErrorType>>()?))
+        },
+        _ => {
+            path.pop();
+            return Ok(value);
+        }
+    };
+    path.pop();
+    Ok(VmObject::native_convert(KaramelPrimative::Frozen(VmObject::native_convert(inner))))
+}
+
+/* Lists and dicts that can be changed again, frozen values can not contain themselves */
+fn thaw(value: VmObject) -> VmObject {
+    match &*value.deref() {
+        KaramelPrimative::Frozen(inner) => match &*inner.deref() {
+            KaramelPrimative::List(items) => VmObject::native_convert(KaramelPrimative::List(RefCell::new(items.borrow().iter().map(|item| thaw(*item)).collect()))),
+            KaramelPrimative::Dict(items) => VmObject::native_convert(KaramelPrimative::Dict(RefCell::new(items.borrow().iter().map(|(key, item)| (key.clone(), thaw(*item))).collect::<HashMap<_, _>>()))),
+            _ => *inner
+        },
+        _ => value
+    }
+}
+
+#[derive(Default)]
+pub struct FrozenClass {
+    base: BasicInnerClass
+}
+
+impl GetType for FrozenClass {
+    fn get_type(&self) -> String {
+        "dondurulmuş".to_string()
+    }
+}
+
+impl FrozenClass {
+    pub fn new() -> Self {
+        let mut frozen = FrozenClass::default();
+        frozen.add_class_method("getir", get);
+        frozen.add_class_method("uzunluk", length);
+        frozen.add_class_method("içeriyormu", contains);
+        frozen.add_class_method("iceriyormu", contains);
+        frozen.add_class_method("anahtarlar", keys);
+        frozen.add_class_method("kopyala", copy);
+
+        /* Functions of the lists and dicts that change them */
+        for name in ["ekle", "güncelle", "guncelle", "temizle", "arayaekle", "pop", "sil", "sırala", "sirala"].iter() {
+            frozen.add_class_method(name, change);
+        }
+        frozen.set_getter(getter);
+
+        PRIMATIVE_CLASS_NAMES.lock().unwrap().insert(frozen.get_type());
+        frozen
+    }
+
+    pub fn add_class_method(&mut self, name: &str, function: NativeCall) {
+        self.base.add_method(name, function, FunctionFlag::IN_CLASS);
+    }
+}
+
+impl Class for FrozenClass {
+    fn set_class_config(&mut self, config: ClassConfig) {
+        self.base.set_class_config(config);
+    }
+
+    fn get_class_name(&self) -> String {
+        self.get_type()
+    }
+
+    fn has_element(&self, source: Option<VmObject>, field: Rc<String>) -> bool {
+        self.base.has_element(source, field)
+    }
+
+    fn properties(&self) -> std::collections::hash_map::Iter<'_, String, ClassProperty> {
+        self.base.properties()
+    }
+
+    fn get_element(&self, source: Option<VmObject>, field: Rc<String>) -> Option<ClassProperty> {
+        match self.base.get_element(source, field.clone()) {
+            Some(property) => Some(property),
+            None => match source.map(|object| object.deref()).as_deref() {
+                Some(KaramelPrimative::Frozen(inner)) => match &*inner.deref() {
+                    KaramelPrimative::Dict(dict) => dict.borrow().get(&DictKey::from(field)).map(|data| ClassProperty::Field(data.deref())),
+                    _ => None
+                },
+                _ => None
+            }
+        }
+    }
+
+    fn property_count(&self) -> usize {
+        self.base.property_count()
+    }
+
+    fn add_method(&mut self, name: &str, function: NativeCall, flags: FunctionFlag) {
+        self.base.add_method(name, function, flags);
+    }
+
+    fn add_property(&mut self, name: &str, property: Rc<KaramelPrimative>) {
+        self.base.add_property(name, property);
+    }
+
+    fn set_getter(&mut self, indexer: IndexerGetCall) {
+        self.base.set_getter(indexer);
+    }
+
+    fn get_getter(&self) -> Option<IndexerGetCall> {
+        self.base.get_getter()
+    }
+
+    fn set_setter(&mut self, indexer: IndexerSetCall) {
+        self.base.set_setter(indexer);
+    }
+
+    fn get_setter(&self) -> Option<IndexerSetCall> {
+        self.base.get_setter()
+    }
+}
+
+pub fn get_primative_class() -> Rc<dyn Class> {
+    Rc::new(FrozenClass::new())
+}
+
+/* Inner list or dict of the frozen value */
+fn inner(source: VmObject) -> Rc<KaramelPrimative> {
+    match &*source.deref() {
+        KaramelPrimative::Frozen(inner) => inner.deref(),
+        _ => Rc::new(KaramelPrimative::Empty)
+    }
+}
+
+fn getter(source: VmObject, index: f64) -> NativeCallResult {
+    match &*inner(source) {
+        KaramelPrimative::List(list) if index >= 0.0 => Ok(list.borrow().get(index as usize).copied().unwrap_or(EMPTY_OBJECT)),
+        KaramelPrimative::Dict(dict) => Ok(DictKey::from_value(&KaramelPrimative::Number(index)).and_then(|key| dict.borrow().get(&key).copied()).unwrap_or(EMPTY_OBJECT)),
+        _ => Ok(EMPTY_OBJECT)
+    }
+}
+
+fn get(parameter: FunctionParameter) -> NativeCallResult {
+    if parameter.length() != 1 {
+        return n_parameter_expected!("getir".to_string(), 1, parameter.length());
+    }
+
+    let key = parameter.iter().next().unwrap().deref();
+    match (&*inner(parameter.source().unwrap()), &*key) {
+        (KaramelPrimative::List(_), KaramelPrimative::Number(index)) => getter(parameter.source().unwrap(), *index),
+        (KaramelPrimative::List(_), _) => expected_parameter_type!("sıra".to_string(), "Sayı".to_string()),
+        (KaramelPrimative::Dict(dict), _) => Ok(dict.borrow().get(&DictKey::try_from_value(&key)?).copied().unwrap_or(EMPTY_OBJECT)),
+        _ => Ok(EMPTY_OBJECT)
+    }
+}
+
+fn length(parameter: FunctionParameter) -> NativeCallResult {
+    match &*inner(parameter.source().unwrap()) {
+        KaramelPrimative::List(list) => Ok(VmObject::from(list.borrow().len() as f64)),
+        KaramelPrimative::Dict(dict) => Ok(VmObject::from(dict.borrow().len() as f64)),
+        _ => Ok(EMPTY_OBJECT)
+    }
+}
+
+fn contains(parameter: FunctionParameter) -> NativeCallResult {
+    if let KaramelPrimative::Dict(dict) = &*inner(parameter.source().unwrap()) {
+        return match parameter.length() {
+            1 => Ok(arc_bool!(dict.borrow().contains_key(&DictKey::try_from_value(&parameter.iter().next().unwrap().deref())?))),
+            _ => n_parameter_expected!("içeriyormu".to_string(), 1, parameter.length())
+        };
+    }
+    Ok(EMPTY_OBJECT)
+}
+
+fn keys(parameter: FunctionParameter) -> NativeCallResult {
+    if let KaramelPrimative::Dict(dict) = &*inner(parameter.source().unwrap()) {
+        let keys = dict.borrow().keys().map(DictKey::to_value).collect::<Vec<_>>();
+        return Ok(VmObject::from(keys));
+    }
+    Ok(EMPTY_OBJECT)
+}
+
+fn copy(parameter: FunctionParameter) -> NativeCallResult {
+    match parameter.length() {
+        0 => Ok(thaw(parameter.source().unwrap())),
+        _ => n_parameter_expected!("kopyala".to_string(), 0, parameter.length())
+    }
+}
+
+fn change(_: FunctionParameter) -> NativeCallResult {
+    Err(KaramelErrorType::FrozenValueCannotBeChanged)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::rc::Rc;
+    use std::cell::RefCell;
+    use crate::compiler::value::KaramelPrimative;
+    use crate::compiler::semantics::deep_equal;
+    use super::*;
+
+    use crate::arc_text;
+    use crate::primative_text;
+    use crate::arc_number;
+
+    fn call(function: NativeCall, source: VmObject, arguments: Vec<VmObject>) -> NativeCallResult {
+        let stdout = Some(RefCell::new(String::new()));
+        let stderr = Some(RefCell::new(String::new()));
+        function(FunctionParameter::new(&arguments, Some(source), arguments.len(), arguments.len() as u8, &stdout, &stderr))
+    }
+
+    #[test]
+    fn test_freeze_list() {
+        let list = VmObject::from(vec![arc_number!(1), VmObject::from(vec![arc_text!("iki")])]);
+        let frozen = freeze(list).unwrap();
+        assert!(deep_equal(&frozen.deref(), &list.deref()));
+        assert_eq!(frozen.deref().get_type(), "dondurulmuş_liste");
+
+        /* Inner list is frozen too */
+        let inner = getter(frozen, 1.0).unwrap();
+        assert!(matches!(&*inner.deref(), KaramelPrimative::Frozen(_)));
+        assert_eq!(call(get, inner, vec![arc_number!(0)]).unwrap().deref().get_text(), "iki");
+        assert_eq!(call(length, frozen, Vec::new()).unwrap().as_number(), Some(2.0));
+        assert_eq!(call(change, frozen, vec![arc_number!(3)]), Err(KaramelErrorType::FrozenValueCannotBeChanged));
+    }
+
+    #[test]
+    fn test_thaw() {
+        let mut items = HashMap::new();
+        items.insert(DictKey::from("a"), VmObject::from(vec![arc_number!(1)]));
+        let dict = VmObject::native_convert(KaramelPrimative::Dict(RefCell::new(items)));
+
+        let frozen = freeze(dict).unwrap();
+        assert_eq!(call(contains, frozen, vec![arc_text!("a")]).unwrap(), arc_bool!(true));
+
+        let copy = call(copy, frozen, Vec::new()).unwrap();
+        match &*copy.deref() {
+            KaramelPrimative::Dict(items) => assert!(matches!(&*items.borrow()[&DictKey::from("a")].deref(), KaramelPrimative::List(_))),
+            value => panic!("{:?}", value)
+        };
+        assert!(deep_equal(&copy.deref(), &dict.deref()));
+    }
+
+    #[test]
+    fn test_self_containing_list() {
+        let list = VmObject::from(Vec::new());
+        if let KaramelPrimative::List(items) = &*list.deref() {
+            items.borrow_mut().push(list);
+        }
+        assert!(freeze(list).is_err());
+        assert_eq!(*freeze(arc_text!("yazı")).unwrap().deref(), primative_text!("yazı"));
+    }
+}
@@ -1,9 +1,10 @@
 use std::rc::Rc;
 
 use crate::{buildin::Class, compiler::function::{FunctionParameter, NativeCallResult}};
-use crate::compiler::value::EMPTY_OBJECT;
+use crate::compiler::value::{EMPTY_OBJECT, number_to_text};
 use crate::buildin::class::baseclass::BasicInnerClass;
 use crate::compiler::value::KaramelPrimative;
+use crate::compiler::semantics::compare;
 use crate::error::KaramelErrorType;
 use crate::types::VmObject;
 use crate::{n_parameter_expected, expected_parameter_type, arc_bool, arc_empty};
@@ -22,6 +23,9 @@ pub fn get_primative_class() -> Rc<dyn Class> {
     opcode.add_class_method("arayaekle", insert);
     opcode.add_class_method("pop", pop);
     opcode.add_class_method("sil", remove);
+    opcode.add_class_method("sırala", sort);
+    opcode.add_class_method("sirala", sort);
+    opcode.add_class_method("kopyala", copy);
     opcode.set_getter(getter);
     opcode.set_setter(setter);
 
@@ -99,20 +103,17 @@ fn getter(source: VmObject, index: f64) -> NativeCallResult {
 }
 
 fn setter(source: VmObject, index: f64, item: VmObject) -> NativeCallResult {
-    let index = match index >= 0.0 {
-        true => index as usize,
-        false =>  return Ok(EMPTY_OBJECT)
-    };
-
     if let KaramelPrimative::List(list) = &*source.deref() {
-
-        let is_in_size = index <= list.borrow().len();
-        return match is_in_size {
+        let length = list.borrow().len();
+        return match index >= 0.0 && index.fract() == 0.0 && (index as usize) < length {
             true => {
-                list.borrow_mut()[index] = item; 
+                list.borrow_mut()[index as usize] = item;
                 Ok(arc_bool!(true))
             },
-            false => Ok(arc_bool!(false))
+            false => Err(KaramelErrorType::IndexOutOfRange {
+                index: number_to_text(index),
+                length
+            })
         };
     }
     Ok(EMPTY_OBJECT)
@@ -126,6 +127,13 @@ fn length(parameter: FunctionParameter) -> NativeCallResult {
     Ok(EMPTY_OBJECT)
 }
 
+fn copy(parameter: FunctionParameter) -> NativeCallResult {
+    match parameter.length() {
+        0 => parameter.deep_copy(parameter.source().unwrap()),
+        _ => n_parameter_expected!("kopyala".to_string(), 0, parameter.length())
+    }
+}
+
 fn clear(parameter: FunctionParameter) -> NativeCallResult {
     if let KaramelPrimative::List(list) = &*parameter.source().unwrap().deref() {
         list.borrow_mut().clear();
@@ -133,6 +141,16 @@ fn clear(parameter: FunctionParameter) -> NativeCallResult {
     Ok(EMPTY_OBJECT)
 }
 
+/* Items are sorted out of the list, because the list can contain itself */
+fn sort(parameter: FunctionParameter) -> NativeCallResult {
+    if let KaramelPrimative::List(list) = &*parameter.source().unwrap().deref() {
+        let mut items = list.borrow().clone();
+        items.sort_by(|left, right| compare(&left.deref(), &right.deref()));
+        *list.borrow_mut() = items;
+    }
+    Ok(EMPTY_OBJECT)
+}
+
 pub fn add(parameter: FunctionParameter) -> NativeCallResult {
     if let KaramelPrimative::List(list) = &*parameter.source().unwrap().deref() {
         return match parameter.length() {
@@ -4,6 +4,14 @@ pub mod list;
 pub mod dict;
 pub mod baseclass;
 pub mod proxy;
+pub mod stack;
+pub mod queue;
+pub mod priority_queue;
+pub mod error;
+pub mod text_builder;
+pub mod frozen;
+pub mod sorted_dict;
+pub mod bytes;
 
 use crate::buildin::class::baseclass::BasicInnerClass;
 use std::{collections::HashSet, rc::Rc};
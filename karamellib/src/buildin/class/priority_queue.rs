@@ -0,0 +1,239 @@
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+use std::rc::Rc;
+
+use crate::{buildin::Class, compiler::function::{FunctionParameter, NativeCallResult}};
+use crate::compiler::value::EMPTY_OBJECT;
+use crate::buildin::class::baseclass::BasicInnerClass;
+use crate::compiler::value::KaramelPrimative;
+use crate::error::KaramelErrorType;
+use crate::types::VmObject;
+use crate::{n_parameter_expected, expected_parameter_type, arc_bool};
+use crate::buildin::class::PRIMATIVE_CLASS_NAMES;
+
+#[derive(Clone)]
+pub struct PriorityItem {
+    pub priority: f64,
+    pub order: usize,
+    pub value: VmObject
+}
+
+/* Lowest priority comes first, items with same priority keep insertion order */
+impl Ord for PriorityItem {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match other.priority.partial_cmp(&self.priority) {
+            Some(Ordering::Equal) | None => other.order.cmp(&self.order),
+            Some(ordering) => ordering
+        }
+    }
+}
+
+impl PartialOrd for PriorityItem {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for PriorityItem {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for PriorityItem {}
+
+#[derive(Clone, Default)]
+pub struct PriorityQueue {
+    items: BinaryHeap<PriorityItem>,
+    counter: usize
+}
+
+impl PriorityQueue {
+    pub fn new() -> Self {
+        PriorityQueue::default()
+    }
+
+    pub fn push(&mut self, value: VmObject, priority: f64) {
+        self.items.push(PriorityItem { priority, order: self.counter, value });
+        self.counter += 1;
+    }
+
+    pub fn pop(&mut self) -> Option<VmObject> {
+        self.items.pop().map(|item| item.value)
+    }
+
+    pub fn peek(&self) -> Option<VmObject> {
+        self.items.peek().map(|item| item.value)
+    }
+
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.items.clear();
+    }
+
+    /// Items in no particular order
+    pub fn values(&self) -> impl Iterator<Item = VmObject> + '_ {
+        self.items.iter().map(|item| item.value)
+    }
+
+    /// Same queue with the values changed by the function, priorities and the insertion order are kept
+    pub fn map_values<F: FnMut(VmObject) -> VmObject>(mut self, mut function: F) -> Self {
+        self.items = self.items.into_iter().map(|mut item| {
+            item.value = function(item.value);
+            item
+        }).collect();
+        self
+    }
+
+    /// Items in the order they will be taken out from the queue
+    pub fn to_vec(&self) -> Vec<VmObject> {
+        self.items.clone().into_sorted_vec().iter().rev().map(|item| item.value).collect()
+    }
+}
+
+pub fn get_primative_class() -> Rc<dyn Class> {
+    let mut opcode = BasicInnerClass::default();
+    opcode.set_name("öncelik_kuyruğu");
+    
+    opcode.add_class_method("ekle", push);
+    opcode.add_class_method("çıkar", pop);
+    opcode.add_class_method("cikar", pop);
+    opcode.add_class_method("üst", peek);
+    opcode.add_class_method("ust", peek);
+    opcode.add_class_method("uzunluk", length);
+    opcode.add_class_method("boşmu", is_empty);
+    opcode.add_class_method("bosmu", is_empty);
+    opcode.add_class_method("temizle", clear);
+
+    PRIMATIVE_CLASS_NAMES.lock().unwrap().insert(opcode.get_class_name());
+    Rc::new(opcode)
+}
+
+fn push(parameter: FunctionParameter) -> NativeCallResult {
+    if let KaramelPrimative::PriorityQueue(queue) = &*parameter.source().unwrap().deref() {
+        let mut iter = parameter.iter();
+        let (value, priority) = match parameter.length() {
+            0 =>  return n_parameter_expected!("ekle".to_string(), 1),
+
+            /* Value is also the priority */
+            1 => {
+                let value = *iter.next().unwrap();
+                (value, value.as_number())
+            },
+            2 => (*iter.next().unwrap(), iter.next().unwrap().as_number()),
+            _ => return n_parameter_expected!("ekle".to_string(), 2, parameter.length())
+        };
+
+        return match priority {
+            Some(priority) => {
+                queue.borrow_mut().push(value, priority);
+                Ok(VmObject::from(queue.borrow().len() as f64))
+            },
+            None => expected_parameter_type!("öncelik".to_string(), "Sayı".to_string())
+        };
+    }
+    Ok(EMPTY_OBJECT)
+}
+
+fn pop(parameter: FunctionParameter) -> NativeCallResult {
+    if let KaramelPrimative::PriorityQueue(queue) = &*parameter.source().unwrap().deref() {
+        let item = queue.borrow_mut().pop();
+        return match item {
+            Some(data) => Ok(data),
+            _ => Ok(EMPTY_OBJECT)
+        };
+    }
+    Ok(EMPTY_OBJECT)
+}
+
+fn peek(parameter: FunctionParameter) -> NativeCallResult {
+    if let KaramelPrimative::PriorityQueue(queue) = &*parameter.source().unwrap().deref() {
+        return match queue.borrow().peek() {
+            Some(data) => Ok(data),
+            _ => Ok(EMPTY_OBJECT)
+        };
+    }
+    Ok(EMPTY_OBJECT)
+}
+
+fn length(parameter: FunctionParameter) -> NativeCallResult {
+    if let KaramelPrimative::PriorityQueue(queue) = &*parameter.source().unwrap().deref() {
+        return Ok(VmObject::from(queue.borrow().len() as f64));
+    }
+    Ok(EMPTY_OBJECT)
+}
+
+fn is_empty(parameter: FunctionParameter) -> NativeCallResult {
+    if let KaramelPrimative::PriorityQueue(queue) = &*parameter.source().unwrap().deref() {
+        return Ok(arc_bool!(queue.borrow().is_empty()));
+    }
+    Ok(EMPTY_OBJECT)
+}
+
+fn clear(parameter: FunctionParameter) -> NativeCallResult {
+    if let KaramelPrimative::PriorityQueue(queue) = &*parameter.source().unwrap().deref() {
+        queue.borrow_mut().clear();
+    }
+    Ok(EMPTY_OBJECT)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::rc::Rc;
+    use std::cell::RefCell;
+    use crate::compiler::value::KaramelPrimative;
+    use super::*;
+
+    use crate::nativecall_test;
+    use crate::arc_text;
+    use crate::arc_number;
+    use crate::primative_text;
+    use crate::primative_number;
+
+    nativecall_test!{test_length_1, length, KaramelPrimative::PriorityQueue(RefCell::new(PriorityQueue::new())), KaramelPrimative::Number(0.0)}
+    nativecall_test!{test_is_empty_1, is_empty, KaramelPrimative::PriorityQueue(RefCell::new(PriorityQueue::new())), KaramelPrimative::Bool(true)}
+    nativecall_test!{test_pop_1, pop, KaramelPrimative::PriorityQueue(RefCell::new(PriorityQueue::new())), KaramelPrimative::Empty}
+
+    #[test]
+    fn test_push_pop_1 () {
+        let stdout = Some(RefCell::new(String::new()));
+        let stderr = Some(RefCell::new(String::new()));
+        let queue = Rc::new(KaramelPrimative::PriorityQueue(RefCell::new(PriorityQueue::new())));
+        let obj = VmObject::native_convert_by_ref(queue.clone());
+
+        for number in [5, 1, 3].iter() {
+            assert!(push(FunctionParameter::new(&[arc_number!(*number)].to_vec(), Some(obj), 1 as usize, 1 as u8, &stdout, &stderr)).is_ok());
+        }
+
+        for number in [1, 3, 5].iter() {
+            let result = pop(FunctionParameter::new(&Vec::new(), Some(obj), 0 as usize, 0 as u8, &stdout, &stderr));
+            assert_eq!(*result.unwrap().deref(), primative_number!(*number));
+        }
+    }
+
+    #[test]
+    fn test_push_pop_2 () {
+        let stdout = Some(RefCell::new(String::new()));
+        let stderr = Some(RefCell::new(String::new()));
+        let queue = Rc::new(KaramelPrimative::PriorityQueue(RefCell::new(PriorityQueue::new())));
+        let obj = VmObject::native_convert_by_ref(queue.clone());
+
+        assert!(push(FunctionParameter::new(&[arc_text!("iki"), arc_number!(2)].to_vec(), Some(obj), 2 as usize, 2 as u8, &stdout, &stderr)).is_ok());
+        assert!(push(FunctionParameter::new(&[arc_text!("bir"), arc_number!(1)].to_vec(), Some(obj), 2 as usize, 2 as u8, &stdout, &stderr)).is_ok());
+        assert!(push(FunctionParameter::new(&[arc_text!("bir-2"), arc_number!(1)].to_vec(), Some(obj), 2 as usize, 2 as u8, &stdout, &stderr)).is_ok());
+        assert!(push(FunctionParameter::new(&[arc_text!("hata")].to_vec(), Some(obj), 1 as usize, 1 as u8, &stdout, &stderr)).is_err());
+
+        for text in ["bir", "bir-2", "iki"].iter() {
+            let result = pop(FunctionParameter::new(&Vec::new(), Some(obj), 0 as usize, 0 as u8, &stdout, &stderr));
+            assert_eq!(*result.unwrap().deref(), primative_text!(*text));
+        }
+    }
+}
@@ -0,0 +1,123 @@
+use std::rc::Rc;
+
+use crate::{buildin::Class, compiler::function::{FunctionParameter, NativeCallResult}};
+use crate::compiler::value::EMPTY_OBJECT;
+use crate::buildin::class::baseclass::BasicInnerClass;
+use crate::compiler::value::KaramelPrimative;
+use crate::error::KaramelErrorType;
+use crate::types::VmObject;
+use crate::{n_parameter_expected, arc_bool};
+use crate::buildin::class::PRIMATIVE_CLASS_NAMES;
+
+pub fn get_primative_class() -> Rc<dyn Class> {
+    let mut opcode = BasicInnerClass::default();
+    opcode.set_name("kuyruk");
+    
+    opcode.add_class_method("ekle", push);
+    opcode.add_class_method("çıkar", pop);
+    opcode.add_class_method("cikar", pop);
+    opcode.add_class_method("ön", peek);
+    opcode.add_class_method("on", peek);
+    opcode.add_class_method("uzunluk", length);
+    opcode.add_class_method("boşmu", is_empty);
+    opcode.add_class_method("bosmu", is_empty);
+    opcode.add_class_method("temizle", clear);
+
+    PRIMATIVE_CLASS_NAMES.lock().unwrap().insert(opcode.get_class_name());
+    Rc::new(opcode)
+}
+
+fn push(parameter: FunctionParameter) -> NativeCallResult {
+    if let KaramelPrimative::Queue(queue) = &*parameter.source().unwrap().deref() {
+        return match parameter.length() {
+            0 =>  n_parameter_expected!("ekle".to_string(), 1),
+            1 => {
+                queue.borrow_mut().push_back(*parameter.iter().next().unwrap());
+                Ok(VmObject::from(queue.borrow().len() as f64))
+            },
+            _ => n_parameter_expected!("ekle".to_string(), 1, parameter.length())
+        };
+    }
+    Ok(EMPTY_OBJECT)
+}
+
+fn pop(parameter: FunctionParameter) -> NativeCallResult {
+    if let KaramelPrimative::Queue(queue) = &*parameter.source().unwrap().deref() {
+        let item = queue.borrow_mut().pop_front();
+        return match item {
+            Some(data) => Ok(data),
+            _ => Ok(EMPTY_OBJECT)
+        };
+    }
+    Ok(EMPTY_OBJECT)
+}
+
+fn peek(parameter: FunctionParameter) -> NativeCallResult {
+    if let KaramelPrimative::Queue(queue) = &*parameter.source().unwrap().deref() {
+        return match queue.borrow().front() {
+            Some(data) => Ok(*data),
+            _ => Ok(EMPTY_OBJECT)
+        };
+    }
+    Ok(EMPTY_OBJECT)
+}
+
+fn length(parameter: FunctionParameter) -> NativeCallResult {
+    if let KaramelPrimative::Queue(queue) = &*parameter.source().unwrap().deref() {
+        return Ok(VmObject::from(queue.borrow().len() as f64));
+    }
+    Ok(EMPTY_OBJECT)
+}
+
+fn is_empty(parameter: FunctionParameter) -> NativeCallResult {
+    if let KaramelPrimative::Queue(queue) = &*parameter.source().unwrap().deref() {
+        return Ok(arc_bool!(queue.borrow().is_empty()));
+    }
+    Ok(EMPTY_OBJECT)
+}
+
+fn clear(parameter: FunctionParameter) -> NativeCallResult {
+    if let KaramelPrimative::Queue(queue) = &*parameter.source().unwrap().deref() {
+        queue.borrow_mut().clear();
+    }
+    Ok(EMPTY_OBJECT)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::rc::Rc;
+    use std::cell::RefCell;
+    use std::collections::VecDeque;
+    use crate::compiler::value::KaramelPrimative;
+    use super::*;
+
+    use crate::nativecall_test_with_params;
+    use crate::nativecall_test;
+    use crate::arc_number;
+    use crate::primative_number;
+
+    nativecall_test!{test_length_1, length, KaramelPrimative::Queue(RefCell::new(VecDeque::from(vec![arc_number!(1)]))), KaramelPrimative::Number(1.0)}
+    nativecall_test!{test_is_empty_1, is_empty, KaramelPrimative::Queue(RefCell::new(VecDeque::new())), KaramelPrimative::Bool(true)}
+    nativecall_test!{test_peek_1, peek, KaramelPrimative::Queue(RefCell::new(VecDeque::from(vec![arc_number!(1), arc_number!(2)]))), primative_number!(1)}
+    nativecall_test!{test_pop_1, pop, KaramelPrimative::Queue(RefCell::new(VecDeque::from(vec![arc_number!(1), arc_number!(2)]))), primative_number!(1)}
+    nativecall_test!{test_pop_2, pop, KaramelPrimative::Queue(RefCell::new(VecDeque::new())), KaramelPrimative::Empty}
+    nativecall_test_with_params!{test_push_1, push, KaramelPrimative::Queue(RefCell::new(VecDeque::new())), [arc_number!(8)], primative_number!(1)}
+
+    #[test]
+    fn test_push_pop_1 () {
+        let stdout = Some(RefCell::new(String::new()));
+        let stderr = Some(RefCell::new(String::new()));
+        let queue = Rc::new(KaramelPrimative::Queue(RefCell::new(VecDeque::new())));
+        let obj = VmObject::native_convert_by_ref(queue.clone());
+
+        assert!(push(FunctionParameter::new(&[arc_number!(1)].to_vec(), Some(obj), 1 as usize, 1 as u8, &stdout, &stderr)).is_ok());
+        assert!(push(FunctionParameter::new(&[arc_number!(2)].to_vec(), Some(obj), 1 as usize, 1 as u8, &stdout, &stderr)).is_ok());
+
+        let result = pop(FunctionParameter::new(&Vec::new(), Some(obj), 0 as usize, 0 as u8, &stdout, &stderr));
+        assert_eq!(*result.unwrap().deref(), primative_number!(1));
+
+        let result = pop(FunctionParameter::new(&Vec::new(), Some(obj), 0 as usize, 0 as u8, &stdout, &stderr));
+        assert_eq!(*result.unwrap().deref(), primative_number!(2));
+    }
+}
@@ -0,0 +1,322 @@
+use std::rc::Rc;
+
+use crate::{buildin::{Class, ClassConfig, ClassProperty}, compiler::{GetType, function::{FunctionParameter, IndexerGetCall, IndexerSetCall, NativeCall, NativeCallResult, FunctionFlag}}};
+use crate::compiler::value::EMPTY_OBJECT;
+use crate::buildin::class::baseclass::BasicInnerClass;
+use crate::compiler::value::KaramelPrimative;
+use crate::compiler::DictKey;
+use crate::error::KaramelErrorType;
+use crate::types::VmObject;
+use crate::{n_parameter_expected, arc_bool};
+
+use crate::buildin::class::PRIMATIVE_CLASS_NAMES;
+
+/// Dict that keeps its items ordered by their keys. Keys are ordered as 'boş', bools, numbers, texts and the
+/// frozen values, numbers are ordered by their values and texts alphabetically.
+#[derive(Default)]
+pub struct SortedDictClass {
+    base: BasicInnerClass
+}
+
+impl GetType for SortedDictClass {
+    fn get_type(&self) -> String {
+        "sıralı_sözlük".to_string()
+    }
+}
+
+impl SortedDictClass {
+    pub fn new() -> Self {
+        let mut dict = SortedDictClass::default();
+        dict.add_class_method("getir", get);
+        dict.add_class_method("ekle", add);
+        dict.add_class_method("güncelle", set);
+        dict.add_class_method("guncelle", set);
+        dict.add_class_method("içeriyormu", contains);
+        dict.add_class_method("iceriyormu", contains);
+        dict.add_class_method("sil", remove);
+        dict.add_class_method("uzunluk", length);
+        dict.add_class_method("boşmu", is_empty);
+        dict.add_class_method("bosmu", is_empty);
+        dict.add_class_method("temizle", clear);
+        dict.add_class_method("anahtarlar", keys);
+        dict.add_class_method("değerler", values);
+        dict.add_class_method("degerler", values);
+        dict.add_class_method("ilk", first);
+        dict.add_class_method("son", last);
+        dict.add_class_method("çıkar", pop_first);
+        dict.add_class_method("cikar", pop_first);
+        dict.add_class_method("kopyala", copy);
+        dict.set_getter(getter);
+
+        PRIMATIVE_CLASS_NAMES.lock().unwrap().insert(dict.get_type());
+        dict
+    }
+
+    pub fn add_class_method(&mut self, name: &str, function: NativeCall) {
+        self.base.add_method(name, function, FunctionFlag::IN_CLASS);
+    }
+}
+
+impl Class for SortedDictClass {
+    fn set_class_config(&mut self, config: ClassConfig) {
+        self.base.set_class_config(config);
+    }
+
+    fn get_class_name(&self) -> String {
+        self.get_type()
+    }
+
+    fn has_element(&self, source: Option<VmObject>, field: Rc<String>) -> bool {
+        self.base.has_element(source, field)
+    }
+
+    fn properties(&self) -> std::collections::hash_map::Iter<'_, String, ClassProperty> {
+        self.base.properties()
+    }
+
+    fn get_element(&self, source: Option<VmObject>, field: Rc<String>) -> Option<ClassProperty> {
+        match self.base.get_element(source, field.clone()) {
+            Some(property) => Some(property),
+            None => match source.map(|object| object.deref()).as_deref() {
+                Some(KaramelPrimative::SortedDict(dict)) => dict.borrow().get(&DictKey::from(field)).map(|data| ClassProperty::Field(data.deref())),
+                _ => None
+            }
+        }
+    }
+
+    fn property_count(&self) -> usize {
+        self.base.property_count()
+    }
+
+    fn add_method(&mut self, name: &str, function: NativeCall, flags: FunctionFlag) {
+        self.base.add_method(name, function, flags);
+    }
+
+    fn add_property(&mut self, name: &str, property: Rc<KaramelPrimative>) {
+        self.base.add_property(name, property);
+    }
+
+    fn set_getter(&mut self, indexer: IndexerGetCall) {
+        self.base.set_getter(indexer);
+    }
+
+    fn get_getter(&self) -> Option<IndexerGetCall> {
+        self.base.get_getter()
+    }
+
+    fn set_setter(&mut self, indexer: IndexerSetCall) {
+        self.base.set_setter(indexer);
+    }
+
+    fn get_setter(&self) -> Option<IndexerSetCall> {
+        self.base.get_setter()
+    }
+}
+
+pub fn get_primative_class() -> Rc<dyn Class> {
+    Rc::new(SortedDictClass::new())
+}
+
+/* Key and value of the item as a list, 'boş' when there is no item */
+fn item_pair(item: Option<(&DictKey, &VmObject)>) -> VmObject {
+    match item {
+        Some((key, value)) => VmObject::from(vec![key.to_value(), *value]),
+        None => EMPTY_OBJECT
+    }
+}
+
+fn getter(source: VmObject, index: f64) -> NativeCallResult {
+    if let KaramelPrimative::SortedDict(dict) = &*source.deref() {
+        if let Some(key) = DictKey::from_value(&KaramelPrimative::Number(index)) {
+            return Ok(dict.borrow().get(&key).copied().unwrap_or(EMPTY_OBJECT));
+        }
+    }
+    Ok(EMPTY_OBJECT)
+}
+
+fn get(parameter: FunctionParameter) -> NativeCallResult {
+    if let KaramelPrimative::SortedDict(dict) = &*parameter.source().unwrap().deref() {
+        return match parameter.length() {
+            1 => {
+                let key = DictKey::try_from_value(&parameter.iter().next().unwrap().deref())?;
+                Ok(dict.borrow().get(&key).copied().unwrap_or(EMPTY_OBJECT))
+            },
+            _ => n_parameter_expected!("getir".to_string(), 1, parameter.length())
+        };
+    }
+    Ok(EMPTY_OBJECT)
+}
+
+fn set(parameter: FunctionParameter) -> NativeCallResult {
+    insert_or_update(parameter, "güncelle")
+}
+
+fn add(parameter: FunctionParameter) -> NativeCallResult {
+    insert_or_update(parameter, "ekle")
+}
+
+fn insert_or_update(parameter: FunctionParameter, function_name: &str) -> NativeCallResult {
+    if let KaramelPrimative::SortedDict(dict) = &*parameter.source().unwrap().deref() {
+        return match parameter.length() {
+            2 => {
+                let mut iter = parameter.iter();
+                let (key, item) = (DictKey::try_from_value(&iter.next().unwrap().deref())?, *iter.next().unwrap());
+                dict.borrow_mut().insert(key, item);
+                Ok(EMPTY_OBJECT)
+            },
+            _ => n_parameter_expected!(function_name.to_string(), 2, parameter.length())
+        };
+    }
+    Ok(EMPTY_OBJECT)
+}
+
+fn contains(parameter: FunctionParameter) -> NativeCallResult {
+    if let KaramelPrimative::SortedDict(dict) = &*parameter.source().unwrap().deref() {
+        return match parameter.length() {
+            1 => {
+                let key = DictKey::try_from_value(&parameter.iter().next().unwrap().deref())?;
+                Ok(arc_bool!(dict.borrow().contains_key(&key)))
+            },
+            _ => n_parameter_expected!("içeriyormu".to_string(), 1, parameter.length())
+        };
+    }
+    Ok(EMPTY_OBJECT)
+}
+
+fn remove(parameter: FunctionParameter) -> NativeCallResult {
+    if let KaramelPrimative::SortedDict(dict) = &*parameter.source().unwrap().deref() {
+        return match parameter.length() {
+            1 => {
+                let key = DictKey::try_from_value(&parameter.iter().next().unwrap().deref())?;
+                Ok(arc_bool!(dict.borrow_mut().remove(&key).is_some()))
+            },
+            _ => n_parameter_expected!("sil".to_string(), 1, parameter.length())
+        };
+    }
+    Ok(EMPTY_OBJECT)
+}
+
+fn length(parameter: FunctionParameter) -> NativeCallResult {
+    if let KaramelPrimative::SortedDict(dict) = &*parameter.source().unwrap().deref() {
+        return Ok(VmObject::from(dict.borrow().len() as f64));
+    }
+    Ok(EMPTY_OBJECT)
+}
+
+fn is_empty(parameter: FunctionParameter) -> NativeCallResult {
+    if let KaramelPrimative::SortedDict(dict) = &*parameter.source().unwrap().deref() {
+        return Ok(arc_bool!(dict.borrow().is_empty()));
+    }
+    Ok(EMPTY_OBJECT)
+}
+
+fn clear(parameter: FunctionParameter) -> NativeCallResult {
+    if let KaramelPrimative::SortedDict(dict) = &*parameter.source().unwrap().deref() {
+        dict.borrow_mut().clear();
+    }
+    Ok(EMPTY_OBJECT)
+}
+
+fn keys(parameter: FunctionParameter) -> NativeCallResult {
+    if let KaramelPrimative::SortedDict(dict) = &*parameter.source().unwrap().deref() {
+        let keys = dict.borrow().keys().map(DictKey::to_value).collect::<Vec<_>>();
+        return Ok(VmObject::from(keys));
+    }
+    Ok(EMPTY_OBJECT)
+}
+
+fn values(parameter: FunctionParameter) -> NativeCallResult {
+    if let KaramelPrimative::SortedDict(dict) = &*parameter.source().unwrap().deref() {
+        let values = dict.borrow().values().copied().collect::<Vec<_>>();
+        return Ok(VmObject::from(values));
+    }
+    Ok(EMPTY_OBJECT)
+}
+
+fn first(parameter: FunctionParameter) -> NativeCallResult {
+    if let KaramelPrimative::SortedDict(dict) = &*parameter.source().unwrap().deref() {
+        return Ok(item_pair(dict.borrow().iter().next()));
+    }
+    Ok(EMPTY_OBJECT)
+}
+
+fn last(parameter: FunctionParameter) -> NativeCallResult {
+    if let KaramelPrimative::SortedDict(dict) = &*parameter.source().unwrap().deref() {
+        return Ok(item_pair(dict.borrow().iter().next_back()));
+    }
+    Ok(EMPTY_OBJECT)
+}
+
+/* Item with the smallest key is removed and given back */
+fn pop_first(parameter: FunctionParameter) -> NativeCallResult {
+    if let KaramelPrimative::SortedDict(dict) = &*parameter.source().unwrap().deref() {
+        let item = dict.borrow_mut().pop_first();
+        return Ok(item_pair(item.as_ref().map(|(key, value)| (key, value))));
+    }
+    Ok(EMPTY_OBJECT)
+}
+
+fn copy(parameter: FunctionParameter) -> NativeCallResult {
+    match parameter.length() {
+        0 => parameter.deep_copy(parameter.source().unwrap()),
+        _ => n_parameter_expected!("kopyala".to_string(), 0, parameter.length())
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+    use std::collections::BTreeMap;
+    use crate::compiler::value::KaramelPrimative;
+    use super::*;
+
+    use crate::arc_text;
+    use crate::primative_text;
+    use crate::arc_number;
+
+    fn call(function: NativeCall, source: VmObject, arguments: Vec<VmObject>) -> NativeCallResult {
+        let stdout = Some(RefCell::new(String::new()));
+        let stderr = Some(RefCell::new(String::new()));
+        function(FunctionParameter::new(&arguments, Some(source), arguments.len(), arguments.len() as u8, &stdout, &stderr))
+    }
+
+    fn text_list(value: VmObject) -> Vec<String> {
+        match &*value.deref() {
+            KaramelPrimative::List(items) => items.borrow().iter().map(|item| format!("{:?}", item.deref())).collect(),
+            value => panic!("{:?}", value)
+        }
+    }
+
+    #[test]
+    fn test_ordered_keys() {
+        let dict = VmObject::native_convert(KaramelPrimative::SortedDict(RefCell::new(BTreeMap::new())));
+        for key in [arc_text!("b"), arc_number!(10), arc_text!("a"), arc_number!(2)].iter() {
+            assert!(call(add, dict, vec![*key, arc_bool!(true)]).is_ok());
+        }
+
+        assert_eq!(text_list(call(keys, dict, Vec::new()).unwrap()), vec!["2", "10", "\"a\"", "\"b\""]);
+        assert_eq!(text_list(call(last, dict, Vec::new()).unwrap()), vec!["\"b\"", "doğru"]);
+        assert_eq!(getter(dict, 10.0).unwrap(), arc_bool!(true));
+    }
+
+    #[test]
+    fn test_pop_first() {
+        let dict = VmObject::native_convert(KaramelPrimative::SortedDict(RefCell::new(BTreeMap::new())));
+        assert!(call(add, dict, vec![arc_number!(3), arc_text!("üç")]).is_ok());
+        assert!(call(add, dict, vec![arc_number!(1), arc_text!("bir")]).is_ok());
+
+        assert_eq!(text_list(call(pop_first, dict, Vec::new()).unwrap()), vec!["1", "\"bir\""]);
+        assert_eq!(text_list(call(pop_first, dict, Vec::new()).unwrap()), vec!["3", "\"üç\""]);
+        assert_eq!(call(pop_first, dict, Vec::new()).unwrap(), EMPTY_OBJECT);
+        assert_eq!(call(is_empty, dict, Vec::new()).unwrap(), arc_bool!(true));
+    }
+
+    #[test]
+    fn test_invalid_key() {
+        let dict = VmObject::native_convert(KaramelPrimative::SortedDict(RefCell::new(BTreeMap::new())));
+        let key = VmObject::from(Vec::new());
+        assert_eq!(call(add, dict, vec![key, arc_number!(1)]), Err(KaramelErrorType::DictionaryKeyNotHashable(key.deref())));
+        assert_eq!(*call(get, dict, vec![arc_text!("yok")]).unwrap().deref(), KaramelPrimative::Empty);
+    }
+}
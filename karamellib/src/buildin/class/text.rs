@@ -42,7 +42,6 @@ pub fn get_primative_class() -> Rc<dyn Class> {
     opcode.add_class_method("sayi", number);
     opcode.add_class_method("levenshtein", levenshtein);
     opcode.set_getter(getter);
-    opcode.set_setter(setter);
 
     PRIMATIVE_CLASS_NAMES.lock().unwrap().insert(opcode.get_class_name());
     Rc::new(opcode)
@@ -65,55 +64,6 @@ fn getter(source: VmObject, index: f64) -> NativeCallResult {
     Ok(EMPTY_OBJECT)
 }
 
-fn setter(source: VmObject, index: f64, item: VmObject) -> NativeCallResult {
-    let index = match index >= 0.0 {
-        true => index as usize,
-        false =>  return Ok(EMPTY_OBJECT)
-    };
-
-    if let KaramelPrimative::Text(text) = &*source.deref() {
-        return match text.chars().nth(index) {
-            Some(old_char) => {
-                match &*item.deref() {
-                    KaramelPrimative::Text(data) => {
-                        if data.chars().count() != 1 {
-                            return Ok(EMPTY_OBJECT);
-                        }
-
-                        let new_char = data.chars().nth(0).unwrap();
-                        let mut real_index = 0;
-                        let mut real_total = 0;
-
-                        for (i, ch) in text.chars().enumerate() {
-                            if i < index{
-                                real_index += ch.len_utf8();
-                            }
-                            real_total += ch.len_utf8();
-                        }
-                        
-                        /* full text size + new char size - old char size */
-                        let mut new_string = String::with_capacity(real_total + data.len() - old_char.len_utf8());
-                        new_string.push_str(&text[0..real_index]);
-                        new_string.push(new_char);
-                        new_string.push_str(&text[real_index+old_char.len_utf8()..]);
-
-                        unsafe {
-                            /* Update text with new one */
-                            let text_ptr = text as *const Rc<String> as *mut Rc<String>;
-                            *Rc::make_mut(&mut *text_ptr) = new_string;
-                        }
-
-                        Ok(EMPTY_OBJECT)
-                    },
-                    _ => Ok(EMPTY_OBJECT) //We cant use other types in text
-                }
-            },
-            None => Ok(EMPTY_OBJECT)
-        };
-    }
-    Ok(EMPTY_OBJECT)
-}
-
 fn length(parameter: FunctionParameter) -> NativeCallResult {
     if let KaramelPrimative::Text(text) = &*parameter.source().unwrap().deref() {
         return Ok(VmObject::native_convert(KaramelPrimative::Number(text.chars().count() as f64)));
@@ -0,0 +1,116 @@
+use std::rc::Rc;
+
+use crate::{buildin::Class, compiler::function::{FunctionParameter, NativeCallResult}};
+use crate::compiler::value::{EMPTY_OBJECT, number_to_text};
+use crate::buildin::class::baseclass::BasicInnerClass;
+use crate::compiler::value::KaramelPrimative;
+use crate::error::KaramelErrorType;
+use crate::types::VmObject;
+use crate::{n_parameter_expected, expected_parameter_type, arc_bool};
+use crate::buildin::class::PRIMATIVE_CLASS_NAMES;
+
+pub fn get_primative_class() -> Rc<dyn Class> {
+    let mut opcode = BasicInnerClass::default();
+    opcode.set_name("yazı_oluşturucu");
+
+    opcode.add_class_method("ekle", append);
+    opcode.add_class_method("yazı", to_text);
+    opcode.add_class_method("yazi", to_text);
+    opcode.add_class_method("uzunluk", length);
+    opcode.add_class_method("boşmu", is_empty);
+    opcode.add_class_method("bosmu", is_empty);
+    opcode.add_class_method("temizle", clear);
+
+    PRIMATIVE_CLASS_NAMES.lock().unwrap().insert(opcode.get_class_name());
+    Rc::new(opcode)
+}
+
+/* The text is appended in place, the builder itself is returned so the calls can be chained */
+fn append(parameter: FunctionParameter) -> NativeCallResult {
+    if let KaramelPrimative::TextBuilder(text) = &*parameter.source().unwrap().deref() {
+        return match parameter.length() {
+            0 =>  n_parameter_expected!("ekle".to_string(), 1),
+            1 => {
+                let value = match &*parameter.iter().next().unwrap().deref() {
+                    KaramelPrimative::Text(value) => value.to_string(),
+                    KaramelPrimative::Number(value) => number_to_text(*value),
+                    _ => return expected_parameter_type!("ekle".to_string(), "Yazı".to_string())
+                };
+                parameter.allocate(value.len())?;
+                text.borrow_mut().push_str(&value);
+                Ok(parameter.source().unwrap())
+            },
+            _ => n_parameter_expected!("ekle".to_string(), 1, parameter.length())
+        };
+    }
+    Ok(EMPTY_OBJECT)
+}
+
+fn to_text(parameter: FunctionParameter) -> NativeCallResult {
+    if let KaramelPrimative::TextBuilder(text) = &*parameter.source().unwrap().deref() {
+        parameter.allocate(text.borrow().len())?;
+        return Ok(VmObject::from(Rc::new(text.borrow().clone())));
+    }
+    Ok(EMPTY_OBJECT)
+}
+
+fn length(parameter: FunctionParameter) -> NativeCallResult {
+    if let KaramelPrimative::TextBuilder(text) = &*parameter.source().unwrap().deref() {
+        return Ok(VmObject::from(text.borrow().chars().count() as f64));
+    }
+    Ok(EMPTY_OBJECT)
+}
+
+fn is_empty(parameter: FunctionParameter) -> NativeCallResult {
+    if let KaramelPrimative::TextBuilder(text) = &*parameter.source().unwrap().deref() {
+        return Ok(arc_bool!(text.borrow().is_empty()));
+    }
+    Ok(EMPTY_OBJECT)
+}
+
+fn clear(parameter: FunctionParameter) -> NativeCallResult {
+    if let KaramelPrimative::TextBuilder(text) = &*parameter.source().unwrap().deref() {
+        text.borrow_mut().clear();
+    }
+    Ok(EMPTY_OBJECT)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::rc::Rc;
+    use std::cell::RefCell;
+    use crate::compiler::value::KaramelPrimative;
+    use super::*;
+
+    use crate::nativecall_test_with_params;
+    use crate::nativecall_test;
+    use crate::arc_text;
+    use crate::primative_text;
+    use crate::arc_number;
+    use crate::primative_number;
+
+    nativecall_test!{test_length_1, length, KaramelPrimative::TextBuilder(RefCell::new("ığdır".to_string())), primative_number!(5)}
+    nativecall_test!{test_is_empty_1, is_empty, KaramelPrimative::TextBuilder(RefCell::new(String::new())), KaramelPrimative::Bool(true)}
+    nativecall_test!{test_to_text_1, to_text, KaramelPrimative::TextBuilder(RefCell::new("karamel".to_string())), primative_text!("karamel")}
+    nativecall_test_with_params!{test_append_1, append, KaramelPrimative::TextBuilder(RefCell::new("a".to_string())), [arc_text!("b")], KaramelPrimative::TextBuilder(RefCell::new("ab".to_string()))}
+    nativecall_test_with_params!{test_append_2, append, KaramelPrimative::TextBuilder(RefCell::new("a".to_string())), [arc_number!(1.5)], KaramelPrimative::TextBuilder(RefCell::new("a1,5".to_string()))}
+
+    #[test]
+    fn test_append_to_text_1 () {
+        let stdout = Some(RefCell::new(String::new()));
+        let stderr = Some(RefCell::new(String::new()));
+        let builder = Rc::new(KaramelPrimative::TextBuilder(RefCell::new(String::new())));
+        let obj = VmObject::native_convert_by_ref(builder.clone());
+
+        for _ in 0..3 {
+            assert!(append(FunctionParameter::new(&[arc_text!("ha")].to_vec(), Some(obj), 1 as usize, 1 as u8, &stdout, &stderr)).is_ok());
+        }
+
+        let result = to_text(FunctionParameter::new(&Vec::new(), Some(obj), 0 as usize, 0 as u8, &stdout, &stderr));
+        assert_eq!(*result.unwrap().deref(), primative_text!("hahaha"));
+
+        let result = append(FunctionParameter::new(&[arc_bool!(true)].to_vec(), Some(obj), 1 as usize, 1 as u8, &stdout, &stderr));
+        assert!(result.is_err());
+    }
+}
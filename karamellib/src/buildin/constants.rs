@@ -0,0 +1,124 @@
+use crate::compiler::function::FunctionReference;
+use crate::compiler::value::KaramelPrimative;
+use crate::buildin::{Module, Class};
+use std::{cell::RefCell, collections::HashMap};
+use std::rc::Rc;
+
+/* (Turkish name, ASCII alias, value) */
+static CONSTANTS: &[(&str, &str, f64)] = &[
+    /* Math */
+    ("pi",                 "pi",                 std::f64::consts::PI),
+    ("tau",                "tau",                std::f64::consts::TAU),
+    ("e",                  "e",                  std::f64::consts::E),
+    ("altın_oran",         "altin_oran",         1.618_033_988_749_895),
+    ("kök_iki",            "kok_iki",            std::f64::consts::SQRT_2),
+
+    /* Physics */
+    ("ışık_hızı",          "isik_hizi",          299_792_458.0),
+    ("ses_hızı",           "ses_hizi",           343.0),
+    ("yerçekimi",          "yercekimi",          9.806_65),
+    ("avogadro",           "avogadro",           6.022_140_76e23),
+    ("planck",             "planck",             6.626_070_15e-34),
+    ("boltzmann",          "boltzmann",          1.380_649e-23),
+    ("elektron_yükü",      "elektron_yuku",      1.602_176_634e-19),
+    ("gaz_sabiti",         "gaz_sabiti",         8.314_462_618),
+
+    /* Education */
+    ("geçme_notu",         "gecme_notu",         50.0),
+    ("teşekkür_notu",      "tesekkur_notu",      70.0),
+    ("takdir_notu",        "takdir_notu",        85.0),
+    ("tam_not",            "tam_not",            100.0),
+    ("ders_süresi",        "ders_suresi",        40.0),
+    ("teneffüs_süresi",    "teneffus_suresi",    10.0)
+];
+
+pub struct ConstantsModule {
+    constants: RefCell<HashMap<String, Rc<KaramelPrimative>>>,
+    path: Vec<String>
+}
+
+impl Module for ConstantsModule {
+    fn get_module_name(&self) -> String {
+        "sabitler".to_string()
+    }
+
+    fn get_path(&self) -> &Vec<String> {
+        &self.path
+    }
+
+    fn get_method(&self, _: &str) -> Option<Rc<FunctionReference>> {
+        None
+    }
+
+    fn get_module(&self, _: &str) -> Option<Rc<dyn Module>> {
+        None
+    }
+
+    fn get_methods(&self) -> Vec<Rc<FunctionReference>> {
+        Vec::new()
+    }
+
+    fn get_modules(&self) -> HashMap<String, Rc<dyn Module>> {
+        HashMap::new()
+    }
+
+    fn get_classes(&self) -> Vec<Rc<dyn Class>> {
+        Vec::new()
+    }
+
+    fn get_constant(&self, name: &str) -> Option<Rc<KaramelPrimative>> {
+        self.constants.borrow().get(name).cloned()
+    }
+
+    fn get_constants(&self) -> HashMap<String, Rc<KaramelPrimative>> {
+        self.constants.borrow().clone()
+    }
+}
+
+impl ConstantsModule {
+    pub fn new() -> Rc<ConstantsModule> {
+        let module = ConstantsModule {
+            constants: RefCell::new(HashMap::new()),
+            path: vec!["sabitler".to_string()]
+        };
+
+        for (name, alias, value) in CONSTANTS.iter() {
+            module.add_constant(name, KaramelPrimative::Number(*value));
+            module.add_constant(alias, KaramelPrimative::Number(*value));
+        }
+
+        Rc::new(module)
+    }
+
+    /// Adds a new constant or overrides an existing one. Should be called before the module is registered to the context.
+    pub fn add_constant(&self, name: &str, value: KaramelPrimative) {
+        self.constants.borrow_mut().insert(name.to_string(), Rc::new(value));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ConstantsModule;
+    use crate::buildin::Module;
+    use crate::compiler::value::KaramelPrimative;
+    use std::rc::Rc;
+
+    #[test]
+    fn constants_test() {
+        let module = ConstantsModule::new();
+        assert_eq!(module.get_constant("pi"), Some(Rc::new(KaramelPrimative::Number(std::f64::consts::PI))));
+        assert_eq!(module.get_constant("ışık_hızı"), module.get_constant("isik_hizi"));
+        assert_eq!(module.get_constant("geçme_notu"), Some(Rc::new(KaramelPrimative::Number(50.0))));
+        assert_eq!(module.get_constant("bilinmeyen"), None);
+    }
+
+    #[test]
+    fn add_constant_test() {
+        let module = ConstantsModule::new();
+        module.add_constant("okul_adı", KaramelPrimative::Text(Rc::new("Atatürk Lisesi".to_string())));
+        module.add_constant("geçme_notu", KaramelPrimative::Number(45.0));
+
+        assert_eq!(module.get_constant("okul_adı"), Some(Rc::new(KaramelPrimative::Text(Rc::new("Atatürk Lisesi".to_string())))));
+        assert_eq!(module.get_constant("geçme_notu"), Some(Rc::new(KaramelPrimative::Number(45.0))));
+    }
+}
@@ -1,7 +1,10 @@
 use crate::buildin::{Module, Class};
 use crate::compiler::function::{FunctionReference, NativeCall, NativeCallResult};
 use crate::compiler::function::FunctionParameter;
-use crate::compiler::value::EMPTY_OBJECT;
+use crate::compiler::value::{EMPTY_OBJECT, KaramelPrimative};
+use crate::compiler::DictKey;
+use crate::types::{SourcePosition, VmObject};
+use crate::n_parameter_expected;
 use crate::error::KaramelErrorType;
 use std::cell::RefCell;
 use std::collections::HashMap;
@@ -54,30 +57,93 @@ impl DebugModule  {
 
         let rc_module = Rc::new(module);
         rc_module.methods.borrow_mut().insert("doğrula".to_string(), FunctionReference::native_function(Self::assert as NativeCall, "doğrula".to_string(), rc_module.clone()));
+        rc_module.methods.borrow_mut().insert("çağrı_yığını".to_string(), FunctionReference::native_function(Self::call_stack as NativeCall, "çağrı_yığını".to_string(), rc_module.clone()));
+        rc_module.methods.borrow_mut().insert("cagri_yigini".to_string(), FunctionReference::native_function(Self::call_stack as NativeCall, "cagri_yigini".to_string(), rc_module.clone()));
+        rc_module.methods.borrow_mut().insert("geçerli_fonksiyon".to_string(), FunctionReference::native_function(Self::current_function as NativeCall, "geçerli_fonksiyon".to_string(), rc_module.clone()));
+        rc_module.methods.borrow_mut().insert("gecerli_fonksiyon".to_string(), FunctionReference::native_function(Self::current_function as NativeCall, "gecerli_fonksiyon".to_string(), rc_module.clone()));
+        rc_module.methods.borrow_mut().insert("çöp_topla".to_string(), FunctionReference::native_function(Self::collect_garbage as NativeCall, "çöp_topla".to_string(), rc_module.clone()));
+        rc_module.methods.borrow_mut().insert("cop_topla".to_string(), FunctionReference::native_function(Self::collect_garbage as NativeCall, "cop_topla".to_string(), rc_module.clone()));
         rc_module.clone()
     }
 
+    /// Checks the condition or compares two values. A text after the condition is written with the error when the condition is false.
     pub fn assert(parameter: FunctionParameter) -> NativeCallResult {
-        match parameter.length() {
-            1 => {
-                match parameter.iter().next().unwrap().deref().is_true() {
-                    false => Err(KaramelErrorType::AssertFailed),
-                    true  => Ok(EMPTY_OBJECT)
-                }
+        let arguments: Vec<Rc<KaramelPrimative>> = parameter.iter().map(|argument| argument.deref()).collect();
+        match &arguments[..] {
+            [condition] => match condition.is_true() {
+                false => Err(Self::condition_failed(&parameter, condition, String::new())),
+                true  => Ok(EMPTY_OBJECT)
             },
-            2 => {
-                let mut iter = parameter.iter();
-                let left = iter.next().unwrap().deref();
-                let right = iter.next().unwrap().deref();
-                match left == right {
-                    false => Err(KaramelErrorType::AssertFailedWithArgument {
-                        left: left.clone(),
-                        right: right.clone()
-                    }),
-                    true  => Ok(EMPTY_OBJECT)
-                }
+            [condition, message] if matches!((&**condition, &**message), (KaramelPrimative::Bool(_), KaramelPrimative::Text(_))) => match condition.is_true() {
+                false => Err(Self::condition_failed(&parameter, condition, message.get_text())),
+                true  => Ok(EMPTY_OBJECT)
+            },
+            [left, right] => match left == right {
+                false => Err(KaramelErrorType::AssertFailedWithArgument {
+                    left: left.clone(),
+                    right: right.clone()
+                }),
+                true  => Ok(EMPTY_OBJECT)
             },
             _ => Err(KaramelErrorType::AssertFailed)
         }
     }
-}
\ No newline at end of file
+
+    /* Condition text is taken from the source while compiling, the value is written when the text is not known */
+    fn condition_failed(parameter: &FunctionParameter, condition: &KaramelPrimative, message: String) -> KaramelErrorType {
+        let frame = parameter.call_stack().pop();
+        let position = frame.as_ref().and_then(|frame| Some(SourcePosition::new(frame.line?, frame.column?)));
+        let expression = position.and_then(|position| parameter.context()?.assert_expressions.get(&position).cloned());
+
+        KaramelErrorType::AssertFailedWithExpression {
+            expression: expression.unwrap_or_else(|| format!("{:?}", condition)),
+            line: frame.and_then(|frame| frame.line).unwrap_or_default(),
+            message
+        }
+    }
+
+    /// Each frame is a dictionary with 'fonksiyon', 'satır' and 'parametreler' keys. Main code is the first frame and its function is empty.
+    /// Parameters are written as texts, the same way the stack traces write them.
+    pub fn call_stack(parameter: FunctionParameter) -> NativeCallResult {
+        if parameter.length() != 0 {
+            return n_parameter_expected!("çağrı_yığını".to_string(), 0, parameter.length());
+        }
+
+        let frames = parameter.call_stack().into_iter().map(|frame| {
+            let mut items = HashMap::new();
+            items.insert(DictKey::from("fonksiyon"), match frame.function {
+                Some(name) => VmObject::from(Rc::new(name)),
+                None => EMPTY_OBJECT
+            });
+            items.insert(DictKey::from("satır"), match frame.line {
+                Some(line) => VmObject::from((line + 1) as f64),
+                None => EMPTY_OBJECT
+            });
+
+            let arguments = frame.arguments.into_iter().map(|(name, value)| (DictKey::from(name), VmObject::from(Rc::new(value)))).collect();
+            items.insert(DictKey::from("parametreler"), VmObject::native_convert(KaramelPrimative::Dict(RefCell::new(arguments))));
+            VmObject::native_convert(KaramelPrimative::Dict(RefCell::new(items)))
+        }).collect();
+
+        Ok(VmObject::native_convert(KaramelPrimative::List(RefCell::new(frames))))
+    }
+
+    pub fn collect_garbage(parameter: FunctionParameter) -> NativeCallResult {
+        if parameter.length() != 0 {
+            return n_parameter_expected!("çöp_topla".to_string(), 0, parameter.length());
+        }
+
+        Ok(VmObject::from(parameter.collect_garbage()))
+    }
+
+    pub fn current_function(parameter: FunctionParameter) -> NativeCallResult {
+        if parameter.length() != 0 {
+            return n_parameter_expected!("geçerli_fonksiyon".to_string(), 0, parameter.length());
+        }
+
+        match parameter.call_stack().pop().and_then(|frame| frame.function) {
+            Some(name) => Ok(VmObject::from(Rc::new(name))),
+            None => Ok(EMPTY_OBJECT)
+        }
+    }
+}
@@ -0,0 +1,131 @@
+use crate::compiler::KaramelPrimative;
+use crate::compiler::context::KaramelCompilerContext;
+use crate::compiler::function::{FunctionParameter, FunctionReference, NativeCall, NativeCallResult};
+use crate::types::VmObject;
+use crate::error::KaramelErrorType;
+use crate::buildin::{Module, Class, Permission};
+use crate::vm::ffi::{self, ForeignType};
+use crate::{n_parameter_expected, expected_parameter_type};
+use std::{cell::RefCell, collections::HashMap};
+use std::rc::Rc;
+
+pub struct FfiModule {
+    methods: RefCell<HashMap<String, Rc<FunctionReference>>>,
+    path: Vec<String>
+}
+
+impl Module for FfiModule {
+    fn get_module_name(&self) -> String {
+        "ffi".to_string()
+    }
+
+    fn get_path(&self) -> &Vec<String> {
+        &self.path
+    }
+
+    fn get_method(&self, name: &str) -> Option<Rc<FunctionReference>> {
+        self.methods.borrow().get(name).map(|method| method.clone())
+    }
+
+    fn get_module(&self, _: &str) -> Option<Rc<dyn Module>> {
+        None
+    }
+
+    fn get_methods(&self) -> Vec<Rc<FunctionReference>> {
+        let mut response = Vec::new();
+        self.methods.borrow().iter().for_each(|(_, reference)| response.push(reference.clone()));
+        response
+    }
+
+    fn get_modules(&self) -> HashMap<String, Rc<dyn Module>> {
+        HashMap::new()
+    }
+
+    fn get_classes(&self) -> Vec<Rc<dyn Class>> {
+        Vec::new()
+    }
+
+    fn get_permission(&self) -> Option<Permission> {
+        Some(Permission::Ffi)
+    }
+}
+
+impl FfiModule {
+    pub fn new() -> Rc<FfiModule> {
+        let module = FfiModule {
+            methods: RefCell::new(HashMap::new()),
+            path: vec!["ffi".to_string()]
+        };
+
+        let rc_module = Rc::new(module);
+        rc_module.methods.borrow_mut().insert("aç".to_string(), FunctionReference::native_function(Self::open as NativeCall, "aç".to_string(), rc_module.clone()));
+        rc_module.methods.borrow_mut().insert("ac".to_string(), FunctionReference::native_function(Self::open as NativeCall, "ac".to_string(), rc_module.clone()));
+        rc_module.methods.borrow_mut().insert("tanımla".to_string(), FunctionReference::native_function(Self::declare as NativeCall, "tanımla".to_string(), rc_module.clone()));
+        rc_module.methods.borrow_mut().insert("tanimla".to_string(), FunctionReference::native_function(Self::declare as NativeCall, "tanimla".to_string(), rc_module.clone()));
+        rc_module.methods.borrow_mut().insert("çağır".to_string(), FunctionReference::native_function(Self::call as NativeCall, "çağır".to_string(), rc_module.clone()));
+        rc_module.methods.borrow_mut().insert("cagir".to_string(), FunctionReference::native_function(Self::call as NativeCall, "cagir".to_string(), rc_module.clone()));
+        rc_module.clone()
+    }
+
+    fn get_context<'a>(parameter: &'a FunctionParameter) -> Result<&'a KaramelCompilerContext, KaramelErrorType> {
+        parameter.context().ok_or_else(|| KaramelErrorType::GeneralError("Yabancı fonksiyonlar sadece program çalışırken kullanılabilir".to_string()))
+    }
+
+    fn get_number(function_name: &str, object: &VmObject) -> Result<usize, KaramelErrorType> {
+        match object.as_number() {
+            Some(number) if number >= 0.0 && number.fract() == 0.0 => Ok(number as usize),
+            _ => expected_parameter_type!(function_name.to_string(), "Sayı".to_string())
+        }
+    }
+
+    fn get_text(function_name: &str, object: &VmObject) -> Result<String, KaramelErrorType> {
+        match &*object.deref() {
+            KaramelPrimative::Text(text) => Ok(text.to_string()),
+            _ => expected_parameter_type!(function_name.to_string(), "Yazı".to_string())
+        }
+    }
+
+    fn get_type(function_name: &str, object: &VmObject) -> Result<ForeignType, KaramelErrorType> {
+        let name = Self::get_text(function_name, object)?;
+        ForeignType::from_name(&name).ok_or(KaramelErrorType::ForeignTypeNotSupported(name))
+    }
+
+    /// Opens the shared library ('.so', '.dylib' or '.dll') and returns its handle.
+    pub fn open(parameter: FunctionParameter) -> NativeCallResult {
+        if parameter.length() != 1 {
+            return n_parameter_expected!("aç".to_string(), 1, parameter.length());
+        }
+
+        let path = Self::get_text("aç", parameter.iter().next().unwrap())?;
+        Ok(VmObject::from(ffi::load_library(Self::get_context(&parameter)?, &path)? as f64))
+    }
+
+    /// Parameters are the library handle, function name, list of the argument types and the return type. Returns the handle of the function.
+    pub fn declare(parameter: FunctionParameter) -> NativeCallResult {
+        if parameter.length() != 4 {
+            return n_parameter_expected!("tanımla".to_string(), 4, parameter.length());
+        }
+
+        let mut iter = parameter.iter();
+        let library = Self::get_number("tanımla", iter.next().unwrap())?;
+        let name = Self::get_text("tanımla", iter.next().unwrap())?;
+        let arguments = match &*iter.next().unwrap().deref() {
+            KaramelPrimative::List(items) => items.borrow().iter().map(|item| Self::get_type("tanımla", item)).collect::<Result<Vec<_>, _>>()?,
+            _ => return expected_parameter_type!("tanımla".to_string(), "Liste".to_string())
+        };
+        let return_type = Self::get_type("tanımla", iter.next().unwrap())?;
+
+        Ok(VmObject::from(ffi::declare_function(Self::get_context(&parameter)?, library, &name, arguments, return_type)? as f64))
+    }
+
+    /// First parameter is the function handle, others are passed to the function.
+    pub fn call(parameter: FunctionParameter) -> NativeCallResult {
+        if parameter.length() == 0 {
+            return n_parameter_expected!("çağır".to_string(), 1, parameter.length());
+        }
+
+        let arguments: Vec<VmObject> = parameter.iter().copied().collect();
+        let function = Self::get_number("çağır", &arguments[0])?;
+        ffi::call_function(Self::get_context(&parameter)?, function, &arguments[1..])
+    }
+}
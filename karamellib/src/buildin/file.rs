@@ -0,0 +1,115 @@
+use crate::compiler::function::{FunctionParameter, FunctionReference, NativeCall, NativeCallResult};
+use crate::types::VmObject;
+use crate::compiler::value::KaramelPrimative;
+use crate::compiler::value::EMPTY_OBJECT;
+use crate::error::KaramelErrorType;
+use crate::buildin::{Module, Class, Permission};
+use crate::{n_parameter_expected, expected_parameter_type};
+use std::{cell::RefCell, collections::HashMap};
+use std::rc::Rc;
+
+pub struct FileModule {
+    methods: RefCell<HashMap<String, Rc<FunctionReference>>>,
+    path: Vec<String>
+}
+
+impl Module for FileModule {
+    fn get_module_name(&self) -> String {
+        "dosya".to_string()
+    }
+
+    fn get_path(&self) -> &Vec<String> {
+        &self.path
+    }
+
+    fn get_method(&self, name: &str) -> Option<Rc<FunctionReference>> {
+        self.methods.borrow().get(name).map(|method| method.clone())
+    }
+
+    fn get_module(&self, _: &str) -> Option<Rc<dyn Module>> {
+        None
+    }
+
+    fn get_methods(&self) -> Vec<Rc<FunctionReference>> {
+        let mut response = Vec::new();
+        self.methods.borrow().iter().for_each(|(_, reference)| response.push(reference.clone()));
+        response
+    }
+
+    fn get_modules(&self) -> HashMap<String, Rc<dyn Module>> {
+        HashMap::new()
+    }
+
+    fn get_classes(&self) -> Vec<Rc<dyn Class>> {
+        Vec::new()
+    }
+
+    fn get_permission(&self) -> Option<Permission> {
+        Some(Permission::File)
+    }
+}
+
+impl FileModule {
+    pub fn new() -> Rc<FileModule> {
+        let module = FileModule {
+            methods: RefCell::new(HashMap::new()),
+            path: vec!["dosya".to_string()]
+        };
+
+        let rc_module = Rc::new(module);
+        rc_module.methods.borrow_mut().insert("oku".to_string(), FunctionReference::native_function(Self::read as NativeCall, "oku".to_string(), rc_module.clone()));
+        rc_module.methods.borrow_mut().insert("yaz".to_string(), FunctionReference::native_function(Self::write as NativeCall, "yaz".to_string(), rc_module.clone()));
+        rc_module.methods.borrow_mut().insert("listele".to_string(), FunctionReference::native_function(Self::list as NativeCall, "listele".to_string(), rc_module.clone()));
+        rc_module.methods.borrow_mut().insert("varmı".to_string(), FunctionReference::native_function(Self::exists as NativeCall, "varmı".to_string(), rc_module.clone()));
+        rc_module.methods.borrow_mut().insert("varmi".to_string(), FunctionReference::native_function(Self::exists as NativeCall, "varmi".to_string(), rc_module.clone()));
+        rc_module.clone()
+    }
+
+    fn get_text(function_name: &str, object: &VmObject) -> Result<String, KaramelErrorType> {
+        match &*object.deref() {
+            KaramelPrimative::Text(text) => Ok(text.to_string()),
+            _ => expected_parameter_type!(function_name.to_string(), "Yazı".to_string())
+        }
+    }
+
+    pub fn read(parameter: FunctionParameter) -> NativeCallResult {
+        if parameter.length() != 1 {
+            return n_parameter_expected!("oku".to_string(), 1, parameter.length());
+        }
+
+        let path = Self::get_text("oku", parameter.iter().next().unwrap())?;
+        Ok(VmObject::from(parameter.file_system().read(&path)?))
+    }
+
+    pub fn write(parameter: FunctionParameter) -> NativeCallResult {
+        if parameter.length() != 2 {
+            return n_parameter_expected!("yaz".to_string(), 2, parameter.length());
+        }
+
+        let mut iter = parameter.iter();
+        let path = Self::get_text("yaz", iter.next().unwrap())?;
+        let content = Self::get_text("yaz", iter.next().unwrap())?;
+        parameter.file_system().write(&path, &content)?;
+        Ok(EMPTY_OBJECT)
+    }
+
+    pub fn list(parameter: FunctionParameter) -> NativeCallResult {
+        let path = match parameter.length() {
+            0 => ".".to_string(),
+            1 => Self::get_text("listele", parameter.iter().next().unwrap())?,
+            _ => return n_parameter_expected!("listele".to_string(), 1, parameter.length())
+        };
+
+        let names = parameter.file_system().list(&path)?.into_iter().map(VmObject::from).collect::<Vec<VmObject>>();
+        Ok(VmObject::from(names))
+    }
+
+    pub fn exists(parameter: FunctionParameter) -> NativeCallResult {
+        if parameter.length() != 1 {
+            return n_parameter_expected!("varmı".to_string(), 1, parameter.length());
+        }
+
+        let path = Self::get_text("varmı", parameter.iter().next().unwrap())?;
+        Ok(VmObject::from(parameter.file_system().is_file(&path)))
+    }
+}
@@ -1,10 +1,13 @@
 use crate::compiler::{function::{FunctionParameter, FunctionReference, NativeCall, NativeCallResult}};
 use crate::types::{VmObject};
-use crate::compiler::value::EMPTY_OBJECT;
+use crate::compiler::value::{EMPTY_OBJECT, print_precision, set_print_precision};
+use crate::compiler::KaramelPrimative;
+use crate::constants::KARAMEL_MAX_PRINT_PRECISION;
+use crate::error::KaramelErrorType;
+use crate::{n_parameter_expected, expected_parameter_type};
 use crate::buildin::{Module, Class};
 use std::{cell::RefCell, collections::HashMap};
 use std::rc::Rc;
-use std::io;
 
 use log;
 
@@ -62,14 +65,14 @@ impl IoModule  {
         rc_module.methods.borrow_mut().insert("satiryaz".to_string(), FunctionReference::native_function(Self::printline as NativeCall, "satiryaz".to_string(), rc_module.clone()));
         rc_module.methods.borrow_mut().insert("biçimlendir".to_string(), FunctionReference::native_function(Self::format as NativeCall, "biçimlendir".to_string(), rc_module.clone()));
         rc_module.methods.borrow_mut().insert("bicimlendir".to_string(), FunctionReference::native_function(Self::format as NativeCall, "bicimlendir".to_string(), rc_module.clone()));
+        rc_module.methods.borrow_mut().insert("hassasiyet".to_string(), FunctionReference::native_function(Self::precision as NativeCall, "hassasiyet".to_string(), rc_module.clone()));
         rc_module.clone()
     }
 
-    pub fn readline(_: FunctionParameter) -> NativeCallResult {        
-        let mut line = String::new();
-        match io::stdin().read_line(&mut line) {
-            Ok(_) => return Ok(VmObject::from(Rc::new(line.trim().to_string()))),
-            _ => Ok(EMPTY_OBJECT)
+    pub fn readline(parameter: FunctionParameter) -> NativeCallResult {
+        match parameter.read_line() {
+            Some(line) => Ok(VmObject::from(Rc::new(line.trim().to_string()))),
+            None => Ok(EMPTY_OBJECT)
         }
     }
 
@@ -105,4 +108,18 @@ impl IoModule  {
 
         Ok(VmObject::from(Rc::new(format!("{}", parameter.iter().next().unwrap().deref()))))
     }
+    /// Changes the significant digit count of the printed numbers. Returns the previous value.
+    pub fn precision(parameter: FunctionParameter) -> NativeCallResult {
+        if parameter.length() != 1 {
+            return n_parameter_expected!("hassasiyet".to_string(), 1, parameter.length());
+        }
+
+        let previous = print_precision();
+        match &*parameter.iter().next().unwrap().deref() {
+            KaramelPrimative::Number(precision) if *precision >= 1.0 && *precision <= KARAMEL_MAX_PRINT_PRECISION as f64 && precision.fract() == 0.0 => set_print_precision(*precision as usize),
+            _ => return expected_parameter_type!("hassasiyet".to_string(), format!("1 ile {} arasında tam sayı", KARAMEL_MAX_PRINT_PRECISION))
+        };
+
+        Ok(VmObject::from(previous as f64))
+    }
 }
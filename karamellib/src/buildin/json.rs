@@ -0,0 +1,239 @@
+use crate::types::VmObject;
+use crate::compiler::value::KaramelPrimative;
+use crate::compiler::value::EMPTY_OBJECT;
+use crate::compiler::{GetType, DictKey};
+use crate::error::KaramelErrorType;
+use crate::error::diagnostic::encode_text;
+use std::collections::HashMap;
+use std::rc::Rc;
+use std::iter::Peekable;
+use std::str::Chars;
+
+/* Values are written as JSON. Used by the store module and the event stream. */
+pub fn encode(value: VmObject, buffer: &mut String) -> Result<(), KaramelErrorType> {
+    encode_value(value, buffer, &mut Vec::new())
+}
+
+/* Lists and dicts that are being written are kept in 'parents', a value that contains itself can not be written */
+fn encode_value(value: VmObject, buffer: &mut String, parents: &mut Vec<*const KaramelPrimative>) -> Result<(), KaramelErrorType> {
+    let source = value.deref();
+    if let KaramelPrimative::List(_) | KaramelPrimative::Dict(_) = &*source {
+        if parents.contains(&Rc::as_ptr(&source)) {
+            return Err(KaramelErrorType::GeneralError("Kendini içeren liste ve sözlükler depoya kaydedilemez".to_string()));
+        }
+        parents.push(Rc::as_ptr(&source));
+    }
+
+    match &*source {
+        KaramelPrimative::Empty => buffer.push_str("null"),
+        KaramelPrimative::Bool(true) => buffer.push_str("true"),
+        KaramelPrimative::Bool(false) => buffer.push_str("false"),
+        KaramelPrimative::Number(number) if !number.is_finite() => return Err(KaramelErrorType::GeneralError(format!("'{}' sayısı depoya kaydedilemez", number))),
+        KaramelPrimative::Number(number) => buffer.push_str(&number.to_string()),
+        KaramelPrimative::Text(text) => encode_text(text, buffer),
+        KaramelPrimative::List(list) => {
+            buffer.push('[');
+            for (index, item) in list.borrow().iter().enumerate() {
+                if index > 0 {
+                    buffer.push_str(", ");
+                }
+                encode_value(*item, buffer, parents)?;
+            }
+            buffer.push(']');
+            parents.pop();
+        },
+        KaramelPrimative::Dict(dict) => {
+            let dict = dict.borrow();
+            let mut keys = dict.keys().collect::<Vec<&DictKey>>();
+            keys.sort();
+
+            buffer.push('{');
+            for (index, key) in keys.iter().enumerate() {
+                if index > 0 {
+                    buffer.push_str(", ");
+                }
+                match key.as_text() {
+                    Some(text) => encode_text(text, buffer),
+                    None => return Err(KaramelErrorType::GeneralError(format!("'{:?}' anahtarı depoya kaydedilemez", key)))
+                };
+                buffer.push_str(": ");
+                encode_value(dict[*key], buffer, parents)?;
+            }
+            buffer.push('}');
+            parents.pop();
+        },
+        KaramelPrimative::Frozen(inner) => encode_value(*inner, buffer, parents)?,
+        primative => return Err(KaramelErrorType::GeneralError(format!("'{}' türü depoya kaydedilemez", primative.get_type())))
+    };
+    Ok(())
+}
+
+fn skip_whitespace(iter: &mut Peekable<Chars>) {
+    while let Some(chr) = iter.peek() {
+        if !chr.is_whitespace() {
+            break;
+        }
+        iter.next();
+    }
+}
+
+fn expect_word(iter: &mut Peekable<Chars>, word: &str) -> Option<()> {
+    for expected in word.chars() {
+        if iter.next()? != expected {
+            return None;
+        }
+    }
+    Some(())
+}
+
+fn decode_text(iter: &mut Peekable<Chars>) -> Option<String> {
+    let mut text = String::new();
+    iter.next()?;
+
+    loop {
+        match iter.next()? {
+            '"' => return Some(text),
+            '\\' => match iter.next()? {
+                'n' => text.push('\n'),
+                'r' => text.push('\r'),
+                't' => text.push('\t'),
+                'u' => {
+                    let code = (0..4).map(|_| iter.next()).collect::<Option<String>>()?;
+                    text.push(std::char::from_u32(u32::from_str_radix(&code, 16).ok()?)?);
+                },
+                chr => text.push(chr)
+            },
+            chr => text.push(chr)
+        };
+    }
+}
+
+pub fn decode(iter: &mut Peekable<Chars>) -> Option<VmObject> {
+    skip_whitespace(iter);
+    let value = match iter.peek()? {
+        'n' => expect_word(iter, "null").map(|_| EMPTY_OBJECT)?,
+        't' => expect_word(iter, "true").map(|_| VmObject::from(true))?,
+        'f' => expect_word(iter, "false").map(|_| VmObject::from(false))?,
+        '"' => VmObject::from(decode_text(iter)?),
+        '[' => {
+            iter.next();
+            let mut list = Vec::new();
+            skip_whitespace(iter);
+            if iter.peek() == Some(&']') {
+                iter.next();
+            } else {
+                loop {
+                    list.push(decode(iter)?);
+                    skip_whitespace(iter);
+                    match iter.next()? {
+                        ',' => continue,
+                        ']' => break,
+                        _ => return None
+                    };
+                }
+            }
+            VmObject::from(list)
+        },
+        '{' => {
+            iter.next();
+            let mut dict = HashMap::new();
+            skip_whitespace(iter);
+            if iter.peek() == Some(&'}') {
+                iter.next();
+            } else {
+                loop {
+                    skip_whitespace(iter);
+                    let key = decode_text(iter)?;
+                    skip_whitespace(iter);
+                    if iter.next()? != ':' {
+                        return None;
+                    }
+                    dict.insert(DictKey::from(key), decode(iter)?);
+                    skip_whitespace(iter);
+                    match iter.next()? {
+                        ',' => continue,
+                        '}' => break,
+                        _ => return None
+                    };
+                }
+            }
+            VmObject::from(dict)
+        },
+        _ => {
+            let mut number = String::new();
+            while let Some(chr) = iter.peek() {
+                match chr {
+                    '0'..='9' | '-' | '+' | '.' | 'e' | 'E' => number.push(*chr),
+                    _ => break
+                };
+                iter.next();
+            }
+            VmObject::from(number.parse::<f64>().ok()?)
+        }
+    };
+    Some(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+    use std::collections::HashMap;
+    use std::rc::Rc;
+    use crate::compiler::value::KaramelPrimative;
+    use super::*;
+
+    use crate::primative_text;
+    use crate::primative_list;
+    use crate::arc_text;
+    use crate::arc_number;
+    use crate::arc_bool;
+    use crate::arc_empty;
+
+    fn round_trip(value: VmObject) -> Rc<KaramelPrimative> {
+        let mut buffer = String::new();
+        encode(value, &mut buffer).unwrap();
+        decode(&mut buffer.chars().peekable()).unwrap().deref()
+    }
+
+    #[test]
+    fn test_encode_decode() {
+        assert_eq!(*round_trip(arc_number!(1024)), KaramelPrimative::Number(1024.0));
+        assert_eq!(*round_trip(arc_number!(-1.5)), KaramelPrimative::Number(-1.5));
+        assert_eq!(*round_trip(arc_text!("merhaba \"dünya\"\n")), primative_text!("merhaba \"dünya\"\n"));
+        assert_eq!(*round_trip(arc_bool!(true)), KaramelPrimative::Bool(true));
+        assert_eq!(*round_trip(arc_empty!()), KaramelPrimative::Empty);
+        assert_eq!(*round_trip(VmObject::from(vec![arc_number!(1), arc_text!("iki")])), primative_list!(vec![arc_number!(1), arc_text!("iki")]));
+
+        let mut dict = HashMap::new();
+        dict.insert(DictKey::from("ad"), arc_text!("erhan"));
+        dict.insert(DictKey::from("liste"), VmObject::from(Vec::new()));
+        assert_eq!(*round_trip(VmObject::from(dict.clone())), KaramelPrimative::Dict(RefCell::new(dict)));
+    }
+
+    #[test]
+    fn test_invalid_values() {
+        let mut buffer = String::new();
+        for number in [f64::INFINITY, f64::NEG_INFINITY, f64::NAN].iter() {
+            assert!(encode(VmObject::from(vec![arc_number!(1), VmObject::from(*number)]), &mut buffer).is_err());
+        }
+
+        let list = VmObject::from(vec![arc_number!(1)]);
+        if let KaramelPrimative::List(items) = &*list.deref() {
+            items.borrow_mut().push(list);
+        }
+        assert!(encode(list, &mut buffer).is_err());
+
+        /* Same list can be written more than once when it does not contain itself */
+        let item = VmObject::from(vec![arc_number!(1)]);
+        let mut buffer = String::new();
+        encode(VmObject::from(vec![item, item]), &mut buffer).unwrap();
+        assert_eq!(buffer, "[[1], [1]]");
+    }
+
+    #[test]
+    fn test_invalid_data() {
+        assert!(decode(&mut "[1, 2".chars().peekable()).is_none());
+        assert!(decode(&mut "{'a': 1}".chars().peekable()).is_none());
+        assert!(decode(&mut "yanlış".chars().peekable()).is_none());
+    }
+}
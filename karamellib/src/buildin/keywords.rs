@@ -0,0 +1,103 @@
+use crate::types::{KaramelKeywordType, KEYWORDS};
+
+/* (Keyword, explanation, example) */
+static KEYWORD_DOCUMENTATIONS: &[(KaramelKeywordType, &str, &str)] = &[
+    (KaramelKeywordType::True,     "Mantıksal doğru değeridir. Koşulların sonucu doğru ya da yanlış olur.",
+                                   "geçti = doğru"),
+    (KaramelKeywordType::False,    "Mantıksal yanlış değeridir. Koşulların sonucu doğru ya da yanlış olur.",
+                                   "geçti = yanlış"),
+    (KaramelKeywordType::Empty,    "Değeri olmayan değişkenleri göstermek için kullanılır.",
+                                   "sonuç = boş"),
+    (KaramelKeywordType::If,       "Koşul doğru ise altındaki kodları çalıştırır. Koşul yanlış ise 'veya' ile başlayan kodlara geçilir.",
+                                   "not = 70\nnot >= 50 ise:\n    gç::satıryaz('Geçti')\nveya:\n    gç::satıryaz('Kaldı')"),
+    (KaramelKeywordType::And,      "İki koşulun da doğru olup olmadığını kontrol eder. İlk koşul yanlış ise ikinci koşul hiç çalıştırılmaz.",
+                                   "yaş = 20\nyaş > 18 ve yaş < 65 ise:\n    gç::satıryaz('Çalışabilir')"),
+    (KaramelKeywordType::Or,       "İki koşuldan birinin doğru olup olmadığını kontrol eder. İlk koşul doğru ise ikinci koşul hiç çalıştırılmaz. 'ise' ile birlikte diğer koşulları yazmak için de kullanılır.",
+                                   "not = 40\nnot > 50 ise:\n    gç::satıryaz('Geçti')\nveya not > 30 ise:\n    gç::satıryaz('Bütünlemeye kaldı')\nveya:\n    gç::satıryaz('Kaldı')"),
+    (KaramelKeywordType::Not,      "Koşulun tersini alır. Doğru olan yanlış, yanlış olan doğru olur.",
+                                   "yağmurlu = yanlış\ndeğil yağmurlu ise:\n    gç::satıryaz('Dışarı çık')"),
+    (KaramelKeywordType::Modulo,   "Bölme işleminden kalanı verir.",
+                                   "kalan = 7 mod 2\ngç::satıryaz(kalan)"),
+    (KaramelKeywordType::Fn,       "Yeni bir fonksiyon tanımlar. Parametreler parantez içinde yazılır.",
+                                   "fonk kare(x):\n    döndür x * x\ngç::satıryaz(kare(4))"),
+    (KaramelKeywordType::Return,   "Fonksiyonu sonlandırır ve verilen değeri fonksiyonu çağırana geri gönderir.",
+                                   "fonk topla(a, b):\n    döndür a + b\ngç::satıryaz(topla(1, 2))"),
+    (KaramelKeywordType::While,    "Koşul doğru olduğu sürece altındaki kodları tekrar tekrar çalıştırır.",
+                                   "sayaç = 0\ndöngü sayaç < 3:\n    gç::satıryaz(sayaç)\n    sayaç += 1"),
+    (KaramelKeywordType::Endless,  "Hiç bitmeyen bir döngü başlatır. Döngüden 'kır' ile çıkılır.",
+                                   "sayaç = 0\nsonsuz:\n    sayaç += 1\n    sayaç == 3 ise:\n        kır"),
+    (KaramelKeywordType::Break,    "İçinde bulunulan döngüyü hemen sonlandırır.",
+                                   "sayaç = 0\ndöngü sayaç < 10:\n    sayaç += 1\n    sayaç == 5 ise:\n        kır"),
+    (KaramelKeywordType::Continue, "Döngünün geri kalanını atlar ve bir sonraki tura geçer.",
+                                   "sayaç = 0\ndöngü sayaç < 5:\n    sayaç += 1\n    sayaç mod 2 == 0 ise:\n        devam\n    gç::satıryaz(sayaç)"),
+    (KaramelKeywordType::Load,     "Başka bir dosyada yazılmış modülü programa ekler.",
+                                   "hesapmakinesi yükle"),
+    (KaramelKeywordType::Try,      "Altındaki kodlarda oluşan hataları yakalar. Hata oluşursa 'yakala' bloğuna geçilir.",
+                                   "dene:\n    sonuç = sayı::biçimle(5, 50)\nyakala hata:\n    gç::satıryaz(hata.mesaj())"),
+    (KaramelKeywordType::Catch,    "'dene' bloğunda oluşan hatayı yakalar. Hata, yazılan isimdeki değişkene atanır. Hatanın kod(), mesaj() ve veri() bilgileri vardır.",
+                                   "dene:\n    fırlat 'Bir sorun oluştu'\nyakala hata:\n    gç::satıryaz(hata.mesaj())"),
+    (KaramelKeywordType::Throw,    "Hata oluşturur ve çalışmayı en yakın 'yakala' bloğuna taşır. Hata yakalanmazsa program sonlanır.",
+                                   "fonk kontrol(b):\n    b == 0 ise:\n        fırlat baz::hata(1, 'Sıfıra bölünemez')\n    döndür b\ngç::satıryaz(kontrol(2))"),
+    (KaramelKeywordType::Defer,    "Altındaki kodları fonksiyon bittiğinde çalıştırır. Fonksiyon hata ile bitse de çalışır. Birden fazla 'ertele' bloğu yazılmış ise son yazılan ilk çalışır.",
+                                   "fonk kaydet(kayıtlar):\n    ertele:\n        gç::satıryaz('Kayıt bitti')\n    kayıtlar.ekle(1)\n    döndür kayıtlar\ngç::satıryaz(kaydet([]))"),
+    (KaramelKeywordType::With,     "Değeri 'olarak' ile yazılan değişkene atar ve blok bittiğinde değerin 'kapat' fonksiyonunu çağırır. Blok 'döndür' ya da hata ile bitse de 'kapat' çağrılır.",
+                                   "fonk kapat():\n    gç::satıryaz('Kapatıldı')\nbirlikte {'kapat': kapat} olarak kaynak:\n    gç::satıryaz('Çalışıyor')"),
+    (KaramelKeywordType::Assume,   "Koşulun doğru olduğunu kontrol eder, koşul yanlış ise hata oluşturur. Virgülden sonra hatanın mesajı yazılabilir. Sadece '--varsay' ile çalıştırıldığında kontrol edilir, diğer durumlarda satır hiç çalıştırılmaz.",
+                                   "fonk ortalama(notlar):\n    varsay notlar.uzunluk() > 0, 'Not listesi boş olamaz'\n    döndür notlar.getir(0)\ngç::satıryaz(ortalama([90]))"),
+    (KaramelKeywordType::Constant, "Değeri değiştirilemeyen bir değişken tanımlar. Aynı fonksiyon içinde değişkene tekrar değer atanırsa program derlenmez.",
+                                   "sabit PI = 3.14\nyarıçap = 2\ngç::satıryaz(PI * yarıçap * yarıçap)"),
+    (KaramelKeywordType::Local,    "İçinde bulunduğu 'ise', 'veya' ya da 'döngü' bloğuna ait bir değişken tanımlar. Değişken blok bittiğinde kullanılamaz, dışarıdaki aynı isimdeki değişkeni değiştirmez.",
+                                   "toplam = 0\ndöngü i = 0, i < 3, ++i:\n    yerel kare = i * i\n    toplam += kare\ngç::satıryaz(toplam)"),
+    (KaramelKeywordType::Enum,     "İsimlendirilmiş seçeneklerden oluşan bir tür tanımlar. Seçenekler 'Tür::Seçenek' şeklinde kullanılır ve sadece kendileri ile eşittir.",
+                                   "seçenekler Renk: Kırmızı, Yeşil, Mavi\nrenk = Renk::Yeşil\nrenk == Renk::Yeşil ise:\n    gç::satıryaz('Yeşil')"),
+    (KaramelKeywordType::Private,  "Fonksiyonu gizler. Gizli fonksiyonlar sadece tanımlandıkları dosyada kullanılabilir, dosyayı 'yükle' ile ekleyen kodlar çağıramaz.",
+                                   "gizli fonk kare(sayı_1):\n    döndür sayı_1 * sayı_1\ngç::satıryaz(kare(4))"),
+    (KaramelKeywordType::Use,      "Modülü programa ekler. 'olarak' ile modüle kısa bir ad verilebilir, '{}' içinde yazılan fonksiyonlar modülün adı yazılmadan çağrılabilir.",
+                                   "kullan gç::{satıryaz}\nsatıryaz('Merhaba')"),
+    (KaramelKeywordType::As,       "'kullan' ile eklenen modüle kısa bir ad verir. Modülün fonksiyonları bu ad ile çağrılır. 'birlikte' bloğunda değerin atanacağı değişkeni yazmak için de kullanılır.",
+                                   "kullan gç olarak g\ng::satıryaz('Merhaba')"),
+    (KaramelKeywordType::Until,    "İleride kullanılmak üzere ayrılmış kelimedir. Değişken ya da fonksiyon adı olarak kullanılamaz.", ""),
+    (KaramelKeywordType::Else,     "İleride kullanılmak üzere ayrılmış kelimedir. Koşul sağlanmadığında çalışacak kodlar 'veya:' ile yazılır.", "")
+];
+
+/// Short Turkish explanation and example of the keyword. ASCII spellings of the keywords are also accepted.
+pub fn keyword_documentation(name: &str) -> Option<String> {
+    let (_, keyword_type) = KEYWORDS.iter().find(|(keyword, _)| *keyword == name.trim())?;
+    let (_, explanation, example) = KEYWORD_DOCUMENTATIONS.iter().find(|(documented, _, _)| documented == keyword_type)?;
+
+    let mut documentation = format!("{}: {}", name.trim(), explanation);
+    if !example.is_empty() {
+        documentation.push_str("\nÖrnek:");
+        for line in example.lines() {
+            documentation.push_str("\n    ");
+            documentation.push_str(line);
+        }
+    }
+    Some(documentation)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{keyword_documentation, KEYWORD_DOCUMENTATIONS};
+    use crate::types::{KaramelKeywordType, KEYWORDS};
+    use crate::vm::executer::code_compiler;
+
+    #[test]
+    fn all_keywords_documented() {
+        for (keyword, _) in KEYWORDS.iter() {
+            assert!(keyword_documentation(keyword).is_some(), "'{}' açıklaması bulunamadı", keyword);
+        }
+
+        assert_eq!(keyword_documentation("değişken"), None);
+        assert_eq!(keyword_documentation("dongu"), keyword_documentation("döngü").map(|text| text.replacen("döngü", "dongu", 1)));
+    }
+
+    #[test]
+    fn examples_compile() {
+        for (keyword_type, _, example) in KEYWORD_DOCUMENTATIONS.iter() {
+            if !example.is_empty() && *keyword_type != KaramelKeywordType::Load {
+                assert!(code_compiler(*example).is_ok(), "{}", example);
+            }
+        }
+    }
+}
@@ -1,7 +1,22 @@
+#[cfg(feature = "modules")]
 pub mod debug;
+#[cfg(feature = "modules")]
 pub mod io;
+#[cfg(feature = "modules")]
 pub mod num;
 pub mod base_functions;
+#[cfg(feature = "modules")]
+pub mod store;
+#[cfg(feature = "modules")]
+pub mod constants;
+#[cfg(feature = "modules")]
+pub mod file;
+#[cfg(feature = "modules")]
+pub mod task;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+pub mod json;
+pub mod keywords;
 
 use std::collections::hash_map::Iter;
 
@@ -15,6 +30,7 @@ use std::vec::Vec;
 use std::rc::Rc;
 
 use crate::compiler::{KaramelPrimative, function::{FunctionReference, NativeCall}};
+use crate::interner::intern;
 
 pub trait Module {
     fn get_module_name(&self) -> String;
@@ -27,6 +43,40 @@ pub trait Module {
     fn get_modules(&self) -> HashMap<String, Rc<dyn Module>>;
 
     fn get_classes(&self) -> Vec<Rc<dyn Class>>;
+
+    fn get_constant(&self, _: &str) -> Option<Rc<KaramelPrimative>> { None }
+    fn get_constants(&self) -> HashMap<String, Rc<KaramelPrimative>> { HashMap::new() }
+
+    /// Permission that the embedder should give before the code can use the module
+    fn get_permission(&self) -> Option<Permission> { None }
+}
+
+/// Capabilities of the built-in modules that reach outside of the vm. Embedders can allow only some of them,
+/// modules of the other permissions can not be used by the code.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Permission {
+    /// Reading and writing the files, 'dosya' and 'depo' modules
+    File,
+
+    /// Loading the native libraries, 'ffi' module
+    Ffi
+}
+
+impl Permission {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Permission::File => "dosya",
+            Permission::Ffi => "ffi"
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Permission> {
+        match name {
+            "dosya" => Some(Permission::File),
+            "ffi" => Some(Permission::Ffi),
+            _ => None
+        }
+    }
 }
 
 pub struct ModuleCollectionIterator<'a> {
@@ -49,6 +99,10 @@ impl ModuleCollection
         self.modules.insert(module.get_module_name(), module);
     }
 
+    pub fn remove_module(&mut self, module_path: &[String]) {
+        self.modules.retain(|_, module| module.get_path() != module_path);
+    }
+
     pub fn iter(&self) -> ModuleCollectionIterator {
         ModuleCollectionIterator  { 
             iter: self.modules.iter().clone()
@@ -109,6 +163,15 @@ pub trait Class: GetType {
     
     fn set_setter(&mut self, indexer: IndexerSetCall);
     fn get_setter(&self) -> Option<IndexerSetCall>;
+
+    /// Function that the language calls by its name, like 'kapat' at the end of the 'birlikte' block.
+    /// Fields are accepted when their value is a function, dictionaries keep their functions as fields.
+    fn get_protocol_method(&self, source: Option<VmObject>, name: &str) -> Option<ClassProperty> {
+        match self.get_element(source, intern(name))? {
+            ClassProperty::Field(field) if !matches!(&*field, KaramelPrimative::Function(_, _) | KaramelPrimative::Bound(_)) => None,
+            property => Some(property)
+        }
+    }
 }
 
 pub struct DummyModule {
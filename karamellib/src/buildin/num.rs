@@ -4,7 +4,7 @@ use crate::compiler::value::KaramelPrimative;
 use crate::compiler::value::EMPTY_OBJECT;
 use crate::error::KaramelErrorType;
 use crate::buildin::{Module, Class};
-use crate::{n_parameter_expected, expected_parameter_type};
+use crate::{n_parameter_expected, expected_parameter_type, arc_text, primative_text};
 use std::{cell::RefCell, collections::HashMap};
 use std::rc::Rc;
 
@@ -57,6 +57,8 @@ impl NumModule {
 
         let rc_module = Rc::new(module);
         rc_module.methods.borrow_mut().insert("oku".to_string(), FunctionReference::native_function(Self::parse as NativeCall, "tür_bilgisi".to_string(), rc_module.clone()));
+        rc_module.methods.borrow_mut().insert("biçimle".to_string(), FunctionReference::native_function(Self::format as NativeCall, "biçimle".to_string(), rc_module.clone()));
+        rc_module.methods.borrow_mut().insert("yazıya_çevir".to_string(), FunctionReference::native_function(Self::to_text as NativeCall, "yazıya_çevir".to_string(), rc_module.clone()));
         rc_module.clone()
     }
 
@@ -81,4 +83,129 @@ impl NumModule {
             _ => Ok(EMPTY_OBJECT)
         }
     }
+    /* Number, digit count and locale parameters of the formatting functions */
+    fn format_parameters(function_name: &str, parameter: &FunctionParameter, locale_index: usize) -> Result<(f64, NumberLocale), KaramelErrorType> {
+        let mut arguments = parameter.iter();
+        let number = match &*arguments.next().unwrap().deref() {
+            KaramelPrimative::Number(number) => *number,
+            _ => return expected_parameter_type!(function_name.to_string(), "Sayı".to_string())
+        };
+
+        let locale = match arguments.nth(locale_index - 1).map(|locale| locale.deref()) {
+            Some(locale) => match &*locale {
+                KaramelPrimative::Text(locale) => NumberLocale::from_name(locale).ok_or_else(|| KaramelErrorType::FunctionExpectedThatParameterType {
+                    function: function_name.to_string(),
+                    expected: "'tr' ya da 'en'".to_string()
+                })?,
+                _ => return expected_parameter_type!(function_name.to_string(), "Yazı".to_string())
+            },
+            None => NumberLocale::Turkish
+        };
+
+        Ok((number, locale))
+    }
+
+    pub fn format(parameter: FunctionParameter) -> NativeCallResult {
+        if parameter.length() != 2 && parameter.length() != 3 {
+            return n_parameter_expected!("biçimle".to_string(), 2, parameter.length());
+        }
+
+        let (number, locale) = Self::format_parameters("biçimle", &parameter, 2)?;
+        let digits = match &*parameter.iter().nth(1).unwrap().deref() {
+            KaramelPrimative::Number(digits) if *digits >= 0.0 && *digits <= MAX_FORMAT_DIGITS && digits.fract() == 0.0 => *digits as usize,
+            _ => return expected_parameter_type!("biçimle".to_string(), "0 ile 20 arasında tam sayı".to_string())
+        };
+
+        Ok(arc_text!(format_number(number, Some(digits), locale)))
+    }
+
+    pub fn to_text(parameter: FunctionParameter) -> NativeCallResult {
+        if parameter.length() != 1 && parameter.length() != 2 {
+            return n_parameter_expected!("yazıya_çevir".to_string(), 1, parameter.length());
+        }
+
+        let (number, locale) = Self::format_parameters("yazıya_çevir", &parameter, 1)?;
+        Ok(arc_text!(format_number(number, None, locale)))
+    }
+}
+
+const MAX_FORMAT_DIGITS: f64 = 20.0;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum NumberLocale {
+    /// 1.234,5
+    Turkish,
+
+    /// 1,234.5
+    English
+}
+
+impl NumberLocale {
+    pub fn from_name(name: &str) -> Option<NumberLocale> {
+        match name {
+            "tr" => Some(NumberLocale::Turkish),
+            "en" => Some(NumberLocale::English),
+            _ => None
+        }
+    }
+
+    fn separators(&self) -> (char, char) {
+        match self {
+            NumberLocale::Turkish => ('.', ','),
+            NumberLocale::English => (',', '.')
+        }
+    }
+}
+
+/// Writes the number with the thousand and decimal separators of the locale. All digits are written if the digit count is not given.
+pub fn format_number(number: f64, digits: Option<usize>, locale: NumberLocale) -> String {
+    if !number.is_finite() {
+        return number.to_string();
+    }
+
+    let text = match digits {
+        Some(digits) => format!("{:.*}", digits, number.abs()),
+        None => number.abs().to_string()
+    };
+
+    let (thousand_separator, decimal_separator) = locale.separators();
+    let (integer, fraction) = match text.split_once('.') {
+        Some((integer, fraction)) => (integer, Some(fraction)),
+        None => (text.as_str(), None)
+    };
+
+    let mut result = String::new();
+    if number < 0.0 && text.chars().any(|ch| ch.is_ascii_digit() && ch != '0') {
+        result.push('-');
+    }
+
+    for (index, ch) in integer.chars().enumerate() {
+        if index > 0 && (integer.len() - index) % 3 == 0 {
+            result.push(thousand_separator);
+        }
+        result.push(ch);
+    }
+
+    if let Some(fraction) = fraction {
+        result.push(decimal_separator);
+        result.push_str(fraction);
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{format_number, NumberLocale};
+
+    #[test]
+    fn format_number_test() {
+        assert_eq!(format_number(1234.5, Some(2), NumberLocale::Turkish), "1.234,50");
+        assert_eq!(format_number(1234.5, Some(2), NumberLocale::English), "1,234.50");
+        assert_eq!(format_number(-1234567.0, Some(0), NumberLocale::Turkish), "-1.234.567");
+        assert_eq!(format_number(-0.001, Some(2), NumberLocale::Turkish), "0,00");
+        assert_eq!(format_number(3.14159, Some(3), NumberLocale::Turkish), "3,142");
+        assert_eq!(format_number(0.25, None, NumberLocale::Turkish), "0,25");
+        assert_eq!(format_number(1.5e10, None, NumberLocale::Turkish), "15.000.000.000");
+        assert_eq!(format_number(999.0, None, NumberLocale::English), "999");
+    }
 }
@@ -0,0 +1,223 @@
+use crate::compiler::{function::{FunctionParameter, FunctionReference, NativeCall, NativeCallResult}};
+use crate::constants::KARAMEL_STORE_FILE;
+use crate::types::VmObject;
+use crate::compiler::value::KaramelPrimative;
+use crate::compiler::value::EMPTY_OBJECT;
+use crate::error::KaramelErrorType;
+use crate::error::diagnostic::encode_text;
+use crate::buildin::{Module, Class, Permission};
+use crate::buildin::json::{encode, decode};
+use crate::{n_parameter_expected, expected_parameter_type, arc_bool};
+use std::{cell::RefCell, collections::{BTreeMap, HashMap}};
+use std::fs;
+use std::path::Path;
+use std::rc::Rc;
+
+type StoreData = BTreeMap<String, VmObject>;
+
+pub struct StoreModule {
+    methods: RefCell<HashMap<String, Rc<FunctionReference>>>,
+    path: Vec<String>
+}
+
+impl Module for StoreModule {
+    fn get_module_name(&self) -> String {
+        "depo".to_string()
+    }
+
+    fn get_path(&self) -> &Vec<String> {
+        &self.path
+    }
+
+    fn get_method(&self, name: &str) -> Option<Rc<FunctionReference>> {
+        match self.methods.borrow().get(name) {
+            Some(method) => Some(method.clone()),
+            None => None
+        }
+    }
+
+    fn get_module(&self, _: &str) -> Option<Rc<dyn Module>> {
+        None
+    }
+
+    fn get_methods(&self) -> Vec<Rc<FunctionReference>> {
+        let mut response = Vec::new();
+        self.methods.borrow().iter().for_each(|(_, reference)| response.push(reference.clone()));
+        response
+    }
+
+    fn get_modules(&self) -> HashMap<String, Rc<dyn Module>> {
+        HashMap::new()
+    }
+
+    fn get_classes(&self) -> Vec<Rc<dyn Class>> {
+        Vec::new()
+    }
+
+    fn get_permission(&self) -> Option<Permission> {
+        Some(Permission::File)
+    }
+}
+
+impl StoreModule {
+    pub fn new() -> Rc<StoreModule> {
+        let module = StoreModule {
+            methods: RefCell::new(HashMap::new()),
+            path: vec!["depo".to_string()]
+        };
+
+        let rc_module = Rc::new(module);
+        rc_module.methods.borrow_mut().insert("al".to_string(), FunctionReference::native_function(Self::get as NativeCall, "al".to_string(), rc_module.clone()));
+        rc_module.methods.borrow_mut().insert("koy".to_string(), FunctionReference::native_function(Self::set as NativeCall, "koy".to_string(), rc_module.clone()));
+        rc_module.methods.borrow_mut().insert("sil".to_string(), FunctionReference::native_function(Self::remove as NativeCall, "sil".to_string(), rc_module.clone()));
+        rc_module.methods.borrow_mut().insert("anahtarlar".to_string(), FunctionReference::native_function(Self::keys as NativeCall, "anahtarlar".to_string(), rc_module.clone()));
+        rc_module.clone()
+    }
+
+    fn get_key(function_name: &str, object: &VmObject) -> Result<String, KaramelErrorType> {
+        match &*object.deref() {
+            KaramelPrimative::Text(text) => Ok(text.to_string()),
+            _ => expected_parameter_type!(function_name.to_string(), "Yazı".to_string())
+        }
+    }
+
+    pub fn get(parameter: FunctionParameter) -> NativeCallResult {
+        if parameter.length() != 1 {
+            return n_parameter_expected!("al".to_string(), 1, parameter.length());
+        }
+
+        let key = Self::get_key("al", parameter.iter().next().unwrap())?;
+        match load(KARAMEL_STORE_FILE)?.get(&key) {
+            Some(value) => Ok(*value),
+            None => Ok(EMPTY_OBJECT)
+        }
+    }
+
+    pub fn set(parameter: FunctionParameter) -> NativeCallResult {
+        if parameter.length() != 2 {
+            return n_parameter_expected!("koy".to_string(), 2, parameter.length());
+        }
+
+        let mut iter = parameter.iter();
+        let key = Self::get_key("koy", iter.next().unwrap())?;
+        let value = *iter.next().unwrap();
+
+        let mut data = load(KARAMEL_STORE_FILE)?;
+        data.insert(key, value);
+        save(KARAMEL_STORE_FILE, &data)?;
+        Ok(arc_bool!(true))
+    }
+
+    pub fn remove(parameter: FunctionParameter) -> NativeCallResult {
+        if parameter.length() != 1 {
+            return n_parameter_expected!("sil".to_string(), 1, parameter.length());
+        }
+
+        let key = Self::get_key("sil", parameter.iter().next().unwrap())?;
+        let mut data = load(KARAMEL_STORE_FILE)?;
+        match data.remove(&key) {
+            Some(value) => {
+                save(KARAMEL_STORE_FILE, &data)?;
+                Ok(value)
+            },
+            None => Ok(EMPTY_OBJECT)
+        }
+    }
+
+    pub fn keys(parameter: FunctionParameter) -> NativeCallResult {
+        if parameter.length() != 0 {
+            return n_parameter_expected!("anahtarlar".to_string(), 0, parameter.length());
+        }
+
+        let keys = load(KARAMEL_STORE_FILE)?.keys().map(|key| VmObject::from(key.to_string())).collect::<Vec<VmObject>>();
+        Ok(VmObject::from(keys))
+    }
+}
+
+fn load(file_name: &str) -> Result<StoreData, KaramelErrorType> {
+    if !Path::new(file_name).exists() {
+        return Ok(StoreData::new());
+    }
+
+    let content = match fs::read_to_string(file_name) {
+        Ok(content) => content,
+        Err(error) => return Err(KaramelErrorType::FileReadError {
+            filename: file_name.to_string(),
+            error: error.to_string()
+        })
+    };
+
+    let invalid_content = || KaramelErrorType::FileReadError {
+        filename: file_name.to_string(),
+        error: "Depo dosyası geçerli değil".to_string()
+    };
+
+    let mut iter = content.chars().peekable();
+    let data = match decode(&mut iter) {
+        Some(data) => data,
+        None => return Err(invalid_content())
+    };
+
+    match &*data.deref() {
+        KaramelPrimative::Dict(dict) => dict.borrow().iter().map(|(key, value)| Ok((key.as_text().ok_or_else(invalid_content)?.to_string(), *value))).collect(),
+        _ => Err(invalid_content())
+    }
+}
+
+/* Data written into temporary file first, then moved over the original file. So, store file is never half written. */
+fn save(file_name: &str, data: &StoreData) -> Result<(), KaramelErrorType> {
+    let mut content = String::new();
+    content.push('{');
+    for (index, (key, value)) in data.iter().enumerate() {
+        if index > 0 {
+            content.push_str(", ");
+        }
+        encode_text(key, &mut content);
+        content.push_str(": ");
+        encode(*value, &mut content)?;
+    }
+    content.push('}');
+
+    let temp_file_name = format!("{}.tmp", file_name);
+    let result = fs::write(&temp_file_name, content).and_then(|_| fs::rename(&temp_file_name, file_name));
+    match result {
+        Ok(_) => Ok(()),
+        Err(error) => Err(KaramelErrorType::FileWriteError {
+            filename: file_name.to_string(),
+            error: error.to_string()
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+    use std::collections::HashMap;
+    use std::rc::Rc;
+    use crate::compiler::value::KaramelPrimative;
+    use super::*;
+
+    use crate::primative_text;
+    use crate::primative_list;
+    use crate::arc_text;
+    use crate::arc_number;
+    use crate::arc_bool;
+    use crate::arc_empty;
+
+    #[test]
+    fn test_load_and_save() {
+        let file_name = std::env::temp_dir().join("karamel_depo_test.json").to_str().unwrap().to_string();
+        let _ = fs::remove_file(&file_name);
+        assert!(load(&file_name).unwrap().is_empty());
+
+        let mut data = StoreData::new();
+        data.insert("sayı".to_string(), arc_number!(10));
+        data.insert("yazı".to_string(), arc_text!("merhaba"));
+        save(&file_name, &data).unwrap();
+
+        let loaded = load(&file_name).unwrap();
+        assert_eq!(loaded.keys().collect::<Vec<&String>>(), vec!["sayı", "yazı"]);
+        assert_eq!(*loaded["sayı"].deref(), KaramelPrimative::Number(10.0));
+        let _ = fs::remove_file(&file_name);
+    }
+}
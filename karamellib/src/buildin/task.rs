@@ -0,0 +1,125 @@
+use crate::compiler::context::KaramelCompilerContext;
+use crate::compiler::function::{FunctionParameter, FunctionReference, NativeCall, NativeCallResult};
+use crate::types::VmObject;
+use crate::compiler::value::EMPTY_OBJECT;
+use crate::error::KaramelErrorType;
+use crate::buildin::{Module, Class};
+use crate::vm::task;
+use crate::{n_parameter_expected, expected_parameter_type};
+use std::{cell::RefCell, collections::HashMap};
+use std::rc::Rc;
+
+pub struct TaskModule {
+    methods: RefCell<HashMap<String, Rc<FunctionReference>>>,
+    path: Vec<String>
+}
+
+impl Module for TaskModule {
+    fn get_module_name(&self) -> String {
+        "görev".to_string()
+    }
+
+    fn get_path(&self) -> &Vec<String> {
+        &self.path
+    }
+
+    fn get_method(&self, name: &str) -> Option<Rc<FunctionReference>> {
+        self.methods.borrow().get(name).map(|method| method.clone())
+    }
+
+    fn get_module(&self, _: &str) -> Option<Rc<dyn Module>> {
+        None
+    }
+
+    fn get_methods(&self) -> Vec<Rc<FunctionReference>> {
+        let mut response = Vec::new();
+        self.methods.borrow().iter().for_each(|(_, reference)| response.push(reference.clone()));
+        response
+    }
+
+    fn get_modules(&self) -> HashMap<String, Rc<dyn Module>> {
+        HashMap::new()
+    }
+
+    fn get_classes(&self) -> Vec<Rc<dyn Class>> {
+        Vec::new()
+    }
+}
+
+impl TaskModule {
+    pub fn new() -> Rc<TaskModule> {
+        let module = TaskModule {
+            methods: RefCell::new(HashMap::new()),
+            path: vec!["görev".to_string()]
+        };
+
+        let rc_module = Rc::new(module);
+        rc_module.methods.borrow_mut().insert("başlat".to_string(), FunctionReference::native_function(Self::spawn as NativeCall, "başlat".to_string(), rc_module.clone()));
+        rc_module.methods.borrow_mut().insert("baslat".to_string(), FunctionReference::native_function(Self::spawn as NativeCall, "baslat".to_string(), rc_module.clone()));
+        rc_module.methods.borrow_mut().insert("bekle".to_string(), FunctionReference::native_function(Self::wait as NativeCall, "bekle".to_string(), rc_module.clone()));
+        rc_module.methods.borrow_mut().insert("kanal".to_string(), FunctionReference::native_function(Self::channel as NativeCall, "kanal".to_string(), rc_module.clone()));
+        rc_module.methods.borrow_mut().insert("gönder".to_string(), FunctionReference::native_function(Self::send as NativeCall, "gönder".to_string(), rc_module.clone()));
+        rc_module.methods.borrow_mut().insert("gonder".to_string(), FunctionReference::native_function(Self::send as NativeCall, "gonder".to_string(), rc_module.clone()));
+        rc_module.methods.borrow_mut().insert("al".to_string(), FunctionReference::native_function(Self::receive as NativeCall, "al".to_string(), rc_module.clone()));
+        rc_module.clone()
+    }
+
+    fn get_context<'a>(parameter: &'a FunctionParameter) -> Result<&'a KaramelCompilerContext, KaramelErrorType> {
+        parameter.context().ok_or_else(|| KaramelErrorType::GeneralError("Görevler sadece program çalışırken kullanılabilir".to_string()))
+    }
+
+    fn get_number(function_name: &str, object: &VmObject) -> Result<usize, KaramelErrorType> {
+        match object.as_number() {
+            Some(number) if number >= 0.0 && number.fract() == 0.0 => Ok(number as usize),
+            _ => expected_parameter_type!(function_name.to_string(), "Sayı".to_string())
+        }
+    }
+
+    /// First parameter is the function, others are passed to the function when the task starts.
+    pub fn spawn(parameter: FunctionParameter) -> NativeCallResult {
+        if parameter.length() == 0 {
+            return n_parameter_expected!("başlat".to_string(), 1, parameter.length());
+        }
+
+        let arguments: Vec<VmObject> = parameter.iter().copied().collect();
+        let task = task::spawn(Self::get_context(&parameter)?, arguments[0], &arguments[1..])?;
+        Ok(VmObject::from(task as f64))
+    }
+
+    pub fn wait(parameter: FunctionParameter) -> NativeCallResult {
+        if parameter.length() != 1 {
+            return n_parameter_expected!("bekle".to_string(), 1, parameter.length());
+        }
+
+        let task = Self::get_number("bekle", parameter.iter().next().unwrap())?;
+        task::wait(Self::get_context(&parameter)?, task)
+    }
+
+    pub fn channel(parameter: FunctionParameter) -> NativeCallResult {
+        if parameter.length() != 0 {
+            return n_parameter_expected!("kanal".to_string(), 0, parameter.length());
+        }
+
+        Ok(VmObject::from(task::create_channel(Self::get_context(&parameter)?) as f64))
+    }
+
+    pub fn send(parameter: FunctionParameter) -> NativeCallResult {
+        if parameter.length() != 2 {
+            return n_parameter_expected!("gönder".to_string(), 2, parameter.length());
+        }
+
+        let mut iter = parameter.iter();
+        let channel = Self::get_number("gönder", iter.next().unwrap())?;
+        task::send(Self::get_context(&parameter)?, channel, *iter.next().unwrap())?;
+        Ok(EMPTY_OBJECT)
+    }
+
+    pub fn receive(parameter: FunctionParameter) -> NativeCallResult {
+        if parameter.length() != 1 {
+            return n_parameter_expected!("al".to_string(), 1, parameter.length());
+        }
+
+        let channel = Self::get_number("al", parameter.iter().next().unwrap())?;
+        task::receive(Self::get_context(&parameter)?, channel)
+    }
+}
@@ -83,11 +83,26 @@ pub enum KaramelAstType {
     FunctionDefination {
         name: String,
         arguments: Vec<String>,
-        body: Rc<KaramelAstType>
+        argument_types: Vec<Option<String>>,
+        body: Rc<KaramelAstType>,
+        documentation: Option<Rc<String>>,
+
+        /// '@' lines before the definition, outermost first. Function is given to them at the definition point.
+        decorators: Vec<Rc<KaramelAstType>>,
+
+        /// Written with 'gizli', only the code of the same module can use it
+        private: bool
     },
     Symbol(String),
     ModulePath(Vec<String>),
     Load(Vec<String>),
+
+    /// 'kullan' line. Module is called with 'alias' when it is written with 'olarak', 'names' are written in '{}' and used without the module path.
+    Use {
+        path: Vec<String>,
+        alias: Option<String>,
+        names: Vec<String>
+    },
     List(Vec<Rc<KaramelAstType>>),
     Dict(Vec<Rc<KaramelDictItem>>),
     Indexer { body: Rc<KaramelAstType>, indexer: Rc<KaramelAstType> },
@@ -97,5 +112,42 @@ pub enum KaramelAstType {
     Loop {
         loop_type: LoopType,
         body: Rc<KaramelAstType>
+    },
+    Try {
+        body: Rc<KaramelAstType>,
+        variable: Option<String>,
+        catch_body: Rc<KaramelAstType>
+    },
+    Throw(Rc<KaramelAstType>),
+    Defer(Rc<KaramelAstType>),
+
+    /// 'birlikte' block. Value is assigned to the variable, 'close' is the 'değişken.kapat()' call that runs when the block ends.
+    With {
+        assignment: Rc<KaramelAstType>,
+        close: Rc<KaramelAstType>,
+        body: Rc<KaramelAstType>
+    },
+    Assume {
+        condition: Rc<KaramelAstType>,
+        message: Option<Rc<KaramelAstType>>
+    },
+
+    /// 'sabit' definition, the assignment is always to a symbol with the '=' operator.
+    Constant(Rc<KaramelAstType>),
+
+    /// 'yerel' definition. The variable belongs to the innermost 'ise' or 'döngü' body, same assignment form with the constants.
+    Local(Rc<KaramelAstType>),
+
+    /// 'seçenekler' definition. Variants are registered before the compilation, the definition itself does not generate code.
+    Enum {
+        name: String,
+        variants: Vec<String>
+    },
+
+    /// Value that is used more than once by the lowered code. The expression is evaluated once and kept in a hidden variable,
+    /// without the expression the hidden variable is loaded.
+    Temporary {
+        name: String,
+        expression: Option<Rc<KaramelAstType>>
     }
 }
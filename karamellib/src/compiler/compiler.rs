@@ -1,8 +1,8 @@
+use std::convert::TryFrom;
 use std::borrow::Borrow;
 use std::cell::Cell;
 use std::vec::Vec;
 use std::rc::Rc;
-use std::path::PathBuf;
 use std::cell::RefCell;
 
 use ast::KaramelDictItem;
@@ -19,14 +19,24 @@ use crate::compiler::ast::{KaramelAstType, KaramelIfStatementElseItem};
 use crate::compiler::storage_builder::StorageBuilder;
 use crate::compiler::function::FunctionReference;
 use crate::buildin::class::PRIMATIVE_CLASS_NAMES;
+use crate::interner::intern;
 use super::generator::location::OpcodeLocation;
 
 use log;
 
 use super::context::KaramelCompilerContext;
 use super::function::find_function_definition_type;
-use super::module::{OpcodeModule, get_modules};
+use super::module::{OpcodeModule, get_modules, get_module_file_path};
 
+/* Call opcodes carry the argument count in a single byte */
+fn argument_size(arguments: &[Rc<KaramelAstType>]) -> Result<u8, KaramelErrorType> {
+    u8::try_from(arguments.len()).map_err(|_| KaramelErrorType::TooManyArguments(u8::MAX as usize))
+}
+
+/* Init opcode carries the item count in two bytes */
+fn item_size(size: usize) -> Result<u16, KaramelErrorType> {
+    u16::try_from(size).map_err(|_| KaramelErrorType::TooManyItems(u16::MAX as usize))
+}
 
 pub struct InterpreterCompiler;
 impl InterpreterCompiler {   
@@ -43,15 +53,16 @@ impl InterpreterCompiler {
 
         storage_builder.prepare(main_module.clone(), &*main_ast, 0, context)?;
 
-        /* First part of the codes are functions */
-        let mut functions = Vec::new();
+        /* First part of the codes are functions. Functions of a module find the other functions with the path of their module. */
         for module in modules.iter() {
+            let mut functions = Vec::new();
             self.get_function_definations(module.clone(), module.main_ast.clone(), &mut functions, context, module.storage_index)?;
+            self.generate_functions(module.clone(), &functions, context)?;
         }
 
+        let mut functions = Vec::new();
         self.get_function_definations(main_module.clone(), main_ast.clone(), &mut functions, context, 0)?;
-
-        self.generate_functions(main_module.clone(), &mut functions, context)?;
+        self.generate_functions(main_module.clone(), &functions, context)?;
 
         /* Prepare jump code for main function */
         context.opcode_generator.set_current_location(main_location.clone());
@@ -77,6 +88,7 @@ impl InterpreterCompiler {
         context.main_module = module.as_ref() as *const OpcodeModule as *mut OpcodeModule;
         context.add_module(module.clone());
 
+        context.register_enums(&main_ast)?;
         find_function_definition_type(module.clone(), main_ast.clone(), context, 0, true)?;
         Ok(module.clone())
     }
@@ -93,6 +105,22 @@ impl InterpreterCompiler {
         Ok(())   
     }
 
+    /* 'sabit' variables are marked by the storage builder, only their definitions can store to them */
+    fn check_read_only(&self, variable: &str, context: &KaramelCompilerContext, storage_index: usize) -> CompilerResult {
+        let storage = &context.storages[storage_index];
+        match storage.get_variable_location(variable) {
+            Some(location) if storage.is_read_only(location) => Err(KaramelErrorType::ConstantReassigned(variable.to_string())),
+            _ => Ok(())
+        }
+    }
+
+    fn check_teaching_constant<T: Borrow<String>>(&self, variable: T, context: &KaramelCompilerContext) -> CompilerResult {
+        match context.get_teaching_constant(variable.borrow()) {
+            Some(_) => Err(KaramelErrorType::ReservedName(variable.borrow().to_string())),
+            None => Ok(())
+        }
+    }
+
     pub fn prepare_modules(&self, context: &mut KaramelCompilerContext) -> CompilerResult {
         let mut functions = Vec::new();
 
@@ -110,7 +138,7 @@ impl InterpreterCompiler {
 
     fn get_function_definations(&self, module: Rc<OpcodeModule>, ast: Rc<KaramelAstType>, functions: &mut Vec<Rc<FunctionReference>>, context: &mut KaramelCompilerContext, storage_index: usize) -> CompilerResult{
         match &*ast {
-            KaramelAstType::FunctionDefination { name, arguments: _, body  } => {
+            KaramelAstType::FunctionDefination { name, body, .. } => {
                 let search = context.get_function(name.to_string(), module.get_path(), storage_index);
                 match search {
                     Some(reference) => {
@@ -138,10 +166,16 @@ impl InterpreterCompiler {
             self.check_prohibited_names(&function.name)?;
             for argument in &function.arguments {
                 self.check_prohibited_names(argument)?;
+                self.check_teaching_constant(argument, context)?;
             }
 
             self.check_prohibited_names(&function.name)?;
+            if let Some(position) = function.definition_position.get() {
+                context.opcode_generator.mark_position(position);
+            }
+
             context.opcode_generator.create_function_definition(function.clone());
+            self.generate_type_guards(function, context)?;
             self.generate_opcode(module.clone(), &function.opcode_body.as_ref().unwrap(), &function.opcode_body.as_ref().unwrap(), context, function.storage_index as usize)?;
         }
 
@@ -149,9 +183,19 @@ impl InterpreterCompiler {
     }
 
     fn generate_opcode(&self, module: Rc<OpcodeModule>, ast: &KaramelAstType, upper_ast: &KaramelAstType, context: &mut KaramelCompilerContext, storage_index: usize) -> CompilerResult {
+        if let Some(position) = context.get_statement_position(ast) {
+            context.opcode_generator.mark_position(position);
+            if context.events.is_some() {
+                context.opcode_generator.create_line(position.line.min(u16::MAX as u32) as u16);
+            }
+        }
+
         match ast {
             KaramelAstType::Assignment { variable, operator, expression } => self.generate_assignment(module.clone(), variable, operator, expression, context, storage_index),
-            KaramelAstType::Symbol(variable) => self.generate_symbol(module.clone(), variable, upper_ast, context, storage_index),
+            KaramelAstType::Symbol(variable) => {
+                let variable = context.get_variable_name(storage_index, ast, variable);
+                self.generate_symbol(module.clone(), &variable, upper_ast, context, storage_index)
+            },
             KaramelAstType::Control { left, operator, right } => self.generate_control(module.clone(), left, operator, right, upper_ast, context, storage_index),
             KaramelAstType::Binary { left, operator, right } => self.generate_binary(module.clone(), left, operator, right, upper_ast, context, storage_index),
             KaramelAstType::Block(asts) => self.generate_block(module.clone(), asts, upper_ast, context, storage_index),
@@ -163,16 +207,26 @@ impl InterpreterCompiler {
             KaramelAstType::PrefixUnary { operator, expression, assign_to_temp } => self.generate_prefix_unary(module.clone(), operator, expression, assign_to_temp, upper_ast, context, storage_index),
             KaramelAstType::SuffixUnary(operator, expression) => self.generate_suffix_unary(operator, expression, upper_ast, context, storage_index),
             KaramelAstType::NewLine => Ok(()),
-            KaramelAstType::Loop { loop_type, body } => self.generate_loop(module.clone(), loop_type, body, upper_ast, context, storage_index),
+            KaramelAstType::Loop { loop_type, body } => self.generate_loop(module.clone(), ast, loop_type, body, upper_ast, context, storage_index),
             KaramelAstType::Break => self.generate_break(upper_ast, context, storage_index),
             KaramelAstType::Continue => self.generate_continue(upper_ast, context, storage_index),
             KaramelAstType::Return(expression) => self.generate_return(module.clone(), expression, upper_ast, context, storage_index),
             KaramelAstType::IfStatement {condition, body, else_body, else_if} => self.generate_if_condition(module.clone(),condition, body, else_body, else_if, upper_ast, context, storage_index),
             KaramelAstType::Indexer {body, indexer} => self.generate_indexer(module.clone(), body, indexer, upper_ast, context, storage_index),
             KaramelAstType::None => self.generate_none(context, storage_index),
-            KaramelAstType::FunctionDefination{name: _, arguments: _, body: _} => Ok(()),
+            KaramelAstType::FunctionDefination { name, decorators, .. } => self.generate_decorators(module.clone(), name, decorators, upper_ast, context, storage_index),
+            KaramelAstType::Enum { .. } => Ok(()),
             KaramelAstType::ModulePath(name) => self.generate_function_map(name, context, storage_index),
             KaramelAstType::Load(names) => self.generate_load_module(names, context),
+            KaramelAstType::Use { .. } => Ok(()),
+            KaramelAstType::Try { body, variable, catch_body } => self.generate_try(module.clone(), body, variable, catch_body, upper_ast, context, storage_index),
+            KaramelAstType::Throw(expression) => self.generate_throw(module.clone(), expression, upper_ast, context, storage_index),
+            KaramelAstType::Defer(body) => self.generate_defer(module.clone(), body, upper_ast, context, storage_index),
+            KaramelAstType::With { assignment, close, body } => self.generate_with(module.clone(), assignment, close, body, upper_ast, context, storage_index),
+            KaramelAstType::Assume { condition, message } => self.generate_assume(module.clone(), condition, message, upper_ast, context, storage_index),
+            KaramelAstType::Constant(assignment) => self.generate_constant(module.clone(), assignment, context, storage_index),
+            KaramelAstType::Local(assignment) => self.generate_opcode(module.clone(), assignment, upper_ast, context, storage_index),
+            KaramelAstType::Temporary { name, expression } => self.generate_temporary(module.clone(), name, expression, upper_ast, context, storage_index),
         }
     }
 
@@ -190,14 +244,7 @@ impl InterpreterCompiler {
     }
 
     fn generate_load_module(&self, params: &[String], context: &mut KaramelCompilerContext) -> CompilerResult {
-        let mut path = PathBuf::from(&context.execution_path.path[..]);
-        let module = &params[(params.len() - 1)];
-
-        for item in params.iter().take(params.len() - 1) {
-            path.push(item);
-        }
-
-        path.push(module);
+        let path = get_module_file_path(params, context);
         match read_module_or_script(path.to_str().unwrap(), context) {
             Ok(content) => {
                 let mut parser = Parser::new(&content);
@@ -238,10 +285,39 @@ impl InterpreterCompiler {
                     _ => Err(KaramelErrorType::FunctionNotFoundInStorage(name.to_string()))
                 }
             },
-            None => Err(KaramelErrorType::FunctionNotFoundInStorage(name.to_string()))
+            None => match context.get_constant(&name, &module_path).or_else(|| context.get_imported_constant(&name, &module_path, storage_index)) {
+                Some(constant) => self.generate_primative(constant, &KaramelAstType::None, context, storage_index),
+                None => Err(KaramelErrorType::FunctionNotFoundInStorage(name.to_string()))
+            }
         }
     }
 
+    /* '@a @b fonk f' is stored as 'a(b(f))' at the definition point */
+    fn generate_decorators(&self, module: Rc<OpcodeModule>, name: &str, decorators: &[Rc<KaramelAstType>], upper_ast: &KaramelAstType, context: &mut KaramelCompilerContext, storage_index: usize) -> CompilerResult {
+        let location = match context.get_decorated_location(storage_index, name) {
+            Some(location) => location,
+            None => return Ok(())
+        };
+
+        let function = match context.get_function(name.to_string(), module.get_path(), storage_index) {
+            Some(function) => function,
+            None => return Err(KaramelErrorType::FunctionNotFound(name.to_string()))
+        };
+
+        match context.storages[storage_index].get_constant_location(Rc::new(KaramelPrimative::Function(function, None))) {
+            Some(index) => { context.opcode_generator.create_constant(index); },
+            None => return Err(KaramelErrorType::FunctionNotFoundInStorage(name.to_string()))
+        };
+
+        for decorator in decorators.iter().rev() {
+            self.generate_opcode(module.clone(), decorator, upper_ast, context, storage_index)?;
+            context.opcode_generator.create_call_stack(1, true);
+        }
+
+        context.opcode_generator.create_store(location);
+        Ok(())
+    }
+
     fn generate_none(&self, context: &mut KaramelCompilerContext, storage_index: usize) -> CompilerResult {
         let storage = &context.storages[storage_index];
 
@@ -259,7 +335,7 @@ impl InterpreterCompiler {
         for item in list.iter().rev() {
             self.generate_opcode(module.clone(), item, upper_ast, context, storage_index)?;
         }
-        context.opcode_generator.create_init_list(list.len());
+        context.opcode_generator.create_init_list(item_size(list.len())?);
         Ok(())
     }
 
@@ -268,7 +344,7 @@ impl InterpreterCompiler {
             self.generate_primative(item.key.clone(), upper_ast, context, storage_index)?;
             self.generate_opcode(module.clone(), &item.value, upper_ast, context, storage_index)?;
         }
-        context.opcode_generator.create_init_dict(dict.len());
+        context.opcode_generator.create_init_dict(item_size(dict.len())?);
         Ok(())
     }
 
@@ -280,7 +356,7 @@ impl InterpreterCompiler {
                 let search_location = context.storages[storage_index].get_constant_location(Rc::new(KaramelPrimative::Function(function_ref.clone(), None)));
                 match search_location {
                     Some(location) => {
-                        context.opcode_generator.create_call(location, arguments.len() as u8, assign_to_temp);
+                        context.opcode_generator.create_call(location, argument_size(arguments)?, assign_to_temp);
                         return Ok(true);
                     },
                     _ => return Err(KaramelErrorType::FunctionNotFound(function_ref.name.to_string()))
@@ -293,7 +369,7 @@ impl InterpreterCompiler {
             /* Variable found */
             Some(location) => {
                 context.opcode_generator.create_load(location);
-                context.opcode_generator.create_call_stack(arguments.len() as u8, assign_to_temp);
+                context.opcode_generator.create_call_stack(argument_size(arguments)?, assign_to_temp);
                 return Ok(true);
             },
             /* Variable not found, lets check for function */
@@ -316,12 +392,15 @@ impl InterpreterCompiler {
                     self.generate_opcode(module.clone(), source, &KaramelAstType::None, context, storage_index)?;
                     //todo: Pass real object to function as a parameter.
                     
-                    let search_location = context.storages[storage_index].get_constant_location(Rc::new(KaramelPrimative::Text(Rc::new(function_name.to_string()))));
+                    let search_location = context.storages[storage_index].get_constant_location(Rc::new(KaramelPrimative::Text(intern(function_name.as_str()))));
                     match search_location {
                         Some(location) => {
                             context.opcode_generator.create_constant(location);
-                            context.opcode_generator.add_opcode(VmOpCode::GetItem);
-                            context.opcode_generator.create_call_stack(arguments.len() as u8, assign_to_temp);
+                            match context.add_inline_cache() {
+                                Some(cache) => { context.opcode_generator.create_get_member(cache); },
+                                None => context.opcode_generator.add_opcode(VmOpCode::GetItem)
+                            };
+                            context.opcode_generator.create_call_stack(argument_size(arguments)?, assign_to_temp);
                             return Ok(());
                         },
                         _ => return Err(KaramelErrorType::FunctionNotFound(function_name.to_string()))
@@ -353,6 +432,12 @@ impl InterpreterCompiler {
 
         match &func_name_expression {
             KaramelAstType::Symbol(function_name) => {
+                if let Some(location) = context.get_decorated_location(storage_index, function_name) {
+                    context.opcode_generator.create_load(location);
+                    context.opcode_generator.create_call_stack(argument_size(arguments)?, assign_to_temp);
+                    return Ok(());
+                }
+
                 let result = self.generate_func_call_by_name(&function_name, module.get_path(), &arguments, assign_to_temp, context, storage_index)?;
                 match result {
                     true => return Ok(()),
@@ -365,7 +450,7 @@ impl InterpreterCompiler {
 
             KaramelAstType::FuncCall {func_name_expression, arguments: inner_arguments, assign_to_temp: _} => {
                 self.generate_func_call(module.clone(), func_name_expression, inner_arguments, true, upper_ast, context, storage_index)?;
-                context.opcode_generator.create_call_stack(arguments.len() as u8, assign_to_temp);
+                context.opcode_generator.create_call_stack(argument_size(arguments)?, assign_to_temp);
                 return Ok(());
             },
 
@@ -373,12 +458,12 @@ impl InterpreterCompiler {
                 let result = self.generate_func_call_by_name(&names[names.len() - 1].to_string(), &names[0..(names.len()-1)].to_vec(), &arguments, assign_to_temp, context, storage_index)?;
                 match result {
                     true => return Ok(()),
-                    false =>  return Err(KaramelErrorType::FunctionNotFound(names[names.len() - 1].to_string()))
+                    false =>  return Err(context.function_not_found(&names[names.len() - 1], &names[0..(names.len()-1)], storage_index))
                 }
             },
             _ => {
                 self.generate_opcode(module.clone(), func_name_expression, upper_ast, context, storage_index)?;
-                context.opcode_generator.create_call_stack(arguments.len() as u8, assign_to_temp);
+                context.opcode_generator.create_call_stack(argument_size(arguments)?, assign_to_temp);
                 return Ok(());
             }
         }
@@ -398,13 +483,169 @@ impl InterpreterCompiler {
         Ok(())
     }
 
+    fn generate_try(&self, module: Rc<OpcodeModule>, body: &KaramelAstType, variable: &Option<String>, catch_body: &KaramelAstType, upper_ast: &KaramelAstType, context: &mut KaramelCompilerContext, storage_index: usize) -> CompilerResult {
+        /*
+        ╔════════════════════╗
+        ║   TRY START WITH   ║
+        ║   CATCH LOCATION   ║
+        ╠════════════════════╣
+        ║   TRY BODY         ║
+        ╠════════════════════╣
+        ║   TRY END, JUMP TO ║
+        ║   OUT OF CATCH     ║
+        ╠════════════════════╣
+        ║   CATCH BODY       ║
+        ╚════════════════════╝
+        */
+        let variable_location = match variable {
+            Some(name) => {
+                self.check_prohibited_names(name)?;
+                self.check_teaching_constant(name, context)?;
+                self.check_read_only(name, context, storage_index)?;
+                Some(context.storages.get_mut(storage_index).unwrap().add_variable(name)?)
+            },
+            None => None
+        };
+
+        let catch_location = context.opcode_generator.create_location();
+        let exit_location  = context.opcode_generator.create_location();
+
+        context.opcode_generator.create_try_start(catch_location.clone(), variable_location);
+        self.generate_opcode(module.clone(), body, upper_ast, context, storage_index)?;
+        context.opcode_generator.create_try_end(exit_location.clone());
+
+        /* Vm jumps to here with the error value */
+        context.opcode_generator.set_current_location(catch_location);
+        self.generate_opcode(module.clone(), catch_body, upper_ast, context, storage_index)?;
+        context.opcode_generator.set_current_location(exit_location);
+        Ok(())
+    }
+
+    fn generate_throw(&self, module: Rc<OpcodeModule>, expression: &KaramelAstType, upper_ast: &KaramelAstType, context: &mut KaramelCompilerContext, storage_index: usize) -> CompilerResult {
+        self.generate_opcode(module.clone(), expression, upper_ast, context, storage_index)?;
+        context.opcode_generator.add_opcode(VmOpCode::Throw);
+        Ok(())
+    }
+
+    /* 'varsay' statements are not compiled at all unless the debug assertions are enabled */
+    fn generate_assume(&self, module: Rc<OpcodeModule>, condition: &KaramelAstType, message: &Option<Rc<KaramelAstType>>, upper_ast: &KaramelAstType, context: &mut KaramelCompilerContext, storage_index: usize) -> CompilerResult {
+        if !context.debug_assertions {
+            return Ok(());
+        }
+
+        self.generate_opcode(module.clone(), condition, upper_ast, context, storage_index)?;
+        match message {
+            Some(message) => self.generate_opcode(module.clone(), message, upper_ast, context, storage_index)?,
+            None => self.generate_none(context, storage_index)?
+        };
+        context.opcode_generator.add_opcode(VmOpCode::Assume);
+        Ok(())
+    }
+
+    /* Annotated arguments are checked before the body, guards are not compiled unless they are enabled */
+    fn generate_type_guards(&self, function: &FunctionReference, context: &mut KaramelCompilerContext) -> CompilerResult {
+        if !context.type_guards {
+            return Ok(());
+        }
+
+        for (argument, argument_type) in function.arguments.iter().zip(function.argument_types.borrow().iter()) {
+            if let Some(argument_type) = argument_type {
+                let storage = &context.storages[function.storage_index];
+                let location = storage.get_variable_location(argument).ok_or(KaramelErrorType::ValueNotFoundInStorage)?;
+                let type_location = storage.get_constant_location(Rc::new(KaramelPrimative::Text(intern(argument_type.as_str())))).ok_or(KaramelErrorType::ValueNotFoundInStorage)?;
+                let name_location = storage.get_constant_location(Rc::new(KaramelPrimative::Text(intern(argument.as_str())))).ok_or(KaramelErrorType::ValueNotFoundInStorage)?;
+
+                context.opcode_generator.create_load(location);
+                context.opcode_generator.create_constant(type_location);
+                context.opcode_generator.create_constant(name_location);
+                context.opcode_generator.add_opcode(VmOpCode::TypeGuard);
+            }
+        }
+        Ok(())
+    }
+
+    /* Value stays at the stack after it is copied to the hidden variable */
+    fn generate_temporary(&self, module: Rc<OpcodeModule>, name: &str, expression: &Option<Rc<KaramelAstType>>, upper_ast: &KaramelAstType, context: &mut KaramelCompilerContext, storage_index: usize) -> CompilerResult {
+        let location = context.storages.get_mut(storage_index).unwrap().add_variable(name)?;
+        match expression {
+            Some(expression) => {
+                self.generate_opcode(module.clone(), expression, upper_ast, context, storage_index)?;
+                context.opcode_generator.create_copy_to_store(location);
+            },
+            None => {
+                context.opcode_generator.create_load(location);
+            }
+        };
+        Ok(())
+    }
+
+    fn generate_defer(&self, module: Rc<OpcodeModule>, body: &KaramelAstType, upper_ast: &KaramelAstType, context: &mut KaramelCompilerContext, storage_index: usize) -> CompilerResult {
+        /*
+        ╔════════════════════╗
+        ║   DEFER, JUMP TO   ║
+        ║   OUT OF BLOCK     ║
+        ╠════════════════════╣
+        ║   DEFERRED BODY    ║
+        ╠════════════════════╣
+        ║   DEFER END        ║
+        ╚════════════════════╝
+        */
+        let exit_location = context.opcode_generator.create_location();
+
+        context.opcode_generator.create_defer(exit_location.clone());
+        self.generate_opcode(module.clone(), body, upper_ast, context, storage_index)?;
+        context.opcode_generator.add_opcode(VmOpCode::DeferEnd);
+        context.opcode_generator.set_current_location(exit_location);
+        Ok(())
+    }
+
+    fn generate_with(&self, module: Rc<OpcodeModule>, assignment: &KaramelAstType, close: &KaramelAstType, body: &KaramelAstType, upper_ast: &KaramelAstType, context: &mut KaramelCompilerContext, storage_index: usize) -> CompilerResult {
+        /*
+        ╔════════════════════╗
+        ║   ASSIGNMENT       ║
+        ╠════════════════════╣
+        ║   WITH START       ║
+        ╠════════════════════╣
+        ║   DEFER, JUMP TO   ║
+        ║   OUT OF BLOCK     ║
+        ╠════════════════════╣
+        ║   CLOSE CALL       ║
+        ╠════════════════════╣
+        ║   DEFER END        ║
+        ╠════════════════════╣
+        ║   BODY             ║
+        ╠════════════════════╣
+        ║   WITH END         ║
+        ╠════════════════════╣
+        ║   CLOSE CALL       ║
+        ╚════════════════════╝
+        Deferred call closes the value when the function is left with 'döndür' or an error. Body end removes it and calls 'kapat' directly.
+        */
+        self.generate_opcode(module.clone(), assignment, upper_ast, context, storage_index)?;
+        if let KaramelAstType::Assignment { variable, .. } = assignment {
+            self.generate_opcode(module.clone(), variable, upper_ast, context, storage_index)?;
+        }
+        context.opcode_generator.add_opcode(VmOpCode::WithStart);
+
+        let exit_location = context.opcode_generator.create_location();
+        context.opcode_generator.create_defer(exit_location.clone());
+        let close_location = context.opcode_generator.current_location();
+        self.generate_opcode(module.clone(), close, upper_ast, context, storage_index)?;
+        context.opcode_generator.add_opcode(VmOpCode::DeferEnd);
+        context.opcode_generator.set_current_location(exit_location);
+
+        self.generate_opcode(module.clone(), body, upper_ast, context, storage_index)?;
+        context.opcode_generator.create_with_end(close_location);
+        self.generate_opcode(module.clone(), close, upper_ast, context, storage_index)
+    }
+
     fn generate_return(&self, module: Rc<OpcodeModule>, expression: &KaramelAstType, upper_ast: &KaramelAstType, context: &mut KaramelCompilerContext, storage_index: usize) -> CompilerResult {
         self.generate_opcode(module.clone(), expression, upper_ast, context, storage_index)?;
         context.opcode_generator.add_opcode(VmOpCode::Return);
         Ok(())
     }
 
-    fn generate_loop(&self, module: Rc<OpcodeModule>, loop_type: &LoopType, body: &KaramelAstType, upper_ast: &KaramelAstType, context: &mut KaramelCompilerContext, storage_index: usize) -> CompilerResult {
+    fn generate_loop(&self, module: Rc<OpcodeModule>, loop_statement: &KaramelAstType, loop_type: &LoopType, body: &KaramelAstType, upper_ast: &KaramelAstType, context: &mut KaramelCompilerContext, storage_index: usize) -> CompilerResult {
         /* Backup loop informations */
         context.opcode_generator.loop_started();
         let mut compare_location: Option<Rc<OpcodeLocation>> = None;
@@ -442,6 +683,11 @@ impl InterpreterCompiler {
             };
         }
 
+        if context.teaching_mode {
+            let index = context.add_loop_counter(loop_statement);
+            context.opcode_generator.create_loop_counter(index);
+        }
+
         self.generate_opcode(module.clone(), body, upper_ast, context, storage_index)?;
 
         if let Some(increment) = &increment {
@@ -464,6 +710,19 @@ impl InterpreterCompiler {
     }
 
     fn generate_symbol(&self, module: Rc<OpcodeModule>, variable: &String, _: &KaramelAstType, context: &mut KaramelCompilerContext, storage_index: usize) -> CompilerResult {
+        if let Some(constant) = context.get_teaching_constant(variable) {
+            return self.generate_primative(constant, &KaramelAstType::None, context, storage_index);
+        }
+
+        if let Some(constant) = context.get_imported_constant(variable, module.get_path(), storage_index) {
+            return self.generate_primative(constant, &KaramelAstType::None, context, storage_index);
+        }
+
+        if let Some(location) = context.get_decorated_location(storage_index, variable) {
+            context.opcode_generator.create_load(location);
+            return Ok(());
+        }
+
         let storage = &context.storages[storage_index];                
         let result = storage.get_function_constant(variable.to_string(), module.clone());
         match result {
@@ -474,6 +733,13 @@ impl InterpreterCompiler {
             _ => ()
         };
 
+        /* Functions that are taken with 'kullan' belong to the other modules */
+        let imported = context.imported_path(variable, module.get_path(), storage_index).and_then(|module_path| context.get_function(variable.to_string(), &module_path, storage_index));
+        if let Some(index) = imported.and_then(|function| storage.get_constant_location(Rc::new(KaramelPrimative::Function(function, None)))) {
+            context.opcode_generator.create_constant(index);
+            return Ok(());
+        }
+
         let result = storage.get_class_constant(variable.to_string(), module.clone());
         match result {
             Some(index) => {
@@ -495,7 +761,19 @@ impl InterpreterCompiler {
     }
 
     fn generate_control(&self, module: Rc<OpcodeModule>, left_ast: &KaramelAstType, operator: &KaramelOperatorType, right_ast: &KaramelAstType, _: &KaramelAstType, context: &mut KaramelCompilerContext, storage_index: usize) -> CompilerResult {
+        /* Right side of 've' and 'veya' is skipped when the left side is enough for the result */
+        let short_circuit = match operator {
+            KaramelOperatorType::And => Some(VmOpCode::JumpIfFalse),
+            KaramelOperatorType::Or  => Some(VmOpCode::JumpIfTrue),
+            _ => None
+        };
+
         self.generate_opcode(module.clone(), left_ast, &KaramelAstType::None, context, storage_index)?;
+        let exit_location = short_circuit.map(|opcode| {
+            let location = context.opcode_generator.create_location();
+            context.opcode_generator.create_conditional_jump(opcode, location.clone());
+            location
+        });
         self.generate_opcode(module.clone(), right_ast, &KaramelAstType::None, context, storage_index)?;
 
         let opcode = match operator {
@@ -509,32 +787,57 @@ impl InterpreterCompiler {
         };
 
         context.opcode_generator.add_opcode(opcode);
+        if let Some(location) = exit_location {
+            context.opcode_generator.set_current_location(location);
+        }
         Ok(())
     }
 
+    fn generate_constant(&self, module: Rc<OpcodeModule>, assignment: &KaramelAstType, context: &mut KaramelCompilerContext, storage_index: usize) -> CompilerResult {
+        match assignment {
+            KaramelAstType::Assignment { variable, operator, expression } => self.generate_store_assignment(module, variable, operator, expression, context, storage_index),
+            _ => Err(KaramelErrorType::ConstantValueRequired)
+        }
+    }
+
     fn generate_assignment(&self, module: Rc<OpcodeModule>, variable: &KaramelAstType, operator: &KaramelOperatorType, expression_ast: &KaramelAstType, context: &mut KaramelCompilerContext, storage_index: usize) -> CompilerResult {
+        if let KaramelAstType::Symbol(name) = variable {
+            let name = context.get_variable_name(storage_index, variable, name);
+            self.check_read_only(&name, context, storage_index)?;
+        }
+        self.generate_store_assignment(module, variable, operator, expression_ast, context, storage_index)
+    }
+
+    fn generate_store_assignment(&self, module: Rc<OpcodeModule>, variable: &KaramelAstType, operator: &KaramelOperatorType, expression_ast: &KaramelAstType, context: &mut KaramelCompilerContext, storage_index: usize) -> CompilerResult {
         match variable {
             KaramelAstType::Symbol(symbol) => {
                 
                 /* Validate function name and parameters */
                 if let KaramelAstType::Symbol(variable_name) = variable {
-                    self.check_prohibited_names(variable_name)?;    
+                    self.check_prohibited_names(variable_name)?;
+                    self.check_teaching_constant(variable_name, context)?;
+                    if context.get_imported_constant(variable_name, module.get_path(), storage_index).is_some() {
+                        return Err(KaramelErrorType::ReservedName(variable_name.to_string()));
+                    }
                 }
                 
-                let location = context.storages.get_mut(storage_index).unwrap().add_variable(&*symbol);
+                let symbol = context.get_variable_name(storage_index, variable, symbol);
+                let location = context.storages.get_mut(storage_index).unwrap().add_variable(&symbol)?;
                 let storage = &context.storages[storage_index];
                 
                 if let KaramelAstType::Primative(primative) = expression_ast {
                     if mem::discriminant(&**primative) != mem::discriminant(&KaramelPrimative::List(RefCell::new(Vec::new()))) && 
                     *operator == KaramelOperatorType::Assign {
+                        /* FastStore has 8 bit locations, the other constants are loaded to the stack and stored */
                         let result = storage.get_constant_location(primative.clone());
-                        let primative_location = match result {
-                            Some(index) => index as u8,
-                            _ => return Err(KaramelErrorType::ValueNotFoundInStorage)
+                        match (result.map(u8::try_from), u8::try_from(location)) {
+                            (Some(Ok(primative_location)), Ok(location)) => {
+                                context.opcode_generator.create_fast_store(primative_location, location);
+                                return Ok(());
+                            },
+                            (Some(_), _) => (),
+                            (None, _) => return Err(KaramelErrorType::ValueNotFoundInStorage)
                         };
-
-                        context.opcode_generator.create_fast_store(primative_location, location);
-                        return Ok(());
                     }
                 }
 
@@ -545,15 +848,7 @@ impl InterpreterCompiler {
 
                     self.generate_opcode(module.clone(), expression_ast, &KaramelAstType::None, context, storage_index)?;
 
-                    let opcode = match operator {
-                        KaramelOperatorType::AssignAddition       => VmOpCode::Addition,
-                        KaramelOperatorType::AssignDivision       => VmOpCode::Division,
-                        KaramelOperatorType::AssignMultiplication => VmOpCode::Multiply,
-                        KaramelOperatorType::AssignSubtraction    => VmOpCode::Subraction,
-                        _ => return Err(KaramelErrorType::OperatorNotValid)
-                    };
-
-                    context.opcode_generator.add_opcode(opcode);
+                    context.opcode_generator.add_opcode(self.get_compound_opcode(operator)?);
                 } else {
                     self.generate_opcode(module.clone(), expression_ast, &KaramelAstType::None, context, storage_index)?;
                 }
@@ -565,8 +860,19 @@ impl InterpreterCompiler {
             KaramelAstType::Indexer {body, indexer} => {
                 self.generate_opcode(module.clone(), body, &KaramelAstType::None, context, storage_index)?;
                 self.generate_opcode(module.clone(), indexer, &KaramelAstType::None, context, storage_index)?;
-                self.generate_opcode(module.clone(), expression_ast, &KaramelAstType::None, context, storage_index)?;
-                
+
+                if *operator != KaramelOperatorType::Assign {
+
+                    /* Object and indexer are evaluated once, their copies are used to load the current value */
+                    context.opcode_generator.add_opcode(VmOpCode::DublicatePair);
+                    context.opcode_generator.add_opcode(VmOpCode::GetItem);
+
+                    self.generate_opcode(module.clone(), expression_ast, &KaramelAstType::None, context, storage_index)?;
+                    context.opcode_generator.add_opcode(self.get_compound_opcode(operator)?);
+                } else {
+                    self.generate_opcode(module.clone(), expression_ast, &KaramelAstType::None, context, storage_index)?;
+                }
+
                 context.opcode_generator.add_opcode(VmOpCode::SetItem);
                 Ok(())
             },
@@ -574,6 +880,16 @@ impl InterpreterCompiler {
         }
     }
 
+    fn get_compound_opcode(&self, operator: &KaramelOperatorType) -> Result<VmOpCode, KaramelErrorType> {
+        match operator {
+            KaramelOperatorType::AssignAddition       => Ok(VmOpCode::Addition),
+            KaramelOperatorType::AssignDivision       => Ok(VmOpCode::Division),
+            KaramelOperatorType::AssignMultiplication => Ok(VmOpCode::Multiply),
+            KaramelOperatorType::AssignSubtraction    => Ok(VmOpCode::Subraction),
+            _ => Err(KaramelErrorType::OperatorNotValid)
+        }
+    }
+
     fn generate_binary(&self, module: Rc<OpcodeModule>, left_ast: &KaramelAstType, operator: &KaramelOperatorType, right_ast: &KaramelAstType, _: &KaramelAstType, context: &mut KaramelCompilerContext, storage_index: usize) -> CompilerResult { 
         self.generate_opcode(module.clone(), left_ast, &KaramelAstType::None, context, storage_index)?;
         self.generate_opcode(module.clone(), right_ast, &KaramelAstType::None, context, storage_index)?;
@@ -597,7 +913,9 @@ impl InterpreterCompiler {
         }
 
         if let KaramelAstType::Symbol(variable) = expression {
-            let location = match context.storages.get_mut(storage_index).unwrap().get_variable_location(variable) {
+            let variable = context.get_variable_name(storage_index, expression, variable);
+            self.check_read_only(&variable, context, storage_index)?;
+            let location = match context.storages.get_mut(storage_index).unwrap().get_variable_location(&variable) {
                 Some(location) => location,
                 _ => return Err(KaramelErrorType::ValueNotFoundInStorage)
             };
@@ -711,7 +1029,9 @@ impl InterpreterCompiler {
 
     fn generate_suffix_unary(&self, operator: &KaramelOperatorType, expression: &KaramelAstType, _: &KaramelAstType, context: &mut KaramelCompilerContext, storage_index: usize) -> CompilerResult { 
         if let KaramelAstType::Symbol(variable) = expression {
-            let location = match context.storages.get_mut(storage_index).unwrap().get_variable_location(variable) {
+            let variable = context.get_variable_name(storage_index, expression, variable);
+            self.check_read_only(&variable, context, storage_index)?;
+            let location = match context.storages.get_mut(storage_index).unwrap().get_variable_location(&variable) {
                 Some(location) => location,
                 _ => return Err(KaramelErrorType::ValueNotFoundInStorage)
             };
@@ -751,7 +1071,7 @@ mod tests {
     use std::rc::Rc;
     use crate::compiler::ast::{KaramelAstType};
     use crate::compiler::storage_builder::StorageBuilder;
-    use crate::compiler::function::{FunctionReference};
+    use crate::compiler::function::{FunctionReference, FunctionFlag};
     use super::module::{OpcodeModule};
 
     use crate::error::KaramelErrorType;
@@ -762,7 +1082,7 @@ mod tests {
         let compiler = InterpreterCompiler {};
         let storage_builder: StorageBuilder = StorageBuilder::new();
 
-        let function_define = FunctionReference::opcode_function("test".to_string(), Vec::new(), Rc::new(KaramelAstType::None), Rc::new(DummyModule::new()), 0, 0, true);
+        let function_define = FunctionReference::opcode_function("test".to_string(), Vec::new(), Rc::new(KaramelAstType::None), Rc::new(DummyModule::new()), 0, 0, FunctionFlag::MODULE_LEVEL);
 
         let mut functions = Vec::new();
         functions.push(function_define);
@@ -779,7 +1099,7 @@ mod tests {
         let compiler = InterpreterCompiler {};
         let storage_builder: StorageBuilder = StorageBuilder::new();
 
-        let function_define = FunctionReference::opcode_function("yazı".to_string(), Vec::new(), Rc::new(KaramelAstType::None), Rc::new(DummyModule::new()), 0, 0, true);
+        let function_define = FunctionReference::opcode_function("yazı".to_string(), Vec::new(), Rc::new(KaramelAstType::None), Rc::new(DummyModule::new()), 0, 0, FunctionFlag::MODULE_LEVEL);
 
         let mut functions = Vec::new();
         functions.push(function_define);
@@ -800,7 +1120,7 @@ mod tests {
         let compiler = InterpreterCompiler {};
         let storage_builder: StorageBuilder = StorageBuilder::new();
 
-        let function_define = FunctionReference::opcode_function("döndür".to_string(), Vec::new(), Rc::new(KaramelAstType::None), Rc::new(DummyModule::new()), 0, 0, true);
+        let function_define = FunctionReference::opcode_function("döndür".to_string(), Vec::new(), Rc::new(KaramelAstType::None), Rc::new(DummyModule::new()), 0, 0, FunctionFlag::MODULE_LEVEL);
 
         let mut functions = Vec::new();
         functions.push(function_define);
@@ -821,7 +1141,7 @@ mod tests {
         let compiler = InterpreterCompiler {};
         let storage_builder: StorageBuilder = StorageBuilder::new();
 
-        let function_define = FunctionReference::opcode_function("sayı".to_string(), Vec::new(), Rc::new(KaramelAstType::None), Rc::new(DummyModule::new()), 0, 0, true);
+        let function_define = FunctionReference::opcode_function("sayı".to_string(), Vec::new(), Rc::new(KaramelAstType::None), Rc::new(DummyModule::new()), 0, 0, FunctionFlag::MODULE_LEVEL);
 
         let mut functions = Vec::new();
         functions.push(function_define);
@@ -843,7 +1163,7 @@ mod tests {
         let compiler = InterpreterCompiler {};
         let storage_builder: StorageBuilder = StorageBuilder::new();
 
-        let function_define = FunctionReference::opcode_function("test".to_string(), vec!["test".to_string()], Rc::new(KaramelAstType::None), Rc::new(DummyModule::new()), 0, 0, true);
+        let function_define = FunctionReference::opcode_function("test".to_string(), vec!["test".to_string()], Rc::new(KaramelAstType::None), Rc::new(DummyModule::new()), 0, 0, FunctionFlag::MODULE_LEVEL);
 
         let mut functions = Vec::new();
         functions.push(function_define);
@@ -860,7 +1180,7 @@ mod tests {
         let compiler = InterpreterCompiler {};
         let storage_builder: StorageBuilder = StorageBuilder::new();
 
-        let function_define = FunctionReference::opcode_function("test".to_string(), vec!["sayı".to_string()], Rc::new(KaramelAstType::None), Rc::new(DummyModule::new()), 0, 0, true);
+        let function_define = FunctionReference::opcode_function("test".to_string(), vec!["sayı".to_string()], Rc::new(KaramelAstType::None), Rc::new(DummyModule::new()), 0, 0, FunctionFlag::MODULE_LEVEL);
 
         let mut functions = Vec::new();
         functions.push(function_define);
@@ -881,7 +1201,7 @@ mod tests {
         let compiler = InterpreterCompiler {};
         let storage_builder: StorageBuilder = StorageBuilder::new();
 
-        let function_define = FunctionReference::opcode_function("döndür".to_string(), vec!["sayı".to_string()], Rc::new(KaramelAstType::None), Rc::new(DummyModule::new()), 0, 0, true);
+        let function_define = FunctionReference::opcode_function("döndür".to_string(), vec!["sayı".to_string()], Rc::new(KaramelAstType::None), Rc::new(DummyModule::new()), 0, 0, FunctionFlag::MODULE_LEVEL);
 
         let mut functions = Vec::new();
         functions.push(function_define);
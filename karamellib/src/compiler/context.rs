@@ -1,11 +1,32 @@
 use std::borrow::Borrow;
-use std::{cell::RefCell, ptr, rc::Rc};
-use crate::buildin::num::{NumModule};
+use std::convert::TryFrom;
+use std::collections::HashMap;
+use std::{cell::{Cell, RefCell}, ptr, rc::Rc};
+use std::sync::Arc;
+use std::sync::atomic::AtomicBool;
+#[cfg(feature = "modules")]
+use crate::buildin::{debug, io, num::NumModule, store::StoreModule, constants::ConstantsModule, file::FileModule, task::TaskModule};
 
-use crate::types::VmObject;
-use crate::{buildin::{Class, Module, ModuleCollection, base_functions, class::{dict, get_empty_class, list, number, proxy, text}, debug, io}, compiler::scope::Scope};
+use crate::compiler::ast::{KaramelAstType, KaramelIfStatementElseItem};
+use crate::compiler::visitor::{AstVisitor, VisitorResult, walk_loop};
+use crate::syntax::ExpressionSpan;
+use crate::syntax::loops::LoopType;
+use crate::types::{SourcePosition, VmObject};
+use crate::gc;
+use crate::interner::intern;
+use crate::vm::events::VmEventTracker;
+use crate::vm::task::TaskScheduler;
+#[cfg(feature = "ffi")]
+use crate::{buildin::ffi::FfiModule, vm::ffi::ForeignInterface};
+use crate::vm::trace::VmTracer;
+use crate::error::{CompilerWarning, KaramelErrorType};
+use crate::constants::{KARAMEL_DEFAULT_PRINT_PRECISION, KARAMEL_MAX_CALL_DEPTH};
+use crate::file::{FileSystem, OsFileSystem};
+use crate::{buildin::{Class, ClassProperty, Module, ModuleCollection, Permission, base_functions, class::{dict, error, get_empty_class, list, number, priority_queue, proxy, queue, stack, text, text_builder, frozen, sorted_dict, bytes}}, compiler::scope::{Scope, ErrorHandler, CallFrame}};
 
 use super::generator::OpcodeGenerator;
+use super::value::EnumVariant;
+use super::GetType;
 use super::{KaramelPrimative, StaticStorage, function::{FunctionReference, FunctionType, FunctionFlag}, module::OpcodeModule};
 
 #[derive(Default)]
@@ -16,6 +37,132 @@ pub struct ExecutionPathInfo {
 
 const MAX_STACK: usize = 64 * 1024 + 1;
 
+/* Free slots that are kept for the temporary values of the called function */
+const STACK_RESERVE: usize = 256;
+
+/// Iteration count of a source loop. Collected at teaching mode.
+#[derive(Clone, Debug, PartialEq)]
+pub struct LoopCounter {
+    pub line: u32,
+    pub iterations: usize
+}
+
+/// Module that a file takes with 'kullan'. 'file' is the module path of the file, names are only found at its code.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ModuleImport {
+    pub file: Vec<String>,
+    pub path: Vec<String>,
+    pub alias: Option<String>,
+    pub names: Vec<String>
+}
+
+/// Last class and its property at a 'nesne.metot()' call site. Empty cache has zero class.
+#[derive(Clone, Default)]
+pub struct InlineCache {
+    pub class: usize,
+    pub property: Option<ClassProperty>
+}
+
+/* Finds the statements in the source order. Items of the bodies are statements, other nodes are walked only for the inner bodies. */
+#[derive(Default)]
+struct StatementCollector {
+    statements: Vec<usize>
+}
+
+impl StatementCollector {
+    fn visit_body(&mut self, body: &KaramelAstType) -> VisitorResult<()> {
+        match body {
+            KaramelAstType::Block(items) => for item in items.iter() {
+                self.statements.push(&**item as *const KaramelAstType as usize);
+                self.visit(item)?;
+            },
+            KaramelAstType::None => (),
+            _ => {
+                self.statements.push(body as *const KaramelAstType as usize);
+                self.visit(body)?;
+            }
+        };
+        Ok(())
+    }
+}
+
+impl AstVisitor for StatementCollector {
+    type Error = ();
+
+    fn visit_if_statement(&mut self, condition: &KaramelAstType, body: &KaramelAstType, else_body: Option<&KaramelAstType>, else_if: &[Rc<KaramelIfStatementElseItem>]) -> VisitorResult<()> {
+        self.visit(condition)?;
+        self.visit_body(body)?;
+
+        for item in else_if.iter() {
+            self.visit(&item.condition)?;
+            self.visit_body(&item.body)?;
+        }
+
+        if let Some(else_body) = else_body {
+            self.visit_body(else_body)?;
+        }
+        Ok(())
+    }
+
+    fn visit_function_defination(&mut self, _: &str, _: &[String], body: &KaramelAstType) -> VisitorResult<()> {
+        self.visit_body(body)
+    }
+
+    fn visit_loop(&mut self, loop_type: &LoopType, body: &KaramelAstType) -> VisitorResult<()> {
+        walk_loop(self, loop_type, &KaramelAstType::None)?;
+        self.visit_body(body)
+    }
+
+    fn visit_try(&mut self, body: &KaramelAstType, _: Option<&str>, catch_body: &KaramelAstType) -> VisitorResult<()> {
+        self.visit_body(body)?;
+        self.visit_body(catch_body)
+    }
+
+    fn visit_defer(&mut self, body: &KaramelAstType) -> VisitorResult<()> {
+        self.visit_body(body)
+    }
+
+    fn visit_with(&mut self, assignment: &KaramelAstType, _: &KaramelAstType, body: &KaramelAstType) -> VisitorResult<()> {
+        self.visit(assignment)?;
+        self.visit_body(body)
+    }
+}
+
+/// Hidden variable name of a decorated function, '@' can not be written in a variable name.
+pub fn decorated_name(name: &str) -> String {
+    format!("@{}", name)
+}
+
+/* Finds the 'seçenekler' definitions in the whole program, definitions in the bodies are found too */
+#[derive(Default)]
+struct EnumCollector {
+    enums: Vec<(String, Vec<String>)>
+}
+
+impl AstVisitor for EnumCollector {
+    type Error = ();
+
+    fn visit_enum(&mut self, name: &str, variants: &[String]) -> VisitorResult<()> {
+        self.enums.push((name.to_string(), variants.to_vec()));
+        Ok(())
+    }
+}
+
+/* Text of an argument at the call stack. Stack traces can be long, so the collections are not written with their items. */
+fn frame_argument(value: VmObject) -> String {
+    let value = value.deref();
+    match &*value {
+        KaramelPrimative::List(items) | KaramelPrimative::Stack(items) => format!("<{}({})>", value.get_type(), items.borrow().len()),
+        KaramelPrimative::Dict(items) => format!("<{}({})>", value.get_type(), items.borrow().len()),
+        KaramelPrimative::SortedDict(items) => format!("<{}({})>", value.get_type(), items.borrow().len()),
+        KaramelPrimative::Bytes(bytes) if bytes.len() > 32 => format!("<{}({})>", value.get_type(), bytes.len()),
+        KaramelPrimative::Queue(items) => format!("<{}({})>", value.get_type(), items.borrow().len()),
+        KaramelPrimative::PriorityQueue(items) => format!("<{}({})>", value.get_type(), items.borrow().len()),
+        KaramelPrimative::Text(text) if text.chars().count() > 32 => format!("\"{}...\"", text.chars().take(32).collect::<String>()),
+        _ => format!("{:?}", value)
+    }
+}
+
 pub struct KaramelCompilerContext {
     pub execution_path: ExecutionPathInfo,
     pub opcodes : Vec<u8>,
@@ -31,14 +178,82 @@ pub struct KaramelCompilerContext {
     pub classes : Vec<Rc<dyn Class >>,
     pub stdout: Option<RefCell<String>>,
     pub stderr: Option<RefCell<String>>,
+
+    /// Modules that are removed because their permission is not given, their calls are reported with the permission
+    pub denied_modules: Vec<(Vec<String>, Permission)>,
+
+    /// Lines for 'gç::satıroku'. Standard input is read when it is not given. Shared with the tasks.
+    pub stdin: Option<Rc<RefCell<String>>>,
     pub memory_dump: Option<String>,
     pub opcode_dump: Option<String>,
     pub opcodes_ptr: *mut u8,
     pub opcodes_top_ptr: *mut u8,
     pub primative_classes: Vec<Rc<dyn Class>>,
     pub opcode_generator: OpcodeGenerator,
-    pub stack: [VmObject; MAX_STACK],
-    pub stack_ptr: *mut VmObject
+    pub stack: Box<[VmObject]>,
+    pub stack_ptr: *mut VmObject,
+    pub teaching_mode: bool,
+
+    /// 'varsay' statements are compiled only when enabled
+    pub debug_assertions: bool,
+
+    /// Arguments with a type annotation are checked at the start of the function
+    pub type_guards: bool,
+
+    /// Function calls that can be nested, the main code is not counted
+    pub max_call_depth: usize,
+
+    /// 'yükle' and the 'dosya' module read the files from here
+    pub file_system: Rc<dyn FileSystem>,
+    pub statement_positions: HashMap<usize, SourcePosition>,
+
+    /// Hidden names of the 'yerel' variables. Keys are the storage index and the address of the symbol, filled by the storage builder.
+    pub local_variables: HashMap<(usize, usize), Rc<String>>,
+
+    /// Directories of the küfe packages, keys are the package names. Filled from the 'küfe.toml' file of the project.
+    pub packages: HashMap<String, String>,
+
+    /// 'kullan' lines of the loaded files. Keys are the storage index of the files, every loaded file has an entry.
+    pub imports: HashMap<usize, Vec<ModuleImport>>,
+
+    /// Variants of the 'seçenekler' definitions in the definition order, keys are the definition names
+    pub enums: HashMap<String, Vec<Rc<KaramelPrimative>>>,
+
+    /// Condition texts of the 'doğrula' calls with the position of their statements
+    pub assert_expressions: HashMap<SourcePosition, String>,
+    pub loop_counters: Vec<LoopCounter>,
+    pub inline_caches: Vec<InlineCache>,
+    pub instruction_count: usize,
+
+    /// Executed instructions are counted at the teaching mode and while benchmarking
+    pub count_instructions: bool,
+
+    /// Execution stops with the 'InstructionLimitExceeded' error after this many instructions. Only checked while counting the instructions.
+    pub instruction_limit: Option<usize>,
+
+    /// Execution stops with the 'MemoryLimitExceeded' error after the texts, bytes and copies that are created reach this size in bytes
+    pub allocation_limit: Option<usize>,
+    pub allocated_bytes: Cell<usize>,
+
+    /// Backward jumps of the running program, teaching mode stops the execution after the limit
+    pub loop_iterations: usize,
+    pub events: Option<VmEventTracker>,
+    pub warnings: Vec<CompilerWarning>,
+    pub error_handlers: Vec<ErrorHandler>,
+
+    /// Significant digit count of the printed numbers at the start of the execution
+    pub print_precision: usize,
+
+    /// Tasks and channels of the 'görev' module. Vm instances of the tasks share it with the main code.
+    pub tasks: Rc<RefCell<TaskScheduler>>,
+
+    /// Libraries and functions of the 'ffi' module. Shared with the tasks like the scheduler.
+    #[cfg(feature = "ffi")]
+    pub foreign: Rc<RefCell<ForeignInterface>>,
+
+    /// Checked before each instruction, the execution stops when it is set
+    pub interrupt: Arc<AtomicBool>,
+    pub tracer: Option<VmTracer>
 }
 
 impl  KaramelCompilerContext {
@@ -57,15 +272,45 @@ impl  KaramelCompilerContext {
             classes: Vec::new(),
             stdout: None,
             stderr: None,
+            denied_modules: Vec::new(),
+            stdin: None,
             opcodes_ptr: ptr::null_mut(),
             opcodes_top_ptr: ptr::null_mut(),
             primative_classes: Vec::new(),
             main_module: ptr::null_mut(),
             opcode_generator: OpcodeGenerator::new(),
-            stack: [VmObject(0); MAX_STACK],
+            stack: vec![VmObject(0); MAX_STACK].into_boxed_slice(),
             stack_ptr: ptr::null_mut(),
             memory_dump: None,
-            opcode_dump: None
+            opcode_dump: None,
+            teaching_mode: false,
+            debug_assertions: false,
+            type_guards: false,
+            max_call_depth: KARAMEL_MAX_CALL_DEPTH,
+            file_system: Rc::new(OsFileSystem::default()),
+            statement_positions: HashMap::new(),
+            local_variables: HashMap::new(),
+            packages: HashMap::new(),
+            imports: HashMap::new(),
+            enums: HashMap::new(),
+            assert_expressions: HashMap::new(),
+            loop_counters: Vec::new(),
+            inline_caches: Vec::new(),
+            instruction_count: 0,
+            count_instructions: false,
+            instruction_limit: None,
+            allocation_limit: None,
+            allocated_bytes: Cell::new(0),
+            loop_iterations: 0,
+            events: None,
+            warnings: Vec::new(),
+            error_handlers: Vec::new(),
+            print_precision: KARAMEL_DEFAULT_PRINT_PRECISION,
+            tasks: Rc::new(RefCell::new(TaskScheduler::default())),
+            #[cfg(feature = "ffi")]
+            foreign: Rc::new(RefCell::new(ForeignInterface::default())),
+            interrupt: Arc::new(AtomicBool::new(false)),
+            tracer: None
         };
         
         compiler.primative_classes.push(number::get_primative_class());
@@ -78,11 +323,32 @@ impl  KaramelCompilerContext {
         compiler.primative_classes.push(get_empty_class());
         compiler.primative_classes.push(proxy::get_primative_class());
         compiler.primative_classes.push(get_empty_class());
+        compiler.primative_classes.push(stack::get_primative_class());
+        compiler.primative_classes.push(queue::get_primative_class());
+        compiler.primative_classes.push(priority_queue::get_primative_class());
+        compiler.primative_classes.push(error::get_primative_class());
+        compiler.primative_classes.push(get_empty_class());
+        compiler.primative_classes.push(get_empty_class());
+        compiler.primative_classes.push(text_builder::get_primative_class());
+        compiler.primative_classes.push(frozen::get_primative_class());
+        compiler.primative_classes.push(sorted_dict::get_primative_class());
+        compiler.primative_classes.push(bytes::get_primative_class());
 
         compiler.add_module(base_functions::BaseFunctionsModule::new());
-        compiler.add_module(io::IoModule::new());
-        compiler.add_module(NumModule::new());
-        compiler.add_module(debug::DebugModule::new());
+
+        #[cfg(feature = "modules")]
+        {
+            compiler.add_module(io::IoModule::new());
+            compiler.add_module(NumModule::new());
+            compiler.add_module(debug::DebugModule::new());
+            compiler.add_module(StoreModule::new());
+            compiler.add_module(FileModule::new());
+            compiler.add_module(ConstantsModule::new());
+            compiler.add_module(TaskModule::new());
+        }
+
+        #[cfg(feature = "ffi")]
+        compiler.add_module(FfiModule::new());
 
         for _ in 0..32 {
             compiler.scopes.push(Scope::empty());
@@ -106,6 +372,28 @@ impl  KaramelCompilerContext {
         }
     }
 
+    /// Removes the built-in modules that need a permission out of the given ones. Their functions are removed too,
+    /// using them is reported with the missing permission.
+    pub fn allow_permissions(&mut self, permissions: &[Permission]) {
+        let denied: Vec<(Vec<String>, Permission)> = self.modules.iter()
+            .filter_map(|(_, module)| module.get_permission().filter(|permission| !permissions.contains(permission)).map(|permission| (module.get_path().clone(), permission)))
+            .collect();
+
+        for (module_path, _) in denied.iter() {
+            self.modules.remove_module(module_path);
+            self.functions.retain(|function| function.module.get_path() != module_path);
+        }
+        self.denied_modules.extend(denied);
+    }
+
+    /// Error of a module that is removed because of its permission
+    pub fn permission_error(&self, module_path: &[String]) -> Option<KaramelErrorType> {
+        self.denied_modules.iter().find(|(path, _)| path == module_path).map(|(path, permission)| KaramelErrorType::PermissionDenied {
+            module: path.join("::"),
+            permission: permission.name().to_string()
+        })
+    }
+
     pub fn add_function(&mut self, information: Rc<FunctionReference>) {
         self.functions.push(information);
     }
@@ -114,7 +402,78 @@ impl  KaramelCompilerContext {
         self.classes.push(class_info.clone());
     }
 
+    /* Private functions can be used by the storages of their module, the storage of a module is the parent of its functions */
+    fn is_visible(&self, function: &FunctionReference, start_storage_index: usize) -> bool {
+        if !function.flags.contains(FunctionFlag::PRIVATE) {
+            return true;
+        }
+
+        let mut search_storage = start_storage_index;
+        loop {
+            if search_storage == function.defined_storage_index {
+                return true;
+            }
+
+            search_storage = match self.storages[search_storage].get_parent_location() {
+                Some(parent_storage_index) if parent_storage_index != search_storage => parent_storage_index,
+                _ => return false
+            };
+        }
+    }
+
+    /* 'kullan' lines of the file that the storage belongs to */
+    fn file_imports(&self, start_storage_index: usize) -> Option<&Vec<ModuleImport>> {
+        let mut search_storage = start_storage_index;
+        loop {
+            if let Some(imports) = self.imports.get(&search_storage) {
+                return Some(imports);
+            }
+
+            search_storage = match self.storages[search_storage].get_parent_location() {
+                Some(parent_storage_index) if parent_storage_index != search_storage => parent_storage_index,
+                _ => return None
+            };
+        }
+    }
+
+    /// Module path of a name that is taken with 'kullan'. Alias at the start of the path is replaced with the module path,
+    /// names that are written in '{}' are found when they are used without a module path.
+    pub fn imported_path(&self, name: &str, module_path: &[String], start_storage_index: usize) -> Option<Vec<String>> {
+        let imports = self.file_imports(start_storage_index)?;
+        if let Some(first) = module_path.first() {
+            if let Some(import) = imports.iter().find(|import| import.alias.as_ref() == Some(first)) {
+                return Some([&import.path[..], &module_path[1..]].concat());
+            }
+        }
+
+        imports.iter()
+            .find(|import| import.file == module_path && import.names.iter().any(|imported| imported == name))
+            .map(|import| import.path.clone())
+    }
+
+    /// Error of a function that could not be found. Private functions of the modules are reported as private.
+    pub fn function_not_found(&self, name: &str, module_path: &[String], start_storage_index: usize) -> KaramelErrorType {
+        let module_path = &self.imported_path(name, module_path, start_storage_index).unwrap_or_else(|| module_path.to_vec());
+        if let Some(error) = self.permission_error(module_path) {
+            return error;
+        }
+
+        let private = self.modules.iter().any(|(_, module)| module.get_path() == module_path && module.get_method(name).is_some_and(|function| function.flags.contains(FunctionFlag::PRIVATE)));
+        match private {
+            true => KaramelErrorType::FunctionIsPrivate(format!("{}::{}", module_path.join("::"), name)),
+            false => KaramelErrorType::FunctionNotFound(name.to_string())
+        }
+    }
+
     pub fn get_function<T: Borrow<String>>(&self, name: T, module_path: &Vec<String>, start_storage_index: usize) -> Option<Rc<FunctionReference>> {
+        let name = name.borrow();
+        self.find_function(name, module_path, start_storage_index).or_else(|| {
+            let module_path = self.imported_path(name, module_path, start_storage_index)?;
+            self.find_function(name, &module_path, start_storage_index)
+        })
+    }
+
+    fn find_function(&self, name: &String, module_path: &Vec<String>, start_storage_index: usize) -> Option<Rc<FunctionReference>> {
         let mut search_storage = start_storage_index;
         loop {
             /* Search function with storage */
@@ -123,14 +482,14 @@ impl  KaramelCompilerContext {
                     let result = match &function_reference.callback {
                         FunctionType::Native(_) =>
                             function_reference.module.get_path() == module_path && 
-                            &function_reference.name == name.borrow(),
+                            &function_reference.name == name,
                         FunctionType::Opcode => 
-                            &function_reference.name == name.borrow() && 
+                            &function_reference.name == name && 
                             function_reference.module.get_path() == module_path && 
                             (function_reference.defined_storage_index == search_storage || function_reference.flags.contains(FunctionFlag::MODULE_LEVEL))
                     };
 
-                    if result {
+                    if result && self.is_visible(function_reference, start_storage_index) {
                         return Some(function_reference.clone())
                     }
                 }
@@ -146,6 +505,181 @@ impl  KaramelCompilerContext {
         }
     }
 
+    /// Function of the main code from the constants. Functions of a loaded program are not in the modules, they are found with this.
+    pub fn get_main_function(&self, name: &str) -> Option<VmObject> {
+        self.storages.first()?.constants.iter().copied().find(|constant| match &*constant.deref() {
+            KaramelPrimative::Function(reference, None) => reference.name == name,
+            _ => false
+        })
+    }
+
+    pub fn get_constant<T: Borrow<String>>(&self, name: T, module_path: &Vec<String>) -> Option<Rc<KaramelPrimative>> {
+        let name = name.borrow();
+        self.modules.iter().find_map(|(_, module)| match module.get_path() == module_path {
+            true => module.get_constant(name),
+            false => None
+        }).or_else(|| self.get_enum_variant(name, module_path))
+    }
+
+    /// Constant of a module that is taken with 'kullan', 'm::pi' with an alias or only 'pi'.
+    pub fn get_imported_constant(&self, name: &str, module_path: &[String], start_storage_index: usize) -> Option<Rc<KaramelPrimative>> {
+        let module_path = self.imported_path(name, module_path, start_storage_index)?;
+        self.get_constant(name.to_string(), &module_path)
+    }
+
+    /// 'Renk::Kırmızı' is the 'Kırmızı' variant of the 'Renk' definition.
+    pub fn get_enum_variant(&self, name: &str, module_path: &[String]) -> Option<Rc<KaramelPrimative>> {
+        match module_path {
+            [enum_name] => self.enums.get(enum_name)?.iter().find(|variant| matches!(&***variant, KaramelPrimative::Variant(variant) if variant.name == name)).cloned(),
+            _ => None
+        }
+    }
+
+    /// Registers the 'seçenekler' definitions before the storages are built, variants can be used before their definitions.
+    pub fn register_enums(&mut self, ast: &KaramelAstType) -> Result<(), KaramelErrorType> {
+        let mut collector = EnumCollector::default();
+        let _ = collector.visit(ast);
+
+        self.enums.clear();
+        for (enum_name, variants) in collector.enums.into_iter() {
+            if self.enums.contains_key(&enum_name) {
+                return Err(KaramelErrorType::EnumAlreadyDefined(enum_name));
+            }
+
+            let variants = variants.into_iter().enumerate()
+                .map(|(index, name)| Rc::new(KaramelPrimative::Variant(Rc::new(EnumVariant { enum_name: enum_name.to_string(), name, index }))))
+                .collect();
+            self.enums.insert(enum_name, variants);
+        }
+        Ok(())
+    }
+
+    /// In teaching mode the constants of the 'sabitler' module can be used without the module name.
+    pub fn get_teaching_constant<T: Borrow<String>>(&self, name: T) -> Option<Rc<KaramelPrimative>> {
+        match self.teaching_mode {
+            true => self.get_constant(name, &vec!["sabitler".to_string()]),
+            false => None
+        }
+    }
+
+    /// Matches the statements with their source positions. Positions are collected by the syntax parser in the same order.
+    pub fn register_statement_positions(&mut self, ast: &KaramelAstType, positions: &[Option<SourcePosition>]) {
+        let mut collector = StatementCollector::default();
+        let _ = collector.visit_body(ast);
+
+        self.statement_positions = collector.statements.into_iter().zip(positions.iter())
+            .filter_map(|(statement, position)| position.map(|position| (statement, position)))
+            .collect();
+    }
+
+    /// Takes the texts of the 'doğrula' conditions from the source.
+    pub fn register_assert_expressions(&mut self, source: &str, expressions: &[ExpressionSpan]) {
+        for expression in expressions.iter() {
+            self.assert_expressions.insert(expression.statement, expression.text(source));
+        }
+    }
+
+    pub fn get_statement_position(&self, statement: &KaramelAstType) -> Option<SourcePosition> {
+        self.statement_positions.get(&(statement as *const KaramelAstType as usize)).copied()
+    }
+
+    /// Name of the variable slot that the symbol uses. Block variables have hidden names, other symbols use their own names.
+    pub fn get_variable_name(&self, storage_index: usize, symbol: &KaramelAstType, name: &str) -> Rc<String> {
+        match self.local_variables.get(&(storage_index, symbol as *const KaramelAstType as usize)) {
+            Some(hidden) => hidden.clone(),
+            None => intern(name)
+        }
+    }
+
+    /// Slot that keeps the result of the decorators. Only the storage that defines the decorated function has it.
+    pub fn get_decorated_location(&self, storage_index: usize, name: &str) -> Option<u16> {
+        self.storages[storage_index].get_variable_location(&decorated_name(name))
+    }
+
+    pub fn get_statement_line(&self, statement: &KaramelAstType) -> Option<u32> {
+        self.get_statement_position(statement).map(|position| position.line)
+    }
+
+    /// Releases the values that the program can not reach anymore. Stack and the constants are the roots.
+    /// Returns the count of the released values.
+    ///
+    /// # Safety
+    /// Every value in use must be at the stack or at the constants, so it can only be called between the opcodes or from a native function.
+    pub unsafe fn collect_garbage(&self) -> usize {
+        let constants = self.storages.iter().flat_map(|storage| storage.constants.iter().copied());
+        gc::collect(self.stack.iter().copied().chain(constants))
+    }
+
+    /// Counts the size of a new value. Released values are not subtracted, so the limit is for the whole execution.
+    pub fn allocate(&self, size: usize) -> Result<(), KaramelErrorType> {
+        if let Some(limit) = self.allocation_limit {
+            let allocated = self.allocated_bytes.get().saturating_add(size);
+            if allocated > limit {
+                return Err(KaramelErrorType::MemoryLimitExceeded(limit));
+            }
+            self.allocated_bytes.set(allocated);
+        }
+        Ok(())
+    }
+
+    /// Running function calls, the main code is the first one. Line of a caller is the line of the call.
+    pub fn call_stack(&self) -> Vec<CallFrame> {
+        let opcodes = self.opcodes.as_ptr();
+        let mut frames = Vec::with_capacity(self.scope_index + 1);
+
+        for index in 0..=self.scope_index {
+            let scope = &self.scopes[index];
+            let location = match index < self.scope_index {
+                true => self.scopes[index + 1].location as *const u8,
+                false => self.opcodes_ptr as *const u8
+            };
+
+            let position = match location.is_null() {
+                true => None,
+                false => self.opcode_generator.find_position(unsafe { location.offset_from(opcodes) } as usize)
+            };
+
+            let (function, arguments) = match scope.function.is_null() {
+                true => (None, Vec::new()),
+                false => {
+                    let function = unsafe { &*scope.function };
+                    let arguments = function.arguments.iter().enumerate()
+                        .map(|(index, name)| (name.to_string(), frame_argument(unsafe { *scope.top_stack.add(index) })))
+                        .collect();
+                    (Some(function.name.to_string()), arguments)
+                }
+            };
+
+            frames.push(CallFrame {
+                function,
+                line: position.map(|position| position.line),
+                column: position.map(|position| position.column),
+                arguments
+            });
+        }
+        frames
+    }
+
+    /// Called function gets the 'size' slots after the stack pointer, a few more are kept for its temporary values
+    pub fn has_stack_space(&self, size: usize) -> bool {
+        let used = unsafe { self.stack_ptr.offset_from(self.stack.as_ptr()) } as usize;
+        used + size + STACK_RESERVE <= self.stack.len()
+    }
+
+    /// Adds a new loop counter and returns its index.
+    pub fn add_loop_counter(&mut self, loop_statement: &KaramelAstType) -> u16 {
+        let line = self.get_statement_line(loop_statement).unwrap_or_default();
+        self.loop_counters.push(LoopCounter { line, iterations: 0 });
+        (self.loop_counters.len() - 1) as u16
+    }
+
+    /// Adds a new inline cache and returns its index. Call sites after the first 65536 are not cached.
+    pub fn add_inline_cache(&mut self) -> Option<u16> {
+        let index = u16::try_from(self.inline_caches.len()).ok()?;
+        self.inline_caches.push(InlineCache::default());
+        Some(index)
+    }
+
     pub fn get_class(&self, value: &KaramelPrimative) -> Rc<dyn Class > {
         unsafe {
             self.primative_classes.get_unchecked(value.discriminant()).clone()
@@ -163,4 +697,4 @@ impl  KaramelCompilerContext {
     pub fn reset(&mut self) {
         self.opcodes = Vec::new();
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,184 @@
+use std::cell::RefCell;
+use std::cmp::Ordering;
+use std::fmt;
+use std::rc::Rc;
+
+use crate::compiler::value::{KaramelPrimative, EMPTY_OBJECT};
+use crate::types::VmObject;
+use crate::error::KaramelErrorType;
+
+/// Key of the dict items. Texts, bytes, numbers, bools, 'boş' and the frozen lists or dicts that contain them can be
+/// used as keys. Keys are compared by their values, two frozen lists with the same items are the same key.
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub enum DictKey {
+    Empty,
+    Bool(bool),
+
+    /* Bits of the number, 0 and -0 are the same key */
+    Number(u64),
+    Text(Rc<String>),
+    Bytes(Rc<Vec<u8>>),
+    List(Vec<DictKey>),
+
+    /* Items are sorted, so the same items give the same key */
+    Dict(Vec<(DictKey, DictKey)>)
+}
+
+impl DictKey {
+    /// None for the values that can be changed, like lists and dicts that are not frozen
+    pub fn from_value(value: &KaramelPrimative) -> Option<DictKey> {
+        match value {
+            KaramelPrimative::Empty => Some(DictKey::Empty),
+            KaramelPrimative::Bool(value) => Some(DictKey::Bool(*value)),
+            KaramelPrimative::Number(number) if number.is_nan() => None,
+            KaramelPrimative::Number(number) => Some(DictKey::Number((number + 0.0).to_bits())),
+            KaramelPrimative::Text(text) => Some(DictKey::Text(text.clone())),
+            KaramelPrimative::Bytes(bytes) => Some(DictKey::Bytes(bytes.clone())),
+            KaramelPrimative::Frozen(inner) => match &*inner.deref() {
+                KaramelPrimative::List(items) => items.borrow().iter().map(|item| DictKey::from_value(&item.deref())).collect::<Option<_>>().map(DictKey::List),
+                KaramelPrimative::Dict(items) => {
+                    let mut items = items.borrow().iter().map(|(key, item)| Some((key.clone(), DictKey::from_value(&item.deref())?))).collect::<Option<Vec<_>>>()?;
+                    items.sort();
+                    Some(DictKey::Dict(items))
+                },
+                _ => None
+            },
+            _ => None
+        }
+    }
+
+    pub fn try_from_value(value: &Rc<KaramelPrimative>) -> Result<DictKey, KaramelErrorType> {
+        DictKey::from_value(value).ok_or_else(|| KaramelErrorType::DictionaryKeyNotHashable(value.clone()))
+    }
+
+    /// Value of the key, lists and dicts are given back frozen
+    pub fn to_value(&self) -> VmObject {
+        match self {
+            DictKey::Empty => EMPTY_OBJECT,
+            DictKey::Bool(value) => VmObject::from(*value),
+            DictKey::Number(bits) => VmObject::from(f64::from_bits(*bits)),
+            DictKey::Text(text) => VmObject::from(text.clone()),
+            DictKey::Bytes(bytes) => VmObject::native_convert(KaramelPrimative::Bytes(bytes.clone())),
+            DictKey::List(items) => Self::frozen(KaramelPrimative::List(RefCell::new(items.iter().map(|item| item.to_value()).collect()))),
+            DictKey::Dict(items) => Self::frozen(KaramelPrimative::Dict(RefCell::new(items.iter().map(|(key, item)| (key.clone(), item.to_value())).collect())))
+        }
+    }
+
+    pub fn as_text(&self) -> Option<&Rc<String>> {
+        match self {
+            DictKey::Text(text) => Some(text),
+            _ => None
+        }
+    }
+
+    fn frozen(inner: KaramelPrimative) -> VmObject {
+        VmObject::native_convert(KaramelPrimative::Frozen(VmObject::native_convert(inner)))
+    }
+
+    fn type_order(&self) -> u8 {
+        match self {
+            DictKey::Empty => 0,
+            DictKey::Bool(_) => 1,
+            DictKey::Number(_) => 2,
+            DictKey::Text(_) => 3,
+            DictKey::Bytes(_) => 4,
+            DictKey::List(_) => 5,
+            DictKey::Dict(_) => 6
+        }
+    }
+}
+
+/* Numbers are ordered by their values, not by their bits */
+impl Ord for DictKey {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (DictKey::Bool(left), DictKey::Bool(right)) => left.cmp(right),
+            (DictKey::Number(left), DictKey::Number(right)) => f64::from_bits(*left).total_cmp(&f64::from_bits(*right)),
+            (DictKey::Text(left), DictKey::Text(right)) => left.cmp(right),
+            (DictKey::Bytes(left), DictKey::Bytes(right)) => left.cmp(right),
+            (DictKey::List(left), DictKey::List(right)) => left.cmp(right),
+            (DictKey::Dict(left), DictKey::Dict(right)) => left.cmp(right),
+            _ => self.type_order().cmp(&other.type_order())
+        }
+    }
+}
+
+impl PartialOrd for DictKey {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl From<&str> for DictKey {
+    fn from(source: &str) -> Self {
+        DictKey::Text(Rc::new(source.to_string()))
+    }
+}
+
+impl From<String> for DictKey {
+    fn from(source: String) -> Self {
+        DictKey::Text(Rc::new(source))
+    }
+}
+
+impl From<Rc<String>> for DictKey {
+    fn from(source: Rc<String>) -> Self {
+        DictKey::Text(source)
+    }
+}
+
+impl fmt::Debug for DictKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DictKey::Empty => KaramelPrimative::Empty.format(f),
+            DictKey::Bool(value) => KaramelPrimative::Bool(*value).format(f),
+            DictKey::Number(bits) => KaramelPrimative::Number(f64::from_bits(*bits)).format(f),
+            DictKey::Text(text) => write!(f, "{:?}", text),
+            DictKey::Bytes(bytes) => KaramelPrimative::Bytes(bytes.clone()).format(f),
+            DictKey::List(items) => f.debug_list().entries(items).finish(),
+            DictKey::Dict(items) => f.debug_map().entries(items.iter().map(|(key, item)| (key, item))).finish()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use super::*;
+
+    use crate::arc_text;
+    use crate::primative_text;
+    use crate::arc_number;
+
+    fn frozen_list(items: Vec<VmObject>) -> KaramelPrimative {
+        KaramelPrimative::Frozen(VmObject::from(items))
+    }
+
+    #[test]
+    fn test_frozen_list_key() {
+        let key = DictKey::from_value(&frozen_list(vec![arc_number!(1), arc_text!("a")])).unwrap();
+        assert_eq!(key, DictKey::from_value(&frozen_list(vec![arc_number!(1), arc_text!("a")])).unwrap());
+        assert_ne!(key, DictKey::from_value(&frozen_list(vec![arc_text!("a"), arc_number!(1)])).unwrap());
+        assert_eq!(format!("{:?}", key), "[1, \"a\"]");
+
+        let mut dict = HashMap::new();
+        dict.insert(key.clone(), 5);
+        assert_eq!(dict.get(&DictKey::from_value(&frozen_list(vec![arc_number!(1), arc_text!("a")])).unwrap()), Some(&5));
+        assert!(matches!(&*key.to_value().deref(), KaramelPrimative::Frozen(_)));
+    }
+
+    #[test]
+    fn test_invalid_keys() {
+        assert_eq!(DictKey::from_value(&KaramelPrimative::List(RefCell::new(Vec::new()))), None);
+        assert_eq!(DictKey::from_value(&KaramelPrimative::Number(f64::NAN)), None);
+        assert_eq!(DictKey::from_value(&frozen_list(vec![VmObject::from(Vec::new())])), None);
+        assert_eq!(DictKey::from_value(&KaramelPrimative::Number(-0.0)), DictKey::from_value(&KaramelPrimative::Number(0.0)));
+    }
+
+    #[test]
+    fn test_key_order() {
+        let mut keys = vec![DictKey::from("b"), DictKey::Number(2.0_f64.to_bits()), DictKey::Number((-1.0_f64).to_bits()), DictKey::from("a")];
+        keys.sort();
+        assert_eq!(keys, vec![DictKey::Number((-1.0_f64).to_bits()), DictKey::Number(2.0_f64.to_bits()), DictKey::from("a"), DictKey::from("b")]);
+    }
+}
@@ -4,17 +4,23 @@ use std::cell::RefCell;
 use std::cell::Cell;
 use std::slice::Iter;
 use std::iter::Take;
+use std::ptr;
+use std::mem;
+use std::io;
 use bitflags::bitflags;
 
 use crate::buildin::{DummyModule, Module};
-use crate::compiler::scope::Scope;
+use crate::compiler::scope::{Scope, CallFrame};
 use crate::error::KaramelErrorType;
 use crate::{inc_memory_index, dec_memory_index, get_memory_index};
 use crate::types::*;
 use crate::compiler::context::KaramelCompilerContext;
+use crate::compiler::semantics::deep_copy_with_size;
+use crate::file::{FileSystem, OsFileSystem};
+use crate::interner::intern;
 
 use super::module::OpcodeModule;
-use super::{KaramelPrimative, StaticStorage};
+use super::{KaramelPrimative, StaticStorage, EMPTY_OBJECT};
 use super::ast::KaramelAstType;
 use super::storage_builder::StorageBuilder;
 
@@ -30,7 +36,8 @@ pub struct FunctionParameter<'a> {
     last_position: usize, 
     arg_size: u8,
     stdout: &'a Option<RefCell<String>>,
-    stderr: &'a Option<RefCell<String>>
+    stderr: &'a Option<RefCell<String>>,
+    context: *const KaramelCompilerContext
 }
 
 pub struct FunctionParameterIterator<'a> {
@@ -39,7 +46,57 @@ pub struct FunctionParameterIterator<'a> {
 
 impl<'a> FunctionParameter<'a> {
     pub fn new(stack: &'a [VmObject], source: Option<VmObject>, last_position: usize, arg_size: u8, stdout: &'a Option<RefCell<String>>, stderr: &'a Option<RefCell<String>>) -> Self {
-        FunctionParameter { stack, source, last_position, arg_size, stdout, stderr }
+        FunctionParameter { stack, source, last_position, arg_size, stdout, stderr, context: ptr::null() }
+    }
+
+    /// Gives access to the running program, native functions that inspect the call stack need it.
+    pub fn with_context(mut self, context: *const KaramelCompilerContext) -> Self {
+        self.context = context;
+        self
+    }
+
+    /// Files of the running program. Files of the operating system when the parameter is not created by the vm.
+    pub fn file_system(&self) -> Rc<dyn FileSystem> {
+        match self.context.is_null() {
+            true => Rc::new(OsFileSystem::default()),
+            false => unsafe { (*self.context).file_system.clone() }
+        }
+    }
+
+    /// Running function calls, the main code is the first one. Empty when the parameter is not created by the vm.
+    pub fn call_stack(&self) -> Vec<CallFrame> {
+        match self.context.is_null() {
+            true => Vec::new(),
+            false => unsafe { (*self.context).call_stack() }
+        }
+    }
+
+    /// Runs the garbage collector and returns the count of the released values. Nothing is collected when the parameter is not created by the vm.
+    pub fn collect_garbage(&self) -> usize {
+        match self.context.is_null() {
+            true => 0,
+            false => unsafe { (*self.context).collect_garbage() }
+        }
+    }
+
+    /// Counts the size of a new value for the memory limit of the running program. Nothing is counted when the parameter is not created by the vm.
+    pub fn allocate(&self, size: usize) -> Result<(), KaramelErrorType> {
+        match self.context.is_null() {
+            true => Ok(()),
+            false => unsafe { (*self.context).allocate(size) }
+        }
+    }
+
+    /// Copy of the value with its collections, the copied items are counted for the memory limit.
+    pub fn deep_copy(&self, value: VmObject) -> NativeCallResult {
+        let (object, size) = deep_copy_with_size(value);
+        self.allocate(size * mem::size_of::<VmObject>())?;
+        Ok(object)
+    }
+
+    /// Context of the vm that called the function. Empty when the parameter is not created by the vm.
+    pub fn context(&self) -> Option<&KaramelCompilerContext> {
+        unsafe { self.context.as_ref() }
     }
 
     pub fn source(&self) -> Option<VmObject> {
@@ -53,6 +110,27 @@ impl<'a> FunctionParameter<'a> {
         self.arg_size
     }
 
+    /// Next line of the input that is given to the running program, standard input is read when it is not given.
+    /// Returns an empty line at the end of the input.
+    pub fn read_line(&self) -> Option<String> {
+        let input = match self.context.is_null() {
+            true => None,
+            false => unsafe { (*self.context).stdin.as_ref() }
+        };
+
+        match input {
+            Some(input) => {
+                let mut input = input.borrow_mut();
+                let end = input.find('\n').map_or(input.len(), |index| index + 1);
+                Some(input.drain(..end).collect())
+            },
+            None => {
+                let mut line = String::new();
+                io::stdin().read_line(&mut line).ok().map(|_| line)
+            }
+        }
+    }
+
     pub fn write_to_stdout<'b>(&self, data: &'b str) {
         match self.stdout {
             Some(out) => match out.try_borrow_mut() {
@@ -86,6 +164,7 @@ bitflags! {
         const STATIC       = 0b00000001;
         const IN_CLASS     = 0b00000010;
         const MODULE_LEVEL = 0b00000100;
+        const PRIVATE      = 0b00001000;
     }
 }
 
@@ -100,7 +179,16 @@ pub struct FunctionReference {
     pub opcode_location: Cell<usize>,
     pub used_locations: RefCell<Vec<u16>>,
     pub opcode_body: Option<Rc<KaramelAstType>>,
-    pub module: Rc<dyn Module>
+    pub module: Rc<dyn Module>,
+
+    /// '##' comments written before the function definition.
+    pub documentation: RefCell<Option<Rc<String>>>,
+
+    /// Source line of the 'fonk' keyword, zero based.
+    pub definition_position: Cell<Option<SourcePosition>>,
+
+    /// Types written next to the arguments, 'fonk topla(a: sayı, b)'. Same order with the arguments.
+    pub argument_types: RefCell<Vec<Option<String>>>
 }
 
 unsafe impl Send for FunctionReference {}
@@ -118,10 +206,16 @@ impl Default for FunctionType {
 
 impl FunctionReference {
     pub fn execute(&self, compiler: &mut KaramelCompilerContext, base: Option<VmObject>) -> Result<(), KaramelErrorType>{
+        let argument_size = unsafe { *compiler.opcodes_ptr.offset(1) };
+        self.execute_with_arguments(compiler, base, argument_size)
+    }
+
+    /// Calls the function with the last 'argument_size' values of the stack. Bound functions have more arguments than the call opcode.
+    pub fn execute_with_arguments(&self, compiler: &mut KaramelCompilerContext, base: Option<VmObject>, argument_size: u8) -> Result<(), KaramelErrorType>{
         unsafe {
             match self.callback {
-                FunctionType::Native(func) => FunctionReference::native_function_call(&self, func, compiler, base),
-                FunctionType::Opcode => FunctionReference::opcode_function_call(&self,  compiler)
+                FunctionType::Native(func) => FunctionReference::native_function_call(self, func, compiler, base, argument_size),
+                FunctionType::Opcode => FunctionReference::opcode_function_call(self, compiler, argument_size)
             }
         }
     }
@@ -137,7 +231,10 @@ impl FunctionReference {
             used_locations: RefCell::new(Vec::new()),
             defined_storage_index: 0,
             opcode_body: None,
-            module: Rc::new(DummyModule::new())
+            module: Rc::new(DummyModule::new()),
+            documentation: RefCell::new(None),
+            definition_position: Cell::new(None),
+            argument_types: RefCell::new(Vec::new())
         };
         Rc::new(reference)
     }
@@ -153,15 +250,18 @@ impl FunctionReference {
             used_locations: RefCell::new(Vec::new()),
             defined_storage_index: 0,
             opcode_body: None,
-            module
+            module,
+            documentation: RefCell::new(None),
+            definition_position: Cell::new(None),
+            argument_types: RefCell::new(Vec::new())
         };
         Rc::new(reference)
     }
 
-    pub fn opcode_function(name: String, arguments: Vec<String>, body: Rc<KaramelAstType>, module: Rc<dyn Module>, storage_index: usize, defined_storage_index: usize, module_level: bool) -> Rc<FunctionReference> {
-        let mut reference = FunctionReference {
+    pub fn opcode_function(name: String, arguments: Vec<String>, body: Rc<KaramelAstType>, module: Rc<dyn Module>, storage_index: usize, defined_storage_index: usize, flags: FunctionFlag) -> Rc<FunctionReference> {
+        let reference = FunctionReference {
             callback: FunctionType::Opcode,
-            flags: FunctionFlag::STATIC,
+            flags: FunctionFlag::STATIC | flags,
             module,
             name,
             arguments,
@@ -169,20 +269,18 @@ impl FunctionReference {
             defined_storage_index,
             opcode_location: Cell::new(0),
             used_locations: RefCell::new(Vec::new()),
-            opcode_body: Some(body.clone())
+            opcode_body: Some(body.clone()),
+            documentation: RefCell::new(None),
+            definition_position: Cell::new(None),
+            argument_types: RefCell::new(Vec::new())
         };
 
-        if module_level {
-            reference.flags = reference.flags | FunctionFlag::MODULE_LEVEL;
-        }
-
         Rc::new(reference)
     }
 
-    unsafe fn native_function_call(reference: &FunctionReference, func: NativeCall, compiler: &mut KaramelCompilerContext, source: Option<VmObject>) -> Result<(), KaramelErrorType> {            
-        let total_args                 = *compiler.opcodes_ptr.offset(1);
+    unsafe fn native_function_call(reference: &FunctionReference, func: NativeCall, compiler: &mut KaramelCompilerContext, source: Option<VmObject>, total_args: u8) -> Result<(), KaramelErrorType> {            
         let call_return_assign_to_temp = *compiler.opcodes_ptr.offset(2) != 0;
-        let parameter = FunctionParameter::new(&compiler.stack, source, get_memory_index!(compiler) as usize, karamel_dbg!(total_args), &compiler.stdout, &compiler.stderr);
+        let parameter = FunctionParameter::new(&compiler.stack, source, get_memory_index!(compiler) as usize, karamel_dbg!(total_args), &compiler.stdout, &compiler.stderr).with_context(compiler as *const KaramelCompilerContext);
 
         dump_data!(compiler, "native_function_call");
         
@@ -200,29 +298,36 @@ impl FunctionReference {
             },
             Err(error) => {
                 dec_memory_index!(compiler, total_args as usize);
-                println!("{:?}", error);
+                log::debug!("{:?}", error);
                 Err(error)
             }
         }
     }
 
-    fn opcode_function_call(reference: &FunctionReference, options: &mut KaramelCompilerContext) -> Result<(), KaramelErrorType> {
+    fn opcode_function_call(reference: &FunctionReference, options: &mut KaramelCompilerContext, argument_size: u8) -> Result<(), KaramelErrorType> {
         unsafe {
-            let argument_size              = *options.opcodes_ptr.offset(1);
             let call_return_assign_to_temp = *options.opcodes_ptr.offset(2) != 0;
             let old_index                  = options.opcodes_ptr.offset(2);
             let location = reference.opcode_location.get() as isize;
-            options.opcodes_ptr            = options.opcodes_top_ptr.offset(location);
-            options.scope_index           += 1;
 
-            if argument_size != *options.opcodes_ptr {
+            /* Vm state should stay at the caller, so the error can be caught by the caller */
+            let function_ptr = options.opcodes_top_ptr.offset(location);
+            if argument_size != *function_ptr {
                 return Err(KaramelErrorType::FunctionArgumentNotMatching {
                     function: reference.name.to_string(),
                     expected: argument_size, 
-                    found: *options.opcodes_ptr
+                    found: *function_ptr
                 });
             }
 
+            let storage = options.storages_ptr.add(reference.storage_index);
+            if options.scope_index >= options.max_call_depth || !options.has_stack_space((*storage).get_variable_size() as usize) {
+                return Err(KaramelErrorType::StackOverflow(options.max_call_depth));
+            }
+
+            options.opcodes_ptr            = function_ptr;
+            options.scope_index           += 1;
+
             dec_memory_index!(options, argument_size.into());
             dump_data!(options, "Current");
 
@@ -232,54 +337,111 @@ impl FunctionReference {
             }
 
             let mut scope = options.scopes_ptr.add(options.scope_index);
-            let storage = options.storages_ptr.add(reference.storage_index);
 
             (*scope).constant_ptr = (*storage).constants.as_ptr();
             (*scope).top_stack = options.stack_ptr;
+            (*scope).storage_index = reference.storage_index;
+            (*scope).function = reference as *const FunctionReference;
 
             (*scope).location                   = old_index;
             (*scope).call_return_assign_to_temp = call_return_assign_to_temp;
+            (*scope).deferred.clear();
+            (*scope).defer_error = None;
 
             options.current_scope = scope;
+
+            if let Some(events) = options.events.as_mut() {
+                let arguments = reference.arguments.iter().enumerate().map(|(index, argument)| (argument.to_string(), *(*scope).top_stack.add(index))).collect();
+                events.function_called(&reference.name, arguments);
+            }
+
             inc_memory_index!(options, argument_size.into());
+
+            /* Local variables are kept after the arguments, stack of the function starts after them */
+            let local_size = ((*storage).get_variable_size() as usize).saturating_sub(argument_size.into());
+            for index in 0..local_size {
+                *options.stack_ptr.add(index) = EMPTY_OBJECT;
+            }
+            inc_memory_index!(options, local_size);
         }
         Ok(())
     }
 }
 
 pub fn find_function_definition_type(module: Rc<OpcodeModule>, ast: Rc<KaramelAstType>, options: &mut KaramelCompilerContext, current_storage_index: usize, module_level: bool) -> CompilerResult {
+    /* Functions of the body are registered before their storages are built, so they can call and use each other in any order */
+    let mut definitions = Vec::new();
+    register_function_definitions(module.clone(), &ast, options, current_storage_index, module_level, &mut definitions)?;
+
+    for (function, definition) in definitions.into_iter() {
+        prepare_function_definition(module.clone(), function, &definition, options)?;
+    }
+    Ok(())
+}
+
+fn register_function_definitions(module: Rc<OpcodeModule>, ast: &Rc<KaramelAstType>, options: &mut KaramelCompilerContext, current_storage_index: usize, module_level: bool, definitions: &mut Vec<(Rc<FunctionReference>, Rc<KaramelAstType>)>) -> CompilerResult {
     match ast.borrow() {
-        KaramelAstType::FunctionDefination { name, arguments, body  } => {
+        KaramelAstType::FunctionDefination { name, arguments, argument_types, body, documentation, private, .. } => {
+            /* Argument count is written into a single byte of the function definition */
+            if arguments.len() > u8::MAX as usize {
+                return Err(KaramelErrorType::TooManyArguments(u8::MAX as usize));
+            }
+
             /* Create new storage for new function */
             let new_storage_index = options.storages.len();
             options.storages.push(StaticStorage::new(new_storage_index));
             options.storages[new_storage_index].set_parent_location(current_storage_index);
 
-            let function = FunctionReference::opcode_function(name.to_string(), arguments.to_vec(), body.clone(), module.clone(), new_storage_index, current_storage_index, module_level);
+            let mut flags = FunctionFlag::NONE;
+            flags.set(FunctionFlag::MODULE_LEVEL, module_level);
+            flags.set(FunctionFlag::PRIVATE, *private);
+
+            let function = FunctionReference::opcode_function(name.to_string(), arguments.to_vec(), body.clone(), module.clone(), new_storage_index, current_storage_index, flags);
+            *function.documentation.borrow_mut() = documentation.clone();
+            *function.argument_types.borrow_mut() = argument_types.to_vec();
+            function.definition_position.set(options.get_statement_position(ast.borrow()));
             let old_function = module.functions.borrow_mut().insert(name.to_string(), function.clone());
 
             if let Some(_) = old_function {
                 return Err(KaramelErrorType::FunctionAlreadyDefined(name.to_string()));
             }
-            
-            find_function_definition_type(module.clone(), body.clone(), options, new_storage_index, false)?;
-
-            let storage_builder = StorageBuilder::new();
-            storage_builder.prepare(module.clone(), ast.borrow(), new_storage_index, options)?;
-
-            options.storages[current_storage_index].add_constant(Rc::new(KaramelPrimative::Function(function.clone(), None)));
 
-            for argument in arguments {
-                options.storages[new_storage_index].add_variable(argument);
-            }
+            options.storages[current_storage_index].add_constant(Rc::new(KaramelPrimative::Function(function.clone(), None)))?;
+            definitions.push((function, ast.clone()));
         },
         KaramelAstType::Block(blocks) => {
             for block in blocks {
-                find_function_definition_type(module.clone(), block.clone(), options, current_storage_index, module_level)?;
+                register_function_definitions(module.clone(), block, options, current_storage_index, module_level, definitions)?;
             }
         },
         _ => ()
     }
 
+    Ok(())
+}
+
+fn prepare_function_definition(module: Rc<OpcodeModule>, function: Rc<FunctionReference>, definition: &KaramelAstType, options: &mut KaramelCompilerContext) -> CompilerResult {
+    if let KaramelAstType::FunctionDefination { arguments, argument_types, body, .. } = definition {
+        let storage_index = function.storage_index;
+        find_function_definition_type(module.clone(), body.clone(), options, storage_index, false)?;
+
+        /* Arguments are the first variables of the function, body can not take their locations */
+        for argument in arguments {
+            options.storages[storage_index].add_variable(argument)?;
+        }
+
+        let storage_builder = StorageBuilder::new();
+        storage_builder.prepare(module.clone(), definition, storage_index, options)?;
+
+        /* Type guards compare the argument with the type name and report the argument name */
+        if options.type_guards {
+            for (argument, argument_type) in arguments.iter().zip(argument_types.iter()) {
+                if let Some(argument_type) = argument_type {
+                    options.storages[storage_index].add_constant(Rc::new(KaramelPrimative::Text(intern(argument_type.as_str()))))?;
+                    options.storages[storage_index].add_constant(Rc::new(KaramelPrimative::Text(intern(argument.as_str()))))?;
+                }
+            }
+        }
+    }
     Ok(())
 }
\ No newline at end of file
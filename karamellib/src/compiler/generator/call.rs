@@ -2,15 +2,15 @@ use std::{rc::Rc, sync::atomic::{AtomicUsize, Ordering}};
 
 use crate::compiler::VmOpCode;
 
-use super::{DumpBuilder, OpcodeGeneratorTrait};
+use super::{DumpBuilder, OpcodeGeneratorTrait, constant::ConstantGenerator};
 
 #[derive(Clone)]
 
 /// Function call type. Karamel is support two type of function call mechanism
 pub enum CallType {
 
-    /// Call function from memory location
-    Call { constant_location: u8 },
+    /// Call function from memory location. Functions after the first 256 constants are loaded with 'ConstantWide' and called from the stack.
+    Call { constant_location: u16 },
 
     /// Call function from last stack value
     CallStack
@@ -34,9 +34,13 @@ pub struct CallGenerator {
 impl OpcodeGeneratorTrait for CallGenerator {
     fn generate(&self, opcodes: &mut Vec<u8>) {
         match self.call_type {
-            CallType::Call { constant_location } => {
+            CallType::Call { constant_location } if constant_location <= u8::MAX as u16 => {
                 opcodes.push(VmOpCode::Call.into());
-                opcodes.push(constant_location);
+                opcodes.push(constant_location as u8);
+            },
+            CallType::Call { constant_location } => {
+                ConstantGenerator { location: constant_location }.generate(opcodes);
+                opcodes.push(VmOpCode::CallStack.into());
             },
             CallType::CallStack => opcodes.push(VmOpCode::CallStack.into())
         };
@@ -44,15 +48,21 @@ impl OpcodeGeneratorTrait for CallGenerator {
         opcodes.push(self.assign_to_temp.into());
     }
 
-    fn dump<'a>(&self, builder: &'a DumpBuilder, index: Rc<AtomicUsize>, _: &Vec<u8>) {
+    fn dump<'a>(&self, builder: &'a DumpBuilder, index: Rc<AtomicUsize>, opcodes: &Vec<u8>) {
+        if let CallType::Call { constant_location } = self.call_type {
+            if constant_location > u8::MAX as u16 {
+                ConstantGenerator { location: constant_location }.dump(builder, index.clone(), opcodes);
+            }
+        }
+
         let opcode_index = index.fetch_add(3, Ordering::SeqCst);
 
         match self.call_type {
-            CallType::Call { constant_location } => {
+            CallType::Call { constant_location } if constant_location <= u8::MAX as u16 => {
                 index.fetch_add(1, Ordering::SeqCst);
                 builder.add(opcode_index, VmOpCode::Call, constant_location.to_string(), self.argument_size.to_string(), (self.assign_to_temp as u8).to_string());
             },
-            CallType::CallStack => {
+            _ => {
                 builder.add(opcode_index, VmOpCode::CallStack, self.argument_size.to_string(), (self.assign_to_temp as u8).to_string(), "".to_string());
             }
         };
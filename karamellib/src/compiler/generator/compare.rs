@@ -1,23 +1,34 @@
-use std::{rc::Rc, sync::atomic::{AtomicUsize, Ordering}};
+use std::{cell::Cell, rc::Rc, sync::atomic::{AtomicUsize, Ordering}};
 
 use crate::compiler::VmOpCode;
 
-use super::{DumpBuilder, OpcodeGeneratorTrait, OpcodeLocation, opcode_to_location};
+use super::{DumpBuilder, OpcodeGeneratorTrait, OpcodeLocation, opcode_to_location, opcode_to_offset};
 
 
 #[derive(Clone)]
-/// Generate compare opcodes
-pub struct CompareGenerator { pub location: Rc<OpcodeLocation> }
+/// Generate compare opcodes. Location is the distance from the compare opcode to the false block.
+pub struct CompareGenerator { pub location: Rc<OpcodeLocation>, pub wide: Rc<Cell<bool>> }
 impl OpcodeGeneratorTrait for CompareGenerator {
     fn generate(&self, opcodes: &mut Vec<u8>) {
-        opcodes.push(VmOpCode::Compare.into());
-        self.location.apply(opcodes);
+        match self.wide.get() {
+            true => {
+                /* Location is already relative to the compare opcode */
+                opcodes.push(VmOpCode::CompareWide.into());
+                self.location.apply_wide(opcodes, 0);
+            },
+            false => {
+                opcodes.push(VmOpCode::Compare.into());
+                self.location.apply(opcodes);
+            }
+        };
     }
 
     fn dump<'a>(&self, builder: &'a DumpBuilder, index: Rc<AtomicUsize>, opcodes: &Vec<u8>) {
         let opcode_index = index.fetch_add(1, Ordering::SeqCst);
-        let location = opcode_to_location(index, opcodes);
-        builder.add(opcode_index, VmOpCode::Compare, location.to_string(), "".to_string(), "".to_string());
+        match self.wide.get() {
+            true => builder.add(opcode_index, VmOpCode::CompareWide, opcode_to_offset(index, opcodes).to_string(), "".to_string(), "".to_string()),
+            false => builder.add(opcode_index, VmOpCode::Compare, opcode_to_location(index, opcodes).to_string(), "".to_string(), "".to_string())
+        };
     }
 }
 
@@ -36,7 +47,8 @@ mod tests {
         let mut opcodes = Vec::new();
         let location =  Rc::new(OpcodeLocation::new(123));
         let generator = CompareGenerator {
-            location: location.clone()
+            location: location.clone(),
+            wide: Rc::new(Cell::new(false))
         };
 
         generator.generate(&mut opcodes);
@@ -52,7 +64,8 @@ mod tests {
         let mut opcodes = Vec::new();
         let location =  Rc::new(OpcodeLocation::new(123456789));
         let generator = CompareGenerator {
-            location: location.clone()
+            location: location.clone(),
+            wide: Rc::new(Cell::new(false))
         };
 
         generator.generate(&mut opcodes);
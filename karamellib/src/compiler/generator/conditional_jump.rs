@@ -0,0 +1,53 @@
+use std::{cell::Cell, rc::Rc, sync::atomic::{AtomicUsize, Ordering}};
+
+use crate::compiler::VmOpCode;
+
+use super::{DumpBuilder, OpcodeGeneratorTrait, OpcodeLocation, generate_jump_opcode, opcode_to_jump_location};
+
+#[derive(Clone)]
+/// Generate 've' and 'veya' jumps. Opcode is JumpIfFalse or JumpIfTrue.
+pub struct ConditionalJumpGenerator { pub opcode: VmOpCode, pub location: Rc<OpcodeLocation>, pub wide: Rc<Cell<bool>> }
+
+impl ConditionalJumpGenerator {
+    fn wide_opcode(&self) -> VmOpCode {
+        match self.opcode {
+            VmOpCode::JumpIfTrue => VmOpCode::JumpIfTrueWide,
+            _ => VmOpCode::JumpIfFalseWide
+        }
+    }
+}
+impl OpcodeGeneratorTrait for ConditionalJumpGenerator {
+    fn generate(&self, opcodes: &mut Vec<u8>) {
+        generate_jump_opcode(opcodes, self.opcode, self.wide_opcode(), &self.location, self.wide.get());
+    }
+
+    fn dump<'a>(&self, builder: &'a DumpBuilder, index: Rc<AtomicUsize>, opcodes: &Vec<u8>) {
+        let opcode_index = index.fetch_add(1, Ordering::SeqCst);
+        let location = opcode_to_jump_location(opcode_index, index, opcodes, self.wide.get());
+        let opcode = if self.wide.get() { self.wide_opcode() } else { self.opcode };
+        builder.add(opcode_index, opcode, location.to_string(), "".to_string(), "".to_string());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_1() {
+        let mut opcodes = Vec::new();
+        let location =  Rc::new(OpcodeLocation::new(300));
+        let generator = ConditionalJumpGenerator {
+            opcode: VmOpCode::JumpIfFalse,
+            location: location.clone(),
+            wide: Rc::new(Cell::new(false))
+        };
+
+        generator.generate(&mut opcodes);
+
+        assert_eq!(opcodes.len(), 3);
+        assert_eq!(opcodes[0], VmOpCode::JumpIfFalse.into());
+        assert_eq!(opcodes[1], 44);
+        assert_eq!(opcodes[2], 1);
+    }
+}
@@ -1,20 +1,18 @@
-use std::{rc::Rc, sync::atomic::{AtomicUsize, Ordering}};
+use std::{rc::Rc, sync::atomic::AtomicUsize};
 
 use crate::compiler::VmOpCode;
 
-use super::{DumpBuilder, OpcodeGeneratorTrait};
+use super::{DumpBuilder, OpcodeGeneratorTrait, generate_location_opcode, dump_location_opcode};
 
 
 #[derive(Clone)]
-pub struct ConstantGenerator { pub location: u8 }
+pub struct ConstantGenerator { pub location: u16 }
 impl OpcodeGeneratorTrait for ConstantGenerator {
     fn generate(&self, opcodes: &mut Vec<u8>) {
-        opcodes.push(VmOpCode::Constant.into());
-        opcodes.push(self.location);
+        generate_location_opcode(opcodes, VmOpCode::Constant, VmOpCode::ConstantWide, self.location);
     }
 
     fn dump<'a>(&self, builder: &'a DumpBuilder, index: Rc<AtomicUsize>, _: &Vec<u8>) {
-        let opcode_index = index.fetch_add(2, Ordering::SeqCst);
-        builder.add(opcode_index, VmOpCode::Constant, self.location.to_string(), "".to_string(), "".to_string());
+        dump_location_opcode(builder, index, VmOpCode::Constant, VmOpCode::ConstantWide, self.location);
     }
 }
\ No newline at end of file
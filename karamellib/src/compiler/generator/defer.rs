@@ -0,0 +1,39 @@
+use std::{cell::Cell, rc::Rc, sync::atomic::{AtomicUsize, Ordering}};
+
+use crate::compiler::VmOpCode;
+
+use super::{DumpBuilder, OpcodeGeneratorTrait, OpcodeLocation, generate_jump_opcode, opcode_to_jump_location};
+
+#[derive(Clone)]
+/// Generate deferred block opcode. Deferred block starts after the opcode and the location points to the end of the block.
+pub struct DeferGenerator { pub location: Rc<OpcodeLocation>, pub wide: Rc<Cell<bool>> }
+
+impl OpcodeGeneratorTrait for DeferGenerator {
+    fn generate(&self, opcodes: &mut Vec<u8>) {
+        generate_jump_opcode(opcodes, VmOpCode::Defer, VmOpCode::DeferWide, &self.location, self.wide.get());
+    }
+
+    fn dump<'a>(&self, builder: &'a DumpBuilder, index: Rc<AtomicUsize>, opcodes: &Vec<u8>) {
+        let opcode_index = index.fetch_add(1, Ordering::SeqCst);
+        let location = opcode_to_jump_location(opcode_index, index, opcodes, self.wide.get());
+        let opcode = if self.wide.get() { VmOpCode::DeferWide } else { VmOpCode::Defer };
+        builder.add(opcode_index, opcode, location.to_string(), "".to_string(), "".to_string());
+    }
+}
+
+#[derive(Clone)]
+/// Generate the end of the 'birlikte' block. Location points to the start of the deferred 'kapat' call of the block.
+pub struct WithEndGenerator { pub location: Rc<OpcodeLocation>, pub wide: Rc<Cell<bool>> }
+
+impl OpcodeGeneratorTrait for WithEndGenerator {
+    fn generate(&self, opcodes: &mut Vec<u8>) {
+        generate_jump_opcode(opcodes, VmOpCode::WithEnd, VmOpCode::WithEndWide, &self.location, self.wide.get());
+    }
+
+    fn dump(&self, builder: &DumpBuilder, index: Rc<AtomicUsize>, opcodes: &Vec<u8>) {
+        let opcode_index = index.fetch_add(1, Ordering::SeqCst);
+        let location = opcode_to_jump_location(opcode_index, index, opcodes, self.wide.get());
+        let opcode = if self.wide.get() { VmOpCode::WithEndWide } else { VmOpCode::WithEnd };
+        builder.add(opcode_index, opcode, location.to_string(), "".to_string(), "".to_string());
+    }
+}
@@ -7,7 +7,7 @@ use super::{DumpBuilder, OpcodeGeneratorTrait};
 #[derive(Debug)]
 #[derive(Clone)]
 pub struct InitListGenerator {
-    pub argument_size: usize
+    pub argument_size: u16
 }
 
 impl OpcodeGeneratorTrait for InitListGenerator {
@@ -15,10 +15,11 @@ impl OpcodeGeneratorTrait for InitListGenerator {
         opcodes.push(VmOpCode::Init.into());
         opcodes.push(1);
         opcodes.push(self.argument_size as u8);
+        opcodes.push((self.argument_size >> 8) as u8);
     }
 
     fn dump<'a>(&self, builder: &'a DumpBuilder, index: Rc<AtomicUsize>, _: &Vec<u8>) {
-        let opcode_index = index.fetch_add(3, Ordering::SeqCst);
+        let opcode_index = index.fetch_add(4, Ordering::SeqCst);
         builder.add(opcode_index, VmOpCode::Init, "1".to_string(), self.argument_size.to_string(), "".to_string());
     }
 }
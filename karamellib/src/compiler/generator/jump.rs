@@ -1,21 +1,21 @@
-use std::{rc::Rc, sync::atomic::{AtomicUsize, Ordering}};
+use std::{cell::Cell, rc::Rc, sync::atomic::{AtomicUsize, Ordering}};
 
 use crate::compiler::VmOpCode;
 
-use super::{DumpBuilder, OpcodeGeneratorTrait, OpcodeLocation, opcode_to_location};
+use super::{DumpBuilder, OpcodeGeneratorTrait, OpcodeLocation, generate_jump_opcode, opcode_to_jump_location};
 
 #[derive(Clone)]
 /// Generate jump opcodes. 
-pub struct JumpGenerator { pub location:  Rc<OpcodeLocation> }
+pub struct JumpGenerator { pub location:  Rc<OpcodeLocation>, pub wide: Rc<Cell<bool>> }
 impl OpcodeGeneratorTrait for JumpGenerator {
     fn generate(&self, opcodes: &mut Vec<u8>) {
-        opcodes.push(VmOpCode::Jump.into());
-        self.location.apply(opcodes);
+        generate_jump_opcode(opcodes, VmOpCode::Jump, VmOpCode::JumpWide, &self.location, self.wide.get());
     }
 
     fn dump<'a>(&self, builder: &'a DumpBuilder, index: Rc<AtomicUsize>, opcodes: &Vec<u8>) {
         let opcode_index = index.fetch_add(1, Ordering::SeqCst);
-        let location = opcode_to_location(index, opcodes);
-        builder.add(opcode_index, VmOpCode::Jump, location.to_string(), "".to_string(), "".to_string());
+        let location = opcode_to_jump_location(opcode_index, index, opcodes, self.wide.get());
+        let opcode = if self.wide.get() { VmOpCode::JumpWide } else { VmOpCode::Jump };
+        builder.add(opcode_index, opcode, location.to_string(), "".to_string(), "".to_string());
     }
 }
\ No newline at end of file
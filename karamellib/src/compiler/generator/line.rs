@@ -0,0 +1,24 @@
+use std::{rc::Rc, sync::atomic::{AtomicUsize, Ordering}};
+
+use crate::compiler::VmOpCode;
+
+use super::{DumpBuilder, OpcodeGeneratorTrait};
+
+#[derive(Debug)]
+#[derive(Clone)]
+pub struct LineGenerator {
+    pub line: u16
+}
+
+impl OpcodeGeneratorTrait for LineGenerator {
+    fn generate(&self, opcodes: &mut Vec<u8>) {
+        opcodes.push(VmOpCode::Line.into());
+        opcodes.push(self.line as u8);
+        opcodes.push((self.line >> 8) as u8);
+    }
+
+    fn dump<'a>(&self, builder: &'a DumpBuilder, index: Rc<AtomicUsize>, _: &Vec<u8>) {
+        let opcode_index = index.fetch_add(3, Ordering::SeqCst);
+        builder.add(opcode_index, VmOpCode::Line, self.line.to_string(), "".to_string(), "".to_string());
+    }
+}
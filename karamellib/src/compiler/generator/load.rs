@@ -1,20 +1,18 @@
-use std::{rc::Rc, sync::atomic::{AtomicUsize, Ordering}};
+use std::{rc::Rc, sync::atomic::AtomicUsize};
 
 use crate::compiler::VmOpCode;
 
-use super::{DumpBuilder, OpcodeGeneratorTrait};
+use super::{DumpBuilder, OpcodeGeneratorTrait, generate_location_opcode, dump_location_opcode};
 
 
 #[derive(Clone)]
-pub struct LoadGenerator { pub location: u8 }
+pub struct LoadGenerator { pub location: u16 }
 impl OpcodeGeneratorTrait for LoadGenerator {
     fn generate(&self, opcodes: &mut Vec<u8>) {
-        opcodes.push(VmOpCode::Load.into());
-        opcodes.push(self.location);
+        generate_location_opcode(opcodes, VmOpCode::Load, VmOpCode::LoadWide, self.location);
     }
 
     fn dump<'a>(&self, builder: &'a DumpBuilder, index: Rc<AtomicUsize>, _: &Vec<u8>) {
-        let opcode_index = index.fetch_add(2, Ordering::SeqCst);
-        builder.add(opcode_index, VmOpCode::Load, self.location.to_string(), "".to_string(), "".to_string());
+        dump_location_opcode(builder, index, VmOpCode::Load, VmOpCode::LoadWide, self.location);
     }
 }
\ No newline at end of file
@@ -15,12 +15,37 @@ pub enum LocationType {
     }
 }
 
+/// Place of the location in the opcodes
+#[derive(Clone, Copy)]
+enum LocationOperand {
+    /// 16 bit location, low and high byte
+    Narrow(usize),
+
+    /// 32 bit offset from the base, little endian
+    Wide { position: usize, base: usize }
+}
+
+impl LocationOperand {
+    fn write(&self, location: usize, opcodes: &mut Vec<u8>) {
+        match *self {
+            LocationOperand::Narrow(position) => {
+                opcodes[position] = location as u8;
+                opcodes[position + 1] = (location >> 8) as u8;
+            },
+            LocationOperand::Wide { position, base } => {
+                let offset = (location as i64 - base as i64) as i32;
+                opcodes[position..position + 4].copy_from_slice(&offset.to_le_bytes());
+            }
+        }
+    }
+}
+
 #[derive(Clone)]
 pub struct OpcodeLocation {
     #[cfg(debug_assertions)]
     index: usize,
     location: RefCell<LocationType>,
-    used_location: RefCell<Vec<usize>>
+    used_location: RefCell<Vec<LocationOperand>>
 }
 
 impl OpcodeLocation {
@@ -61,11 +86,15 @@ impl OpcodeLocation {
         *self.location.borrow_mut() = LocationType::Fixed(location);
 
         for used_location in self.used_location.borrow().iter() {
-            opcodes[*used_location] = location as u8;
-            opcodes[*used_location + 1] = (location >> 8) as u8;
+            used_location.write(location, opcodes);
         }
     }
 
+    /// Forget the written places, opcodes are going to be generated again.
+    pub fn clear_usages(&self) {
+        self.used_location.borrow_mut().clear();
+    }
+
     pub fn subtraction(&self, left_hand: Rc<OpcodeLocation>, right_hand: Rc<OpcodeLocation>) {
         #[cfg(debug_assertions)]
         assert!(left_hand.get_index() != right_hand.get_index());
@@ -77,10 +106,19 @@ impl OpcodeLocation {
     }
 
     pub fn apply(&self, opcodes: &mut Vec<u8>) {
+        self.push(LocationOperand::Narrow(opcodes.len()), opcodes, 2);
+    }
+
+    /// Write the location as a 32 bit offset from the base
+    pub fn apply_wide(&self, opcodes: &mut Vec<u8>, base: usize) {
+        self.push(LocationOperand::Wide { position: opcodes.len(), base }, opcodes, 4);
+    }
+
+    fn push(&self, operand: LocationOperand, opcodes: &mut Vec<u8>, size: usize) {
         // Save position
-        self.used_location.borrow_mut().push(opcodes.len());
-        opcodes.push(self.get() as u8);
-        opcodes.push((self.get() >> 8) as u8);
+        self.used_location.borrow_mut().push(operand);
+        opcodes.resize(opcodes.len() + size, 0);
+        operand.write(self.get(), opcodes);
     }
 }
 
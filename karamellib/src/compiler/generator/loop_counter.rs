@@ -0,0 +1,24 @@
+use std::{rc::Rc, sync::atomic::{AtomicUsize, Ordering}};
+
+use crate::compiler::VmOpCode;
+
+use super::{DumpBuilder, OpcodeGeneratorTrait};
+
+#[derive(Debug)]
+#[derive(Clone)]
+pub struct LoopCounterGenerator {
+    pub index: u16
+}
+
+impl OpcodeGeneratorTrait for LoopCounterGenerator {
+    fn generate(&self, opcodes: &mut Vec<u8>) {
+        opcodes.push(VmOpCode::LoopCounter.into());
+        opcodes.push(self.index as u8);
+        opcodes.push((self.index >> 8) as u8);
+    }
+
+    fn dump<'a>(&self, builder: &'a DumpBuilder, index: Rc<AtomicUsize>, _: &Vec<u8>) {
+        let opcode_index = index.fetch_add(3, Ordering::SeqCst);
+        builder.add(opcode_index, VmOpCode::LoopCounter, self.index.to_string(), "".to_string(), "".to_string());
+    }
+}
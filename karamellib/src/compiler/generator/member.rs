@@ -0,0 +1,24 @@
+use std::{rc::Rc, sync::atomic::{AtomicUsize, Ordering}};
+
+use crate::compiler::VmOpCode;
+
+use super::{DumpBuilder, OpcodeGeneratorTrait};
+
+#[derive(Debug)]
+#[derive(Clone)]
+pub struct GetMemberGenerator {
+    pub cache: u16
+}
+
+impl OpcodeGeneratorTrait for GetMemberGenerator {
+    fn generate(&self, opcodes: &mut Vec<u8>) {
+        opcodes.push(VmOpCode::GetMember.into());
+        opcodes.push(self.cache as u8);
+        opcodes.push((self.cache >> 8) as u8);
+    }
+
+    fn dump<'a>(&self, builder: &'a DumpBuilder, index: Rc<AtomicUsize>, _: &Vec<u8>) {
+        let opcode_index = index.fetch_add(3, Ordering::SeqCst);
+        builder.add(opcode_index, VmOpCode::GetMember, self.cache.to_string(), "".to_string(), "".to_string());
+    }
+}
@@ -1,10 +1,11 @@
 use std::{borrow::Borrow, cell::{Cell, RefCell}, cmp, collections::VecDeque, rc::Rc, sync::atomic::{AtomicUsize, Ordering}};
 
-use crate::{compiler::generator::location::DynamicLocationUpdateGenerator, constants::{DUMP_INDEX_WIDTH, DUMP_OPCODE_COLUMN_1, DUMP_OPCODE_COLUMN_2, DUMP_OPCODE_COLUMN_3, DUMP_OPCODE_TITLE, DUMP_OPCODE_WIDTH}};
+use crate::{compiler::generator::location::DynamicLocationUpdateGenerator, constants::{DUMP_INDEX_WIDTH, DUMP_OPCODE_COLUMN_1, DUMP_OPCODE_COLUMN_2, DUMP_OPCODE_COLUMN_3, DUMP_OPCODE_TITLE, DUMP_OPCODE_WIDTH, LISTING_LINE_WIDTH}};
 
-use self::{call::{CallGenerator, CallType}, compare::CompareGenerator, constant::ConstantGenerator, function::FunctionGenerator, init_dict::InitDictGenerator, init_list::InitListGenerator, jump::JumpGenerator, load::LoadGenerator, location::{CurrentLocationUpdateGenerator, OpcodeLocation, SubtractionGenerator}, location_group::OpcodeLocationGroup, opcode_item::OpcodeItem, store::{StoreGenerator, StoreType}};
+use self::{call::{CallGenerator, CallType}, compare::CompareGenerator, constant::ConstantGenerator, function::FunctionGenerator, init_dict::InitDictGenerator, init_list::InitListGenerator, jump::JumpGenerator, conditional_jump::ConditionalJumpGenerator, load::LoadGenerator, loop_counter::LoopCounterGenerator, member::GetMemberGenerator, line::LineGenerator, try_block::{TryStartGenerator, TryEndGenerator}, defer::{DeferGenerator, WithEndGenerator}, location::{CurrentLocationUpdateGenerator, OpcodeLocation, SubtractionGenerator}, location_group::OpcodeLocationGroup, opcode_item::OpcodeItem, store::{StoreGenerator, StoreType}};
 
 use super::{VmOpCode, function::FunctionReference};
+use crate::types::SourcePosition;
 
 pub mod opcode_item;
 pub mod location;
@@ -18,6 +19,12 @@ pub mod call;
 pub mod location_group;
 pub mod init_list;
 pub mod init_dict;
+pub mod loop_counter;
+pub mod line;
+pub mod try_block;
+pub mod defer;
+pub mod conditional_jump;
+pub mod member;
 
 pub trait OpcodeGeneratorTrait {
     fn generate(&self, opcodes: &mut Vec<u8>);
@@ -39,6 +46,56 @@ pub fn opcode_to_location(index: Rc<AtomicUsize>, opcodes: &Vec<u8>) -> usize {
     ((location_1 as u16 * 256) + location_2 as u16) as usize
 }
 
+/// Opcode with a storage location. Locations after the first 256 use the wide opcode with a 16 bit location.
+pub fn generate_location_opcode(opcodes: &mut Vec<u8>, opcode: VmOpCode, wide_opcode: VmOpCode, location: u16) {
+    match location <= u8::MAX as u16 {
+        true => {
+            opcodes.push(opcode.into());
+            opcodes.push(location as u8);
+        },
+        false => {
+            opcodes.push(wide_opcode.into());
+            opcodes.push(location as u8);
+            opcodes.push((location >> 8) as u8);
+        }
+    };
+}
+
+pub fn dump_location_opcode(builder: &DumpBuilder, index: Rc<AtomicUsize>, opcode: VmOpCode, wide_opcode: VmOpCode, location: u16) {
+    match location <= u8::MAX as u16 {
+        true => builder.add(index.fetch_add(2, Ordering::SeqCst), opcode, location.to_string(), "".to_string(), "".to_string()),
+        false => builder.add(index.fetch_add(3, Ordering::SeqCst), wide_opcode, location.to_string(), "".to_string(), "".to_string())
+    };
+}
+
+/// Opcode with a jump location. Long programs use the wide opcode with a 32 bit offset from the opcode.
+pub fn generate_jump_opcode(opcodes: &mut Vec<u8>, opcode: VmOpCode, wide_opcode: VmOpCode, location: &OpcodeLocation, wide: bool) {
+    let base = opcodes.len();
+    match wide {
+        true => {
+            opcodes.push(wide_opcode.into());
+            location.apply_wide(opcodes, base);
+        },
+        false => {
+            opcodes.push(opcode.into());
+            location.apply(opcodes);
+        }
+    };
+}
+
+pub fn opcode_to_offset(index: Rc<AtomicUsize>, opcodes: &Vec<u8>) -> i32 {
+    let start = index.fetch_add(4, Ordering::SeqCst);
+    i32::from_le_bytes([opcodes[start], opcodes[start + 1], opcodes[start + 2], opcodes[start + 3]])
+}
+
+/// Jump location of the opcode at the opcode index. Index is moved after the location.
+pub fn opcode_to_jump_location(opcode_index: usize, index: Rc<AtomicUsize>, opcodes: &Vec<u8>, wide: bool) -> usize {
+    match wide {
+        true => (opcode_index as i64 + opcode_to_offset(index, opcodes) as i64) as usize,
+        false => opcode_to_location(index, opcodes)
+    }
+}
+
 pub struct LoopItem {
     pub loop_breaks: OpcodeLocationGroup,
     pub loop_continues:  OpcodeLocationGroup,
@@ -121,28 +178,49 @@ impl DumpBuilder {
 
 pub struct OpcodeGenerator {
     generators: RefCell<Vec<Rc<dyn OpcodeGeneratorTrait>>>,
-    loop_groups: RefCell<VecDeque<LoopItem>>
+    loop_groups: RefCell<VecDeque<LoopItem>>,
+
+    /// Index of the first generator of the statements
+    positions: RefCell<Vec<(usize, SourcePosition)>>,
+
+    /// Index of the first opcode of the statements, filled while the opcodes are generated
+    opcode_positions: RefCell<Vec<(usize, SourcePosition)>>,
+
+    /// Jumps use 32 bit offsets when the opcodes do not fit into the 16 bit locations
+    wide_jumps: Rc<Cell<bool>>,
+
+    /// Created locations, their written places are cleared before the opcodes are generated again
+    locations: RefCell<Vec<Rc<OpcodeLocation>>>
 }
 
 impl OpcodeGenerator {
     pub fn new() -> Self {
         OpcodeGenerator {
             generators: RefCell::new(Vec::new()),
-            loop_groups: RefCell::new(VecDeque::new())
+            loop_groups: RefCell::new(VecDeque::new()),
+            positions: RefCell::new(Vec::new()),
+            opcode_positions: RefCell::new(Vec::new()),
+            wide_jumps: Rc::new(Cell::new(false)),
+            locations: RefCell::new(Vec::new())
         }
     }
 
+    /// Following opcodes belong to the statement at the position
+    pub fn mark_position(&self, position: SourcePosition) {
+        self.positions.borrow_mut().push((self.generators.borrow().len(), position));
+    }
+
     pub fn add_opcode<T: Borrow<VmOpCode>>(&self, opcode: T) {
         self.generators.borrow_mut().push(Rc::new(OpcodeItem { opcode: opcode.borrow().clone() }));
     }
 
-    pub fn create_load(&self, location: u8) -> Rc<LoadGenerator> {
+    pub fn create_load(&self, location: u16) -> Rc<LoadGenerator> {
         let generator = Rc::new(LoadGenerator { location: location });
         self.generators.borrow_mut().push(generator.clone());
         generator
     }
 
-    pub fn create_constant(&self, location: u8) -> Rc<ConstantGenerator> {
+    pub fn create_constant(&self, location: u16) -> Rc<ConstantGenerator> {
         let generator = Rc::new(ConstantGenerator { location: location });
         self.generators.borrow_mut().push(generator.clone());
         generator
@@ -200,7 +278,13 @@ impl OpcodeGenerator {
 
     /// Create empty location point. It is used for jump and compare location positions.
     pub fn create_location(&self) -> Rc<OpcodeLocation> {
-        Rc::new(OpcodeLocation::empty())
+        self.register_location(OpcodeLocation::empty())
+    }
+
+    fn register_location(&self, location: OpcodeLocation) -> Rc<OpcodeLocation> {
+        let location = Rc::new(location);
+        self.locations.borrow_mut().push(location.clone());
+        location
     }
 
     /// Set location information with opcode length
@@ -224,29 +308,35 @@ impl OpcodeGenerator {
     }
 
     pub fn create_location_with_data(&self, location: usize) -> Rc<OpcodeLocation> {
-        Rc::new(OpcodeLocation::new(location))
+        self.register_location(OpcodeLocation::new(location))
     }
 
     /// Create a new location information and that location information should be populated at generation time with current location
     pub fn current_location(&self) -> Rc<OpcodeLocation> {
-        let location = Rc::new(OpcodeLocation::empty());
+        let location = self.create_location();
         self.set_current_location(location.clone());
         location
     }
 
     pub fn create_jump(&self, location: Rc<OpcodeLocation>) -> Rc<JumpGenerator> {
-        let generator = Rc::new(JumpGenerator { location: location.clone() });
+        let generator = Rc::new(JumpGenerator { location: location.clone(), wide: self.wide_jumps.clone() });
+        self.generators.borrow_mut().push(generator.clone());
+        generator
+    }
+
+    pub fn create_conditional_jump(&self, opcode: VmOpCode, location: Rc<OpcodeLocation>) -> Rc<ConditionalJumpGenerator> {
+        let generator = Rc::new(ConditionalJumpGenerator { opcode, location: location.clone(), wide: self.wide_jumps.clone() });
         self.generators.borrow_mut().push(generator.clone());
         generator
     }
 
     pub fn create_compare(&self, location: Rc<OpcodeLocation>) -> Rc<CompareGenerator> {
-        let generator = Rc::new(CompareGenerator { location: location.clone() });
+        let generator = Rc::new(CompareGenerator { location: location.clone(), wide: self.wide_jumps.clone() });
         self.generators.borrow_mut().push(generator.clone());
         generator
     }
 
-    pub fn create_store(&self, destination: u8) -> Rc<StoreGenerator> {
+    pub fn create_store(&self, destination: u16) -> Rc<StoreGenerator> {
         let generator = Rc::new(StoreGenerator { 
             store_type: StoreType::Store(destination)
          });
@@ -254,7 +344,7 @@ impl OpcodeGenerator {
         generator
     }
 
-    pub fn create_copy_to_store(&self, destination: u8) -> Rc<StoreGenerator> {
+    pub fn create_copy_to_store(&self, destination: u16) -> Rc<StoreGenerator> {
         let generator = Rc::new(StoreGenerator { 
             store_type: StoreType::CopyToStore(destination)
          });
@@ -273,7 +363,7 @@ impl OpcodeGenerator {
         generator
     }
 
-    pub fn create_call(&self, function_location: u8, argument_size: u8, assign_to_temp: bool) -> Rc<CallGenerator> {
+    pub fn create_call(&self, function_location: u16, argument_size: u8, assign_to_temp: bool) -> Rc<CallGenerator> {
         let generator = Rc::new(CallGenerator { 
                 call_type: CallType::Call { constant_location: function_location },
                 argument_size,
@@ -299,26 +389,120 @@ impl OpcodeGenerator {
         generator
     }
 
-    pub fn create_init_list(&self, argument_size: usize) -> Rc<InitListGenerator> {
+    pub fn create_init_list(&self, argument_size: u16) -> Rc<InitListGenerator> {
         let generator = Rc::new(InitListGenerator { argument_size });
         self.generators.borrow_mut().push(generator.clone());
         generator
     }
 
-    pub fn create_init_dict(&self, argument_size: usize) -> Rc<InitDictGenerator> {
+    pub fn create_init_dict(&self, argument_size: u16) -> Rc<InitDictGenerator> {
         let generator = Rc::new(InitDictGenerator { argument_size });
         self.generators.borrow_mut().push(generator.clone());
         generator
     }
+
+    pub fn create_loop_counter(&self, index: u16) -> Rc<LoopCounterGenerator> {
+        let generator = Rc::new(LoopCounterGenerator { index });
+        self.generators.borrow_mut().push(generator.clone());
+        generator
+    }
+
+    pub fn create_get_member(&self, cache: u16) -> Rc<GetMemberGenerator> {
+        let generator = Rc::new(GetMemberGenerator { cache });
+        self.generators.borrow_mut().push(generator.clone());
+        generator
+    }
+
+    pub fn create_line(&self, line: u16) -> Rc<LineGenerator> {
+        let generator = Rc::new(LineGenerator { line });
+        self.generators.borrow_mut().push(generator.clone());
+        generator
+    }
+
+    pub fn create_try_start(&self, catch_location: Rc<OpcodeLocation>, variable: Option<u16>) -> Rc<TryStartGenerator> {
+        let generator = Rc::new(TryStartGenerator { catch_location, variable, wide: self.wide_jumps.clone() });
+        self.generators.borrow_mut().push(generator.clone());
+        generator
+    }
+
+    pub fn create_try_end(&self, location: Rc<OpcodeLocation>) -> Rc<TryEndGenerator> {
+        let generator = Rc::new(TryEndGenerator { location, wide: self.wide_jumps.clone() });
+        self.generators.borrow_mut().push(generator.clone());
+        generator
+    }
+
+    pub fn create_defer(&self, location: Rc<OpcodeLocation>) -> Rc<DeferGenerator> {
+        let generator = Rc::new(DeferGenerator { location, wide: self.wide_jumps.clone() });
+        self.generators.borrow_mut().push(generator.clone());
+        generator
+    }
+
+    pub fn create_with_end(&self, location: Rc<OpcodeLocation>) -> Rc<WithEndGenerator> {
+        let generator = Rc::new(WithEndGenerator { location, wide: self.wide_jumps.clone() });
+        self.generators.borrow_mut().push(generator.clone());
+        generator
+    }
 }
 
 impl OpcodeGenerator {
     pub fn generate(&self, opcodes: &mut Vec<u8>) {
-        for generator in self.generators.borrow().iter() {
+        let start = opcodes.len();
+        self.generate_opcodes(opcodes);
+
+        /* 16 bit locations can not address all opcodes, jumps are generated again with 32 bit offsets */
+        if opcodes.len() > u16::MAX as usize && !self.wide_jumps.get() {
+            opcodes.truncate(start);
+            self.wide_jumps.set(true);
+            for location in self.locations.borrow().iter() {
+                location.clear_usages();
+            }
+            self.generate_opcodes(opcodes);
+        }
+    }
+
+    /// Opcodes are generated with 32 bit jump offsets
+    pub fn is_wide_jumps(&self) -> bool {
+        self.wide_jumps.get()
+    }
+
+    fn generate_opcodes(&self, opcodes: &mut Vec<u8>) {
+        let positions = self.positions.borrow();
+        let mut positions = positions.iter().peekable();
+        let mut opcode_positions = self.opcode_positions.borrow_mut();
+        opcode_positions.clear();
+
+        for (index, generator) in self.generators.borrow().iter().enumerate() {
+            while let Some((_, position)) = positions.next_if(|(start, _)| *start == index) {
+                opcode_positions.push((opcodes.len(), *position));
+            }
             generator.generate(opcodes);
         }
     }
 
+    /// Source position of the statement that generated the opcode at the location.
+    pub fn find_position(&self, location: usize) -> Option<SourcePosition> {
+        let opcode_positions = self.opcode_positions.borrow();
+        match opcode_positions.partition_point(|(start, _)| *start <= location) {
+            0 => None,
+            index => Some(opcode_positions[index - 1].1)
+        }
+    }
+
+    /// Source line of the opcode at the location. Zero based.
+    pub fn find_line(&self, location: usize) -> Option<u32> {
+        self.find_position(location).map(|position| position.line)
+    }
+
+    /// Start locations of the statements in the generated opcodes, sorted by the location.
+    pub fn opcode_positions(&self) -> Vec<(usize, SourcePosition)> {
+        self.opcode_positions.borrow().clone()
+    }
+
+    /// Position table of opcodes that are generated by another generator.
+    pub fn set_opcode_positions(&self, opcode_positions: Vec<(usize, SourcePosition)>) {
+        *self.opcode_positions.borrow_mut() = opcode_positions;
+    }
+
     pub fn dump(&self, opcodes: &Vec<u8>) -> String {
         let builder = DumpBuilder::new();
         let indexer = Rc::new(AtomicUsize::new(0));
@@ -356,13 +540,54 @@ impl OpcodeGenerator {
         buffer.push_str(&format!("╚═{:═<DUMP_INDEX_WIDTH$}═╩═{:═<DUMP_OPCODE_WIDTH$}═╩═{:═<DUMP_OPCODE_COLUMN_1$}═╩═{:═<DUMP_OPCODE_COLUMN_2$}═╩═{:═<DUMP_OPCODE_COLUMN_3$}═╝", "═", "═", "═", "═", "═", DUMP_INDEX_WIDTH=builder.max_index_width.get(), DUMP_OPCODE_WIDTH=builder.max_opcode_width.get(), DUMP_OPCODE_COLUMN_1=builder.max_column1_width.get(), DUMP_OPCODE_COLUMN_2=builder.max_column2_width.get(), DUMP_OPCODE_COLUMN_3=builder.max_column3_width.get())[..]);
         buffer
     }
+
+    /// Source lines, each followed by the opcodes generated for it. Function bodies are listed after the main code.
+    pub fn listing(&self, opcodes: &Vec<u8>, source: &str) -> String {
+        let builder = DumpBuilder::new();
+        let indexer = Rc::new(AtomicUsize::new(0));
+        let source_lines: Vec<&str> = source.split('\n').collect();
+        let positions = self.positions.borrow();
+        let mut positions = positions.iter().peekable();
+        let mut pending_line = None;
+        let mut last_line = None;
+
+        let mut buffer = String::with_capacity(1024);
+        for (index, generator) in self.generators.borrow().iter().enumerate() {
+            /* Lines without opcodes, like the function definitions in the main code, are skipped */
+            while let Some((_, position)) = positions.next_if(|(start, _)| *start == index) {
+                pending_line = Some(position.line);
+            }
+
+            let start = builder.dumps.borrow().len();
+            generator.dump(&builder, indexer.clone(), opcodes);
+            for item in builder.dumps.borrow()[start..].iter() {
+                if let Some(line) = pending_line.take() {
+                    if last_line != Some(line) {
+                        let code = source_lines.get(line as usize).map(|code| code.trim_end()).unwrap_or_default();
+                        buffer.push_str(&format!("{:>LISTING_LINE_WIDTH$} │ {}\n", line + 1, code));
+                        last_line = Some(line);
+                    }
+                }
+
+                let opcode = match &item.opcode {
+                    DumpItemType::Opcode(opcode) => opcode.to_string(),
+                    DumpItemType::Text(text) => text.to_string()
+                };
+
+                let line = format!("{:>LISTING_LINE_WIDTH$} │     {:04} {:DUMP_OPCODE_WIDTH$} {} {} {}", "", item.index, opcode, item.column1, item.column2, item.column3);
+                buffer.push_str(line.trim_end());
+                buffer.push('\n');
+            }
+        }
+        buffer
+    }
 }
 
 
 
 #[cfg(test)]
 mod tests {
-    use crate::{buildin::DummyModule, compiler::ast::KaramelAstType};
+    use crate::{buildin::DummyModule, compiler::{ast::KaramelAstType, function::FunctionFlag}};
 
     use super::*;
 
@@ -470,7 +695,7 @@ mod tests {
         let mut opcodes = Vec::new();
         let generator = OpcodeGenerator::new();
 
-        let function = FunctionReference::opcode_function("TEST FUNCTION".to_string(), Vec::new(), Rc::new(KaramelAstType::None), Rc::new(DummyModule::new()), 0, 0, true);
+        let function = FunctionReference::opcode_function("TEST FUNCTION".to_string(), Vec::new(), Rc::new(KaramelAstType::None), Rc::new(DummyModule::new()), 0, 0, FunctionFlag::MODULE_LEVEL);
 
         generator.add_opcode(VmOpCode::Halt);
         generator.create_function_definition(function);
@@ -778,4 +1003,97 @@ mod tests {
 
         assert_eq!(expected, generated);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_find_line() {
+        let mut opcodes = Vec::new();
+        let generator = OpcodeGenerator::new();
+        generator.add_opcode(VmOpCode::Halt);
+        generator.mark_position(SourcePosition::new(3, 0));
+        generator.create_load(0);
+        generator.create_constant(1);
+        generator.mark_position(SourcePosition::new(4, 8));
+        generator.add_opcode(VmOpCode::Halt);
+        generator.generate(&mut opcodes);
+
+        assert_eq!(generator.find_position(0), None);
+        assert_eq!(generator.find_position(5), Some(SourcePosition::new(4, 8)));
+        assert_eq!(generator.opcode_positions(), vec![(1, SourcePosition::new(3, 0)), (5, SourcePosition::new(4, 8))]);
+        assert_eq!(generator.find_line(0), None);
+        assert_eq!(generator.find_line(1), Some(3));
+        assert_eq!(generator.find_line(4), Some(3));
+        assert_eq!(generator.find_line(5), Some(4));
+        assert_eq!(generator.find_line(100), Some(4));
+    }
+
+    #[test]
+    fn test_wide_constant() {
+        let mut opcodes = Vec::new();
+        let generator = OpcodeGenerator::new();
+        generator.create_constant(255);
+        generator.create_constant(300);
+        generator.create_call(2, 1, false);
+        generator.create_call(258, 1, true);
+        generator.generate(&mut opcodes);
+
+        assert_eq!(opcodes, vec![
+            VmOpCode::Constant as u8, 255,
+            VmOpCode::ConstantWide as u8, 44, 1,
+            VmOpCode::Call as u8, 2, 1, 0,
+            VmOpCode::ConstantWide as u8, 2, 1, VmOpCode::CallStack as u8, 1, 1]);
+    }
+
+    #[test]
+    fn test_wide_variable() {
+        let mut opcodes = Vec::new();
+        let generator = OpcodeGenerator::new();
+        generator.create_load(255);
+        generator.create_load(256);
+        generator.create_store(10);
+        generator.create_store(513);
+        generator.create_copy_to_store(300);
+        generator.create_init_list(300);
+        generator.generate(&mut opcodes);
+
+        assert_eq!(opcodes, vec![
+            VmOpCode::Load as u8, 255,
+            VmOpCode::LoadWide as u8, 0, 1,
+            VmOpCode::Store as u8, 10,
+            VmOpCode::StoreWide as u8, 1, 2,
+            VmOpCode::CopyToStoreWide as u8, 44, 1,
+            VmOpCode::Init as u8, 1, 44, 1]);
+    }
+
+    #[test]
+    fn test_get_member() {
+        let mut opcodes = Vec::new();
+        let generator = OpcodeGenerator::new();
+        generator.create_get_member(2);
+        generator.create_get_member(300);
+        generator.generate(&mut opcodes);
+
+        assert_eq!(opcodes, vec![
+            VmOpCode::GetMember as u8, 2, 0,
+            VmOpCode::GetMember as u8, 44, 1]);
+    }
+
+    #[test]
+    fn test_wide_jump() {
+        let mut opcodes = Vec::new();
+        let generator = OpcodeGenerator::new();
+        let start = generator.current_location();
+        let end = generator.create_location();
+        generator.create_jump(end.clone());
+        for _ in 0..70000 {
+            generator.add_opcode(VmOpCode::Halt);
+        }
+        generator.create_jump(start);
+        generator.set_current_location(end);
+        generator.generate(&mut opcodes);
+
+        assert!(generator.is_wide_jumps());
+        assert_eq!(opcodes.len(), 70010);
+        assert_eq!(&opcodes[..5], &[VmOpCode::JumpWide as u8, 0x7a, 0x11, 0x01, 0x00]);
+        assert_eq!(&opcodes[70005..], &[VmOpCode::JumpWide as u8, 0x8b, 0xee, 0xfe, 0xff]);
+    }
+}
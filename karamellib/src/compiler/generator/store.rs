@@ -2,17 +2,19 @@ use std::{rc::Rc, sync::atomic::{AtomicUsize, Ordering}};
 
 use crate::compiler::VmOpCode;
 
-use super::{DumpBuilder, OpcodeGeneratorTrait};
+use super::{DumpBuilder, OpcodeGeneratorTrait, generate_location_opcode, dump_location_opcode};
 
 #[derive(Debug)]
 #[derive(Clone)]
 pub enum StoreType {
-    Store(u8),
+    Store(u16),
+
+    /// Only used when both locations are 8 bit
     FastStore {
         destination: u8,
         source: u8
     },
-    CopyToStore(u8)
+    CopyToStore(u16)
 }
 
 #[derive(Debug)]
@@ -24,14 +26,8 @@ pub struct StoreGenerator {
 impl OpcodeGeneratorTrait for StoreGenerator {
     fn generate(&self, opcodes: &mut Vec<u8>) {
         match self.store_type {
-            StoreType::Store(destination) => {
-                opcodes.push(VmOpCode::Store.into());
-                opcodes.push(destination);
-            },
-            StoreType::CopyToStore(destination) => {
-                opcodes.push(VmOpCode::CopyToStore.into());
-                opcodes.push(destination);
-            },
+            StoreType::Store(destination) => generate_location_opcode(opcodes, VmOpCode::Store, VmOpCode::StoreWide, destination),
+            StoreType::CopyToStore(destination) => generate_location_opcode(opcodes, VmOpCode::CopyToStore, VmOpCode::CopyToStoreWide, destination),
             StoreType::FastStore { destination, source} => {
                 opcodes.push(VmOpCode::FastStore.into());
                 opcodes.push(destination);
@@ -41,18 +37,12 @@ impl OpcodeGeneratorTrait for StoreGenerator {
     }
 
     fn dump<'a>(&self, builder: &'a DumpBuilder, index: Rc<AtomicUsize>, _: &Vec<u8>) {
-        let opcode_index = index.fetch_add(2, Ordering::SeqCst);
-        
         match self.store_type {
-            StoreType::Store(destination) => {
-                builder.add(opcode_index, VmOpCode::Store, destination.to_string(), "".to_string(), "".to_string());
-            },
-            StoreType::CopyToStore(destination) => {
-                builder.add(opcode_index, VmOpCode::CopyToStore, destination.to_string(), "".to_string(), "".to_string());
-            },
+            StoreType::Store(destination) => dump_location_opcode(builder, index, VmOpCode::Store, VmOpCode::StoreWide, destination),
+            StoreType::CopyToStore(destination) => dump_location_opcode(builder, index, VmOpCode::CopyToStore, VmOpCode::CopyToStoreWide, destination),
             StoreType::FastStore { destination, source} => {
+                let opcode_index = index.fetch_add(3, Ordering::SeqCst);
                 builder.add(opcode_index, VmOpCode::FastStore, destination.to_string(), source.to_string(), "".to_string());
-                index.fetch_add(1, Ordering::SeqCst);
             }
         };
     }
@@ -0,0 +1,53 @@
+use std::{cell::Cell, rc::Rc, sync::atomic::{AtomicUsize, Ordering}};
+
+use crate::compiler::VmOpCode;
+
+use super::{DumpBuilder, OpcodeGeneratorTrait, OpcodeLocation, generate_jump_opcode, opcode_to_jump_location};
+
+#[derive(Clone)]
+/// Generate protected block start opcode. Catch variable is written by the vm, so catch block does not need a store opcode.
+pub struct TryStartGenerator {
+    pub catch_location: Rc<OpcodeLocation>,
+    pub variable: Option<u16>,
+    pub wide: Rc<Cell<bool>>
+}
+
+impl OpcodeGeneratorTrait for TryStartGenerator {
+    fn generate(&self, opcodes: &mut Vec<u8>) {
+        generate_jump_opcode(opcodes, VmOpCode::TryStart, VmOpCode::TryStartWide, &self.catch_location, self.wide.get());
+        let variable = self.variable.unwrap_or_default();
+        opcodes.push(self.variable.is_some() as u8);
+        opcodes.push(variable as u8);
+        opcodes.push((variable >> 8) as u8);
+    }
+
+    fn dump<'a>(&self, builder: &'a DumpBuilder, index: Rc<AtomicUsize>, opcodes: &Vec<u8>) {
+        let opcode_index = index.fetch_add(1, Ordering::SeqCst);
+        let location = opcode_to_jump_location(opcode_index, index.clone(), opcodes, self.wide.get());
+        index.fetch_add(3, Ordering::SeqCst);
+
+        let variable = match self.variable {
+            Some(variable) => variable.to_string(),
+            None => "".to_string()
+        };
+        let opcode = if self.wide.get() { VmOpCode::TryStartWide } else { VmOpCode::TryStart };
+        builder.add(opcode_index, opcode, location.to_string(), variable, "".to_string());
+    }
+}
+
+#[derive(Clone)]
+/// Generate protected block end opcode. Removes the error handler and jumps over the catch block.
+pub struct TryEndGenerator { pub location: Rc<OpcodeLocation>, pub wide: Rc<Cell<bool>> }
+
+impl OpcodeGeneratorTrait for TryEndGenerator {
+    fn generate(&self, opcodes: &mut Vec<u8>) {
+        generate_jump_opcode(opcodes, VmOpCode::TryEnd, VmOpCode::TryEndWide, &self.location, self.wide.get());
+    }
+
+    fn dump<'a>(&self, builder: &'a DumpBuilder, index: Rc<AtomicUsize>, opcodes: &Vec<u8>) {
+        let opcode_index = index.fetch_add(1, Ordering::SeqCst);
+        let location = opcode_to_jump_location(opcode_index, index, opcodes, self.wide.get());
+        let opcode = if self.wide.get() { VmOpCode::TryEndWide } else { VmOpCode::TryEnd };
+        builder.add(opcode_index, opcode, location.to_string(), "".to_string(), "".to_string());
+    }
+}
@@ -0,0 +1,42 @@
+use std::collections::{HashMap, VecDeque};
+
+use crate::types::*;
+
+/// Line and column of a token
+pub type Position = (u32, u32);
+
+/// The AST does not keep the token positions. Same kind of tokens are consumed in the source order while the AST is walked in the source order.
+#[derive(Default)]
+pub struct TokenLocator {
+    symbols: HashMap<String, VecDeque<Position>>,
+    operators: HashMap<String, VecDeque<Position>>,
+    conditions: VecDeque<Position>
+}
+
+impl TokenLocator {
+    pub fn new(tokens: &[Token]) -> TokenLocator {
+        let mut locator = TokenLocator::default();
+        for token in tokens.iter() {
+            let position = (token.line, token.start);
+            match &token.token_type {
+                KaramelTokenType::Symbol(symbol) => locator.symbols.entry(symbol.to_string()).or_default().push_back(position),
+                KaramelTokenType::Operator(operator) => locator.operators.entry(format!("{:?}", operator)).or_default().push_back(position),
+                KaramelTokenType::Keyword(KaramelKeywordType::If) => locator.conditions.push_back(position),
+                _ => ()
+            };
+        }
+        locator
+    }
+
+    pub fn symbol(&mut self, name: &str) -> Position {
+        self.symbols.get_mut(name).and_then(|positions| positions.pop_front()).unwrap_or_default()
+    }
+
+    pub fn operator(&mut self, operator: KaramelOperatorType) -> Position {
+        self.operators.get_mut(&format!("{:?}", operator)).and_then(|positions| positions.pop_front()).unwrap_or_default()
+    }
+
+    pub fn condition(&mut self) -> Position {
+        self.conditions.pop_front().unwrap_or_default()
+    }
+}
@@ -1,20 +1,39 @@
+#[cfg(feature = "vm")]
 mod compiler;
+#[cfg(feature = "vm")]
 mod static_storage;
+#[cfg(feature = "vm")]
 mod storage_builder;
+#[cfg(feature = "vm")]
 pub mod function;
 
 pub mod value;
+pub mod dict_key;
+pub mod semantics;
 pub mod ast;
+#[cfg(feature = "vm")]
 pub mod module;
+#[cfg(feature = "vm")]
 pub mod scope;
+#[cfg(feature = "vm")]
 pub mod context;
+#[cfg(feature = "vm")]
 pub mod generator;
+pub mod visitor;
+pub mod locator;
+#[cfg(feature = "vm")]
+pub mod resolver;
 
+#[cfg(feature = "vm")]
 pub use self::compiler::*;
+#[cfg(feature = "vm")]
 pub use self::static_storage::*;
 pub use self::value::*;
+pub use self::dict_key::DictKey;
+#[cfg(feature = "vm")]
 pub use self::context::KaramelCompilerContext;
 
+#[cfg(feature = "vm")]
 use std::vec::Vec;
 use std::mem;
 use std::fmt;
@@ -42,16 +61,19 @@ impl VmByte {
     }
 }
 
+#[cfg(feature = "vm")]
 trait VmByteDecode {
     fn encode(&self) -> VmByte;
 }
 
+#[cfg(feature = "vm")]
 impl VmByteDecode for VmOpCode {
     fn encode(&self) -> VmByte {
         VmByte::new_opcode(*self)
     }
 }
 
+#[cfg(feature = "vm")]
 impl VmByteDecode for u8 {
     fn encode(&self) -> VmByte {
         VmByte(*self)
@@ -88,6 +110,7 @@ pub enum VmOpCode {
     Compare = 22,
     Jump = 23,
 
+    /// Build a list or dictionary. Followed by the type (0 dict, 1 list) and the 16-bit item count.
     Init = 24,
 
     /// Copy value from memory to stack.
@@ -105,7 +128,110 @@ pub enum VmOpCode {
     GetItem = 31,
     SetItem = 32,
     Constant = 33,
-    Halt = 34
+    Halt = 34,
+
+    /// Only generated at teaching mode. Increase the iteration counter of the loop.
+    /// Next 2 opcode are the loop index as low and high byte.
+    LoopCounter = 35,
+
+    /// Only generated when the execution events are listened. Notify that new statement line is started.
+    /// Next 2 opcode are the line number as low and high byte.
+    Line = 36,
+
+    /// Start of the 'dene' block. Next 2 opcode are the catch block location as low and high byte.
+    /// Following 3 opcode are the catch variable flag and the variable location as low and high byte.
+    TryStart = 37,
+
+    /// End of the 'dene' block. Removes the error handler and jumps to the location at the next 2 opcode.
+    TryEnd = 38,
+
+    /// Raises the last stack value as error.
+    Throw = 39,
+
+    /// Adds the block after the opcode to the deferred blocks of the function and jumps to the location at the next 2 opcode.
+    Defer = 40,
+
+    /// End of the deferred block. Continues with the next deferred block or completes the function return.
+    DeferEnd = 41,
+
+    /// Only generated when the debug assertions are enabled. Raises an error if the condition before the message is not true.
+    Assume = 42,
+
+    /// Copies the last two stack values. Compound assignment on indexers keeps the object and the indexer for SetItem with it.
+    DublicatePair = 43,
+
+    /// Only generated when the type guards are enabled. Raises an error if the type of the argument is not the expected type.
+    TypeGuard = 44,
+
+    /// Left side of 've'. Replaces the last stack value with its bool value, jumps to the location at the next 2 opcode if it is false.
+    JumpIfFalse = 45,
+
+    /// Left side of 'veya'. Replaces the last stack value with its bool value, jumps to the location at the next 2 opcode if it is true.
+    JumpIfTrue = 46,
+
+    /// Same as Constant for the constants after the first 256. Next 2 opcode are the constant location as low and high byte.
+    ConstantWide = 47,
+
+    /// Same as Load for the variables after the first 256. Next 2 opcode are the variable location as low and high byte.
+    LoadWide = 48,
+
+    /// Same as Store for the variables after the first 256. Next 2 opcode are the variable location as low and high byte.
+    StoreWide = 49,
+
+    /// Same as CopyToStore for the variables after the first 256. Next 2 opcode are the variable location as low and high byte.
+    CopyToStoreWide = 50,
+
+    /// Same as Jump for the long programs. Next 4 opcode are the signed offset from the opcode, little endian.
+    JumpWide = 51,
+
+    /// Same as Compare for the long programs. Next 4 opcode are the signed offset of the false block, little endian.
+    CompareWide = 52,
+
+    /// Same as JumpIfFalse for the long programs. Next 4 opcode are the signed offset from the opcode, little endian.
+    JumpIfFalseWide = 53,
+
+    /// Same as JumpIfTrue for the long programs. Next 4 opcode are the signed offset from the opcode, little endian.
+    JumpIfTrueWide = 54,
+
+    /// Same as TryStart for the long programs. Next 4 opcode are the signed offset of the catch block, followed by the catch variable.
+    TryStartWide = 55,
+
+    /// Same as TryEnd for the long programs. Next 4 opcode are the signed offset from the opcode, little endian.
+    TryEndWide = 56,
+
+    /// Same as Defer for the long programs. Next 4 opcode are the signed offset of the block end, little endian.
+    DeferWide = 57,
+
+    /// Same as GetItem for the 'nesne.metot()' calls. Next 2 opcode are the inline cache index as low and high byte.
+    /// Last class of the object and the found property are kept at the cache, same class skips the property search.
+    GetMember = 58,
+
+    /// Start of the 'birlikte' block. Raises an error if the last stack value does not have a 'kapat' function.
+    WithStart = 59,
+
+    /// End of the 'birlikte' block. Removes the deferred 'kapat' call of the block, the call is made after the opcode.
+    /// Next 2 opcode are the deferred block location as low and high byte.
+    WithEnd = 60,
+
+    /// Same as WithEnd for the long programs. Next 4 opcode are the signed offset of the deferred block, little endian.
+    WithEndWide = 61
+}
+
+impl VmOpCode {
+    /// Count of the bytes after the opcode
+    pub fn operand_size(&self) -> usize {
+        match self {
+            VmOpCode::Constant | VmOpCode::Load | VmOpCode::Store | VmOpCode::CopyToStore => 1,
+            VmOpCode::CallStack | VmOpCode::FastStore | VmOpCode::ConstantWide | VmOpCode::LoadWide | VmOpCode::StoreWide | VmOpCode::CopyToStoreWide |
+            VmOpCode::Compare | VmOpCode::Jump | VmOpCode::JumpIfFalse | VmOpCode::JumpIfTrue | VmOpCode::TryEnd | VmOpCode::Defer | VmOpCode::WithEnd |
+            VmOpCode::LoopCounter | VmOpCode::Line | VmOpCode::GetMember => 2,
+            VmOpCode::Call | VmOpCode::Init => 3,
+            VmOpCode::CompareWide | VmOpCode::JumpWide | VmOpCode::JumpIfFalseWide | VmOpCode::JumpIfTrueWide | VmOpCode::TryEndWide | VmOpCode::DeferWide | VmOpCode::WithEndWide => 4,
+            VmOpCode::TryStart => 5,
+            VmOpCode::TryStartWide => 7,
+            _ => 0
+        }
+    }
 }
 
 impl From<VmOpCode> for u8 {
@@ -1,21 +1,30 @@
 use std::cell::RefCell;
 use std::collections::HashMap;
+#[cfg(not(target_arch = "wasm32"))]
+use std::collections::HashSet;
 use std::path::PathBuf;
 use std::rc::Rc;
+#[cfg(not(target_arch = "wasm32"))]
+use std::sync::{Mutex, mpsc};
+#[cfg(not(target_arch = "wasm32"))]
+use std::thread;
 
 use crate::buildin::Class;
 use crate::buildin::Module;
 use crate::compiler::StaticStorage;
 use crate::compiler::function::find_function_definition_type;
+#[cfg(not(target_arch = "wasm32"))]
+use crate::constants::KARAMEL_MODULE_PARSER_THREADS;
 use crate::error::{KaramelError};
 use crate::file::read_module_or_script;
+use crate::stdlib::get_source;
 use crate::parser::Parser;
 use crate::syntax::SyntaxParser;
 use crate::types::CompilerResult;
 
-use super::context::KaramelCompilerContext;
+use super::context::{KaramelCompilerContext, ModuleImport};
 use super::ast::KaramelAstType;
-use super::function::FunctionReference;
+use super::function::{FunctionReference, FunctionFlag};
 
 use crate::error::*;
 
@@ -91,37 +100,186 @@ fn get_module_path(options: &KaramelCompilerContext, module_path: &PathBuf) -> V
     path
 }
 
-pub fn load_module(params: &[String], modules: &mut Vec<Rc<OpcodeModule>>, options: &mut KaramelCompilerContext, upper_storage_index: usize) -> Result<Rc<OpcodeModule>, KaramelError> {
-    let mut path = PathBuf::from(&options.execution_path.path[..]);
-    let module = params[(params.len() - 1)].to_string();
+/* Syntax tree of the module file or the error of reading and parsing it */
+type ParsedModule = Result<Rc<KaramelAstType>, KaramelError>;
 
-    for item in params.iter().take(params.len() - 1) {
-        path.push(item);
-    }
+/* Parsed modules are moved to the compiler thread after the parser threads are joined, so the reference counts are never changed from two threads */
+#[cfg(not(target_arch = "wasm32"))]
+struct ThreadParsedModule(ParsedModule);
 
-    path.push(module.clone());
+#[cfg(not(target_arch = "wasm32"))]
+unsafe impl Send for ThreadParsedModule {}
 
-    let content = match read_module_or_script(path.to_str().unwrap(), options) {
-        Ok(content) => content,
-        Err(error) => return Err(KaramelError::new(0, 0, error))
+/* Modules of the küfe packages are searched at the directory of the package */
+pub fn get_module_file_path(params: &[String], options: &KaramelCompilerContext) -> PathBuf {
+    let (mut path, params) = match options.packages.get(&params[0]) {
+        Some(directory) => (PathBuf::from(directory), &params[1..]),
+        None => (PathBuf::from(&options.execution_path.path[..]), params)
     };
 
-    let mut parser = Parser::new(&content);
+    for item in params.iter() {
+        path.push(item);
+    }
+    path
+}
+
+/* Files of the program are searched first, standard library modules that are written in Karamel are used when there is no file */
+fn read_module(params: &[String], path: &str, options: &KaramelCompilerContext) -> Result<String, KaramelErrorType> {
+    match read_module_or_script(path, options) {
+        Ok(content) => Ok(content),
+        Err(error) => get_source(params).map(str::to_string).ok_or(error)
+    }
+}
+
+fn parse_module(content: &str) -> ParsedModule {
+    let mut parser = Parser::new(content);
     parser.parse()?;
+    SyntaxParser::new(parser.tokens().to_vec()).parse()
+}
 
-    let syntax = SyntaxParser::new(parser.tokens().to_vec());
-    return match syntax.parse() {
+/* Same search with 'find_load_type', only the module names are collected */
+fn find_load_names(ast: &KaramelAstType, names: &mut Vec<Vec<String>>) {
+    match ast {
+        KaramelAstType::Load(module_name) => names.push(module_name.clone()),
+        KaramelAstType::Use { path, .. } => names.push(path.clone()),
+        KaramelAstType::Block(blocks) => for block in blocks {
+            find_load_names(block, names);
+        },
+        _ => ()
+    }
+}
+
+/* Module files are read here and parsed at the worker threads. Idle workers take the next file from the shared queue,
+   'yükle' lines of the parsed files add new files to the queue. Modules are added to the compiler later in the source order. */
+#[cfg(not(target_arch = "wasm32"))]
+fn parse_modules(main_ast: &KaramelAstType, options: &KaramelCompilerContext) -> HashMap<String, ParsedModule> {
+    let mut parsed_modules = HashMap::new();
+    let mut names = Vec::new();
+    find_load_names(main_ast, &mut names);
+    if names.is_empty() {
+        return parsed_modules;
+    }
+
+    let workers = thread::available_parallelism().map_or(1, |count| count.get()).min(KARAMEL_MODULE_PARSER_THREADS);
+    let (job_sender, job_receiver) = mpsc::channel::<(String, String)>();
+    let (result_sender, result_receiver) = mpsc::channel();
+    let job_receiver = Mutex::new(job_receiver);
+    let mut results = Vec::new();
+
+    thread::scope(|scope| {
+        let mut handles = Vec::with_capacity(workers);
+        for _ in 0..workers {
+            let job_receiver = &job_receiver;
+            let result_sender = result_sender.clone();
+            handles.push(scope.spawn(move || loop {
+                let job = job_receiver.lock().unwrap().recv();
+                let (path, content) = match job {
+                    Ok(job) => job,
+                    Err(_) => break
+                };
+
+                let parsed = parse_module(&content);
+                let mut names = Vec::new();
+                if let Ok(ast) = &parsed {
+                    find_load_names(ast, &mut names);
+                }
+
+                if result_sender.send((path, names, ThreadParsedModule(parsed))).is_err() {
+                    break;
+                }
+            }));
+        }
+        drop(result_sender);
+
+        let mut requested = HashSet::new();
+        let mut pending = 0;
+        loop {
+            for name in names.drain(..) {
+                if options.has_module(&name) || options.permission_error(&name).is_some() {
+                    continue;
+                }
+
+                let path = get_module_file_path(&name, options).to_str().unwrap().to_string();
+                if !requested.insert(path.clone()) {
+                    continue;
+                }
+
+                match read_module(&name, path.as_str(), options) {
+                    Ok(content) => {
+                        job_sender.send((path, content)).unwrap();
+                        pending += 1;
+                    },
+                    Err(error) => { parsed_modules.insert(path, Err(KaramelError::new(0, 0, error))); }
+                };
+            }
+
+            if pending == 0 {
+                break;
+            }
+
+            let (path, module_names, parsed) = match result_receiver.recv() {
+                Ok(result) => result,
+                Err(_) => break
+            };
+            pending -= 1;
+            names = module_names;
+            results.push((path, parsed));
+        }
+
+        /* Thread locals of the workers keep the interned texts of the trees, they are released when the threads are joined */
+        drop(job_sender);
+        for handle in handles {
+            handle.join().unwrap();
+        }
+    });
+
+    for (path, ThreadParsedModule(parsed)) in results {
+        parsed_modules.insert(path, parsed);
+    }
+    parsed_modules
+}
+
+/* Threads are not available at the browser */
+#[cfg(target_arch = "wasm32")]
+fn parse_modules(_: &KaramelAstType, _: &KaramelCompilerContext) -> HashMap<String, ParsedModule> {
+    HashMap::new()
+}
+
+pub fn load_module(params: &[String], modules: &mut Vec<Rc<OpcodeModule>>, options: &mut KaramelCompilerContext, upper_storage_index: usize) -> Result<Rc<OpcodeModule>, KaramelError> {
+    load_parsed_module(params, modules, options, upper_storage_index, &mut HashMap::new())
+}
+
+/* Modules that are parsed before are taken from 'parsed_modules', others are read and parsed here */
+fn load_parsed_module(params: &[String], modules: &mut Vec<Rc<OpcodeModule>>, options: &mut KaramelCompilerContext, upper_storage_index: usize, parsed_modules: &mut HashMap<String, ParsedModule>) -> Result<Rc<OpcodeModule>, KaramelError> {
+    let path = get_module_file_path(params, options);
+    let module = params[(params.len() - 1)].to_string();
+
+    let parsed = match parsed_modules.remove(path.to_str().unwrap()) {
+        Some(parsed) => parsed,
+        None => match read_module(params, path.to_str().unwrap(), options) {
+            Ok(content) => parse_module(&content),
+            Err(error) => return Err(KaramelError::new(0, 0, error))
+        }
+    };
+
+    return match parsed {
         Ok(ast) => {
             let module_storage = options.storages.len();
             options.storages.push(StaticStorage::new(module_storage));
             options.storages[module_storage].set_parent_location(upper_storage_index);
 
             let mut module = OpcodeModule::new(module, path.to_str().unwrap().to_string(), ast.clone());
-            module.path = get_module_path(options, &path);
+            module.path = match options.packages.contains_key(&params[0]) {
+                true => params.to_vec(),
+                false => get_module_path(options, &path)
+            };
             module.storage_index = module_storage;
 
+            /* Functions of the module are searched in the collection while their storages are built */
             let module = Rc::new(module);
-            find_load_type(module.main_ast.clone(), options, modules, module.storage_index)?;
+            options.modules.add_module(module.clone());
+            options.imports.insert(module_storage, Vec::new());
+            find_load_type(module.main_ast.clone(), options, modules, &module.path, module.storage_index, parsed_modules)?;
             find_function_definition_type(module.clone(), ast.clone(), options, module_storage, true).map_err(KaramelErrorType::from)?;
             Ok(module.clone())
         },
@@ -129,18 +287,60 @@ pub fn load_module(params: &[String], modules: &mut Vec<Rc<OpcodeModule>>, optio
     };
 }
 
-fn find_load_type(ast: Rc<KaramelAstType>, options: &mut KaramelCompilerContext, modules: &mut Vec<Rc<OpcodeModule>>, upper_storage_index: usize) -> CompilerResult {
+/* Names that are written in '{}' should be found in the module, private functions can not be taken */
+fn check_imported_names(path: &[String], names: &[String], options: &KaramelCompilerContext) -> CompilerResult {
+    let module = match options.modules.iter().find(|(_, module)| module.get_path() == path) {
+        Some((_, module)) => module.clone(),
+        None => return Err(KaramelErrorType::ImportNotFound(path.join("::")))
+    };
+
+    for name in names.iter() {
+        let full_name = format!("{}::{}", path.join("::"), name);
+        match module.get_method(name) {
+            Some(function) if function.flags.contains(FunctionFlag::PRIVATE) => return Err(KaramelErrorType::FunctionIsPrivate(full_name)),
+            Some(_) => (),
+            None if module.get_constant(name).is_some() => (),
+            None => return Err(KaramelErrorType::ImportNotFound(full_name))
+        };
+    }
+    Ok(())
+}
+
+fn find_load_type(ast: Rc<KaramelAstType>, options: &mut KaramelCompilerContext, modules: &mut Vec<Rc<OpcodeModule>>, file: &[String], upper_storage_index: usize, parsed_modules: &mut HashMap<String, ParsedModule>) -> CompilerResult {
     match &*ast {
         KaramelAstType::Load(module_name) => {
+            if let Some(error) = options.permission_error(module_name) {
+                return Err(error);
+            }
+
             if !options.has_module(&module_name) {
-                let module = load_module(module_name, modules, options, upper_storage_index)?;
+                let module = load_parsed_module(module_name, modules, options, upper_storage_index, parsed_modules)?;
                 options.add_module(module.clone());
                 modules.push(module.clone());
             }
         },
+        KaramelAstType::Use { path, alias, names } => {
+            if let Some(error) = options.permission_error(path) {
+                return Err(error);
+            }
+
+            if !options.has_module(path) {
+                let module = load_parsed_module(path, modules, options, upper_storage_index, parsed_modules)?;
+                options.add_module(module.clone());
+                modules.push(module.clone());
+            }
+
+            check_imported_names(path, names, options)?;
+            options.imports.entry(upper_storage_index).or_default().push(ModuleImport {
+                file: file.to_vec(),
+                path: path.to_vec(),
+                alias: alias.clone(),
+                names: names.to_vec()
+            });
+        },
         KaramelAstType::Block(blocks) => {
             for block in blocks {
-                find_load_type(block.clone(), options, modules, upper_storage_index)?;
+                find_load_type(block.clone(), options, modules, file, upper_storage_index, parsed_modules)?;
             }
         },
         _ => ()
@@ -151,7 +351,9 @@ fn find_load_type(ast: Rc<KaramelAstType>, options: &mut KaramelCompilerContext,
 
 pub fn get_modules(main_ast: Rc<KaramelAstType>, options: &mut KaramelCompilerContext) -> Result<Vec<Rc<OpcodeModule>>, KaramelError> {
     let mut modules: Vec<Rc<OpcodeModule>> = Vec::new();
-    match find_load_type(main_ast, options, &mut modules, 0) {
+    let mut parsed_modules = parse_modules(&main_ast, options);
+    options.imports.insert(0, Vec::new());
+    match find_load_type(main_ast, options, &mut modules, &[], 0, &mut parsed_modules) {
         Ok(()) => Ok(modules),
         Err(error) => Err(KaramelError::new(0, 0, error))
     }
@@ -171,6 +373,8 @@ mod tests {
     use crate::error::KaramelErrorType;
     use crate::vm::executer::ExecutionSource;
     use crate::vm::executer::get_execution_path;
+    use crate::file::MemoryFileSystem;
+    use std::rc::Rc;
 
     fn setup() {
         println!("setup");
@@ -254,4 +458,20 @@ fonk topla2(bir, iki): dondur module_1::topla(bir, iki)"#;
             Ok(())
         }, [module_1_path, module_2_path].to_vec())
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_memory_file_system() -> Result<(), KaramelErrorType> {
+        let file_system = MemoryFileSystem::new();
+        file_system.add_file("proje/topla.k", "fonk topla(bir, iki): dondur bir + iki");
+        file_system.add_file("proje/araclar/baz.k", "fonk ikile(bir): dondur bir * 2");
+
+        let mut modules = Vec::new();
+        let mut options = KaramelCompilerContext::new();
+        options.file_system = Rc::new(file_system);
+        options.execution_path = get_execution_path(ExecutionSource::File("./proje".to_string()));
+        load_module(&[String::from("topla")].to_vec(), &mut modules, &mut options, 0)?;
+        load_module(&[String::from("araclar")].to_vec(), &mut modules, &mut options, 0)?;
+        assert!(load_module(&[String::from("yok")].to_vec(), &mut modules, &mut options, 0).is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,488 @@
+use std::collections::HashSet;
+use std::rc::Rc;
+
+use crate::compiler::ast::{KaramelAstType, KaramelIfStatementElseItem};
+use crate::compiler::KaramelPrimative;
+use crate::compiler::context::KaramelCompilerContext;
+use crate::compiler::locator::{Position, TokenLocator};
+use crate::compiler::visitor::{AstVisitor, VisitorResult, walk_loop};
+use crate::syntax::loops::LoopType;
+use crate::error::{CompilerWarning, KaramelError, KaramelErrorType, KaramelWarningType};
+use crate::types::{KaramelOperatorType, Token};
+
+#[derive(Default)]
+struct ResolverScope {
+    /* Names that are assigned until the current position */
+    assigned: HashSet<String>,
+
+    /* Names that are assigned anywhere in the scope */
+    variables: HashSet<String>,
+    functions: HashSet<String>,
+
+    /* 'yerel' variables of the 'ise' and 'döngü' bodies, innermost body is the last */
+    blocks: Vec<HashSet<String>>,
+
+    /* Names that are assigned in the enclosing loops. Value of the previous iteration can be read before the assignment. */
+    loops: Vec<HashSet<String>>
+}
+
+/* Collects the variables and the functions of the scope. Inner function bodies belong to other scopes. */
+struct ScopeCollector<'a> {
+    scope: &'a mut ResolverScope
+}
+
+impl AstVisitor for ScopeCollector<'_> {
+    type Error = ();
+
+    fn visit_assignment(&mut self, variable: &KaramelAstType, _: KaramelOperatorType, expression: &KaramelAstType) -> VisitorResult<()> {
+        if let KaramelAstType::Symbol(name) = variable {
+            self.scope.variables.insert(name.to_string());
+        }
+        self.visit(expression)
+    }
+
+    /* Block variables are not the variables of the scope */
+    fn visit_local(&mut self, assignment: &KaramelAstType) -> VisitorResult<()> {
+        match assignment {
+            KaramelAstType::Assignment { expression, .. } => self.visit(expression),
+            _ => self.visit(assignment)
+        }
+    }
+
+    fn visit_function_defination(&mut self, name: &str, _: &[String], _: &KaramelAstType) -> VisitorResult<()> {
+        self.scope.functions.insert(name.to_string());
+        Ok(())
+    }
+
+    /* Names that are taken with 'kullan' are the functions and the constants of the module */
+    fn visit_use(&mut self, _: &[String], _: Option<&str>, names: &[String]) -> VisitorResult<()> {
+        self.scope.functions.extend(names.iter().cloned());
+        Ok(())
+    }
+
+    fn visit_try(&mut self, body: &KaramelAstType, variable: Option<&str>, catch_body: &KaramelAstType) -> VisitorResult<()> {
+        if let Some(variable) = variable {
+            self.scope.variables.insert(variable.to_string());
+        }
+        self.visit(body)?;
+        self.visit(catch_body)
+    }
+}
+
+/* Type of the expressions that only use literals */
+#[derive(PartialEq)]
+enum StaticType {
+    Number,
+    Text
+}
+
+impl StaticType {
+    fn of(ast: &KaramelAstType) -> Option<StaticType> {
+        match ast {
+            KaramelAstType::Primative(primative) => match &**primative {
+                KaramelPrimative::Number(_) => Some(StaticType::Number),
+                KaramelPrimative::Text(_) => Some(StaticType::Text),
+                _ => None
+            },
+            KaramelAstType::Binary { left, operator, right } => {
+                let (left, right) = (StaticType::of(left)?, StaticType::of(right)?);
+                match (left == right, operator) {
+                    (true, KaramelOperatorType::Addition) => Some(left),
+                    (true, _) if left == StaticType::Number => Some(StaticType::Number),
+                    _ => None
+                }
+            },
+            _ => None
+        }
+    }
+}
+
+struct Resolver<'a> {
+    context: &'a KaramelCompilerContext,
+    locator: TokenLocator,
+    scopes: Vec<ResolverScope>,
+    defined_functions: HashSet<String>,
+    function_positions: Vec<(String, Position)>,
+    used_functions: HashSet<String>,
+    errors: Vec<KaramelError>,
+    warnings: Vec<CompilerWarning>
+}
+
+impl Resolver<'_> {
+    fn error(&mut self, position: Position, error_type: KaramelErrorType) {
+        self.errors.push(KaramelError::new(position.0, position.1, error_type));
+    }
+
+    fn warning(&mut self, position: Position, warning_type: KaramelWarningType) {
+        self.warnings.push(CompilerWarning::new(position.0, position.1, warning_type));
+    }
+
+    /* Function scopes can not reach the outer variables, but same names confuse the readers */
+    fn check_shadowing(&mut self, name: &str, position: Position) {
+        let (current, outers) = self.scopes.split_last().unwrap();
+        if current.assigned.contains(name) {
+            return;
+        }
+
+        if outers.iter().any(|scope| scope.variables.contains(name) || scope.assigned.contains(name)) {
+            self.warning(position, KaramelWarningType::ShadowedVariable(name.to_string()));
+        }
+    }
+
+    fn push_scope(&mut self, arguments: &[String], body: &KaramelAstType) {
+        let mut scope = ResolverScope::default();
+        scope.assigned.extend(arguments.iter().cloned());
+        let _ = ScopeCollector { scope: &mut scope }.visit(body);
+        self.scopes.push(scope);
+    }
+
+    fn assign(&mut self, name: &str) {
+        self.scopes.last_mut().unwrap().assigned.insert(name.to_string());
+    }
+
+    fn is_local(&self, name: &str) -> bool {
+        self.scopes.last().unwrap().blocks.iter().any(|block| block.contains(name))
+    }
+
+    fn visit_block_body(&mut self, body: &KaramelAstType) -> VisitorResult<()> {
+        self.scopes.last_mut().unwrap().blocks.push(HashSet::new());
+        let result = self.visit(body);
+        self.scopes.last_mut().unwrap().blocks.pop();
+        result
+    }
+
+    fn is_function(&self, name: &str) -> bool {
+        self.scopes.iter().any(|scope| scope.functions.contains(name))
+    }
+
+    fn is_loop_variable(&self, name: &str) -> bool {
+        self.scopes.last().unwrap().loops.iter().any(|names| names.contains(name))
+    }
+
+    fn resolve_symbol(&mut self, name: &str, position: Position) {
+        if self.is_function(name) {
+            self.used_functions.insert(name.to_string());
+        }
+
+        let scope = self.scopes.last().unwrap();
+        if scope.assigned.contains(name) || self.is_local(name) || self.is_function(name) || self.is_loop_variable(name) {
+            return;
+        }
+
+        if self.context.find_class(name.to_string(), &Vec::new(), 0).is_some() || self.context.get_teaching_constant(name.to_string()).is_some() {
+            return;
+        }
+
+        match scope.variables.contains(name) {
+            true => self.error(position, KaramelErrorType::VariableUsedBeforeAssignment(name.to_string())),
+            false => self.error(position, KaramelErrorType::VariableNotDefined(name.to_string()))
+        };
+    }
+}
+
+impl AstVisitor for Resolver<'_> {
+    type Error = ();
+
+    fn visit_func_call(&mut self, func_name_expression: &KaramelAstType, arguments: &[Rc<KaramelAstType>]) -> VisitorResult<()> {
+        match func_name_expression {
+            KaramelAstType::Symbol(name) => {
+                let position = self.locator.symbol(name);
+                self.used_functions.insert(name.to_string());
+                let scope = self.scopes.last().unwrap();
+                if !scope.assigned.contains(name) && !self.is_function(name) && !self.is_loop_variable(name) {
+                    match scope.variables.contains(name) {
+                        true => self.error(position, KaramelErrorType::VariableUsedBeforeAssignment(name.to_string())),
+                        false => self.error(position, KaramelErrorType::FunctionNotFound(name.to_string()))
+                    };
+                }
+            },
+            _ => self.visit(func_name_expression)?
+        };
+
+        for argument in arguments.iter() {
+            self.visit(argument)?;
+        }
+        Ok(())
+    }
+
+    fn visit_accessor_func_call(&mut self, source: &KaramelAstType, indexer: &KaramelAstType) -> VisitorResult<()> {
+        self.visit(source)?;
+
+        /* Method names are resolved at runtime */
+        match indexer {
+            KaramelAstType::FuncCall { func_name_expression, arguments, assign_to_temp: _ } if matches!(&**func_name_expression, KaramelAstType::Symbol(_)) => {
+                if let KaramelAstType::Symbol(name) = &**func_name_expression {
+                    self.locator.symbol(name);
+                }
+
+                for argument in arguments.iter() {
+                    self.visit(argument)?;
+                }
+                Ok(())
+            },
+            _ => self.visit(indexer)
+        }
+    }
+
+    fn visit_assignment(&mut self, variable: &KaramelAstType, operator: KaramelOperatorType, expression: &KaramelAstType) -> VisitorResult<()> {
+        match variable {
+            KaramelAstType::Symbol(name) if self.is_local(name) => {
+                let position = self.locator.symbol(name);
+                if operator != KaramelOperatorType::Assign {
+                    self.resolve_symbol(name, position);
+                }
+                self.visit(expression)
+            },
+            KaramelAstType::Symbol(name) => {
+                let position = self.locator.symbol(name);
+                if operator != KaramelOperatorType::Assign {
+                    self.resolve_symbol(name, position);
+                }
+                self.check_shadowing(name, position);
+
+                self.visit(expression)?;
+                self.assign(name);
+                Ok(())
+            },
+            _ => {
+                self.visit(variable)?;
+                self.visit(expression)
+            }
+        }
+    }
+
+    fn visit_binary(&mut self, left: &KaramelAstType, operator: KaramelOperatorType, right: &KaramelAstType) -> VisitorResult<()> {
+        self.visit(left)?;
+        let position = self.locator.operator(operator);
+
+        if operator == KaramelOperatorType::Addition {
+            match (StaticType::of(left), StaticType::of(right)) {
+                (Some(StaticType::Text), Some(StaticType::Number)) | (Some(StaticType::Number), Some(StaticType::Text)) => self.warning(position, KaramelWarningType::ImplicitNumberToText),
+                _ => ()
+            };
+        }
+        self.visit(right)
+    }
+
+    fn visit_if_statement(&mut self, condition: &KaramelAstType, body: &KaramelAstType, else_body: Option<&KaramelAstType>, else_if: &[Rc<KaramelIfStatementElseItem>]) -> VisitorResult<()> {
+        self.visit(condition)?;
+        self.visit_block_body(body)?;
+
+        for item in else_if.iter() {
+            self.visit(&item.condition)?;
+            self.visit_block_body(&item.body)?;
+        }
+
+        if let Some(else_body) = else_body {
+            self.visit_block_body(else_body)?;
+        }
+        Ok(())
+    }
+
+    fn visit_loop(&mut self, loop_type: &LoopType, body: &KaramelAstType) -> VisitorResult<()> {
+        let mut names = ResolverScope::default();
+        let _ = walk_loop(&mut ScopeCollector { scope: &mut names }, loop_type, body);
+        self.scopes.last_mut().unwrap().loops.push(names.variables);
+
+        let result = walk_loop(self, loop_type, &KaramelAstType::None).and_then(|_| self.visit_block_body(body));
+        self.scopes.last_mut().unwrap().loops.pop();
+        result
+    }
+
+    /* 'kapat' call is not written in the code, it has no position */
+    fn visit_with(&mut self, assignment: &KaramelAstType, _: &KaramelAstType, body: &KaramelAstType) -> VisitorResult<()> {
+        self.visit(assignment)?;
+        self.visit_block_body(body)
+    }
+
+    /* Value is resolved before the variable exists, so 'yerel a = a + 1' reads the outer variable */
+    fn visit_local(&mut self, assignment: &KaramelAstType) -> VisitorResult<()> {
+        if self.scopes.last().unwrap().blocks.is_empty() {
+            return self.visit(assignment);
+        }
+
+        match assignment {
+            KaramelAstType::Assignment { variable, expression, .. } => match &**variable {
+                KaramelAstType::Symbol(name) => {
+                    self.locator.symbol(name);
+                    self.visit(expression)?;
+                    self.scopes.last_mut().unwrap().blocks.last_mut().unwrap().insert(name.to_string());
+                    Ok(())
+                },
+                _ => self.visit(assignment)
+            },
+            _ => self.visit(assignment)
+        }
+    }
+
+    fn visit_function_defination(&mut self, name: &str, arguments: &[String], body: &KaramelAstType) -> VisitorResult<()> {
+        let position = self.locator.symbol(name);
+        if !self.defined_functions.insert(name.to_string()) {
+            self.error(position, KaramelErrorType::FunctionAlreadyDefined(name.to_string()));
+        }
+        self.function_positions.push((name.to_string(), position));
+
+        self.scopes.push(ResolverScope::default());
+        for argument in arguments.iter() {
+            let position = self.locator.symbol(argument);
+            self.check_shadowing(argument, position);
+        }
+        self.scopes.pop();
+
+        self.push_scope(arguments, body);
+        let result = self.visit(body);
+        self.scopes.pop();
+        result
+    }
+
+    fn visit_try(&mut self, body: &KaramelAstType, variable: Option<&str>, catch_body: &KaramelAstType) -> VisitorResult<()> {
+        self.visit(body)?;
+
+        /* Catch variable is assigned before the catch body runs */
+        if let Some(variable) = variable {
+            let position = self.locator.symbol(variable);
+            self.check_shadowing(variable, position);
+            self.assign(variable);
+        }
+        self.visit(catch_body)
+    }
+
+    fn visit_symbol(&mut self, name: &str) -> VisitorResult<()> {
+        let position = self.locator.symbol(name);
+        self.resolve_symbol(name, position);
+        Ok(())
+    }
+
+    fn visit_module_path(&mut self, path: &[String]) -> VisitorResult<()> {
+        for name in path.iter() {
+            self.locator.symbol(name);
+        }
+        Ok(())
+    }
+
+    fn visit_load(&mut self, path: &[String]) -> VisitorResult<()> {
+        self.visit_module_path(path)
+    }
+
+    fn visit_use(&mut self, path: &[String], alias: Option<&str>, names: &[String]) -> VisitorResult<()> {
+        self.visit_module_path(path)?;
+        self.visit_module_path(names)?;
+        if let Some(alias) = alias {
+            self.locator.symbol(alias);
+        }
+        Ok(())
+    }
+
+    fn visit_enum(&mut self, name: &str, variants: &[String]) -> VisitorResult<()> {
+        self.locator.symbol(name);
+        for variant in variants.iter() {
+            self.locator.symbol(variant);
+        }
+        Ok(())
+    }
+}
+
+/// Checks the names before the opcodes are generated. Undefined variables, variables that are used before the assignment and
+/// functions that are defined more than once are reported with their positions. Errors are ordered by the position.
+/// Non fatal issues are added to the warnings of the context.
+pub fn resolve(ast: &KaramelAstType, tokens: &[Token], context: &mut KaramelCompilerContext) -> Vec<KaramelError> {
+    let mut resolver = Resolver {
+        context,
+        locator: TokenLocator::new(tokens),
+        scopes: Vec::new(),
+        defined_functions: HashSet::new(),
+        function_positions: Vec::new(),
+        used_functions: HashSet::new(),
+        errors: Vec::new(),
+        warnings: Vec::new()
+    };
+
+    resolver.push_scope(&[], ast);
+    let _ = resolver.visit(ast);
+
+    for (name, position) in std::mem::take(&mut resolver.function_positions) {
+        if !resolver.used_functions.contains(&name) && !name.starts_with('_') {
+            resolver.warning(position, KaramelWarningType::UnusedFunction(name));
+        }
+    }
+
+    let mut errors = resolver.errors;
+    let mut warnings = resolver.warnings;
+    errors.sort_by_key(|error| (error.line, error.column));
+    warnings.sort_by_key(|warning| (warning.line, warning.column));
+
+    context.warnings.extend(warnings);
+    errors
+}
+
+#[cfg(test)]
+mod tests {
+    use super::resolve;
+    use crate::compiler::context::KaramelCompilerContext;
+    use crate::error::{KaramelErrorType, KaramelWarningType};
+    use crate::parser::Parser;
+    use crate::syntax::SyntaxParser;
+
+    fn check(code: &str) -> Vec<(u32, u32, KaramelErrorType)> {
+        let mut parser = Parser::new(code);
+        parser.parse().unwrap();
+        let ast = SyntaxParser::new(parser.tokens()).parse().unwrap();
+        resolve(&ast, &parser.tokens(), &mut KaramelCompilerContext::new()).into_iter().map(|error| (error.line, error.column, error.error_type)).collect()
+    }
+
+    fn warnings(code: &str) -> Vec<(u32, u32, KaramelWarningType)> {
+        let mut parser = Parser::new(code);
+        parser.parse().unwrap();
+        let ast = SyntaxParser::new(parser.tokens()).parse().unwrap();
+        let mut context = KaramelCompilerContext::new();
+        assert!(resolve(&ast, &parser.tokens(), &mut context).is_empty());
+        context.warnings.into_iter().map(|warning| (warning.line, warning.column, warning.warning_type)).collect()
+    }
+
+    #[test]
+    fn undefined_variable() {
+        assert_eq!(check("a = 1\nb = a + c"), vec![(1, 8, KaramelErrorType::VariableNotDefined("c".to_string()))]);
+        assert_eq!(check("a = 1\na += 1\nliste = [a]\nliste.uzunluk()\ngç::satıryaz(a)"), vec![]);
+    }
+
+    #[test]
+    fn use_before_assignment() {
+        assert_eq!(check("b = a\na = 1"), vec![(0, 4, KaramelErrorType::VariableUsedBeforeAssignment("a".to_string()))]);
+        assert_eq!(check("a = a + 1"), vec![(0, 4, KaramelErrorType::VariableUsedBeforeAssignment("a".to_string()))]);
+        assert_eq!(check("döngü i = 0, i < 2, ++i:\n    a = i"), vec![]);
+        assert_eq!(check("i = 0\ndöngü i < 2:\n    i > 0 ise:\n        gç::satıryaz(önceki)\n    önceki = i\n    i += 1"), vec![]);
+        assert_eq!(check("döngü i = 0, i < 2, ++i:\n    b = a\na = 1"), vec![(1, 8, KaramelErrorType::VariableUsedBeforeAssignment("a".to_string()))]);
+    }
+
+    #[test]
+    fn function_scope() {
+        assert_eq!(check("fonk topla(a, b):\n    döndür a + b\ntopla(1, 2)"), vec![]);
+        assert_eq!(check("çarpan = 2\nfonk çarp(a):\n    döndür a * çarpan"), vec![(2, 15, KaramelErrorType::VariableNotDefined("çarpan".to_string()))]);
+        assert_eq!(check("sonuç = çağır()\nfonk çağır():\n    döndür 1"), vec![]);
+        assert_eq!(check("bilinmeyen()"), vec![(0, 0, KaramelErrorType::FunctionNotFound("bilinmeyen".to_string()))]);
+    }
+
+    #[test]
+    fn imported_names() {
+        assert_eq!(check("kullan gç::{satıryaz}\nsatıryaz(1)\nfonk yaz():\n    satıryaz(2)"), vec![]);
+        assert_eq!(check("kullan gç olarak g\ng::satıryaz(1)\nyaz(1)"), vec![(2, 0, KaramelErrorType::FunctionNotFound("yaz".to_string()))]);
+    }
+
+    #[test]
+    fn catch_variable() {
+        assert_eq!(check("dene:\n    fırlat 'yok'\nyakala hata:\n    gç::satıryaz(hata)"), vec![]);
+        assert_eq!(check("dene:\n    a = 1\nyakala:\n    gç::satıryaz(hata)"), vec![(3, 17, KaramelErrorType::VariableNotDefined("hata".to_string()))]);
+    }
+
+    #[test]
+    fn duplicate_function() {
+        assert_eq!(check("fonk f():\n    döndür 1\nfonk f():\n    döndür 2"), vec![(2, 5, KaramelErrorType::FunctionAlreadyDefined("f".to_string()))]);
+    }
+
+    #[test]
+    fn compiler_warnings() {
+        assert_eq!(warnings("fonk f():\n    döndür 1\nfonk _g():\n    döndür 2"), vec![(0, 5, KaramelWarningType::UnusedFunction("f".to_string()))]);
+        assert_eq!(warnings("a = 'not: ' + 1\nb = 1 + 2 + 'x'"), vec![(0, 12, KaramelWarningType::ImplicitNumberToText), (1, 10, KaramelWarningType::ImplicitNumberToText)]);
+        assert_eq!(warnings("a = 1\nfonk f(a):\n    b = a\n    döndür b\nb = f(a)"), vec![(1, 7, KaramelWarningType::ShadowedVariable("a".to_string())), (2, 4, KaramelWarningType::ShadowedVariable("b".to_string()))]);
+    }
+}
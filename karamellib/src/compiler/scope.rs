@@ -1,6 +1,8 @@
 use std::ptr;
 
 use crate::types::VmObject;
+use crate::error::KaramelErrorType;
+use crate::compiler::function::FunctionReference;
 
 
 #[derive(Clone)]
@@ -8,7 +10,23 @@ pub struct Scope {
     pub location: *mut u8,
     pub call_return_assign_to_temp: bool,
     pub top_stack: *mut VmObject,
-    pub constant_ptr: *const VmObject
+    pub constant_ptr: *const VmObject,
+    pub storage_index: usize,
+
+    /// Called function. Empty for the main code.
+    pub function: *const FunctionReference,
+
+    /// Opcode locations of the 'ertele' blocks. Last added block runs first.
+    pub deferred: Vec<usize>,
+
+    /// Return opcode location. Execution continues from here after the deferred block.
+    pub defer_return: *mut u8,
+
+    /// Stack position of the return value. Deferred block could leave values at the stack, they are dropped.
+    pub defer_stack: *mut VmObject,
+
+    /// Function is left with this error, it is sent to the caller after the deferred blocks.
+    pub defer_error: Option<KaramelErrorType>
 }
 
 impl Scope {
@@ -17,7 +35,38 @@ impl Scope {
             call_return_assign_to_temp: false, 
             location: ptr::null_mut(), 
             top_stack: ptr::null_mut(), 
-            constant_ptr: ptr::null()
+            constant_ptr: ptr::null(),
+            storage_index: 0,
+            function: ptr::null(),
+            deferred: Vec::new(),
+            defer_return: ptr::null_mut(),
+            defer_stack: ptr::null_mut(),
+            defer_error: None
         }
     }
-}
\ No newline at end of file
+}
+/// Function call that is still running. Main code is the first frame.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CallFrame {
+    /// Empty for the main code
+    pub function: Option<String>,
+
+    /// Line that is running in the function, zero based
+    pub line: Option<u32>,
+
+    /// Start column of the running statement, zero based
+    pub column: Option<u32>,
+
+    /// Names and the current values of the arguments. Values are written short, collections are written with their type and size.
+    pub arguments: Vec<(String, String)>
+}
+
+/// Active 'dene' block. Locations are opcode indexes, the protected range starts with the TryStart opcode and ends before the catch block.
+#[derive(Clone)]
+pub struct ErrorHandler {
+    pub start: usize,
+    pub catch_location: usize,
+    pub variable: Option<usize>,
+    pub scope_index: usize,
+    pub stack_ptr: *mut VmObject
+}
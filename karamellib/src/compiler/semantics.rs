@@ -0,0 +1,445 @@
+use std::cell::RefCell;
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::rc::Rc;
+
+use crate::types::VmObject;
+use crate::compiler::value::KaramelPrimative;
+use crate::interner::same_text;
+#[cfg(feature = "vm")]
+use crate::buildin::class::priority_queue::PriorityQueue;
+
+/* Pairs of values that are being compared. Lists and dicts can contain themselves, so a pair that is already on the way is not compared again. */
+type Visited = Vec<(*const KaramelPrimative, *const KaramelPrimative)>;
+
+/* Collections that are already copied. A collection that contains itself is copied once and the copy contains the copy. */
+type Copies = HashMap<*const KaramelPrimative, VmObject>;
+
+pub fn is_truthy(value: &KaramelPrimative) -> bool {
+    match value {
+        KaramelPrimative::Text(value)       => !value.is_empty(),
+        KaramelPrimative::Number(value)     => *value > 0.0,
+        KaramelPrimative::Bool(value)       => *value,
+        KaramelPrimative::List(items)       => !items.borrow().is_empty(),
+        KaramelPrimative::Dict(items)       => !items.borrow().is_empty(),
+        KaramelPrimative::Empty             => false,
+        #[cfg(feature = "vm")]
+        KaramelPrimative::Function(_, _)    => true,
+        #[cfg(feature = "vm")]
+        KaramelPrimative::Class(_)          => true,
+        KaramelPrimative::Stack(items)      => !items.borrow().is_empty(),
+        KaramelPrimative::Queue(items)      => !items.borrow().is_empty(),
+        #[cfg(feature = "vm")]
+        KaramelPrimative::PriorityQueue(items) => !items.borrow().is_empty(),
+        #[cfg(feature = "vm")]
+        KaramelPrimative::Error(_)          => true,
+        KaramelPrimative::Variant(_)        => true,
+        #[cfg(feature = "vm")]
+        KaramelPrimative::Bound(_)          => true,
+        KaramelPrimative::TextBuilder(text) => !text.borrow().is_empty(),
+        KaramelPrimative::Frozen(inner)     => is_truthy(&inner.deref()),
+        KaramelPrimative::SortedDict(items) => !items.borrow().is_empty(),
+        KaramelPrimative::Bytes(bytes)      => !bytes.is_empty()
+    }
+}
+
+/// Structural equality. Lists, dicts, stacks and queues are equal when their items are equal.
+pub fn deep_equal(left: &KaramelPrimative, right: &KaramelPrimative) -> bool {
+    equal(left, right, &mut Vec::new())
+}
+
+/// Total ordering of the values, used for sorting lists that keep different types.
+/// Values are ordered by type first: boş, bool, sayı, yazı, liste, sözlük and the others.
+pub fn compare(left: &KaramelPrimative, right: &KaramelPrimative) -> Ordering {
+    order(left, right, &mut Vec::new())
+}
+
+/// Copy of the value together with the collections in it. Assignment and function calls share the collections,
+/// changes to the copy are not seen from the original. Texts, functions and errors can not be changed, they are not copied.
+pub fn deep_copy(value: VmObject) -> VmObject {
+    copy(value, &mut HashMap::new())
+}
+
+/// Copy of the value and the count of the items in the copied collections, used for limiting the memory of the copies.
+pub fn deep_copy_with_size(value: VmObject) -> (VmObject, usize) {
+    let mut copies = HashMap::new();
+    let object = copy(value, &mut copies);
+    (object, copies.values().map(|copy| item_count(&copy.deref())).sum())
+}
+
+fn item_count(value: &KaramelPrimative) -> usize {
+    match value {
+        KaramelPrimative::List(items) | KaramelPrimative::Stack(items) => items.borrow().len(),
+        KaramelPrimative::Dict(items) => items.borrow().len(),
+        KaramelPrimative::SortedDict(items) => items.borrow().len(),
+        KaramelPrimative::Queue(items) => items.borrow().len(),
+        #[cfg(feature = "vm")]
+        KaramelPrimative::PriorityQueue(items) => items.borrow().len(),
+        KaramelPrimative::TextBuilder(text) => text.borrow().len(),
+        _ => 0
+    }
+}
+
+fn copy_items(items: &[VmObject], copies: &mut Copies) -> Vec<VmObject> {
+    items.iter().map(|item| copy(*item, copies)).collect()
+}
+
+fn copy(value: VmObject, copies: &mut Copies) -> VmObject {
+    let source = value.deref();
+    if let Some(object) = copies.get(&Rc::as_ptr(&source)) {
+        return *object;
+    }
+
+    /* Copy is registered before its items, so the items can refer to it */
+    let target = Rc::new(match &*source {
+        KaramelPrimative::List(_) => KaramelPrimative::List(RefCell::new(Vec::new())),
+        KaramelPrimative::Dict(_) => KaramelPrimative::Dict(RefCell::new(HashMap::new())),
+        KaramelPrimative::Stack(_) => KaramelPrimative::Stack(RefCell::new(Vec::new())),
+        KaramelPrimative::Queue(_) => KaramelPrimative::Queue(RefCell::new(VecDeque::new())),
+        #[cfg(feature = "vm")]
+        KaramelPrimative::PriorityQueue(_) => KaramelPrimative::PriorityQueue(RefCell::new(PriorityQueue::new())),
+        KaramelPrimative::TextBuilder(text) => KaramelPrimative::TextBuilder(RefCell::new(text.borrow().clone())),
+        KaramelPrimative::SortedDict(_) => KaramelPrimative::SortedDict(RefCell::new(BTreeMap::new())),
+        _ => return value
+    });
+    let object = VmObject::native_convert_by_ref(target.clone());
+    copies.insert(Rc::as_ptr(&source), object);
+
+    match (&*source, &*target) {
+        (KaramelPrimative::List(items), KaramelPrimative::List(target)) | (KaramelPrimative::Stack(items), KaramelPrimative::Stack(target)) => {
+            let items = items.borrow().clone();
+            *target.borrow_mut() = copy_items(&items, copies);
+        },
+        (KaramelPrimative::Dict(items), KaramelPrimative::Dict(target)) => {
+            let items = items.borrow().clone();
+            *target.borrow_mut() = items.into_iter().map(|(key, item)| (key, copy(item, copies))).collect();
+        },
+        (KaramelPrimative::SortedDict(items), KaramelPrimative::SortedDict(target)) => {
+            let items = items.borrow().clone();
+            *target.borrow_mut() = items.into_iter().map(|(key, item)| (key, copy(item, copies))).collect();
+        },
+        (KaramelPrimative::Queue(items), KaramelPrimative::Queue(target)) => {
+            let items = items.borrow().iter().copied().collect::<Vec<_>>();
+            *target.borrow_mut() = copy_items(&items, copies).into();
+        },
+        #[cfg(feature = "vm")]
+        (KaramelPrimative::PriorityQueue(items), KaramelPrimative::PriorityQueue(target)) => {
+            let items = items.borrow().clone();
+            *target.borrow_mut() = items.map_values(|item| copy(item, copies));
+        },
+        _ => ()
+    };
+    object
+}
+
+fn is_visited(left: &KaramelPrimative, right: &KaramelPrimative, visited: &mut Visited) -> bool {
+    let pair = (left as *const KaramelPrimative, right as *const KaramelPrimative);
+    if visited.contains(&pair) {
+        return true;
+    }
+    visited.push(pair);
+    false
+}
+
+fn equal_items<'a, I>(left: I, right: I, visited: &mut Visited) -> bool where I: ExactSizeIterator<Item = &'a VmObject> {
+    left.len() == right.len() && left.zip(right).all(|(l_item, r_item)| equal(&l_item.deref(), &r_item.deref(), visited))
+}
+
+fn equal(left: &KaramelPrimative, right: &KaramelPrimative, visited: &mut Visited) -> bool {
+    if std::ptr::eq(left, right) {
+        return true;
+    }
+
+    match (left, right) {
+        /* Frozen values are equal to the lists and dicts with the same items */
+        (KaramelPrimative::Frozen(l_value), _) => equal(&l_value.deref(), right, visited),
+        (_, KaramelPrimative::Frozen(r_value)) => equal(left, &r_value.deref(), visited),
+        (KaramelPrimative::Bool(lvalue),   KaramelPrimative::Bool(rvalue)) => lvalue == rvalue,
+        (KaramelPrimative::Empty,          KaramelPrimative::Empty)        => true,
+        (KaramelPrimative::Number(n),      KaramelPrimative::Number(m))    => if n.is_nan() && m.is_nan() { true } else { n == m },
+        (KaramelPrimative::Text(lvalue),   KaramelPrimative::Text(rvalue)) => same_text(lvalue, rvalue),
+        (KaramelPrimative::List(l_value),  KaramelPrimative::List(r_value)) => {
+            if is_visited(left, right, visited) {
+                return true;
+            }
+            let (l_value, r_value) = (l_value.borrow(), r_value.borrow());
+            equal_items(l_value.iter(), r_value.iter(), visited)
+        },
+        (KaramelPrimative::Dict(l_value),  KaramelPrimative::Dict(r_value)) => {
+            if is_visited(left, right, visited) {
+                return true;
+            }
+            let (l_value, r_value) = (l_value.borrow(), r_value.borrow());
+            l_value.len() == r_value.len() && l_value.iter().all(|(key, l_item)| match r_value.get(key) {
+                Some(r_item) => equal(&l_item.deref(), &r_item.deref(), visited),
+                None => false
+            })
+        },
+        (KaramelPrimative::Stack(l_value), KaramelPrimative::Stack(r_value)) => {
+            if is_visited(left, right, visited) {
+                return true;
+            }
+            let (l_value, r_value) = (l_value.borrow(), r_value.borrow());
+            equal_items(l_value.iter(), r_value.iter(), visited)
+        },
+        (KaramelPrimative::Queue(l_value), KaramelPrimative::Queue(r_value)) => {
+            if is_visited(left, right, visited) {
+                return true;
+            }
+            let (l_value, r_value) = (l_value.borrow(), r_value.borrow());
+            equal_items(l_value.iter(), r_value.iter(), visited)
+        },
+        #[cfg(feature = "vm")]
+        (KaramelPrimative::PriorityQueue(l_value), KaramelPrimative::PriorityQueue(r_value)) => {
+            let (l_value, r_value) = (l_value.borrow().to_vec(), r_value.borrow().to_vec());
+            equal_items(l_value.iter(), r_value.iter(), visited)
+        },
+        #[cfg(feature = "vm")]
+        (KaramelPrimative::Function(l_value, _), KaramelPrimative::Function(r_value, _)) => {
+            l_value.name == r_value.name && l_value.module.get_path() == r_value.module.get_path()
+        },
+        #[cfg(feature = "vm")]
+        (KaramelPrimative::Class(l_value), KaramelPrimative::Class(r_value)) => l_value.get_type() == r_value.get_type(),
+        #[cfg(feature = "vm")]
+        (KaramelPrimative::Error(l_value), KaramelPrimative::Error(r_value)) => {
+            l_value.message == r_value.message &&
+            equal(&l_value.code.deref(), &r_value.code.deref(), visited) &&
+            equal(&l_value.data.deref(), &r_value.data.deref(), visited)
+        },
+        (KaramelPrimative::Variant(l_value), KaramelPrimative::Variant(r_value)) => l_value == r_value,
+
+        /* Captured arguments can be changed lists, so only the same binding is equal */
+        #[cfg(feature = "vm")]
+        (KaramelPrimative::Bound(l_value), KaramelPrimative::Bound(r_value)) => std::rc::Rc::ptr_eq(l_value, r_value),
+        (KaramelPrimative::TextBuilder(l_value), KaramelPrimative::TextBuilder(r_value)) => *l_value.borrow() == *r_value.borrow(),
+        (KaramelPrimative::Bytes(l_value), KaramelPrimative::Bytes(r_value)) => l_value == r_value,
+        (KaramelPrimative::SortedDict(l_value), KaramelPrimative::SortedDict(r_value)) => {
+            if is_visited(left, right, visited) {
+                return true;
+            }
+            let (l_value, r_value) = (l_value.borrow(), r_value.borrow());
+            l_value.len() == r_value.len() && l_value.iter().zip(r_value.iter()).all(|((l_key, l_item), (r_key, r_item))| l_key == r_key && equal(&l_item.deref(), &r_item.deref(), visited))
+        },
+        _ => false
+    }
+}
+
+fn type_order(value: &KaramelPrimative) -> u8 {
+    match value {
+        KaramelPrimative::Empty => 0,
+        KaramelPrimative::Bool(_) => 1,
+        KaramelPrimative::Number(_) => 2,
+        KaramelPrimative::Text(_) => 3,
+        KaramelPrimative::List(_) => 4,
+        KaramelPrimative::Dict(_) => 5,
+        KaramelPrimative::Stack(_) => 6,
+        KaramelPrimative::Queue(_) => 7,
+        #[cfg(feature = "vm")]
+        KaramelPrimative::PriorityQueue(_) => 8,
+        #[cfg(feature = "vm")]
+        KaramelPrimative::Error(_) => 9,
+        #[cfg(feature = "vm")]
+        KaramelPrimative::Function(_, _) | KaramelPrimative::Bound(_) => 10,
+        #[cfg(feature = "vm")]
+        KaramelPrimative::Class(_) => 11,
+        KaramelPrimative::Variant(_) => 12,
+        KaramelPrimative::TextBuilder(_) => 13,
+        KaramelPrimative::Frozen(inner) => type_order(&inner.deref()),
+        KaramelPrimative::SortedDict(_) => 14,
+        KaramelPrimative::Bytes(_) => 15
+    }
+}
+
+fn order_items<'a, I>(mut left: I, mut right: I, visited: &mut Visited) -> Ordering where I: Iterator<Item = &'a VmObject> {
+    loop {
+        match (left.next(), right.next()) {
+            (Some(l_item), Some(r_item)) => match order(&l_item.deref(), &r_item.deref(), visited) {
+                Ordering::Equal => continue,
+                ordering => return ordering
+            },
+            (None, Some(_)) => return Ordering::Less,
+            (Some(_), None) => return Ordering::Greater,
+            (None, None) => return Ordering::Equal
+        }
+    }
+}
+
+fn order(left: &KaramelPrimative, right: &KaramelPrimative, visited: &mut Visited) -> Ordering {
+    if std::ptr::eq(left, right) {
+        return Ordering::Equal;
+    }
+
+    match (left, right) {
+        (KaramelPrimative::Frozen(l_value), _) => order(&l_value.deref(), right, visited),
+        (_, KaramelPrimative::Frozen(r_value)) => order(left, &r_value.deref(), visited),
+        (KaramelPrimative::Bool(lvalue),   KaramelPrimative::Bool(rvalue)) => lvalue.cmp(rvalue),
+        (KaramelPrimative::Empty,          KaramelPrimative::Empty)        => Ordering::Equal,
+
+        /* NaN comes after all other numbers */
+        (KaramelPrimative::Number(n),      KaramelPrimative::Number(m))    => match (n.is_nan(), m.is_nan()) {
+            (true, true) => Ordering::Equal,
+            (true, false) => Ordering::Greater,
+            (false, true) => Ordering::Less,
+            (false, false) => n.partial_cmp(m).unwrap_or(Ordering::Equal)
+        },
+        (KaramelPrimative::Text(lvalue),   KaramelPrimative::Text(rvalue)) => lvalue.cmp(rvalue),
+        (KaramelPrimative::List(l_value),  KaramelPrimative::List(r_value)) => {
+            if is_visited(left, right, visited) {
+                return Ordering::Equal;
+            }
+            let (l_value, r_value) = (l_value.borrow(), r_value.borrow());
+            order_items(l_value.iter(), r_value.iter(), visited)
+        },
+        (KaramelPrimative::Dict(l_value),  KaramelPrimative::Dict(r_value)) => {
+            if is_visited(left, right, visited) {
+                return Ordering::Equal;
+            }
+
+            /* Dicts are compared as lists of key and value pairs, sorted by key */
+            let (l_value, r_value) = (l_value.borrow(), r_value.borrow());
+            let mut l_items = l_value.iter().collect::<Vec<_>>();
+            let mut r_items = r_value.iter().collect::<Vec<_>>();
+            l_items.sort_by_key(|(key, _)| *key);
+            r_items.sort_by_key(|(key, _)| *key);
+
+            for ((l_key, l_item), (r_key, r_item)) in l_items.iter().zip(r_items.iter()) {
+                let ordering = l_key.cmp(r_key).then_with(|| order(&l_item.deref(), &r_item.deref(), visited));
+                if ordering != Ordering::Equal {
+                    return ordering;
+                }
+            }
+            l_items.len().cmp(&r_items.len())
+        },
+        (KaramelPrimative::Stack(l_value), KaramelPrimative::Stack(r_value)) => {
+            if is_visited(left, right, visited) {
+                return Ordering::Equal;
+            }
+            let (l_value, r_value) = (l_value.borrow(), r_value.borrow());
+            order_items(l_value.iter(), r_value.iter(), visited)
+        },
+        (KaramelPrimative::Queue(l_value), KaramelPrimative::Queue(r_value)) => {
+            if is_visited(left, right, visited) {
+                return Ordering::Equal;
+            }
+            let (l_value, r_value) = (l_value.borrow(), r_value.borrow());
+            order_items(l_value.iter(), r_value.iter(), visited)
+        },
+        #[cfg(feature = "vm")]
+        (KaramelPrimative::PriorityQueue(l_value), KaramelPrimative::PriorityQueue(r_value)) => {
+            let (l_value, r_value) = (l_value.borrow().to_vec(), r_value.borrow().to_vec());
+            order_items(l_value.iter(), r_value.iter(), visited)
+        },
+        #[cfg(feature = "vm")]
+        (KaramelPrimative::Error(l_value), KaramelPrimative::Error(r_value)) => {
+            order(&l_value.code.deref(), &r_value.code.deref(), visited).then_with(|| l_value.message.cmp(&r_value.message))
+        },
+        #[cfg(feature = "vm")]
+        (KaramelPrimative::Function(l_value, _), KaramelPrimative::Function(r_value, _)) => l_value.name.cmp(&r_value.name),
+        #[cfg(feature = "vm")]
+        (KaramelPrimative::Bound(l_value), KaramelPrimative::Bound(r_value)) => l_value.function.name.cmp(&r_value.function.name),
+        (KaramelPrimative::TextBuilder(l_value), KaramelPrimative::TextBuilder(r_value)) => l_value.borrow().cmp(&r_value.borrow()),
+        (KaramelPrimative::Bytes(l_value), KaramelPrimative::Bytes(r_value)) => l_value.cmp(r_value),
+        (KaramelPrimative::SortedDict(l_value), KaramelPrimative::SortedDict(r_value)) => {
+            if is_visited(left, right, visited) {
+                return Ordering::Equal;
+            }
+            let (l_value, r_value) = (l_value.borrow(), r_value.borrow());
+            for ((l_key, l_item), (r_key, r_item)) in l_value.iter().zip(r_value.iter()) {
+                let ordering = l_key.cmp(r_key).then_with(|| order(&l_item.deref(), &r_item.deref(), visited));
+                if ordering != Ordering::Equal {
+                    return ordering;
+                }
+            }
+            l_value.len().cmp(&r_value.len())
+        },
+
+        /* Variants of the same definition keep the definition order */
+        (KaramelPrimative::Variant(l_value), KaramelPrimative::Variant(r_value)) => l_value.enum_name.cmp(&r_value.enum_name).then(l_value.index.cmp(&r_value.index)),
+
+        /* Values with the same type keep their places */
+        _ => type_order(left).cmp(&type_order(right))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+    use std::collections::HashMap;
+    use std::rc::Rc;
+
+    use super::*;
+    use crate::compiler::value::EMPTY_OBJECT;
+    use crate::compiler::DictKey;
+    use crate::primative_text;
+    use crate::arc_text;
+    use crate::arc_number;
+    use crate::arc_bool;
+    use crate::arc_empty;
+
+    #[test]
+    fn test_deep_equal() {
+        let left = KaramelPrimative::List(RefCell::new(vec![arc_number!(1), VmObject::from(vec![arc_text!("iki")])]));
+        let right = KaramelPrimative::List(RefCell::new(vec![arc_number!(1), VmObject::from(vec![arc_text!("iki")])]));
+        assert!(deep_equal(&left, &right));
+
+        let mut dict = HashMap::new();
+        dict.insert(DictKey::from("a"), VmObject::from(vec![arc_number!(1)]));
+        let other = KaramelPrimative::Dict(RefCell::new(dict));
+        assert!(!deep_equal(&left, &other));
+        assert!(!deep_equal(&KaramelPrimative::Number(1.0), &KaramelPrimative::Text(Rc::new("1".to_string()))));
+    }
+
+    #[test]
+    fn test_self_containing_list() {
+        let left = VmObject::from(Vec::new());
+        let right = VmObject::from(Vec::new());
+        for list in [left, right].iter() {
+            if let KaramelPrimative::List(items) = &*list.deref() {
+                items.borrow_mut().push(*list);
+            }
+        }
+        assert!(deep_equal(&left.deref(), &right.deref()));
+        assert_eq!(compare(&left.deref(), &right.deref()), Ordering::Equal);
+    }
+
+    #[test]
+    fn test_deep_copy() {
+        let inner = VmObject::from(vec![arc_number!(1)]);
+        let list = VmObject::from(vec![inner, arc_text!("iki")]);
+        let copied = deep_copy(list);
+        assert!(deep_equal(&list.deref(), &copied.deref()));
+
+        if let KaramelPrimative::List(items) = &*inner.deref() {
+            items.borrow_mut().push(arc_number!(2));
+        }
+        assert!(!deep_equal(&list.deref(), &copied.deref()));
+        assert_eq!(deep_copy(arc_number!(5)).as_number(), Some(5.0));
+    }
+
+    #[test]
+    fn test_deep_copy_self_containing_list() {
+        let list = VmObject::from(Vec::new());
+        if let KaramelPrimative::List(items) = &*list.deref() {
+            items.borrow_mut().push(list);
+        }
+
+        let copied = deep_copy(list);
+        match &*copied.deref() {
+            KaramelPrimative::List(items) => assert_eq!(items.borrow()[0].deref().as_ref() as *const _, copied.deref().as_ref() as *const _),
+            value => panic!("{:?}", value)
+        };
+        assert!(deep_equal(&list.deref(), &copied.deref()));
+    }
+
+    #[test]
+    fn test_compare() {
+        let mut items = vec![arc_text!("b"), arc_number!(3), arc_empty!(), VmObject::from(vec![arc_number!(1)]), arc_bool!(true), arc_number!(f64::NAN), arc_text!("a"), arc_number!(-1)];
+        items.sort_by(|left, right| compare(&left.deref(), &right.deref()));
+        let sorted = items.iter().map(|item| format!("{:?}", item.deref())).collect::<Vec<_>>();
+        assert_eq!(sorted, vec!["boş", "doğru", "-1.0", "3", "NaN", "\"a\"", "\"b\"", "[1]"]);
+
+        let short = KaramelPrimative::List(RefCell::new(vec![arc_number!(1)]));
+        let long = KaramelPrimative::List(RefCell::new(vec![arc_number!(1), arc_number!(0)]));
+        assert_eq!(compare(&short, &long), Ordering::Less);
+    }
+}
@@ -1,19 +1,53 @@
 use crate::buildin::Module;
 use crate::types::*;
 use crate::compiler::*;
+use crate::interner::{intern, lookup};
+use crate::error::KaramelErrorType;
 use std::rc::Rc;
 
 #[cfg(not(feature = "unittest"))]
 use crate::{debug_println};
 
+use std::collections::{HashMap, HashSet};
 use std::ptr;
 
+/* Hashable form of the simple constants. Numbers are compared by their bits, so 0 and -0 are different constants. */
+#[derive(PartialEq, Eq, Hash)]
+enum ConstantKey {
+    Empty,
+    Bool(bool),
+    Number(u64),
+    Text(Rc<String>),
+    Bytes(Rc<Vec<u8>>)
+}
+
+impl ConstantKey {
+    fn new(value: &KaramelPrimative) -> Option<ConstantKey> {
+        match value {
+            KaramelPrimative::Empty          => Some(ConstantKey::Empty),
+            KaramelPrimative::Bool(value)    => Some(ConstantKey::Bool(*value)),
+            KaramelPrimative::Number(number) => Some(ConstantKey::Number(number.to_bits())),
+            KaramelPrimative::Text(text)     => Some(ConstantKey::Text(text.clone())),
+            KaramelPrimative::Bytes(bytes)   => Some(ConstantKey::Bytes(bytes.clone())),
+            _ => None
+        }
+    }
+}
+
 pub struct StaticStorage {
     pub index                 : usize,
     pub constants             : Vec<VmObject>,
     pub constants_ptr         : *const VmObject,
-    pub variables             : Vec<String>,
-    pub parent_location       : Option<usize>
+    pub variables             : Vec<Rc<String>>,
+    pub parent_location       : Option<usize>,
+    constant_locations        : HashMap<ConstantKey, usize>,
+
+    /* Functions are found with their names and module paths, classes only with their names */
+    function_locations        : HashMap<(String, Vec<String>), usize>,
+    class_locations           : HashMap<String, usize>,
+
+    /* Locations of the 'sabit' variables, only their definitions can assign them */
+    read_only_variables       : HashSet<u16>
 }
 
 impl StaticStorage {
@@ -23,12 +57,16 @@ impl StaticStorage {
             constants: Vec::with_capacity(128),
             constants_ptr: ptr::null(),
             variables: Vec::new(),
-            parent_location: None
+            parent_location: None,
+            constant_locations: HashMap::new(),
+            function_locations: HashMap::new(),
+            class_locations: HashMap::new(),
+            read_only_variables: HashSet::new()
         };
         storage.constants_ptr = storage.constants.as_ptr();
         storage
     }
-    pub fn get_variable_size(&self) -> u8 { self.variables.len() as u8 }
+    pub fn get_variable_size(&self) -> u16 { self.variables.len() as u16 }
     
     pub fn set_parent_location(&mut self, parent_location: usize) {
         self.parent_location = Some(parent_location);
@@ -36,71 +74,79 @@ impl StaticStorage {
     pub fn get_parent_location(&self) -> Option<usize> {
         self.parent_location
     }
-    pub fn add_constant(&mut self, value: Rc<KaramelPrimative>) -> usize {
-        let constant_position = self.constants.iter().position(|x| {
-            *x.deref() == *value
-        });
-        
-        match constant_position {
-            Some(position) => position,
-            None => {
-                self.constants.push(VmObject::convert(value));
-                self.constants.len() -1
-            }
+    /// Same constants are added once. Constant locations are 16 bit, 'Constant' opcode is used for the first 256 constants and 'ConstantWide' for the rest.
+    pub fn add_constant(&mut self, value: Rc<KaramelPrimative>) -> Result<u16, KaramelErrorType> {
+        if let Some(location) = self.get_constant_location(value.clone()) {
+            return Ok(location);
         }
+
+        let location = self.constants.len();
+        if location > u16::MAX as usize {
+            return Err(KaramelErrorType::TooManyConstants(u16::MAX as usize + 1));
+        }
+
+        match &*value {
+            KaramelPrimative::Function(reference, _) => { self.function_locations.entry((reference.name.to_string(), reference.module.get_path().to_vec())).or_insert(location); },
+            KaramelPrimative::Class(class) => { self.class_locations.entry(class.get_class_name()).or_insert(location); },
+            _ => if let Some(key) = ConstantKey::new(&value) {
+                self.constant_locations.insert(key, location);
+            }
+        };
+        self.constants.push(VmObject::convert(value));
+        Ok(location as u16)
     }
 
-    pub fn add_variable(&mut self, name: &str) -> u8 {
-        let result = self.variables.iter().position(|key| key == name);
+    /// Variable locations are 16 bit, the wide opcodes are used for the variables after the first 256.
+    pub fn add_variable(&mut self, name: &str) -> Result<u16, KaramelErrorType> {
+        let name = intern(name);
+        let result = self.variables.iter().position(|key| Rc::ptr_eq(key, &name));
         match result {
-            Some(location) => location as u8,
+            Some(location) => Ok(location as u16),
+            _ if self.variables.len() > u16::MAX as usize => Err(KaramelErrorType::TooManyVariables(u16::MAX as usize + 1)),
             _ => {
-                self.variables.push(name.to_string());
-                (self.variables.len()-1) as u8
+                self.variables.push(name);
+                Ok((self.variables.len()-1) as u16)
             }
         }
     }
 
-    pub fn get_variable_location(&self, name: &str) -> Option<u8> {
-        let result = self.variables.iter().position(|key| key == name);
-        match result {
-            Some(location) => Some(location as u8),
-            _ => None
+    /// Adds the variable of a 'sabit' definition. Defining the same constant twice is a reassignment.
+    pub fn add_read_only_variable(&mut self, name: &str) -> Result<u16, KaramelErrorType> {
+        let location = self.add_variable(name)?;
+        match self.read_only_variables.insert(location) {
+            true => Ok(location),
+            false => Err(KaramelErrorType::ConstantReassigned(name.to_string()))
         }
     }
 
-    pub fn get_constant_location(&self, value: Rc<KaramelPrimative>) -> Option<u8> {
-        return match self.constants.iter().position(|x| { *x.deref() == *value }) {
-            Some(number) => Some(number as u8),
-            _ => None
-        };
+    pub fn is_read_only(&self, location: u16) -> bool {
+        self.read_only_variables.contains(&location)
     }
 
-    pub fn get_function_constant(&self, name: String, module: Rc<dyn Module>) -> Option<u8> {
-        
-        for (index, item) in self.constants.iter().enumerate() {
-            if let KaramelPrimative::Function(reference, _) = &*item.deref() {
-                if reference.name        == name && 
-                   reference.module.get_path() == module.get_path() {
-                    return Some(index as u8);
-                }
-            }
+    /* Variable names are interned, a name that is not in the table can not be a variable */
+    pub fn get_variable_location(&self, name: &str) -> Option<u16> {
+        let name = lookup(name)?;
+        let result = self.variables.iter().position(|key| Rc::ptr_eq(key, &name));
+        match result {
+            Some(location) => Some(location as u16),
+            _ => None
         }
+    }
 
-        None
+    pub fn get_constant_location(&self, value: Rc<KaramelPrimative>) -> Option<u16> {
+        let location = match ConstantKey::new(&value) {
+            Some(key) => self.constant_locations.get(&key).copied(),
+            None => self.constants.iter().position(|x| { *x.deref() == *value })
+        };
+        location.map(|location| location as u16)
     }
 
-    pub fn get_class_constant(&self, name: String, _module_path: Rc<dyn Module>) -> Option<u8> {
-        
-        for (index, item) in self.constants.iter().enumerate() {
-            if let KaramelPrimative::Class(reference) = &*item.deref() {
-                if reference.get_class_name() == name {
-                    return Some(index as u8);
-                }
-            }
-        }
+    pub fn get_function_constant(&self, name: String, module: Rc<dyn Module>) -> Option<u16> {
+        self.function_locations.get(&(name, module.get_path().to_vec())).map(|location| *location as u16)
+    }
 
-        None
+    pub fn get_class_constant(&self, name: String, _module_path: Rc<dyn Module>) -> Option<u16> {
+        self.class_locations.get(&name).map(|location| *location as u16)
     }
 
     #[cfg(feature = "unittest")]
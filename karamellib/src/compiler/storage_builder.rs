@@ -1,112 +1,115 @@
+use std::collections::HashMap;
 use std::rc::Rc;
 
 use crate::error::KaramelErrorType;
 use crate::compiler::ast::KaramelAstType;
 use crate::compiler::value::KaramelPrimative;
-use crate::compiler::context::KaramelCompilerContext;
+use crate::compiler::context::{KaramelCompilerContext, decorated_name};
+use crate::compiler::ast::KaramelIfStatementElseItem;
+use crate::compiler::visitor::{AstVisitor, VisitorResult, walk_ast, walk_items, walk_loop};
 use crate::syntax::loops::LoopType;
+use crate::buildin::Module;
+use crate::interner::intern;
 
 use super::module::OpcodeModule;
 pub struct StorageBuilder;
 
+struct StorageVisitor<'a> {
+    module: Rc<OpcodeModule>,
+    options: &'a mut KaramelCompilerContext,
+    storage_index: usize,
+
+    /* 'yerel' variables of the 'ise' and 'döngü' bodies, innermost body is the last */
+    blocks: Vec<HashMap<String, Rc<String>>>,
+    local_count: usize
+}
+
 impl StorageBuilder {
     pub fn new() -> Self {
         StorageBuilder { }
     }
 
     pub fn prepare(&self, module: Rc<OpcodeModule>, ast: &KaramelAstType, storage_index: usize, options: &mut KaramelCompilerContext) -> Result<(), KaramelErrorType> {
-        self.build(module.clone(),ast, &KaramelAstType::None, options, storage_index)?;
+        let mut visitor = StorageVisitor { module, options, storage_index, blocks: Vec::new(), local_count: 0 };
+        visitor.visit(ast)
+    }
+}
+
+impl StorageVisitor<'_> {
+    fn add_constant(&mut self, constant: Rc<KaramelPrimative>) -> VisitorResult<KaramelErrorType> {
+        self.options.storages.get_mut(self.storage_index).unwrap().add_constant(constant)?;
         Ok(())
     }
 
-    fn build(&self, module: Rc<OpcodeModule>, ast: &KaramelAstType, _: &KaramelAstType, options: &mut KaramelCompilerContext, storage_index: usize) -> Result<(), KaramelErrorType> {
-        use crate::buildin::Module;
-        
-        match ast {
-            KaramelAstType::Binary {
-                left,
-                operator: _,
-                right} => {
-                    self.build(module.clone(),left, ast, options, storage_index)?;
-                    self.build(module.clone(),right, ast, options, storage_index)?;
-                },
-            
-            KaramelAstType::Control {
-                left,
-                operator: _,
-                right} => {
-                    self.build(module.clone(),left, ast, options, storage_index)?;
-                    self.build(module.clone(),right, ast, options, storage_index)?;
-                },
-            
-            KaramelAstType::PrefixUnary { operator: _, expression, assign_to_temp: _ } => {
-                self.build(module.clone(),expression, ast, options, storage_index)?;
-            },
+    fn visit_block_body(&mut self, body: &KaramelAstType) -> VisitorResult<KaramelErrorType> {
+        self.blocks.push(HashMap::new());
+        let result = self.visit(body);
+        self.blocks.pop();
+        result
+    }
 
-            KaramelAstType::SuffixUnary(_, expression) => {
-                self.build(module.clone(),expression, ast, options, storage_index)?;
-            },
-            
-            KaramelAstType::Symbol(string) => {
-                match module.get_method(&string[..]) {
-                    Some(reference) => {
-                        options.storages.get_mut(storage_index).unwrap().add_constant(Rc::new(KaramelPrimative::Function(reference, None)));
-                    },
-                    None => ()
-                };
+    fn find_local(&self, name: &str) -> Option<Rc<String>> {
+        self.blocks.iter().rev().find_map(|block| block.get(name).cloned())
+    }
+}
+
+impl AstVisitor for StorageVisitor<'_> {
+    type Error = KaramelErrorType;
+
+    /* Symbols of the block variables are bound to their hidden slots, the compiler finds the slot with the address of the symbol */
+    fn visit(&mut self, ast: &KaramelAstType) -> VisitorResult<KaramelErrorType> {
+        if let KaramelAstType::Symbol(name) = ast {
+            if let Some(hidden) = self.find_local(name) {
+                self.options.local_variables.insert((self.storage_index, ast as *const KaramelAstType as usize), hidden.clone());
+                self.options.storages.get_mut(self.storage_index).unwrap().add_variable(&hidden)?;
+                return Ok(());
+            }
+        }
+        walk_ast(self, ast)
+    }
 
-                let function_search = options.get_function(string.to_string(), module.get_path(), storage_index);
-                match function_search {
-                    Some(reference) => {
-                        options.storages.get_mut(storage_index).unwrap().add_constant(Rc::new(KaramelPrimative::Function(reference, None)));
-                    },
-                    None => ()
-                };
+    fn visit_symbol(&mut self, string: &str) -> VisitorResult<KaramelErrorType> {
+        if let Some(reference) = self.module.get_method(string) {
+            self.add_constant(Rc::new(KaramelPrimative::Function(reference, None)))?;
+        }
 
-                let class_search = options.find_class(string.to_string(), module.get_path(), storage_index);
-                match class_search {
-                    Some(reference) => {
-                        options.storages.get_mut(storage_index).unwrap().add_constant(Rc::new(KaramelPrimative::Class(reference)));
-                    },
-                    None => ()
-                };
+        let function_search = self.options.get_function(string.to_string(), self.module.get_path(), self.storage_index);
+        if let Some(reference) = function_search {
+            self.add_constant(Rc::new(KaramelPrimative::Function(reference, None)))?;
+        }
 
-                options.storages.get_mut(storage_index).unwrap().add_variable(&string);
-            },
+        let class_search = self.options.find_class(string.to_string(), self.module.get_path(), self.storage_index);
+        if let Some(reference) = class_search {
+            self.add_constant(Rc::new(KaramelPrimative::Class(reference)))?;
+        }
 
-            KaramelAstType::ModulePath(params) => {
-                let name = params[params.len() - 1].to_string();
-                let module_path = params[0..(params.len() - 1)].to_vec();
+        if let Some(constant) = self.options.get_teaching_constant(string.to_string()) {
+            self.add_constant(constant)?;
+        }
 
-                let function_search = options.get_function(&name, &module_path, storage_index);
-                match function_search {
-                    Some(reference) => options.storages.get_mut(storage_index).unwrap().add_constant(Rc::new(KaramelPrimative::Function(reference, None))),
-                    None => return Err(KaramelErrorType::FunctionNotFound(name.to_string()))
-                };
-            },
-            
-            KaramelAstType::Assignment {
-                variable,
-                operator: _,
-                expression} =>  {
-                self.build(module.clone(),variable, ast, options, storage_index)?;                
-                self.build(module.clone(),expression, ast, options, storage_index)?;
-            },
-            
-            KaramelAstType::Block(asts) => {
-                for array_item in asts {
-                    self.build(module.clone(),array_item, ast, options, storage_index)?;
-                }
-            },
-            
-            KaramelAstType::AccessorFuncCall {
-                source,
-                indexer,
-                assign_to_temp: _
-            } => {
-                self.build(module.clone(),source, ast, options, storage_index)?;
-                self.build(module.clone(),indexer, ast, options, storage_index)?;
-            },
+        if let Some(constant) = self.options.get_imported_constant(string, self.module.get_path(), self.storage_index) {
+            self.add_constant(constant)?;
+        }
+
+        self.options.storages.get_mut(self.storage_index).unwrap().add_variable(string)?;
+        Ok(())
+    }
+
+    fn visit_module_path(&mut self, params: &[String]) -> VisitorResult<KaramelErrorType> {
+        let name = params[params.len() - 1].to_string();
+        let module_path = params[0..(params.len() - 1)].to_vec();
+
+        let function_search = self.options.get_function(&name, &module_path, self.storage_index);
+        match function_search {
+            Some(reference) => self.add_constant(Rc::new(KaramelPrimative::Function(reference, None)))?,
+            None => match self.options.get_constant(&name, &module_path).or_else(|| self.options.get_imported_constant(&name, &module_path, self.storage_index)) {
+                Some(constant) => self.add_constant(constant)?,
+                None if module_path.len() == 1 && self.options.enums.contains_key(&module_path[0]) => return Err(KaramelErrorType::EnumVariantNotFound(params.join("::"))),
+                None => return Err(self.options.function_not_found(&name, &module_path, self.storage_index))
+            }
+        };
+        Ok(())
+    }
 
 /*
 ╔══════════════════════╗
@@ -122,107 +125,130 @@ impl StorageBuilder {
 ║   Function Pointer   ║
 ╚══════════════════════╝
  */
-            KaramelAstType::FuncCall { func_name_expression, arguments, assign_to_temp: _ } => {
-                /* Build arguments */
-                for arg in arguments {
-                    self.build(module.clone(),arg, ast, options, storage_index)?;
-                }
-
-                //compiler_option.max_stack = max(max_temp.max_stack);
-
-                match &**func_name_expression {
-                    KaramelAstType::Symbol(function_name) => {
-                        let function_search = options.get_function(function_name.to_string(), module.get_path(), storage_index);
-                        if let Some(reference) = function_search {
-                            options.storages.get_mut(storage_index).unwrap().add_constant(Rc::new(KaramelPrimative::Function(reference, None)));
-                        }
-                        else {
-                            options.storages.get_mut(storage_index).unwrap().add_constant(Rc::new(KaramelPrimative::Text(Rc::new(function_name.to_string()))));
-                        }
-                    },
-                    KaramelAstType::ModulePath(names) => {
-                        let function_search = options.get_function(names[names.len() - 1].to_string(), &names[0..(names.len()-1)].to_vec(), storage_index);
-                        match function_search {
-                            Some(reference) => options.storages.get_mut(storage_index).unwrap().add_constant(Rc::new(KaramelPrimative::Function(reference, None))),
-                            None => return Err(KaramelErrorType::FunctionNotFound(names[names.len() - 1].to_string()))
-                        };
-                    },
-                    _ => {
-                        self.build(module.clone(),func_name_expression, ast, options, storage_index)?;
-                    }
-                };
-            },
+    fn visit_func_call(&mut self, func_name_expression: &KaramelAstType, arguments: &[Rc<KaramelAstType>]) -> VisitorResult<KaramelErrorType> {
+        /* Build arguments */
+        walk_items(self, arguments)?;
 
-            KaramelAstType::Return(expression) => {
-                self.build(module.clone(),expression, ast, options, storage_index)?;
+        match func_name_expression {
+            KaramelAstType::Symbol(function_name) => {
+                let function_search = self.options.get_function(function_name.to_string(), self.module.get_path(), self.storage_index);
+                match function_search {
+                    Some(reference) => self.add_constant(Rc::new(KaramelPrimative::Function(reference, None)))?,
+                    None => self.add_constant(Rc::new(KaramelPrimative::Text(intern(function_name.as_str()))))?
+                };
             },
-
-            KaramelAstType::Loop {
-                loop_type,
-                body
-            } => {
-                match loop_type {
-                    LoopType::Scalar { variable, control, increment } => {
-                        self.build(module.clone(),&*variable, ast, options, storage_index)?;
-                        self.build(module.clone(),&*control, ast, options, storage_index)?;
-                        self.build(module.clone(),&*increment, ast, options, storage_index)?;
-                    },
-                    LoopType::Simple(control) => {
-                        self.build(module.clone(),&*control, ast, options, storage_index)?
-                    },
-                    LoopType::Endless => {}
+            KaramelAstType::ModulePath(names) => {
+                let function_search = self.options.get_function(names[names.len() - 1].to_string(), &names[0..(names.len()-1)].to_vec(), self.storage_index);
+                match function_search {
+                    Some(reference) => self.add_constant(Rc::new(KaramelPrimative::Function(reference, None)))?,
+                    None => return Err(self.options.function_not_found(&names[names.len() - 1], &names[0..(names.len()-1)], self.storage_index))
                 };
-                self.build(module.clone(),&*body, ast, options, storage_index)?;
             },
+            _ => self.visit(func_name_expression)?
+        };
+        Ok(())
+    }
 
-            KaramelAstType::Primative(primative) => {
-                options.storages.get_mut(storage_index).unwrap().add_constant(Rc::clone(primative));
-            },
+    fn visit_primative(&mut self, primative: &Rc<KaramelPrimative>) -> VisitorResult<KaramelErrorType> {
+        self.add_constant(primative.clone())?;
+        Ok(())
+    }
 
-            KaramelAstType::List(list) => {
-                for array_item in list {
-                    self.build(module.clone(),&*array_item, ast, options, storage_index)?;
-                }
-                return Ok(())
-            },
+    fn visit_none(&mut self) -> VisitorResult<KaramelErrorType> {
+        self.add_constant(Rc::new(KaramelPrimative::Empty))?;
+        Ok(())
+    }
 
-            KaramelAstType::Dict(dict) => {
-                for dict_item in dict {
-                    options.storages.get_mut(storage_index).unwrap().add_constant(dict_item.key.clone());
-                    self.build(module.clone(),&dict_item.value, ast, options, storage_index)?;
-                }
-                return Ok(())
-            },
+    fn visit_try(&mut self, body: &KaramelAstType, variable: Option<&str>, catch_body: &KaramelAstType) -> VisitorResult<KaramelErrorType> {
+        self.visit(body)?;
+        if let Some(variable) = variable {
+            self.options.storages.get_mut(self.storage_index).unwrap().add_variable(variable)?;
+        }
+        self.visit(catch_body)
+    }
+
+    fn visit_temporary(&mut self, name: &str, expression: Option<&KaramelAstType>) -> VisitorResult<KaramelErrorType> {
+        if let Some(expression) = expression {
+            self.visit(expression)?;
+        }
+        self.options.storages.get_mut(self.storage_index).unwrap().add_variable(name)?;
+        Ok(())
+    }
 
-            KaramelAstType::Indexer { body, indexer } => {
-                self.build(module.clone(),body, ast, options, storage_index)?;
-                self.build(module.clone(),indexer, ast, options, storage_index)?;
+    /* Only the storage that defines the function keeps the decorated value, the function body calls itself directly */
+    fn visit_decorators(&mut self, name: &str, decorators: &[Rc<KaramelAstType>]) -> VisitorResult<KaramelErrorType> {
+        match self.options.get_function(name.to_string(), self.module.get_path(), self.storage_index) {
+            Some(function) if function.defined_storage_index == self.storage_index => {
+                walk_items(self, decorators)?;
+                self.options.storages.get_mut(self.storage_index).unwrap().add_variable(&decorated_name(name))?;
+                Ok(())
             },
+            _ => Ok(())
+        }
+    }
 
-            KaramelAstType::FunctionDefination { name: _, arguments: _, body } => {
-                self.build(module.clone(),body, ast, options, storage_index)?;
+    /* Value is visited first, so 'yerel a = a + 1' reads the outer variable. Functions have their own storages, they never see the block variables. */
+    fn visit_local(&mut self, assignment: &KaramelAstType) -> VisitorResult<KaramelErrorType> {
+        match assignment {
+            KaramelAstType::Assignment { variable, expression, .. } if !self.blocks.is_empty() => match &**variable {
+                KaramelAstType::Symbol(name) => {
+                    self.visit(expression)?;
+                    self.local_count += 1;
+                    let hidden = Rc::new(format!("{}#{}", name, self.local_count));
+                    self.blocks.last_mut().unwrap().insert(name.to_string(), hidden);
+                    self.visit(variable)
+                },
+                _ => self.visit(assignment)
             },
+            _ => self.visit(assignment)
+        }
+    }
 
-            KaramelAstType::IfStatement {
-                condition, body, else_body, else_if} => {
-                    self.build(module.clone(),condition, ast, options, storage_index)?;
-                    self.build(module.clone(),body, ast, options, storage_index)?;
+    fn visit_if_statement(&mut self, condition: &KaramelAstType, body: &KaramelAstType, else_body: Option<&KaramelAstType>, else_if: &[Rc<KaramelIfStatementElseItem>]) -> VisitorResult<KaramelErrorType> {
+        self.visit(condition)?;
+        self.visit_block_body(body)?;
 
-                    if let Some(else_) = else_body {
-                        self.build(module.clone(),else_, ast, options, storage_index)?;
-                    }
+        if let Some(else_body) = else_body {
+            self.visit_block_body(else_body)?;
+        }
 
-                    for else_if_item in else_if {
-                        self.build(module.clone(),&else_if_item.condition, ast, options, storage_index)?;
-                        self.build(module.clone(),&else_if_item.body, ast, options, storage_index)?;
-                    }
-                },
+        for item in else_if.iter() {
+            self.visit(&item.condition)?;
+            self.visit_block_body(&item.body)?;
+        }
+        Ok(())
+    }
 
-                KaramelAstType::None => {
-                    options.storages.get_mut(storage_index).unwrap().add_constant(Rc::new(KaramelPrimative::Empty));
-                },
-            _ => ()
-        };
-        return Ok(());
+    fn visit_loop(&mut self, loop_type: &LoopType, body: &KaramelAstType) -> VisitorResult<KaramelErrorType> {
+        walk_loop(self, loop_type, &KaramelAstType::None)?;
+        self.visit_block_body(body)
+    }
+
+    /* Method name of the 'kapat' call is searched in the constants, a function with the same name should not replace it */
+    fn visit_with(&mut self, assignment: &KaramelAstType, _: &KaramelAstType, body: &KaramelAstType) -> VisitorResult<KaramelErrorType> {
+        self.visit(assignment)?;
+        self.add_constant(Rc::new(KaramelPrimative::Text(intern("kapat"))))?;
+        self.visit_block_body(body)
+    }
+
+    /* Slot is marked before the code is generated, so assignments written before the definition are found too */
+    fn visit_constant(&mut self, assignment: &KaramelAstType) -> VisitorResult<KaramelErrorType> {
+        self.visit(assignment)?;
+        if let KaramelAstType::Assignment { variable, .. } = assignment {
+            if let KaramelAstType::Symbol(name) = &**variable {
+                let name = self.options.get_variable_name(self.storage_index, variable, name);
+                self.options.storages.get_mut(self.storage_index).unwrap().add_read_only_variable(&name)?;
+            }
+        }
+        Ok(())
+    }
+
+    /* Empty value is used as the message when it is not written */
+    fn visit_assume(&mut self, condition: &KaramelAstType, message: Option<&KaramelAstType>) -> VisitorResult<KaramelErrorType> {
+        self.visit(condition)?;
+        match message {
+            Some(message) => self.visit(message),
+            None => self.visit_none()
+        }
     }
-}
\ No newline at end of file
+}
@@ -1,21 +1,83 @@
 use std::borrow::Borrow;
 use std::vec::Vec;
 use std::rc::Rc;
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
 use std::mem::ManuallyDrop;
 use std::fmt;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap, VecDeque};
 
 
-use crate::{buildin::Class, types::*};
+use crate::types::*;
+#[cfg(feature = "vm")]
+use crate::buildin::{Class, class::{priority_queue::PriorityQueue, error::ErrorObject}};
+#[cfg(feature = "vm")]
 use crate::compiler::function::FunctionReference;
 use crate::compiler::GetType;
+use crate::compiler::semantics;
+use crate::compiler::dict_key::DictKey;
+use crate::gc;
+use crate::constants::{KARAMEL_DEFAULT_PRINT_PRECISION, KARAMEL_MAX_PRINT_PRECISION};
 
 pub const EMPTY_OBJECT: VmObject = VmObject(QNAN | EMPTY_FLAG);
 pub const TRUE_OBJECT: VmObject  = VmObject(QNAN | TRUE_FLAG);
 pub const FALSE_OBJECT: VmObject = VmObject(QNAN | FALSE_FLAG);
 pub static EMPTY_PRIMATIVE: KaramelPrimative = KaramelPrimative::Empty;
 
+thread_local! {
+    /* Significant digit count of the printed numbers */
+    static PRINT_PRECISION: Cell<usize> = Cell::new(KARAMEL_DEFAULT_PRINT_PRECISION);
+}
+
+pub fn set_print_precision(precision: usize) {
+    PRINT_PRECISION.with(|current| current.set(precision.clamp(1, KARAMEL_MAX_PRINT_PRECISION)));
+}
+
+pub fn print_precision() -> usize {
+    PRINT_PRECISION.with(|current| current.get())
+}
+
+/// Rounds the number to the print precision. Removes the floating point noise like 0.1 + 0.2 = 0.30000000000000004.
+pub fn round_for_print(number: f64) -> f64 {
+    let precision = print_precision();
+    match precision >= KARAMEL_MAX_PRINT_PRECISION || !number.is_finite() {
+        true => number,
+        false => format!("{:.*e}", precision - 1, number).parse().unwrap_or(number)
+    }
+}
+
+/// Text of the number when it is added to a text. Decimal separator is comma, thousands are not grouped so the years stay as they are (2021, 3,5).
+pub fn number_to_text(number: f64) -> String {
+    let number = round_for_print(number);
+    match number.is_finite() {
+        true => number.to_string().replace('.', ","),
+        false => number.to_string()
+    }
+}
+
+/// Repeat count of the text. Only the positive integers and zero can repeat a text.
+pub fn repeat_count(number: f64) -> Option<usize> {
+    match number >= 0.0 && number.fract() == 0.0 && number <= usize::MAX as f64 {
+        true => Some(number as usize),
+        false => None
+    }
+}
+
+/// Variant of a 'seçenekler' definition. Index is the order of the variant in the definition.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EnumVariant {
+    pub enum_name: String,
+    pub name: String,
+    pub index: usize
+}
+
+/// Function that is created with 'bağla'. Captured arguments are given before the arguments of the call.
+#[cfg(feature = "vm")]
+pub struct BoundFunction {
+    pub function: Rc<FunctionReference>,
+    pub base: Option<VmObject>,
+    pub arguments: Vec<VmObject>
+}
+
 #[repr(C)]
 #[derive(Clone)]
 pub enum KaramelPrimative {
@@ -23,10 +85,29 @@ pub enum KaramelPrimative {
     Number(f64),
     Bool(bool),
     List(RefCell<Vec<VmObject>>),
-    Dict(RefCell<HashMap<String, VmObject>>),
+    Dict(RefCell<HashMap<DictKey, VmObject>>),
     Text(Rc<String>),
+    #[cfg(feature = "vm")]
     Function(Rc<FunctionReference>, Option<VmObject>),
-    Class(Rc<dyn Class>)
+    #[cfg(feature = "vm")]
+    Class(Rc<dyn Class>),
+    Stack(RefCell<Vec<VmObject>>),
+    Queue(RefCell<VecDeque<VmObject>>),
+    #[cfg(feature = "vm")]
+    PriorityQueue(RefCell<PriorityQueue>),
+    #[cfg(feature = "vm")]
+    Error(ErrorObject),
+    Variant(Rc<EnumVariant>),
+    #[cfg(feature = "vm")]
+    Bound(Rc<BoundFunction>),
+    TextBuilder(RefCell<String>),
+    SortedDict(RefCell<BTreeMap<DictKey, VmObject>>),
+
+    /* Bytes can not be changed like the texts, changes give new bytes */
+    Bytes(Rc<Vec<u8>>),
+
+    /* List or dict that can not be changed, its items are frozen too */
+    Frozen(VmObject)
 }
 
 unsafe impl Send for KaramelPrimative {}
@@ -45,6 +126,7 @@ impl KaramelPrimative {
         match self {
             KaramelPrimative::Empty => write!(f, "boş"),
             KaramelPrimative::Number(number) => {
+                let number = &round_for_print(*number);
                 if *number == (*number as u64) as f64 {
                     write!(f, "{:?}", (*number as u64))
                 } else {
@@ -58,22 +140,28 @@ impl KaramelPrimative {
             KaramelPrimative::List(b) => write!(f, "{:?}", b.borrow()),
             KaramelPrimative::Dict(b) => write!(f, "{:?}", b.borrow()),
             KaramelPrimative::Text(b) => write!(f, "\"{}\"", b),
+            #[cfg(feature = "vm")]
             KaramelPrimative::Function(func, _) => write!(f, "<Fonksiyon='{}'>", func.name),
-            KaramelPrimative::Class(class) => write!(f, "<Sınıf='{}'>", class.get_type())
+            #[cfg(feature = "vm")]
+            KaramelPrimative::Class(class) => write!(f, "<Sınıf='{}'>", class.get_type()),
+            KaramelPrimative::Stack(b) => write!(f, "{:?}", b.borrow()),
+            KaramelPrimative::Queue(b) => write!(f, "{:?}", b.borrow()),
+            #[cfg(feature = "vm")]
+            KaramelPrimative::PriorityQueue(b) => write!(f, "{:?}", b.borrow().to_vec()),
+            #[cfg(feature = "vm")]
+            KaramelPrimative::Error(error) => write!(f, "{:?}", error),
+            KaramelPrimative::Variant(variant) => write!(f, "{}::{}", variant.enum_name, variant.name),
+            #[cfg(feature = "vm")]
+            KaramelPrimative::Bound(bound) => write!(f, "<Fonksiyon='{}'>", bound.function.name),
+            KaramelPrimative::TextBuilder(b) => write!(f, "\"{}\"", b.borrow()),
+            KaramelPrimative::SortedDict(b) => write!(f, "{:?}", b.borrow()),
+            KaramelPrimative::Bytes(bytes) => write!(f, "b'{}'", bytes.escape_ascii()),
+            KaramelPrimative::Frozen(inner) => inner.deref().format(f)
         }
     }
 
     pub fn is_true(&self) -> bool {
-        match self {
-            KaramelPrimative::Text(value)       => !value.is_empty(),
-            KaramelPrimative::Number(value)     => *value > 0.0,
-            KaramelPrimative::Bool(value)       => *value,
-            KaramelPrimative::List(items)       => !items.borrow().is_empty(),
-            KaramelPrimative::Dict(items) => !items.borrow().is_empty(),
-            KaramelPrimative::Empty             => false,
-            KaramelPrimative::Function(_, _) => true,
-            KaramelPrimative::Class(_) => true
-        }
+        semantics::is_truthy(self)
     }
 
     pub fn get_text(&self) -> String {
@@ -92,8 +180,23 @@ impl KaramelPrimative {
             
             KaramelPrimative::Empty => 4,
             KaramelPrimative::Bool(_) => 5,
+            #[cfg(feature = "vm")]
             KaramelPrimative::Function(_, _) => 6,
-            KaramelPrimative::Class(_) => 7
+            #[cfg(feature = "vm")]
+            KaramelPrimative::Class(_) => 7,
+            KaramelPrimative::Stack(_) => 10,
+            KaramelPrimative::Queue(_) => 11,
+            #[cfg(feature = "vm")]
+            KaramelPrimative::PriorityQueue(_) => 12,
+            #[cfg(feature = "vm")]
+            KaramelPrimative::Error(_) => 13,
+            KaramelPrimative::Variant(_) => 14,
+            #[cfg(feature = "vm")]
+            KaramelPrimative::Bound(_) => 15,
+            KaramelPrimative::TextBuilder(_) => 16,
+            KaramelPrimative::Frozen(_) => 17,
+            KaramelPrimative::SortedDict(_) => 18,
+            KaramelPrimative::Bytes(_) => 19
         }
     }
 }
@@ -107,8 +210,23 @@ impl GetType for KaramelPrimative {
             KaramelPrimative::List(_)     => "liste".to_string(),
             KaramelPrimative::Dict(_)     => "sözlük".to_string(),
             KaramelPrimative::Empty       => "boş".to_string(),
+            #[cfg(feature = "vm")]
             KaramelPrimative::Function(_, _) => "fonksiyon".to_string(),
-            KaramelPrimative::Class(_)    => "sınıf".to_string()
+            #[cfg(feature = "vm")]
+            KaramelPrimative::Class(_)    => "sınıf".to_string(),
+            KaramelPrimative::Stack(_)    => "yığın".to_string(),
+            KaramelPrimative::Queue(_)    => "kuyruk".to_string(),
+            #[cfg(feature = "vm")]
+            KaramelPrimative::PriorityQueue(_) => "öncelik_kuyruğu".to_string(),
+            #[cfg(feature = "vm")]
+            KaramelPrimative::Error(_) => "hata".to_string(),
+            KaramelPrimative::Variant(variant) => variant.enum_name.to_string(),
+            #[cfg(feature = "vm")]
+            KaramelPrimative::Bound(_) => "fonksiyon".to_string(),
+            KaramelPrimative::TextBuilder(_) => "yazı_oluşturucu".to_string(),
+            KaramelPrimative::SortedDict(_) => "sıralı_sözlük".to_string(),
+            KaramelPrimative::Bytes(_) => "bayt".to_string(),
+            KaramelPrimative::Frozen(inner) => format!("dondurulmuş_{}", inner.deref().get_type())
         }
     }
 }
@@ -164,8 +282,8 @@ impl From<Rc<KaramelPrimative>> for VmObject {
     }
 }
 
-impl From<HashMap<String, VmObject>> for VmObject {
-    fn from(source: HashMap<String, VmObject>) -> Self {
+impl From<HashMap<DictKey, VmObject>> for VmObject {
+    fn from(source: HashMap<DictKey, VmObject>) -> Self {
         VmObject::convert(Rc::new(KaramelPrimative::Dict(RefCell::new(source))))
     }
 }
@@ -202,56 +320,7 @@ impl Drop for KaramelPrimative {
 
 impl PartialEq for KaramelPrimative {
     fn eq(&self, other: &Self) -> bool {
-        match (self, &other) {
-            (KaramelPrimative::Bool(lvalue),            KaramelPrimative::Bool(rvalue)) => lvalue == rvalue,
-            (KaramelPrimative::Empty,                   KaramelPrimative::Empty)        => true,
-            (KaramelPrimative::Number(n),               KaramelPrimative::Number(m))    => if n.is_nan() && m.is_nan() { true } else { n == m },
-            (KaramelPrimative::Text(lvalue),            KaramelPrimative::Text(rvalue)) => lvalue == rvalue,
-            (KaramelPrimative::List(l_value),           KaramelPrimative::List(r_value))       => {
-                if (*l_value).borrow().len() != (*r_value).borrow().len() {
-                    return false;
-                }
-
-                for i in 0..(*l_value).borrow().len() {
-                    if (*l_value).borrow()[i].deref() != (*r_value).borrow()[i].deref() {
-                        return false;
-                    }
-                }
-                true
-            },
-            (KaramelPrimative::Function(l_value, _), KaramelPrimative::Function(r_value, _)) => {
-                if l_value.name != r_value.name ||
-                   l_value.module.get_path() != r_value.module.get_path() {
-                    return false;
-                }
-                true
-            },
-            (KaramelPrimative::Class(l_value), KaramelPrimative::Class(r_value)) => {
-                l_value.get_type() == r_value.get_type()
-            },
-            (KaramelPrimative::Dict(l_value),           KaramelPrimative::Dict(r_value))       => {
-                if (*l_value).borrow().len() != (*r_value).borrow().len() {
-                    return false;
-                }
-
-                for (key, l_item) in l_value.borrow().iter() {
-                    match r_value.borrow().get(key) {
-                        Some(r_item) => {
-                            if l_item.deref() != r_item.deref() {
-                                return false;
-                            }
-                        },
-                        None => return false
-                    }
-                }
-                true
-            },
-            _ => false
-        }
-    }
-
-    fn ne(&self, other: &Self) -> bool {
-        !(self == other)
+        semantics::deep_equal(self, other)
     }
 }
 
@@ -265,7 +334,9 @@ impl VmObject {
             KaramelPrimative::Bool(true)       => TRUE_OBJECT,
             KaramelPrimative::Bool(false)      => FALSE_OBJECT,
             _                                => {
-                VmObject(QNAN | POINTER_FLAG | (POINTER_MASK & (Rc::into_raw(primative)) as u64))
+                let object = VmObject(QNAN | POINTER_FLAG | (POINTER_MASK & (Rc::into_raw(primative)) as u64));
+                gc::track(object);
+                object
             }
         }
     }
@@ -278,7 +349,9 @@ impl VmObject {
             KaramelPrimative::Bool(true)       => TRUE_OBJECT,
             KaramelPrimative::Bool(false)      => FALSE_OBJECT,
             _                                => {
-                VmObject(QNAN | POINTER_FLAG | (POINTER_MASK & (Rc::into_raw(Rc::new(primative))) as u64))
+                let object = VmObject(QNAN | POINTER_FLAG | (POINTER_MASK & (Rc::into_raw(Rc::new(primative))) as u64));
+                gc::track(object);
+                object
             }
         }
     }
@@ -291,7 +364,9 @@ impl VmObject {
             KaramelPrimative::Bool(true)       => TRUE_OBJECT,
             KaramelPrimative::Bool(false)      => FALSE_OBJECT,
             _                                => {
-                VmObject(QNAN | POINTER_FLAG | (POINTER_MASK & (Rc::into_raw(primative)) as u64))
+                let object = VmObject(QNAN | POINTER_FLAG | (POINTER_MASK & (Rc::into_raw(primative)) as u64));
+                gc::track(object);
+                object
             }
         }
     }
@@ -326,8 +401,23 @@ impl VmObject {
                     KaramelPrimative::Text(text) => KaramelPrimative::Text(text.clone()),
                     KaramelPrimative::List(list) => KaramelPrimative::List(list.clone()),
                     KaramelPrimative::Dict(dict) => KaramelPrimative::Dict(dict.clone()),
+                    #[cfg(feature = "vm")]
                     KaramelPrimative::Function(func, base) => KaramelPrimative::Function(func.clone(), *base),
+                    #[cfg(feature = "vm")]
                     KaramelPrimative::Class(klass) => KaramelPrimative::Class(klass.clone()),
+                    KaramelPrimative::Stack(stack) => KaramelPrimative::Stack(stack.clone()),
+                    KaramelPrimative::Queue(queue) => KaramelPrimative::Queue(queue.clone()),
+                    #[cfg(feature = "vm")]
+                    KaramelPrimative::PriorityQueue(queue) => KaramelPrimative::PriorityQueue(queue.clone()),
+                    #[cfg(feature = "vm")]
+                    KaramelPrimative::Error(error) => KaramelPrimative::Error(error.clone()),
+                    KaramelPrimative::Variant(variant) => KaramelPrimative::Variant(variant.clone()),
+                    #[cfg(feature = "vm")]
+                    KaramelPrimative::Bound(bound) => KaramelPrimative::Bound(bound.clone()),
+                    KaramelPrimative::TextBuilder(text) => KaramelPrimative::TextBuilder(text.clone()),
+                    KaramelPrimative::SortedDict(dict) => KaramelPrimative::SortedDict(dict.clone()),
+                    KaramelPrimative::Bytes(bytes) => KaramelPrimative::Bytes(bytes.clone()),
+                    KaramelPrimative::Frozen(inner) => KaramelPrimative::Frozen(*inner),
                     _ => KaramelPrimative::Empty
                 }
             },
@@ -0,0 +1,332 @@
+use std::rc::Rc;
+
+use crate::compiler::ast::{KaramelAstType, KaramelDictItem, KaramelIfStatementElseItem};
+use crate::compiler::value::KaramelPrimative;
+use crate::syntax::loops::LoopType;
+use crate::types::KaramelOperatorType;
+
+pub type VisitorResult<E> = Result<(), E>;
+
+/// Traverses the parse tree. Every method walks into the child nodes by default, so the implementor only overrides the nodes it is interested in.
+/// Overridden methods can call the matching `walk_*` function to keep going into the children.
+pub trait AstVisitor: Sized {
+    type Error;
+
+    fn visit(&mut self, ast: &KaramelAstType) -> VisitorResult<Self::Error> {
+        walk_ast(self, ast)
+    }
+
+    fn visit_none(&mut self) -> VisitorResult<Self::Error> {
+        Ok(())
+    }
+
+    fn visit_new_line(&mut self) -> VisitorResult<Self::Error> {
+        Ok(())
+    }
+
+    fn visit_block(&mut self, items: &[Rc<KaramelAstType>]) -> VisitorResult<Self::Error> {
+        walk_items(self, items)
+    }
+
+    fn visit_func_call(&mut self, func_name_expression: &KaramelAstType, arguments: &[Rc<KaramelAstType>]) -> VisitorResult<Self::Error> {
+        walk_func_call(self, func_name_expression, arguments)
+    }
+
+    fn visit_accessor_func_call(&mut self, source: &KaramelAstType, indexer: &KaramelAstType) -> VisitorResult<Self::Error> {
+        self.visit(source)?;
+        self.visit(indexer)
+    }
+
+    fn visit_primative(&mut self, _: &Rc<KaramelPrimative>) -> VisitorResult<Self::Error> {
+        Ok(())
+    }
+
+    fn visit_binary(&mut self, left: &KaramelAstType, _: KaramelOperatorType, right: &KaramelAstType) -> VisitorResult<Self::Error> {
+        self.visit(left)?;
+        self.visit(right)
+    }
+
+    fn visit_control(&mut self, left: &KaramelAstType, _: KaramelOperatorType, right: &KaramelAstType) -> VisitorResult<Self::Error> {
+        self.visit(left)?;
+        self.visit(right)
+    }
+
+    fn visit_prefix_unary(&mut self, _: KaramelOperatorType, expression: &KaramelAstType) -> VisitorResult<Self::Error> {
+        self.visit(expression)
+    }
+
+    fn visit_suffix_unary(&mut self, _: KaramelOperatorType, expression: &KaramelAstType) -> VisitorResult<Self::Error> {
+        self.visit(expression)
+    }
+
+    fn visit_assignment(&mut self, variable: &KaramelAstType, _: KaramelOperatorType, expression: &KaramelAstType) -> VisitorResult<Self::Error> {
+        self.visit(variable)?;
+        self.visit(expression)
+    }
+
+    fn visit_if_statement(&mut self, condition: &KaramelAstType, body: &KaramelAstType, else_body: Option<&KaramelAstType>, else_if: &[Rc<KaramelIfStatementElseItem>]) -> VisitorResult<Self::Error> {
+        walk_if_statement(self, condition, body, else_body, else_if)
+    }
+
+    fn visit_function_defination(&mut self, _: &str, _: &[String], body: &KaramelAstType) -> VisitorResult<Self::Error> {
+        self.visit(body)
+    }
+
+    /// Called before 'visit_function_defination' when the function has decorators. Decorators belong to the outer scope.
+    fn visit_decorators(&mut self, _: &str, decorators: &[Rc<KaramelAstType>]) -> VisitorResult<Self::Error> {
+        walk_items(self, decorators)
+    }
+
+    fn visit_symbol(&mut self, _: &str) -> VisitorResult<Self::Error> {
+        Ok(())
+    }
+
+    fn visit_module_path(&mut self, _: &[String]) -> VisitorResult<Self::Error> {
+        Ok(())
+    }
+
+    fn visit_load(&mut self, _: &[String]) -> VisitorResult<Self::Error> {
+        Ok(())
+    }
+
+    fn visit_use(&mut self, _: &[String], _: Option<&str>, _: &[String]) -> VisitorResult<Self::Error> {
+        Ok(())
+    }
+
+    fn visit_list(&mut self, items: &[Rc<KaramelAstType>]) -> VisitorResult<Self::Error> {
+        walk_items(self, items)
+    }
+
+    fn visit_dict(&mut self, items: &[Rc<KaramelDictItem>]) -> VisitorResult<Self::Error> {
+        walk_dict(self, items)
+    }
+
+    fn visit_indexer(&mut self, body: &KaramelAstType, indexer: &KaramelAstType) -> VisitorResult<Self::Error> {
+        self.visit(body)?;
+        self.visit(indexer)
+    }
+
+    fn visit_return(&mut self, expression: &KaramelAstType) -> VisitorResult<Self::Error> {
+        self.visit(expression)
+    }
+
+    fn visit_break(&mut self) -> VisitorResult<Self::Error> {
+        Ok(())
+    }
+
+    fn visit_continue(&mut self) -> VisitorResult<Self::Error> {
+        Ok(())
+    }
+
+    fn visit_loop(&mut self, loop_type: &LoopType, body: &KaramelAstType) -> VisitorResult<Self::Error> {
+        walk_loop(self, loop_type, body)
+    }
+
+    /// Catch variable is not visited as symbol, implementors that track the variables should handle it.
+    fn visit_try(&mut self, body: &KaramelAstType, _: Option<&str>, catch_body: &KaramelAstType) -> VisitorResult<Self::Error> {
+        self.visit(body)?;
+        self.visit(catch_body)
+    }
+
+    fn visit_throw(&mut self, expression: &KaramelAstType) -> VisitorResult<Self::Error> {
+        self.visit(expression)
+    }
+
+    fn visit_defer(&mut self, body: &KaramelAstType) -> VisitorResult<Self::Error> {
+        self.visit(body)
+    }
+
+    fn visit_with(&mut self, assignment: &KaramelAstType, close: &KaramelAstType, body: &KaramelAstType) -> VisitorResult<Self::Error> {
+        self.visit(assignment)?;
+        self.visit(close)?;
+        self.visit(body)
+    }
+
+    fn visit_constant(&mut self, assignment: &KaramelAstType) -> VisitorResult<Self::Error> {
+        self.visit(assignment)
+    }
+
+    fn visit_local(&mut self, assignment: &KaramelAstType) -> VisitorResult<Self::Error> {
+        self.visit(assignment)
+    }
+
+    fn visit_assume(&mut self, condition: &KaramelAstType, message: Option<&KaramelAstType>) -> VisitorResult<Self::Error> {
+        self.visit(condition)?;
+        match message {
+            Some(message) => self.visit(message),
+            None => Ok(())
+        }
+    }
+
+    fn visit_enum(&mut self, _: &str, _: &[String]) -> VisitorResult<Self::Error> {
+        Ok(())
+    }
+
+    fn visit_temporary(&mut self, _: &str, expression: Option<&KaramelAstType>) -> VisitorResult<Self::Error> {
+        match expression {
+            Some(expression) => self.visit(expression),
+            None => Ok(())
+        }
+    }
+}
+
+/// Calls the `visit_*` method that matches with the node type.
+pub fn walk_ast<V: AstVisitor>(visitor: &mut V, ast: &KaramelAstType) -> VisitorResult<V::Error> {
+    match ast {
+        KaramelAstType::None => visitor.visit_none(),
+        KaramelAstType::NewLine => visitor.visit_new_line(),
+        KaramelAstType::Block(items) => visitor.visit_block(items),
+        KaramelAstType::FuncCall { func_name_expression, arguments, assign_to_temp: _ } => visitor.visit_func_call(func_name_expression, arguments),
+        KaramelAstType::AccessorFuncCall { source, indexer, assign_to_temp: _ } => visitor.visit_accessor_func_call(source, indexer),
+        KaramelAstType::Primative(primative) => visitor.visit_primative(primative),
+        KaramelAstType::Binary { left, operator, right } => visitor.visit_binary(left, *operator, right),
+        KaramelAstType::Control { left, operator, right } => visitor.visit_control(left, *operator, right),
+        KaramelAstType::PrefixUnary { operator, expression, assign_to_temp: _ } => visitor.visit_prefix_unary(*operator, expression),
+        KaramelAstType::SuffixUnary(operator, expression) => visitor.visit_suffix_unary(*operator, expression),
+        KaramelAstType::Assignment { variable, operator, expression } => visitor.visit_assignment(variable, *operator, expression),
+        KaramelAstType::IfStatement { condition, body, else_body, else_if } => visitor.visit_if_statement(condition, body, else_body.as_deref(), else_if),
+        KaramelAstType::FunctionDefination { name, arguments, body, decorators, .. } => {
+            if !decorators.is_empty() {
+                visitor.visit_decorators(name, decorators)?;
+            }
+            visitor.visit_function_defination(name, arguments, body)
+        },
+        KaramelAstType::Symbol(symbol) => visitor.visit_symbol(symbol),
+        KaramelAstType::ModulePath(path) => visitor.visit_module_path(path),
+        KaramelAstType::Load(path) => visitor.visit_load(path),
+        KaramelAstType::Use { path, alias, names } => visitor.visit_use(path, alias.as_deref(), names),
+        KaramelAstType::List(items) => visitor.visit_list(items),
+        KaramelAstType::Dict(items) => visitor.visit_dict(items),
+        KaramelAstType::Indexer { body, indexer } => visitor.visit_indexer(body, indexer),
+        KaramelAstType::Return(expression) => visitor.visit_return(expression),
+        KaramelAstType::Break => visitor.visit_break(),
+        KaramelAstType::Continue => visitor.visit_continue(),
+        KaramelAstType::Loop { loop_type, body } => visitor.visit_loop(loop_type, body),
+        KaramelAstType::Try { body, variable, catch_body } => visitor.visit_try(body, variable.as_deref(), catch_body),
+        KaramelAstType::Throw(expression) => visitor.visit_throw(expression),
+        KaramelAstType::Defer(body) => visitor.visit_defer(body),
+        KaramelAstType::With { assignment, close, body } => visitor.visit_with(assignment, close, body),
+        KaramelAstType::Assume { condition, message } => visitor.visit_assume(condition, message.as_deref()),
+        KaramelAstType::Constant(assignment) => visitor.visit_constant(assignment),
+        KaramelAstType::Local(assignment) => visitor.visit_local(assignment),
+        KaramelAstType::Enum { name, variants } => visitor.visit_enum(name, variants),
+        KaramelAstType::Temporary { name, expression } => visitor.visit_temporary(name, expression.as_deref())
+    }
+}
+
+pub fn walk_items<V: AstVisitor>(visitor: &mut V, items: &[Rc<KaramelAstType>]) -> VisitorResult<V::Error> {
+    for item in items.iter() {
+        visitor.visit(item)?;
+    }
+    Ok(())
+}
+
+pub fn walk_func_call<V: AstVisitor>(visitor: &mut V, func_name_expression: &KaramelAstType, arguments: &[Rc<KaramelAstType>]) -> VisitorResult<V::Error> {
+    walk_items(visitor, arguments)?;
+    visitor.visit(func_name_expression)
+}
+
+pub fn walk_dict<V: AstVisitor>(visitor: &mut V, items: &[Rc<KaramelDictItem>]) -> VisitorResult<V::Error> {
+    for item in items.iter() {
+        visitor.visit_primative(&item.key)?;
+        visitor.visit(&item.value)?;
+    }
+    Ok(())
+}
+
+pub fn walk_if_statement<V: AstVisitor>(visitor: &mut V, condition: &KaramelAstType, body: &KaramelAstType, else_body: Option<&KaramelAstType>, else_if: &[Rc<KaramelIfStatementElseItem>]) -> VisitorResult<V::Error> {
+    visitor.visit(condition)?;
+    visitor.visit(body)?;
+
+    if let Some(else_body) = else_body {
+        visitor.visit(else_body)?;
+    }
+
+    for item in else_if.iter() {
+        visitor.visit(&item.condition)?;
+        visitor.visit(&item.body)?;
+    }
+    Ok(())
+}
+
+pub fn walk_loop<V: AstVisitor>(visitor: &mut V, loop_type: &LoopType, body: &KaramelAstType) -> VisitorResult<V::Error> {
+    match loop_type {
+        LoopType::Scalar { variable, control, increment } => {
+            visitor.visit(variable)?;
+            visitor.visit(control)?;
+            visitor.visit(increment)?;
+        },
+        LoopType::Simple(control) => visitor.visit(control)?,
+        LoopType::Endless => ()
+    };
+    visitor.visit(body)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::rc::Rc;
+
+    use super::AstVisitor;
+    use crate::compiler::ast::KaramelAstType;
+    use crate::parser::Parser;
+    use crate::syntax::SyntaxParser;
+
+    #[derive(Default)]
+    struct SymbolCollector {
+        symbols: Vec<String>,
+        functions: Vec<String>
+    }
+
+    impl AstVisitor for SymbolCollector {
+        type Error = ();
+
+        fn visit_symbol(&mut self, symbol: &str) -> Result<(), ()> {
+            self.symbols.push(symbol.to_string());
+            Ok(())
+        }
+
+        fn visit_function_defination(&mut self, name: &str, _: &[String], body: &KaramelAstType) -> Result<(), ()> {
+            self.functions.push(name.to_string());
+            self.visit(body)
+        }
+    }
+
+    fn parse(code: &str) -> Rc<KaramelAstType> {
+        let mut parser = Parser::new(code);
+        parser.parse().unwrap();
+        SyntaxParser::new(parser.tokens()).parse().unwrap()
+    }
+
+    #[test]
+    fn visitor_test_1() {
+        let ast = parse(r#"
+fonk topla(a, b):
+    döndür a + b
+
+sonuç = topla(1, 2)
+sonuç > 2 ise:
+    liste = [sonuç, {'anahtar': kayıt}]
+"#);
+        let mut collector = SymbolCollector::default();
+        collector.visit(&ast).unwrap();
+
+        assert_eq!(collector.functions, vec!["topla".to_string()]);
+        assert_eq!(collector.symbols, vec!["a", "b", "sonuç", "topla", "sonuç", "liste", "sonuç", "kayıt"]);
+    }
+
+    struct Stopper;
+
+    impl AstVisitor for Stopper {
+        type Error = String;
+
+        fn visit_break(&mut self) -> Result<(), String> {
+            Err("kır".to_string())
+        }
+    }
+
+    #[test]
+    fn visitor_test_2() {
+        assert_eq!(Stopper.visit(&parse("döngü doğru:\n    kır")), Err("kır".to_string()));
+        assert_eq!(Stopper.visit(&parse("a = 1")), Ok(()));
+    }
+}
@@ -3,11 +3,52 @@ pub static KARAMEL_FILE_EXTENSION: &'static str = ".k";
 pub static KARAMEL_VERSION: &'static str = "0.1";
 pub static KARAMEL_CONTACT_EMAIL: &'static str = "erhanbaris@gmail.com";
 pub static KARAMEL_TITLE: &'static str = "Karamel Programlama Dili";
-pub static KARAMEL_HELP_ABOUT: &'static str = r#"Karamel Programlama Dili Derleyicisi.
-
-https://github.com/erhanbaris/karamel"#;
+pub static KARAMEL_LANGUAGE_VARIABLE: &'static str = "KARAMEL_DIL";
 
 pub static STARTUP_MODULE_NAME: &'static str = "baz.k";
+pub static KARAMEL_STORE_FILE: &'static str = "karamel_depo.json";
+
+/// Files of the küfe packages. Manifest is written by the user, the lock file is updated while the packages are loaded.
+pub static KARAMEL_PACKAGE_MANIFEST: &str = "küfe.toml";
+pub static KARAMEL_PACKAGE_LOCK: &str = "küfe.kilit";
+
+/// Git dependencies are cloned into this directory of the project
+pub static KARAMEL_PACKAGE_DIRECTORY: &str = ".küfe";
+
+pub static KARAMEL_TEACHING_LOOP_LIMIT: usize = 100_000;
+pub static KARAMEL_EVENT_BUFFER_SIZE: usize = 1024;
+
+/// Nested lists and dicts deeper than this are written as '<...>' at the event stream
+pub static KARAMEL_EVENT_VALUE_DEPTH: usize = 32;
+pub static KARAMEL_GC_THRESHOLD: usize = 100_000;
+pub static KARAMEL_MODULE_PARSER_THREADS: usize = 8;
+pub static KARAMEL_DEFAULT_PRINT_PRECISION: usize = 15;
+pub static KARAMEL_MAX_PRINT_PRECISION: usize = 17;
+
+/// Names of the variables that are generated by the compiler start with this prefix. It can not be written in the code.
+pub static KARAMEL_HIDDEN_VARIABLE_PREFIX: &'static str = "$";
+
+/// Functions of the main code that start with this prefix are run by the test runner.
+pub static KARAMEL_TEST_PREFIX: &'static str = "test_";
+
+/// Nesting level of the expressions and blocks that the hardened syntax parser accepts. Deeper code would overflow the stack.
+pub static KARAMEL_MAX_NESTING_DEPTH: usize = 64;
+
+/// Function calls that can be nested by default. Deeper calls stop with the 'StackOverflow' error instead of overflowing the stack of the vm.
+pub static KARAMEL_MAX_CALL_DEPTH: usize = 1_000;
+
+/// Bytes that a text or a bytes value can have after '+' and '*'. Bigger values stop with the 'ValueTooLarge' error instead of
+/// aborting the process while the memory is allocated.
+pub static KARAMEL_MAX_TEXT_SIZE: usize = 256 * 1024 * 1024;
+
+/// Bytes of the texts, bytes and copies that the sandboxed code can create by default
+pub static KARAMEL_SANDBOX_MEMORY_LIMIT: usize = 256 * 1024 * 1024;
+
+/// Instructions that the sandboxed code can execute by default
+pub static KARAMEL_SANDBOX_INSTRUCTION_LIMIT: usize = 10_000_000;
+
+/// Type names that 'baz::türü' returns. Function parameters can be annotated with them.
+pub static KARAMEL_TYPE_NAMES: [&'static str; 17] = ["sayı", "yazı", "bool", "liste", "sözlük", "boş", "fonksiyon", "sınıf", "yığın", "kuyruk", "öncelik_kuyruğu", "hata", "yazı_oluşturucu", "dondurulmuş_liste", "dondurulmuş_sözlük", "sıralı_sözlük", "bayt"];
 
 
 pub static DUMP_OPCODE_TITLE: &'static str = "OPCODE DUMP";
@@ -15,4 +56,5 @@ pub static DUMP_INDEX_WIDTH: usize = 5;
 pub static DUMP_OPCODE_WIDTH: usize = 15;
 pub static DUMP_OPCODE_COLUMN_1: usize = 5;
 pub static DUMP_OPCODE_COLUMN_2: usize = 5;
-pub static DUMP_OPCODE_COLUMN_3: usize = 5;
\ No newline at end of file
+pub static DUMP_OPCODE_COLUMN_3: usize = 5;
+pub static LISTING_LINE_WIDTH: usize = 5;
\ No newline at end of file
@@ -0,0 +1,227 @@
+use strum::EnumMessage;
+
+use crate::lint::LintWarning;
+use crate::messages;
+
+use super::{CompilerWarning, KaramelError};
+
+/* Maximum number of the source characters shown by the rendered diagnostic */
+const RENDER_WIDTH: usize = 80;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DiagnosticSeverity {
+    Error,
+    Warning,
+    Lint
+}
+
+impl DiagnosticSeverity {
+    pub fn name(&self) -> &'static str {
+        match self {
+            DiagnosticSeverity::Error => "error",
+            DiagnosticSeverity::Warning => "warning",
+            DiagnosticSeverity::Lint => "lint"
+        }
+    }
+}
+
+/// Source location of the diagnostic. Lines and columns are zero based, same as the tokens.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Span {
+    pub line: u32,
+    pub start: u32,
+    pub end: u32
+}
+
+impl Span {
+    pub fn new(line: u32, start: u32, end: u32) -> Self {
+        Span { line, start, end }
+    }
+
+    pub fn point(line: u32, column: u32) -> Self {
+        Span { line, start: column, end: column }
+    }
+}
+
+/// Common form of the errors, compiler warnings and lint warnings. Used for rendering them the same way
+/// in the console, language server and machine readable outputs. The tokenizer, parser, compiler and vm still
+/// return their own error types, they are converted to this adapter with the 'From' implementations below.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Diagnostic {
+    pub code: String,
+    pub severity: DiagnosticSeverity,
+    pub span: Span,
+
+    /// Stable name of the message, independent from the language of the text
+    pub message_key: String,
+    pub message: String,
+    pub notes: Vec<String>
+}
+
+impl Diagnostic {
+    /// Source line with a marker under the column, followed by the code and message.
+    pub fn render<T: AsRef<str>>(&self, source: T) -> String {
+        let header = match self.severity {
+            DiagnosticSeverity::Error => "...".to_string(),
+            _ => messages::text("diagnostic.warning")
+        };
+
+        let line = source.as_ref().split('\n').nth(self.span.line as usize).unwrap_or_default();
+        let (line, column) = excerpt(line, self.span.start as usize);
+        format!("{}\r\n{}\r\n{:>width$} [{}:{}] (#{}) {}", header, line, "^", self.span.line, self.span.start, self.code, self.message, width=column)
+    }
+
+    /// Single line JSON object. Lines and columns are one based.
+    pub fn to_json(&self) -> String {
+        let mut buffer = String::new();
+        buffer.push_str("{\"code\": ");
+        encode_text(&self.code, &mut buffer);
+        buffer.push_str(&format!(", \"severity\": \"{}\", \"line\": {}, \"start\": {}, \"end\": {}, \"key\": ",
+            self.severity.name(), self.span.line + 1, self.span.start + 1, self.span.end + 1));
+        encode_text(&self.message_key, &mut buffer);
+        buffer.push_str(", \"message\": ");
+        encode_text(&self.message, &mut buffer);
+        buffer.push_str(", \"notes\": [");
+
+        for (index, note) in self.notes.iter().enumerate() {
+            if index > 0 {
+                buffer.push_str(", ");
+            }
+            encode_text(note, &mut buffer);
+        }
+        buffer.push_str("]}");
+        buffer
+    }
+}
+
+impl From<&KaramelError> for Diagnostic {
+    fn from(error: &KaramelError) -> Self {
+        Diagnostic {
+            code: error.error_type.get_message().unwrap_or_default().to_string(),
+            severity: DiagnosticSeverity::Error,
+            span: Span::point(error.line, error.column),
+            message_key: error.error_type.message_key(),
+            message: error.error_type.to_string(),
+            notes: error.error_type.teaching_hint().map(|hint| vec![hint]).unwrap_or_default()
+        }
+    }
+}
+
+impl From<&CompilerWarning> for Diagnostic {
+    fn from(warning: &CompilerWarning) -> Self {
+        Diagnostic {
+            code: warning.warning_type.get_message().unwrap_or_default().to_string(),
+            severity: DiagnosticSeverity::Warning,
+            span: Span::point(warning.line, warning.column),
+            message_key: warning.warning_type.message_key(),
+            message: warning.warning_type.to_string(),
+            notes: warning.warning_type.teaching_hint().map(|hint| vec![hint]).unwrap_or_default()
+        }
+    }
+}
+
+impl From<&LintWarning> for Diagnostic {
+    fn from(warning: &LintWarning) -> Self {
+        Diagnostic {
+            code: warning.rule.code().to_string(),
+            severity: DiagnosticSeverity::Lint,
+            span: Span::point(warning.line, warning.column),
+            message_key: format!("{:?}", warning.rule),
+            message: warning.message.clone(),
+            notes: Vec::new()
+        }
+    }
+}
+
+/* Long lines are shortened around the column, so the marker stays under the error */
+fn excerpt(line: &str, column: usize) -> (String, usize) {
+    let length = line.chars().count();
+    if length <= RENDER_WIDTH {
+        return (line.to_string(), column);
+    }
+
+    let start = column.saturating_sub(RENDER_WIDTH / 2).min(length - RENDER_WIDTH);
+    let mut text: String = line.chars().skip(start).take(RENDER_WIDTH).collect();
+    let mut column = column - start;
+
+    if start > 0 {
+        text.insert_str(0, "...");
+        column += 3;
+    }
+
+    if start + RENDER_WIDTH < length {
+        text.push_str("...");
+    }
+    (text, column)
+}
+
+/// Writes the text as a JSON string
+pub(crate) fn encode_text(text: &str, buffer: &mut String) {
+    buffer.push('"');
+    for chr in text.chars() {
+        match chr {
+            '"'  => buffer.push_str("\\\""),
+            '\\' => buffer.push_str("\\\\"),
+            '\n' => buffer.push_str("\\n"),
+            '\r' => buffer.push_str("\\r"),
+            '\t' => buffer.push_str("\\t"),
+            chr if (chr as u32) < 0x20 => buffer.push_str(&format!("\\u{:04x}", chr as u32)),
+            chr => buffer.push(chr)
+        };
+    }
+    buffer.push('"');
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Diagnostic, DiagnosticSeverity, Span};
+    use crate::error::{CompilerWarning, KaramelError, KaramelErrorType, KaramelWarningType};
+    use crate::lint::{LintRule, LintWarning};
+
+    #[test]
+    fn error_diagnostic() {
+        let error = KaramelError::new(1, 2, KaramelErrorType::VariableNotDefined("a".to_string()));
+        let diagnostic = Diagnostic::from(&error);
+
+        assert_eq!(diagnostic.code, "157");
+        assert_eq!(diagnostic.severity, DiagnosticSeverity::Error);
+        assert_eq!(diagnostic.span, Span::point(1, 2));
+        assert_eq!(diagnostic.message_key, "VariableNotDefined");
+        assert_eq!(diagnostic.notes.len(), 1);
+        assert_eq!(diagnostic.render("b = 1\nc = a"), format!("...\r\nc = a\r\n ^ [1:2] (#157) {}", error.error_type));
+    }
+
+    #[test]
+    fn long_line_diagnostic() {
+        let source = format!("a = '{}", "x".repeat(100_000));
+        let error = KaramelError::new(0, 50_000, KaramelErrorType::MissingStringDeliminator);
+        let rendered = Diagnostic::from(&error).render(&source);
+        let lines: Vec<&str> = rendered.split("\r\n").collect();
+
+        assert_eq!(lines[1], format!("...{}...", "x".repeat(80)));
+        assert!(lines[2].starts_with(&format!("{}^", " ".repeat(42))));
+
+        let error = KaramelError::new(0, 2, KaramelErrorType::MissingStringDeliminator);
+        let rendered = Diagnostic::from(&error).render(&source);
+        assert!(rendered.contains(&format!("\r\na = '{}...\r\n ^", "x".repeat(75))));
+    }
+
+    #[test]
+    fn warning_diagnostic() {
+        let warning = CompilerWarning::new(0, 4, KaramelWarningType::UnusedFunction("topla".to_string()));
+        let diagnostic = Diagnostic::from(&warning);
+
+        assert_eq!(diagnostic.code, "U001");
+        assert_eq!(diagnostic.severity, DiagnosticSeverity::Warning);
+        assert_eq!(diagnostic.message_key, "UnusedFunction");
+        assert!(diagnostic.render("fonk topla():").starts_with("Uyarı\r\nfonk topla():"));
+    }
+
+    #[test]
+    fn lint_diagnostic_json() {
+        let warning = LintWarning { rule: LintRule::UnusedVariable, message: "'a' \"kullanılmıyor\"".to_string(), line: 2, column: 0 };
+        let diagnostic = Diagnostic::from(&warning);
+
+        assert_eq!(diagnostic.to_json(), "{\"code\": \"L001\", \"severity\": \"lint\", \"line\": 3, \"start\": 1, \"end\": 1, \"key\": \"UnusedVariable\", \"message\": \"'a' \\\"kullanılmıyor\\\"\", \"notes\": []}");
+    }
+}
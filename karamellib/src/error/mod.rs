@@ -1,13 +1,20 @@
 use std::borrow::Borrow;
+use std::fmt;
 use std::rc::Rc;
 
 use strum::EnumMessage;
 use strum_macros::EnumIter;
 use strum_macros::EnumMessage;
 use strum_macros::EnumDiscriminants;
-use thiserror::Error;
 
 use crate::compiler::KaramelPrimative;
+#[cfg(feature = "vm")]
+use crate::compiler::scope::CallFrame;
+use crate::messages;
+
+pub mod diagnostic;
+
+use diagnostic::Diagnostic;
 
 
 pub enum KaramelErrorSeverity {
@@ -19,171 +26,130 @@ pub enum KaramelErrorSeverity {
 #[derive(Clone)]
 #[derive(Debug)]
 #[derive(PartialEq)]
-#[derive(Error)]
 #[derive(EnumIter)]
 #[derive(EnumMessage)]
 #[derive(EnumDiscriminants)]
 pub enum KaramelErrorType {
-    #[error("Sozdizimi hatasi")]
     #[strum(message = "100")]
     SyntaxError,
     
-    #[error("Geçersiz ifade")]
     #[strum(message = "101")]
     InvalidExpression,
 
-    #[error("Birden fazla degisken kullanilamaz")]
     #[strum(message = "102")]
     MoreThan1ArgumentPassed,
     
-    #[error("Sağ parantaz eksik")]
     #[strum(message = "103")]
     RightParanthesesMissing,
     
-    #[error("Doğrulanama  başarısız")]
     #[strum(message = "104")]
     AssertFailed,
     
-    #[error("Sayı ayrıştırılamadı")]
     #[strum(message = "105")]
     NumberNotParsed,
     
-    #[error("Yazı sonlandırıcısı bulunamadı")]
     #[strum(message = "106")]
     MissingStringDeliminator,
     
-    #[error("Karakter geçerli değil")]
     #[strum(message = "107")]
     CharNotValid,
     
-    #[error("İfadenin sağ tarafı bulunamadı")]
     #[strum(message = "108")]
     RightSideOfExpressionNotFound,
     
-    #[error("Döndür komutu fonksiyon içinde kullanılmalıdır")]
     #[strum(message = "109")]
     ReturnMustBeUsedInFunction,
     
-    #[error("Fonksiyon çağırma sözdizimi geçerli değil")]
     #[strum(message = "110")]
     FunctionCallSyntaxNotValid,
     
-    #[error("Fonksiyon adı tanımlanmamış")]
     #[strum(message = "111")]
     FunctionNameNotDefined,
     
-    #[error("Değişken yazı olmalıdır")]
     #[strum(message = "112")]
     ArgumentMustBeText,
     
-    #[error("Koşul gövdesi eksik")]
     #[strum(message = "113")]
     IfConditionBodyNotFound,
     
-    #[error("Parantez kapatılmamış")]
     #[strum(message = "114")]
     ParenthesesNotClosed,
     
-    #[error("Geçersiz tekli işlem")]
     #[strum(message = "115")]
     InvalidUnaryOperation,
     
-    #[error("Tekli numara ile çalışmaktadır")]
     #[strum(message = "116")]
     UnaryWorksWithNumber,
     
-    #[error("Parametre bulunamadı")]
     #[strum(message = "117")]
     ArgumentNotFound,
     
-    #[error("Birden fazla 'yoksa' ifadesi kullanılamaz")]
     #[strum(message = "118")]
     MultipleElseUsageNotValid,
     
-    #[error("'kır' ve 'devam' ifadeleri döngü içinde kullanılabilir")]
     #[strum(message = "119")]
     BreakAndContinueBelongToLoops,
     
-    #[error("Fonksiyon içi kodlar bulunamadı")]
     #[strum(message = "120")]
     FunctionConditionBodyNotFound,
     
-    #[error("':' eksik")]
     #[strum(message = "121")]
     ColonMarkMissing,
     
-    #[error("'yoksa' zaten kullanıldı")]
     #[strum(message = "122")]
     ElseIsUsed,
     
-    #[error("Girinti sorunu")]
     #[strum(message = "123")]
     IndentationIssue,
     
-    #[error("Sözlük düzgün kapatılmamış")]
     #[strum(message = "124")]
     DictNotClosed,
     
-    #[error("Dizi düzgün kapatılmadı")]
     #[strum(message = "125")]
     ArrayNotClosed,
     
-    #[error("Dizi elemanı geçersiz")]
     #[strum(message = "126")]
     InvalidListItem,
     
-    #[error("Sözlük anahtarı geçersiz")]
     #[strum(message = "127")]
     DictionaryKeyNotValid,
     
-    #[error("Sözlük geçeri geçersiz")]
     #[strum(message = "128")]
     DictionaryValueNotValid,
     
-    #[error("Yorum bilgisi düzgün kapatılmadı")]
     #[strum(message = "129")]
     CommentNotFinished,
     
-    #[error("Döngü düzgün tanımlanmamış")]
     #[strum(message = "130")]
     WhileStatementNotValid,
     
-    #[error("Fonksiyon tanımlaması hatalı")]
     #[strum(message = "131")]
     FunctionDefinationNotValid,
     
-    #[error("'ise' sözcüğü eksik")]
     #[strum(message = "132")]
     MissingIf,
     
-    #[error("Anahtar kelimeler kullanılamaz")]
     #[strum(message = "133")]
     KeywordCouldNotBeUsed,
     
-    #[error("'{filename}' okunamadi. Hata: '{error}'")]
     #[strum(message = "134")]
     FileReadError {
         filename: String,
         error: String
     },
     
-    #[error("'{0}' bulunamadi")]
     #[strum(message = "135")]
     FileNotFound(String),
     
-    #[error("{0}")]
     #[strum(message = "136")]
     GeneralError(String),
     
-    #[error("'{0}' fonksiyonu önceden tanımlanmış")]
     #[strum(message = "137")]
     FunctionAlreadyDefined(String),
 
-    #[error("'{0}' fonksiyonu bulunamadı")]
     #[strum(message = "138")]
     FunctionNotFound(String),
 
-    #[error("'{function}' fonksiyon parametreleri eşleşmiyor. {expected} adet beklenirken {found} adet bulundu")]
     #[strum(message = "139")]
     FunctionArgumentNotMatching {
         function: String,
@@ -191,70 +157,427 @@ pub enum KaramelErrorType {
         found: u8
     },
 
-    #[error("'{function}' fonksiyonu sadece {expected} parametresini kabul ediyor")]
     #[strum(message = "140")]
     FunctionExpectedThatParameterType {
         function: String,
         expected: String
     },
     
-    #[error("Doğrulama başarısız (Sol: {left:?}, sağ: {right:?})")]
     #[strum(message = "141")]
     AssertFailedWithArgument {
         left: Rc<KaramelPrimative>,
         right: Rc<KaramelPrimative>
     },
 
-    #[error("Tekli ifade geçerli değil")]
     #[strum(message = "142")]
     UnaryExpressionNotValid,
 
-    #[error("Tekli operatör bulunamadi")]
     #[strum(message = "143")]
     UnaryOperatorNotFound,
 
-    #[error("Depoda değer bulunamadı")]
     #[strum(message = "144")]
     ValueNotFoundInStorage,
 
-    #[error("'{0}' reserv edilmiş kelimedir, kullanılamaz")]
     #[strum(message = "145")]
     ReservedName(String),
 
-    #[error("'{name}' modül okuma sırasında hata ile karşılaşıldı. Hata {error}")]
     #[strum(message = "146")]
     ModuleParseError {
         name: String,
         error: String
     },
 
-    #[error("Depoda fonksiyon({0}) bulunamadı")]
     #[strum(message = "147")]
     FunctionNotFoundInStorage(String),
 
-    #[error("'{0:?}' fonksiyon olarak çağrılabilir değil")]
     #[strum(message = "148")]
     NotCallable(Rc<KaramelPrimative>),
 
-    #[error("'{0:?}' geçerli bir sıralayıcı değil, sayı olması gerekiyor")]
     #[strum(message = "149")]
     IndexerMustBeNumber(Rc<KaramelPrimative>),
 
-    #[error("'{0:?}' geçerli bir sıralayıcı değil, yazı olması gerekiyor")]
     #[strum(message = "150")]
     IndexerMustBeString(Rc<KaramelPrimative>),
 
-    #[error("Döngü ile sadece atama öperatörü kullanılabilir")]
     #[strum(message = "151")]
     AssignOperatorRequiredForLoop,
 
-    #[error("virgül eksik")]
     #[strum(message = "152")]
     CommaIsMissing,
 
-    #[error("Öperatör geçerli değil")]
     #[strum(message = "153")]
-    OperatorNotValid
+    OperatorNotValid,
+
+    #[strum(message = "154")]
+    FileWriteError {
+        filename: String,
+        error: String
+    },
+
+    #[strum(message = "155")]
+    DisabledInTeachingMode(String),
+
+    #[strum(message = "156")]
+    LoopIterationLimitExceeded(usize),
+
+    #[strum(message = "157")]
+    VariableNotDefined(String),
+
+    #[strum(message = "158")]
+    VariableUsedBeforeAssignment(String),
+
+    #[strum(message = "159")]
+    InternalError(String),
+
+    #[strum(message = "160")]
+    InvalidNumberLiteral(String),
+
+    #[strum(message = "161")]
+    InvalidEscape(String),
+
+    #[strum(message = "162")]
+    UncaughtError(Rc<KaramelPrimative>),
+
+    #[strum(message = "163")]
+    CatchBlockNotFound,
+
+    #[strum(message = "164")]
+    DeferMustBeUsedInFunction,
+
+    #[strum(message = "165")]
+    ConversionFailed {
+        value: String,
+        target: String
+    },
+
+    #[strum(message = "166")]
+    AssumptionFailed(String),
+
+    #[strum(message = "167")]
+    IndexOutOfRange {
+        index: String,
+        length: usize
+    },
+
+    #[strum(message = "168")]
+    IndexerAssignmentNotSupported(Rc<KaramelPrimative>),
+
+    #[strum(message = "169")]
+    SymbolTooLong(usize),
+
+    #[strum(message = "170")]
+    TextTooLong(usize),
+
+    #[strum(message = "171")]
+    LineTooLong(usize),
+
+    #[strum(message = "172")]
+    UnknownTypeName(String),
+
+    #[strum(message = "173")]
+    ArgumentTypeMismatch {
+        argument: String,
+        expected: String,
+        found: String
+    },
+
+    #[strum(message = "174")]
+    TooManyConstants(usize),
+
+    #[strum(message = "175")]
+    TooManyVariables(usize),
+
+    #[strum(message = "176")]
+    TooManyArguments(usize),
+
+    #[strum(message = "177")]
+    TooManyItems(usize),
+
+    #[strum(message = "178")]
+    TaskNotFound(usize),
+
+    #[strum(message = "179")]
+    TaskDeadlock(usize),
+
+    #[strum(message = "180")]
+    ChannelNotFound(usize),
+
+    #[strum(message = "181")]
+    ChannelEmpty(usize),
+
+    #[strum(message = "182")]
+    Interrupted,
+
+    #[strum(message = "183")]
+    AssertFailedWithExpression {
+        expression: String,
+        line: u32,
+        message: String
+    },
+
+    #[strum(message = "184")]
+    IndexerNotClosed,
+
+    #[strum(message = "185")]
+    NestingTooDeep(usize),
+
+    #[strum(message = "186")]
+    TranspileNotSupported(String),
+
+    #[strum(message = "187")]
+    ForeignLibraryNotLoaded {
+        path: String,
+        error: String
+    },
+
+    #[strum(message = "188")]
+    ForeignFunctionNotFound(String),
+
+    #[strum(message = "189")]
+    ForeignTypeNotSupported(String),
+
+    #[strum(message = "190")]
+    ConstantReassigned(String),
+
+    #[strum(message = "191")]
+    ConstantValueRequired,
+
+    #[strum(message = "192")]
+    LocalValueRequired,
+
+    #[strum(message = "193")]
+    EnumVariantsRequired,
+
+    #[strum(message = "194")]
+    EnumAlreadyDefined(String),
+
+    #[strum(message = "195")]
+    EnumVariantAlreadyDefined(String),
+
+    #[strum(message = "196")]
+    EnumVariantNotFound(String),
+
+    #[strum(message = "197")]
+    DecoratorWithoutFunction,
+
+    #[strum(message = "198")]
+    StackOverflow(usize),
+
+    #[strum(message = "199")]
+    PrivateWithoutFunction,
+
+    #[strum(message = "200")]
+    FunctionIsPrivate(String),
+
+    #[strum(message = "201")]
+    InvalidUseStatement,
+
+    #[strum(message = "202")]
+    ImportNotFound(String),
+
+    #[strum(message = "203")]
+    InvalidPackageFile {
+        file: String,
+        line: usize
+    },
+
+    #[strum(message = "204")]
+    DependencyNotFound(String),
+
+    #[strum(message = "205")]
+    DependencyConflict(String),
+
+    #[strum(message = "206")]
+    InvalidSourceEncoding {
+        file: String,
+        encoding: String
+    },
+
+    #[strum(message = "207")]
+    InstructionLimitExceeded(usize),
+
+    #[strum(message = "208")]
+    PermissionDenied {
+        module: String,
+        permission: String
+    },
+
+    #[strum(message = "209")]
+    InvalidWithStatement,
+
+    #[strum(message = "210")]
+    CloseFunctionNotFound(String),
+
+    #[strum(message = "211")]
+    FrozenValueCannotBeChanged,
+
+    #[strum(message = "212")]
+    DictionaryKeyNotHashable(Rc<KaramelPrimative>),
+
+    #[strum(message = "213")]
+    ValueTooLarge(usize),
+
+    #[strum(message = "214")]
+    MemoryLimitExceeded(usize),
+
+    #[strum(message = "215")]
+    DependencyNotInstalled(String)
+}
+
+impl KaramelErrorType {
+    /// Stable name of the error. Also the key of the message in the catalog.
+    pub fn message_key(&self) -> String {
+        format!("{:?}", KaramelErrorTypeDiscriminants::from(self))
+    }
+
+    /// Longer explanation of the error for the beginners. Used by the teaching mode.
+    pub fn teaching_hint(&self) -> Option<String> {
+        self.hint_key().map(messages::text)
+    }
+
+    /// Failed 'doğrula' calls and 'varsay' statements
+    pub fn is_assertion(&self) -> bool {
+        matches!(self, KaramelErrorType::AssertFailed | KaramelErrorType::AssertFailedWithArgument { .. } |
+            KaramelErrorType::AssertFailedWithExpression { .. } | KaramelErrorType::AssumptionFailed(_))
+    }
+
+    fn hint_key(&self) -> Option<&'static str> {
+        match self {
+            KaramelErrorType::SyntaxError => Some("SyntaxError.hint"),
+            KaramelErrorType::InvalidExpression => Some("InvalidExpression.hint"),
+            KaramelErrorType::RightParanthesesMissing | KaramelErrorType::ParenthesesNotClosed => Some("RightParanthesesMissing.hint"),
+            KaramelErrorType::MissingStringDeliminator => Some("MissingStringDeliminator.hint"),
+            KaramelErrorType::ColonMarkMissing => Some("ColonMarkMissing.hint"),
+            KaramelErrorType::IndentationIssue => Some("IndentationIssue.hint"),
+            KaramelErrorType::IfConditionBodyNotFound => Some("IfConditionBodyNotFound.hint"),
+            KaramelErrorType::BreakAndContinueBelongToLoops => Some("BreakAndContinueBelongToLoops.hint"),
+            KaramelErrorType::ReturnMustBeUsedInFunction => Some("ReturnMustBeUsedInFunction.hint"),
+            KaramelErrorType::FunctionNotFound(_) | KaramelErrorType::FunctionNotFoundInStorage(_) => Some("FunctionNotFound.hint"),
+            KaramelErrorType::FunctionArgumentNotMatching { .. } => Some("FunctionArgumentNotMatching.hint"),
+            KaramelErrorType::ReservedName(_) => Some("ReservedName.hint"),
+            KaramelErrorType::AssertFailed | KaramelErrorType::AssertFailedWithArgument { .. } => Some("AssertFailed.hint"),
+            KaramelErrorType::AssertFailedWithExpression { .. } => Some("AssertFailedWithExpression.hint"),
+            KaramelErrorType::DisabledInTeachingMode(_) => Some("DisabledInTeachingMode.hint"),
+            KaramelErrorType::VariableNotDefined(_) => Some("VariableNotDefined.hint"),
+            KaramelErrorType::VariableUsedBeforeAssignment(_) => Some("VariableUsedBeforeAssignment.hint"),
+            KaramelErrorType::InvalidNumberLiteral(_) => Some("InvalidNumberLiteral.hint"),
+            KaramelErrorType::InvalidEscape(_) => Some("InvalidEscape.hint"),
+            KaramelErrorType::UncaughtError(_) => Some("UncaughtError.hint"),
+            KaramelErrorType::CatchBlockNotFound => Some("CatchBlockNotFound.hint"),
+            KaramelErrorType::DeferMustBeUsedInFunction => Some("DeferMustBeUsedInFunction.hint"),
+            KaramelErrorType::ConversionFailed { .. } => Some("ConversionFailed.hint"),
+            KaramelErrorType::AssumptionFailed(_) => Some("AssumptionFailed.hint"),
+            KaramelErrorType::IndexOutOfRange { .. } => Some("IndexOutOfRange.hint"),
+            KaramelErrorType::TextTooLong(_) => Some("TextTooLong.hint"),
+            KaramelErrorType::ArgumentTypeMismatch { .. } => Some("ArgumentTypeMismatch.hint"),
+            KaramelErrorType::InternalError(_) => Some("InternalError.hint"),
+            KaramelErrorType::LoopIterationLimitExceeded(_) => Some("LoopIterationLimitExceeded.hint"),
+            KaramelErrorType::ConstantReassigned(_) => Some("ConstantReassigned.hint"),
+            KaramelErrorType::InvalidSourceEncoding { .. } => Some("InvalidSourceEncoding.hint"),
+            KaramelErrorType::CloseFunctionNotFound(_) => Some("CloseFunctionNotFound.hint"),
+            KaramelErrorType::FrozenValueCannotBeChanged => Some("FrozenValueCannotBeChanged.hint"),
+            KaramelErrorType::DictionaryKeyNotHashable(_) => Some("DictionaryKeyNotHashable.hint"),
+            KaramelErrorType::ValueTooLarge(_) => Some("ValueTooLarge.hint"),
+            KaramelErrorType::MemoryLimitExceeded(_) => Some("MemoryLimitExceeded.hint"),
+            KaramelErrorType::DependencyNotInstalled(_) => Some("DependencyNotInstalled.hint"),
+            _ => None
+        }
+    }
+
+    fn message_arguments(&self) -> Vec<(&'static str, String)> {
+        match self {
+            KaramelErrorType::FileReadError { filename, error } | KaramelErrorType::FileWriteError { filename, error } => vec![("filename", filename.to_string()), ("error", error.to_string())],
+            KaramelErrorType::ForeignLibraryNotLoaded { path, error } => vec![("path", path.to_string()), ("error", error.to_string())],
+            KaramelErrorType::ModuleParseError { name, error } => vec![("name", name.to_string()), ("error", error.to_string())],
+            KaramelErrorType::InvalidPackageFile { file, line } => vec![("file", file.to_string()), ("line", line.to_string())],
+            KaramelErrorType::InvalidSourceEncoding { file, encoding } => vec![("file", file.to_string()), ("encoding", encoding.to_string())],
+            KaramelErrorType::PermissionDenied { module, permission } => vec![("module", module.to_string()), ("permission", permission.to_string())],
+            KaramelErrorType::ConversionFailed { value, target } => vec![("value", value.to_string()), ("target", target.to_string())],
+            KaramelErrorType::FunctionArgumentNotMatching { function, expected, found } => vec![("function", function.to_string()), ("expected", expected.to_string()), ("found", found.to_string())],
+            KaramelErrorType::FunctionExpectedThatParameterType { function, expected } => vec![("function", function.to_string()), ("expected", expected.to_string())],
+            KaramelErrorType::AssertFailedWithArgument { left, right } => vec![("left", format!("{:?}", left)), ("right", format!("{:?}", right))],
+            KaramelErrorType::AssertFailedWithExpression { expression, line, message } => vec![("expression", expression.to_string()), ("line", (line + 1).to_string()), ("message", message.to_string())],
+            KaramelErrorType::IndexOutOfRange { index, length } => vec![("index", index.to_string()), ("length", length.to_string())],
+            KaramelErrorType::ArgumentTypeMismatch { argument, expected, found } => vec![("argument", argument.to_string()), ("expected", expected.to_string()), ("found", found.to_string())],
+            KaramelErrorType::NotCallable(value) |
+            KaramelErrorType::IndexerMustBeNumber(value) |
+            KaramelErrorType::IndexerMustBeString(value) |
+            KaramelErrorType::DictionaryKeyNotHashable(value) |
+            KaramelErrorType::IndexerAssignmentNotSupported(value) |
+            KaramelErrorType::UncaughtError(value) => vec![("0", format!("{:?}", value))],
+            KaramelErrorType::LoopIterationLimitExceeded(limit) |
+            KaramelErrorType::InstructionLimitExceeded(limit) |
+            KaramelErrorType::SymbolTooLong(limit) |
+            KaramelErrorType::TextTooLong(limit) |
+            KaramelErrorType::ValueTooLarge(limit) |
+            KaramelErrorType::MemoryLimitExceeded(limit) |
+            KaramelErrorType::LineTooLong(limit) |
+            KaramelErrorType::TooManyConstants(limit) |
+            KaramelErrorType::TooManyVariables(limit) |
+            KaramelErrorType::TooManyArguments(limit) |
+            KaramelErrorType::TooManyItems(limit) |
+            KaramelErrorType::NestingTooDeep(limit) |
+            KaramelErrorType::StackOverflow(limit) |
+            KaramelErrorType::TaskNotFound(limit) |
+            KaramelErrorType::TaskDeadlock(limit) |
+            KaramelErrorType::ChannelNotFound(limit) |
+            KaramelErrorType::ChannelEmpty(limit) => vec![("0", limit.to_string())],
+            KaramelErrorType::FileNotFound(text) |
+            KaramelErrorType::UnknownTypeName(text) |
+            KaramelErrorType::GeneralError(text) |
+            KaramelErrorType::FunctionAlreadyDefined(text) |
+            KaramelErrorType::FunctionNotFound(text) |
+            KaramelErrorType::FunctionIsPrivate(text) |
+            KaramelErrorType::ImportNotFound(text) |
+            KaramelErrorType::DependencyNotFound(text) |
+            KaramelErrorType::DependencyConflict(text) |
+            KaramelErrorType::DependencyNotInstalled(text) |
+            KaramelErrorType::ReservedName(text) |
+            KaramelErrorType::FunctionNotFoundInStorage(text) |
+            KaramelErrorType::DisabledInTeachingMode(text) |
+            KaramelErrorType::VariableNotDefined(text) |
+            KaramelErrorType::VariableUsedBeforeAssignment(text) |
+            KaramelErrorType::InternalError(text) |
+            KaramelErrorType::InvalidNumberLiteral(text) |
+            KaramelErrorType::InvalidEscape(text) |
+            KaramelErrorType::AssumptionFailed(text) |
+            KaramelErrorType::TranspileNotSupported(text) |
+            KaramelErrorType::ForeignFunctionNotFound(text) |
+            KaramelErrorType::ForeignTypeNotSupported(text) |
+            KaramelErrorType::ConstantReassigned(text) |
+            KaramelErrorType::CloseFunctionNotFound(text) |
+            KaramelErrorType::EnumAlreadyDefined(text) |
+            KaramelErrorType::EnumVariantAlreadyDefined(text) |
+            KaramelErrorType::EnumVariantNotFound(text) => vec![("0", text.to_string())],
+            _ => Vec::new()
+        }
+    }
+}
+
+impl KaramelErrorType {
+    /// Message of the error in the given language.
+    pub fn message_in(&self, language: &str) -> String {
+        let (key, arguments) = match self {
+            KaramelErrorType::AssumptionFailed(message) if message.is_empty() => ("AssumptionFailed.empty".to_string(), Vec::new()),
+            KaramelErrorType::AssertFailedWithExpression { message, .. } if message.is_empty() => ("AssertFailedWithExpression.empty".to_string(), self.message_arguments()),
+            _ => (self.message_key(), self.message_arguments())
+        };
+        let arguments: Vec<(&str, &dyn fmt::Display)> = arguments.iter().map(|(name, value)| (*name, value as &dyn fmt::Display)).collect();
+        messages::format_in(language, &key, &arguments)
+    }
+}
+
+impl fmt::Display for KaramelErrorType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message_in(&messages::get_language()))
+    }
+}
+
+impl std::error::Error for KaramelErrorType {}
+
+fn write_message(f: &mut fmt::Formatter<'_>, key: &str, arguments: Vec<(&'static str, String)>) -> fmt::Result {
+    let arguments: Vec<(&str, &dyn fmt::Display)> = arguments.iter().map(|(name, value)| (*name, value as &dyn fmt::Display)).collect();
+    write!(f, "{}", messages::format(key, &arguments))
 }
 
 impl From<KaramelErrorType> for KaramelError {
@@ -288,11 +611,172 @@ impl KaramelError {
     }
 }
 
+#[cfg(feature = "vm")]
+/// Error of the running code. Line is found from the instruction that failed, the call stack is taken before the functions are closed.
+#[derive(Clone)]
+#[derive(Debug)]
+#[derive(PartialEq)]
+pub struct KaramelRuntimeError {
+    pub error_type: KaramelErrorType,
+
+    /// Zero based line of the failed instruction
+    pub line: Option<u32>,
+
+    /// Zero based start column of the failed statement
+    pub column: Option<u32>,
+
+    /// Main code is the first frame, the function that failed is the last one
+    pub call_stack: Vec<CallFrame>
+}
+
+#[cfg(feature = "vm")]
+impl KaramelRuntimeError {
+    pub fn new(error_type: KaramelErrorType, call_stack: Vec<CallFrame>) -> Self {
+        KaramelRuntimeError {
+            line: call_stack.last().and_then(|frame| frame.line),
+            column: call_stack.last().and_then(|frame| frame.column),
+            error_type,
+            call_stack
+        }
+    }
+
+    /// Message of the error in Turkish, it does not change with the selected language.
+    pub fn message(&self) -> String {
+        self.error_type.message_in(messages::DEFAULT_LANGUAGE)
+    }
+
+    /// Frames from the failed function to the main code, one frame at each line. Functions are written with their arguments.
+    /// Recursive calls from the same line are written once with the count of the others.
+    pub fn stack_trace(&self) -> String {
+        let main = messages::text("runtime.main_code");
+        let mut buffer = messages::text("runtime.call_stack");
+        let mut frames = self.call_stack.iter().rev().peekable();
+        while let Some(frame) = frames.next() {
+            let name = match &frame.function {
+                Some(function) => format!("{}({})", function, frame.arguments.iter().map(|(name, value)| format!("{}={}", name, value)).collect::<Vec<_>>().join(", ")),
+                None => main.to_string()
+            };
+
+            buffer.push('\n');
+            buffer.push_str(&match frame.line {
+                Some(line) => messages::format("runtime.frame", &[("0", &name), ("1", &(line + 1))]),
+                None => messages::format("runtime.frame_without_line", &[("0", &name)])
+            });
+
+            let mut repeated = 0;
+            while frames.next_if(|next| next.function.is_some() && next.function == frame.function && next.line == frame.line).is_some() {
+                repeated += 1;
+            }
+
+            if repeated > 0 {
+                buffer.push('\n');
+                buffer.push_str(&messages::format("runtime.frame_repeated", &[("0", &repeated)]));
+            }
+        }
+        buffer
+    }
+}
+
+#[cfg(feature = "vm")]
+impl fmt::Display for KaramelRuntimeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}\n{}", self.error_type, self.stack_trace())
+    }
+}
+
+#[cfg(feature = "vm")]
+impl std::error::Error for KaramelRuntimeError {}
+
+#[cfg(feature = "vm")]
+impl From<KaramelErrorType> for KaramelRuntimeError {
+    fn from(item: KaramelErrorType) -> Self {
+        KaramelRuntimeError::new(item, Vec::new())
+    }
+}
+
+#[cfg(feature = "vm")]
+impl From<KaramelRuntimeError> for KaramelError {
+    fn from(item: KaramelRuntimeError) -> Self {
+        KaramelError {
+            line: item.line.unwrap_or_default(),
+            column: item.column.unwrap_or_default(),
+            error_type: item.error_type
+        }
+    }
+}
+
+/// Non fatal issues that are found while compiling. Compilation continues after the warning.
+#[derive(Clone)]
+#[derive(Debug)]
+#[derive(PartialEq)]
+#[derive(EnumIter)]
+#[derive(EnumMessage)]
+#[derive(EnumDiscriminants)]
+pub enum KaramelWarningType {
+    #[strum(message = "U001")]
+    UnusedFunction(String),
+
+    #[strum(message = "U002")]
+    ImplicitNumberToText,
+
+    #[strum(message = "U003")]
+    ShadowedVariable(String)
+}
+
+impl KaramelWarningType {
+    pub fn message_key(&self) -> String {
+        format!("{:?}", KaramelWarningTypeDiscriminants::from(self))
+    }
+
+    pub fn teaching_hint(&self) -> Option<String> {
+        self.hint_key().map(messages::text)
+    }
+
+    fn hint_key(&self) -> Option<&'static str> {
+        match self {
+            KaramelWarningType::UnusedFunction(_) => Some("UnusedFunction.hint"),
+            KaramelWarningType::ImplicitNumberToText => Some("ImplicitNumberToText.hint"),
+            KaramelWarningType::ShadowedVariable(_) => Some("ShadowedVariable.hint")
+        }
+    }
+
+    fn message_arguments(&self) -> Vec<(&'static str, String)> {
+        match self {
+            KaramelWarningType::UnusedFunction(name) | KaramelWarningType::ShadowedVariable(name) => vec![("0", name.to_string())],
+            KaramelWarningType::ImplicitNumberToText => Vec::new()
+        }
+    }
+}
+
+impl fmt::Display for KaramelWarningType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_message(f, &self.message_key(), self.message_arguments())
+    }
+}
+
+impl std::error::Error for KaramelWarningType {}
+
+#[derive(Clone)]
+#[derive(Debug)]
+#[derive(PartialEq)]
+pub struct CompilerWarning {
+    pub warning_type: KaramelWarningType,
+    pub line: u32,
+    pub column: u32
+}
+
+impl CompilerWarning {
+    pub fn new(line: u32, column: u32, warning_type: KaramelWarningType) -> Self {
+        CompilerWarning { line, column, warning_type }
+    }
+}
+
+pub fn generate_warning_message<T: AsRef<str>, W: Borrow<CompilerWarning>>(data: T, warning: W) -> String {
+    Diagnostic::from(warning.borrow()).render(data)
+}
+
 pub fn generate_error_message<T: AsRef<str>, E: Borrow<KaramelError>>(data: T, error: E) -> String {
-    let error_ref = error.borrow();
-    let lines = data.as_ref().split(|c| c == '\n').collect::<Vec<_>>();
-    let line = lines.iter().skip(error_ref.line as usize).next().unwrap();
-    return format!("...\r\n{}\r\n{:>width$} [{}:{}] (#{}) {}", line, "^", error_ref.line, error_ref.column, error_ref.error_type.get_message().unwrap(), error_ref.error_type,  width=error_ref.column as usize);
+    Diagnostic::from(error.borrow()).render(data)
 }
 
 #[cfg(test)]
@@ -300,6 +784,7 @@ mod test {
     use strum::IntoEnumIterator;
     use strum::EnumMessage;
     use crate::error::KaramelErrorType;
+    use crate::messages;
 
     #[test]
     fn test_all_error_has_number() {
@@ -315,6 +800,45 @@ mod test {
             }
         }
     }
+    #[test]
+    fn test_all_warning_has_code() {
+        for warning_enum in super::KaramelWarningType::iter() {
+            assert!(warning_enum.get_message().is_some(), "'{:?}' uyari kodu yok", warning_enum);
+            assert!(warning_enum.teaching_hint().is_some(), "'{:?}' ipucu yok", warning_enum);
+        }
+    }
+
+    #[test]
+    fn test_all_messages_in_catalog() {
+        for error_enum in super::KaramelErrorType::iter() {
+            assert!(messages::has_message(&error_enum.message_key()), "'{:?}' mesaj katalogunda yok", error_enum);
+            if let Some(key) = error_enum.hint_key() {
+                assert!(messages::has_message(key), "'{}' mesaj katalogunda yok", key);
+            }
+        }
+
+        for warning_enum in super::KaramelWarningType::iter() {
+            assert!(messages::has_message(&warning_enum.message_key()), "'{:?}' mesaj katalogunda yok", warning_enum);
+            assert!(messages::has_message(warning_enum.hint_key().unwrap()), "'{:?}' ipucu mesaj katalogunda yok", warning_enum);
+        }
+    }
+
+    #[test]
+    fn test_english_error_message() {
+        let error = KaramelErrorType::FunctionArgumentNotMatching { function: "topla".to_string(), expected: 2, found: 1 };
+        assert_eq!(error.to_string(), "'topla' fonksiyon parametreleri eşleşmiyor. 2 adet beklenirken 1 adet bulundu");
+        assert_eq!(messages::format_in(messages::ENGLISH_LANGUAGE, &error.message_key(), &[("function", &"topla"), ("expected", &2), ("found", &1)]),
+            "'topla' function arguments do not match. Expected 2 but found 1");
+    }
+
+    #[test]
+    fn test_warning_message_generator() {
+        let warning = super::CompilerWarning::new(1, 4, super::KaramelWarningType::ImplicitNumberToText);
+        let message = super::generate_warning_message("a = 1\nb = 'a' + 1", &warning);
+        assert!(message.contains("b = 'a' + 1"));
+        assert!(message.contains("U002"));
+    }
+
     #[test]
     fn test_error_message_generator() {
         let error_info = super::KaramelError {
@@ -0,0 +1,116 @@
+use crate::error::KaramelErrorType;
+
+/// Encodings of the source files. Old Turkish files are usually saved as Windows-1254 or ISO-8859-9, they are
+/// converted to UTF-8 before the tokenizer reads them.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SourceEncoding {
+    Utf8,
+    Windows1254,
+    Iso8859_9,
+
+    /// Files that are not UTF-8 are converted with the detected encoding
+    Detect
+}
+
+/* Characters of the 0x80-0x9F bytes in Windows-1254. Undefined bytes are kept as the control characters of ISO-8859-9. */
+const WINDOWS_1254_EXTRA: [char; 32] = [
+    '\u{20AC}', '\u{0081}', '\u{201A}', '\u{0192}', '\u{201E}', '\u{2026}', '\u{2020}', '\u{2021}',
+    '\u{02C6}', '\u{2030}', '\u{0160}', '\u{2039}', '\u{0152}', '\u{008D}', '\u{008E}', '\u{008F}',
+    '\u{0090}', '\u{2018}', '\u{2019}', '\u{201C}', '\u{201D}', '\u{2022}', '\u{2013}', '\u{2014}',
+    '\u{02DC}', '\u{2122}', '\u{0161}', '\u{203A}', '\u{0153}', '\u{009D}', '\u{009E}', '\u{0178}'
+];
+
+impl SourceEncoding {
+    pub fn from_name(name: &str) -> Option<SourceEncoding> {
+        match &name.to_lowercase()[..] {
+            "utf-8" | "utf8" => Some(SourceEncoding::Utf8),
+            "windows-1254" | "cp1254" => Some(SourceEncoding::Windows1254),
+            "iso-8859-9" | "latin5" => Some(SourceEncoding::Iso8859_9),
+            "otomatik" => Some(SourceEncoding::Detect),
+            _ => None
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            SourceEncoding::Utf8 => "utf-8",
+            SourceEncoding::Windows1254 => "windows-1254",
+            SourceEncoding::Iso8859_9 => "iso-8859-9",
+            SourceEncoding::Detect => "otomatik"
+        }
+    }
+
+    /// Bytes that are not UTF-8 are guessed from the 0x80-0x9F bytes. Only Windows-1254 has characters there,
+    /// both encodings give the same text without them.
+    pub fn detect(bytes: &[u8]) -> SourceEncoding {
+        match std::str::from_utf8(bytes) {
+            Ok(_) => SourceEncoding::Utf8,
+            Err(_) if bytes.iter().any(|byte| (0x80..0xA0).contains(byte)) => SourceEncoding::Windows1254,
+            Err(_) => SourceEncoding::Iso8859_9
+        }
+    }
+
+    fn decode_byte(&self, byte: u8) -> char {
+        match byte {
+            0x80..=0x9F if *self == SourceEncoding::Windows1254 => WINDOWS_1254_EXTRA[(byte - 0x80) as usize],
+            0xD0 => 'Ğ',
+            0xDD => 'İ',
+            0xDE => 'Ş',
+            0xF0 => 'ğ',
+            0xFD => 'ı',
+            0xFE => 'ş',
+            _ => byte as char
+        }
+    }
+}
+
+/// Converts the file content to text. UTF-8 is expected when the encoding is not given, the error names the
+/// detected encoding so the file can be run again with it.
+pub fn decode_source(file: &str, bytes: Vec<u8>, encoding: Option<SourceEncoding>) -> Result<String, KaramelErrorType> {
+    let encoding = match encoding {
+        Some(SourceEncoding::Detect) => SourceEncoding::detect(&bytes),
+        Some(encoding) => encoding,
+        None => SourceEncoding::Utf8
+    };
+
+    match encoding {
+        SourceEncoding::Utf8 => String::from_utf8(bytes).map_err(|error| KaramelErrorType::InvalidSourceEncoding {
+            file: file.to_string(),
+            encoding: SourceEncoding::detect(error.as_bytes()).name().to_string()
+        }),
+        _ => Ok(bytes.iter().map(|byte| encoding.decode_byte(*byte)).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decode_turkish_letters() {
+        /* "ığüşöç İĞÜŞÖÇ" */
+        let bytes = vec![0xFD, 0xF0, 0xFC, 0xFE, 0xF6, 0xE7, 0x20, 0xDD, 0xD0, 0xDC, 0xDE, 0xD6, 0xC7];
+        assert_eq!(decode_source("a.k", bytes.clone(), Some(SourceEncoding::Windows1254)).unwrap(), "ığüşöç İĞÜŞÖÇ");
+        assert_eq!(decode_source("a.k", bytes, Some(SourceEncoding::Iso8859_9)).unwrap(), "ığüşöç İĞÜŞÖÇ");
+        assert_eq!(decode_source("a.k", vec![0x93, 0x80, 0x94], Some(SourceEncoding::Windows1254)).unwrap(), "“€”");
+    }
+
+    #[test]
+    fn detect_encoding() {
+        assert_eq!(SourceEncoding::detect("ığüşöç".as_bytes()), SourceEncoding::Utf8);
+        assert_eq!(SourceEncoding::detect(&[0x93, 0xFD, 0x94]), SourceEncoding::Windows1254);
+        assert_eq!(SourceEncoding::detect(&[0xFD, 0xF0]), SourceEncoding::Iso8859_9);
+        assert_eq!(decode_source("a.k", vec![0x61, 0xFD], Some(SourceEncoding::Detect)).unwrap(), "aı");
+        assert_eq!(decode_source("a.k", "aı".as_bytes().to_vec(), Some(SourceEncoding::Detect)).unwrap(), "aı");
+    }
+
+    #[test]
+    fn invalid_utf8() {
+        assert_eq!(decode_source("a.k", vec![0x93, 0xFD, 0x94], None), Err(KaramelErrorType::InvalidSourceEncoding {
+            file: "a.k".to_string(),
+            encoding: "windows-1254".to_string()
+        }));
+        assert_eq!(SourceEncoding::from_name("CP1254"), Some(SourceEncoding::Windows1254));
+        assert_eq!(SourceEncoding::from_name("ascii"), None);
+    }
+}
@@ -1,20 +1,142 @@
-use std::{borrow::Borrow, fs::File};
-use std::io::prelude::*;
-use std::path::Path;
-use std::fs::canonicalize;
+pub mod encoding;
+
+use std::borrow::Borrow;
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
 
 use crate::compiler::KaramelCompilerContext;
 use crate::constants::{KARAMEL_FILE_EXTENSION, STARTUP_MODULE_NAME};
 use crate::error::KaramelErrorType;
+use self::encoding::{SourceEncoding, decode_source};
+
+/// Files that are used by 'yükle' and the 'dosya' module. Embedders can give their own implementation to the execution, the playground keeps the files in the memory.
+pub trait FileSystem {
+    fn read(&self, path: &str) -> Result<String, KaramelErrorType>;
+    fn write(&self, path: &str, content: &str) -> Result<(), KaramelErrorType>;
+
+    /// Names of the files and directories in the directory, sorted.
+    fn list(&self, path: &str) -> Result<Vec<String>, KaramelErrorType>;
+    fn is_file(&self, path: &str) -> bool;
+}
+
+/// Files of the operating system. Used by default. Files are read as UTF-8 when the encoding is not given.
+#[derive(Default)]
+pub struct OsFileSystem {
+    pub encoding: Option<SourceEncoding>
+}
+
+impl OsFileSystem {
+    pub fn with_encoding(encoding: SourceEncoding) -> Self {
+        OsFileSystem { encoding: Some(encoding) }
+    }
+}
+
+impl FileSystem for OsFileSystem {
+    fn read(&self, path: &str) -> Result<String, KaramelErrorType> {
+        read_encoded_file(path, self.encoding)
+    }
+
+    fn write(&self, path: &str, content: &str) -> Result<(), KaramelErrorType> {
+        fs::write(path, content).map_err(|error| KaramelErrorType::FileWriteError {
+            filename: path.to_string(),
+            error: error.to_string()
+        })
+    }
+
+    fn list(&self, path: &str) -> Result<Vec<String>, KaramelErrorType> {
+        let entries = fs::read_dir(path).map_err(|error| KaramelErrorType::FileReadError {
+            filename: path.to_string(),
+            error: error.to_string()
+        })?;
+
+        let mut names: Vec<String> = entries.filter_map(|entry| entry.ok()).map(|entry| entry.file_name().to_string_lossy().to_string()).collect();
+        names.sort();
+        Ok(names)
+    }
+
+    fn is_file(&self, path: &str) -> bool {
+        Path::new(path).is_file()
+    }
+}
+
+/// Files kept in the memory. Paths are normalized, 'a/./b.k' and 'a/c/../b.k' are the same file.
+#[derive(Default)]
+pub struct MemoryFileSystem {
+    files: RefCell<BTreeMap<String, String>>
+}
+
+impl MemoryFileSystem {
+    pub fn new() -> Self {
+        MemoryFileSystem::default()
+    }
+
+    pub fn add_file<T: Borrow<str>>(&self, path: T, content: T) {
+        self.files.borrow_mut().insert(normalize_path(path.borrow()), content.borrow().to_string());
+    }
+}
+
+impl FileSystem for MemoryFileSystem {
+    fn read(&self, path: &str) -> Result<String, KaramelErrorType> {
+        match self.files.borrow().get(&normalize_path(path)) {
+            Some(content) => Ok(content.to_string()),
+            None => Err(KaramelErrorType::FileNotFound(path.to_string()))
+        }
+    }
+
+    fn write(&self, path: &str, content: &str) -> Result<(), KaramelErrorType> {
+        self.add_file(path, content);
+        Ok(())
+    }
+
+    fn list(&self, path: &str) -> Result<Vec<String>, KaramelErrorType> {
+        let directory = normalize_path(path);
+        let prefix = match directory.is_empty() {
+            true => String::new(),
+            false => format!("{}/", directory)
+        };
+
+        let mut names: Vec<String> = self.files.borrow().keys()
+            .filter_map(|file| file.strip_prefix(&prefix[..]))
+            .filter_map(|name| name.split('/').next())
+            .map(|name| name.to_string())
+            .collect();
+        names.dedup();
+
+        match names.is_empty() && !prefix.is_empty() {
+            true => Err(KaramelErrorType::FileNotFound(path.to_string())),
+            false => Ok(names)
+        }
+    }
+
+    fn is_file(&self, path: &str) -> bool {
+        self.files.borrow().contains_key(&normalize_path(path))
+    }
+}
+
+/* Removes the '.' parts and resolves the '..' parts without touching the disk */
+fn normalize_path(path: &str) -> String {
+    let mut parts: Vec<String> = Vec::new();
+    for component in Path::new(path).components() {
+        match component {
+            Component::ParentDir => { parts.pop(); },
+            Component::Normal(part) => parts.push(part.to_string_lossy().to_string()),
+            _ => ()
+        }
+    }
+    parts.join("/")
+}
 
 pub fn read_file<T: Borrow<str>>(file_name: T) -> Result<String, KaramelErrorType> {
-    match File::open(file_name.borrow()) {
-        Ok(mut file) => {
-            let mut contents = String::new();
-            file.read_to_string(&mut contents).unwrap();
-            Ok(contents)
-        },
-        Err(error) => return Err(KaramelErrorType::FileReadError {
+    read_encoded_file(file_name, None)
+}
+
+/// Reads the file and converts it from the given encoding. Content must be UTF-8 when the encoding is not given.
+pub fn read_encoded_file<T: Borrow<str>>(file_name: T, encoding: Option<SourceEncoding>) -> Result<String, KaramelErrorType> {
+    match fs::read(file_name.borrow()) {
+        Ok(contents) => decode_source(file_name.borrow(), contents, encoding),
+        Err(error) => Err(KaramelErrorType::FileReadError {
             filename: file_name.borrow().to_owned(),
             error: error.to_string()
         })
@@ -22,24 +144,19 @@ pub fn read_file<T: Borrow<str>>(file_name: T) -> Result<String, KaramelErrorTyp
 }
 
 fn read_script<T: Borrow<str>>(file_name: T, context: &KaramelCompilerContext) -> Result<String, KaramelErrorType> {
-    let path = Path::new(file_name.borrow());
-
-    if path.exists() && path.is_file() {
-        return read_file(file_name);
+    if context.file_system.is_file(file_name.borrow()) {
+        return context.file_system.read(file_name.borrow());
     } 
 
     let script_path = Path::new(&context.execution_path.path);
     let calculated_path = script_path.join(Path::new(file_name.borrow()));
     
-    match canonicalize(&calculated_path) {
-        Ok(path) => match path.exists() && path.is_file() {
-            true => return read_file(path.to_str().unwrap()),
-            false => match calculated_path.to_str() {
-                Some(filename) => Err(KaramelErrorType::FileNotFound(filename.to_string())),
-                None => Err(KaramelErrorType::GeneralError("Dosya bulunamadi.".to_string()))
-            },
+    match calculated_path.to_str() {
+        Some(filename) => match context.file_system.is_file(filename) {
+            true => context.file_system.read(filename),
+            false => Err(KaramelErrorType::FileNotFound(filename.to_string()))
         },
-        Err(error) => Err(KaramelErrorType::GeneralError(format!("Dosya yolu okunurken hata ile karsilasildi. Hata bilgisi: {}", error)))
+        None => Err(KaramelErrorType::GeneralError("Dosya bulunamadi.".to_string()))
     }
 }
 
@@ -54,19 +171,18 @@ pub fn read_module_or_script<T: Borrow<str>>(file_name: T, context: &KaramelComp
         Err(_) => ()
     };
 
-    let script_path = Path::new(&context.execution_path.path);
-    let calculated_path = script_path.join(Path::new(file_name.borrow()));
-    
-    match canonicalize(&calculated_path) {
-        Ok(path) => match path.exists() && path.is_file() {
-            true => return read_file(path.to_str().unwrap()),
-            false => (),
-        },
-        Err(_) => ()
-    };
+    /* Directories are loaded with their startup module */
+    let file_path = PathBuf::from(file_name.borrow());
+    let calculated_path = Path::new(&context.execution_path.path).join(&file_path);
+    let candidates = [calculated_path.clone(), file_path.join(STARTUP_MODULE_NAME), calculated_path.join(STARTUP_MODULE_NAME)];
 
-    match canonicalize(calculated_path.join(STARTUP_MODULE_NAME)) {
-        Ok(path) => return read_file(path.to_str().unwrap()),
-        Err(error) => Err(KaramelErrorType::GeneralError(format!("Dosya yolu okunurken hata ile karsilasildi. Hata bilgisi: {}", error)))
+    for path in candidates.iter() {
+        if let Some(path) = path.to_str() {
+            if context.file_system.is_file(path) {
+                return context.file_system.read(path);
+            }
+        }
     }
-}
\ No newline at end of file
+
+    Err(KaramelErrorType::FileNotFound(file_name.borrow().to_string()))
+}
@@ -0,0 +1,388 @@
+use std::borrow::Borrow;
+
+use crate::error::KaramelError;
+use crate::parser::{Parser, script_start};
+use crate::syntax::SyntaxParser;
+use crate::types::*;
+use crate::compiler::value::KaramelPrimative;
+
+pub const INDENTATION_SIZE: usize = 4;
+
+struct SourceLines {
+    lines: Vec<Vec<char>>
+}
+
+impl SourceLines {
+    fn new(source: &str) -> SourceLines {
+        SourceLines {
+            lines: source.split('\n').map(|line| line.chars().collect()).collect()
+        }
+    }
+
+    fn slice(&self, token: &Token) -> Option<String> {
+        let line = self.lines.get(token.line as usize)?;
+        let (start, end) = (token.start as usize, token.end as usize);
+        match start < end && end <= line.len() {
+            true => Some(line[start..end].iter().collect()),
+            false => None
+        }
+    }
+
+    /* Triple quoted texts may continue in the next lines, so the literal is read until the closing quotes */
+    fn text_literal(&self, token: &Token) -> Option<String> {
+        let line = self.lines.get(token.line as usize)?;
+        let start = token.start as usize;
+        let tag = *line.get(start)?;
+        if line.get(start + 1) != Some(&tag) || line.get(start + 2) != Some(&tag) {
+            return self.slice(token);
+        }
+
+        let source = self.lines[token.line as usize..].join(&'\n');
+        let chars = &source[start..];
+        let mut index = 3;
+        while index + 2 < chars.len() {
+            match chars[index] {
+                '\\' => index += 2,
+                ch if ch == tag && chars[index + 1] == tag && chars[index + 2] == tag => return Some(chars[..index + 3].iter().collect()),
+                _ => index += 1
+            };
+        }
+        None
+    }
+}
+
+struct FormatLine<'a> {
+    indentation: usize,
+    tokens: Vec<&'a Token>
+}
+
+fn keyword_text(keyword: KaramelKeywordType) -> &'static str {
+    match KEYWORDS.iter().find(|(_, keyword_type)| *keyword_type == keyword) {
+        Some((text, _)) => text,
+        None => ""
+    }
+}
+
+fn operator_text(operator: KaramelOperatorType) -> &'static str {
+    match operator {
+        KaramelOperatorType::Addition             => "+",
+        KaramelOperatorType::Subtraction          => "-",
+        KaramelOperatorType::Multiplication       => "*",
+        KaramelOperatorType::Division             => "/",
+        KaramelOperatorType::Modulo               => keyword_text(KaramelKeywordType::Modulo),
+        KaramelOperatorType::Increment            => "++",
+        KaramelOperatorType::Deccrement           => "--",
+        KaramelOperatorType::Assign               => "=",
+        KaramelOperatorType::AssignAddition       => "+=",
+        KaramelOperatorType::AssignSubtraction    => "-=",
+        KaramelOperatorType::AssignMultiplication => "*=",
+        KaramelOperatorType::AssignDivision       => "/=",
+        KaramelOperatorType::Equal                => "==",
+        KaramelOperatorType::NotEqual             => "!=",
+        KaramelOperatorType::Not                  => "!",
+        KaramelOperatorType::And                  => keyword_text(KaramelKeywordType::And),
+        KaramelOperatorType::Or                   => keyword_text(KaramelKeywordType::Or),
+        KaramelOperatorType::GreaterThan          => ">",
+        KaramelOperatorType::LessThan             => "<",
+        KaramelOperatorType::GreaterEqualThan     => ">=",
+        KaramelOperatorType::LessEqualThan        => "<=",
+        KaramelOperatorType::QuestionMark         => "?",
+        KaramelOperatorType::ColonMark            => ":",
+        KaramelOperatorType::LeftParentheses      => "(",
+        KaramelOperatorType::RightParentheses     => ")",
+        KaramelOperatorType::SquareBracketStart   => "[",
+        KaramelOperatorType::SquareBracketEnd     => "]",
+        KaramelOperatorType::Comma                => ",",
+        KaramelOperatorType::Semicolon            => ";",
+        KaramelOperatorType::Dot                  => ".",
+        KaramelOperatorType::CommentLine          => "//",
+        KaramelOperatorType::CommentMultilineStart=> "/*",
+        KaramelOperatorType::CommentMultilineEnd  => "*/",
+        KaramelOperatorType::CurveBracketStart    => "{",
+        KaramelOperatorType::CurveBracketEnd      => "}",
+        KaramelOperatorType::At                   => "@",
+        KaramelOperatorType::None                 => ""
+    }
+}
+
+fn is_operator(token: Option<&&Token>, operators: &[KaramelOperatorType]) -> bool {
+    match token.map(|token| &token.token_type) {
+        Some(KaramelTokenType::Operator(operator)) => operators.contains(operator),
+        _ => false
+    }
+}
+
+/* Tokens that end an operand. A '(' or '[' right after them is a call or an indexer. */
+fn is_operand_end(token: Option<&&Token>) -> bool {
+    match token.map(|token| &token.token_type) {
+        Some(KaramelTokenType::Symbol(_)) | Some(KaramelTokenType::Text(_)) | Some(KaramelTokenType::Bytes(_)) |
+        Some(KaramelTokenType::Integer(_)) | Some(KaramelTokenType::Double(_)) => true,
+        Some(KaramelTokenType::Keyword(keyword)) => matches!(keyword, KaramelKeywordType::True | KaramelKeywordType::False | KaramelKeywordType::Empty),
+        Some(KaramelTokenType::Operator(operator)) => matches!(operator, KaramelOperatorType::RightParentheses | KaramelOperatorType::SquareBracketEnd | KaramelOperatorType::CurveBracketEnd | KaramelOperatorType::Increment | KaramelOperatorType::Deccrement),
+        _ => false
+    }
+}
+
+struct Formatter<'a> {
+    source: SourceLines,
+    tokens: &'a [Token],
+    output: String
+}
+
+impl<'a> Formatter<'a> {
+    fn token_text(&self, token: &Token) -> String {
+        match &token.token_type {
+            KaramelTokenType::Integer(number) => self.source.slice(token).unwrap_or_else(|| number.to_string()),
+            KaramelTokenType::Double(number) => self.source.slice(token).unwrap_or_else(|| number.to_string()),
+            KaramelTokenType::Symbol(symbol) => symbol.to_string(),
+            KaramelTokenType::Keyword(keyword) => keyword_text(*keyword).to_string(),
+            KaramelTokenType::Comment(comment) => comment.to_string(),
+            KaramelTokenType::DocComment(documentation) => format!("## {}", documentation).trim_end().to_string(),
+            KaramelTokenType::Text(text) => {
+                /* The tokenizer drops the quote characters and resolves the escapes, so the original text is taken from the source */
+                match self.source.text_literal(token) {
+                    Some(slice) if slice.starts_with(['\'', '"']) => slice,
+                    _ => format!("'{}'", text)
+                }
+            },
+            KaramelTokenType::Bytes(bytes) => match self.source.slice(token) {
+                Some(slice) if slice.starts_with('b') => slice,
+                _ => KaramelPrimative::Bytes(bytes.clone()).to_string()
+            },
+            KaramelTokenType::Operator(KaramelOperatorType::Not) => match self.source.slice(token) {
+                Some(slice) if slice.starts_with('!') => "!".to_string(),
+                _ => keyword_text(KaramelKeywordType::Not).to_string()
+            },
+            KaramelTokenType::Operator(operator) => operator_text(*operator).to_string(),
+            KaramelTokenType::WhiteSpace(_) | KaramelTokenType::NewLine(_) => String::new()
+        }
+    }
+
+    fn split_lines(&self) -> Vec<FormatLine<'a>> {
+        let mut lines = Vec::new();
+        let mut current = FormatLine { indentation: 0, tokens: Vec::new() };
+
+        for token in self.tokens.iter() {
+            match token.token_type {
+                KaramelTokenType::NewLine(size) => {
+                    lines.push(current);
+                    current = FormatLine { indentation: size as usize, tokens: Vec::new() };
+                },
+                KaramelTokenType::WhiteSpace(size) => {
+                    if current.tokens.is_empty() {
+                        current.indentation += size as usize;
+                    }
+                },
+                _ => current.tokens.push(token)
+            };
+        }
+
+        lines.push(current);
+        lines
+    }
+
+    fn is_unary(&self, previous: Option<&&Token>, token: &Token) -> bool {
+        match token.token_type {
+            KaramelTokenType::Operator(KaramelOperatorType::Subtraction) |
+            KaramelTokenType::Operator(KaramelOperatorType::Addition) |
+            KaramelTokenType::Operator(KaramelOperatorType::Increment) |
+            KaramelTokenType::Operator(KaramelOperatorType::Deccrement) => !is_operand_end(previous),
+            KaramelTokenType::Operator(KaramelOperatorType::Not) => self.token_text(token) == "!",
+            _ => false
+        }
+    }
+
+    fn need_space(&self, previous: Option<&&Token>, token: &Token, attached: bool) -> bool {
+        use KaramelOperatorType::*;
+
+        if previous.is_none() || attached {
+            return false;
+        }
+
+        if let KaramelTokenType::Comment(_) | KaramelTokenType::DocComment(_) = token.token_type {
+            return true;
+        }
+
+        match token.token_type {
+            KaramelTokenType::Operator(Comma) | KaramelTokenType::Operator(RightParentheses) |
+            KaramelTokenType::Operator(SquareBracketEnd) | KaramelTokenType::Operator(ColonMark) |
+            KaramelTokenType::Operator(Dot) | KaramelTokenType::Operator(Semicolon) |
+            KaramelTokenType::Operator(CurveBracketEnd) => return false,
+            KaramelTokenType::Operator(LeftParentheses) | KaramelTokenType::Operator(SquareBracketStart) => return !is_operand_end(previous),
+            KaramelTokenType::Operator(Increment) | KaramelTokenType::Operator(Deccrement) => return !is_operand_end(previous),
+            _ => ()
+        };
+
+        !is_operator(previous, &[LeftParentheses, SquareBracketStart, CurveBracketStart, Dot, At])
+    }
+
+    fn write_line(&mut self, line: &FormatLine, level: usize) {
+        let mut text = " ".repeat(level * INDENTATION_SIZE);
+        let mut attached = false;
+
+        for (index, token) in line.tokens.iter().enumerate() {
+            let previous = match index {
+                0 => None,
+                _ => line.tokens.get(index - 1)
+            };
+
+            /* '::' module access is written without spaces */
+            let module_access = is_operator(previous, &[KaramelOperatorType::ColonMark]) && index > 1 && is_operator(line.tokens.get(index - 2), &[KaramelOperatorType::ColonMark]);
+            if self.need_space(previous, token, attached || module_access) {
+                text.push(' ');
+            }
+
+            text.push_str(&self.token_text(token));
+            attached = self.is_unary(previous, token) || is_operator(Some(token), &[KaramelOperatorType::ColonMark]) && is_operator(line.tokens.get(index + 1), &[KaramelOperatorType::ColonMark]);
+        }
+
+        self.output.push_str(text.trim_end());
+        self.output.push('\n');
+    }
+
+    fn bracket_depth(line: &FormatLine) -> i32 {
+        line.tokens.iter().fold(0, |depth, token| match token.token_type {
+            KaramelTokenType::Operator(KaramelOperatorType::LeftParentheses) |
+            KaramelTokenType::Operator(KaramelOperatorType::SquareBracketStart) |
+            KaramelTokenType::Operator(KaramelOperatorType::CurveBracketStart) => depth + 1,
+            KaramelTokenType::Operator(KaramelOperatorType::RightParentheses) |
+            KaramelTokenType::Operator(KaramelOperatorType::SquareBracketEnd) |
+            KaramelTokenType::Operator(KaramelOperatorType::CurveBracketEnd) => depth - 1,
+            _ => depth
+        })
+    }
+
+    fn format(mut self) -> String {
+        let lines = self.split_lines();
+        let mut indentations: Vec<usize> = vec![0];
+        let mut depth = 0;
+        let mut blank_line = false;
+
+        for line in lines.iter() {
+            if line.tokens.is_empty() {
+                blank_line = !self.output.is_empty();
+                continue;
+            }
+
+            if blank_line {
+                self.output.push('\n');
+                blank_line = false;
+            }
+
+            /* Lines inside of an open bracket are continuation lines and do not change the block level */
+            let level = match depth > 0 {
+                true => indentations.len(),
+                false => {
+                    while indentations.len() > 1 && *indentations.last().unwrap() > line.indentation {
+                        indentations.pop();
+                    }
+
+                    if *indentations.last().unwrap() < line.indentation {
+                        indentations.push(line.indentation);
+                    }
+                    indentations.len() - 1
+                }
+            };
+
+            self.write_line(line, level);
+            depth = i32::max(0, depth + Self::bracket_depth(line));
+        }
+
+        self.output
+    }
+}
+
+/// Parses the script and pretty prints it with canonical indentation, operator spacing and keyword spelling. Comments are kept.
+pub fn format_code<T: Borrow<str>>(source: T) -> Result<String, KaramelError> {
+    /* Byte order mark is removed, columns of the tokens are counted after it */
+    let source = source.borrow().trim_start_matches('\u{feff}');
+
+    /* Formatting is only allowed for valid scripts */
+    let mut parser = Parser::new(source);
+    parser.parse()?;
+    SyntaxParser::new(parser.tokens()).parse()?;
+
+    let mut parser = Parser::with_comments(source);
+    parser.parse()?;
+    let tokens = parser.tokens();
+
+    let formatter = Formatter {
+        source: SourceLines::new(source),
+        tokens: &tokens,
+        output: String::new()
+    };
+
+    /* '#!' line is not tokenized, it is written back as it is */
+    let shebang = &source[..script_start(source) as usize];
+    Ok(match shebang.is_empty() {
+        true => formatter.format(),
+        false => format!("{}\n{}", shebang, formatter.format())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::format_code;
+
+    #[test]
+    fn format_operators() {
+        assert_eq!(format_code("a=1+2*3").unwrap(), "a = 1 + 2 * 3\n");
+        assert_eq!(format_code("a   =  -1").unwrap(), "a = -1\n");
+        assert_eq!(format_code("a = [1,2 ,3]").unwrap(), "a = [1, 2, 3]\n");
+        assert_eq!(format_code("a = {'a':1}").unwrap(), "a = {'a': 1}\n");
+    }
+
+    #[test]
+    fn format_shebang() {
+        assert_eq!(format_code("#!/usr/bin/env karamelapp\na=1").unwrap(), "#!/usr/bin/env karamelapp\na = 1\n");
+        assert_eq!(format_code("\u{feff}a=1").unwrap(), "a = 1\n");
+    }
+
+    #[test]
+    fn format_keywords() {
+        assert_eq!(format_code("a = degil dogru").unwrap(), "a = değil doğru\n");
+        assert_eq!(format_code("a = dogru ve yanlis").unwrap(), "a = doğru ve yanlış\n");
+        assert_eq!(format_code("a = 10 mod 3").unwrap(), "a = 10 mod 3\n");
+    }
+
+    #[test]
+    fn format_calls() {
+        assert_eq!(format_code("gç::satıryaz ( \"merhaba\" )").unwrap(), "gç::satıryaz(\"merhaba\")\n");
+        assert_eq!(format_code("a = 'merhaba'.uzunluk()").unwrap(), "a = 'merhaba'.uzunluk()\n");
+        assert_eq!(format_code("@ duyur\nfonk f():\n  döndür").unwrap(), "@duyur\nfonk f():\n    döndür\n");
+    }
+
+    #[test]
+    fn format_blocks() {
+        let source = "fonk topla(a,b):\n  dondur a+b\n\n\n\nkayit = 0x10\ndongu i=0,i<10,i++:\n  kayit++\n  i==5 ise:\n        kir\n";
+        let expected = "fonk topla(a, b):\n    döndür a + b\n\nkayit = 0x10\ndöngü i = 0, i < 10, i++:\n    kayit++\n    i == 5 ise:\n        kır\n";
+        assert_eq!(format_code(source).unwrap(), expected);
+    }
+
+    #[test]
+    fn format_comments() {
+        let source = "// açıklama\na=1 // satır sonu\n/* çoklu\n   satır */\nb=2";
+        let expected = "// açıklama\na = 1 // satır sonu\n/* çoklu\n   satır */\nb = 2\n";
+        assert_eq!(format_code(source).unwrap(), expected);
+        assert_eq!(format_code("##toplar\nfonk topla(a,b):\n  dondur a+b").unwrap(), "## toplar\nfonk topla(a, b):\n    döndür a + b\n");
+    }
+
+    #[test]
+    fn format_multi_line_text() {
+        let source = "a='''satır 1\n  satır \\'2\\''''\nb=\"\"\"\n\"\"\"+a";
+        let expected = "a = '''satır 1\n  satır \\'2\\''''\nb = \"\"\"\n\"\"\" + a\n";
+        assert_eq!(format_code(source).unwrap(), expected);
+    }
+
+    #[test]
+    fn format_is_stable() {
+        let source = "fonk test(a):\n    a > 10 ise:\n        döndür değil a\n    veya:\n        döndür a - 1\n";
+        let formatted = format_code(source).unwrap();
+        assert_eq!(format_code(formatted.clone()).unwrap(), formatted);
+    }
+
+    #[test]
+    fn format_invalid_code() {
+        assert!(format_code("a = (1 + ").is_err());
+    }
+}
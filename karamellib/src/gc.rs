@@ -0,0 +1,193 @@
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+use std::mem::ManuallyDrop;
+use std::rc::Rc;
+
+use crate::compiler::value::KaramelPrimative;
+use crate::constants::KARAMEL_GC_THRESHOLD;
+use crate::types::{VmObject, QNAN, POINTER_FLAG, POINTER_MASK};
+
+/* Heap values of the vm. Values are kept with 'Rc::into_raw' and 'VmObject' does not release them, so every conversion is counted here. */
+struct Heap {
+    /* Pointer and the count of the references given to the vm */
+    objects: HashMap<usize, usize>,
+    allocated: usize,
+    threshold: usize,
+    collections: usize,
+
+    /* Nested vm instances are running, values of the outer instances are not in their roots */
+    paused: usize
+}
+
+thread_local! {
+    static HEAP: RefCell<Heap> = RefCell::new(Heap {
+        objects: HashMap::new(),
+        allocated: 0,
+        threshold: KARAMEL_GC_THRESHOLD,
+        collections: 0,
+        paused: 0
+    });
+}
+
+#[inline]
+fn pointer(object: VmObject) -> Option<usize> {
+    match object.0 & QNAN == QNAN && object.0 & POINTER_FLAG == POINTER_FLAG {
+        true => Some((object.0 & POINTER_MASK) as usize),
+        false => None
+    }
+}
+
+/// Counts the reference that is given to the vm with 'Rc::into_raw'.
+#[inline]
+pub fn track(object: VmObject) {
+    if let Some(pointer) = pointer(object) {
+        HEAP.with(|heap| {
+            let mut heap = heap.borrow_mut();
+            *heap.objects.entry(pointer).or_insert(0) += 1;
+            heap.allocated += 1;
+        });
+    }
+}
+
+/// Forgets the values of the previous execution and sets the count of the allocations between the collections.
+pub fn reset(threshold: usize) {
+    HEAP.with(|heap| {
+        let mut heap = heap.borrow_mut();
+        heap.objects.clear();
+        heap.allocated = 0;
+        heap.threshold = threshold.max(1);
+        heap.collections = 0;
+        heap.paused = 0;
+    });
+}
+
+/// Collections are skipped until 'resume' is called. Used while a vm is running inside the native function of another vm.
+pub fn pause() {
+    HEAP.with(|heap| heap.borrow_mut().paused += 1);
+}
+
+pub fn resume() {
+    HEAP.with(|heap| {
+        let mut heap = heap.borrow_mut();
+        heap.paused = heap.paused.saturating_sub(1);
+    });
+}
+
+/// Enough values are allocated since the last collection.
+#[inline]
+pub fn should_collect() -> bool {
+    HEAP.with(|heap| {
+        let heap = heap.borrow();
+        heap.paused == 0 && heap.allocated >= heap.threshold
+    })
+}
+
+pub fn tracked_count() -> usize {
+    HEAP.with(|heap| heap.borrow().objects.len())
+}
+
+pub fn collection_count() -> usize {
+    HEAP.with(|heap| heap.borrow().collections)
+}
+
+fn children(primative: &KaramelPrimative, items: &mut Vec<VmObject>) {
+    match primative {
+        KaramelPrimative::List(list) | KaramelPrimative::Stack(list) => items.extend(list.borrow().iter()),
+        KaramelPrimative::Dict(dict) => items.extend(dict.borrow().values()),
+        KaramelPrimative::Frozen(inner) => items.push(*inner),
+        KaramelPrimative::SortedDict(dict) => items.extend(dict.borrow().values()),
+        KaramelPrimative::Queue(queue) => items.extend(queue.borrow().iter()),
+        #[cfg(feature = "vm")]
+        KaramelPrimative::PriorityQueue(queue) => items.extend(queue.borrow().values()),
+        #[cfg(feature = "vm")]
+        KaramelPrimative::Function(_, Some(base)) => items.push(*base),
+        #[cfg(feature = "vm")]
+        KaramelPrimative::Bound(bound) => {
+            items.extend(bound.base.iter());
+            items.extend(bound.arguments.iter());
+        },
+        #[cfg(feature = "vm")]
+        KaramelPrimative::Error(error) => {
+            items.push(error.code);
+            items.push(error.data);
+        },
+        _ => ()
+    };
+}
+
+/// Mark and sweep. Values that can not be reached from the roots are released, even when they hold each other.
+/// Values that are also kept by the rust code are roots, their count is higher than the references given to the vm.
+/// Returns the count of the released values. Nothing is released while the collections are paused.
+///
+/// # Safety
+/// Every value that the vm can still use must be in the roots or reachable from them.
+pub unsafe fn collect<I: Iterator<Item = VmObject>>(roots: I) -> usize {
+    HEAP.with(|heap| {
+        let mut heap = heap.borrow_mut();
+        if heap.paused > 0 {
+            return 0;
+        }
+
+        heap.allocated = 0;
+        heap.collections += 1;
+
+        let mut pending: Vec<VmObject> = roots.collect();
+        for (pointer, references) in heap.objects.iter() {
+            let data = ManuallyDrop::new(Rc::from_raw(*pointer as *const KaramelPrimative));
+            if Rc::strong_count(&data) > *references {
+                pending.push(VmObject(QNAN | POINTER_FLAG | *pointer as u64));
+            }
+        }
+
+        let mut marked = HashSet::new();
+        while let Some(object) = pending.pop() {
+            let pointer = match pointer(object) {
+                Some(pointer) => pointer,
+                None => continue
+            };
+
+            if heap.objects.contains_key(&pointer) && marked.insert(pointer) {
+                children(&*(pointer as *const KaramelPrimative), &mut pending);
+            }
+        }
+
+        let unreachable: Vec<(usize, usize)> = heap.objects.iter()
+            .filter(|(pointer, _)| !marked.contains(*pointer))
+            .map(|(pointer, references)| (*pointer, *references))
+            .collect();
+
+        for (pointer, references) in unreachable.iter() {
+            heap.objects.remove(pointer);
+            for _ in 0..*references {
+                Rc::decrement_strong_count(*pointer as *const KaramelPrimative);
+            }
+        }
+
+        unreachable.len()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+
+    #[test]
+    fn test_collect_cycle() {
+        reset(KARAMEL_GC_THRESHOLD);
+        let first = VmObject::from(Vec::new());
+        let second = VmObject::from(vec![first]);
+        if let KaramelPrimative::List(list) = &*first.deref() {
+            list.borrow_mut().push(second);
+        }
+        let kept = VmObject::native_convert(KaramelPrimative::List(RefCell::new(vec![VmObject::from(1.0)])));
+
+        assert_eq!(tracked_count(), 3);
+        assert_eq!(unsafe { collect(vec![kept].into_iter()) }, 2);
+        assert_eq!(tracked_count(), 1);
+        match &*kept.deref() {
+            KaramelPrimative::List(list) => assert_eq!(list.borrow().len(), 1),
+            _ => panic!("list expected")
+        };
+    }
+}
@@ -0,0 +1,87 @@
+use std::borrow::Borrow;
+use std::cell::RefCell;
+use std::collections::HashSet;
+use std::hash::{Hash, Hasher};
+use std::rc::Rc;
+
+/* Hashed and compared by the text, so the table can be searched with a '&str' */
+#[derive(Clone)]
+struct Interned(Rc<String>);
+
+impl Hash for Interned {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.as_str().hash(state)
+    }
+}
+
+impl PartialEq for Interned {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.as_str() == other.0.as_str()
+    }
+}
+
+impl Eq for Interned {}
+
+impl Borrow<str> for Interned {
+    fn borrow(&self) -> &str {
+        self.0.as_str()
+    }
+}
+
+thread_local! {
+    static STRING_TABLE: RefCell<HashSet<Interned>> = RefCell::new(HashSet::new());
+}
+
+/// Returns the shared copy of the text. Same texts are the same 'Rc', so they can be compared with 'Rc::ptr_eq'.
+pub fn intern<T: Borrow<str>>(text: T) -> Rc<String> {
+    STRING_TABLE.with(|table| {
+        if let Some(interned) = table.borrow().get(text.borrow()) {
+            return interned.0.clone();
+        }
+
+        let interned = Rc::new(text.borrow().to_string());
+        table.borrow_mut().insert(Interned(interned.clone()));
+        interned
+    })
+}
+
+/// Returns the shared copy of the text without adding it to the table.
+pub fn lookup(text: &str) -> Option<Rc<String>> {
+    STRING_TABLE.with(|table| table.borrow().get(text).map(|interned| interned.0.clone()))
+}
+
+/// Same text check that does not compare the characters when both texts are interned.
+#[inline]
+pub fn same_text(left: &Rc<String>, right: &Rc<String>) -> bool {
+    Rc::ptr_eq(left, right) || left == right
+}
+
+/// Removes the texts that are used only by the table.
+pub fn release_unused() {
+    STRING_TABLE.with(|table| table.borrow_mut().retain(|interned| Rc::strong_count(&interned.0) > 1));
+}
+
+pub fn interned_count() -> usize {
+    STRING_TABLE.with(|table| table.borrow().len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_intern() {
+        let first = intern("karamel_intern_test");
+        let second = intern(String::from("karamel_intern_test"));
+        assert!(Rc::ptr_eq(&first, &second));
+        assert!(lookup("karamel_intern_test").is_some());
+        assert!(same_text(&first, &Rc::new("karamel_intern_test".to_string())));
+
+        let count = interned_count();
+        drop(first);
+        drop(second);
+        release_unused();
+        assert!(interned_count() < count);
+        assert!(lookup("karamel_intern_test").is_none());
+    }
+}
@@ -5,10 +5,27 @@ pub mod macros;
 pub mod parser;
 pub mod syntax;
 pub mod types;
+#[cfg(feature = "vm")]
 pub mod vm;
 pub mod compiler;
+#[cfg(feature = "vm")]
 pub mod buildin;
+#[cfg(feature = "vm")]
 pub mod logger;
 pub mod error;
+#[cfg(feature = "vm")]
 pub mod file;
+#[cfg(feature = "vm")]
+pub mod package;
+#[cfg(feature = "vm")]
+pub mod stdlib;
 pub mod constants;
+pub mod interner;
+pub mod gc;
+pub mod formatter;
+pub mod lint;
+pub mod transpiler;
+pub mod messages;
+
+#[cfg(feature = "wasm")]
+pub mod wasm;
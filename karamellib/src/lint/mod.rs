@@ -0,0 +1,429 @@
+use std::borrow::Borrow;
+use std::collections::{HashMap, HashSet};
+use std::rc::Rc;
+
+use strum::{EnumMessage, IntoEnumIterator};
+use strum_macros::{EnumIter, EnumMessage};
+
+use crate::compiler::ast::{KaramelAstType, KaramelIfStatementElseItem};
+use crate::compiler::locator::{Position, TokenLocator};
+use crate::compiler::visitor::{AstVisitor, VisitorResult, walk_ast};
+use crate::error::KaramelError;
+use crate::messages;
+use crate::parser::Parser;
+use crate::syntax::SyntaxParser;
+use crate::types::*;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, EnumIter, EnumMessage)]
+pub enum LintRule {
+    #[strum(message = "L001")]
+    UnusedVariable,
+
+    #[strum(message = "L002")]
+    ShadowedName,
+
+    #[strum(message = "L003")]
+    UnreadAssignment,
+
+    #[strum(message = "L004")]
+    SelfComparison,
+
+    #[strum(message = "L005")]
+    ConstantCondition
+}
+
+impl LintRule {
+    pub fn code(&self) -> &'static str {
+        self.get_message().unwrap()
+    }
+
+    pub fn from_code(code: &str) -> Option<LintRule> {
+        LintRule::iter().find(|rule| rule.code() == code)
+    }
+}
+
+#[derive(Default)]
+pub struct LintConfig {
+    disabled_rules: HashSet<LintRule>
+}
+
+impl LintConfig {
+    pub fn disable(&mut self, rule: LintRule) {
+        self.disabled_rules.insert(rule);
+    }
+
+    pub fn is_enabled(&self, rule: LintRule) -> bool {
+        !self.disabled_rules.contains(&rule)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct LintWarning {
+    pub rule: LintRule,
+    pub message: String,
+    pub line: u32,
+    pub column: u32
+}
+
+#[derive(Default)]
+struct LintScope {
+    assigned: Vec<(String, Position)>,
+    reads: HashSet<String>,
+    functions: HashSet<String>
+}
+
+impl LintScope {
+    fn assign(&mut self, name: &str, position: Position) {
+        if !self.assigned.iter().any(|(assigned, _)| assigned == name) {
+            self.assigned.push((name.to_string(), position));
+        }
+    }
+}
+
+struct Linter<'a> {
+    config: &'a LintConfig,
+    locator: TokenLocator,
+    warnings: Vec<LintWarning>,
+    scopes: Vec<LintScope>,
+    function_scopes: Vec<LintScope>,
+
+    /* Names that are read or written by the statement that is being walked */
+    touched: Vec<HashSet<String>>
+}
+
+fn is_comparison(operator: KaramelOperatorType) -> bool {
+    matches!(operator, KaramelOperatorType::Equal | KaramelOperatorType::NotEqual | KaramelOperatorType::GreaterThan |
+        KaramelOperatorType::GreaterEqualThan | KaramelOperatorType::LessThan | KaramelOperatorType::LessEqualThan)
+}
+
+/* Expression without function call or assignment. Its result only depends on the values in it. */
+fn is_pure(ast: &KaramelAstType) -> bool {
+    match ast {
+        KaramelAstType::Primative(_) | KaramelAstType::Symbol(_) | KaramelAstType::ModulePath(_) | KaramelAstType::None => true,
+        KaramelAstType::Binary { left, operator: _, right } | KaramelAstType::Control { left, operator: _, right } => is_pure(left) && is_pure(right),
+        KaramelAstType::PrefixUnary { operator: KaramelOperatorType::Not, expression, assign_to_temp: _ } => is_pure(expression),
+        KaramelAstType::Indexer { body, indexer } => is_pure(body) && is_pure(indexer),
+        KaramelAstType::List(items) => items.iter().all(|item| is_pure(item)),
+        _ => false
+    }
+}
+
+fn is_constant(ast: &KaramelAstType) -> bool {
+    match ast {
+        KaramelAstType::Primative(_) | KaramelAstType::None => true,
+        KaramelAstType::Binary { left, operator: _, right } | KaramelAstType::Control { left, operator: _, right } => is_constant(left) && is_constant(right),
+        KaramelAstType::PrefixUnary { operator: _, expression, assign_to_temp: _ } => is_constant(expression),
+        _ => false
+    }
+}
+
+impl<'a> Linter<'a> {
+    fn warning(&mut self, rule: LintRule, position: Position, message: String) {
+        if self.config.is_enabled(rule) {
+            self.warnings.push(LintWarning { rule, message, line: position.0, column: position.1 });
+        }
+    }
+
+    fn scope(&mut self) -> &mut LintScope {
+        self.scopes.last_mut().unwrap()
+    }
+
+    fn touch(&mut self, name: &str) {
+        if let Some(touched) = self.touched.last_mut() {
+            touched.insert(name.to_string());
+        }
+    }
+
+    fn read(&mut self, name: &str) {
+        self.scope().reads.insert(name.to_string());
+        self.touch(name);
+    }
+
+    fn write(&mut self, name: &str, position: Position) {
+        self.scope().assign(name, position);
+        self.touch(name);
+    }
+
+    /* Walks the ast and returns the names that are touched by it */
+    fn visit_statement(&mut self, ast: &KaramelAstType) -> Result<HashSet<String>, ()> {
+        self.touched.push(HashSet::new());
+        let result = self.visit(ast);
+        let touched = self.touched.pop().unwrap();
+        result?;
+
+        if let Some(parent) = self.touched.last_mut() {
+            parent.extend(touched.iter().cloned());
+        }
+        Ok(touched)
+    }
+
+    fn check_condition(&mut self, condition: &KaramelAstType) {
+        let position = self.locator.condition();
+        if is_constant(condition) {
+            self.warning(LintRule::ConstantCondition, position, messages::text("ConstantCondition"));
+        }
+    }
+
+    fn finish_scope(&mut self, scope: &LintScope) {
+        for (name, position) in scope.assigned.iter() {
+            if !name.starts_with('_') && !scope.reads.contains(name) {
+                self.warning(LintRule::UnusedVariable, *position, messages::format("UnusedVariable", &[("0", name)]));
+            }
+        }
+    }
+
+    fn finish(&mut self) {
+        let main_scope = self.scopes.pop().unwrap();
+        self.finish_scope(&main_scope);
+
+        for scope in std::mem::take(&mut self.function_scopes).iter() {
+            for (name, position) in scope.assigned.iter() {
+                if main_scope.functions.contains(name) {
+                    self.warning(LintRule::ShadowedName, *position, messages::format("ShadowedName", &[("0", name)]));
+                }
+                else if main_scope.assigned.iter().any(|(assigned, _)| assigned == name) {
+                    self.warning(LintRule::ShadowedName, *position, messages::format("ShadowedVariable", &[("0", name)]));
+                }
+            }
+        }
+
+        self.warnings.sort_by_key(|warning| (warning.line, warning.column));
+    }
+}
+
+impl AstVisitor for Linter<'_> {
+    type Error = ();
+
+    fn visit(&mut self, ast: &KaramelAstType) -> VisitorResult<()> {
+        walk_ast(self, ast)
+    }
+
+    fn visit_block(&mut self, items: &[Rc<KaramelAstType>]) -> VisitorResult<()> {
+        /* Assignments that are not read yet */
+        let mut pending: HashMap<String, Position> = HashMap::new();
+
+        for item in items.iter() {
+            match &**item {
+                KaramelAstType::Assignment { variable, operator: KaramelOperatorType::Assign, expression } if matches!(&**variable, KaramelAstType::Symbol(_)) => {
+                    let name = match &**variable {
+                        KaramelAstType::Symbol(name) => name,
+                        _ => unreachable!()
+                    };
+
+                    let position = self.locator.symbol(name);
+                    for touched in self.visit_statement(expression)? {
+                        pending.remove(&touched);
+                    }
+
+                    if let Some(old_position) = pending.insert(name.to_string(), position) {
+                        self.warning(LintRule::UnreadAssignment, old_position, messages::format("UnreadAssignment", &[("0", name)]));
+                    }
+                    self.write(name, position);
+                },
+                _ => for touched in self.visit_statement(item)? {
+                    pending.remove(&touched);
+                }
+            };
+        }
+        Ok(())
+    }
+
+    fn visit_func_call(&mut self, func_name_expression: &KaramelAstType, arguments: &[Rc<KaramelAstType>]) -> VisitorResult<()> {
+        self.visit(func_name_expression)?;
+        for argument in arguments.iter() {
+            self.visit(argument)?;
+        }
+        Ok(())
+    }
+
+    fn visit_control(&mut self, left: &KaramelAstType, operator: KaramelOperatorType, right: &KaramelAstType) -> VisitorResult<()> {
+        self.visit(left)?;
+        if is_comparison(operator) {
+            let position = self.locator.operator(operator);
+            if left == right && is_pure(left) {
+                self.warning(LintRule::SelfComparison, position, messages::text("SelfComparison"));
+            }
+        }
+        self.visit(right)
+    }
+
+    fn visit_suffix_unary(&mut self, _: KaramelOperatorType, expression: &KaramelAstType) -> VisitorResult<()> {
+        self.visit(expression)
+    }
+
+    fn visit_assignment(&mut self, variable: &KaramelAstType, operator: KaramelOperatorType, expression: &KaramelAstType) -> VisitorResult<()> {
+        match variable {
+            KaramelAstType::Symbol(name) => {
+                let position = self.locator.symbol(name);
+                if operator != KaramelOperatorType::Assign {
+                    self.read(name);
+                }
+                self.write(name, position);
+            },
+            _ => self.visit(variable)?
+        };
+        self.visit(expression)
+    }
+
+    fn visit_if_statement(&mut self, condition: &KaramelAstType, body: &KaramelAstType, else_body: Option<&KaramelAstType>, else_if: &[Rc<KaramelIfStatementElseItem>]) -> VisitorResult<()> {
+        self.check_condition(condition);
+        self.visit(condition)?;
+        self.visit(body)?;
+
+        for item in else_if.iter() {
+            self.check_condition(&item.condition);
+            self.visit(&item.condition)?;
+            self.visit(&item.body)?;
+        }
+
+        if let Some(else_body) = else_body {
+            self.visit(else_body)?;
+        }
+        Ok(())
+    }
+
+    fn visit_function_defination(&mut self, name: &str, arguments: &[String], body: &KaramelAstType) -> VisitorResult<()> {
+        self.locator.symbol(name);
+        self.scope().functions.insert(name.to_string());
+
+        let mut scope = LintScope::default();
+        for argument in arguments.iter() {
+            let position = self.locator.symbol(argument);
+            scope.assign(argument, position);
+        }
+
+        /* Function body can not see the outer variables */
+        self.scopes.push(scope);
+        self.touched.push(HashSet::new());
+        let result = self.visit(body);
+        self.touched.pop();
+        let scope = self.scopes.pop().unwrap();
+        result?;
+
+        self.finish_scope(&scope);
+        self.function_scopes.push(scope);
+        Ok(())
+    }
+
+    fn visit_try(&mut self, body: &KaramelAstType, variable: Option<&str>, catch_body: &KaramelAstType) -> VisitorResult<()> {
+        self.visit(body)?;
+        if let Some(variable) = variable {
+            let position = self.locator.symbol(variable);
+            self.write(variable, position);
+        }
+        self.visit(catch_body)
+    }
+
+    /* Variable is read by the 'kapat' call at the end of the block */
+    fn visit_with(&mut self, assignment: &KaramelAstType, _: &KaramelAstType, body: &KaramelAstType) -> VisitorResult<()> {
+        if let KaramelAstType::Assignment { variable, expression, .. } = assignment {
+            self.visit(expression)?;
+            if let KaramelAstType::Symbol(name) = &**variable {
+                let position = self.locator.symbol(name);
+                self.write(name, position);
+                self.read(name);
+            }
+        }
+        self.visit(body)
+    }
+
+    fn visit_symbol(&mut self, name: &str) -> VisitorResult<()> {
+        self.locator.symbol(name);
+        self.read(name);
+        Ok(())
+    }
+
+    fn visit_module_path(&mut self, path: &[String]) -> VisitorResult<()> {
+        for name in path.iter() {
+            self.locator.symbol(name);
+        }
+        Ok(())
+    }
+
+    fn visit_load(&mut self, path: &[String]) -> VisitorResult<()> {
+        self.visit_module_path(path)
+    }
+
+    fn visit_enum(&mut self, name: &str, variants: &[String]) -> VisitorResult<()> {
+        self.locator.symbol(name);
+        for variant in variants.iter() {
+            self.locator.symbol(variant);
+        }
+        Ok(())
+    }
+}
+
+/// Parses the script and reports the suspicious code parts. Syntax errors are returned as error.
+pub fn lint_code<T: Borrow<str>>(source: T, config: &LintConfig) -> Result<Vec<LintWarning>, KaramelError> {
+    let mut parser = Parser::new(source.borrow());
+    parser.parse()?;
+
+    let tokens = parser.tokens();
+    let ast = SyntaxParser::new(tokens.to_vec()).parse()?;
+
+    let mut linter = Linter {
+        config,
+        locator: TokenLocator::new(&tokens),
+        warnings: Vec::new(),
+        scopes: vec![LintScope::default()],
+        function_scopes: Vec::new(),
+        touched: Vec::new()
+    };
+
+    /* Single statement scripts are not parsed as block */
+    let _ = match &*ast {
+        KaramelAstType::Block(_) => linter.visit(&ast),
+        _ => linter.visit_block(std::slice::from_ref(&ast))
+    };
+    linter.finish();
+    Ok(linter.warnings)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lint(code: &str) -> Vec<(LintRule, u32, u32)> {
+        lint_code(code, &LintConfig::default()).unwrap().iter().map(|warning| (warning.rule, warning.line, warning.column)).collect()
+    }
+
+    #[test]
+    fn unused_variable() {
+        assert_eq!(lint("a = 1\nb = 2\ngç::satıryaz(a)"), vec![(LintRule::UnusedVariable, 1, 0)]);
+        assert_eq!(lint("_a = 1"), vec![]);
+        assert_eq!(lint("fonk topla(a, b):\n    döndür a\ngç::satıryaz(topla(1, 2))"), vec![(LintRule::UnusedVariable, 0, 14)]);
+    }
+
+    #[test]
+    fn shadowed_name() {
+        let code = "sayaç = 1\nfonk arttır(sayaç):\n    döndür sayaç + 1\nsayaç = arttır(sayaç)\ngç::satıryaz(sayaç)";
+        assert_eq!(lint(code), vec![(LintRule::ShadowedName, 1, 12)]);
+    }
+
+    #[test]
+    fn unread_assignment() {
+        assert_eq!(lint("a = 1\na = 2\ngç::satıryaz(a)"), vec![(LintRule::UnreadAssignment, 0, 0)]);
+        assert_eq!(lint("a = 1\na = a + 2\ngç::satıryaz(a)"), vec![]);
+        assert_eq!(lint("a = 1\na > 0 ise:\n    a = 2\na = 3\ngç::satıryaz(a)"), vec![]);
+    }
+
+    #[test]
+    fn self_comparison() {
+        assert_eq!(lint("a = 1\na == a ise:\n    gç::satıryaz(a)"), vec![(LintRule::SelfComparison, 1, 2)]);
+        assert_eq!(lint("a = 1\na == 1 ise:\n    gç::satıryaz(a)"), vec![]);
+    }
+
+    #[test]
+    fn constant_condition() {
+        assert_eq!(lint("1 > 2 ise:\n    gç::satıryaz(1)\nveya 1 ise:\n    gç::satıryaz(2)"), vec![(LintRule::ConstantCondition, 0, 6), (LintRule::ConstantCondition, 2, 7)]);
+    }
+
+    #[test]
+    fn config() {
+        let mut config = LintConfig::default();
+        config.disable(LintRule::UnusedVariable);
+        assert_eq!(lint_code("a = 1", &config).unwrap(), vec![]);
+        assert_eq!(LintRule::from_code("L003"), Some(LintRule::UnreadAssignment));
+        assert_eq!(LintRule::SelfComparison.code(), "L004");
+    }
+}
@@ -0,0 +1,301 @@
+use super::Message;
+
+/// Every user facing text of the library and the command line application. Keys of the errors and
+/// warnings are the names of their variants, teaching hints use the '.hint' suffix.
+pub const MESSAGES: &[Message] = &[
+    // Errors
+    Message { key: "SyntaxError", tr: "Sozdizimi hatasi", en: "Syntax error" },
+    Message { key: "InvalidExpression", tr: "Geçersiz ifade", en: "Invalid expression" },
+    Message { key: "MoreThan1ArgumentPassed", tr: "Birden fazla degisken kullanilamaz", en: "More than one variable cannot be used" },
+    Message { key: "RightParanthesesMissing", tr: "Sağ parantaz eksik", en: "Right parenthesis is missing" },
+    Message { key: "AssertFailed", tr: "Doğrulanama  başarısız", en: "Assertion failed" },
+    Message { key: "NumberNotParsed", tr: "Sayı ayrıştırılamadı", en: "Number could not be parsed" },
+    Message { key: "MissingStringDeliminator", tr: "Yazı sonlandırıcısı bulunamadı", en: "Text terminator not found" },
+    Message { key: "CharNotValid", tr: "Karakter geçerli değil", en: "Character is not valid" },
+    Message { key: "RightSideOfExpressionNotFound", tr: "İfadenin sağ tarafı bulunamadı", en: "Right side of the expression not found" },
+    Message { key: "ReturnMustBeUsedInFunction", tr: "Döndür komutu fonksiyon içinde kullanılmalıdır", en: "Return must be used inside a function" },
+    Message { key: "FunctionCallSyntaxNotValid", tr: "Fonksiyon çağırma sözdizimi geçerli değil", en: "Function call syntax is not valid" },
+    Message { key: "FunctionNameNotDefined", tr: "Fonksiyon adı tanımlanmamış", en: "Function name is not defined" },
+    Message { key: "ArgumentMustBeText", tr: "Değişken yazı olmalıdır", en: "Argument must be text" },
+    Message { key: "IfConditionBodyNotFound", tr: "Koşul gövdesi eksik", en: "Condition body is missing" },
+    Message { key: "ParenthesesNotClosed", tr: "Parantez kapatılmamış", en: "Parenthesis is not closed" },
+    Message { key: "InvalidUnaryOperation", tr: "Geçersiz tekli işlem", en: "Invalid unary operation" },
+    Message { key: "UnaryWorksWithNumber", tr: "Tekli numara ile çalışmaktadır", en: "Unary operator works with numbers" },
+    Message { key: "ArgumentNotFound", tr: "Parametre bulunamadı", en: "Argument not found" },
+    Message { key: "MultipleElseUsageNotValid", tr: "Birden fazla 'yoksa' ifadesi kullanılamaz", en: "'yoksa' cannot be used more than once" },
+    Message { key: "BreakAndContinueBelongToLoops", tr: "'kır' ve 'devam' ifadeleri döngü içinde kullanılabilir", en: "'kır' and 'devam' can be used inside loops" },
+    Message { key: "FunctionConditionBodyNotFound", tr: "Fonksiyon içi kodlar bulunamadı", en: "Function body not found" },
+    Message { key: "ColonMarkMissing", tr: "':' eksik", en: "':' is missing" },
+    Message { key: "ElseIsUsed", tr: "'yoksa' zaten kullanıldı", en: "'yoksa' is already used" },
+    Message { key: "IndentationIssue", tr: "Girinti sorunu", en: "Indentation issue" },
+    Message { key: "DictNotClosed", tr: "Sözlük düzgün kapatılmamış", en: "Dictionary is not closed properly" },
+    Message { key: "ArrayNotClosed", tr: "Dizi düzgün kapatılmadı", en: "List is not closed properly" },
+    Message { key: "InvalidListItem", tr: "Dizi elemanı geçersiz", en: "List item is not valid" },
+    Message { key: "DictionaryKeyNotValid", tr: "Sözlük anahtarı geçersiz", en: "Dictionary key is not valid" },
+    Message { key: "DictionaryValueNotValid", tr: "Sözlük geçeri geçersiz", en: "Dictionary value is not valid" },
+    Message { key: "CommentNotFinished", tr: "Yorum bilgisi düzgün kapatılmadı", en: "Comment is not closed properly" },
+    Message { key: "WhileStatementNotValid", tr: "Döngü düzgün tanımlanmamış", en: "Loop is not defined properly" },
+    Message { key: "FunctionDefinationNotValid", tr: "Fonksiyon tanımlaması hatalı", en: "Function definition is not valid" },
+    Message { key: "MissingIf", tr: "'ise' sözcüğü eksik", en: "'ise' is missing" },
+    Message { key: "KeywordCouldNotBeUsed", tr: "Anahtar kelimeler kullanılamaz", en: "Keywords cannot be used" },
+    Message { key: "FileReadError", tr: "'{filename}' okunamadi. Hata: '{error}'", en: "'{filename}' could not be read. Error: '{error}'" },
+    Message { key: "FileNotFound", tr: "'{0}' bulunamadi", en: "'{0}' not found" },
+    Message { key: "GeneralError", tr: "{0}", en: "{0}" },
+    Message { key: "FunctionAlreadyDefined", tr: "'{0}' fonksiyonu önceden tanımlanmış", en: "'{0}' function is already defined" },
+    Message { key: "FunctionNotFound", tr: "'{0}' fonksiyonu bulunamadı", en: "'{0}' function not found" },
+    Message { key: "FunctionArgumentNotMatching", tr: "'{function}' fonksiyon parametreleri eşleşmiyor. {expected} adet beklenirken {found} adet bulundu", en: "'{function}' function arguments do not match. Expected {expected} but found {found}" },
+    Message { key: "FunctionExpectedThatParameterType", tr: "'{function}' fonksiyonu sadece {expected} parametresini kabul ediyor", en: "'{function}' function only accepts {expected} argument" },
+    Message { key: "AssertFailedWithArgument", tr: "Doğrulama başarısız (Sol: {left}, sağ: {right})", en: "Assertion failed (Left: {left}, right: {right})" },
+    Message { key: "UnaryExpressionNotValid", tr: "Tekli ifade geçerli değil", en: "Unary expression is not valid" },
+    Message { key: "UnaryOperatorNotFound", tr: "Tekli operatör bulunamadi", en: "Unary operator not found" },
+    Message { key: "ValueNotFoundInStorage", tr: "Depoda değer bulunamadı", en: "Value not found in the storage" },
+    Message { key: "ReservedName", tr: "'{0}' reserv edilmiş kelimedir, kullanılamaz", en: "'{0}' is a reserved word and cannot be used" },
+    Message { key: "ModuleParseError", tr: "'{name}' modül okuma sırasında hata ile karşılaşıldı. Hata {error}", en: "An error occurred while reading the '{name}' module. Error {error}" },
+    Message { key: "FunctionNotFoundInStorage", tr: "Depoda fonksiyon({0}) bulunamadı", en: "Function({0}) not found in the storage" },
+    Message { key: "NotCallable", tr: "'{0}' fonksiyon olarak çağrılabilir değil", en: "'{0}' is not callable as a function" },
+    Message { key: "IndexerMustBeNumber", tr: "'{0}' geçerli bir sıralayıcı değil, sayı olması gerekiyor", en: "'{0}' is not a valid indexer, it must be a number" },
+    Message { key: "IndexerMustBeString", tr: "'{0}' geçerli bir sıralayıcı değil, yazı olması gerekiyor", en: "'{0}' is not a valid indexer, it must be a text" },
+    Message { key: "AssignOperatorRequiredForLoop", tr: "Döngü ile sadece atama öperatörü kullanılabilir", en: "Only the assignment operator can be used with loops" },
+    Message { key: "CommaIsMissing", tr: "virgül eksik", en: "comma is missing" },
+    Message { key: "OperatorNotValid", tr: "Öperatör geçerli değil", en: "Operator is not valid" },
+    Message { key: "FileWriteError", tr: "'{filename}' yazılamadı. Hata: '{error}'", en: "'{filename}' could not be written. Error: '{error}'" },
+    Message { key: "DisabledInTeachingMode", tr: "{0} öğretim kipinde kullanılamaz", en: "{0} cannot be used in the teaching mode" },
+    Message { key: "LoopIterationLimitExceeded", tr: "Döngü {0} adımdan fazla çalıştı", en: "Loop ran more than {0} steps" },
+    Message { key: "VariableNotDefined", tr: "'{0}' değişkeni tanımlanmamış", en: "'{0}' variable is not defined" },
+    Message { key: "VariableUsedBeforeAssignment", tr: "'{0}' değişkeni değer atanmadan kullanılmış", en: "'{0}' variable is used before a value is assigned" },
+    Message { key: "InternalError", tr: "Yorumlayıcıda beklenmeyen bir hata oluştu ({0})", en: "An unexpected error occurred in the interpreter ({0})" },
+    Message { key: "InvalidNumberLiteral", tr: "{0} sayı hatalı yazılmış", en: "{0} number is not written correctly" },
+    Message { key: "InvalidEscape", tr: "'{0}' geçerli bir kaçış dizisi değil", en: "'{0}' is not a valid escape sequence" },
+    Message { key: "UncaughtError", tr: "Yakalanmayan hata: {0}", en: "Uncaught error: {0}" },
+    Message { key: "CatchBlockNotFound", tr: "'dene' bloğundan sonra 'yakala' bloğu gelmeli", en: "'dene' block must be followed by a 'yakala' block" },
+    Message { key: "DeferMustBeUsedInFunction", tr: "'ertele' fonksiyon içinde kullanılmalıdır", en: "'ertele' must be used inside a function" },
+    Message { key: "ConversionFailed", tr: "{value} değeri {target} türüne dönüştürülemedi", en: "{value} could not be converted to {target}" },
+    Message { key: "AssumptionFailed", tr: "Varsayım sağlanmadı: {0}", en: "Assumption failed: {0}" },
+    Message { key: "AssumptionFailed.empty", tr: "Varsayım sağlanmadı", en: "Assumption failed" },
+    Message { key: "IndexOutOfRange", tr: "{index} sırası geçersiz, uzunluk {length}", en: "{index} is not a valid index, the length is {length}" },
+    Message { key: "IndexerAssignmentNotSupported", tr: "'{0}' değerinin sıralayıcısına atama yapılamaz", en: "Cannot assign to an index of '{0}'" },
+    Message { key: "SymbolTooLong", tr: "İsimler en fazla {0} karakter olabilir", en: "Names can be at most {0} characters long" },
+    Message { key: "TextTooLong", tr: "Yazılar en fazla {0} karakter olabilir", en: "Texts can be at most {0} characters long" },
+    Message { key: "LineTooLong", tr: "Satırlar en fazla {0} karakter olabilir", en: "Lines can be at most {0} characters long" },
+    Message { key: "UnknownTypeName", tr: "'{0}' bilinen bir tür değil", en: "'{0}' is not a known type" },
+    Message { key: "TooManyConstants", tr: "Bir fonksiyonda en fazla {0} farklı sabit değer kullanılabilir", en: "A function can use at most {0} different constant values" },
+    Message { key: "TooManyVariables", tr: "Bir fonksiyonda en fazla {0} değişken kullanılabilir", en: "A function can use at most {0} variables" },
+    Message { key: "TooManyArguments", tr: "Fonksiyonlar en fazla {0} parametre alabilir", en: "Functions can take at most {0} arguments" },
+    Message { key: "TooManyItems", tr: "Liste ve sözlükler kodun içinde en fazla {0} eleman ile yazılabilir", en: "Lists and dictionaries can be written with at most {0} items in the code" },
+    Message { key: "ArgumentTypeMismatch", tr: "'{argument}' parametresi {expected} olmalı fakat {found} verildi", en: "'{argument}' parameter must be {expected} but {found} was given" },
+    Message { key: "TaskNotFound", tr: "{0} numaralı görev bulunamadı", en: "Task {0} is not found" },
+    Message { key: "TaskDeadlock", tr: "{0} numaralı görev kendi sonucunu bekliyor", en: "Task {0} is waiting for its own result" },
+    Message { key: "ChannelNotFound", tr: "{0} numaralı kanal bulunamadı", en: "Channel {0} is not found" },
+    Message { key: "ChannelEmpty", tr: "{0} numaralı kanalda değer yok ve bekleyen görev kalmadı", en: "Channel {0} is empty and there is no waiting task" },
+    Message { key: "Interrupted", tr: "Program kullanıcı tarafından durduruldu", en: "Program was interrupted by the user" },
+    Message { key: "AssertFailedWithExpression", tr: "Doğrulama başarısız, satır {line}: {expression} ({message})", en: "Assertion failed, line {line}: {expression} ({message})" },
+    Message { key: "AssertFailedWithExpression.empty", tr: "Doğrulama başarısız, satır {line}: {expression}", en: "Assertion failed, line {line}: {expression}" },
+    Message { key: "IndexerNotClosed", tr: "'[' ile başlayan erişim ']' ile kapatılmadı", en: "Access that starts with '[' is not closed with ']'" },
+    Message { key: "NestingTooDeep", tr: "İfadeler ve bloklar en fazla {0} seviye iç içe yazılabilir", en: "Expressions and blocks can be nested at most {0} levels" },
+    Message { key: "TranspileNotSupported", tr: "'{0}' hedef dile çevrilemiyor", en: "'{0}' can not be transpiled to the target language" },
+    Message { key: "ForeignLibraryNotLoaded", tr: "'{path}' kütüphanesi yüklenemedi. Hata: '{error}'", en: "'{path}' library could not be loaded. Error: '{error}'" },
+    Message { key: "ForeignFunctionNotFound", tr: "'{0}' yabancı fonksiyonu bulunamadı", en: "'{0}' foreign function is not found" },
+    Message { key: "ForeignTypeNotSupported", tr: "'{0}' yabancı fonksiyon tanımında kullanılamaz", en: "'{0}' can not be used in a foreign function declaration" },
+    Message { key: "ConstantReassigned", tr: "'{0}' sabit olarak tanımlanmış, değeri değiştirilemez", en: "'{0}' is defined as a constant, its value can not be changed" },
+    Message { key: "ConstantValueRequired", tr: "'sabit' kelimesinden sonra değişken adı ve '=' ile değeri yazılmalı", en: "Variable name and its value after '=' should be written after 'sabit'" },
+    Message { key: "LocalValueRequired", tr: "'yerel' kelimesinden sonra değişken adı ve '=' ile değeri yazılmalı", en: "Variable name and its value after '=' should be written after 'yerel'" },
+    Message { key: "EnumVariantsRequired", tr: "'seçenekler' kelimesinden sonra tür adı, ':' ve virgül ile ayrılmış seçenekler yazılmalı", en: "Type name, ':' and the variants separated by comma should be written after 'seçenekler'" },
+    Message { key: "EnumAlreadyDefined", tr: "'{0}' seçenekleri daha önce tanımlanmış", en: "'{0}' variants are already defined" },
+    Message { key: "EnumVariantAlreadyDefined", tr: "'{0}' seçeneği birden fazla yazılmış", en: "'{0}' variant is written more than once" },
+    Message { key: "EnumVariantNotFound", tr: "'{0}' seçeneği bulunamadı", en: "'{0}' variant not found" },
+    Message { key: "DecoratorWithoutFunction", tr: "'@' satırından sonraki satırda fonksiyon tanımlanmalı", en: "A function should be defined after the '@' line" },
+    Message { key: "PrivateWithoutFunction", tr: "'gizli' kelimesinden sonra fonksiyon tanımlanmalı", en: "A function should be defined after 'gizli'" },
+    Message { key: "FunctionIsPrivate", tr: "'{0}' fonksiyonu gizli, sadece tanımlandığı modülde kullanılabilir", en: "'{0}' function is private, it can only be used in its module" },
+    Message { key: "InvalidUseStatement", tr: "'kullan' satırı 'kullan modül olarak ad' ya da 'kullan modül::{ad1, ad2}' şeklinde yazılmalı", en: "'kullan' line should be written as 'kullan module olarak name' or 'kullan module::{name1, name2}'" },
+    Message { key: "ImportNotFound", tr: "'{0}' modülde bulunamadı", en: "'{0}' could not be found in the module" },
+    Message { key: "InvalidPackageFile", tr: "'{file}' dosyasının {line}. satırı okunamadı", en: "Line {line} of the '{file}' file could not be read" },
+    Message { key: "DependencyNotFound", tr: "'{0}' bağımlılığı bulunamadı", en: "'{0}' dependency could not be found" },
+    Message { key: "DependencyConflict", tr: "'{0}' paketi farklı kaynaklardan eklenmiş", en: "'{0}' package is added from different sources" },
+    Message { key: "InvalidSourceEncoding", tr: "'{file}' dosyası UTF-8 değil, {encoding} olarak kaydedilmiş görünüyor", en: "'{file}' file is not UTF-8, it looks like it is saved as {encoding}" },
+    Message { key: "InstructionLimitExceeded", tr: "Program {0} komuttan fazla çalıştı", en: "Program executed more than {0} instructions" },
+    Message { key: "PermissionDenied", tr: "'{module}' modülü için '{permission}' izni verilmemiş", en: "'{permission}' permission is not given for the '{module}' module" },
+    Message { key: "InvalidWithStatement", tr: "'birlikte' satırı 'birlikte değer olarak ad:' şeklinde yazılmalı", en: "'birlikte' line should be written as 'birlikte value olarak name:'" },
+    Message { key: "CloseFunctionNotFound", tr: "'{0}' türündeki değerin 'kapat' fonksiyonu yok", en: "Value of the '{0}' type does not have a 'kapat' function" },
+    Message { key: "FrozenValueCannotBeChanged", tr: "Dondurulmuş değerler değiştirilemez", en: "Frozen values can not be changed" },
+    Message { key: "ValueTooLarge", tr: "Yazı ve baytlar en fazla {0} bayt olabilir", en: "Texts and bytes can be at most {0} bytes long" },
+    Message { key: "MemoryLimitExceeded", tr: "Program en fazla {0} bayt bellek kullanabilir", en: "The program can use at most {0} bytes of memory" },
+    Message { key: "DependencyNotInstalled", tr: "'{0}' bağımlılığı kurulmamış", en: "'{0}' dependency is not installed" },
+    Message { key: "DictionaryKeyNotHashable", tr: "'{0}' sözlük anahtarı olarak kullanılamaz", en: "'{0}' can not be used as a dictionary key" },
+    Message { key: "StackOverflow", tr: "Fonksiyonlar en fazla {0} seviye iç içe çağrılabilir", en: "Functions can be called at most {0} levels deep" },
+
+    // Compiler warnings
+    Message { key: "UnusedFunction", tr: "'{0}' fonksiyonu tanımlanmış fakat hiç kullanılmamış", en: "'{0}' function is defined but never used" },
+    Message { key: "ImplicitNumberToText", tr: "Sayı, yazı ile toplanırken yazıya dönüştürülüyor", en: "Number is converted to text while it is added to a text" },
+    Message { key: "ShadowedVariable", tr: "'{0}' aynı isimdeki dış değişkeni gölgeliyor", en: "'{0}' shadows the outer variable with the same name" },
+
+    // Teaching hints
+    Message { key: "SyntaxError.hint", tr: "Yazdığın satır Karamel kurallarına uymuyor. Satırdaki parantezleri, tırnak işaretlerini ve iki nokta üst üste işaretini kontrol et.", en: "The line does not follow the Karamel rules. Check the parentheses, quotation marks and colons on the line." },
+    Message { key: "InvalidExpression.hint", tr: "İfade tamamlanmamış görünüyor. Örneğin 'a = (1 + 2)' yerine 'a = (1 + ' yazılmış olabilir.", en: "The expression looks incomplete. For example 'a = (1 + ' may have been written instead of 'a = (1 + 2)'." },
+    Message { key: "RightParanthesesMissing.hint", tr: "Açtığın her parantezi kapatman gerekiyor. '(' sayısı ile ')' sayısı aynı olmalı.", en: "Every opened parenthesis must be closed. The number of '(' and ')' must be the same." },
+    Message { key: "MissingStringDeliminator.hint", tr: "Yazılar tırnak işareti ile başlar ve aynı tırnak işareti ile biter. Örnek: 'merhaba' ya da \"merhaba\".", en: "Texts start with a quotation mark and end with the same quotation mark. Example: 'merhaba' or \"merhaba\"." },
+    Message { key: "ColonMarkMissing.hint", tr: "'ise', 'döngü' ve 'fonk' satırlarının sonuna ':' konulmalı. Örnek: 'a > 5 ise:'.", en: "Lines with 'ise', 'döngü' and 'fonk' must end with ':'. Example: 'a > 5 ise:'." },
+    Message { key: "IndentationIssue.hint", tr: "Bloğun içindeki satırlar aynı sayıda boşluk ile başlamalı. Her seviye için 4 boşluk kullanmanı öneririz.", en: "Lines inside a block must start with the same number of spaces. We suggest 4 spaces for each level." },
+    Message { key: "IfConditionBodyNotFound.hint", tr: "'ise:' satırından sonra, bir seviye içeride, koşul doğru olduğunda çalışacak kodu yazmalısın.", en: "After the 'ise:' line, write the code that runs when the condition is true, one level inside." },
+    Message { key: "BreakAndContinueBelongToLoops.hint", tr: "'kır' ve 'devam' sadece bir döngünün içinde kullanılabilir.", en: "'kır' and 'devam' can only be used inside a loop." },
+    Message { key: "ReturnMustBeUsedInFunction.hint", tr: "'döndür' sadece bir fonksiyonun içinde kullanılabilir.", en: "'döndür' can only be used inside a function." },
+    Message { key: "FunctionNotFound.hint", tr: "Bu isimde bir fonksiyon bulunamadı. İsmin doğru yazıldığından ve fonksiyonun tanımlandığından emin ol.", en: "No function found with this name. Make sure the name is spelled correctly and the function is defined." },
+    Message { key: "FunctionArgumentNotMatching.hint", tr: "Fonksiyonu tanımlarken yazdığın parametre sayısı ile çağırırken verdiğin değer sayısı aynı olmalı.", en: "The number of parameters in the function definition and the number of values given in the call must be the same." },
+    Message { key: "ReservedName.hint", tr: "Bu isim Karamel tarafından kullanılıyor. Değişkenine başka bir isim ver.", en: "This name is used by Karamel. Give your variable another name." },
+    Message { key: "AssertFailed.hint", tr: "Doğrulama sırasında iki değer birbirine eşit çıkmadı. Değişkenlerin değerlerini 'gç::satıryaz' ile ekrana basarak kontrol edebilirsin.", en: "The two values were not equal during the assertion. You can check the values of the variables by printing them with 'gç::satıryaz'." },
+    Message { key: "DisabledInTeachingMode.hint", tr: "Bu özellik ileri seviye olduğu için öğretim kipinde kapalı. Fonksiyonlarını iç içe yazmak yerine ayrı ayrı tanımla.", en: "This feature is disabled in the teaching mode because it is advanced. Define your functions separately instead of nesting them." },
+    Message { key: "VariableNotDefined.hint", tr: "Değişkeni kullanmadan önce bir değer ataman gerekiyor. Fonksiyonların içinden dışarıdaki değişkenlere erişilemez, değeri parametre olarak gönder.", en: "A value must be assigned to the variable before it is used. Functions cannot access the outer variables, pass the value as a parameter." },
+    Message { key: "VariableUsedBeforeAssignment.hint", tr: "Değişkene değer atayan satır, değişkeni kullanan satırdan önce yazılmalı.", en: "The line that assigns a value to the variable must be written before the line that uses it." },
+    Message { key: "InvalidNumberLiteral.hint", tr: "Onaltılık sayılar '0x', ikilik sayılar '0b', sekizlik sayılar '0o' ile başlar ve sadece o sayı sistemindeki rakamları içerebilir. Örnek: 0xFF, 0b1010, 0o17.", en: "Hexadecimal numbers start with '0x', binary numbers with '0b', octal numbers with '0o' and can only contain the digits of that number system. Example: 0xFF, 0b1010, 0o17." },
+    Message { key: "InvalidEscape.hint", tr: "Yazıların içinde '\\n' (yeni satır), '\\t' (sekme), '\\\\' (ters bölü), '\\'' ve '\\\"' (tırnak) ile '\\u{011F}' gibi Unicode kodları kullanılabilir. Ters bölü yazmak için '\\\\' kullan.", en: "Texts can contain '\\n' (new line), '\\t' (tab), '\\\\' (backslash), '\\'' and '\\\"' (quotation) and Unicode codes like '\\u{011F}'. Use '\\\\' to write a backslash." },
+    Message { key: "UncaughtError.hint", tr: "Fırlatılan hatalar 'dene:' bloğunun altındaki 'yakala:' bloğunda yakalanabilir. Hatanın bilgilerine 'yakala hata:' ile verdiğin isimden ulaşabilirsin.", en: "Thrown errors can be caught in the 'yakala:' block under the 'dene:' block. The details of the error are available through the name given with 'yakala hata:'." },
+    Message { key: "CatchBlockNotFound.hint", tr: "'dene:' bloğunun hemen altına, aynı girintide 'yakala:' ya da 'yakala hata:' yazmalısın.", en: "Write 'yakala:' or 'yakala hata:' right after the 'dene:' block, with the same indentation." },
+    Message { key: "DeferMustBeUsedInFunction.hint", tr: "'ertele:' bloğu, içinde bulunduğu fonksiyon bittiğinde çalışır. Bu yüzden sadece bir fonksiyonun içinde kullanılabilir.", en: "The 'ertele:' block runs when the function it is in ends. That is why it can only be used inside a function." },
+    Message { key: "ConversionFailed.hint", tr: "Dönüştürme fonksiyonları hata ile karşılaşınca hata değeri döndürür. Sonucun 'baz::türü(sonuç) == 'hata'' ile kontrol edilmesi gerekir.", en: "Conversion functions return an error value when they fail. Check the result with 'baz::türü(result) == 'hata''." },
+    Message { key: "AssertFailedWithExpression.hint", tr: "'doğrula' ile kontrol edilen koşul yanlış çıktı. Koşuldaki değişkenlerin değerlerini 'gç::satıryaz' ile ekrana basarak kontrol edebilirsin.", en: "The condition checked with 'doğrula' was false. You can check the values of the variables in the condition by printing them with 'gç::satıryaz'." },
+    Message { key: "AssumptionFailed.hint", tr: "'varsay' satırındaki koşul yanlış çıktı. Programın bu noktaya gelmeden önce değişkenlere beklenmeyen bir değer atanmış olabilir.", en: "The condition on the 'varsay' line was false. A variable may have been given an unexpected value before the program reached this point." },
+    Message { key: "ArgumentTypeMismatch.hint", tr: "Fonksiyonun tanımında parametrenin yanına yazılan tür ile çağırırken verilen değerin türü aynı olmalı. Değerin türünü 'baz::türü' ile öğrenebilirsin.", en: "The type written next to the parameter in the function definition must match the type of the given value. Use 'baz::türü' to learn the type of a value." },
+    Message { key: "IndexOutOfRange.hint", tr: "Listelerde sıralar 0'dan başlar, son elemanın sırası uzunluğun bir eksiğidir. Listeye yeni eleman eklemek için 'ekle' fonksiyonunu kullan.", en: "List indexes start from 0, the index of the last item is one less than the length. Use the 'ekle' function to add a new item to a list." },
+    Message { key: "TextTooLong.hint", tr: "Yazının kapanış tırnağı unutulmuş olabilir. Çok büyük yazıları kodun içine yazmak yerine dosyadan okuyabilirsin.", en: "The closing quotation mark of the text may be missing. Instead of writing very big texts into the code, you can read them from a file." },
+    Message { key: "InternalError.hint", tr: "Bu hata senin kodundan değil, Karamel'in kendisinden kaynaklanıyor. Ekrana yazılan hata raporu dosyasını öğretmenine ya da Karamel geliştiricilerine gönder.", en: "This error is caused by Karamel itself, not by your code. Send the error report file printed on the screen to your teacher or to the Karamel developers." },
+    Message { key: "LoopIterationLimitExceeded.hint", tr: "Döngü çok uzun süre çalıştı. Döngü koşulunun bir gün yanlış olacağından ve döngü değişkeninin değiştiğinden emin ol.", en: "The loop ran for too long. Make sure the loop condition becomes false at some point and the loop variable changes." },
+    Message { key: "ConstantReassigned.hint", tr: "'sabit' ile tanımlanan değişkenlere tekrar değer atanamaz. Değeri değişecek ise tanımdaki 'sabit' kelimesini kaldır ya da yeni değer için başka bir isim kullan.", en: "Variables that are defined with 'sabit' can not be assigned again. Remove 'sabit' from the definition if the value changes, or use another name for the new value." },
+    Message { key: "UnusedFunction.hint", tr: "Kullanılmayan fonksiyonları silebilir ya da çağırmayı unuttuğun yeri kontrol edebilirsin.", en: "You can delete the unused functions or check the place where you forgot to call them." },
+    Message { key: "ImplicitNumberToText.hint", tr: "Sayı ile yazı toplandığında sayı virgüllü olarak yazıya eklenir, 'Not: ' + 3.5 sonucu 'Not: 3,5' olur. 'x' + 1 + 2 sonucu 'x12' olur, sayıları önce toplamak için parantez kullan: 'x' + (1 + 2).", en: "When a number is added to a text it is written with a decimal comma, 'Not: ' + 3.5 gives 'Not: 3,5'. 'x' + 1 + 2 gives 'x12', use parentheses to add the numbers first: 'x' + (1 + 2)." },
+    Message { key: "CloseFunctionNotFound.hint", tr: "'birlikte' bloğu bittiğinde değerin 'kapat' fonksiyonu çağrılır. Değer 'kapat' fonksiyonu olan bir sözlük ya da nesne olmalı.", en: "The 'kapat' function of the value is called when the 'birlikte' block ends. The value should be a dictionary or an object that has a 'kapat' function." },
+    Message { key: "FrozenValueCannotBeChanged.hint", tr: "'baz::dondurulmuş' ile oluşturulan liste ve sözlüklere eleman eklenemez, elemanları değiştirilemez. Değiştirilebilir bir kopya için 'kopyala()' fonksiyonunu kullan.", en: "Items can not be added to or changed in the lists and dictionaries that are created with 'baz::dondurulmuş'. Use the 'kopyala()' function for a copy that can be changed." },
+    Message { key: "DictionaryKeyNotHashable.hint", tr: "Anahtar olarak yazı, sayı, bool, boş ya da 'baz::dondurulmuş' ile dondurulan liste ve sözlükler kullanılabilir. Değiştirilebilen liste ve sözlükler anahtar olamaz.", en: "Texts, numbers, bools, boş or the lists and dictionaries that are frozen with 'baz::dondurulmuş' can be used as keys. Lists and dictionaries that can be changed can not be keys." },
+    Message { key: "ValueTooLarge.hint", tr: "Yazı çok büyük bir sayı ile tekrarlandı ya da bir döngüde kendisi ile birleştirilerek büyütüldü. Sonucun boyutunu kontrol et.", en: "The text is repeated with a very big number or it is grown by joining it with itself in a loop. Check the size of the result." },
+    Message { key: "MemoryLimitExceeded.hint", tr: "Korumalı çalıştırmada oluşturulan yazı, bayt ve kopyaların toplam boyutu sınırlıdır. Büyüyen değerleri döngü içinde tekrar tekrar oluşturmaktan kaçın.", en: "The total size of the texts, bytes and copies that are created in the sandbox is limited. Avoid creating growing values again and again in a loop." },
+    Message { key: "DependencyNotInstalled.hint", tr: "Git bağımlılıkları program çalışırken indirilmez. Projenin dizininde 'karamelapp kur' komutunu çalıştır.", en: "Git dependencies are not downloaded while the program runs. Run the 'karamelapp kur' command in the directory of the project." },
+    Message { key: "InvalidSourceEncoding.hint", tr: "Dosyayı düzenleyicide UTF-8 olarak kaydet ya da kodlamayı --kodlama seçeneği ile ver, örneğin: karamelapp --kodlama windows-1254 ödev.k", en: "Save the file as UTF-8 in the editor or give the encoding with the --kodlama option, for example: karamelapp --kodlama windows-1254 ödev.k" },
+    Message { key: "ShadowedVariable.hint", tr: "Fonksiyonun içindeki değişken dışarıdaki değişkeni değiştirmez. Karışıklığı önlemek için farklı bir isim ver.", en: "The variable inside the function does not change the outer variable. Give it a different name to avoid confusion." },
+
+    // Lint
+    Message { key: "UnusedVariable", tr: "'{0}' tanımlanmış fakat hiç kullanılmamış", en: "'{0}' is defined but never used" },
+    Message { key: "ShadowedName", tr: "'{0}' aynı isimdeki fonksiyonu gölgeliyor", en: "'{0}' shadows the function with the same name" },
+    Message { key: "UnreadAssignment", tr: "'{0}' değişkenine atanan değer okunmadan üzerine yazılıyor", en: "The value assigned to '{0}' is overwritten before it is read" },
+    Message { key: "SelfComparison", tr: "Bir değer kendisi ile karşılaştırılıyor", en: "A value is compared with itself" },
+    Message { key: "ConstantCondition", tr: "Koşul her zaman aynı sonucu veriyor", en: "Condition always gives the same result" },
+    Message { key: "lint.rule_not_found", tr: "'{0}' kuralı bulunamadı", en: "'{0}' rule not found" },
+
+    // Diagnostics and execution
+    Message { key: "diagnostic.warning", tr: "Uyarı", en: "Warning" },
+    Message { key: "execution.hint", tr: "İpucu: {0}", en: "Hint: {0}" },
+    Message { key: "execution.failed", tr: "Program hata ile sonlandırıldı: {0}", en: "Program ended with an error: {0}" },
+    Message { key: "test.passed", tr: "GEÇTİ", en: "PASS" },
+    Message { key: "test.failed", tr: "KALDI", en: "FAIL" },
+    Message { key: "test.error", tr: "satır {0}: {1}", en: "line {0}: {1}" },
+    Message { key: "test.found", tr: "bulunan : {0}", en: "found   : {0}" },
+    Message { key: "test.expected", tr: "beklenen: {0}", en: "expected: {0}" },
+    Message { key: "test.output", tr: "Çıktı:", en: "Output:" },
+    Message { key: "test.summary", tr: "{0} test, {1} geçti, {2} kaldı", en: "{0} tests, {1} passed, {2} failed" },
+    Message { key: "test.not_found", tr: "'test_' ile başlayan fonksiyon bulunamadı", en: "No function starting with 'test_' was found" },
+    Message { key: "bench.title", tr: "{0}: {1} çalışma", en: "{0}: {1} runs" },
+    Message { key: "bench.min", tr: "en kısa", en: "min" },
+    Message { key: "bench.average", tr: "ortalama", en: "average" },
+    Message { key: "bench.p95", tr: "p95", en: "p95" },
+    Message { key: "bench.instructions", tr: "komut", en: "instructions" },
+    Message { key: "bench.baseline_invalid", tr: "'{0}' geçerli bir ölçüm dosyası değil", en: "'{0}' is not a valid benchmark file" },
+    Message { key: "runtime.call_stack", tr: "Çağrı yığını:", en: "Call stack:" },
+    Message { key: "runtime.main_code", tr: "ana kod", en: "main code" },
+    Message { key: "runtime.frame", tr: "    {0}, satır {1}", en: "    {0}, line {1}" },
+    Message { key: "runtime.frame_without_line", tr: "    {0}", en: "    {0}" },
+    Message { key: "runtime.frame_repeated", tr: "    ... aynı satırdan {0} çağrı daha", en: "    ... {0} more calls from the same line" },
+    Message { key: "execution.succeeded", tr: "Program başarıyla çalıştırıldı", en: "Program finished successfully" },
+    Message { key: "execution.error_report", tr: "Hata raporu: {0}", en: "Error report: {0}" },
+    Message { key: "execution.error_report_failed", tr: "Hata raporu yazılamadı: {0}", en: "Error report could not be written: {0}" },
+    Message { key: "execution.step_report", tr: "Adım raporu: {0} komut çalıştırıldı", en: "Step report: {0} instructions executed" },
+    Message { key: "execution.loop_report", tr: "    {line}. satırdaki döngü {count} kez döndü", en: "    Loop at line {line} iterated {count} times" },
+    Message { key: "events.write_failed", tr: "Olay akışı yazılamadı: {0}", en: "Event stream could not be written: {0}" },
+    Message { key: "trace.write_failed", tr: "Komut izi yazılamadı: {0}", en: "Instruction trace could not be written: {0}" },
+
+    // REPL
+    Message { key: "repl.welcome", tr: "Anahtar kelimeler için ':açıkla döngü', çıkmak için ':çık' yazınız.", en: "Type ':açıkla döngü' for keywords, ':çık' to exit." },
+    Message { key: "repl.not_keyword", tr: "'{0}' bir anahtar kelime değil", en: "'{0}' is not a keyword" },
+
+    // Command line
+    Message { key: "cli.about", tr: "Karamel Programlama Dili Derleyicisi.\n\nhttps://github.com/erhanbaris/karamel", en: "Karamel Programming Language Compiler.\n\nhttps://github.com/erhanbaris/karamel" },
+    Message { key: "cli.file", tr: "Çalıştırılacak karamel dosyası, standart girdi için -", en: "Karamel file to run, - for the standard input" },
+    Message { key: "cli.encoding", tr: "Dosyaların kodlaması. 'otomatik' UTF-8 olmayan dosyaların kodlamasını tahmin eder. Verilmezse dosyalar UTF-8 olarak okunur", en: "Encoding of the files. 'otomatik' guesses the encoding of the files that are not UTF-8. Files are read as UTF-8 when it is not given" },
+    Message { key: "cli.script", tr: "Çalıştırılacak karamel dosyası. '#!/usr/bin/env karamelapp' satırı ile başlayan dosyalar doğrudan çalıştırılabilir", en: "Karamel file to run. Files starting with the '#!/usr/bin/env karamelapp' line can be run directly" },
+    Message { key: "cli.teaching", tr: "Öğretim kipinde çalıştırır. Hata mesajları açıklamalı verilir, ileri seviye özellikler kapatılır ve döngülere adım sınırı konulur", en: "Runs in the teaching mode. Error messages are explained, advanced features are disabled and loops get a step limit" },
+    Message { key: "cli.gc_threshold", tr: "Çöp toplayıcı çalışmadan önce oluşturulabilecek değer sayısı", en: "Count of the values that can be created before the garbage collector runs" },
+    Message { key: "cli.gc_threshold.invalid", tr: "'{0}' geçerli bir sayı değil", en: "'{0}' is not a valid number" },
+    Message { key: "cli.call_depth", tr: "İç içe yapılabilecek fonksiyon çağrısı sayısı", en: "Count of the function calls that can be nested" },
+    Message { key: "cli.call_depth.invalid", tr: "'{0}' geçerli bir çağrı derinliği değil", en: "'{0}' is not a valid call depth" },
+    Message { key: "cli.trace", tr: "Çalıştırılan her komutu, değerleri ve yığın değişikliği ile JSON satırları olarak dosyaya yazar. '-' verilirse ekrana yazar", en: "Writes every executed instruction with its values and stack change to the file as JSON lines. Writes to the screen if '-' is given" },
+    Message { key: "cli.events", tr: "Satır, değişken ve fonksiyon olaylarını JSON satırları olarak dosyaya yazar. '-' verilirse ekrana yazar", en: "Writes line, variable and function events to the file as JSON lines. Writes to the screen if '-' is given" },
+    Message { key: "cli.json", tr: "Çalışma sonunda hata ve uyarıları JSON satırları olarak yazar", en: "Writes the errors and warnings as JSON lines at the end of the execution" },
+    Message { key: "cli.emit", tr: "Dosyayı çalıştırmadan derler ve üretilen komutları yazar. 'listing' her satırın altına o satır için üretilen komutları yazar", en: "Compiles the file without running it and writes the generated opcodes. 'listing' writes the opcodes generated for each line under that line" },
+    Message { key: "cli.assumptions", tr: "'varsay' satırlarını derler ve koşulları kontrol eder. Verilmezse 'varsay' satırları çalıştırılmaz", en: "Compiles the 'varsay' lines and checks their conditions. Without it the 'varsay' lines are not run" },
+    Message { key: "cli.type_guards", tr: "Türü yazılan fonksiyon parametrelerini fonksiyon çağrılırken kontrol eder", en: "Checks the function parameters that have a type when the function is called" },
+    Message { key: "cli.run", tr: "Karamel dosyasını çalıştırır", en: "Runs the Karamel file" },
+    Message { key: "cli.compile", tr: "Karamel dosyasını çalıştırmadan derler ve hataları yazar", en: "Compiles the Karamel file without running it and writes the errors" },
+    Message { key: "cli.compile.file", tr: "Derlenecek karamel dosyası, standart girdi için -", en: "Karamel file to compile, - for the standard input" },
+    Message { key: "cli.compile.emit", tr: "Üretilen komutları yazar. 'listing' her satırın altına o satır için üretilen komutları yazar", en: "Writes the generated opcodes. 'listing' writes the opcodes generated for each line under that line" },
+    Message { key: "cli.format", tr: "Karamel dosyasını standart biçime getirir", en: "Formats the Karamel file in the standard style" },
+    Message { key: "cli.format.file", tr: "Biçimlendirilecek karamel dosyası", en: "Karamel file to format" },
+    Message { key: "cli.format.write", tr: "Sonucu ekrana basmak yerine dosyaya yazar", en: "Writes the result to the file instead of the screen" },
+    Message { key: "cli.lint", tr: "Karamel dosyasındaki şüpheli kodları raporlar", en: "Reports the suspicious code in the Karamel file" },
+    Message { key: "cli.lint.file", tr: "İncelenecek karamel dosyası", en: "Karamel file to inspect" },
+    Message { key: "cli.lint.disable", tr: "Kapatılacak kural kodları. Örnek: L001,L003", en: "Rule codes to disable. Example: L001,L003" },
+    Message { key: "cli.lint.json", tr: "Uyarıları JSON satırları olarak yazar", en: "Writes the warnings as JSON lines" },
+    Message { key: "cli.test", tr: "Dosyadaki 'test_' ile başlayan fonksiyonları ayrı ayrı çalıştırır ve sonuçlarını tablo olarak yazar", en: "Runs the functions starting with 'test_' in the file one by one and writes their results as a table" },
+    Message { key: "cli.test.file", tr: "Test edilecek karamel dosyası", en: "Karamel file to test" },
+    Message { key: "cli.bench", tr: "Dosyayı veya içindeki bir fonksiyonu defalarca çalıştırır, süre ve komut sayılarını yazar", en: "Runs the file or a function in it many times and writes the times and instruction counts" },
+    Message { key: "cli.bench.file", tr: "Ölçülecek karamel dosyası", en: "Karamel file to measure" },
+    Message { key: "cli.bench.iterations", tr: "Ölçülen çalışma sayısı. Varsayılan 10", en: "Count of the measured runs. Default is 10" },
+    Message { key: "cli.bench.iterations.invalid", tr: "'{0}' geçerli bir tekrar sayısı değil", en: "'{0}' is not a valid iteration count" },
+    Message { key: "cli.bench.warmup", tr: "Ölçümden önce yapılan çalışma sayısı. Varsayılan 2", en: "Count of the runs before the measurement. Default is 2" },
+    Message { key: "cli.bench.warmup.invalid", tr: "'{0}' geçerli bir ısınma sayısı değil", en: "'{0}' is not a valid warmup count" },
+    Message { key: "cli.bench.function", tr: "Ana koddaki ölçülecek fonksiyon. Ana kod bir kere çalıştırıldıktan sonra sadece fonksiyon çağrısı ölçülür", en: "Function of the main code to measure. Main code runs once and only the function call is measured" },
+    Message { key: "cli.bench.save", tr: "Sonucu daha sonra karşılaştırmak için JSON olarak dosyaya yazar", en: "Writes the result to the file as JSON to compare later" },
+    Message { key: "cli.bench.compare", tr: "Sonucu daha önce kaydedilen JSON dosyası ile karşılaştırır", en: "Compares the result with the JSON file that was saved before" },
+    Message { key: "cli.transpile", tr: "Karamel dosyasını başka bir dilde yazılmış koda çevirir", en: "Translates the Karamel file to the code of another language" },
+    Message { key: "cli.transpile.file", tr: "Çevrilecek karamel dosyası", en: "Karamel file to translate" },
+    Message { key: "cli.transpile.target", tr: "Hedef dil: javascript ya da python. Varsayılan javascript", en: "Target language: javascript or python. Default is javascript" },
+    Message { key: "cli.transpile.output", tr: "Sonucu ekrana basmak yerine verilen dosyaya yazar", en: "Writes the result to the given file instead of the screen" },
+    Message { key: "cli.install", tr: "küfe.toml dosyasındaki git bağımlılıklarını .küfe dizinine indirir ve küfe.kilit dosyasını günceller", en: "Downloads the git dependencies of the küfe.toml file to the .küfe directory and updates the küfe.kilit file" },
+    Message { key: "cli.install.directory", tr: "küfe.toml dosyasının bulunduğu dizin. Varsayılan bulunulan dizin", en: "Directory of the küfe.toml file. Default is the current directory" },
+    Message { key: "cli.install.done", tr: "'{0}' paketi kuruldu", en: "'{0}' package is installed" },
+    Message { key: "cli.repl", tr: "Karamel kodlarını satır satır çalıştıran etkileşimli kabuğu açar", en: "Opens the interactive shell that runs Karamel code line by line" },
+];
+
+/* Catalog is checked while compiling, missing translations and duplicated keys break the build */
+const _: () = check_catalog(MESSAGES);
+
+const fn same_key(left: &str, right: &str) -> bool {
+    let left = left.as_bytes();
+    let right = right.as_bytes();
+    if left.len() != right.len() {
+        return false;
+    }
+
+    let mut index = 0;
+    while index < left.len() {
+        if left[index] != right[index] {
+            return false;
+        }
+        index += 1;
+    }
+    true
+}
+
+const fn check_catalog(messages: &[Message]) {
+    let mut index = 0;
+    while index < messages.len() {
+        let message = &messages[index];
+        if message.key.is_empty() || message.tr.is_empty() || message.en.is_empty() {
+            panic!("Mesaj katalogunda Türkçe ya da İngilizce karşılığı olmayan anahtar var");
+        }
+
+        let mut other = index + 1;
+        while other < messages.len() {
+            if same_key(message.key, messages[other].key) {
+                panic!("Mesaj katalogunda aynı anahtar birden fazla kullanılmış");
+            }
+            other += 1;
+        }
+        index += 1;
+    }
+}
@@ -0,0 +1,118 @@
+mod catalog;
+
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::sync::Mutex;
+
+use lazy_static::*;
+
+pub use catalog::MESSAGES;
+
+pub static DEFAULT_LANGUAGE: &str = "tr";
+pub static ENGLISH_LANGUAGE: &str = "en";
+
+/// Turkish and English texts of a message. Other languages are registered at runtime.
+pub struct Message {
+    pub key: &'static str,
+    pub tr: &'static str,
+    pub en: &'static str
+}
+
+lazy_static! {
+    static ref MESSAGE_INDEX: HashMap<&'static str, &'static Message> = MESSAGES.iter().map(|message| (message.key, message)).collect();
+    static ref LANGUAGES: Mutex<HashMap<String, HashMap<String, String>>> = Mutex::new(HashMap::new());
+    static ref CURRENT_LANGUAGE: Mutex<String> = Mutex::new(DEFAULT_LANGUAGE.to_string());
+}
+
+/// Adds a new language or new texts to an already registered language. Missing keys are shown in Turkish.
+pub fn register_language<I, K, V>(language: &str, messages: I) where I: IntoIterator<Item = (K, V)>, K: Into<String>, V: Into<String> {
+    let mut languages = LANGUAGES.lock().unwrap();
+    let texts = languages.entry(language.to_string()).or_default();
+    for (key, text) in messages {
+        texts.insert(key.into(), text.into());
+    }
+}
+
+/// Changes the language of the messages. Returns false if the language is not known.
+pub fn set_language(language: &str) -> bool {
+    match is_language_known(language) {
+        true => {
+            *CURRENT_LANGUAGE.lock().unwrap() = language.to_string();
+            true
+        },
+        false => false
+    }
+}
+
+pub fn get_language() -> String {
+    CURRENT_LANGUAGE.lock().unwrap().clone()
+}
+
+pub fn is_language_known(language: &str) -> bool {
+    language == DEFAULT_LANGUAGE || language == ENGLISH_LANGUAGE || LANGUAGES.lock().unwrap().contains_key(language)
+}
+
+pub fn has_message(key: &str) -> bool {
+    MESSAGE_INDEX.contains_key(key)
+}
+
+/// Text of the key in the current language.
+pub fn text(key: &str) -> String {
+    text_in(&get_language(), key)
+}
+
+/// Text of the key in the given language. Falls back to Turkish, then to the key itself.
+pub fn text_in(language: &str, key: &str) -> String {
+    if let Some(text) = LANGUAGES.lock().unwrap().get(language).and_then(|texts| texts.get(key)) {
+        return text.clone();
+    }
+
+    match MESSAGE_INDEX.get(key) {
+        Some(message) if language == ENGLISH_LANGUAGE => message.en.to_string(),
+        Some(message) => message.tr.to_string(),
+        None => key.to_string()
+    }
+}
+
+/// Text of the key in the current language with the '{name}' placeholders filled.
+pub fn format(key: &str, arguments: &[(&str, &dyn Display)]) -> String {
+    format_in(&get_language(), key, arguments)
+}
+
+pub fn format_in(language: &str, key: &str, arguments: &[(&str, &dyn Display)]) -> String {
+    let mut message = text_in(language, key);
+    for (name, value) in arguments.iter() {
+        message = message.replace(&format!("{{{}}}", name), &value.to_string());
+    }
+    message
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn english_and_turkish_texts() {
+        assert_eq!(text_in(DEFAULT_LANGUAGE, "SyntaxError"), "Sozdizimi hatasi");
+        assert_eq!(text_in(ENGLISH_LANGUAGE, "SyntaxError"), "Syntax error");
+        assert_eq!(text_in(ENGLISH_LANGUAGE, "bilinmeyen.anahtar"), "bilinmeyen.anahtar");
+    }
+
+    #[test]
+    fn format_arguments() {
+        assert_eq!(format_in(ENGLISH_LANGUAGE, "FunctionArgumentNotMatching", &[("function", &"topla"), ("expected", &2), ("found", &3)]),
+            "'topla' function arguments do not match. Expected 2 but found 3");
+        assert_eq!(format_in(DEFAULT_LANGUAGE, "VariableNotDefined", &[("0", &"a")]), "'a' değişkeni tanımlanmamış");
+    }
+
+    #[test]
+    fn registered_language() {
+        assert!(!is_language_known("az"));
+        register_language("az", vec![("VariableNotDefined", "'{0}' dəyişəni təyin edilməyib")]);
+        assert!(is_language_known("az"));
+        assert_eq!(format_in("az", "VariableNotDefined", &[("0", &"a")]), "'a' dəyişəni təyin edilməyib");
+
+        /* Missing texts are shown in Turkish */
+        assert_eq!(text_in("az", "SyntaxError"), "Sozdizimi hatasi");
+    }
+}
@@ -0,0 +1,457 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+#[cfg(not(target_arch = "wasm32"))]
+use std::process::Command;
+
+use crate::compiler::KaramelCompilerContext;
+use crate::constants::{KARAMEL_PACKAGE_DIRECTORY, KARAMEL_PACKAGE_LOCK, KARAMEL_PACKAGE_MANIFEST};
+use crate::error::KaramelErrorType;
+use crate::file::FileSystem;
+
+/// Where the files of a dependency come from
+#[derive(Clone, Debug, PartialEq)]
+pub enum PackageSource {
+    /// Directory relative to the package that depends on it
+    Path(String),
+
+    /// Repository is cloned into the '.küfe' directory of the project. Revision is a branch, tag or commit.
+    Git { url: String, revision: Option<String> }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Dependency {
+    pub name: String,
+    pub source: PackageSource
+}
+
+/// Content of the 'küfe.toml' file
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Manifest {
+    pub name: Option<String>,
+    pub version: Option<String>,
+    pub dependencies: Vec<Dependency>
+}
+
+/// Dependency after it is found. Directory is relative to the project, commit is only known for the git dependencies.
+#[derive(Clone, Debug, PartialEq)]
+pub struct LockedPackage {
+    pub name: String,
+    pub source: PackageSource,
+    pub directory: String,
+    pub commit: Option<String>
+}
+
+enum Value {
+    Text(String),
+    Table(Vec<(String, String)>)
+}
+
+/* Lines of the file as (line, section, key, value). Only the texts and the inline tables of the texts are supported. */
+fn parse_lines(content: &str, file: &str) -> Result<Vec<(usize, String, String, Value)>, KaramelErrorType> {
+    let mut items = Vec::new();
+    let mut section = String::new();
+
+    for (index, line) in content.lines().enumerate() {
+        let error = || KaramelErrorType::InvalidPackageFile { file: file.to_string(), line: index + 1 };
+        let line = strip_comment(line).trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        if let Some(name) = line.strip_prefix('[') {
+            section = parse_key(name.strip_suffix(']').ok_or_else(error)?).ok_or_else(error)?;
+            continue;
+        }
+
+        let (key, value) = line.split_once('=').ok_or_else(error)?;
+        let key = parse_key(key).ok_or_else(error)?;
+        let value = value.trim();
+        let value = match value.strip_prefix('{') {
+            Some(table) => Value::Table(parse_table(table.strip_suffix('}').ok_or_else(error)?).ok_or_else(error)?),
+            None => Value::Text(parse_text(value).ok_or_else(error)?)
+        };
+        items.push((index + 1, section.clone(), key, value));
+    }
+    Ok(items)
+}
+
+/* '#' starts a comment when it is not in a text */
+fn strip_comment(line: &str) -> &str {
+    let mut in_text = false;
+    for (index, chr) in line.char_indices() {
+        match chr {
+            '"' => in_text = !in_text,
+            '#' if !in_text => return &line[..index],
+            _ => ()
+        };
+    }
+    line
+}
+
+/* Keys can be written with the Turkish letters without the quotes */
+fn parse_key(key: &str) -> Option<String> {
+    let key = key.trim();
+    if key.starts_with('"') {
+        return parse_text(key);
+    }
+
+    match !key.is_empty() && key.chars().all(|chr| chr.is_alphanumeric() || chr == '_' || chr == '-' || chr == '.') {
+        true => Some(key.to_string()),
+        false => None
+    }
+}
+
+fn parse_text(value: &str) -> Option<String> {
+    let text = value.trim().strip_prefix('"')?.strip_suffix('"')?;
+    match text.contains('"') {
+        true => None,
+        false => Some(text.replace("\\\\", "\\"))
+    }
+}
+
+fn parse_table(table: &str) -> Option<Vec<(String, String)>> {
+    let mut items = Vec::new();
+    for item in table.split(',') {
+        if item.trim().is_empty() {
+            continue;
+        }
+
+        let (key, value) = item.split_once('=')?;
+        items.push((parse_key(key)?, parse_text(value)?));
+    }
+    Some(items)
+}
+
+/* Names are used as directory names at the '.küfe' directory, so they can not point to another directory */
+fn valid_name(name: &str) -> bool {
+    !name.starts_with('.') && !name.contains("..") && name.chars().all(|chr| chr.is_alphanumeric() || chr == '_' || chr == '-' || chr == '.')
+}
+
+/* Values that start with '-' would be read as an option by git */
+fn valid_git_argument(value: &str) -> bool {
+    !value.is_empty() && !value.starts_with('-')
+}
+
+fn table_value<'a>(table: &'a [(String, String)], keys: &[&str]) -> Option<&'a String> {
+    table.iter().find(|(key, _)| keys.contains(&key.as_str())).map(|(_, value)| value)
+}
+
+fn write_text(buffer: &mut String, key: &str, value: &str) {
+    buffer.push_str(&format!("{} = \"{}\"\n", key, value.replace('\\', "\\\\")));
+}
+
+impl Manifest {
+    pub fn parse(content: &str) -> Result<Manifest, KaramelErrorType> {
+        let mut manifest = Manifest::default();
+        for (line, section, key, value) in parse_lines(content, KARAMEL_PACKAGE_MANIFEST)? {
+            let error = KaramelErrorType::InvalidPackageFile { file: KARAMEL_PACKAGE_MANIFEST.to_string(), line };
+
+            match (section.as_str(), key.as_str(), value) {
+                ("paket", "ad", Value::Text(name)) => manifest.name = Some(name),
+                ("paket", "sürüm" | "surum", Value::Text(version)) => manifest.version = Some(version),
+                ("bağımlılıklar" | "bagimliliklar", _, Value::Table(table)) if valid_name(&key) => {
+                    let source = match (table_value(&table, &["yol"]), table_value(&table, &["git"]), table_value(&table, &["sürüm", "surum"])) {
+                        (Some(path), None, _) => PackageSource::Path(path.to_string()),
+                        (None, Some(url), revision) if valid_git_argument(url) && revision.is_none_or(|revision| valid_git_argument(revision)) =>
+                            PackageSource::Git { url: url.to_string(), revision: revision.cloned() },
+                        _ => return Err(error)
+                    };
+                    manifest.dependencies.push(Dependency { name: key, source });
+                },
+                _ => return Err(error)
+            };
+        }
+        Ok(manifest)
+    }
+}
+
+/// Packages of the 'küfe.kilit' file. Lock file is created by the loader, broken files are ignored and written again.
+pub fn parse_lock(content: &str) -> Option<Vec<LockedPackage>> {
+    let mut sections: Vec<(String, Vec<(String, String)>)> = Vec::new();
+    for (_, section, key, value) in parse_lines(content, KARAMEL_PACKAGE_LOCK).ok()? {
+        let value = match value {
+            Value::Text(value) => value,
+            Value::Table(_) => return None
+        };
+
+        match sections.last_mut() {
+            Some((name, items)) if *name == section => items.push((key, value)),
+            _ => sections.push((section, vec![(key, value)]))
+        };
+    }
+
+    sections.into_iter().map(|(name, items)| {
+        let directory = table_value(&items, &["dizin"])?.to_string();
+        let source = match (table_value(&items, &["yol"]), table_value(&items, &["git"])) {
+            (Some(path), None) => PackageSource::Path(path.to_string()),
+            (None, Some(url)) => PackageSource::Git { url: url.to_string(), revision: table_value(&items, &["sürüm"]).cloned() },
+            _ => return None
+        };
+
+        let commit = table_value(&items, &["işleme"]).cloned();
+        match valid_name(&name) && commit.as_deref().is_none_or(valid_git_argument) {
+            true => Some(LockedPackage { name, source, directory, commit }),
+            false => None
+        }
+    }).collect()
+}
+
+/// Content of the 'küfe.kilit' file. Packages are sorted by their names so the file only changes with the dependencies.
+pub fn write_lock(packages: &[LockedPackage]) -> String {
+    let mut packages = packages.to_vec();
+    packages.sort_by(|left, right| left.name.cmp(&right.name));
+
+    let mut buffer = String::from("# Bu dosya küfe tarafından oluşturulur, elle değiştirmeyin.\n");
+    for package in packages.iter() {
+        buffer.push_str(&format!("\n[{}]\n", package.name));
+        match &package.source {
+            PackageSource::Path(path) => write_text(&mut buffer, "yol", path),
+            PackageSource::Git { url, revision } => {
+                write_text(&mut buffer, "git", url);
+                if let Some(revision) = revision {
+                    write_text(&mut buffer, "sürüm", revision);
+                }
+            }
+        };
+        write_text(&mut buffer, "dizin", &package.directory);
+        if let Some(commit) = &package.commit {
+            write_text(&mut buffer, "işleme", commit);
+        }
+    }
+    buffer
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn git(arguments: &[&str]) -> Option<String> {
+    let output = Command::new("git").args(arguments).output().ok()?;
+    match output.status.success() {
+        true => Some(String::from_utf8_lossy(&output.stdout).trim().to_string()),
+        false => None
+    }
+}
+
+/* Repository is cloned once. Locked commit is used until the source at the manifest changes. */
+#[cfg(not(target_arch = "wasm32"))]
+fn fetch_git(directory: &Path, url: &str, target: Option<&str>) -> Option<String> {
+    let directory_name = directory.to_str()?;
+    if !directory.is_dir() {
+        git(&["clone", "--quiet", "--", url, directory_name])?;
+    }
+
+    if let Some(target) = target {
+        /* '--' after the target makes git read it as a revision and not as a file */
+        if git(&["-C", directory_name, "checkout", "--quiet", target, "--"]).is_none() {
+            git(&["-C", directory_name, "fetch", "--quiet", "origin"])?;
+            git(&["-C", directory_name, "checkout", "--quiet", target, "--"])?;
+        }
+    }
+    git(&["-C", directory_name, "rev-parse", "HEAD"])
+}
+
+#[cfg(target_arch = "wasm32")]
+fn fetch_git(_: &Path, _: &str, _: Option<&str>) -> Option<String> {
+    None
+}
+
+struct PackageResolver<'a> {
+    project: PathBuf,
+    file_system: &'a dyn FileSystem,
+    locked: Vec<LockedPackage>,
+    packages: Vec<LockedPackage>,
+    fetch: bool
+}
+
+impl PackageResolver<'_> {
+    fn resolve(&mut self, manifest: &Manifest, base: &Path) -> Result<(), KaramelErrorType> {
+        for dependency in manifest.dependencies.iter() {
+            let (directory, commit) = match &dependency.source {
+                PackageSource::Path(path) => (base.join(path), None),
+                PackageSource::Git { url, revision } => {
+                    let directory = Path::new(KARAMEL_PACKAGE_DIRECTORY).join(&dependency.name);
+                    let locked = self.locked.iter().find(|locked| locked.name == dependency.name && locked.source == dependency.source).and_then(|locked| locked.commit.clone());
+                    let commit = match self.fetch {
+                        true => {
+                            let target = locked.as_ref().or(revision.as_ref()).map(|target| target.as_str());
+                            fetch_git(&self.project.join(&directory), url, target).ok_or_else(|| KaramelErrorType::DependencyNotFound(dependency.name.to_string()))?
+                        },
+
+                        /* Programs are only run with the repositories that are installed before */
+                        false => match (locked, self.file_system.list(&self.project.join(&directory).to_string_lossy())) {
+                            (Some(commit), Ok(_)) => commit,
+                            _ => return Err(KaramelErrorType::DependencyNotInstalled(dependency.name.to_string()))
+                        }
+                    };
+                    (directory, Some(commit))
+                }
+            };
+
+            let directory_name = directory.to_string_lossy().replace('\\', "/");
+            if let Some(package) = self.packages.iter().find(|package| package.name == dependency.name) {
+                match package.directory == directory_name {
+                    true => continue,
+                    false => return Err(KaramelErrorType::DependencyConflict(dependency.name.to_string()))
+                };
+            }
+
+            let full_directory = self.project.join(&directory);
+            if self.file_system.list(&full_directory.to_string_lossy()).is_err() {
+                return Err(KaramelErrorType::DependencyNotFound(dependency.name.to_string()));
+            }
+
+            self.packages.push(LockedPackage {
+                name: dependency.name.to_string(),
+                source: dependency.source.clone(),
+                directory: directory_name,
+                commit
+            });
+
+            /* Dependencies of the dependency are relative to its directory */
+            let manifest_path = full_directory.join(KARAMEL_PACKAGE_MANIFEST);
+            if self.file_system.is_file(&manifest_path.to_string_lossy()) {
+                let manifest = Manifest::parse(&self.file_system.read(&manifest_path.to_string_lossy())?)?;
+                self.resolve(&manifest, &directory)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Finds the dependencies of the 'küfe.toml' file of the project. Modules of the packages are loaded with the package name,
+/// 'kullan matematik::vektör' reads 'vektör.k' of the 'matematik' package. Git repositories are not downloaded, they are
+/// read from the '.küfe' directory with the commits of the lock file.
+pub fn load_packages(context: &mut KaramelCompilerContext) -> Result<(), KaramelErrorType> {
+    resolve_packages(context, false)
+}
+
+/// Downloads the git dependencies of the project and updates the lock file
+pub fn install_packages(context: &mut KaramelCompilerContext) -> Result<(), KaramelErrorType> {
+    resolve_packages(context, true)
+}
+
+fn resolve_packages(context: &mut KaramelCompilerContext, fetch: bool) -> Result<(), KaramelErrorType> {
+    let execution_path = PathBuf::from(&context.execution_path.path);
+    let project = match context.file_system.is_file(&context.execution_path.path) {
+        true => execution_path.parent().map(Path::to_path_buf).unwrap_or_default(),
+        false => execution_path
+    };
+
+    let manifest_path = project.join(KARAMEL_PACKAGE_MANIFEST).to_string_lossy().to_string();
+    if !context.file_system.is_file(&manifest_path) {
+        return Ok(());
+    }
+
+    let manifest = Manifest::parse(&context.file_system.read(&manifest_path)?)?;
+    let lock_path = project.join(KARAMEL_PACKAGE_LOCK).to_string_lossy().to_string();
+    let lock = context.file_system.read(&lock_path).ok();
+
+    let file_system = context.file_system.clone();
+    let mut resolver = PackageResolver {
+        project: project.clone(),
+        file_system: &*file_system,
+        locked: lock.as_deref().and_then(parse_lock).unwrap_or_default(),
+        packages: Vec::new(),
+        fetch
+    };
+    resolver.resolve(&manifest, Path::new(""))?;
+
+    let content = write_lock(&resolver.packages);
+    if fetch && lock.as_deref() != Some(&content[..]) {
+        if let Err(error) = context.file_system.write(&lock_path, &content) {
+            log::warn!("{}", error);
+        }
+    }
+
+    context.packages = resolver.packages.into_iter()
+        .map(|package| (package.name, project.join(package.directory).to_string_lossy().to_string()))
+        .collect::<HashMap<String, String>>();
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::rc::Rc;
+
+    use super::*;
+    use crate::file::MemoryFileSystem;
+
+    #[test]
+    fn manifest() {
+        let manifest = Manifest::parse("[paket]\nad = \"uygulama\" # ana paket\nsürüm = \"0.1.0\"\n\n[bağımlılıklar]\nmatematik = { yol = \"../matematik\" }\ngrafik = { git = \"https://ornek.org/grafik.git\", sürüm = \"v1\" }").unwrap();
+        assert_eq!(manifest.name, Some("uygulama".to_string()));
+        assert_eq!(manifest.version, Some("0.1.0".to_string()));
+        assert_eq!(manifest.dependencies, vec![
+            Dependency { name: "matematik".to_string(), source: PackageSource::Path("../matematik".to_string()) },
+            Dependency { name: "grafik".to_string(), source: PackageSource::Git { url: "https://ornek.org/grafik.git".to_string(), revision: Some("v1".to_string()) } }
+        ]);
+    }
+
+    #[test]
+    fn invalid_manifest() {
+        let error = |line| Err(KaramelErrorType::InvalidPackageFile { file: KARAMEL_PACKAGE_MANIFEST.to_string(), line });
+        assert_eq!(Manifest::parse("[paket]\nad = uygulama"), error(2));
+        assert_eq!(Manifest::parse("[bağımlılıklar]\n\nmatematik = \"../matematik\""), error(3));
+        assert_eq!(Manifest::parse("[bağımlılıklar]\nmatematik = { yol = \"a\", git = \"b\" }"), error(2));
+        assert_eq!(Manifest::parse("[paket]\nyazar = \"ben\""), error(2));
+        assert_eq!(Manifest::parse("[bağımlılıklar]\n\"../dış\" = { yol = \"a\" }"), error(2));
+        assert_eq!(Manifest::parse("[bağımlılıklar]\n\"a/b\" = { git = \"https://ornek.org/a.git\" }"), error(2));
+        assert_eq!(Manifest::parse("[bağımlılıklar]\n.. = { git = \"https://ornek.org/a.git\" }"), error(2));
+        assert_eq!(Manifest::parse("[bağımlılıklar]\na = { git = \"--upload-pack=touch x\" }"), error(2));
+        assert_eq!(Manifest::parse("[bağımlılıklar]\na = { git = \"https://ornek.org/a.git\", sürüm = \"-b\" }"), error(2));
+    }
+
+    #[test]
+    fn lock_file() {
+        let packages = vec![
+            LockedPackage { name: "grafik".to_string(), source: PackageSource::Git { url: "https://ornek.org/grafik.git".to_string(), revision: None }, directory: ".küfe/grafik".to_string(), commit: Some("4b825dc".to_string()) },
+            LockedPackage { name: "araç".to_string(), source: PackageSource::Path("araç".to_string()), directory: "araç".to_string(), commit: None }
+        ];
+
+        let content = write_lock(&packages);
+        assert!(content.find("[araç]").unwrap() < content.find("[grafik]").unwrap());
+        assert_eq!(parse_lock(&content), Some(vec![packages[1].clone(), packages[0].clone()]));
+        assert_eq!(parse_lock("[araç]\nyol = \"araç\""), None);
+        assert_eq!(parse_lock("[\"../araç\"]\nyol = \"araç\"\ndizin = \"araç\""), None);
+        assert_eq!(parse_lock("[grafik]\ngit = \"https://ornek.org/grafik.git\"\ndizin = \".küfe/grafik\"\nişleme = \"--output=x\""), None);
+    }
+
+    #[test]
+    fn path_dependencies() {
+        let file_system = Rc::new(MemoryFileSystem::new());
+        file_system.add_file("proje/küfe.toml", "[bağımlılıklar]\nmatematik = { yol = \"../matematik\" }");
+        file_system.add_file("matematik/baz.k", "fonk kare(x):\n    döndür x * x");
+        file_system.add_file("matematik/küfe.toml", "[bağımlılıklar]\nsabit = { yol = \"sabit\" }");
+        file_system.add_file("matematik/sabit/baz.k", "");
+
+        let mut context = KaramelCompilerContext::new();
+        context.file_system = file_system.clone();
+        context.execution_path.path = "proje".to_string();
+        load_packages(&mut context).unwrap();
+        assert!(file_system.read("proje/küfe.kilit").is_err());
+
+        install_packages(&mut context).unwrap();
+        assert_eq!(context.packages.get("matematik"), Some(&"proje/../matematik".to_string()));
+        assert_eq!(context.packages.get("sabit"), Some(&"proje/../matematik/sabit".to_string()));
+        assert!(file_system.read("proje/küfe.kilit").unwrap().contains("[sabit]\nyol = \"sabit\"\ndizin = \"../matematik/sabit\"\n"));
+
+        file_system.add_file("proje/küfe.toml", "[bağımlılıklar]\nyok = { yol = \"yok\" }");
+        assert_eq!(load_packages(&mut context), Err(KaramelErrorType::DependencyNotFound("yok".to_string())));
+    }
+
+    #[test]
+    fn git_dependencies_are_not_fetched_on_run() {
+        let file_system = Rc::new(MemoryFileSystem::new());
+        file_system.add_file("proje/küfe.toml", "[bağımlılıklar]\ngrafik = { git = \"https://ornek.org/grafik.git\" }");
+
+        let mut context = KaramelCompilerContext::new();
+        context.file_system = file_system.clone();
+        context.execution_path.path = "proje".to_string();
+        assert_eq!(load_packages(&mut context), Err(KaramelErrorType::DependencyNotInstalled("grafik".to_string())));
+
+        file_system.add_file("proje/küfe.kilit", "[grafik]\ngit = \"https://ornek.org/grafik.git\"\ndizin = \".küfe/grafik\"\nişleme = \"4b825dc\"");
+        assert_eq!(load_packages(&mut context), Err(KaramelErrorType::DependencyNotInstalled("grafik".to_string())));
+
+        file_system.add_file("proje/.küfe/grafik/baz.k", "");
+        load_packages(&mut context).unwrap();
+        assert_eq!(context.packages.get("grafik"), Some(&"proje/.küfe/grafik".to_string()));
+    }
+}
@@ -1,18 +1,25 @@
+use std::rc::Rc;
 use crate::types::*;
 use crate::error::KaramelErrorType;
 
-pub struct CommentParser;
+pub struct CommentParser {
+    pub keep_comments: bool
+}
 
 impl TokenParser for CommentParser {
     fn check(&self, tokinizer: &mut Tokinizer) -> bool {
         let ch      = tokinizer.get_char();
         let ch_next = tokinizer.get_next_char();
-        return (ch == '/' && ch_next == '*') || (ch == '/' && ch_next == '/');
+        return (ch == '/' && ch_next == '*') || (ch == '/' && ch_next == '/') || (ch == '#' && ch_next == '#');
     }
 
     fn parse(&self, tokinizer: &mut Tokinizer) -> Result<(), KaramelErrorType> {
         let mut ch                   = tokinizer.get_char();
         let mut ch_next              = tokinizer.get_next_char();
+        let start                    = tokinizer.index as usize;
+        let start_line               = tokinizer.line;
+        let start_column             = tokinizer.column;
+        let ch_start                 = ch;
 
         if ch == '/' && ch_next == '*' {
             let mut comment_end = false;
@@ -54,6 +61,26 @@ impl TokenParser for CommentParser {
             }
         }
 
+        /* Documentation is a part of the program, so it is always kept */
+        if ch_start == '#' {
+            let documentation = tokinizer.data[start + 2..tokinizer.index as usize].trim().to_string();
+            tokinizer.tokens.push(Token {
+                line: start_line,
+                start: start_column,
+                end: tokinizer.column,
+                token_type: KaramelTokenType::DocComment(Rc::new(documentation))
+            });
+        }
+        else if self.keep_comments {
+            let comment = tokinizer.data[start..tokinizer.index as usize].trim_end().to_string();
+            tokinizer.tokens.push(Token {
+                line: start_line,
+                start: start_column,
+                end: tokinizer.column,
+                token_type: KaramelTokenType::Comment(Rc::new(comment))
+            });
+        }
+
         return Ok(());
     }
 }
\ No newline at end of file
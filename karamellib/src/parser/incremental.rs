@@ -0,0 +1,116 @@
+use crate::error::KaramelError;
+use crate::types::*;
+use super::{Parser, TokenParsers, script_start};
+
+/// Changed lines of the source. Lines `start_line..old_end_line` of the old source are replaced with the lines
+/// `start_line..new_end_line` of the new source. Line numbers start from zero.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LineEdit {
+    pub start_line: u32,
+    pub old_end_line: u32,
+    pub new_end_line: u32
+}
+
+/// Difference between the old and the new token lists. `removed` tokens starting from `start` are replaced with
+/// `inserted` tokens, and the lines of the tokens after them are moved by `line_shift`.
+#[derive(Clone, Debug)]
+pub struct TokenDelta {
+    pub start: usize,
+    pub removed: usize,
+    pub inserted: Vec<Token>,
+    pub line_shift: i64
+}
+
+impl TokenDelta {
+    pub fn apply(&self, tokens: &mut Vec<Token>) {
+        let end = self.start + self.inserted.len();
+        tokens.splice(self.start..self.start + self.removed, self.inserted.iter().cloned());
+        for token in tokens[end..].iter_mut() {
+            token.line = (token.line as i64 + self.line_shift) as u32;
+        }
+    }
+}
+
+/* Where the tokenization starts again: token index, byte index, line and column */
+struct Restart {
+    token: usize,
+    index: u32,
+    line: u32,
+    column: u32
+}
+
+impl Restart {
+    fn beginning(data: &str) -> Restart {
+        Restart { token: 0, index: script_start(data), line: 0, column: 0 }
+    }
+
+    /* Tokenization can start from the new line at the end of the previous line, but only if that new line
+       produced a token. New lines in multi line texts and comments do not. */
+    fn find(data: &str, previous: &[Token], start_line: u32) -> Option<Restart> {
+        if start_line == 0 {
+            return Some(Restart::beginning(data));
+        }
+
+        let token = previous.iter().position(|token| token.line >= start_line)?;
+        match previous[token].token_type {
+            KaramelTokenType::NewLine(_) if previous[token].line == start_line => (),
+            _ => return None
+        };
+
+        let (index, _) = data.match_indices('\n').nth(start_line as usize - 1)?;
+        let line_start = data[..index].rfind('\n').map(|position| position + 1).unwrap_or(0);
+        Some(Restart {
+            token,
+            index: index as u32,
+            line: start_line - 1,
+            column: data[line_start..index].chars().count() as u32
+        })
+    }
+}
+
+impl<'a> Parser<'a> {
+    /// Tokenizes only the edited lines of the source. `previous` are the tokens of the old source, the parser must be
+    /// created with the new source. Tokenization stops at the first new line after the edit that is the same with
+    /// the old tokens, the rest of the old tokens are kept.
+    pub fn retokenize(&self, previous: &[Token], edit: &LineEdit) -> Result<TokenDelta, KaramelError> {
+        let data = &self.tokinizer.data[..];
+        let line_shift = edit.new_end_line as i64 - edit.old_end_line as i64;
+        let restart = Restart::find(data, previous, edit.start_line).unwrap_or_else(|| Restart::beginning(data));
+
+        let parsers = TokenParsers::new(self.keep_comments, self.limits);
+        let mut tokinizer = Tokinizer::with_position(data, restart.index, restart.line, restart.column);
+        let mut old_token = restart.token;
+
+        while !tokinizer.is_end() {
+            parsers.step(&mut tokinizer)?;
+
+            let (line, token_type) = match tokinizer.tokens.last() {
+                Some(token) if token.line >= edit.new_end_line => (token.line, &token.token_type),
+                _ => continue
+            };
+
+            if let KaramelTokenType::NewLine(_) = token_type {
+                let old_line = (line as i64 - line_shift) as u32;
+                while old_token < previous.len() && previous[old_token].line < old_line {
+                    old_token += 1;
+                }
+
+                if old_token < previous.len() && previous[old_token].line == old_line && previous[old_token].token_type == *token_type {
+                    return Ok(TokenDelta {
+                        start: restart.token,
+                        removed: old_token + 1 - restart.token,
+                        inserted: tokinizer.tokens,
+                        line_shift
+                    });
+                }
+            }
+        }
+
+        Ok(TokenDelta {
+            start: restart.token,
+            removed: previous.len() - restart.token,
+            inserted: tokinizer.tokens,
+            line_shift
+        })
+    }
+}
@@ -5,11 +5,13 @@ mod symbol;
 mod line;
 mod whitespace;
 mod comment;
+mod incremental;
+mod reader;
 
 use std::str;
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
 
-use crate::{error::KaramelError, types::*};
+use crate::{error::KaramelError, error::KaramelErrorType, error::diagnostic::Diagnostic, types::*};
 use self::number::NumberParser;
 use self::text::TextParser;
 use self::operator::OperatorParser;
@@ -17,31 +19,155 @@ use self::symbol::SymbolParser;
 use self::line::LineParser;
 use self::whitespace::WhitespaceParser;
 use self::comment::CommentParser;
-use crate::error::KaramelErrorType;
+
+pub use self::incremental::{LineEdit, TokenDelta};
+pub use self::reader::ReaderTokenStream;
 
 pub struct Parser<'a> {
-    tokinizer: Tokinizer<'a>
+    tokinizer: Tokinizer<'a>,
+    keep_comments: bool,
+    limits: TokenLimits
+}
+
+/// Upper limits of the tokenizer. Lengths are counted in characters.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TokenLimits {
+    pub symbol_length: usize,
+    pub text_length: usize,
+    pub line_length: usize
+}
+
+impl Default for TokenLimits {
+    fn default() -> Self {
+        TokenLimits {
+            symbol_length: 256,
+            text_length: 1_048_576,
+            line_length: 65_536
+        }
+    }
+}
+
+/* Token parsers in the order of their priority */
+struct TokenParsers {
+    line: LineParser,
+    comment: CommentParser,
+    whitespace: WhitespaceParser,
+    number: NumberParser,
+    bytes_single: TextParser,
+    bytes_double: TextParser,
+    text_single: TextParser,
+    text_double: TextParser,
+    operator: OperatorParser,
+    symbol: SymbolParser,
+    line_length: usize
+}
+
+impl TokenParsers {
+    fn new(keep_comments: bool, limits: TokenLimits) -> TokenParsers {
+        let mut symbol = SymbolParser {
+            keywords: HashMap::new(),
+            max_length: limits.symbol_length
+        };
+        symbol.init_parser();
+
+        TokenParsers {
+            line: LineParser {},
+            comment: CommentParser { keep_comments },
+            whitespace: WhitespaceParser {},
+            number: NumberParser {},
+            bytes_single: TextParser { tag:'\'', max_length: limits.text_length, bytes: true },
+            bytes_double: TextParser { tag:'"', max_length: limits.text_length, bytes: true },
+            text_single: TextParser { tag:'\'', max_length: limits.text_length, bytes: false },
+            text_double: TextParser { tag:'"', max_length: limits.text_length, bytes: false },
+            operator: OperatorParser {},
+            symbol,
+            line_length: limits.line_length
+        }
+    }
+
+    /* Parses the next token. Some of the parsers add more than one token or nothing. */
+    fn step(&self, tokinizer: &mut Tokinizer) -> Result<(), KaramelError> {
+        let status = if self.line.check(tokinizer) {
+            self.line.parse(tokinizer)
+        }
+        else if self.whitespace.check(tokinizer) {
+            self.whitespace.parse(tokinizer)
+        }
+        else if self.comment.check(tokinizer) {
+            self.comment.parse(tokinizer)
+        }
+        /* b'...' would be read as the symbol 'b' */
+        else if self.bytes_single.check(tokinizer) {
+            self.bytes_single.parse(tokinizer)
+        }
+        else if self.bytes_double.check(tokinizer) {
+            self.bytes_double.parse(tokinizer)
+        }
+        else if self.symbol.check(tokinizer) {
+            self.symbol.parse(tokinizer)
+        }
+        else if self.text_single.check(tokinizer) {
+            self.text_single.parse(tokinizer)
+        }
+        else if self.text_double.check(tokinizer) {
+            self.text_double.parse(tokinizer)
+        }
+        else if self.number.check(tokinizer) {
+            self.number.parse(tokinizer)
+        }
+        else {
+            self.operator.parse(tokinizer)
+        };
+
+        /* Checked after every token, multi line texts and comments reset the column on each line */
+        let status = match status {
+            Ok(_) if tokinizer.column as usize > self.line_length => {
+                tokinizer.column = self.line_length as u32;
+                Err(KaramelErrorType::LineTooLong(self.line_length))
+            },
+            status => status
+        };
+
+        status.map_err(|error_type| KaramelError {
+            error_type,
+            line: tokinizer.line,
+            column: tokinizer.column
+        })
+    }
+}
+
+/// Byte index of the first character of the script. UTF-8 byte order mark and the `#!` line of the executable scripts
+/// are skipped, the new line after `#!` is kept so the line numbers do not change.
+pub fn script_start(data: &str) -> u32 {
+    let start = match data.starts_with('\u{feff}') {
+        true => '\u{feff}'.len_utf8(),
+        false => 0
+    };
+
+    match data[start..].starts_with("#!") {
+        true => data[start..].find('\n').map_or(data.len(), |position| start + position) as u32,
+        false => start as u32
+    }
 }
 
 impl<'a> Parser<'a> {
     pub fn new(data: &'a str) -> Parser {
-        let mut parser = Parser {
-            tokinizer: Tokinizer {
-                column: 0,
-                line: 0,
-                tokens: Vec::new(),
-                iter: data.chars().peekable(),
-                iter_second: data.chars().peekable(),
-                iter_third: data.chars().peekable(),
-                data: data.to_string(),
-                index: 0
-            }
-        };
+        Parser {
+            tokinizer: Tokinizer::with_position(data, script_start(data), 0, 0),
+            keep_comments: false,
+            limits: TokenLimits::default()
+        }
+    }
 
-        parser.tokinizer.iter_second.next();
-        parser.tokinizer.iter_third.next();
-        parser.tokinizer.iter_third.next();
-        return parser;
+    pub fn set_limits(&mut self, limits: TokenLimits) {
+        self.limits = limits;
+    }
+
+    /// Comment tokens are normally discarded. This parser keeps them in the token list, which is needed by the formatter.
+    pub fn with_comments(data: &'a str) -> Parser<'a> {
+        let mut parser = Parser::new(data);
+        parser.keep_comments = true;
+        parser
     }
 
     pub fn tokens(&self) -> Vec<Token> {
@@ -49,57 +175,56 @@ impl<'a> Parser<'a> {
     }
 
     pub fn parse(&mut self) -> ParseResult {
+        let parsers = TokenParsers::new(self.keep_comments, self.limits);
+        while !self.tokinizer.is_end() {
+            parsers.step(&mut self.tokinizer)?;
+        }
 
-        let line_parser         = LineParser       {};
-        let comment_parser      = CommentParser    {};
-        let whitespace_parser   = WhitespaceParser {};
-        let number_parser       = NumberParser     {};
-        let text_parser_single  = TextParser       { tag:'\'' };
-        let text_parser_double  = TextParser       { tag:'"' };
-        let operator_parser     = OperatorParser   {};
-        let mut symbol_parser   = SymbolParser     {
-            keywords: HashMap::new()
-        };
+        Ok(())
+    }
+}
 
-        symbol_parser.init_parser();
+/// Lazy form of the parser. Tokens are produced while iterating, so the whole token list is never kept in the memory.
+/// Iteration stops after the first error.
+pub struct TokenStream<'a> {
+    tokinizer: Tokinizer<'a>,
+    parsers: TokenParsers,
+    pending: VecDeque<Token>,
+    finished: bool
+}
 
-        while self.tokinizer.is_end() == false {
-            let status: Result<(), KaramelErrorType>;
+impl<'a> Iterator for TokenStream<'a> {
+    type Item = Result<Token, Diagnostic>;
 
-            if line_parser.check(&mut self.tokinizer) {
-                status = line_parser.parse(&mut self.tokinizer);
-            }
-            else if whitespace_parser.check(&mut self.tokinizer) {
-                status = whitespace_parser.parse(&mut self.tokinizer);
-            }
-            else if comment_parser.check(&mut self.tokinizer) {
-                status = comment_parser.parse(&mut self.tokinizer);
-            }
-            else if symbol_parser.check(&mut self.tokinizer) {
-                status = symbol_parser.parse(&mut self.tokinizer);
-            }
-            else if text_parser_single.check(&mut self.tokinizer) {
-                status = text_parser_single.parse(&mut self.tokinizer);
-            }
-            else if text_parser_double.check(&mut self.tokinizer) {
-                status = text_parser_double.parse(&mut self.tokinizer);
-            }
-            else if number_parser.check(&mut self.tokinizer) {
-                status = number_parser.parse(&mut self.tokinizer);
-            }
-            else {
-                status = operator_parser.parse(&mut self.tokinizer);
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.pending.is_empty() {
+            if self.finished || self.tokinizer.is_end() {
+                return None;
             }
 
-            if status.is_err() {
-                return Err(KaramelError {
-                    error_type: status.err().unwrap(),
-                    line: self.tokinizer.line,
-                    column: self.tokinizer.column
-                });
+            if let Err(error) = self.parsers.step(&mut self.tokinizer) {
+                self.finished = true;
+                return Some(Err(Diagnostic::from(&error)));
             }
+            self.pending.extend(self.tokinizer.tokens.drain(..));
         }
 
-        Ok(())
+        self.pending.pop_front().map(Ok)
     }
-}
\ No newline at end of file
+}
+
+impl<'a> IntoIterator for Parser<'a> {
+    type Item = Result<Token, Diagnostic>;
+    type IntoIter = TokenStream<'a>;
+
+    /// Already parsed tokens are returned first.
+    fn into_iter(mut self) -> TokenStream<'a> {
+        let pending = self.tokinizer.tokens.drain(..).collect();
+        TokenStream {
+            tokinizer: self.tokinizer,
+            parsers: TokenParsers::new(self.keep_comments, self.limits),
+            pending,
+            finished: false
+        }
+    }
+}
@@ -9,23 +9,27 @@ impl NumberParser {
         tokinizer.get_char()
     }
 
-    fn get_digits(&self, tokinizer: &mut Tokinizer) -> (u8, u64) {
-        let mut number: u64    = 0;
-        let mut num_count: u8  = 0;
-        let mut ch :char       = tokinizer.get_char();
+    /* Digits are appended to the buffer, underscores are skipped */
+    fn get_digits(&self, tokinizer: &mut Tokinizer, buffer: &mut String) -> usize {
+        let mut num_count = 0;
+        let mut ch        = tokinizer.get_char();
 
         while !tokinizer.is_end() && (ch.is_ascii_digit() || ch == '_') {
             if ch != '_' {
                 num_count += 1;
-
-                number *= u64::pow(10, 1);
-                number += ch as u64 - '0' as u64;
+                buffer.push(ch);
             }
 
             ch = self.increase(tokinizer);
         }
 
-        (num_count, number)
+        num_count
+    }
+
+    fn is_legacy_octal(&self, tokinizer: &Tokinizer) -> bool {
+        let rest = &tokinizer.data[tokinizer.index as usize..];
+        let digits = rest.find(|ch: char| !ch.is_ascii_digit() && ch != '_').unwrap_or(rest.len());
+        !rest[..digits].contains(['8', '9']) && !rest[digits..].starts_with('.')
     }
 
     fn detect_number_system(&self, tokinizer: &mut Tokinizer) -> KaramelNumberSystem {
@@ -41,7 +45,13 @@ impl NumberParser {
                     self.increase(tokinizer);
                     KaramelNumberSystem::Hexadecimal
                 },
-                '0'..='7' => {
+                'o' | 'O' => {
+                    self.increase(tokinizer);
+                    self.increase(tokinizer);
+                    KaramelNumberSystem::Octal
+                },
+                /* Numbers with a leading zero are octal, unless they have decimal digits or a fraction. Example: 0123456789, 0777.5 */
+                '0'..='7' if self.is_legacy_octal(tokinizer) => {
                     self.increase(tokinizer);
                     KaramelNumberSystem::Octal
                 },
@@ -52,113 +62,78 @@ impl NumberParser {
         return KaramelNumberSystem::Decimal;
     }
 
-    fn parse_hex(&self, tokinizer: &mut Tokinizer) -> KaramelTokenType {
-        let mut number :u64 = 0;
-        let mut ch :char    = tokinizer.get_char();
-
-        while !tokinizer.is_end() && ch.is_ascii_hexdigit() {
-            number = number << 4;
-
-            let tmp_ch = ch.to_digit(16);
-            if tmp_ch.is_some() {
-                number += tmp_ch.unwrap() as u64;
-            }
-
-            ch = self.increase(tokinizer);
-        }
-
-        KaramelTokenType::Integer(number as i64)
-    }
-
-    fn parse_octal(&self, tokinizer: &mut Tokinizer) -> KaramelTokenType {
-        let mut number :u64 = 0;
-        let mut ch :char    = tokinizer.get_char();
-
-        while !tokinizer.is_end() && ch >= '0' && ch <= '7' {
-            number = number << 3;
-
-            let tmp_ch = ch.to_digit(8);
-            if tmp_ch.is_some() {
-                number += tmp_ch.unwrap() as u64;
-            }
+    fn parse_radix(&self, tokinizer: &mut Tokinizer, number_system: KaramelNumberSystem) -> Result<KaramelTokenType, KaramelErrorType> {
+        let radix           = number_system.radix();
+        let mut number: i64 = 0;
+        let mut num_count   = 0;
+        let mut ch          = tokinizer.get_char();
+
+        while !tokinizer.is_end() {
+            match (ch, ch.to_digit(radix)) {
+                ('_', _) if num_count > 0 => (),
+                (_, Some(digit)) => {
+                    num_count += 1;
+                    number = number.checked_mul(radix as i64)
+                        .and_then(|number| number.checked_add(digit as i64))
+                        .ok_or_else(|| KaramelErrorType::InvalidNumberLiteral(number_system.name().to_string()))?;
+                },
+                _ => break
+            };
 
             ch = self.increase(tokinizer);
         }
 
-        KaramelTokenType::Integer(number as i64)
-    }
-
-    fn parse_binary(&self, tokinizer: &mut Tokinizer) -> KaramelTokenType {
-        let mut number :u64 = 0;
-        let mut ch :char    = tokinizer.get_char();
-
-        while !tokinizer.is_end() && ch >= '0' && ch <= '1' {
-            number = number << 1;
-
-            let tmp_ch = ch.to_digit(2);
-            if tmp_ch.is_some() {
-                number += tmp_ch.unwrap() as u64;
-            }
-
-            ch = self.increase(tokinizer);
+        /* Digits that are not valid for the number system are not allowed. Example: 0b102, 0xFG */
+        if num_count == 0 || (!tokinizer.is_end() && (ch.is_alphanumeric() || (ch == '.' && tokinizer.get_next_char().is_ascii_digit()))) {
+            return Err(KaramelErrorType::InvalidNumberLiteral(number_system.name().to_string()));
         }
 
-        KaramelTokenType::Integer(number as i64)
+        Ok(KaramelTokenType::Integer(number))
     }
 
-    fn parse_decimal(&self, tokinizer: &mut Tokinizer) -> KaramelTokenType {
+    fn parse_decimal(&self, tokinizer: &mut Tokinizer) -> Result<KaramelTokenType, KaramelErrorType> {
         /*
-        [NUMBER](.[NUMBER](E(-+)[NUMBER]))
+        [NUMBER](.[NUMBER])(E(-+)[NUMBER])
         */
 
-        let (_, digits)  = self.get_digits(tokinizer);
-        let before_comma = digits;
-        let mut ch       = tokinizer.get_char();
-        let ch_next = tokinizer.get_next_char();
+        let mut buffer = String::new();
+        let mut is_double = false;
+        self.get_digits(tokinizer, &mut buffer);
+        let mut ch = tokinizer.get_char();
 
-        /* Double number */
-        if !tokinizer.is_end() && ch == '.' && (ch_next >= '0' && ch_next <= '9') {
+        /* Fraction */
+        if !tokinizer.is_end() && ch == '.' && tokinizer.get_next_char().is_ascii_digit() {
+            is_double = true;
+            buffer.push('.');
             self.increase(tokinizer);
+            self.get_digits(tokinizer, &mut buffer);
+            ch = tokinizer.get_char();
+        }
 
-            let (digit_num, digits) = self.get_digits(tokinizer);
-            let after_comma = digits;
-            let dot_place   = digit_num;
-            ch          = tokinizer.get_char();
-
-            if !tokinizer.is_end() && (ch == 'e' || ch == 'E') {
-                let mut is_minus      = false;
-
-                ch = self.increase(tokinizer);
-
-                if !tokinizer.is_end() {
-                    match ch {
-                        '-' => {
-                            is_minus = true;
-                            self.increase(tokinizer);
-                        },
-
-                        '+' => { self.increase(tokinizer); },
-                        _ => {}
-                    }
-                }
+        /* Scientific notation. Example: 1.5e10, 2E-3 */
+        if !tokinizer.is_end() && (ch == 'e' || ch == 'E') {
+            is_double = true;
+            buffer.push('e');
+            ch = self.increase(tokinizer);
 
-                let (_, digits) = self.get_digits(tokinizer);
-                let e_after    = digits;
+            if !tokinizer.is_end() && (ch == '-' || ch == '+') {
+                buffer.push(ch);
                 self.increase(tokinizer);
+            }
 
-                let num = before_comma as f64 + (after_comma as f64 * f64::powi(10.0, -1 * dot_place as i32));
-
-                return match is_minus {
-                    true  => KaramelTokenType::Double(num / f64::powi(10.0, e_after as i32)),
-                    false => KaramelTokenType::Double(num * f64::powi(10.0, e_after as i32))
-                }
+            if self.get_digits(tokinizer, &mut buffer) == 0 {
+                return Err(KaramelErrorType::NumberNotParsed);
             }
+        }
 
-            let num = before_comma as f64 + (after_comma as f64 * f64::powi(10.0, -1 * dot_place as i32));
-            return KaramelTokenType::Double(num)
+        /* Integer number. Numbers that are bigger than the integer limit are kept as double */
+        if !is_double {
+            if let Ok(number) = buffer.parse::<i64>() {
+                return Ok(KaramelTokenType::Integer(number));
+            }
         }
 
-        KaramelTokenType::Integer(before_comma as i64)
+        buffer.parse::<f64>().map(KaramelTokenType::Double).map_err(|_| KaramelErrorType::NumberNotParsed)
     }
 }
 
@@ -174,10 +149,8 @@ impl TokenParser for NumberParser {
         let number_system = self.detect_number_system(tokinizer);
 
         let token_type = match number_system {
-            KaramelNumberSystem::Binary      => self.parse_binary(tokinizer),
-            KaramelNumberSystem::Octal       => self.parse_octal(tokinizer),
-            KaramelNumberSystem::Decimal     => self.parse_decimal(tokinizer),
-            KaramelNumberSystem::Hexadecimal => self.parse_hex(tokinizer)
+            KaramelNumberSystem::Decimal => self.parse_decimal(tokinizer)?,
+            _                            => self.parse_radix(tokinizer, number_system)?
         };
         tokinizer.add_token(start_column, token_type);
         
@@ -56,6 +56,7 @@ impl TokenParser for OperatorParser {
                 ';' => KaramelOperatorType::Semicolon,
                 '.' => KaramelOperatorType::Dot,
                 '!' => KaramelOperatorType::Not,
+                '@' => KaramelOperatorType::At,
                 _ => KaramelOperatorType::None
             };
         }
@@ -0,0 +1,137 @@
+use std::collections::VecDeque;
+use std::io::BufRead;
+
+use crate::error::{KaramelError, KaramelErrorType, diagnostic::Diagnostic};
+use crate::types::*;
+use super::{Parser, TokenLimits, TokenParsers, script_start};
+
+/// Tokenizes the source while it is read, line by line. Only the current line is kept in the memory, lines are
+/// joined only while a text or a comment continues on the next line. Iteration stops after the first error.
+pub struct ReaderTokenStream<R: BufRead> {
+    reader: R,
+    parsers: TokenParsers,
+    pending: VecDeque<Token>,
+    line: u32,
+    column: u32,
+    started: bool,
+    has_more: bool,
+    finished: bool
+}
+
+impl<R: BufRead> ReaderTokenStream<R> {
+    pub fn new(reader: R, limits: TokenLimits) -> ReaderTokenStream<R> {
+        ReaderTokenStream {
+            reader,
+            parsers: TokenParsers::new(false, limits),
+            pending: VecDeque::new(),
+            line: 0,
+            column: 0,
+            started: false,
+            has_more: true,
+            finished: false
+        }
+    }
+
+    /* Line without the new line character. Source ending with a new line has an empty last line. */
+    fn read_line(&mut self) -> Result<Option<String>, KaramelError> {
+        if !self.has_more {
+            return Ok(None);
+        }
+
+        let mut line = String::new();
+        if let Err(error) = self.reader.read_line(&mut line) {
+            return Err(KaramelError {
+                error_type: KaramelErrorType::FileReadError { filename: "girdi".to_string(), error: error.to_string() },
+                line: self.line,
+                column: 0
+            });
+        }
+
+        self.has_more = line.ends_with('\n');
+        if self.has_more {
+            line.pop();
+        }
+        Ok(Some(line))
+    }
+
+    /* Texts and comments that are not finished at the end of the line may continue on the next line */
+    fn is_unfinished(error_type: &KaramelErrorType) -> bool {
+        match error_type {
+            KaramelErrorType::MissingStringDeliminator | KaramelErrorType::CommentNotFinished => true,
+            KaramelErrorType::InvalidEscape(escape) => escape == "\\",
+            _ => false
+        }
+    }
+
+    /* Every line after the first one starts with the new line of the previous line, so the indentation is read with its line */
+    fn tokenize(&mut self, line: String) -> Result<(), KaramelError> {
+        let (mut chunk, start) = match self.started {
+            true => (format!("\n{}", line), 0),
+            false => {
+                let start = script_start(&line);
+                (line, start)
+            }
+        };
+
+        loop {
+            let mut tokinizer = Tokinizer::with_position(&chunk, start, self.line, self.column);
+            let mut status = Ok(());
+            while status.is_ok() && !tokinizer.is_end() {
+                status = self.parsers.step(&mut tokinizer);
+            }
+
+            match status {
+                Ok(_) => {
+                    self.line = tokinizer.line;
+                    self.column = tokinizer.column;
+                    self.started = true;
+                    self.pending.extend(tokinizer.tokens);
+                    return Ok(());
+                },
+                Err(error) if Self::is_unfinished(&error.error_type) => match self.read_line()? {
+                    Some(next_line) => {
+                        chunk.push('\n');
+                        chunk.push_str(&next_line);
+                    },
+                    None => return Err(error)
+                },
+                Err(error) => return Err(error)
+            };
+        }
+    }
+}
+
+impl<R: BufRead> Iterator for ReaderTokenStream<R> {
+    type Item = Result<Token, Diagnostic>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.pending.is_empty() {
+            if self.finished {
+                return None;
+            }
+
+            let status = match self.read_line() {
+                Ok(Some(line)) => self.tokenize(line),
+                Ok(None) => {
+                    self.finished = true;
+                    return None;
+                },
+                Err(error) => Err(error)
+            };
+
+            if let Err(error) = status {
+                self.finished = true;
+                return Some(Err(Diagnostic::from(&error)));
+            }
+        }
+
+        self.pending.pop_front().map(Ok)
+    }
+}
+
+impl<'a> Parser<'a> {
+    /// Lazy parser for sources that are read from a file or the standard input. The whole source is never kept in the memory.
+    pub fn from_reader<R: BufRead>(reader: R) -> ReaderTokenStream<R> {
+        ReaderTokenStream::new(reader, TokenLimits::default())
+    }
+}
@@ -1,10 +1,12 @@
+use std::borrow::Cow;
 use std::collections::HashMap;
-use std::rc::Rc;
 use crate::types::*;
 use crate::error::KaramelErrorType;
+use crate::interner::intern;
 
 pub struct SymbolParser {
-    pub keywords: HashMap<&'static str, KaramelKeywordType>
+    pub keywords: HashMap<&'static str, KaramelKeywordType>,
+    pub max_length: usize
 }
 
 impl SymbolParser {
@@ -15,6 +17,43 @@ impl SymbolParser {
     }
 }
 
+/* (Letter, combining mark, composed letter) */
+static COMPOSITIONS: &[(char, char, char)] = &[
+    ('I', '\u{0307}', 'İ'), ('i', '\u{0307}', 'i'),
+    ('O', '\u{0308}', 'Ö'), ('o', '\u{0308}', 'ö'),
+    ('U', '\u{0308}', 'Ü'), ('u', '\u{0308}', 'ü'),
+    ('C', '\u{0327}', 'Ç'), ('c', '\u{0327}', 'ç'),
+    ('S', '\u{0327}', 'Ş'), ('s', '\u{0327}', 'ş'),
+    ('G', '\u{0306}', 'Ğ'), ('g', '\u{0306}', 'ğ'),
+    ('A', '\u{0302}', 'Â'), ('a', '\u{0302}', 'â'),
+    ('I', '\u{0302}', 'Î'), ('i', '\u{0302}', 'î'),
+    ('U', '\u{0302}', 'Û'), ('u', '\u{0302}', 'û')
+];
+
+/// Converts the decomposed Turkish letters to the composed forms, so 'İstanbul' written with 'I' + '◌̇' is the same symbol.
+pub fn normalize_symbol(symbol: &str) -> Cow<'_, str> {
+    if !symbol.chars().any(|ch| ch.is_combining_mark()) {
+        return Cow::Borrowed(symbol);
+    }
+
+    let mut normalized = String::with_capacity(symbol.len());
+    for ch in symbol.chars() {
+        let composed = match (ch.is_combining_mark(), normalized.chars().last()) {
+            (true, Some(last)) => COMPOSITIONS.iter().find(|(letter, mark, _)| *letter == last && *mark == ch).map(|(_, _, composed)| *composed),
+            _ => None
+        };
+
+        match composed {
+            Some(composed) => {
+                normalized.pop();
+                normalized.push(composed);
+            },
+            None => normalized.push(ch)
+        };
+    }
+    Cow::Owned(normalized)
+}
+
 impl TokenParser for SymbolParser {
     fn check(&self, tokinizer: &mut Tokinizer) -> bool {
         let ch = tokinizer.get_char();
@@ -23,6 +62,7 @@ impl TokenParser for SymbolParser {
 
     fn parse(&self, tokinizer: &mut Tokinizer) -> Result<(), KaramelErrorType> {
         let mut ch: char;
+        let mut last: char    = '\0';
         let start             = tokinizer.index as usize;
         let mut end           = start;
         let start_column = tokinizer.column;
@@ -30,18 +70,27 @@ impl TokenParser for SymbolParser {
         while !tokinizer.is_end() {
             ch = tokinizer.get_char();
 
-            if !ch.is_symbol() && !ch.is_integer() {
-                break;
-            }
+            /* Apostrophe is part of the symbol when it is between two letters. Example: istanbul'un_nüfusu */
+            let is_apostrophe = ch == '\'' && last.is_alphabetic() && tokinizer.get_next_char().is_alphabetic();
 
-            if ch.is_whitespace() || ch == '\'' || ch == '"' {
+            if !ch.is_symbol() && !ch.is_integer() && !ch.is_combining_mark() && !is_apostrophe {
                 break;
             }
+
             end += ch.len_utf8();
+            last = ch;
             tokinizer.increase_index();
         }
-        if self.keywords.contains_key(&tokinizer.data[start..end]) {
-            let keyword = match self.keywords.get(&tokinizer.data[start..end]) {
+
+        /* Error is shown at the start of the symbol */
+        if (tokinizer.column - start_column) as usize > self.max_length {
+            tokinizer.column = start_column;
+            return Err(KaramelErrorType::SymbolTooLong(self.max_length));
+        }
+
+        let symbol = normalize_symbol(&tokinizer.data[start..end]).into_owned();
+        if self.keywords.contains_key(symbol.as_str()) {
+            let keyword = match self.keywords.get(symbol.as_str()) {
                 Some(keyword) => keyword,
                 None => &KaramelKeywordType::None
             };
@@ -54,7 +103,7 @@ impl TokenParser for SymbolParser {
             return Ok(());
         }
 
-        tokinizer.add_token(start_column as u32, KaramelTokenType::Symbol(Rc::new(tokinizer.data[start..end].to_string())));
+        tokinizer.add_token(start_column as u32, KaramelTokenType::Symbol(intern(symbol)));
         return Ok(());
     }
 }
\ No newline at end of file
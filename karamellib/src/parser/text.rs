@@ -1,52 +1,159 @@
 use std::rc::Rc;
+
 use crate::types::*;
 use crate::error::KaramelErrorType;
+use crate::interner::intern;
 
+/// Parses the texts and the bytes. Bytes start with 'b' before the quote, b'...' and they can contain \xHH escapes.
 pub struct TextParser {
-    pub tag: char
+    pub tag: char,
+    pub max_length: usize,
+    pub bytes: bool
 }
 
 impl TokenParser for TextParser {
     fn check(&self, tokinizer: &mut Tokinizer) -> bool {
-        let ch = tokinizer.get_char();
-        return ch == self.tag;
+        match self.bytes {
+            true => tokinizer.get_char() == 'b' && tokinizer.get_next_char() == self.tag,
+            false => tokinizer.get_char() == self.tag
+        }
     }
 
     fn parse(&self, tokinizer: &mut Tokinizer) -> Result<(), KaramelErrorType> {
-        tokinizer.increase_index();
-
-        let mut ch: char      = '\0';
-        let mut ch_next: char;
-        let start             = tokinizer.index as usize;
+        let start_line   = tokinizer.line;
         let start_column = tokinizer.column;
-        let mut end           = start;
+        let mut content  = Vec::new();
+        let mut closed   = false;
+
+        if self.bytes {
+            tokinizer.increase_index();
+        }
+
+        /* Triple quoted texts can contain new lines and single quote characters */
+        let multi_line  = tokinizer.get_next_char() == self.tag && tokinizer.get_third_char() == self.tag;
+        let quote_count = if multi_line { 3 } else { 1 };
+        (0..quote_count).for_each(|_| tokinizer.increase_index());
+
+        let mut length   = 0;
 
         while !tokinizer.is_end() {
-            ch      = tokinizer.get_char();
-            ch_next = tokinizer.get_next_char();
+            let ch = tokinizer.get_char();
 
-            if ch == '\\' && ch_next == self.tag {
-                end += ch.len_utf8();
-                end += 1; // for tag char
-                tokinizer.increase_index();
+            /* Stops early, unterminated texts at the end of big files are not read till the end. Error is shown at the opening quote */
+            if length > self.max_length {
+                tokinizer.line   = start_line;
+                tokinizer.column = start_column;
+                return Err(KaramelErrorType::TextTooLong(self.max_length));
             }
-            else if ch == self.tag {
-                tokinizer.increase_index();
+            length += 1;
+
+            if ch == self.tag && (!multi_line || (tokinizer.get_next_char() == self.tag && tokinizer.get_third_char() == self.tag)) {
+                (0..quote_count).for_each(|_| tokinizer.increase_index());
+                closed = true;
                 break;
             }
+            else if ch == '\\' && self.bytes && tokinizer.get_next_char() == 'x' {
+                content.push(self.parse_byte_escape(tokinizer)?);
+            }
+            else if ch == '\\' {
+                push_char(&mut content, self.parse_escape(tokinizer)?);
+            }
+            else if ch.is_new_line() {
+                push_char(&mut content, ch);
+                tokinizer.increase_index();
+                tokinizer.increate_line();
+            }
             else {
-                end += ch.len_utf8();
+                push_char(&mut content, ch);
+                tokinizer.increase_index();
             }
-
-            tokinizer.increase_index();
         }
 
-        if ch != self.tag {
+        if !closed {
             return Err(KaramelErrorType::MissingStringDeliminator);
         }
 
-        tokinizer.add_token(start_column - 1, KaramelTokenType::Text(Rc::new(tokinizer.data[start..end].to_string())));
-        return Ok(());
+        let token_type = match self.bytes {
+            true => KaramelTokenType::Bytes(Rc::new(content)),
+
+            /* Only the characters are added to the texts, they are always valid UTF-8 */
+            false => KaramelTokenType::Text(intern(String::from_utf8(content).unwrap_or_default()))
+        };
+
+        /* Token starts at the line of the opening quote */
+        tokinizer.tokens.push(Token {
+            line: start_line,
+            start: start_column,
+            end: tokinizer.column,
+            token_type
+        });
+        Ok(())
+    }
+}
+
+/* Characters other than ASCII are added as their UTF-8 bytes */
+fn push_char(content: &mut Vec<u8>, ch: char) {
+    content.extend_from_slice(ch.encode_utf8(&mut [0; 4]).as_bytes());
+}
+
+impl TextParser {
+    /* Tokinizer stays at the backslash on error, so the error shows the start of the escape */
+    fn parse_escape(&self, tokinizer: &mut Tokinizer) -> Result<char, KaramelErrorType> {
+        let escape = match tokinizer.get_next_char() {
+            'n'  => '\n',
+            't'  => '\t',
+            'r'  => '\r',
+            '0'  => '\0',
+            '\\' => '\\',
+            '\'' => '\'',
+            '"'  => '"',
+            'u'  => return self.parse_unicode_escape(tokinizer),
+            '\0' => return Err(KaramelErrorType::InvalidEscape("\\".to_string())),
+            ch   => return Err(KaramelErrorType::InvalidEscape(format!("\\{}", ch)))
+        };
+
+        tokinizer.increase_index();
+        tokinizer.increase_index();
+        Ok(escape)
+    }
+
+    /* \xHH with 2 hexadecimal digits */
+    fn parse_byte_escape(&self, tokinizer: &mut Tokinizer) -> Result<u8, KaramelErrorType> {
+        let escape = tokinizer.data[tokinizer.index as usize..].chars().take(4).collect::<String>();
+        match escape.chars().skip(2).filter(|ch| ch.is_ascii_hexdigit()).count() {
+            2 => {
+                (0..4).for_each(|_| tokinizer.increase_index());
+                Ok(u8::from_str_radix(&escape[2..], 16).unwrap_or_default())
+            },
+            _ => Err(KaramelErrorType::InvalidEscape(escape.chars().take(3).collect()))
+        }
+    }
+
+    /* \u{...} with 1 to 6 hexadecimal digits */
+    fn parse_unicode_escape(&self, tokinizer: &mut Tokinizer) -> Result<char, KaramelErrorType> {
+        /* Only the maximum escape length is searched, '\u{' without '}' does not scan the rest of the file */
+        let rest = &tokinizer.data[tokinizer.index as usize..];
+        let rest = &rest[..rest.char_indices().nth(10).map(|(index, _)| index).unwrap_or(rest.len())];
+        let escape = match rest.find('}') {
+            Some(position) if rest[2..].starts_with('{') && position <= 9 => &rest[..=position],
+            _ => return Err(KaramelErrorType::InvalidEscape(rest.chars().take(3).collect()))
+        };
+
+        let digits = &escape[3..escape.len() - 1];
+        let code = match digits.len() {
+            1..=6 if digits.chars().all(|ch| ch.is_ascii_hexdigit()) => u32::from_str_radix(digits, 16).ok().and_then(char::from_u32),
+            _ => None
+        };
+
+        match code {
+            Some(code) => {
+                for _ in 0..escape.chars().count() {
+                    tokinizer.increase_index();
+                }
+                Ok(code)
+            },
+            None => Err(KaramelErrorType::InvalidEscape(escape.to_string()))
+        }
     }
 }
 
@@ -57,18 +164,9 @@ fn text_parse_test_1() {
     use crate::types::Tokinizer;
 
     let data = "\"merhaba dünya\"";
-    let mut tokinizer = Tokinizer {
-        column: 0,
-        line: 0,
-        tokens: Vec::new(),
-        iter: data.chars().peekable(),
-        iter_second: data.chars().peekable(),
-        iter_third: data.chars().peekable(),
-        data: data.to_string(),
-        index: 0
-    };
+    let mut tokinizer = Tokinizer::new(data);
 
-    let parser = TextParser { tag: '"' };
+    let parser = TextParser { tag: '"', max_length: 1024, bytes: false };
     let parse_result = parser.parse(&mut tokinizer);
 
     assert_eq!(parse_result.is_ok(), true);
@@ -89,18 +187,9 @@ fn text_parse_test_2() {
     use crate::types::Tokinizer;
 
     let data = "'merhaba dünya'";
-    let mut tokinizer = Tokinizer {
-        column: 0,
-        line: 0,
-        tokens: Vec::new(),
-        iter: data.chars().peekable(),
-        iter_second: data.chars().peekable(),
-        iter_third: data.chars().peekable(),
-        data: data.to_string(),
-        index: 0
-    };
+    let mut tokinizer = Tokinizer::new(data);
 
-    let parser = TextParser { tag: '\'' };
+    let parser = TextParser { tag: '\'', max_length: 1024, bytes: false };
     let parse_result = parser.parse(&mut tokinizer);
 
     assert_eq!(parse_result.is_ok(), true);
@@ -114,3 +203,25 @@ fn text_parse_test_2() {
         _ => assert_eq!(true, false)
     };
 }
+
+#[cfg(test)]
+#[test]
+fn bytes_parse_test() {
+    use crate::types::Tokinizer;
+
+    let data = "b'a\\xff\\nç'";
+    let mut tokinizer = Tokinizer::new(data);
+
+    let parser = TextParser { tag: '\'', max_length: 1024, bytes: true };
+    assert_eq!(parser.check(&mut tokinizer), true);
+    assert_eq!(parser.parse(&mut tokinizer).is_ok(), true);
+    assert_eq!(tokinizer.tokens[0].end, 11);
+
+    match &tokinizer.tokens[0].token_type {
+        KaramelTokenType::Bytes(data) => assert_eq!(&***data, &[b'a', 0xff, b'\n', 0xc3, 0xa7]),
+        _ => assert_eq!(true, false)
+    };
+
+    let mut tokinizer = Tokinizer::new("b'\\xf'");
+    assert_eq!(parser.parse(&mut tokinizer), Err(KaramelErrorType::InvalidEscape("\\xf".to_string())));
+}
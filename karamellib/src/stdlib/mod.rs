@@ -0,0 +1,36 @@
+/* Modules of the standard library that are written in Karamel. Sources are embedded into the binary and
+   they are parsed like the module files when a program loads them. */
+static SOURCES: &[(&str, &str)] = &[
+    ("listeler", include_str!("listeler.k")),
+    ("yazılar", include_str!("yazilar.k"))
+];
+
+pub fn get_source(path: &[String]) -> Option<&'static str> {
+    match path {
+        [name] => SOURCES.iter().find(|(module, _)| module == name).map(|(_, source)| *source),
+        _ => None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parser::Parser;
+    use crate::syntax::SyntaxParser;
+    use super::*;
+
+    #[test]
+    fn sources_are_valid() {
+        for (name, source) in SOURCES.iter() {
+            let mut parser = Parser::new(source);
+            parser.parse().unwrap();
+            assert!(SyntaxParser::new(parser.tokens().to_vec()).parse().is_ok(), "{}", name);
+        }
+    }
+
+    #[test]
+    fn source_lookup() {
+        assert!(get_source(&["listeler".to_string()]).is_some());
+        assert!(get_source(&["bilinmeyen".to_string()]).is_none());
+        assert!(get_source(&["listeler".to_string(), "eşle".to_string()]).is_none());
+    }
+}
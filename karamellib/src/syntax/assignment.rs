@@ -34,17 +34,15 @@ impl SyntaxParserTrait for AssignmentParser {
             KaramelOperatorType::AssignSubtraction]) {
             parser.cleanup_whitespaces();
 
-            let expression = with_flag(SyntaxFlag::IN_ASSIGNMENT, parser, || ExpressionParser::parse(parser));            
-            match expression {
-                Ok(KaramelAstType::None) => return expression,
-                Ok(_) => (),
-                Err(_) => return expression
+            let expression = match with_flag(SyntaxFlag::IN_ASSIGNMENT, parser, || ExpressionParser::parse(parser))? {
+                KaramelAstType::None => return Ok(KaramelAstType::None),
+                expression => expression
             };
 
             let assignment_ast = KaramelAstType::Assignment {
                 variable: Rc::new(variable),
                 operator,
-                expression: Rc::new(expression.unwrap())
+                expression: Rc::new(expression)
             };
 
             return Ok(assignment_ast);
@@ -0,0 +1,71 @@
+use std::rc::Rc;
+
+use crate::types::*;
+use crate::syntax::{SyntaxParser, SyntaxParserTrait, SyntaxFlag};
+use crate::syntax::util::with_flag;
+use crate::syntax::expression::ExpressionParser;
+use crate::compiler::ast::KaramelAstType;
+use crate::error::KaramelErrorType;
+
+pub struct AssumeParser;
+
+impl SyntaxParserTrait for AssumeParser {
+    fn parse(parser: &SyntaxParser) -> AstResult {
+        let index_backup = parser.get_index();
+        parser.cleanup_whitespaces();
+
+        if !parser.match_keyword(KaramelKeywordType::Assume) {
+            parser.set_index(index_backup);
+            return Ok(KaramelAstType::None);
+        }
+
+        parser.cleanup_whitespaces();
+        let condition = match with_flag(SyntaxFlag::IN_EXPRESSION, parser, || ExpressionParser::parse(parser))? {
+            KaramelAstType::None => return Err(KaramelErrorType::InvalidExpression),
+            ast => Rc::new(ast)
+        };
+
+        /* Message is optional and written after a comma */
+        parser.cleanup_whitespaces();
+        let message = match parser.match_operator(&[KaramelOperatorType::Comma]) {
+            Some(_) => {
+                parser.cleanup_whitespaces();
+                match with_flag(SyntaxFlag::IN_EXPRESSION, parser, || ExpressionParser::parse(parser))? {
+                    KaramelAstType::None => return Err(KaramelErrorType::InvalidExpression),
+                    ast => Some(Rc::new(ast))
+                }
+            },
+            None => None
+        };
+
+        Ok(KaramelAstType::Assume { condition, message })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::KaramelErrorType;
+    use crate::parser::*;
+    use crate::syntax::*;
+    use crate::compiler::ast::KaramelAstType;
+    use std::rc::Rc;
+
+    fn parse(code: &str) -> Result<Rc<KaramelAstType>, KaramelErrorType> {
+        let mut parser = Parser::new(code);
+        parser.parse().unwrap();
+        SyntaxParser::new(parser.tokens().to_vec()).parse().map_err(|error| error.error_type)
+    }
+
+    #[test]
+    fn assume() {
+        assert!(matches!(&*parse("varsay a > 0").unwrap(), KaramelAstType::Assume { message: None, .. }));
+        assert!(matches!(&*parse("varsay a > 0, 'a pozitif olmalı'").unwrap(), KaramelAstType::Assume { message: Some(_), .. }));
+        assert!(matches!(&*parse("hata_ayıklama_doğrula a").unwrap(), KaramelAstType::Assume { message: None, .. }));
+    }
+
+    #[test]
+    fn assume_without_condition() {
+        assert_eq!(parse("varsay"), Err(KaramelErrorType::InvalidExpression));
+        assert_eq!(parse("varsay a,"), Err(KaramelErrorType::InvalidExpression));
+    }
+}
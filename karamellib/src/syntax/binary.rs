@@ -51,17 +51,15 @@ pub fn parse_binary<T: SyntaxParserTrait>(parser: &SyntaxParser, operators: &[Ka
             
             parser.cleanup_whitespaces();
             
-            let right_expr = with_flag(SyntaxFlag::IN_EXPRESSION, parser, || T::parse(parser));
-            match right_expr {
-                Ok(KaramelAstType::None) => return Err(KaramelErrorType::RightSideOfExpressionNotFound),
-                Ok(_) => (),
-                Err(_) => return right_expr
+            let right_expr = match with_flag(SyntaxFlag::IN_EXPRESSION, parser, || T::parse(parser))? {
+                KaramelAstType::None => return Err(KaramelErrorType::RightSideOfExpressionNotFound),
+                right_expr => right_expr
             };
 
             left_expr = KaramelAstType::Binary {
                 left: Rc::new(left_expr),
                 operator,
-                right: Rc::new(right_expr.unwrap())
+                right: Rc::new(right_expr)
             };
         }
         else {
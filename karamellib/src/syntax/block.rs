@@ -29,16 +29,43 @@ impl SyntaxParserTrait for MultiLineBlockParser {
 
 impl BlockParser {
     fn parse(parser: &SyntaxParser, multiline: bool) -> AstResult {
+        let _guard = parser.enter()?;
         let mut block_asts: Vec<Rc<KaramelAstType>> = Vec::new();
         let current_indentation = parser.get_indentation();
+        let current_flags = parser.flags.get();
+        let mut recovered = false;
 
         loop {
-            parser.indentation_check()?;
-            let ast = map_parser(parser, &[FunctionDefinationParser::parse, StatementParser::parse, ExpressionParser::parse, NewlineParser::parse])?;
+            let start = parser.get_index();
+
+            /* Line is reserved before parsing, so the outer statement comes before the inner statements */
+            let line_index = parser.statement_positions.borrow().len();
+            let ast = parser.indentation_check().and_then(|_| {
+                parser.statement_positions.borrow_mut().push(parser.next_statement_position());
+                map_parser(parser, &[FunctionDefinationParser::parse, StatementParser::parse, ExpressionParser::parse, NewlineParser::parse])
+            });
+
+            /* Failed statement is skipped, parsing continues from the next line of this block */
+            let ast = match ast {
+                Ok(ast) => ast,
+                Err(error) if multiline && parser.recover.get() => {
+                    parser.errors.borrow_mut().push(parser.located_error(error));
+                    parser.statement_positions.borrow_mut().truncate(line_index);
+                    parser.set_indentation(current_indentation);
+                    parser.flags.set(current_flags);
+                    parser.synchronize(current_indentation, start);
+                    recovered = true;
+                    KaramelAstType::NewLine
+                },
+                Err(error) => return Err(error)
+            };
     
             match ast {
-                KaramelAstType::None =>  break,
-                KaramelAstType::NewLine =>  (),
+                KaramelAstType::None =>  {
+                    parser.statement_positions.borrow_mut().truncate(line_index);
+                    break
+                },
+                KaramelAstType::NewLine => parser.statement_positions.borrow_mut().truncate(line_index),
                 _ => block_asts.push(Rc::new(ast))
             };
 
@@ -51,6 +78,8 @@ impl BlockParser {
         }
 
         return match block_asts.len() {
+            /* Block is not reported as missing when its statements have errors */
+            0 if recovered => Ok(KaramelAstType::Block(Vec::new())),
             0 => Ok(KaramelAstType::None),
             1 => Ok((&*block_asts[0]).clone()),
             _ => Ok(KaramelAstType::Block(block_asts.to_vec()))
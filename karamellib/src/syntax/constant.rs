@@ -0,0 +1,46 @@
+use crate::types::*;
+use crate::syntax::{SyntaxParser, SyntaxParserTrait};
+use crate::syntax::util::parse_declaration;
+use crate::compiler::ast::KaramelAstType;
+use crate::error::KaramelErrorType;
+
+pub struct ConstantParser;
+
+impl SyntaxParserTrait for ConstantParser {
+    fn parse(parser: &SyntaxParser) -> AstResult {
+        match parse_declaration(parser, KaramelKeywordType::Constant, KaramelErrorType::ConstantValueRequired)? {
+            Some(assignment) => Ok(KaramelAstType::Constant(assignment)),
+            None => Ok(KaramelAstType::None)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::KaramelErrorType;
+    use crate::parser::*;
+    use crate::syntax::*;
+    use crate::compiler::ast::KaramelAstType;
+    use std::rc::Rc;
+
+    fn parse(code: &str) -> Result<Rc<KaramelAstType>, KaramelErrorType> {
+        let mut parser = Parser::new(code);
+        parser.parse().unwrap();
+        SyntaxParser::new(parser.tokens().to_vec()).parse().map_err(|error| error.error_type)
+    }
+
+    #[test]
+    fn constant() {
+        match &*parse("sabit PI = 3.14").unwrap() {
+            KaramelAstType::Constant(assignment) => assert!(matches!(&**assignment, KaramelAstType::Assignment { .. })),
+            ast => panic!("{:?}", ast)
+        };
+    }
+
+    #[test]
+    fn constant_without_value() {
+        assert_eq!(parse("sabit PI"), Err(KaramelErrorType::ConstantValueRequired));
+        assert_eq!(parse("sabit PI += 1"), Err(KaramelErrorType::ConstantValueRequired));
+        assert_eq!(parse("sabit a[0] = 1"), Err(KaramelErrorType::ConstantValueRequired));
+    }
+}
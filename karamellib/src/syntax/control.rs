@@ -6,6 +6,7 @@ use crate::syntax::binary::AddSubtractParser;
 use crate::syntax::util::update_functions_for_temp_return;
 use crate::compiler::ast::KaramelAstType;
 use crate::error::KaramelErrorType;
+use crate::constants::KARAMEL_HIDDEN_VARIABLE_PREFIX;
 
 use super::util::with_flag;
 
@@ -40,7 +41,7 @@ impl SyntaxParserTrait for ControlParser {
 
 pub fn special_control(parser: &SyntaxParser) -> AstResult {
     let mut functions_updated_for_temp = false;
-    let mut left_expr = AddSubtractParser::parse(parser)?;
+    let left_expr = AddSubtractParser::parse(parser)?;
     let operators = [KaramelOperatorType::GreaterEqualThan, 
         KaramelOperatorType::GreaterThan,
         KaramelOperatorType::LessEqualThan, 
@@ -49,41 +50,27 @@ pub fn special_control(parser: &SyntaxParser) -> AstResult {
         KaramelAstType::None => return Ok(left_expr),
         _ => ()
     };
+
+    let mut operands = vec![left_expr];
+    let mut comparisons = Vec::new();
     
     loop {
         let index_backup = parser.get_index();
         parser.cleanup_whitespaces();
         if let Some(operator) = parser.match_operator(&operators) {
             if !functions_updated_for_temp {
-                update_functions_for_temp_return(&left_expr);
+                update_functions_for_temp_return(&operands[0]);
                 functions_updated_for_temp = true;
             }
 
             parser.cleanup_whitespaces();
-            let right_expr = with_flag(SyntaxFlag::IN_EXPRESSION, parser, || AddSubtractParser::parse(parser));
-            match right_expr {
-                Ok(KaramelAstType::None) => return Err(KaramelErrorType::RightSideOfExpressionNotFound),
-                Ok(_) => (),
-                Err(_) => return right_expr
+            let right_expr = match with_flag(SyntaxFlag::IN_EXPRESSION, parser, || AddSubtractParser::parse(parser))? {
+                KaramelAstType::None => return Err(KaramelErrorType::RightSideOfExpressionNotFound),
+                right_expr => right_expr
             };
 
-            left_expr = match operator {
-                KaramelOperatorType::LessEqualThan => KaramelAstType::Control {
-                    left: Rc::new(right_expr.unwrap()),
-                    operator: KaramelOperatorType::GreaterEqualThan,
-                    right: Rc::new(left_expr)
-                },
-                KaramelOperatorType::LessThan => KaramelAstType::Control {
-                    left: Rc::new(right_expr.unwrap()),
-                    operator: KaramelOperatorType::GreaterThan,
-                    right: Rc::new(left_expr)
-                },
-                _ => KaramelAstType::Control {
-                    left: Rc::new(left_expr),
-                    operator,
-                    right: Rc::new(right_expr.unwrap())
-                }
-            };
+            comparisons.push(operator);
+            operands.push(right_expr);
         }        
         else {
             parser.set_index(index_backup);
@@ -91,7 +78,63 @@ pub fn special_control(parser: &SyntaxParser) -> AstResult {
         }
     }
 
-    Ok(left_expr)
+    if comparisons.is_empty() {
+        return Ok(operands.remove(0));
+    }
+
+    /* 'a < b < c' is converted to 'a < b ve b < c'. Operands in the middle are evaluated once and kept in hidden variables. */
+    let operands = operands.into_iter().map(Rc::new).collect::<Vec<_>>();
+    let mut shared: Option<Rc<KaramelAstType>> = None;
+    let mut result: Option<KaramelAstType> = None;
+
+    for (index, operator) in comparisons.iter().enumerate() {
+        let left = shared.take().unwrap_or_else(|| operands[index].clone());
+        let mut right = operands[index + 1].clone();
+
+        if index + 1 < comparisons.len() {
+            match &*right {
+                KaramelAstType::Symbol(_) | KaramelAstType::Primative(_) | KaramelAstType::ModulePath(_) => shared = Some(right.clone()),
+                _ => {
+                    let name = format!("{}karşılaştırma_{}_{}", KARAMEL_HIDDEN_VARIABLE_PREFIX, parser.get_index(), index);
+                    shared = Some(Rc::new(KaramelAstType::Temporary { name: name.to_string(), expression: None }));
+                    right = Rc::new(KaramelAstType::Temporary { name, expression: Some(right) });
+                }
+            };
+        }
+
+        let comparison = create_comparison(left, *operator, right);
+        result = Some(match result {
+            Some(previous) => KaramelAstType::Control {
+                left: Rc::new(previous),
+                operator: KaramelOperatorType::And,
+                right: Rc::new(comparison)
+            },
+            None => comparison
+        });
+    }
+
+    result.ok_or(KaramelErrorType::InvalidExpression)
+}
+
+/* There are no 'less than' opcodes, operands are swapped */
+fn create_comparison(left: Rc<KaramelAstType>, operator: KaramelOperatorType, right: Rc<KaramelAstType>) -> KaramelAstType {
+    match operator {
+        KaramelOperatorType::LessEqualThan => KaramelAstType::Control {
+            left: right,
+            operator: KaramelOperatorType::GreaterEqualThan,
+            right: left
+        },
+        KaramelOperatorType::LessThan => KaramelAstType::Control {
+            left: right,
+            operator: KaramelOperatorType::GreaterThan,
+            right: left
+        },
+        _ => KaramelAstType::Control {
+            left,
+            operator,
+            right
+        }
+    }
 }
 
 pub fn parse_control<T: SyntaxParserTrait>(parser: &SyntaxParser, operators: &[KaramelOperatorType]) -> AstResult {
@@ -112,17 +155,15 @@ pub fn parse_control<T: SyntaxParserTrait>(parser: &SyntaxParser, operators: &[K
             }
 
             parser.cleanup_whitespaces();
-            let right_expr = with_flag(SyntaxFlag::IN_EXPRESSION, parser, || T::parse(parser));
-            match right_expr {
-                Ok(KaramelAstType::None) => return Err(KaramelErrorType::RightSideOfExpressionNotFound),
-                Ok(_) => (),
-                Err(_) => return right_expr
+            let right_expr = match with_flag(SyntaxFlag::IN_EXPRESSION, parser, || T::parse(parser))? {
+                KaramelAstType::None => return Err(KaramelErrorType::RightSideOfExpressionNotFound),
+                right_expr => right_expr
             };
 
             left_expr = KaramelAstType::Control {
                 left: Rc::new(left_expr),
                 operator,
-                right: Rc::new(right_expr.unwrap())
+                right: Rc::new(right_expr)
             };
         }        
         else {
@@ -0,0 +1,69 @@
+use std::rc::Rc;
+
+use crate::types::*;
+use crate::syntax::{SyntaxParser, SyntaxParserTrait, SyntaxFlag};
+use crate::syntax::util::parse_block_body;
+use crate::compiler::ast::KaramelAstType;
+use crate::error::KaramelErrorType;
+
+pub struct DeferParser;
+
+impl SyntaxParserTrait for DeferParser {
+    fn parse(parser: &SyntaxParser) -> AstResult {
+        let index_backup = parser.get_index();
+        parser.indentation_check()?;
+
+        let indentation = parser.get_indentation();
+        if !parser.match_keyword(KaramelKeywordType::Defer) {
+            parser.set_index(index_backup);
+            return Ok(KaramelAstType::None);
+        }
+
+        let parser_flags = parser.flags.get();
+        if !parser_flags.contains(SyntaxFlag::FUNCTION_DEFINATION) {
+            parser.set_index(index_backup);
+            return Err(KaramelErrorType::DeferMustBeUsedInFunction);
+        }
+
+        /* Deferred block runs after the function body, so it can not leave the loop or the function */
+        parser.flags.set(parser_flags - SyntaxFlag::LOOP - SyntaxFlag::FUNCTION_DEFINATION);
+        let body = parse_block_body(parser);
+        parser.flags.set(parser_flags);
+        parser.set_indentation(indentation);
+
+        Ok(KaramelAstType::Defer(Rc::new(body?)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::KaramelErrorType;
+    use crate::parser::*;
+    use crate::syntax::*;
+    use crate::compiler::ast::KaramelAstType;
+    use std::rc::Rc;
+
+    fn parse(code: &str) -> Result<Rc<KaramelAstType>, KaramelErrorType> {
+        let mut parser = Parser::new(code);
+        parser.parse().unwrap();
+        SyntaxParser::new(parser.tokens().to_vec()).parse().map_err(|error| error.error_type)
+    }
+
+    #[test]
+    fn defer() {
+        let ast = parse("fonk test():\n    ertele:\n        a = 1\n    a = 2").unwrap();
+        match &*ast {
+            KaramelAstType::FunctionDefination { body, .. } => match &**body {
+                KaramelAstType::Block(statements) => assert!(matches!(&*statements[0], KaramelAstType::Defer(_))),
+                _ => assert!(false, "Fonksiyon gövdesi blok değil")
+            },
+            _ => assert!(false, "Fonksiyon tanımlanmadı")
+        };
+    }
+
+    #[test]
+    fn defer_outside_function() {
+        assert_eq!(parse("ertele:\n    a = 1"), Err(KaramelErrorType::DeferMustBeUsedInFunction));
+        assert_eq!(parse("fonk test():\n    ertele:\n        döndür 1"), Err(KaramelErrorType::ReturnMustBeUsedInFunction));
+    }
+}
@@ -0,0 +1,83 @@
+use crate::types::*;
+use crate::syntax::{SyntaxParser, SyntaxParserTrait};
+use crate::syntax::primative::PrimativeParser;
+use crate::compiler::ast::KaramelAstType;
+use crate::error::KaramelErrorType;
+
+pub struct EnumDefinationParser;
+
+impl SyntaxParserTrait for EnumDefinationParser {
+    fn parse(parser: &SyntaxParser) -> AstResult {
+        let index_backup = parser.get_index();
+        parser.indentation_check()?;
+
+        if !parser.match_keyword(KaramelKeywordType::Enum) {
+            parser.set_index(index_backup);
+            return Ok(KaramelAstType::None);
+        }
+
+        let name = match PrimativeParser::parse_symbol(parser)? {
+            KaramelAstType::Symbol(name) => name,
+            _ => return Err(KaramelErrorType::EnumVariantsRequired)
+        };
+
+        parser.cleanup_whitespaces();
+        if parser.match_operator(&[KaramelOperatorType::ColonMark]).is_none() {
+            return Err(KaramelErrorType::EnumVariantsRequired);
+        }
+
+        let mut variants: Vec<String> = Vec::new();
+        loop {
+            let variant = match PrimativeParser::parse_symbol(parser)? {
+                KaramelAstType::Symbol(variant) => variant,
+                _ => return Err(KaramelErrorType::EnumVariantsRequired)
+            };
+
+            if variants.contains(&variant) {
+                return Err(KaramelErrorType::EnumVariantAlreadyDefined(format!("{}::{}", name, variant)));
+            }
+            variants.push(variant);
+
+            parser.cleanup_whitespaces();
+            if parser.match_operator(&[KaramelOperatorType::Comma]).is_none() {
+                break;
+            }
+        }
+
+        Ok(KaramelAstType::Enum { name, variants })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::KaramelErrorType;
+    use crate::parser::*;
+    use crate::syntax::*;
+    use crate::compiler::ast::KaramelAstType;
+    use std::rc::Rc;
+
+    fn parse(code: &str) -> Result<Rc<KaramelAstType>, KaramelErrorType> {
+        let mut parser = Parser::new(code);
+        parser.parse().unwrap();
+        SyntaxParser::new(parser.tokens().to_vec()).parse().map_err(|error| error.error_type)
+    }
+
+    #[test]
+    fn enum_defination() {
+        match &*parse("seçenekler Renk: Kırmızı, Yeşil, Mavi").unwrap() {
+            KaramelAstType::Enum { name, variants } => {
+                assert_eq!(name, "Renk");
+                assert_eq!(variants, &["Kırmızı", "Yeşil", "Mavi"]);
+            },
+            ast => panic!("{:?}", ast)
+        };
+    }
+
+    #[test]
+    fn enum_without_variants() {
+        assert_eq!(parse("seçenekler Renk"), Err(KaramelErrorType::EnumVariantsRequired));
+        assert_eq!(parse("seçenekler Renk:"), Err(KaramelErrorType::EnumVariantsRequired));
+        assert_eq!(parse("seçenekler Renk: Kırmızı,"), Err(KaramelErrorType::EnumVariantsRequired));
+        assert_eq!(parse("seçenekler Renk: Kırmızı, Kırmızı"), Err(KaramelErrorType::EnumVariantAlreadyDefined("Renk::Kırmızı".to_string())));
+    }
+}
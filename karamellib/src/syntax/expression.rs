@@ -9,6 +9,7 @@ use crate::syntax::util::update_functions_for_temp_return;
 use crate::compiler::ast::KaramelAstType;
 use crate::compiler::value::KaramelPrimative;
 use crate::error::KaramelErrorType;
+use crate::interner::intern;
 
 use super::util::{mut_with_flag, with_flag};
 
@@ -16,6 +17,7 @@ pub struct ExpressionParser;
 
 impl SyntaxParserTrait for ExpressionParser {
     fn parse(parser: &SyntaxParser) -> AstResult {
+        let _guard = parser.enter()?;
         let mut ast = OrParser::parse(parser)?;
     
         loop {
@@ -38,7 +40,7 @@ impl SyntaxParserTrait for ExpressionParser {
                             body: Rc::new(ast),
                             
                             /* Convert symbol to text */
-                            indexer: Rc::new(KaramelAstType::Primative(Rc::new(KaramelPrimative::Text(Rc::new(symbol.to_string()))))) 
+                            indexer: Rc::new(KaramelAstType::Primative(Rc::new(KaramelPrimative::Text(intern(symbol.as_str()))))) 
                         }
                     },
                     _ => return Err(KaramelErrorType::FunctionCallSyntaxNotValid)
@@ -47,7 +49,10 @@ impl SyntaxParserTrait for ExpressionParser {
             
             /* parse for '["data"]' */
             else if parser.check_operator(&KaramelOperatorType::SquareBracketStart) {
-                ast = UnaryParser::parse_indexer(Rc::new(ast), parser)?;
+                ast = match UnaryParser::parse_indexer(Rc::new(ast), parser)? {
+                    KaramelAstType::None => return Err(KaramelErrorType::IndexerNotClosed),
+                    indexer => indexer
+                };
             } else {
                 parser.set_index(index_backup);
                 break;
@@ -42,6 +42,20 @@ impl SyntaxParserTrait for FuncCallParser {
     }
 }
 
+impl FuncCallParser {
+    fn is_assert_call(ast: &KaramelAstType) -> bool {
+        let name = match ast {
+            KaramelAstType::Symbol(name) => name,
+            KaramelAstType::ModulePath(path) => match path.last() {
+                Some(name) => name,
+                None => return false
+            },
+            _ => return false
+        };
+        name == "doğrula"
+    }
+}
+
 impl ExtensionSyntaxParser for FuncCallParser {
     fn parsable(parser: &SyntaxParser) -> bool {
         if parser.flags.get().contains(SyntaxFlag::IN_DICT_INDEXER) {
@@ -59,6 +73,7 @@ impl ExtensionSyntaxParser for FuncCallParser {
 
         if let Some(_) = parser.match_operator(&[KaramelOperatorType::LeftParentheses]) {
             let mut arguments = Vec::new();
+            let assert_call = FuncCallParser::is_assert_call(ast);
 
             let inner_parser_flags  = parser.flags.get();
             parser.flags.set(parser_flags | SyntaxFlag::IN_FUNCTION_ARG);
@@ -68,11 +83,17 @@ impl ExtensionSyntaxParser for FuncCallParser {
             while continue_to_parse {
                 parser.cleanup_whitespaces();
                 
+                let argument_start = parser.get_index();
                 let param_expression = ExpressionParser::parse(parser);
                 match param_expression {
                     Err(_) => return param_expression,
                     _ => ()
                 };
+
+                /* Text of the condition is shown when the assertion fails */
+                if assert_call && arguments.is_empty() && !matches!(param_expression, Ok(KaramelAstType::None)) {
+                    parser.mark_assert_expression(argument_start, parser.get_index());
+                }
                 
                 parser.cleanup_whitespaces();
 
@@ -1,9 +1,12 @@
 use crate::types::*;
 use crate::syntax::{SyntaxParser, SyntaxParserTrait, SyntaxFlag};
 use crate::syntax::primative::PrimativeParser;
+use crate::syntax::expression::ExpressionParser;
+use crate::syntax::util::update_functions_for_temp_return;
 use crate::compiler::ast::{KaramelAstType};
 use crate::syntax::block::{SingleLineBlockParser, MultiLineBlockParser};
 use crate::error::KaramelErrorType;
+use crate::constants::KARAMEL_TYPE_NAMES;
 use std::rc::Rc;
 
 pub struct FunctionDefinationParser;
@@ -13,12 +16,27 @@ impl SyntaxParserTrait for FunctionDefinationParser {
         let index_backup = parser.get_index();
         parser.indentation_check()?;
 
+        /* Documentation can be written before the decorators or between the decorators and the definition */
+        let documentation = parser.get_documentation(parser.get_index());
+        let decorators = Self::parse_decorators(parser)?;
+        let documentation = documentation.or_else(|| parser.get_documentation(parser.get_index()));
+
+        let private = parser.match_keyword(KaramelKeywordType::Private);
+        if private {
+            parser.cleanup_whitespaces();
+        }
+
         if parser.match_keyword(KaramelKeywordType::Fn) {
+            if parser.teaching_mode && parser.flags.get().contains(SyntaxFlag::FUNCTION_DEFINATION) {
+                return Err(KaramelErrorType::DisabledInTeachingMode("İç içe fonksiyon".to_string()));
+            }
+
             let indentation = parser.get_indentation();
 
             parser.cleanup_whitespaces();
 
             let mut arguments = Vec::new();
+            let mut argument_types = Vec::new();
             let name_expression = PrimativeParser::parse_symbol(parser)?;
             let function_name = match name_expression {
                 KaramelAstType::Symbol(text) => text,
@@ -44,6 +62,9 @@ impl SyntaxParserTrait for FunctionDefinationParser {
                         _ => return Err(KaramelErrorType::ArgumentMustBeText)
                     };
 
+                    parser.cleanup_whitespaces();
+                    argument_types.push(Self::parse_argument_type(parser)?);
+
                     parser.cleanup_whitespaces();
                     if let None = parser.match_operator(&[KaramelOperatorType::Comma]) {
                         break;
@@ -74,16 +95,13 @@ impl SyntaxParserTrait for FunctionDefinationParser {
 
             let has_return = match &body {
                 KaramelAstType::Return(_) => true,
-                KaramelAstType::Block(blocks) =>
-                    match &*blocks[blocks.len() - 1] {
-                        KaramelAstType::Return(_) => true,
-                        _ => false
-                    },
+                KaramelAstType::Block(blocks) => matches!(blocks.last().map(|block| &**block), Some(KaramelAstType::Return(_))),
                 KaramelAstType::None => return Err(KaramelErrorType::FunctionConditionBodyNotFound),
                 _ => false
             };
 
             if !has_return {
+                parser.statement_positions.borrow_mut().push(None);
                 body = match body {
                     KaramelAstType::Block(mut blocks) => {
                         blocks.push(Rc::new(KaramelAstType::Return(Rc::new(KaramelAstType::None))));
@@ -101,14 +119,80 @@ impl SyntaxParserTrait for FunctionDefinationParser {
             let function_defination_ast = KaramelAstType::FunctionDefination {
                 name: function_name,
                 body: Rc::new(body),
-                arguments: arguments
+                arguments: arguments,
+                argument_types,
+                documentation,
+                decorators,
+                private
             };
 
             parser.set_indentation(indentation);
             return Ok(function_defination_ast);
         }
         
+        if private {
+            return Err(KaramelErrorType::PrivateWithoutFunction);
+        }
+
+        if !decorators.is_empty() {
+            return Err(KaramelErrorType::DecoratorWithoutFunction);
+        }
+
         parser.set_index(index_backup);
         return Ok(KaramelAstType::None);
     }
 }
+
+impl FunctionDefinationParser {
+    /* Each decorator is written on its own line with the same indentation, '@zamanla' or '@tekrarla(3)' */
+    fn parse_decorators(parser: &SyntaxParser) -> Result<Vec<Rc<KaramelAstType>>, KaramelErrorType> {
+        let mut decorators = Vec::new();
+        while parser.match_operator(&[KaramelOperatorType::At]).is_some() {
+            let decorator = ExpressionParser::parse(parser)?;
+            if let KaramelAstType::None = decorator {
+                return Err(KaramelErrorType::DecoratorWithoutFunction);
+            }
+
+            /* Result of the decorator call is called with the function */
+            update_functions_for_temp_return(&decorator);
+            decorators.push(Rc::new(decorator));
+
+            parser.cleanup_whitespaces();
+            if !parser.get_newline().0 {
+                return Err(KaramelErrorType::DecoratorWithoutFunction);
+            }
+            parser.indentation_check()?;
+        }
+        Ok(decorators)
+    }
+
+
+    /* Optional type after the argument name, 'fonk topla(a: sayı, b: sayı)' */
+    fn parse_argument_type(parser: &SyntaxParser) -> Result<Option<String>, KaramelErrorType> {
+        let index_backup = parser.get_index();
+        if parser.match_operator(&[KaramelOperatorType::ColonMark]).is_none() {
+            return Ok(None);
+        }
+
+        parser.cleanup_whitespaces();
+        let type_index = parser.get_index();
+        let type_name = match parser.match_keyword(KaramelKeywordType::Empty) {
+            true => "boş".to_string(),
+            false => match PrimativeParser::parse_symbol(parser)? {
+                KaramelAstType::Symbol(text) => text,
+                _ => {
+                    parser.set_index(index_backup);
+                    return Ok(None);
+                }
+            }
+        };
+
+        match KARAMEL_TYPE_NAMES.contains(&&type_name[..]) {
+            true => Ok(Some(type_name)),
+            false => {
+                parser.set_index(type_index);
+                Err(KaramelErrorType::UnknownTypeName(type_name))
+            }
+        }
+    }
+}
@@ -7,6 +7,7 @@ use crate::compiler::ast::{KaramelAstType, KaramelIfStatementElseItem};
 use crate::syntax::block::{SingleLineBlockParser, MultiLineBlockParser};
 use crate::error::KaramelErrorType;
 use crate::syntax::control::OrParser;
+use crate::syntax::util::update_functions_for_temp_return;
 
 pub struct IfConditiontParser;
 
@@ -20,6 +21,8 @@ impl SyntaxParserTrait for IfConditiontParser {
         parser.cleanup_whitespaces();
 
         if parser.match_keyword(KaramelKeywordType::If) {
+            /* Functions that are called at the condition should return their values */
+            update_functions_for_temp_return(&expression);
             parser.cleanup_whitespaces();
             if let None = parser.match_operator(&[KaramelOperatorType::ColonMark]) {
                 return Err(KaramelErrorType::ColonMarkMissing);
@@ -0,0 +1,45 @@
+use crate::types::*;
+use crate::syntax::{SyntaxParser, SyntaxParserTrait};
+use crate::syntax::util::parse_declaration;
+use crate::compiler::ast::KaramelAstType;
+use crate::error::KaramelErrorType;
+
+pub struct LocalParser;
+
+impl SyntaxParserTrait for LocalParser {
+    fn parse(parser: &SyntaxParser) -> AstResult {
+        match parse_declaration(parser, KaramelKeywordType::Local, KaramelErrorType::LocalValueRequired)? {
+            Some(assignment) => Ok(KaramelAstType::Local(assignment)),
+            None => Ok(KaramelAstType::None)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::KaramelErrorType;
+    use crate::parser::*;
+    use crate::syntax::*;
+    use crate::compiler::ast::KaramelAstType;
+    use std::rc::Rc;
+
+    fn parse(code: &str) -> Result<Rc<KaramelAstType>, KaramelErrorType> {
+        let mut parser = Parser::new(code);
+        parser.parse().unwrap();
+        SyntaxParser::new(parser.tokens().to_vec()).parse().map_err(|error| error.error_type)
+    }
+
+    #[test]
+    fn local() {
+        match &*parse("doğru ise:\n    yerel a = 1").unwrap() {
+            KaramelAstType::IfStatement { body, .. } => assert!(matches!(&**body, KaramelAstType::Local(_))),
+            ast => panic!("{:?}", ast)
+        };
+    }
+
+    #[test]
+    fn local_without_value() {
+        assert_eq!(parse("yerel a"), Err(KaramelErrorType::LocalValueRequired));
+        assert_eq!(parse("yerel a -= 1"), Err(KaramelErrorType::LocalValueRequired));
+    }
+}
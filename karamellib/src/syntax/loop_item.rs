@@ -13,11 +13,9 @@ impl SyntaxParserTrait for LoopItemParser {
         if parser.check_keyword(KaramelKeywordType::Break) ||
            parser.check_keyword(KaramelKeywordType::Continue) {
             if parser.flags.get().contains(SyntaxFlag::LOOP) {
-                let keyword = parser.peek_token().unwrap().token_type.get_keyword();
-                parser.consume_token();
-                match keyword {
-                    KaramelKeywordType::Break => return Ok(KaramelAstType::Break),
-                    KaramelKeywordType::Continue => return Ok(KaramelAstType::Continue),
+                match parser.match_keywords(&[KaramelKeywordType::Break, KaramelKeywordType::Continue]) {
+                    Some(KaramelKeywordType::Break) => return Ok(KaramelAstType::Break),
+                    Some(KaramelKeywordType::Continue) => return Ok(KaramelAstType::Continue),
                     _ => ()
                 };
             }
@@ -15,26 +15,105 @@ pub mod loops;
 pub mod loop_item;
 pub mod expression;
 pub mod load_module;
+pub mod use_module;
+pub mod try_catch;
+pub mod defer;
+pub mod with_block;
+pub mod assume;
+pub mod constant;
+pub mod local;
+pub mod enum_defination;
 
 use std::borrow::Borrow;
 use std::rc::Rc;
 use std::vec::Vec;
-use std::cell::Cell;
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
 
 use crate::types::*;
 use self::block::MultiLineBlockParser;
 use crate::compiler::ast::KaramelAstType;
 use crate::error::*;
+use crate::error::diagnostic::Diagnostic;
+use crate::constants::KARAMEL_MAX_NESTING_DEPTH;
+use crate::parser::Parser;
 
 use bitflags::bitflags;
 
 pub type ParseType = fn(parser: &SyntaxParser) -> AstResult;
 
+/// Tokenizes and parses the code with the hardened parser. Any input returns an error instead of a panic or a stack overflow,
+/// so it can be used by fuzzers and for the code that comes from the web.
+pub fn parse_checked(source: &str) -> Result<Rc<KaramelAstType>, KaramelError> {
+    let mut parser = Parser::new(source);
+    parser.parse()?;
+    SyntaxParser::hardened(parser.tokens()).parse()
+}
+
 pub struct SyntaxParser {
     pub tokens: Vec<Token>,
     pub index: Cell<usize>,
     pub indentation: Cell<usize>,
-    pub flags: Cell<SyntaxFlag>
+    pub flags: Cell<SyntaxFlag>,
+    pub teaching_mode: bool,
+
+    /// Positions of the parsed statements in the source order. Generated statements do not have a position.
+    pub statement_positions: RefCell<Vec<Option<SourcePosition>>>,
+
+    /// Conditions of the 'doğrula' calls. Their texts are shown when the assertion fails.
+    pub assert_expressions: RefCell<Vec<ExpressionSpan>>,
+
+    /// Documentation comments with the index of the token that comes after them.
+    pub documentations: HashMap<usize, Rc<String>>,
+
+    /// Nesting level of the expressions and blocks that are being parsed
+    pub depth: Cell<usize>,
+
+    /// Parsing fails after this level. Only the hardened parser has a limit.
+    pub max_depth: Option<usize>,
+
+    /// Failed statements are skipped instead of stopping the parser. Set by 'parse_with_recovery'.
+    pub recover: Cell<bool>,
+
+    /// Errors of the skipped statements
+    pub errors: RefCell<Vec<KaramelError>>
+}
+
+/// Keeps the nesting level increased until it is dropped.
+pub struct DepthGuard<'a> {
+    parser: &'a SyntaxParser
+}
+
+impl<'a> Drop for DepthGuard<'a> {
+    fn drop(&mut self) {
+        self.parser.depth.set(self.parser.depth.get() - 1);
+    }
+}
+
+/// Source range of an expression. End column is after the last character.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ExpressionSpan {
+    /// Statement that contains the expression
+    pub statement: SourcePosition,
+    pub start: SourcePosition,
+    pub end: SourcePosition
+}
+
+impl ExpressionSpan {
+    /// Text of the range. Lines between the start and the end are kept.
+    pub fn text(&self, source: &str) -> String {
+        let lines: Vec<&str> = source.split('\n').skip(self.start.line as usize).take((self.end.line - self.start.line) as usize + 1).collect();
+        let mut text = String::new();
+        for (index, line) in lines.iter().enumerate() {
+            let start = if index == 0 { self.start.column as usize } else { 0 };
+            let end = if index + 1 == lines.len() { self.end.column as usize } else { usize::MAX };
+            if index > 0 {
+                text.push('\n');
+            }
+            text.extend(line.chars().skip(start).take(end.saturating_sub(start)));
+        }
+        text
+    }
 }
 
 bitflags! {
@@ -61,14 +140,70 @@ pub trait ExtensionSyntaxParser: Sized {
 
 impl SyntaxParser {
     pub fn new(tokens: Vec<Token>) -> SyntaxParser {
+        /* Documentation comments are removed from the tokens, consecutive lines are joined and kept for the next token */
+        let mut documentations = HashMap::new();
+        let mut lines: Vec<String> = Vec::new();
+        let mut syntax_tokens = Vec::with_capacity(tokens.len());
+
+        for token in tokens.into_iter() {
+            match &token.token_type {
+                KaramelTokenType::DocComment(line) => {
+                    lines.push(line.to_string());
+                    continue;
+                },
+                KaramelTokenType::WhiteSpace(_) | KaramelTokenType::NewLine(_) | KaramelTokenType::Comment(_) => (),
+                _ => if !lines.is_empty() {
+                    documentations.insert(syntax_tokens.len(), Rc::new(lines.join("\n")));
+                    lines.clear();
+                }
+            };
+            syntax_tokens.push(token);
+        }
+
         SyntaxParser {
-            tokens,
+            tokens: syntax_tokens,
             index: Cell::new(0),
             indentation: Cell::new(0),
-            flags: Cell::new(SyntaxFlag::NONE)
+            flags: Cell::new(SyntaxFlag::NONE),
+            teaching_mode: false,
+            statement_positions: RefCell::new(Vec::new()),
+            assert_expressions: RefCell::new(Vec::new()),
+            documentations,
+            depth: Cell::new(0),
+            max_depth: None,
+            recover: Cell::new(false),
+            errors: RefCell::new(Vec::new())
         }
     }
 
+    /// Parser for the code that comes from untrusted sources. Deeply nested code returns an error instead of overflowing the stack.
+    pub fn hardened(tokens: Vec<Token>) -> SyntaxParser {
+        let mut parser = SyntaxParser::new(tokens);
+        parser.max_depth = Some(KARAMEL_MAX_NESTING_DEPTH);
+        parser
+    }
+
+    /// Increases the nesting level. Recursive parsers call it before parsing their inner parts.
+    pub fn enter(&self) -> Result<DepthGuard<'_>, KaramelErrorType> {
+        match self.max_depth {
+            Some(max_depth) if self.depth.get() >= max_depth => Err(KaramelErrorType::NestingTooDeep(max_depth)),
+            _ => {
+                self.depth.set(self.depth.get() + 1);
+                Ok(DepthGuard { parser: self })
+            }
+        }
+    }
+
+    /// Documentation comment that is written just before the token.
+    pub fn get_documentation(&self, index: usize) -> Option<Rc<String>> {
+        self.documentations.get(&index).cloned()
+    }
+
+    /// Syntax parser looks back to the previous tokens, so the stream is collected before parsing.
+    pub fn from_stream<I: IntoIterator<Item = Result<Token, Diagnostic>>>(tokens: I) -> Result<SyntaxParser, Diagnostic> {
+        Ok(SyntaxParser::new(tokens.into_iter().collect::<Result<Vec<Token>, Diagnostic>>()?))
+    }
+
     pub fn parse(&self) -> Result<Rc<KaramelAstType>, KaramelError> {
         return match MultiLineBlockParser::parse(&self) {
             Ok(ast) => {
@@ -84,23 +219,79 @@ impl SyntaxParser {
                 }
                 Ok(Rc::new(ast))
             },
-            Err(error) => {
-                if let Ok(token) = self.valid_token() {
-                    log::debug!("Syntax parse failed : {:?}", token);
-                    return Err(KaramelError {
-                        error_type: error,
-                        line: token.line,
-                        column: token.end
-                    });
+            Err(error) => Err(self.located_error(error))
+        };
+    }
+
+    /// Parses the whole code even if it has errors. Statements that can not be parsed are skipped until the next line
+    /// that has the same or lower indentation, so all the errors are returned together.
+    pub fn parse_with_recovery(&self) -> Result<Rc<KaramelAstType>, Vec<KaramelError>> {
+        self.recover.set(true);
+        let ast = loop {
+            self.set_indentation(0);
+            self.flags.set(SyntaxFlag::NONE);
+
+            let start = self.get_index();
+            match self.parse() {
+                Ok(ast) => break Some(ast),
+                Err(error) => {
+                    self.errors.borrow_mut().push(error);
+                    self.synchronize(0, start);
+                    if self.peek_token().is_err() {
+                        break None;
+                    }
                 }
+            };
+        };
+        self.recover.set(false);
+
+        let mut errors = self.errors.take();
+        match (ast, errors.is_empty()) {
+            (Some(ast), true) => Ok(ast),
+            _ => {
+                errors.sort_by_key(|error| (error.line, error.column));
+                Err(errors)
+            }
+        }
+    }
 
-                return Err(KaramelError {
-                    error_type: error,
-                    line: 0,
-                    column: 0
-                });
+    /// Error with the position of the last parsed token.
+    pub fn located_error(&self, error_type: KaramelErrorType) -> KaramelError {
+        match self.valid_token() {
+            Ok(token) => {
+                log::debug!("Syntax parse failed : {:?}", token);
+                KaramelError {
+                    error_type,
+                    line: token.line,
+                    column: token.end
+                }
+            },
+            Err(_) => KaramelError {
+                error_type,
+                line: 0,
+                column: 0
             }
-        };
+        }
+    }
+
+    /// Skips the tokens until the end of a line whose next line has the given or lower indentation.
+    /// At least one token is skipped when the parser did not move after the 'start' index.
+    pub fn synchronize(&self, indentation: usize, start: usize) {
+        if self.get_index() <= start {
+            self.set_index(start);
+            self.consume_token();
+        }
+
+        while let Ok(token) = self.peek_token() {
+            if let KaramelTokenType::NewLine(size) = token.token_type {
+                match self.next_token() {
+                    Ok(Token { token_type: KaramelTokenType::NewLine(_), .. }) => (),
+                    Ok(_) if size as usize > indentation => (),
+                    _ => return
+                };
+            }
+            self.consume_token();
+        }
     }
 
     pub fn set_indentation(&self, indentation: usize) {
@@ -126,14 +317,36 @@ impl SyntaxParser {
         else if indentation != self.get_indentation() {
             return false;
         }
-        else if self.peek_token().is_err() {
-            return false;
-        }
-        else if self.peek_token().unwrap().start != indentation as u32 {
-            return false;
-        }
 
-        return true;
+        return match self.peek_token() {
+            Ok(token) => token.start == indentation as u32,
+            Err(_) => false
+        };
+    }
+
+    /// Line of the next token that is not a whitespace or a new line.
+    pub fn next_statement_position(&self) -> Option<SourcePosition> {
+        self.tokens[self.index.get()..].iter()
+            .find(|token| !matches!(token.token_type, KaramelTokenType::WhiteSpace(_) | KaramelTokenType::NewLine(_)))
+            .map(|token| SourcePosition::new(token.line, token.start))
+    }
+
+    /// Keeps the range of the tokens between the indexes as an expression of the current statement.
+    pub fn mark_assert_expression(&self, start: usize, end: usize) {
+        let statement = match self.statement_positions.borrow().last() {
+            Some(Some(statement)) => *statement,
+            _ => return
+        };
+
+        let mut tokens = self.tokens[start..end].iter().filter(|token| !matches!(token.token_type, KaramelTokenType::WhiteSpace(_) | KaramelTokenType::NewLine(_)));
+        if let Some(first) = tokens.next() {
+            let last = tokens.last().unwrap_or(first);
+            self.assert_expressions.borrow_mut().push(ExpressionSpan {
+                statement,
+                start: SourcePosition::new(first.line, first.start),
+                end: SourcePosition::new(last.line, last.end)
+            });
+        }
     }
 
     pub fn peek_token(&self) -> Result<&Token, ()> {
@@ -185,33 +398,22 @@ impl SyntaxParser {
     }
 
     pub fn check_keyword<T: Borrow<KaramelKeywordType>>(&self, keyword: T) -> bool {
-        let token = self.peek_token();
-        if token.is_err() { return false; }
-        return match &token.unwrap().token_type {
-            KaramelTokenType::Keyword(token_keyword) => {
-                if keyword.borrow() == token_keyword {
-                    return true;
-                }
-                return false;
-            },
+        return match self.peek_token() {
+            Ok(Token { token_type: KaramelTokenType::Keyword(token_keyword), .. }) => keyword.borrow() == token_keyword,
             _ => false
         }
     }
 
     fn get_newline(&self) -> (bool, usize) {
-        let token = self.peek_token();
-        if token.is_err() { return (false, 0); }
-        return match token.unwrap().token_type {
-            KaramelTokenType::NewLine(size) => (true, size as usize),
+        return match self.peek_token() {
+            Ok(Token { token_type: KaramelTokenType::NewLine(size), .. }) => (true, *size as usize),
             _ => (false, 0)
         }
     }
 
     fn check_operator(&self, operator: &KaramelOperatorType) -> bool {
-        let token = self.peek_token();
-        if token.is_err() { return false; }
-        return match token.unwrap().token_type {
-            KaramelTokenType::Operator(token_operator) => *operator == token_operator,
+        return match self.peek_token() {
+            Ok(Token { token_type: KaramelTokenType::Operator(token_operator), .. }) => operator == token_operator,
             _ => false
         }
     }
@@ -287,9 +489,8 @@ impl SyntaxParser {
         while let Ok(current_token) = self.peek_token() {                
             let success = match current_token.token_type {
                 KaramelTokenType::NewLine(size) => {
-                    let token_type = &self.next_token().unwrap().token_type;
-                    if let KaramelTokenType::NewLine(_) = token_type {
-                        /* If next token is newline, no need to check */
+                    if let Ok(Token { token_type: KaramelTokenType::NewLine(_), .. }) | Err(_) = self.next_token() {
+                        /* If next token is newline or there is no next token, no need to check */
                         true
                     }
                     else {
@@ -322,9 +523,8 @@ impl SyntaxParser {
         while let Ok(current_token) = self.peek_token() {               
             let success = match current_token.token_type {
                 KaramelTokenType::NewLine(size) => {
-                    let token_type = &self.next_token().unwrap().token_type;
-                    if let KaramelTokenType::NewLine(_) = token_type {
-                        /* If next token is newline, no need to check */
+                    if let Ok(Token { token_type: KaramelTokenType::NewLine(_), .. }) | Err(_) = self.next_token() {
+                        /* If next token is newline or there is no next token, no need to check */
                         true
                     }
                     else {
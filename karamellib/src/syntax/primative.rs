@@ -15,15 +15,16 @@ impl PrimativeParser {
         let index_backup = parser.get_index();
         parser.cleanup_whitespaces();
 
-        let token = parser.peek_token();
-        if token.is_err() {
-            return Ok(KaramelAstType::None);
-        }
+        let token = match parser.peek_token() {
+            Ok(token) => token,
+            Err(_) => return Ok(KaramelAstType::None)
+        };
 
-        let result = match &token.unwrap().token_type {
+        let result = match &token.token_type {
             KaramelTokenType::Integer(int)      => Ok(KaramelAstType::Primative(Rc::new(KaramelPrimative::Number(*int as f64)))),
             KaramelTokenType::Double(double)    => Ok(KaramelAstType::Primative(Rc::new(KaramelPrimative::Number(*double)))),
             KaramelTokenType::Text(text)        => Ok(KaramelAstType::Primative(Rc::new(KaramelPrimative::Text(Rc::clone(text))))),
+            KaramelTokenType::Bytes(bytes)      => Ok(KaramelAstType::Primative(Rc::new(KaramelPrimative::Bytes(Rc::clone(bytes))))),
             KaramelTokenType::Keyword(keyword)  => {
                 match keyword {
                     KaramelKeywordType::True  => Ok(KaramelAstType::Primative(Rc::new(KaramelPrimative::Bool(true)))),
@@ -61,12 +62,10 @@ impl PrimativeParser {
 
                 parser.cleanup_whitespaces();
 
-                let ast = ExpressionParser::parse(parser);
-                if is_ast_empty(&ast) {
-                    return err_or_message(ast, KaramelErrorType::InvalidListItem);
-                }
-                
-                ast_vec.push(Rc::new(ast.unwrap()));
+                match ExpressionParser::parse(parser)? {
+                    KaramelAstType::None => return Err(KaramelErrorType::InvalidListItem),
+                    ast => ast_vec.push(Rc::new(ast))
+                };
 
                 parser.cleanup_whitespaces();
                 if parser.match_operator(&[KaramelOperatorType::Comma]).is_none()  {
@@ -123,14 +122,14 @@ impl PrimativeParser {
                 }
 
                 parser.cleanup();
-                let value = ExpressionParser::parse(parser);
-                if is_ast_empty(&value) {
-                    return err_or_message(value, KaramelErrorType::DictionaryValueNotValid);
-                }
+                let value = match ExpressionParser::parse(parser)? {
+                    KaramelAstType::None => return Err(KaramelErrorType::DictionaryValueNotValid),
+                    value => value
+                };
   
                 dict_items.push(Rc::new(KaramelDictItem {
                     key,
-                    value: Rc::new(value.unwrap())
+                    value: Rc::new(value)
                 }));
 
                 parser.cleanup();
@@ -153,12 +152,7 @@ impl PrimativeParser {
     pub fn parse_symbol(parser: &SyntaxParser) -> AstResult {
         let index_backup = parser.get_index();
         parser.cleanup_whitespaces();
-        let token = parser.peek_token();
-        if token.is_err() {
-            return Ok(KaramelAstType::None);
-        }
-
-        if let KaramelTokenType::Symbol(symbol) = &token.unwrap().token_type {
+        if let Ok(Token { token_type: KaramelTokenType::Symbol(symbol), .. }) = parser.peek_token() {
             parser.consume_token();
             return Ok(KaramelAstType::Symbol(symbol.to_string()));
         }
@@ -169,12 +163,7 @@ impl PrimativeParser {
     pub fn parse_module_path(parser: &SyntaxParser) -> AstResult {
         let index_backup = parser.get_index();
         parser.cleanup_whitespaces();
-        let token = parser.peek_token();
-        if token.is_err() {
-            return Ok(KaramelAstType::None);
-        }
-
-        if let KaramelTokenType::Symbol(symbol) = &token.unwrap().token_type {
+        if let Ok(Token { token_type: KaramelTokenType::Symbol(symbol), .. }) = parser.peek_token() {
             let mut symbol_definitions: Vec<String> = Vec::new();
             symbol_definitions.push(symbol.to_string());
 
@@ -182,7 +171,7 @@ impl PrimativeParser {
             loop {
                 if let Some(_) = parser.match_operator(&[KaramelOperatorType::ColonMark]) {
                     if let Some(_) = parser.match_operator(&[KaramelOperatorType::ColonMark]) {
-                        if let KaramelTokenType::Symbol(inner_symbol) = &parser.peek_token().unwrap().token_type {
+                        if let Ok(Token { token_type: KaramelTokenType::Symbol(inner_symbol), .. }) = parser.peek_token() {
                             parser.consume_token();
                             symbol_definitions.push(inner_symbol.to_string());
                             continue;
@@ -213,16 +202,16 @@ impl PrimativeParser {
         let index_backup = parser.get_index();
         if parser.match_operator(&[KaramelOperatorType::LeftParentheses]).is_some() {
             
-            let ast = ExpressionParser::parse(parser);
-            if is_ast_empty(&ast) {
-                return err_or_message(ast, KaramelErrorType::InvalidExpression);
-            }
+            let ast = match ExpressionParser::parse(parser)? {
+                KaramelAstType::None => return Err(KaramelErrorType::InvalidExpression),
+                ast => ast
+            };
 
             if parser.match_operator(&[KaramelOperatorType::RightParentheses]).is_none() {
                 return Err(KaramelErrorType::ParenthesesNotClosed);
             }
 
-            return Ok(ast.unwrap());
+            return Ok(ast);
         }
 
         parser.set_index(index_backup);
@@ -4,14 +4,22 @@ use crate::syntax::util::map_parser;
 use crate::syntax::if_condition::IfConditiontParser;
 use crate::syntax::assignment::AssignmentParser;
 use crate::syntax::load_module::LoadModuleParser;
+use crate::syntax::use_module::UseModuleParser;
 use crate::syntax::function_return::FunctionReturnParser;
 use crate::syntax::loop_item::LoopItemParser;
 use crate::syntax::loops::WhileLoopParser;
+use crate::syntax::try_catch::{TryParser, ThrowParser};
+use crate::syntax::defer::DeferParser;
+use crate::syntax::with_block::WithParser;
+use crate::syntax::assume::AssumeParser;
+use crate::syntax::constant::ConstantParser;
+use crate::syntax::local::LocalParser;
+use crate::syntax::enum_defination::EnumDefinationParser;
 
 pub struct StatementParser;
 
 impl SyntaxParserTrait for StatementParser {
     fn parse(parser: &SyntaxParser) -> AstResult {
-        return map_parser(parser, &[LoadModuleParser::parse, LoopItemParser::parse, WhileLoopParser::parse, FunctionReturnParser::parse, AssignmentParser::parse, IfConditiontParser::parse]);
+        return map_parser(parser, &[LoadModuleParser::parse, UseModuleParser::parse, LoopItemParser::parse, WhileLoopParser::parse, TryParser::parse, ThrowParser::parse, DeferParser::parse, WithParser::parse, AssumeParser::parse, ConstantParser::parse, LocalParser::parse, EnumDefinationParser::parse, FunctionReturnParser::parse, AssignmentParser::parse, IfConditiontParser::parse]);
     }
 }
@@ -0,0 +1,122 @@
+use std::rc::Rc;
+
+use crate::types::*;
+use crate::syntax::{SyntaxParser, SyntaxParserTrait, SyntaxFlag};
+use crate::syntax::util::{with_flag, parse_block_body};
+use crate::syntax::primative::PrimativeParser;
+use crate::syntax::expression::ExpressionParser;
+use crate::compiler::ast::KaramelAstType;
+use crate::error::KaramelErrorType;
+
+pub struct TryParser;
+pub struct ThrowParser;
+
+impl SyntaxParserTrait for TryParser {
+    fn parse(parser: &SyntaxParser) -> AstResult {
+        let index_backup = parser.get_index();
+        parser.indentation_check()?;
+
+        let indentation = parser.get_indentation();
+        if !parser.match_keyword(KaramelKeywordType::Try) {
+            parser.set_index(index_backup);
+            return Ok(KaramelAstType::None);
+        }
+
+        let body = parse_block_body(parser)?;
+        parser.set_indentation(indentation);
+
+        /* 'yakala' should be at the same indentation with 'dene' */
+        parser.cleanup_whitespaces();
+        if !parser.is_same_indentation(indentation) || !parser.match_keyword(KaramelKeywordType::Catch) {
+            return Err(KaramelErrorType::CatchBlockNotFound);
+        }
+
+        /* Variable name is optional */
+        parser.cleanup_whitespaces();
+        let variable = match PrimativeParser::parse_symbol(parser)? {
+            KaramelAstType::Symbol(name) => Some(name),
+            _ => None
+        };
+
+        let catch_body = parse_block_body(parser)?;
+        parser.set_indentation(indentation);
+
+        Ok(KaramelAstType::Try {
+            body: Rc::new(body),
+            variable,
+            catch_body: Rc::new(catch_body)
+        })
+    }
+}
+
+impl SyntaxParserTrait for ThrowParser {
+    fn parse(parser: &SyntaxParser) -> AstResult {
+        let index_backup = parser.get_index();
+        parser.cleanup_whitespaces();
+
+        if parser.match_keyword(KaramelKeywordType::Throw) {
+            parser.cleanup_whitespaces();
+
+            /* Thrown value should stay at the stack, so the function calls return their values */
+            return match with_flag(SyntaxFlag::IN_EXPRESSION, parser, || ExpressionParser::parse(parser))? {
+                KaramelAstType::None => Err(KaramelErrorType::InvalidExpression),
+                ast => Ok(KaramelAstType::Throw(Rc::new(ast)))
+            };
+        }
+
+        parser.set_index(index_backup);
+        Ok(KaramelAstType::None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::KaramelErrorType;
+    use crate::parser::*;
+    use crate::syntax::*;
+    use crate::compiler::value::KaramelPrimative;
+    use crate::compiler::ast::KaramelAstType;
+    use crate::types::KaramelOperatorType;
+    use std::rc::Rc;
+
+    fn parse(code: &str) -> Result<Rc<KaramelAstType>, KaramelErrorType> {
+        let mut parser = Parser::new(code);
+        parser.parse().unwrap();
+        SyntaxParser::new(parser.tokens().to_vec()).parse().map_err(|error| error.error_type)
+    }
+
+    fn assignment(name: &str, number: f64) -> KaramelAstType {
+        KaramelAstType::Assignment {
+            variable: Rc::new(KaramelAstType::Symbol(name.to_string())),
+            operator: KaramelOperatorType::Assign,
+            expression: Rc::new(KaramelAstType::Primative(Rc::new(KaramelPrimative::Number(number))))
+        }
+    }
+
+    #[test]
+    fn try_catch() {
+        assert_eq!(parse("dene:\n    a = 1\nyakala hata:\n    a = 2\n"), Ok(Rc::new(KaramelAstType::Try {
+            body: Rc::new(assignment("a", 1.0)),
+            variable: Some("hata".to_string()),
+            catch_body: Rc::new(assignment("a", 2.0))
+        })));
+
+        assert_eq!(parse("dene: a = 1\nyakala: a = 2"), Ok(Rc::new(KaramelAstType::Try {
+            body: Rc::new(assignment("a", 1.0)),
+            variable: None,
+            catch_body: Rc::new(assignment("a", 2.0))
+        })));
+    }
+
+    #[test]
+    fn throw() {
+        assert_eq!(parse("fırlat 'hata'"), Ok(Rc::new(KaramelAstType::Throw(Rc::new(KaramelAstType::Primative(Rc::new(KaramelPrimative::Text(Rc::new("hata".to_string())))))))));
+        assert_eq!(parse("firlat"), Err(KaramelErrorType::InvalidExpression));
+    }
+
+    #[test]
+    fn catch_missing() {
+        assert_eq!(parse("dene:\n    a = 1\na = 2"), Err(KaramelErrorType::CatchBlockNotFound));
+        assert_eq!(parse("dene:\n    a = 1\n    yakala:\n        a = 2"), Err(KaramelErrorType::CatchBlockNotFound));
+    }
+}
@@ -1,9 +1,8 @@
 use crate::types::*;
 use crate::syntax::{SyntaxParser, SyntaxParserTrait};
-use crate::syntax::util::map_parser;
+use crate::syntax::util::{map_parser, with_flag};
 use crate::syntax::primative::PrimativeParser;
 use crate::syntax::func_call::FuncCallParser;
-use crate::syntax::util::is_ast_empty;
 use crate::compiler::ast::KaramelAstType;
 use crate::compiler::value::KaramelPrimative;
 use crate::syntax::expression::ExpressionParser;
@@ -25,7 +24,7 @@ impl SyntaxParserTrait for UnaryParser {
         if parser.match_operator(&[KaramelOperatorType::SquareBracketStart]).is_some() {
             parser.cleanup_whitespaces();
 
-            let indexer_ast = ExpressionParser::parse(parser)?;
+            let indexer_ast = with_flag(SyntaxFlag::IN_EXPRESSION, parser, || ExpressionParser::parse(parser))?;
             parser.cleanup_whitespaces();
 
             if parser.match_operator(&[KaramelOperatorType::SquareBracketEnd]).is_some() {
@@ -66,12 +65,19 @@ impl UnaryParser {
         if parser.match_operator(&[KaramelOperatorType::SquareBracketStart]).is_some() {
             parser.cleanup_whitespaces();
 
+            /* Value of the indexer is always used, 'a[f()] = 1' needs the result of the call too */
+            let parser_flags = parser.flags.get();
+            parser.flags.set(parser_flags | SyntaxFlag::IN_EXPRESSION);
             let indexer_ast = ExpressionParser::parse(parser);
+            parser.flags.set(parser_flags);
             parser.cleanup_whitespaces();
 
-            if parser.match_operator(&[KaramelOperatorType::SquareBracketEnd]).is_some() && !is_ast_empty(&indexer_ast) {
-                return Ok(KaramelAstType::Indexer { body: ast, indexer: Rc::new(indexer_ast.unwrap()) });   
-            }
+            match indexer_ast {
+                Ok(KaramelAstType::None) | Err(_) => (),
+                Ok(indexer_ast) => if parser.match_operator(&[KaramelOperatorType::SquareBracketEnd]).is_some() {
+                    return Ok(KaramelAstType::Indexer { body: ast, indexer: Rc::new(indexer_ast) });
+                }
+            };
         }
 
         parser.set_index(index_backup);
@@ -89,7 +95,13 @@ impl UnaryParser {
             parser.cleanup_whitespaces();
 
             let mut unary_ast = KaramelAstType::None;
-            let token         = &parser.peek_token().unwrap();
+            let token = match parser.peek_token() {
+                Ok(token) => token,
+                Err(_) => {
+                    parser.set_index(index_backup);
+                    return Err(KaramelErrorType::InvalidUnaryOperation);
+                }
+            };
 
             match operator {
                 /* +1024 -1024 */
@@ -120,6 +132,7 @@ impl UnaryParser {
                 },
 
                 KaramelOperatorType::Not => {
+                    let _guard = parser.enter()?;
                     let expression = UnaryParser::parse(parser);
                     unary_ast = match expression {
                         Ok(KaramelAstType::None) => {
@@ -127,6 +140,7 @@ impl UnaryParser {
                             return Err(KaramelErrorType::InvalidUnaryOperation);
                         },
                         Ok(ast) => ast,
+                        Err(KaramelErrorType::NestingTooDeep(limit)) => return Err(KaramelErrorType::NestingTooDeep(limit)),
                         Err(_) => {
                             parser.set_index(index_backup);
                             return Err(KaramelErrorType::InvalidUnaryOperation);
@@ -0,0 +1,113 @@
+use crate::types::*;
+use crate::syntax::{SyntaxParser, SyntaxParserTrait};
+use crate::syntax::primative::PrimativeParser;
+use crate::compiler::ast::KaramelAstType;
+use crate::error::KaramelErrorType;
+
+pub struct UseModuleParser;
+
+impl UseModuleParser {
+    fn parse_symbol(parser: &SyntaxParser) -> Result<String, KaramelErrorType> {
+        match PrimativeParser::parse_symbol(parser)? {
+            KaramelAstType::Symbol(name) => Ok(name),
+            _ => Err(KaramelErrorType::InvalidUseStatement)
+        }
+    }
+
+    /* Names between '{' and '}' */
+    fn parse_names(parser: &SyntaxParser) -> Result<Vec<String>, KaramelErrorType> {
+        let mut names = Vec::new();
+        loop {
+            let name = Self::parse_symbol(parser)?;
+            if !names.contains(&name) {
+                names.push(name);
+            }
+
+            parser.cleanup_whitespaces();
+            if parser.match_operator(&[KaramelOperatorType::CurveBracketEnd]).is_some() {
+                return Ok(names);
+            }
+
+            if parser.match_operator(&[KaramelOperatorType::Comma]).is_none() {
+                return Err(KaramelErrorType::InvalidUseStatement);
+            }
+        }
+    }
+}
+
+impl SyntaxParserTrait for UseModuleParser {
+    fn parse(parser: &SyntaxParser) -> AstResult {
+        let index_backup = parser.get_index();
+        parser.indentation_check()?;
+
+        if !parser.match_keyword(KaramelKeywordType::Use) {
+            parser.set_index(index_backup);
+            return Ok(KaramelAstType::None);
+        }
+
+        /* modül1::modül2 olarak ad, modül1::modül2::{ad1, ad2} */
+        let mut path = Vec::new();
+        let mut names = Vec::new();
+        loop {
+            path.push(Self::parse_symbol(parser)?);
+            if parser.match_operator(&[KaramelOperatorType::ColonMark]).is_none() {
+                break;
+            }
+
+            if parser.match_operator(&[KaramelOperatorType::ColonMark]).is_none() {
+                return Err(KaramelErrorType::InvalidUseStatement);
+            }
+
+            if parser.match_operator(&[KaramelOperatorType::CurveBracketStart]).is_some() {
+                names = Self::parse_names(parser)?;
+                break;
+            }
+        }
+
+        parser.cleanup_whitespaces();
+        let alias = match names.is_empty() && parser.match_keyword(KaramelKeywordType::As) {
+            true => Some(Self::parse_symbol(parser)?),
+            false => None
+        };
+
+        Ok(KaramelAstType::Use { path, alias, names })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::KaramelErrorType;
+    use crate::parser::*;
+    use crate::syntax::*;
+    use crate::compiler::ast::KaramelAstType;
+    use std::rc::Rc;
+
+    fn parse(code: &str) -> Result<Rc<KaramelAstType>, KaramelErrorType> {
+        let mut parser = Parser::new(code);
+        parser.parse().unwrap();
+        SyntaxParser::new(parser.tokens().to_vec()).parse().map_err(|error| error.error_type)
+    }
+
+    fn parse_use(code: &str) -> (Vec<String>, Option<String>, Vec<String>) {
+        match &*parse(code).unwrap() {
+            KaramelAstType::Use { path, alias, names } => (path.clone(), alias.clone(), names.clone()),
+            ast => panic!("{:?}", ast)
+        }
+    }
+
+    #[test]
+    fn use_module() {
+        assert_eq!(parse_use("kullan hesap"), (vec!["hesap".to_string()], None, Vec::new()));
+        assert_eq!(parse_use("kullan araçlar::hesap olarak h"), (vec!["araçlar".to_string(), "hesap".to_string()], Some("h".to_string()), Vec::new()));
+        assert_eq!(parse_use("kullan hesap::{kare, küp, kare}"), (vec!["hesap".to_string()], None, vec!["kare".to_string(), "küp".to_string()]));
+    }
+
+    #[test]
+    fn invalid_use() {
+        assert_eq!(parse("kullan"), Err(KaramelErrorType::InvalidUseStatement));
+        assert_eq!(parse("kullan hesap olarak"), Err(KaramelErrorType::InvalidUseStatement));
+        assert_eq!(parse("kullan hesap::"), Err(KaramelErrorType::InvalidUseStatement));
+        assert_eq!(parse("kullan hesap::{}"), Err(KaramelErrorType::InvalidUseStatement));
+        assert_eq!(parse("kullan hesap::{kare küp}"), Err(KaramelErrorType::InvalidUseStatement));
+    }
+}
@@ -1,9 +1,14 @@
+use std::rc::Rc;
+
 use crate::types::*;
 use crate::syntax::{SyntaxParser};
 use crate::syntax::ParseType;
 use crate::compiler::ast::KaramelAstType;
 use crate::error::KaramelErrorType;
 use crate::syntax::SyntaxFlag;
+use crate::syntax::SyntaxParserTrait;
+use crate::syntax::block::{SingleLineBlockParser, MultiLineBlockParser};
+use crate::syntax::assignment::AssignmentParser;
 
 // https://github.com/rust-lang/rust/issues/75429
 
@@ -61,6 +66,9 @@ pub fn update_functions_for_temp_return(ast: &KaramelAstType) {
             update_functions_for_temp_return(indexer);
             assign_to_temp.set(true);
         },
+
+        /* 'nesne.a().b()', the method is read from the result of the previous call */
+        KaramelAstType::Indexer { body, indexer: _ } => update_functions_for_temp_return(body),
         KaramelAstType::Block(blocks) => {
             for block in blocks {
                 update_functions_for_temp_return(&block);
@@ -84,4 +92,39 @@ pub fn mut_with_flag<F: FnMut() -> AstResult>(flag: SyntaxFlag, parser: &SyntaxP
     let loop_control = func()?;
     parser.flags.set(parser_flags);
     Ok(loop_control)
-}
\ No newline at end of file
+}
+
+/* Parses the colon and the block after it. Block could be on the same line or at the next lines. */
+pub fn parse_block_body(parser: &SyntaxParser) -> AstResult {
+    parser.cleanup_whitespaces();
+    if parser.match_operator(&[KaramelOperatorType::ColonMark]).is_none() {
+        return Err(KaramelErrorType::ColonMarkMissing);
+    }
+
+    parser.cleanup_whitespaces();
+    match parser.get_newline() {
+        (true, _) => {
+            parser.in_indication()?;
+            MultiLineBlockParser::parse(parser)
+        },
+        (false, _) => SingleLineBlockParser::parse(parser)
+    }
+}
+
+/* 'isim = değer' after the 'sabit' and 'yerel' keywords. Compound operators and indexers need a previous value, so they are not valid. */
+pub fn parse_declaration(parser: &SyntaxParser, keyword: KaramelKeywordType, error: KaramelErrorType) -> Result<Option<Rc<KaramelAstType>>, KaramelErrorType> {
+    let index_backup = parser.get_index();
+    parser.indentation_check()?;
+
+    if !parser.match_keyword(keyword) {
+        parser.set_index(index_backup);
+        return Ok(None);
+    }
+
+    parser.cleanup_whitespaces();
+    match AssignmentParser::parse(parser)? {
+        KaramelAstType::Assignment { variable, operator: KaramelOperatorType::Assign, expression } if matches!(&*variable, KaramelAstType::Symbol(_)) =>
+            Ok(Some(Rc::new(KaramelAstType::Assignment { variable, operator: KaramelOperatorType::Assign, expression }))),
+        _ => Err(error)
+    }
+}
@@ -0,0 +1,104 @@
+use std::cell::Cell;
+use std::rc::Rc;
+
+use crate::types::*;
+use crate::syntax::{SyntaxParser, SyntaxParserTrait, SyntaxFlag};
+use crate::syntax::util::{with_flag, parse_block_body};
+use crate::syntax::primative::PrimativeParser;
+use crate::syntax::expression::ExpressionParser;
+use crate::compiler::ast::KaramelAstType;
+use crate::error::KaramelErrorType;
+
+pub struct WithParser;
+
+impl SyntaxParserTrait for WithParser {
+    fn parse(parser: &SyntaxParser) -> AstResult {
+        let index_backup = parser.get_index();
+        parser.indentation_check()?;
+
+        let indentation = parser.get_indentation();
+        if !parser.match_keyword(KaramelKeywordType::With) {
+            parser.set_index(index_backup);
+            return Ok(KaramelAstType::None);
+        }
+
+        /* birlikte değer olarak ad: */
+        parser.cleanup_whitespaces();
+        let expression = match with_flag(SyntaxFlag::IN_EXPRESSION, parser, || ExpressionParser::parse(parser))? {
+            KaramelAstType::None => return Err(KaramelErrorType::InvalidWithStatement),
+            ast => ast
+        };
+
+        parser.cleanup_whitespaces();
+        if !parser.match_keyword(KaramelKeywordType::As) {
+            return Err(KaramelErrorType::InvalidWithStatement);
+        }
+
+        parser.cleanup_whitespaces();
+        let variable = match PrimativeParser::parse_symbol(parser)? {
+            KaramelAstType::Symbol(name) => name,
+            _ => return Err(KaramelErrorType::InvalidWithStatement)
+        };
+
+        /* 'kapat' is called after the body, 'kır' and 'devam' would jump over it */
+        let parser_flags = parser.flags.get();
+        parser.flags.set(parser_flags - SyntaxFlag::LOOP);
+        let body = parse_block_body(parser);
+        parser.flags.set(parser_flags);
+        parser.set_indentation(indentation);
+
+        let close = KaramelAstType::AccessorFuncCall {
+            source: Rc::new(KaramelAstType::Symbol(variable.to_string())),
+            indexer: Rc::new(KaramelAstType::FuncCall {
+                func_name_expression: Rc::new(KaramelAstType::Symbol("kapat".to_string())),
+                arguments: Vec::new(),
+                assign_to_temp: Cell::new(false)
+            }),
+            assign_to_temp: Cell::new(true)
+        };
+
+        Ok(KaramelAstType::With {
+            assignment: Rc::new(KaramelAstType::Assignment {
+                variable: Rc::new(KaramelAstType::Symbol(variable)),
+                operator: KaramelOperatorType::Assign,
+                expression: Rc::new(expression)
+            }),
+            close: Rc::new(close),
+            body: Rc::new(body?)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::KaramelErrorType;
+    use crate::parser::*;
+    use crate::syntax::*;
+    use crate::compiler::ast::KaramelAstType;
+    use std::rc::Rc;
+
+    fn parse(code: &str) -> Result<Rc<KaramelAstType>, KaramelErrorType> {
+        let mut parser = Parser::new(code);
+        parser.parse().unwrap();
+        SyntaxParser::new(parser.tokens().to_vec()).parse().map_err(|error| error.error_type)
+    }
+
+    #[test]
+    fn with_block() {
+        match &*parse("birlikte kaynak() olarak k:\n    a = 1\n    b = 2").unwrap() {
+            KaramelAstType::With { assignment, body, .. } => {
+                assert!(matches!(&**assignment, KaramelAstType::Assignment { variable, .. } if **variable == KaramelAstType::Symbol("k".to_string())));
+                assert!(matches!(&**body, KaramelAstType::Block(_)));
+            },
+            ast => assert!(false, "{:?}", ast)
+        };
+    }
+
+    #[test]
+    fn invalid_with() {
+        assert_eq!(parse("birlikte:\n    a = 1"), Err(KaramelErrorType::InvalidWithStatement));
+        assert_eq!(parse("birlikte kaynak():\n    a = 1"), Err(KaramelErrorType::InvalidWithStatement));
+        assert_eq!(parse("birlikte kaynak() olarak:\n    a = 1"), Err(KaramelErrorType::InvalidWithStatement));
+        assert_eq!(parse("sonsuz:\n    birlikte kaynak() olarak k:\n        kır"), Err(KaramelErrorType::BreakAndContinueBelongToLoops));
+    }
+}
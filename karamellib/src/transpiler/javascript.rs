@@ -0,0 +1,535 @@
+use std::rc::Rc;
+
+use crate::compiler::ast::{KaramelAstType, KaramelDictItem, KaramelIfStatementElseItem};
+use crate::compiler::value::KaramelPrimative;
+use crate::error::KaramelErrorType;
+use crate::error::diagnostic::encode_text;
+use crate::syntax::loops::LoopType;
+use crate::types::KaramelOperatorType;
+
+use super::{FunctionScope, Transpiler};
+
+/* Built-in functions, operators and class functions of the language are written in javascript, the runtime is placed before the program */
+const RUNTIME: &str = include_str!("runtime.js");
+
+/* Functions of the modules that the runtime has */
+const MODULES: &[(&str, &[&str])] = &[
+    ("gç", &["yaz", "satıryaz", "satiryaz", "satıroku", "satiroku", "biçimlendir", "bicimlendir", "hassasiyet"]),
+    ("baz", &["hata", "türü", "turu", "sayımı", "sayimi", "yazımı", "yazimi", "listemi", "sözlükmü", "sozlukmu", "sayıya", "sayiya",
+              "yazıya", "yaziya", "mantığa", "mantiga", "listeye", "derin_eşit", "derin_esit", "karşılaştır", "karsilastir", "kopyala"])
+];
+
+/* Javascript keywords and the global names of the runtime. Program names that are same with them get a '_' suffix. */
+const RESERVED_NAMES: &[&str] = &["arguments", "await", "break", "case", "catch", "class", "const", "continue", "debugger", "default", "delete",
+    "do", "else", "enum", "eval", "export", "extends", "false", "finally", "for", "function", "if", "implements", "import", "in", "Infinity",
+    "instanceof", "interface", "let", "NaN", "new", "null", "package", "private", "protected", "public", "return", "static", "super", "switch",
+    "this", "throw", "true", "try", "typeof", "undefined", "var", "void", "while", "with", "yield", "gç", "baz", "Hata", "Sözlük"];
+
+/* Precedence of the written expressions, operands with lower precedence are written in parentheses */
+const LOGICAL_OR: u8 = 1;
+const LOGICAL_AND: u8 = 2;
+const RELATIONAL: u8 = 3;
+const ADDITIVE: u8 = 4;
+const MULTIPLICATIVE: u8 = 5;
+const UNARY: u8 = 6;
+const ATOM: u8 = 7;
+
+type Expression = Result<(String, u8), KaramelErrorType>;
+
+#[derive(Default)]
+pub struct JavaScriptTranspiler {
+    buffer: String,
+    indentation: usize
+}
+
+impl Transpiler for JavaScriptTranspiler {
+    fn transpile(&mut self, ast: &KaramelAstType) -> Result<String, KaramelErrorType> {
+        self.buffer.clear();
+        self.indentation = 0;
+        self.buffer.push_str(RUNTIME);
+        self.buffer.push('\n');
+
+        self.line("$çalıştır(() => {");
+        self.function_body(ast, &[])?;
+        self.line("});");
+        Ok(std::mem::take(&mut self.buffer))
+    }
+}
+
+fn identifier(name: &str) -> String {
+    match RESERVED_NAMES.contains(&name) {
+        true => format!("{}_", name),
+        false => name.to_string()
+    }
+}
+
+fn number(number: f64) -> String {
+    match number {
+        number if number.is_nan() => "NaN".to_string(),
+        number if number.is_infinite() && number > 0.0 => "Infinity".to_string(),
+        number if number.is_infinite() => "-Infinity".to_string(),
+        number if number.fract() == 0.0 && number.abs() < 1e15 => (number as i64).to_string(),
+        number => format!("{:?}", number)
+    }
+}
+
+fn text(value: &str) -> String {
+    let mut buffer = String::new();
+    encode_text(value, &mut buffer);
+    buffer
+}
+
+fn not_supported(name: &str) -> KaramelErrorType {
+    KaramelErrorType::TranspileNotSupported(name.to_string())
+}
+
+impl JavaScriptTranspiler {
+    fn line(&mut self, line: &str) {
+        for _ in 0..self.indentation {
+            self.buffer.push_str("    ");
+        }
+        self.buffer.push_str(line);
+        self.buffer.push('\n');
+    }
+
+    fn block(&mut self, body: &KaramelAstType) -> Result<(), KaramelErrorType> {
+        self.indentation += 1;
+        let result = self.statement(body);
+        self.indentation -= 1;
+        result
+    }
+
+    /* Variables are function scoped, all of them are declared at the start of the function */
+    fn function_body(&mut self, body: &KaramelAstType, arguments: &[String]) -> Result<(), KaramelErrorType> {
+        let scope = FunctionScope::collect(body, arguments);
+        let names = scope.variables.iter().chain(scope.deferred_variables.iter()).map(|variable| identifier(variable)).chain(scope.temporaries.iter().cloned()).collect::<Vec<_>>();
+
+        self.indentation += 1;
+        if !names.is_empty() {
+            self.line(&format!("let {};", names.join(", ")));
+        }
+
+        /* Parser adds an empty return to the end of the functions, the end of the function already returns */
+        let body = match body {
+            KaramelAstType::Block(items) if matches!(items.last().map(|item| &**item), Some(KaramelAstType::Return(expression)) if matches!(&**expression, KaramelAstType::None)) => KaramelAstType::Block(items[..items.len() - 1].to_vec()),
+            body => body.clone()
+        };
+
+        let result = match scope.has_defer {
+            true => {
+                self.line("const $ertelenenler = [];");
+                self.line("try {");
+                self.block(&body)?;
+                self.line("} finally {");
+                self.line("    $ertelenenler.reverse().forEach(iş => iş());");
+                self.line("}");
+                Ok(())
+            },
+            false => self.statement(&body)
+        };
+        self.indentation -= 1;
+        result
+    }
+
+    fn statement(&mut self, ast: &KaramelAstType) -> Result<(), KaramelErrorType> {
+        match ast {
+            KaramelAstType::None | KaramelAstType::NewLine => (),
+            KaramelAstType::Block(items) => {
+                for item in items.iter() {
+                    self.statement(item)?;
+                }
+            },
+            KaramelAstType::Assignment { variable, operator, expression } => {
+                let assignment = self.assignment(variable, *operator, expression)?;
+                self.line(&format!("{};", assignment));
+            },
+            KaramelAstType::IfStatement { condition, body, else_body, else_if } => self.if_statement(condition, body, else_body.as_deref(), else_if)?,
+            KaramelAstType::FunctionDefination { decorators, .. } if !decorators.is_empty() => return Err(not_supported("@")),
+            KaramelAstType::FunctionDefination { name, arguments, body, documentation, .. } => self.function(name, arguments, body, documentation.as_deref())?,
+            KaramelAstType::Return(expression) => match &**expression {
+                KaramelAstType::None => self.line("return;"),
+                expression => {
+                    let (expression, _) = self.expression(expression)?;
+                    self.line(&format!("return {};", expression));
+                }
+            },
+            KaramelAstType::Break => self.line("break;"),
+            KaramelAstType::Continue => self.line("continue;"),
+            KaramelAstType::Loop { loop_type, body } => self.loop_statement(loop_type, body)?,
+            KaramelAstType::Try { body, variable, catch_body } => {
+                self.line("try {");
+                self.block(body)?;
+                self.line("} catch ($hata) {");
+                if let Some(variable) = variable {
+                    self.line(&format!("    {} = $yakala($hata);", identifier(variable)));
+                }
+                self.block(catch_body)?;
+                self.line("}");
+            },
+            KaramelAstType::Throw(expression) => {
+                let (expression, _) = self.expression(expression)?;
+                self.line(&format!("throw $hata({});", expression));
+            },
+            KaramelAstType::Defer(body) => {
+                self.line("$ertelenenler.push(() => {");
+                self.block(body)?;
+                self.line("});");
+            },
+            KaramelAstType::With { assignment, close, body } => {
+                self.statement(assignment)?;
+                self.line("try {");
+                self.block(body)?;
+                self.line("} finally {");
+                self.block(close)?;
+                self.line("}");
+            },
+            KaramelAstType::Assume { condition, message } => {
+                let (condition, _) = self.expression(condition)?;
+                match message {
+                    Some(message) => {
+                        let (message, _) = self.expression(message)?;
+                        self.line(&format!("$varsay({}, {});", condition, message));
+                    },
+                    None => self.line(&format!("$varsay({});", condition))
+                };
+            },
+            KaramelAstType::Constant(assignment) => self.statement(assignment)?,
+            KaramelAstType::Local(_) => return Err(not_supported("yerel")),
+            KaramelAstType::Enum { .. } => return Err(not_supported("seçenekler")),
+            KaramelAstType::Load(_) => return Err(not_supported("yükle")),
+            KaramelAstType::Use { .. } => return Err(not_supported("kullan")),
+            expression => {
+                let (expression, _) = self.expression(expression)?;
+                self.line(&format!("{};", expression));
+            }
+        };
+        Ok(())
+    }
+
+    fn function(&mut self, name: &str, arguments: &[String], body: &KaramelAstType, documentation: Option<&String>) -> Result<(), KaramelErrorType> {
+        if let Some(documentation) = documentation {
+            for line in documentation.lines() {
+                self.line(format!("// {}", line).trim_end());
+            }
+        }
+
+        let parameters = arguments.iter().map(|argument| identifier(argument)).collect::<Vec<_>>();
+        self.line(&format!("function {}({}) {{", identifier(name), parameters.join(", ")));
+        self.function_body(body, arguments)?;
+        self.line("}");
+        Ok(())
+    }
+
+    fn if_statement(&mut self, condition: &KaramelAstType, body: &KaramelAstType, else_body: Option<&KaramelAstType>, else_if: &[Rc<KaramelIfStatementElseItem>]) -> Result<(), KaramelErrorType> {
+        let (condition, _) = self.condition(condition)?;
+        self.line(&format!("if ({}) {{", condition));
+        self.block(body)?;
+
+        for item in else_if.iter() {
+            let (condition, _) = self.condition(&item.condition)?;
+            self.line(&format!("}} else if ({}) {{", condition));
+            self.block(&item.body)?;
+        }
+
+        if let Some(else_body) = else_body {
+            self.line("} else {");
+            self.block(else_body)?;
+        }
+        self.line("}");
+        Ok(())
+    }
+
+    fn loop_statement(&mut self, loop_type: &LoopType, body: &KaramelAstType) -> Result<(), KaramelErrorType> {
+        let header = match loop_type {
+            LoopType::Scalar { variable, control, increment } => {
+                let variable = match &**variable {
+                    KaramelAstType::Assignment { variable, operator, expression } => self.assignment(variable, *operator, expression)?,
+                    variable => self.expression(variable)?.0
+                };
+                let increment = match &**increment {
+                    KaramelAstType::Assignment { variable, operator, expression } => self.assignment(variable, *operator, expression)?,
+                    increment => self.expression(increment)?.0
+                };
+                format!("for ({}; {}; {}) {{", variable, self.condition(control)?.0, increment)
+            },
+            LoopType::Simple(control) => format!("while ({}) {{", self.condition(control)?.0),
+            LoopType::Endless => "while (true) {".to_string()
+        };
+
+        self.line(&header);
+        self.block(body)?;
+        self.line("}");
+        Ok(())
+    }
+
+    fn assignment(&self, variable: &KaramelAstType, operator: KaramelOperatorType, expression: &KaramelAstType) -> Result<String, KaramelErrorType> {
+        let target = match variable {
+            KaramelAstType::Symbol(name) => identifier(name),
+            KaramelAstType::Indexer { .. } => self.expression(variable)?.0,
+            _ => return Err(not_supported("="))
+        };
+
+        /* Addition and multiplication work on texts too, they are done by the runtime */
+        Ok(match operator {
+            KaramelOperatorType::AssignAddition => format!("{} = $topla({}, {})", target, target, self.expression(expression)?.0),
+            KaramelOperatorType::AssignMultiplication => format!("{} = $çarp({}, {})", target, target, self.expression(expression)?.0),
+            KaramelOperatorType::AssignSubtraction => format!("{} -= {}", target, self.expression(expression)?.0),
+            KaramelOperatorType::AssignDivision => format!("{} /= {}", target, self.expression(expression)?.0),
+            _ => format!("{} = {}", target, self.expression(expression)?.0)
+        })
+    }
+
+    fn operand(&self, ast: &KaramelAstType, precedence: u8) -> Result<String, KaramelErrorType> {
+        let (expression, expression_precedence) = self.expression(ast)?;
+        Ok(match expression_precedence < precedence {
+            true => format!("({})", expression),
+            false => expression
+        })
+    }
+
+    /* Conditions of the language accept every value, values that are not bool are converted with the truthiness rules */
+    fn condition(&self, ast: &KaramelAstType) -> Expression {
+        match ast {
+            KaramelAstType::Control { .. } | KaramelAstType::PrefixUnary { operator: KaramelOperatorType::Not, .. } => self.expression(ast),
+            KaramelAstType::Primative(primative) if matches!(&**primative, KaramelPrimative::Bool(_)) => self.expression(ast),
+            _ => Ok((format!("$doğru({})", self.expression(ast)?.0), ATOM))
+        }
+    }
+
+    fn condition_operand(&self, ast: &KaramelAstType, precedence: u8) -> Result<String, KaramelErrorType> {
+        let (condition, condition_precedence) = self.condition(ast)?;
+        Ok(match condition_precedence < precedence {
+            true => format!("({})", condition),
+            false => condition
+        })
+    }
+
+    fn arguments(&self, arguments: &[Rc<KaramelAstType>]) -> Result<String, KaramelErrorType> {
+        let arguments = arguments.iter().map(|argument| self.expression(argument).map(|(argument, _)| argument)).collect::<Result<Vec<_>, _>>()?;
+        Ok(arguments.join(", "))
+    }
+
+    fn module_path(&self, path: &[String]) -> Result<String, KaramelErrorType> {
+        match path {
+            [module, function] if MODULES.iter().any(|(name, functions)| name == module && functions.contains(&function.as_str())) => Ok(format!("{}.{}", module, function)),
+            _ => Err(not_supported(&path.join("::")))
+        }
+    }
+
+    fn method_call(&self, source: &str, indexer: &KaramelAstType) -> Result<String, KaramelErrorType> {
+        match indexer {
+            KaramelAstType::FuncCall { func_name_expression, arguments, .. } => match &**func_name_expression {
+                KaramelAstType::Symbol(name) => Ok(format!("{}.{}({})", source, name, self.arguments(arguments)?)),
+                inner @ KaramelAstType::FuncCall { .. } => Ok(format!("{}({})", self.method_call(source, inner)?, self.arguments(arguments)?)),
+                _ => Err(not_supported("."))
+            },
+            _ => Err(not_supported("."))
+        }
+    }
+
+    fn primative(&self, primative: &KaramelPrimative) -> Expression {
+        Ok(match primative {
+            KaramelPrimative::Empty => ("null".to_string(), ATOM),
+            KaramelPrimative::Number(value) => (number(*value), UNARY),
+            KaramelPrimative::Bool(value) => (value.to_string(), ATOM),
+            KaramelPrimative::Text(value) => (text(value), ATOM),
+            KaramelPrimative::List(items) => {
+                let items = items.borrow().iter().map(|item| self.primative(&item.deref()).map(|(item, _)| item)).collect::<Result<Vec<_>, _>>()?;
+                (format!("[{}]", items.join(", ")), ATOM)
+            },
+            KaramelPrimative::Dict(items) => {
+                let mut keys = items.borrow().keys().cloned().collect::<Vec<_>>();
+                keys.sort();
+                let items = keys.iter().map(|key| match key.as_text() {
+                    Some(name) => self.primative(&items.borrow()[key].deref()).map(|(item, _)| format!("{}: {}", text(name), item)),
+                    None => Err(not_supported(&format!("{:?}", key)))
+                }).collect::<Result<Vec<_>, _>>()?;
+                (format!("new Sözlük({{ {} }})", items.join(", ")), ATOM)
+            },
+            primative => return Err(not_supported(&primative.to_string()))
+        })
+    }
+
+    fn dict(&self, items: &[Rc<KaramelDictItem>]) -> Expression {
+        let items = items.iter().map(|item| {
+            let key = match &*item.key {
+                KaramelPrimative::Text(key) => text(key),
+                key => text(&key.to_string())
+            };
+            self.expression(&item.value).map(|(value, _)| format!("{}: {}", key, value))
+        }).collect::<Result<Vec<_>, _>>()?;
+
+        Ok(match items.is_empty() {
+            true => ("new Sözlük({})".to_string(), ATOM),
+            false => (format!("new Sözlük({{ {} }})", items.join(", ")), ATOM)
+        })
+    }
+
+    fn expression(&self, ast: &KaramelAstType) -> Expression {
+        Ok(match ast {
+            KaramelAstType::Primative(primative) => return self.primative(primative),
+            KaramelAstType::Symbol(name) => (identifier(name), ATOM),
+            KaramelAstType::ModulePath(path) => (self.module_path(path)?, ATOM),
+            KaramelAstType::List(items) => (format!("[{}]", self.arguments(items)?), ATOM),
+            KaramelAstType::Dict(items) => return self.dict(items),
+            KaramelAstType::Indexer { body, indexer } => (format!("{}[{}]", self.operand(body, ATOM)?, self.expression(indexer)?.0), ATOM),
+            KaramelAstType::Temporary { name, expression: Some(expression) } => (format!("({} = {})", name, self.expression(expression)?.0), ATOM),
+            KaramelAstType::Temporary { name, expression: None } => (name.to_string(), ATOM),
+
+            KaramelAstType::FuncCall { func_name_expression, arguments, .. } => {
+                let function = match &**func_name_expression {
+                    KaramelAstType::Symbol(name) => identifier(name),
+                    KaramelAstType::ModulePath(path) => self.module_path(path)?,
+                    KaramelAstType::Indexer { body, indexer } => match &**indexer {
+                        /* 'sayı.hex()' is parsed as an indexer, it is a method call on the value */
+                        KaramelAstType::Primative(name) if matches!(&**name, KaramelPrimative::Text(_)) => format!("{}.{}", self.operand(body, ATOM)?, name.get_text()),
+                        _ => self.operand(func_name_expression, ATOM)?
+                    },
+                    function => self.operand(function, ATOM)?
+                };
+                (format!("{}({})", function, self.arguments(arguments)?), ATOM)
+            },
+            KaramelAstType::AccessorFuncCall { source, indexer, .. } => (self.method_call(&self.operand(source, ATOM)?, indexer)?, ATOM),
+
+            KaramelAstType::Binary { left, operator, right } => match operator {
+                KaramelOperatorType::Addition => (format!("$topla({}, {})", self.expression(left)?.0, self.expression(right)?.0), ATOM),
+                KaramelOperatorType::Multiplication => (format!("$çarp({}, {})", self.expression(left)?.0, self.expression(right)?.0), ATOM),
+                KaramelOperatorType::Subtraction => (format!("{} - {}", self.operand(left, ADDITIVE)?, self.operand(right, ADDITIVE + 1)?), ADDITIVE),
+                KaramelOperatorType::Division => (format!("{} / {}", self.operand(left, MULTIPLICATIVE)?, self.operand(right, MULTIPLICATIVE + 1)?), MULTIPLICATIVE),
+                KaramelOperatorType::Modulo => (format!("{} % {}", self.operand(left, MULTIPLICATIVE)?, self.operand(right, MULTIPLICATIVE + 1)?), MULTIPLICATIVE),
+                _ => return Err(not_supported(&format!("{:?}", operator)))
+            },
+            KaramelAstType::Control { left, operator, right } => {
+                let relational = match operator {
+                    KaramelOperatorType::GreaterThan => ">",
+                    KaramelOperatorType::GreaterEqualThan => ">=",
+                    KaramelOperatorType::LessThan => "<",
+                    KaramelOperatorType::LessEqualThan => "<=",
+                    KaramelOperatorType::Equal => return Ok((format!("$eşit({}, {})", self.expression(left)?.0, self.expression(right)?.0), ATOM)),
+                    KaramelOperatorType::NotEqual => return Ok((format!("!$eşit({}, {})", self.expression(left)?.0, self.expression(right)?.0), UNARY)),
+                    KaramelOperatorType::And => return Ok((format!("{} && {}", self.condition_operand(left, LOGICAL_AND)?, self.condition_operand(right, LOGICAL_AND + 1)?), LOGICAL_AND)),
+                    KaramelOperatorType::Or => return Ok((format!("{} || {}", self.condition_operand(left, LOGICAL_OR)?, self.condition_operand(right, LOGICAL_OR + 1)?), LOGICAL_OR)),
+                    _ => return Err(not_supported(&format!("{:?}", operator)))
+                };
+                (format!("{} {} {}", self.operand(left, RELATIONAL)?, relational, self.operand(right, RELATIONAL + 1)?), RELATIONAL)
+            },
+            KaramelAstType::PrefixUnary { operator, expression, .. } => match operator {
+                KaramelOperatorType::Not => (format!("!{}", self.condition_operand(expression, UNARY)?), UNARY),
+                KaramelOperatorType::Increment => (format!("++{}", self.operand(expression, UNARY)?), UNARY),
+                KaramelOperatorType::Deccrement => (format!("--{}", self.operand(expression, UNARY)?), UNARY),
+                KaramelOperatorType::Subtraction => (format!("-{}", self.operand(expression, UNARY)?), UNARY),
+                _ => return Err(not_supported(&format!("{:?}", operator)))
+            },
+            KaramelAstType::SuffixUnary(operator, expression) => match operator {
+                KaramelOperatorType::Increment => (format!("{}++", self.operand(expression, ATOM)?), UNARY),
+                KaramelOperatorType::Deccrement => (format!("{}--", self.operand(expression, ATOM)?), UNARY),
+                _ => return Err(not_supported(&format!("{:?}", operator)))
+            },
+            KaramelAstType::Assignment { variable, operator, expression } => (format!("({})", self.assignment(variable, *operator, expression)?), ATOM),
+            KaramelAstType::Load(_) => return Err(not_supported("yükle")),
+            KaramelAstType::Use { .. } => return Err(not_supported("kullan")),
+            _ => return Err(not_supported(&format!("{:?}", ast)))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::transpiler::{TranspileTarget, transpile_code};
+
+    fn program(code: &str) -> String {
+        let output = transpile_code(code, TranspileTarget::JavaScript).unwrap();
+        let start = output.rfind("$çalıştır(").unwrap();
+        output[start..].to_string()
+    }
+
+    #[test]
+    fn function_and_loop() {
+        let code = "fonk topla(a, b):\n    döndür a + b\ntoplam = 0\ndöngü i = 0, i < 3, ++i:\n    toplam += topla(i, 1)\ngç::satıryaz(toplam)";
+        assert_eq!(program(code), "$çalıştır(() => {
+    let toplam, i;
+    function topla(a, b) {
+        return $topla(a, b);
+    }
+    toplam = 0;
+    for (i = 0; 3 > i; ++i) {
+        toplam = $topla(toplam, topla(i, 1));
+    }
+    gç.satıryaz(toplam);
+});
+");
+    }
+
+    #[test]
+    fn collections() {
+        let code = "meyveler = ['elma']\nmeyveler.ekle('kiraz')\nfiyatlar = {'elma': 10}\nfiyatlar['kiraz'] = 30\nfiyatlar['elma'] > 5 ve değil meyveler ise:\n    gç::yaz(meyveler[0])";
+        assert_eq!(program(code), "$çalıştır(() => {
+    let meyveler, fiyatlar;
+    meyveler = [\"elma\"];
+    meyveler.ekle(\"kiraz\");
+    fiyatlar = new Sözlük({ \"elma\": 10 });
+    fiyatlar[\"kiraz\"] = 30;
+    if (fiyatlar[\"elma\"] > 5 && !$doğru(meyveler)) {
+        gç.yaz(meyveler[0]);
+    }
+});
+");
+    }
+
+    #[test]
+    fn precedence() {
+        assert_eq!(program("a = (1 - 2) - (3 - 4) * 2 / (5 mod 3)"), "$çalıştır(() => {
+    let a;
+    a = 1 - 2 - $çarp(3 - 4, 2) / (5 % 3);
+});
+");
+        assert!(program("a = 5.hex()").contains("a = (5).hex();"));
+        assert!(program("new = 1 < a < 3").contains("new_ = a > 1 && 3 > a;"));
+    }
+
+    #[test]
+    fn try_and_defer() {
+        let code = "fonk işle():\n    ertele:\n        gç::satıryaz('bitti')\n    fırlat 'hata'\ndene:\n    işle()\nyakala hata:\n    gç::satıryaz(hata.mesaj())";
+        assert_eq!(program(code), "$çalıştır(() => {
+    let hata;
+    function işle() {
+        const $ertelenenler = [];
+        try {
+            $ertelenenler.push(() => {
+                gç.satıryaz(\"bitti\");
+            });
+            throw $hata(\"hata\");
+        } finally {
+            $ertelenenler.reverse().forEach(iş => iş());
+        }
+    }
+    try {
+        işle();
+    } catch ($hata) {
+        hata = $yakala($hata);
+        gç.satıryaz(hata.mesaj());
+    }
+});
+");
+    }
+
+    #[test]
+    fn with_block() {
+        assert_eq!(program("birlikte kaynak() olarak k:\n    gç::satıryaz(k.ad)"), "$çalıştır(() => {
+    let k;
+    k = kaynak();
+    try {
+        gç.satıryaz(k[\"ad\"]);
+    } finally {
+        k.kapat();
+    }
+});
+");
+    }
+
+    #[test]
+    fn not_supported() {
+        assert!(transpile_code("dosya::oku('a.txt')", TranspileTarget::JavaScript).is_err());
+        assert!(transpile_code("ortak yükle", TranspileTarget::JavaScript).is_err());
+    }
+}
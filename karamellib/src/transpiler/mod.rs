@@ -0,0 +1,124 @@
+pub mod javascript;
+pub mod python;
+
+use std::borrow::Borrow;
+
+use crate::compiler::ast::KaramelAstType;
+use crate::compiler::visitor::{AstVisitor, VisitorResult};
+use crate::error::{KaramelError, KaramelErrorType};
+use crate::parser::Parser;
+use crate::syntax::SyntaxParser;
+use crate::types::KaramelOperatorType;
+
+/// Backend that writes the parse tree as the source code of another language instead of opcodes.
+pub trait Transpiler {
+    fn transpile(&mut self, ast: &KaramelAstType) -> Result<String, KaramelErrorType>;
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TranspileTarget {
+    JavaScript,
+    Python
+}
+
+impl TranspileTarget {
+    pub fn from_name(name: &str) -> Option<TranspileTarget> {
+        match name {
+            "javascript" | "js" => Some(TranspileTarget::JavaScript),
+            "python" | "py" => Some(TranspileTarget::Python),
+            _ => None
+        }
+    }
+
+    pub fn transpiler(&self) -> Box<dyn Transpiler> {
+        match self {
+            TranspileTarget::JavaScript => Box::new(javascript::JavaScriptTranspiler::default()),
+            TranspileTarget::Python => Box::new(python::PythonTranspiler::default())
+        }
+    }
+}
+
+/// Names that are created in a function body. Nested functions have their own scope, so they are not visited.
+#[derive(Default)]
+pub struct FunctionScope {
+    pub variables: Vec<String>,
+    pub temporaries: Vec<String>,
+    pub has_defer: bool,
+
+    /* Variables that are only assigned in deferred blocks */
+    pub deferred_variables: Vec<String>,
+    in_defer: bool
+}
+
+impl FunctionScope {
+    pub fn collect(body: &KaramelAstType, arguments: &[String]) -> FunctionScope {
+        let mut scope = FunctionScope::default();
+        let _ = scope.visit(body);
+        scope.variables.retain(|variable| !arguments.contains(variable));
+        let variables = scope.variables.clone();
+        scope.deferred_variables.retain(|variable| !arguments.contains(variable) && !variables.contains(variable));
+        scope
+    }
+
+    fn add_variable(&mut self, name: &str) {
+        let variables = match self.in_defer {
+            true => &mut self.deferred_variables,
+            false => &mut self.variables
+        };
+
+        if !variables.iter().any(|variable| variable == name) {
+            variables.push(name.to_string());
+        }
+    }
+}
+
+impl AstVisitor for FunctionScope {
+    type Error = ();
+
+    fn visit_assignment(&mut self, variable: &KaramelAstType, _: KaramelOperatorType, expression: &KaramelAstType) -> VisitorResult<()> {
+        if let KaramelAstType::Symbol(name) = variable {
+            self.add_variable(name);
+        }
+        self.visit(variable)?;
+        self.visit(expression)
+    }
+
+    fn visit_function_defination(&mut self, _: &str, _: &[String], _: &KaramelAstType) -> VisitorResult<()> {
+        Ok(())
+    }
+
+    fn visit_try(&mut self, body: &KaramelAstType, variable: Option<&str>, catch_body: &KaramelAstType) -> VisitorResult<()> {
+        if let Some(variable) = variable {
+            self.add_variable(variable);
+        }
+        self.visit(body)?;
+        self.visit(catch_body)
+    }
+
+    fn visit_defer(&mut self, body: &KaramelAstType) -> VisitorResult<()> {
+        self.has_defer = true;
+        let in_defer = std::mem::replace(&mut self.in_defer, true);
+        let result = self.visit(body);
+        self.in_defer = in_defer;
+        result
+    }
+
+    fn visit_temporary(&mut self, name: &str, expression: Option<&KaramelAstType>) -> VisitorResult<()> {
+        if !self.temporaries.iter().any(|temporary| temporary == name) {
+            self.temporaries.push(name.to_string());
+        }
+        match expression {
+            Some(expression) => self.visit(expression),
+            None => Ok(())
+        }
+    }
+}
+
+/// Parses the script and writes it in the target language. Syntax errors and the parts that the target can not express are returned as error.
+pub fn transpile_code<T: Borrow<str>>(source: T, target: TranspileTarget) -> Result<String, KaramelError> {
+    let mut parser = Parser::new(source.borrow());
+    parser.parse()?;
+
+    let ast = SyntaxParser::new(parser.tokens()).parse()?;
+    Ok(target.transpiler().transpile(&ast)?)
+}
@@ -0,0 +1,600 @@
+use std::rc::Rc;
+
+use crate::compiler::ast::{KaramelAstType, KaramelDictItem, KaramelIfStatementElseItem};
+use crate::compiler::value::KaramelPrimative;
+use crate::error::KaramelErrorType;
+use crate::error::diagnostic::encode_text;
+use crate::syntax::loops::LoopType;
+use crate::types::KaramelOperatorType;
+
+use super::{FunctionScope, Transpiler};
+
+/* Built-in functions and class functions of the language are written in python, the runtime is placed before the program */
+const RUNTIME: &str = include_str!("runtime.py");
+
+/* Functions of the modules that the runtime has */
+const MODULES: &[(&str, &[&str])] = &[
+    ("gç", &["yaz", "satıryaz", "satiryaz", "satıroku", "satiroku", "biçimlendir", "bicimlendir", "hassasiyet"]),
+    ("baz", &["hata", "türü", "turu", "sayımı", "sayimi", "yazımı", "yazimi", "listemi", "sözlükmü", "sozlukmu", "sayıya", "sayiya",
+              "yazıya", "yaziya", "mantığa", "mantiga", "listeye", "derin_eşit", "derin_esit", "karşılaştır", "karsilastir", "kopyala"])
+];
+
+/* Python keywords and the global names that the written code uses. Program names that are same with them get a '_' suffix. */
+const RESERVED_NAMES: &[&str] = &["and", "as", "assert", "async", "await", "break", "class", "continue", "def", "del", "elif", "else", "except",
+    "False", "finally", "float", "for", "from", "global", "if", "import", "in", "is", "lambda", "None", "nonlocal", "not", "or", "pass", "raise",
+    "return", "reversed", "True", "try", "while", "with", "yield", "gç", "baz", "Hata"];
+
+/* Precedence of the written expressions, operands with lower precedence are written in parentheses */
+const LOGICAL_OR: u8 = 1;
+const LOGICAL_AND: u8 = 2;
+const LOGICAL_NOT: u8 = 3;
+const RELATIONAL: u8 = 4;
+const ADDITIVE: u8 = 5;
+const MULTIPLICATIVE: u8 = 6;
+const UNARY: u8 = 7;
+const ATOM: u8 = 8;
+
+type Expression = Result<(String, u8), KaramelErrorType>;
+
+#[derive(Default)]
+pub struct PythonTranspiler {
+    buffer: String,
+    indentation: usize,
+
+    /* Names of the function that is written, deferred blocks change them with 'nonlocal' */
+    scopes: Vec<Vec<String>>,
+
+    /* 'for' loops are written as 'while', increment is written before 'continue' too */
+    loops: Vec<Option<String>>
+}
+
+impl Transpiler for PythonTranspiler {
+    fn transpile(&mut self, ast: &KaramelAstType) -> Result<String, KaramelErrorType> {
+        self.buffer.clear();
+        self.indentation = 0;
+        self.scopes.clear();
+        self.loops.clear();
+        self.buffer.push_str(RUNTIME);
+        self.buffer.push_str("\n\n");
+
+        self.line("def _program():");
+        self.function_body(ast, &[])?;
+        self.buffer.push_str("\n\n");
+        self.line("_çalıştır(_program)");
+        Ok(std::mem::take(&mut self.buffer))
+    }
+}
+
+fn identifier(name: &str) -> String {
+    match RESERVED_NAMES.contains(&name) {
+        true => format!("{}_", name),
+        false => name.to_string()
+    }
+}
+
+/* Temporary names of the parser start with '$', it can not be used in python names */
+fn temporary(name: &str) -> String {
+    name.replace('$', "_")
+}
+
+fn number(number: f64) -> String {
+    match number {
+        number if number.is_nan() => "float(\"nan\")".to_string(),
+        number if number.is_infinite() && number > 0.0 => "float(\"inf\")".to_string(),
+        number if number.is_infinite() => "float(\"-inf\")".to_string(),
+        number if number.fract() == 0.0 && number.abs() < 1e15 => (number as i64).to_string(),
+        number => format!("{:?}", number)
+    }
+}
+
+fn text(value: &str) -> String {
+    let mut buffer = String::new();
+    encode_text(value, &mut buffer);
+    buffer
+}
+
+fn not_supported(name: &str) -> KaramelErrorType {
+    KaramelErrorType::TranspileNotSupported(name.to_string())
+}
+
+impl PythonTranspiler {
+    fn line(&mut self, line: &str) {
+        for _ in 0..self.indentation {
+            self.buffer.push_str("    ");
+        }
+        self.buffer.push_str(line);
+        self.buffer.push('\n');
+    }
+
+    /* Python does not accept empty blocks */
+    fn block(&mut self, body: &KaramelAstType) -> Result<(), KaramelErrorType> {
+        let length = self.buffer.len();
+        self.indentation += 1;
+        let result = self.statement(body);
+        if result.is_ok() && self.buffer.len() == length {
+            self.line("pass");
+        }
+        self.indentation -= 1;
+        result
+    }
+
+    fn function_body(&mut self, body: &KaramelAstType, arguments: &[String]) -> Result<(), KaramelErrorType> {
+        let scope = FunctionScope::collect(body, arguments);
+        let names = scope.variables.iter().chain(arguments.iter()).map(|variable| identifier(variable)).collect::<Vec<_>>();
+
+        /* Parser adds an empty return to the end of the functions, the end of the function already returns */
+        let body = match body {
+            KaramelAstType::Block(items) if matches!(items.last().map(|item| &**item), Some(KaramelAstType::Return(expression)) if matches!(&**expression, KaramelAstType::None)) => KaramelAstType::Block(items[..items.len() - 1].to_vec()),
+            body => body.clone()
+        };
+
+        self.scopes.push(names);
+        let loops = std::mem::take(&mut self.loops);
+        let result = match scope.has_defer {
+            true => self.deferred_body(&body),
+            false => self.block(&body)
+        };
+        self.loops = loops;
+        self.scopes.pop();
+        result
+    }
+
+    fn deferred_body(&mut self, body: &KaramelAstType) -> Result<(), KaramelErrorType> {
+        self.indentation += 1;
+        self.line("_ertelenenler = []");
+        self.line("try:");
+        self.block(body)?;
+        self.line("finally:");
+        self.line("    for _iş in reversed(_ertelenenler):");
+        self.line("        _iş()");
+        self.indentation -= 1;
+        Ok(())
+    }
+
+    fn statement(&mut self, ast: &KaramelAstType) -> Result<(), KaramelErrorType> {
+        match ast {
+            KaramelAstType::None | KaramelAstType::NewLine => (),
+            KaramelAstType::Block(items) => {
+                for item in items.iter() {
+                    self.statement(item)?;
+                }
+            },
+            KaramelAstType::IfStatement { condition, body, else_body, else_if } => self.if_statement(condition, body, else_body.as_deref(), else_if)?,
+            KaramelAstType::FunctionDefination { decorators, .. } if !decorators.is_empty() => return Err(not_supported("@")),
+            KaramelAstType::FunctionDefination { name, arguments, body, documentation, .. } => self.function(name, arguments, body, documentation.as_deref())?,
+            KaramelAstType::Return(expression) => match &**expression {
+                KaramelAstType::None => self.line("return"),
+                expression => {
+                    let (expression, _) = self.expression(expression)?;
+                    self.line(&format!("return {}", expression));
+                }
+            },
+            KaramelAstType::Break => self.line("break"),
+            KaramelAstType::Continue => {
+                if let Some(Some(increment)) = self.loops.last().cloned() {
+                    self.line(&increment);
+                }
+                self.line("continue");
+            },
+            KaramelAstType::Loop { loop_type, body } => self.loop_statement(loop_type, body)?,
+            KaramelAstType::Try { body, variable, catch_body } => {
+                self.line("try:");
+                self.block(body)?;
+                match variable {
+                    Some(variable) => {
+                        self.line("except Exception as _yakalanan:");
+                        self.line(&format!("    {} = _yakala(_yakalanan)", identifier(variable)));
+                        self.indentation += 1;
+                        self.statement(catch_body)?;
+                        self.indentation -= 1;
+                    },
+                    None => {
+                        self.line("except Exception:");
+                        self.block(catch_body)?;
+                    }
+                };
+            },
+            KaramelAstType::Throw(expression) => {
+                let (expression, _) = self.expression(expression)?;
+                self.line(&format!("raise _hata({})", expression));
+            },
+            KaramelAstType::Defer(body) => self.defer(body)?,
+            KaramelAstType::With { assignment, close, body } => {
+                self.statement(assignment)?;
+                self.line("try:");
+                self.block(body)?;
+                self.line("finally:");
+                self.block(close)?;
+            },
+            KaramelAstType::Assume { condition, message } => {
+                let (condition, _) = self.expression(condition)?;
+                match message {
+                    Some(message) => {
+                        let (message, _) = self.expression(message)?;
+                        self.line(&format!("_varsay({}, {})", condition, message));
+                    },
+                    None => self.line(&format!("_varsay({})", condition))
+                };
+            },
+            KaramelAstType::Constant(assignment) => self.statement(assignment)?,
+            KaramelAstType::Local(_) => return Err(not_supported("yerel")),
+            KaramelAstType::Enum { .. } => return Err(not_supported("seçenekler")),
+            KaramelAstType::Load(_) => return Err(not_supported("yükle")),
+            KaramelAstType::Use { .. } => return Err(not_supported("kullan")),
+            expression => {
+                let statement = self.simple_statement(expression)?;
+                self.line(&statement);
+            }
+        };
+        Ok(())
+    }
+
+    /* Assignments and increments are statements in python, they are written as expressions only when they are used as a value */
+    fn simple_statement(&self, ast: &KaramelAstType) -> Result<String, KaramelErrorType> {
+        match ast {
+            KaramelAstType::Assignment { variable, operator, expression } => self.assignment(variable, *operator, expression),
+            KaramelAstType::PrefixUnary { operator: KaramelOperatorType::Increment, expression, .. } | KaramelAstType::SuffixUnary(KaramelOperatorType::Increment, expression) => Ok(format!("{} += 1", self.target(expression)?)),
+            KaramelAstType::PrefixUnary { operator: KaramelOperatorType::Deccrement, expression, .. } | KaramelAstType::SuffixUnary(KaramelOperatorType::Deccrement, expression) => Ok(format!("{} -= 1", self.target(expression)?)),
+            expression => Ok(self.expression(expression)?.0)
+        }
+    }
+
+    fn function(&mut self, name: &str, arguments: &[String], body: &KaramelAstType, documentation: Option<&String>) -> Result<(), KaramelErrorType> {
+        if let Some(documentation) = documentation {
+            for line in documentation.lines() {
+                self.line(format!("# {}", line).trim_end());
+            }
+        }
+
+        let parameters = arguments.iter().map(|argument| identifier(argument)).collect::<Vec<_>>();
+        self.line(&format!("def {}({}):", identifier(name), parameters.join(", ")));
+        self.function_body(body, arguments)
+    }
+
+    /* Deferred block is a nested function, variables of the function that it changes are marked as 'nonlocal' */
+    fn defer(&mut self, body: &KaramelAstType) -> Result<(), KaramelErrorType> {
+        let names = self.scopes.last().cloned().unwrap_or_default();
+        let changed = FunctionScope::collect(body, &[]).variables.iter().map(|variable| identifier(variable)).filter(|variable| names.contains(variable)).collect::<Vec<_>>();
+
+        self.line("def _ertele():");
+        if !changed.is_empty() {
+            self.line(&format!("    nonlocal {}", changed.join(", ")));
+        }
+        self.block(body)?;
+        self.line("_ertelenenler.append(_ertele)");
+        Ok(())
+    }
+
+    fn if_statement(&mut self, condition: &KaramelAstType, body: &KaramelAstType, else_body: Option<&KaramelAstType>, else_if: &[Rc<KaramelIfStatementElseItem>]) -> Result<(), KaramelErrorType> {
+        let (condition, _) = self.condition(condition)?;
+        self.line(&format!("if {}:", condition));
+        self.block(body)?;
+
+        for item in else_if.iter() {
+            let (condition, _) = self.condition(&item.condition)?;
+            self.line(&format!("elif {}:", condition));
+            self.block(&item.body)?;
+        }
+
+        if let Some(else_body) = else_body {
+            self.line("else:");
+            self.block(else_body)?;
+        }
+        Ok(())
+    }
+
+    fn loop_statement(&mut self, loop_type: &LoopType, body: &KaramelAstType) -> Result<(), KaramelErrorType> {
+        let increment = match loop_type {
+            LoopType::Scalar { variable, control, increment } => {
+                let variable = self.simple_statement(variable)?;
+                self.line(&variable);
+                self.line(&format!("while {}:", self.condition(control)?.0));
+                Some(self.simple_statement(increment)?)
+            },
+            LoopType::Simple(control) => {
+                self.line(&format!("while {}:", self.condition(control)?.0));
+                None
+            },
+            LoopType::Endless => {
+                self.line("while True:");
+                None
+            }
+        };
+
+        self.loops.push(increment.clone());
+        let result = self.block(body);
+        self.loops.pop();
+        result?;
+
+        if let Some(increment) = increment {
+            self.line(&format!("    {}", increment));
+        }
+        Ok(())
+    }
+
+    fn target(&self, variable: &KaramelAstType) -> Result<String, KaramelErrorType> {
+        match variable {
+            KaramelAstType::Symbol(name) => Ok(identifier(name)),
+            KaramelAstType::Indexer { .. } => Ok(self.expression(variable)?.0),
+            _ => Err(not_supported("="))
+        }
+    }
+
+    fn assignment(&self, variable: &KaramelAstType, operator: KaramelOperatorType, expression: &KaramelAstType) -> Result<String, KaramelErrorType> {
+        let target = self.target(variable)?;
+
+        /* Addition and multiplication work on texts too, they are done by the runtime */
+        Ok(match operator {
+            KaramelOperatorType::AssignAddition => format!("{} = _topla({}, {})", target, target, self.expression(expression)?.0),
+            KaramelOperatorType::AssignMultiplication => format!("{} = _çarp({}, {})", target, target, self.expression(expression)?.0),
+            KaramelOperatorType::AssignSubtraction => format!("{} -= {}", target, self.expression(expression)?.0),
+            KaramelOperatorType::AssignDivision => format!("{} /= {}", target, self.expression(expression)?.0),
+            _ => format!("{} = {}", target, self.expression(expression)?.0)
+        })
+    }
+
+    /* Only names can be changed inside an expression, python writes them with ':=' */
+    fn assignment_expression(&self, variable: &KaramelAstType, value: String) -> Result<String, KaramelErrorType> {
+        match variable {
+            KaramelAstType::Symbol(name) => Ok(format!("({} := {})", identifier(name), value)),
+            _ => Err(not_supported("="))
+        }
+    }
+
+    fn operand(&self, ast: &KaramelAstType, precedence: u8) -> Result<String, KaramelErrorType> {
+        let (expression, expression_precedence) = self.expression(ast)?;
+        Ok(match expression_precedence < precedence {
+            true => format!("({})", expression),
+            false => expression
+        })
+    }
+
+    /* Conditions of the language accept every value, values that are not bool are converted with the truthiness rules */
+    fn condition(&self, ast: &KaramelAstType) -> Expression {
+        match ast {
+            KaramelAstType::Control { .. } | KaramelAstType::PrefixUnary { operator: KaramelOperatorType::Not, .. } => self.expression(ast),
+            KaramelAstType::Primative(primative) if matches!(&**primative, KaramelPrimative::Bool(_)) => self.expression(ast),
+            _ => Ok((format!("_doğru({})", self.expression(ast)?.0), ATOM))
+        }
+    }
+
+    fn condition_operand(&self, ast: &KaramelAstType, precedence: u8) -> Result<String, KaramelErrorType> {
+        let (condition, condition_precedence) = self.condition(ast)?;
+        Ok(match condition_precedence < precedence {
+            true => format!("({})", condition),
+            false => condition
+        })
+    }
+
+    fn arguments(&self, arguments: &[Rc<KaramelAstType>]) -> Result<Vec<String>, KaramelErrorType> {
+        arguments.iter().map(|argument| self.expression(argument).map(|(argument, _)| argument)).collect()
+    }
+
+    fn module_path(&self, path: &[String]) -> Result<String, KaramelErrorType> {
+        match path {
+            [module, function] if MODULES.iter().any(|(name, functions)| name == module && functions.contains(&function.as_str())) => Ok(format!("{}.{}", module, function)),
+            _ => Err(not_supported(&path.join("::")))
+        }
+    }
+
+    /* Functions can not be added to the built-in types of python, class functions are called with the runtime */
+    fn method_call(&self, source: String, name: &str, arguments: &[Rc<KaramelAstType>]) -> Result<String, KaramelErrorType> {
+        let mut parameters = vec![source, text(name)];
+        parameters.extend(self.arguments(arguments)?);
+        Ok(format!("_çağır({})", parameters.join(", ")))
+    }
+
+    fn accessor_call(&self, source: String, indexer: &KaramelAstType) -> Result<String, KaramelErrorType> {
+        match indexer {
+            KaramelAstType::FuncCall { func_name_expression, arguments, .. } => match &**func_name_expression {
+                KaramelAstType::Symbol(name) => self.method_call(source, name, arguments),
+                inner @ KaramelAstType::FuncCall { .. } => Ok(format!("{}({})", self.accessor_call(source, inner)?, self.arguments(arguments)?.join(", "))),
+                _ => Err(not_supported("."))
+            },
+            _ => Err(not_supported("."))
+        }
+    }
+
+    fn primative(&self, primative: &KaramelPrimative) -> Expression {
+        Ok(match primative {
+            KaramelPrimative::Empty => ("None".to_string(), ATOM),
+            KaramelPrimative::Number(value) if *value < 0.0 => (number(*value), UNARY),
+            KaramelPrimative::Number(value) => (number(*value), ATOM),
+            KaramelPrimative::Bool(true) => ("True".to_string(), ATOM),
+            KaramelPrimative::Bool(false) => ("False".to_string(), ATOM),
+            KaramelPrimative::Text(value) => (text(value), ATOM),
+            KaramelPrimative::List(items) => {
+                let items = items.borrow().iter().map(|item| self.primative(&item.deref()).map(|(item, _)| item)).collect::<Result<Vec<_>, _>>()?;
+                (format!("[{}]", items.join(", ")), ATOM)
+            },
+            KaramelPrimative::Dict(items) => {
+                let mut keys = items.borrow().keys().cloned().collect::<Vec<_>>();
+                keys.sort();
+                let items = keys.iter().map(|key| match key.as_text() {
+                    Some(name) => self.primative(&items.borrow()[key].deref()).map(|(item, _)| format!("{}: {}", text(name), item)),
+                    None => Err(not_supported(&format!("{:?}", key)))
+                }).collect::<Result<Vec<_>, _>>()?;
+                (format!("{{{}}}", items.join(", ")), ATOM)
+            },
+            primative => return Err(not_supported(&primative.to_string()))
+        })
+    }
+
+    fn dict(&self, items: &[Rc<KaramelDictItem>]) -> Expression {
+        let items = items.iter().map(|item| {
+            let key = match &*item.key {
+                KaramelPrimative::Text(key) => text(key),
+                key => text(&key.to_string())
+            };
+            self.expression(&item.value).map(|(value, _)| format!("{}: {}", key, value))
+        }).collect::<Result<Vec<_>, _>>()?;
+        Ok((format!("{{{}}}", items.join(", ")), ATOM))
+    }
+
+    fn expression(&self, ast: &KaramelAstType) -> Expression {
+        Ok(match ast {
+            KaramelAstType::Primative(primative) => return self.primative(primative),
+            KaramelAstType::Symbol(name) => (identifier(name), ATOM),
+            KaramelAstType::ModulePath(path) => (self.module_path(path)?, ATOM),
+            KaramelAstType::List(items) => (format!("[{}]", self.arguments(items)?.join(", ")), ATOM),
+            KaramelAstType::Dict(items) => return self.dict(items),
+            KaramelAstType::Indexer { body, indexer } => (format!("{}[{}]", self.operand(body, ATOM)?, self.expression(indexer)?.0), ATOM),
+            KaramelAstType::Temporary { name, expression: Some(expression) } => (format!("({} := {})", temporary(name), self.expression(expression)?.0), ATOM),
+            KaramelAstType::Temporary { name, expression: None } => (temporary(name), ATOM),
+
+            KaramelAstType::FuncCall { func_name_expression, arguments, .. } => match &**func_name_expression {
+                KaramelAstType::Symbol(name) => (format!("{}({})", identifier(name), self.arguments(arguments)?.join(", ")), ATOM),
+                KaramelAstType::ModulePath(path) => (format!("{}({})", self.module_path(path)?, self.arguments(arguments)?.join(", ")), ATOM),
+                KaramelAstType::Indexer { body, indexer } => match &**indexer {
+                    /* 'sayı.hex()' is parsed as an indexer, it is a method call on the value */
+                    KaramelAstType::Primative(name) if matches!(&**name, KaramelPrimative::Text(_)) => (self.method_call(self.expression(body)?.0, &name.get_text(), arguments)?, ATOM),
+                    _ => (format!("{}({})", self.operand(func_name_expression, ATOM)?, self.arguments(arguments)?.join(", ")), ATOM)
+                },
+                function => (format!("{}({})", self.operand(function, ATOM)?, self.arguments(arguments)?.join(", ")), ATOM)
+            },
+            KaramelAstType::AccessorFuncCall { source, indexer, .. } => (self.accessor_call(self.expression(source)?.0, indexer)?, ATOM),
+
+            KaramelAstType::Binary { left, operator, right } => match operator {
+                KaramelOperatorType::Addition => (format!("_topla({}, {})", self.expression(left)?.0, self.expression(right)?.0), ATOM),
+                KaramelOperatorType::Multiplication => (format!("_çarp({}, {})", self.expression(left)?.0, self.expression(right)?.0), ATOM),
+                KaramelOperatorType::Subtraction => (format!("{} - {}", self.operand(left, ADDITIVE)?, self.operand(right, ADDITIVE + 1)?), ADDITIVE),
+                KaramelOperatorType::Division => (format!("{} / {}", self.operand(left, MULTIPLICATIVE)?, self.operand(right, MULTIPLICATIVE + 1)?), MULTIPLICATIVE),
+                KaramelOperatorType::Modulo => (format!("{} % {}", self.operand(left, MULTIPLICATIVE)?, self.operand(right, MULTIPLICATIVE + 1)?), MULTIPLICATIVE),
+                _ => return Err(not_supported(&format!("{:?}", operator)))
+            },
+            KaramelAstType::Control { left, operator, right } => {
+                let relational = match operator {
+                    KaramelOperatorType::GreaterThan => ">",
+                    KaramelOperatorType::GreaterEqualThan => ">=",
+                    KaramelOperatorType::LessThan => "<",
+                    KaramelOperatorType::LessEqualThan => "<=",
+                    KaramelOperatorType::Equal => "==",
+                    KaramelOperatorType::NotEqual => "!=",
+                    KaramelOperatorType::And => return Ok((format!("{} and {}", self.condition_operand(left, LOGICAL_AND)?, self.condition_operand(right, LOGICAL_AND + 1)?), LOGICAL_AND)),
+                    KaramelOperatorType::Or => return Ok((format!("{} or {}", self.condition_operand(left, LOGICAL_OR)?, self.condition_operand(right, LOGICAL_OR + 1)?), LOGICAL_OR)),
+                    _ => return Err(not_supported(&format!("{:?}", operator)))
+                };
+
+                /* Python chains the comparisons, 'a > b > c' is not '(a > b) > c' */
+                (format!("{} {} {}", self.operand(left, RELATIONAL + 1)?, relational, self.operand(right, RELATIONAL + 1)?), RELATIONAL)
+            },
+            KaramelAstType::PrefixUnary { operator, expression, .. } => match operator {
+                KaramelOperatorType::Not => (format!("not {}", self.condition_operand(expression, LOGICAL_NOT)?), LOGICAL_NOT),
+                KaramelOperatorType::Increment => (self.assignment_expression(expression, format!("{} + 1", self.operand(expression, ADDITIVE)?))?, ATOM),
+                KaramelOperatorType::Deccrement => (self.assignment_expression(expression, format!("{} - 1", self.operand(expression, ADDITIVE)?))?, ATOM),
+                KaramelOperatorType::Subtraction => (format!("-{}", self.operand(expression, UNARY)?), UNARY),
+                _ => return Err(not_supported(&format!("{:?}", operator)))
+            },
+            KaramelAstType::SuffixUnary(operator, expression) => match operator {
+                KaramelOperatorType::Increment => (format!("{} - 1", self.assignment_expression(expression, format!("{} + 1", self.operand(expression, ADDITIVE)?))?), ADDITIVE),
+                KaramelOperatorType::Deccrement => (format!("{} + 1", self.assignment_expression(expression, format!("{} - 1", self.operand(expression, ADDITIVE)?))?), ADDITIVE),
+                _ => return Err(not_supported(&format!("{:?}", operator)))
+            },
+            KaramelAstType::Assignment { variable, operator, expression } => {
+                let value = match operator {
+                    KaramelOperatorType::AssignAddition => format!("_topla({}, {})", self.expression(variable)?.0, self.expression(expression)?.0),
+                    KaramelOperatorType::AssignMultiplication => format!("_çarp({}, {})", self.expression(variable)?.0, self.expression(expression)?.0),
+                    KaramelOperatorType::AssignSubtraction => format!("{} - {}", self.operand(variable, ADDITIVE)?, self.operand(expression, ADDITIVE + 1)?),
+                    KaramelOperatorType::AssignDivision => format!("{} / {}", self.operand(variable, MULTIPLICATIVE)?, self.operand(expression, MULTIPLICATIVE + 1)?),
+                    _ => self.expression(expression)?.0
+                };
+                (self.assignment_expression(variable, value)?, ATOM)
+            },
+            KaramelAstType::Load(_) => return Err(not_supported("yükle")),
+            KaramelAstType::Use { .. } => return Err(not_supported("kullan")),
+            _ => return Err(not_supported(&format!("{:?}", ast)))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::transpiler::{TranspileTarget, transpile_code};
+
+    fn program(code: &str) -> String {
+        let output = transpile_code(code, TranspileTarget::Python).unwrap();
+        let start = output.rfind("def _program():").unwrap();
+        output[start..].to_string()
+    }
+
+    #[test]
+    fn function_and_loop() {
+        let code = "fonk topla(a, b):\n    döndür a + b\ntoplam = 0\ndöngü i = 0, i < 3, ++i:\n    i == 1 ise:\n        devam\n    toplam += topla(i, 1)\ngç::satıryaz(toplam)";
+        assert_eq!(program(code), "def _program():
+    def topla(a, b):
+        return _topla(a, b)
+    toplam = 0
+    i = 0
+    while 3 > i:
+        if i == 1:
+            i += 1
+            continue
+        toplam = _topla(toplam, topla(i, 1))
+        i += 1
+    gç.satıryaz(toplam)
+
+
+_çalıştır(_program)
+");
+    }
+
+    #[test]
+    fn collections() {
+        let code = "meyveler = ['elma']\nmeyveler.ekle('kiraz')\nfiyatlar = {'elma': 10}\nfiyatlar['kiraz'] = 30\nfiyatlar['elma'] > 5 ve değil meyveler ise:\n    gç::yaz(meyveler[0])\nveya:\n    gç::yaz(5.hex())";
+        assert_eq!(program(code), "def _program():
+    meyveler = [\"elma\"]
+    _çağır(meyveler, \"ekle\", \"kiraz\")
+    fiyatlar = {\"elma\": 10}
+    fiyatlar[\"kiraz\"] = 30
+    if fiyatlar[\"elma\"] > 5 and not _doğru(meyveler):
+        gç.yaz(meyveler[0])
+    else:
+        gç.yaz(_çağır(5, \"hex\"))
+
+
+_çalıştır(_program)
+");
+    }
+
+    #[test]
+    fn precedence() {
+        assert!(program("a = (1 - 2) - (3 - 4) * 2 / (5 mod 3)").contains("a = 1 - 2 - _çarp(3 - 4, 2) / (5 % 3)\n"));
+        assert!(program("pass = 1 < a < 3").contains("pass_ = a > 1 and 3 > a\n"));
+        assert!(program("a = değil (b ve c)").contains("a = not (_doğru(b) and _doğru(c))\n"));
+        assert!(program("a = 1\nb = a++").contains("b = (a := a + 1) - 1\n"));
+    }
+
+    #[test]
+    fn try_and_defer() {
+        let code = "fonk işle():\n    sayaç = 0\n    ertele:\n        sayaç = 1\n        gç::satıryaz('bitti')\n    fırlat 'hata'\ndene:\n    işle()\nyakala hata:\n    gç::satıryaz(hata.mesaj())";
+        assert_eq!(program(code), "def _program():
+    def işle():
+        _ertelenenler = []
+        try:
+            sayaç = 0
+            def _ertele():
+                nonlocal sayaç
+                sayaç = 1
+                gç.satıryaz(\"bitti\")
+            _ertelenenler.append(_ertele)
+            raise _hata(\"hata\")
+        finally:
+            for _iş in reversed(_ertelenenler):
+                _iş()
+    try:
+        işle()
+    except Exception as _yakalanan:
+        hata = _yakala(_yakalanan)
+        gç.satıryaz(_çağır(hata, \"mesaj\"))
+
+
+_çalıştır(_program)
+");
+    }
+
+    #[test]
+    fn not_supported() {
+        assert!(transpile_code("a = [1]\nb = a[0]++", TranspileTarget::Python).is_err());
+        assert!(transpile_code("dosya yükle", TranspileTarget::Python).is_err());
+    }
+}
@@ -0,0 +1,506 @@
+"use strict";
+
+/* Karamel çalışma zamanı. Çevrilen programın kullandığı yerleşik fonksiyonlar ve sınıf fonksiyonları. */
+
+let $hassasiyet = 15;
+let $satır = "";
+const $pop = Array.prototype.pop;
+
+class Sözlük {
+    constructor(elemanlar) {
+        Object.assign(this, elemanlar);
+    }
+}
+
+class Hata extends Error {
+    constructor(kod, mesaj, veri) {
+        super(mesaj);
+        this.$kod = kod;
+        this.$mesaj = mesaj;
+        this.$veri = veri;
+    }
+}
+
+function $boş(değer) {
+    return değer === null || değer === undefined;
+}
+
+function $yuvarla(sayı) {
+    return $hassasiyet >= 17 || !Number.isFinite(sayı) ? sayı : Number(sayı.toPrecision($hassasiyet));
+}
+
+function $sonsuz(sayı) {
+    return Number.isNaN(sayı) ? "NaN" : sayı > 0 ? "inf" : "-inf";
+}
+
+function $sayıyazısı(sayı) {
+    sayı = $yuvarla(sayı);
+    return Number.isFinite(sayı) ? String(sayı).replace(".", ",") : $sonsuz(sayı);
+}
+
+function $biçimle(değer) {
+    if ($boş(değer)) return "boş";
+    switch (typeof değer) {
+        case "number": {
+            const sayı = $yuvarla(değer);
+            if (!Number.isFinite(sayı)) return $sonsuz(sayı);
+            if (Number.isInteger(sayı) && (sayı < 0 || Object.is(sayı, -0))) return sayı + ".0";
+            return String(sayı);
+        }
+        case "boolean": return değer ? "doğru" : "yanlış";
+        case "string": return '"' + değer + '"';
+        case "function": return "<Fonksiyon='" + değer.name + "'>";
+    }
+    if (Array.isArray(değer)) return "[" + değer.map($biçimle).join(", ") + "]";
+    if (değer instanceof Hata) return "<Hata kod=" + $biçimle(değer.$kod) + " mesaj='" + değer.$mesaj + "'>";
+    if (değer instanceof Sözlük) return "{" + Object.keys(değer).map(anahtar => '"' + anahtar + '": ' + $biçimle(değer[anahtar])).join(", ") + "}";
+    return String(değer);
+}
+
+function $tür(değer) {
+    if ($boş(değer)) return "boş";
+    switch (typeof değer) {
+        case "number": return "sayı";
+        case "boolean": return "bool";
+        case "string": return "yazı";
+        case "function": return "fonksiyon";
+    }
+    if (Array.isArray(değer)) return "liste";
+    if (değer instanceof Hata) return "hata";
+    if (değer instanceof Sözlük) return "sözlük";
+    return "boş";
+}
+
+function $doğru(değer) {
+    if ($boş(değer)) return false;
+    switch (typeof değer) {
+        case "number": return değer > 0;
+        case "boolean": return değer;
+        case "string": return değer.length > 0;
+        case "function": return true;
+    }
+    if (Array.isArray(değer)) return değer.length > 0;
+    if (değer instanceof Sözlük) return Object.keys(değer).length > 0;
+    return true;
+}
+
+function $topla(sol, sağ) {
+    if (typeof sol === "number" && typeof sağ === "number") return sol + sağ;
+    if (typeof sol === "string" && typeof sağ === "string") return sol + sağ;
+    if (typeof sol === "string" && typeof sağ === "number") return sol + $sayıyazısı(sağ);
+    if (typeof sol === "number" && typeof sağ === "string") return $sayıyazısı(sol) + sağ;
+    return null;
+}
+
+function $çarp(sol, sağ) {
+    if (typeof sol === "number" && typeof sağ === "number") return sol * sağ;
+    const [yazı, sayı] = typeof sol === "string" ? [sol, sağ] : [sağ, sol];
+    if (typeof yazı === "string" && Number.isInteger(sayı) && sayı >= 0) return yazı.repeat(sayı);
+    return null;
+}
+
+function $sahip(sözlük, anahtar) {
+    return Object.prototype.hasOwnProperty.call(sözlük, anahtar);
+}
+
+function $eşit(sol, sağ) {
+    if ($boş(sol) || $boş(sağ)) return $boş(sol) && $boş(sağ);
+    if (typeof sol === "number" && typeof sağ === "number") return sol === sağ || (Number.isNaN(sol) && Number.isNaN(sağ));
+    if (Array.isArray(sol) && Array.isArray(sağ)) return sol.length === sağ.length && sol.every((eleman, sıra) => $eşit(eleman, sağ[sıra]));
+    if (sol instanceof Hata && sağ instanceof Hata) return $eşit(sol.$kod, sağ.$kod) && sol.$mesaj === sağ.$mesaj && $eşit(sol.$veri, sağ.$veri);
+    if (sol instanceof Sözlük && sağ instanceof Sözlük) {
+        const anahtarlar = Object.keys(sol);
+        return anahtarlar.length === Object.keys(sağ).length && anahtarlar.every(anahtar => $sahip(sağ, anahtar) && $eşit(sol[anahtar], sağ[anahtar]));
+    }
+    return sol === sağ;
+}
+
+/* Kendisini içeren listeler ve sözlükler bir kere kopyalanır, kopya kendi kopyasını içerir */
+function $kopyala(değer, kopyalar = new Map()) {
+    if (kopyalar.has(değer)) return kopyalar.get(değer);
+    if (Array.isArray(değer)) {
+        const kopya = [];
+        kopyalar.set(değer, kopya);
+        değer.forEach(eleman => kopya.push($kopyala(eleman, kopyalar)));
+        return kopya;
+    }
+    if (değer instanceof Sözlük) {
+        const kopya = new Sözlük({});
+        kopyalar.set(değer, kopya);
+        Object.keys(değer).forEach(anahtar => { kopya[anahtar] = $kopyala(değer[anahtar], kopyalar); });
+        return kopya;
+    }
+    return değer;
+}
+
+/* Farklı türler önce tür sırasına göre sıralanır */
+const $türsırası = ["boş", "bool", "sayı", "yazı", "liste", "sözlük", "hata", "fonksiyon"];
+
+function $karşılaştır(sol, sağ) {
+    const fark = $türsırası.indexOf($tür(sol)) - $türsırası.indexOf($tür(sağ));
+    if (fark !== 0) return Math.sign(fark);
+
+    if (typeof sol === "number") {
+        if (Number.isNaN(sol) || Number.isNaN(sağ)) return Number.isNaN(sol) - Number.isNaN(sağ);
+        return Math.sign(sol - sağ);
+    }
+    if (typeof sol === "string" || typeof sol === "boolean") return sol < sağ ? -1 : sol > sağ ? 1 : 0;
+    if (Array.isArray(sol)) {
+        for (let sıra = 0; sıra < Math.min(sol.length, sağ.length); ++sıra) {
+            const sonuç = $karşılaştır(sol[sıra], sağ[sıra]);
+            if (sonuç !== 0) return sonuç;
+        }
+        return Math.sign(sol.length - sağ.length);
+    }
+    if (sol instanceof Sözlük) {
+        const [solanahtarlar, sağanahtarlar] = [Object.keys(sol).sort(), Object.keys(sağ).sort()];
+        for (let sıra = 0; sıra < Math.min(solanahtarlar.length, sağanahtarlar.length); ++sıra) {
+            const [solanahtar, sağanahtar] = [solanahtarlar[sıra], sağanahtarlar[sıra]];
+            const sonuç = solanahtar < sağanahtar ? -1 : solanahtar > sağanahtar ? 1 : $karşılaştır(sol[solanahtar], sağ[sağanahtar]);
+            if (sonuç !== 0) return sonuç;
+        }
+        return Math.sign(solanahtarlar.length - sağanahtarlar.length);
+    }
+    return 0;
+}
+
+/* Fırlatılan değerler her zaman hataya dönüştürülür. Yazı hatanın mesajı olur, diğer değerler verisi olarak saklanır. */
+function $hata(değer) {
+    if (değer instanceof Hata) return değer;
+    if (typeof değer === "string") return new Hata(null, değer, null);
+    return new Hata(null, $biçimle(değer), değer);
+}
+
+/* JavaScript'in kendi hataları da Karamel hatası olarak yakalanır */
+function $yakala(hata) {
+    return hata instanceof Hata ? hata : new Hata(null, hata instanceof Error ? hata.message : $biçimle(hata), null);
+}
+
+function $varsay(koşul, mesaj) {
+    if (!$doğru(koşul)) {
+        const açıklama = $boş(mesaj) ? "" : typeof mesaj === "string" ? mesaj : $biçimle(mesaj);
+        throw new Hata(166, "Varsayım sağlanmadı: " + açıklama, null);
+    }
+}
+
+function $dönüştürülemedi(değer, hedef) {
+    return new Hata(165, $biçimle(değer) + " değeri " + hedef + " türüne dönüştürülemedi", değer);
+}
+
+function $çıktı(metin) {
+    if (typeof process !== "undefined" && process.stdout) {
+        process.stdout.write(metin);
+        return;
+    }
+
+    /* Tarayıcılarda sadece tamamlanan satırlar yazılabilir */
+    const satırlar = ($satır + metin).split("\n");
+    $satır = satırlar.pop();
+    satırlar.forEach(satır => console.log(satır));
+}
+
+function $satıroku() {
+    if (typeof prompt === "function") return (prompt() || "").trim();
+
+    const fs = require("fs");
+    const baytlar = [];
+    const tampon = Buffer.alloc(1);
+    try {
+        while (fs.readSync(0, tampon, 0, 1, null) === 1 && tampon[0] !== 10) baytlar.push(tampon[0]);
+    } catch (hata) {
+        return null;
+    }
+    return Buffer.from(baytlar).toString("utf8").trim();
+}
+
+function $çalıştır(program) {
+    try {
+        program();
+    } catch (hata) {
+        const metin = hata instanceof Hata ? "Yakalanmayan hata: " + $biçimle(hata) : String(hata && hata.message || hata);
+        console.error("Program hata ile sonlandırıldı: " + metin);
+        if (typeof process !== "undefined") process.exitCode = 1;
+    }
+
+    if ($satır.length > 0) console.log($satır);
+}
+
+function $yöntemler(hedef, yöntemler) {
+    for (const [isim, yöntem] of Object.entries(yöntemler)) {
+        Object.defineProperty(hedef, isim, { value: yöntem, writable: true, configurable: true });
+    }
+}
+
+/* Türkçe karakterler olmadan yazılan isimler de kullanılabilir */
+function $eşadlar(hedef, eşadlar) {
+    for (const [isim, asıl] of Object.entries(eşadlar)) {
+        $yöntemler(hedef, { [isim]: hedef[asıl] });
+    }
+}
+
+const gç = {
+    yaz(...değerler) {
+        $çıktı(değerler.map($biçimle).join(""));
+    },
+    satıryaz(...değerler) {
+        $çıktı(değerler.map($biçimle).join("") + "\n");
+    },
+    satıroku() {
+        return $satıroku();
+    },
+    biçimlendir(...değerler) {
+        return değerler.length === 1 ? $biçimle(değerler[0]) : null;
+    },
+    hassasiyet(basamak) {
+        const önceki = $hassasiyet;
+        $hassasiyet = Math.min(Math.max(Math.trunc(basamak), 1), 17);
+        return önceki;
+    }
+};
+$eşadlar(gç, { satiroku: "satıroku", satiryaz: "satıryaz", bicimlendir: "biçimlendir" });
+
+const baz = {
+    hata(kod, mesaj, veri) {
+        return new Hata(kod, mesaj, $boş(veri) ? null : veri);
+    },
+    türü(değer) {
+        return $tür(değer);
+    },
+    sayımı(değer) {
+        return $tür(değer) === "sayı";
+    },
+    yazımı(değer) {
+        return $tür(değer) === "yazı";
+    },
+    listemi(değer) {
+        return $tür(değer) === "liste";
+    },
+    sözlükmü(değer) {
+        return $tür(değer) === "sözlük";
+    },
+    sayıya(değer) {
+        if (typeof değer === "number") return değer;
+        if (typeof değer === "boolean") return değer ? 1 : 0;
+        if (typeof değer === "string") {
+            const yazı = değer.trim();
+            if (/^[+-]?(\d+\.?\d*|\.\d+)([eE][+-]?\d+)?$/.test(yazı)) return Number(yazı);
+            if (/^[+-]?(inf|infinity)$/i.test(yazı)) return yazı.startsWith("-") ? -Infinity : Infinity;
+            if (/^[+-]?nan$/i.test(yazı)) return NaN;
+        }
+        return $dönüştürülemedi(değer, "sayı");
+    },
+    yazıya(değer) {
+        if (typeof değer === "string") return değer;
+        if (typeof değer === "function") return $dönüştürülemedi(değer, "yazı");
+        return $biçimle(değer);
+    },
+    mantığa(değer) {
+        if (typeof değer === "boolean") return değer;
+        if (değer === 1 || değer === 0) return değer === 1;
+        if (typeof değer === "string") {
+            const yazı = değer.trim();
+            if (yazı === "doğru" || yazı === "dogru") return true;
+            if (yazı === "yanlış" || yazı === "yanlis") return false;
+        }
+        return $dönüştürülemedi(değer, "bool");
+    },
+    listeye(değer) {
+        if (Array.isArray(değer)) return değer.slice();
+        if (typeof değer === "string") return [...değer];
+        if (değer instanceof Sözlük) return Object.keys(değer).sort();
+        return $dönüştürülemedi(değer, "liste");
+    },
+    derin_eşit(sol, sağ) {
+        return $eşit(sol, sağ);
+    },
+    karşılaştır(sol, sağ) {
+        return $karşılaştır(sol, sağ);
+    },
+    kopyala(değer) {
+        return $kopyala(değer);
+    }
+};
+$eşadlar(baz, { turu: "türü", sayimi: "sayımı", yazimi: "yazımı", sozlukmu: "sözlükmü", sayiya: "sayıya", yaziya: "yazıya", mantiga: "mantığa", derin_esit: "derin_eşit", karsilastir: "karşılaştır" });
+
+$yöntemler(Array.prototype, {
+    getir(sıra) {
+        const eleman = this[sıra];
+        return $boş(eleman) ? null : eleman;
+    },
+    güncelle(sıra, eleman) {
+        if (sıra < 0 || sıra >= this.length) return false;
+        this[Math.trunc(sıra)] = eleman;
+        return true;
+    },
+    uzunluk() {
+        return this.length;
+    },
+    ekle(eleman) {
+        this.push(eleman);
+        return this.length - 1;
+    },
+    temizle() {
+        this.length = 0;
+        return null;
+    },
+    arayaekle(sıra, eleman) {
+        if (sıra < 0 || sıra > this.length) return false;
+        this.splice(Math.trunc(sıra), 0, eleman);
+        return true;
+    },
+    pop() {
+        return this.length > 0 ? $pop.call(this) : null;
+    },
+    sil(sıra) {
+        if (sıra < 0 || sıra >= this.length) return false;
+        return this.splice(Math.trunc(sıra), 1)[0];
+    },
+    sırala() {
+        this.sort($karşılaştır);
+        return null;
+    },
+    kopyala() {
+        return $kopyala(this);
+    }
+});
+$eşadlar(Array.prototype, { guncelle: "güncelle", sirala: "sırala" });
+
+$yöntemler(String.prototype, {
+    uzunluk() {
+        return [...this].length;
+    },
+    harfleriküçült() {
+        return this.toLocaleLowerCase("tr-TR");
+    },
+    harfleribüyült() {
+        return this.toLocaleUpperCase("tr-TR");
+    },
+    içeriyormu(aranan) {
+        return this.includes(aranan);
+    },
+    satırlar() {
+        const satırlar = this.split("\n").map(satır => satır.endsWith("\r") ? satır.slice(0, -1) : satır);
+        if (satırlar.length > 0 && satırlar[satırlar.length - 1] === "") satırlar.pop();
+        return satırlar;
+    },
+    parçala(ayraç) {
+        return this.split(ayraç);
+    },
+    ara(aranan) {
+        const konum = this.indexOf(aranan);
+        return konum < 0 ? null : [...this.slice(0, konum)].length;
+    },
+    değiştir(eski, yeni) {
+        return this.split(eski).join(yeni);
+    },
+    kırp() {
+        return this.trim();
+    },
+    sonukırp() {
+        return this.trimEnd();
+    },
+    başıkırp() {
+        return this.trimStart();
+    },
+    parçagetir(başlangıç, bitiş) {
+        return [...this].slice(Math.max(Math.trunc(başlangıç), 0), Math.trunc(bitiş)).join("");
+    },
+    sayı() {
+        const sayı = baz.sayıya(String(this));
+        return typeof sayı === "number" ? sayı : null;
+    },
+    levenshtein(diğer) {
+        const [sol, sağ] = [[...this], [...diğer]];
+        let önceki = Array.from({ length: sağ.length + 1 }, (_, sıra) => sıra);
+        for (let i = 1; i <= sol.length; ++i) {
+            const şimdiki = [i];
+            for (let j = 1; j <= sağ.length; ++j) {
+                şimdiki[j] = Math.min(önceki[j] + 1, şimdiki[j - 1] + 1, önceki[j - 1] + (sol[i - 1] === sağ[j - 1] ? 0 : 1));
+            }
+            önceki = şimdiki;
+        }
+        return önceki[sağ.length];
+    }
+});
+$eşadlar(String.prototype, { harflerikucult: "harfleriküçült", harfleribuyult: "harfleribüyült", iceriyormu: "içeriyormu", satirlar: "satırlar", parcala: "parçala", degistir: "değiştir", kirp: "kırp", sonukirp: "sonukırp", basikirp: "başıkırp", parcagetir: "parçagetir", sayi: "sayı" });
+
+$yöntemler(Number.prototype, {
+    hex() {
+        if (this % 1 !== 0) {
+            const görünüm = new DataView(new ArrayBuffer(8));
+            görünüm.setFloat64(0, this);
+            return "0x" + görünüm.getBigUint64(0).toString(16);
+        }
+        return "0x" + BigInt.asUintN(64, BigInt(Math.trunc(this))).toString(16);
+    },
+    yazı() {
+        return String(Number(this));
+    },
+    yuvarla() {
+        return Math.sign(this) * Math.round(Math.abs(this));
+    },
+    tavan() {
+        return Math.ceil(this);
+    },
+    taban() {
+        return Math.floor(this);
+    },
+    tamsayı() {
+        return Math.trunc(this);
+    },
+    kesir() {
+        return this - Math.trunc(this);
+    },
+    üst(kuvvet) {
+        return Math.pow(this, kuvvet);
+    }
+});
+$eşadlar(Number.prototype, { yazi: "yazı" });
+
+$yöntemler(Sözlük.prototype, {
+    getir(anahtar) {
+        return $sahip(this, anahtar) ? this[anahtar] : null;
+    },
+    güncelle(anahtar, değer) {
+        this[anahtar] = değer;
+        return null;
+    },
+    ekle(anahtar, değer) {
+        this[anahtar] = değer;
+        return null;
+    },
+    içeriyormu(anahtar) {
+        return $sahip(this, anahtar);
+    },
+    uzunluk() {
+        return Object.keys(this).length;
+    },
+    temizle() {
+        Object.keys(this).forEach(anahtar => delete this[anahtar]);
+        return null;
+    },
+    sil(anahtar) {
+        if (!$sahip(this, anahtar)) return false;
+        delete this[anahtar];
+        return true;
+    },
+    anahtarlar() {
+        return Object.keys(this);
+    },
+    kopyala() {
+        return $kopyala(this);
+    }
+});
+$eşadlar(Sözlük.prototype, { guncelle: "güncelle", iceriyormu: "içeriyormu" });
+
+$yöntemler(Hata.prototype, {
+    kod() {
+        return $boş(this.$kod) ? null : this.$kod;
+    },
+    mesaj() {
+        return this.$mesaj;
+    },
+    veri() {
+        return $boş(this.$veri) ? null : this.$veri;
+    }
+});
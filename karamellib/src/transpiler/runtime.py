@@ -0,0 +1,493 @@
+# Karamel çalışma zamanı. Çevrilen programın kullandığı yerleşik fonksiyonlar ve sınıf fonksiyonları.
+
+import copy
+import functools
+import math
+import re
+import struct
+import sys
+
+_hassasiyet = 15
+
+
+class Hata(Exception):
+    def __init__(self, kod, mesaj, veri):
+        super().__init__(mesaj)
+        self.kod = kod
+        self.mesaj = mesaj
+        self.veri = veri
+
+
+def _sayımı(değer):
+    return isinstance(değer, (int, float)) and not isinstance(değer, bool)
+
+
+def _yuvarla(sayı):
+    if _hassasiyet >= 17 or not math.isfinite(sayı):
+        return sayı
+    return float(f"{sayı:.{_hassasiyet}g}")
+
+
+def _sonsuz(sayı):
+    return "NaN" if math.isnan(sayı) else "inf" if sayı > 0 else "-inf"
+
+
+def _ondalık(sayı):
+    if sayı.is_integer() and abs(sayı) < 1e21:
+        return str(int(sayı))
+    # Üs gösterimi Rust ile aynı yazılır: 1e-07 yerine 1e-7
+    return re.sub(r"e([+-]?)0*(\d)", lambda eşleşme: "e" + eşleşme.group(1).replace("+", "") + eşleşme.group(2), repr(sayı))
+
+
+def _sayıyazısı(sayı):
+    sayı = _yuvarla(float(sayı))
+    return _ondalık(sayı).replace(".", ",") if math.isfinite(sayı) else _sonsuz(sayı)
+
+
+def _biçimle(değer):
+    if değer is None:
+        return "boş"
+    if isinstance(değer, bool):
+        return "doğru" if değer else "yanlış"
+    if _sayımı(değer):
+        sayı = _yuvarla(float(değer))
+        if not math.isfinite(sayı):
+            return _sonsuz(sayı)
+        if sayı.is_integer() and (sayı < 0 or math.copysign(1, sayı) < 0):
+            return str(int(sayı)) + ".0"
+        return _ondalık(sayı)
+    if isinstance(değer, str):
+        return '"' + değer + '"'
+    if isinstance(değer, list):
+        return "[" + ", ".join(_biçimle(eleman) for eleman in değer) + "]"
+    if isinstance(değer, dict):
+        return "{" + ", ".join('"' + anahtar + '": ' + _biçimle(eleman) for anahtar, eleman in değer.items()) + "}"
+    if isinstance(değer, Hata):
+        return "<Hata kod=" + _biçimle(değer.kod) + " mesaj='" + değer.mesaj + "'>"
+    if callable(değer):
+        return "<Fonksiyon='" + değer.__name__ + "'>"
+    return str(değer)
+
+
+def _tür(değer):
+    if değer is None:
+        return "boş"
+    if isinstance(değer, bool):
+        return "bool"
+    if _sayımı(değer):
+        return "sayı"
+    if isinstance(değer, str):
+        return "yazı"
+    if isinstance(değer, list):
+        return "liste"
+    if isinstance(değer, dict):
+        return "sözlük"
+    if isinstance(değer, Hata):
+        return "hata"
+    if callable(değer):
+        return "fonksiyon"
+    return "boş"
+
+
+# Negatif sayılar Python'dan farklı olarak yanlış kabul edilir
+def _doğru(değer):
+    if değer is None:
+        return False
+    if isinstance(değer, bool):
+        return değer
+    if _sayımı(değer):
+        return değer > 0
+    if isinstance(değer, (str, list, dict)):
+        return len(değer) > 0
+    return True
+
+
+def _topla(sol, sağ):
+    if _sayımı(sol) and _sayımı(sağ):
+        return sol + sağ
+    if isinstance(sol, str) and isinstance(sağ, str):
+        return sol + sağ
+    if isinstance(sol, str) and _sayımı(sağ):
+        return sol + _sayıyazısı(sağ)
+    if _sayımı(sol) and isinstance(sağ, str):
+        return _sayıyazısı(sol) + sağ
+    return None
+
+
+def _çarp(sol, sağ):
+    if _sayımı(sol) and _sayımı(sağ):
+        return sol * sağ
+    yazı, sayı = (sol, sağ) if isinstance(sol, str) else (sağ, sol)
+    if isinstance(yazı, str) and _sayımı(sayı) and float(sayı).is_integer() and sayı >= 0:
+        return yazı * int(sayı)
+    return None
+
+
+def _eşit(sol, sağ):
+    if sol is None or sağ is None:
+        return sol is None and sağ is None
+    if _sayımı(sol) and _sayımı(sağ):
+        return sol == sağ or (math.isnan(sol) and math.isnan(sağ))
+    if isinstance(sol, list) and isinstance(sağ, list):
+        return len(sol) == len(sağ) and all(_eşit(eleman, sağ[sıra]) for sıra, eleman in enumerate(sol))
+    if isinstance(sol, Hata) and isinstance(sağ, Hata):
+        return _eşit(sol.kod, sağ.kod) and sol.mesaj == sağ.mesaj and _eşit(sol.veri, sağ.veri)
+    if isinstance(sol, dict) and isinstance(sağ, dict):
+        return len(sol) == len(sağ) and all(anahtar in sağ and _eşit(eleman, sağ[anahtar]) for anahtar, eleman in sol.items())
+    if type(sol) != type(sağ):
+        return False
+    return sol == sağ
+
+
+# Farklı türler önce tür sırasına göre sıralanır
+_türsırası = ["boş", "bool", "sayı", "yazı", "liste", "sözlük", "hata", "fonksiyon"]
+
+
+def _işaret(fark):
+    return (fark > 0) - (fark < 0)
+
+
+def _karşılaştır(sol, sağ):
+    fark = _türsırası.index(_tür(sol)) - _türsırası.index(_tür(sağ))
+    if fark != 0:
+        return _işaret(fark)
+
+    if _sayımı(sol):
+        if math.isnan(sol) or math.isnan(sağ):
+            return math.isnan(sol) - math.isnan(sağ)
+        return _işaret(sol - sağ)
+    if isinstance(sol, (str, bool)):
+        return -1 if sol < sağ else 1 if sol > sağ else 0
+    if isinstance(sol, list):
+        for soleleman, sağeleman in zip(sol, sağ):
+            sonuç = _karşılaştır(soleleman, sağeleman)
+            if sonuç != 0:
+                return sonuç
+        return _işaret(len(sol) - len(sağ))
+    if isinstance(sol, dict):
+        for solanahtar, sağanahtar in zip(sorted(sol), sorted(sağ)):
+            sonuç = -1 if solanahtar < sağanahtar else 1 if solanahtar > sağanahtar else _karşılaştır(sol[solanahtar], sağ[sağanahtar])
+            if sonuç != 0:
+                return sonuç
+        return _işaret(len(sol) - len(sağ))
+    return 0
+
+
+# Fırlatılan değerler her zaman hataya dönüştürülür. Yazı hatanın mesajı olur, diğer değerler verisi olarak saklanır.
+def _hata(değer):
+    if isinstance(değer, Hata):
+        return değer
+    if isinstance(değer, str):
+        return Hata(None, değer, None)
+    return Hata(None, _biçimle(değer), değer)
+
+
+# Python'un kendi hataları da Karamel hatası olarak yakalanır
+def _yakala(hata):
+    return hata if isinstance(hata, Hata) else Hata(None, str(hata), None)
+
+
+def _varsay(koşul, mesaj=None):
+    if not _doğru(koşul):
+        açıklama = "" if mesaj is None else mesaj if isinstance(mesaj, str) else _biçimle(mesaj)
+        raise Hata(166, "Varsayım sağlanmadı: " + açıklama, None)
+
+
+def _dönüştürülemedi(değer, hedef):
+    return Hata(165, _biçimle(değer) + " değeri " + hedef + " türüne dönüştürülemedi", değer)
+
+
+def _çalıştır(program):
+    try:
+        program()
+    except Exception as hata:
+        metin = "Yakalanmayan hata: " + _biçimle(hata) if isinstance(hata, Hata) else str(hata)
+        sys.stdout.flush()
+        print("Program hata ile sonlandırıldı: " + metin, file=sys.stderr)
+        sys.exit(1)
+
+
+class gç:
+    @staticmethod
+    def yaz(*değerler):
+        sys.stdout.write("".join(_biçimle(değer) for değer in değerler))
+
+    @staticmethod
+    def satıryaz(*değerler):
+        sys.stdout.write("".join(_biçimle(değer) for değer in değerler) + "\n")
+
+    @staticmethod
+    def satıroku():
+        satır = sys.stdin.readline()
+        return satır.strip() if satır else None
+
+    @staticmethod
+    def biçimlendir(*değerler):
+        return _biçimle(değerler[0]) if len(değerler) == 1 else None
+
+    @staticmethod
+    def hassasiyet(basamak):
+        global _hassasiyet
+        önceki = _hassasiyet
+        _hassasiyet = min(max(int(basamak), 1), 17)
+        return önceki
+
+    # Türkçe karakterler olmadan yazılan isimler de kullanılabilir
+    satiroku = satıroku
+    satiryaz = satıryaz
+    bicimlendir = biçimlendir
+
+
+class baz:
+    @staticmethod
+    def hata(kod, mesaj, veri=None):
+        return Hata(kod, mesaj, veri)
+
+    @staticmethod
+    def türü(değer):
+        return _tür(değer)
+
+    @staticmethod
+    def sayımı(değer):
+        return _tür(değer) == "sayı"
+
+    @staticmethod
+    def yazımı(değer):
+        return _tür(değer) == "yazı"
+
+    @staticmethod
+    def listemi(değer):
+        return _tür(değer) == "liste"
+
+    @staticmethod
+    def sözlükmü(değer):
+        return _tür(değer) == "sözlük"
+
+    @staticmethod
+    def sayıya(değer):
+        if isinstance(değer, bool):
+            return 1 if değer else 0
+        if _sayımı(değer):
+            return değer
+        if isinstance(değer, str):
+            yazı = değer.strip()
+            if re.fullmatch(r"[+-]?(\d+\.?\d*|\.\d+)([eE][+-]?\d+)?", yazı):
+                sayı = float(yazı)
+                return int(sayı) if sayı.is_integer() and abs(sayı) < 1e15 else sayı
+            if re.fullmatch(r"[+-]?(inf|infinity|nan)", yazı, re.IGNORECASE):
+                return float(yazı)
+        return _dönüştürülemedi(değer, "sayı")
+
+    @staticmethod
+    def yazıya(değer):
+        if isinstance(değer, str):
+            return değer
+        if _tür(değer) == "fonksiyon":
+            return _dönüştürülemedi(değer, "yazı")
+        return _biçimle(değer)
+
+    @staticmethod
+    def mantığa(değer):
+        if isinstance(değer, bool):
+            return değer
+        if _sayımı(değer) and değer in (0, 1):
+            return değer == 1
+        if isinstance(değer, str):
+            yazı = değer.strip()
+            if yazı in ("doğru", "dogru"):
+                return True
+            if yazı in ("yanlış", "yanlis"):
+                return False
+        return _dönüştürülemedi(değer, "bool")
+
+    @staticmethod
+    def listeye(değer):
+        if isinstance(değer, list):
+            return list(değer)
+        if isinstance(değer, str):
+            return list(değer)
+        if isinstance(değer, dict):
+            return sorted(değer)
+        return _dönüştürülemedi(değer, "liste")
+
+    @staticmethod
+    def derin_eşit(sol, sağ):
+        return _eşit(sol, sağ)
+
+    @staticmethod
+    def karşılaştır(sol, sağ):
+        return _karşılaştır(sol, sağ)
+
+    @staticmethod
+    def kopyala(değer):
+        return copy.deepcopy(değer)
+
+    turu = türü
+    sayimi = sayımı
+    yazimi = yazımı
+    sozlukmu = sözlükmü
+    sayiya = sayıya
+    yaziya = yazıya
+    mantiga = mantığa
+    derin_esit = derin_eşit
+    karsilastir = karşılaştır
+
+
+def _sıra(liste, sıra):
+    return int(sıra) if 0 <= sıra < len(liste) else None
+
+
+def _liste_güncelle(liste, sıra, eleman):
+    if _sıra(liste, sıra) is None:
+        return False
+    liste[int(sıra)] = eleman
+    return True
+
+
+def _liste_ekle(liste, eleman):
+    liste.append(eleman)
+    return len(liste) - 1
+
+
+def _liste_arayaekle(liste, sıra, eleman):
+    if sıra < 0 or sıra > len(liste):
+        return False
+    liste.insert(int(sıra), eleman)
+    return True
+
+
+def _liste_sil(liste, sıra):
+    return liste.pop(int(sıra)) if _sıra(liste, sıra) is not None else False
+
+
+def _liste_sırala(liste):
+    liste.sort(key=functools.cmp_to_key(_karşılaştır))
+
+
+def _yazı_satırlar(yazı):
+    satırlar = [satır[:-1] if satır.endswith("\r") else satır for satır in yazı.split("\n")]
+    if satırlar and satırlar[-1] == "":
+        satırlar.pop()
+    return satırlar
+
+
+def _yazı_ara(yazı, aranan):
+    konum = yazı.find(aranan)
+    return None if konum < 0 else konum
+
+
+def _yazı_sayı(yazı):
+    sayı = baz.sayıya(yazı)
+    return sayı if _sayımı(sayı) else None
+
+
+def _levenshtein(sol, sağ):
+    önceki = list(range(len(sağ) + 1))
+    for i in range(1, len(sol) + 1):
+        şimdiki = [i]
+        for j in range(1, len(sağ) + 1):
+            şimdiki.append(min(önceki[j] + 1, şimdiki[j - 1] + 1, önceki[j - 1] + (0 if sol[i - 1] == sağ[j - 1] else 1)))
+        önceki = şimdiki
+    return önceki[len(sağ)]
+
+
+def _sayı_hex(sayı):
+    if not float(sayı).is_integer():
+        return "0x" + format(struct.unpack(">Q", struct.pack(">d", sayı))[0], "x")
+    return "0x" + format(int(sayı) & 0xFFFFFFFFFFFFFFFF, "x")
+
+
+def _sözlük_sil(sözlük, anahtar):
+    if anahtar not in sözlük:
+        return False
+    del sözlük[anahtar]
+    return True
+
+
+# Türkçe harflerin büyük ve küçük halleri Python'un varsayılan dönüşümünden farklıdır
+def _küçült(yazı):
+    return yazı.replace("I", "ı").replace("İ", "i").lower()
+
+
+def _büyült(yazı):
+    return yazı.replace("i", "İ").replace("ı", "I").upper()
+
+
+_yöntemler = {
+    "liste": {
+        "getir": lambda liste, sıra: liste[int(sıra)] if _sıra(liste, sıra) is not None else None,
+        "güncelle": _liste_güncelle,
+        "uzunluk": len,
+        "ekle": _liste_ekle,
+        "temizle": lambda liste: liste.clear(),
+        "arayaekle": _liste_arayaekle,
+        "pop": lambda liste: liste.pop() if liste else None,
+        "sil": _liste_sil,
+        "sırala": _liste_sırala,
+        "kopyala": copy.deepcopy
+    },
+    "yazı": {
+        "uzunluk": len,
+        "harfleriküçült": _küçült,
+        "harfleribüyült": _büyült,
+        "içeriyormu": lambda yazı, aranan: aranan in yazı,
+        "satırlar": _yazı_satırlar,
+        "parçala": lambda yazı, ayraç: yazı.split(ayraç),
+        "ara": _yazı_ara,
+        "değiştir": lambda yazı, eski, yeni: yazı.replace(eski, yeni),
+        "kırp": lambda yazı: yazı.strip(),
+        "sonukırp": lambda yazı: yazı.rstrip(),
+        "başıkırp": lambda yazı: yazı.lstrip(),
+        "parçagetir": lambda yazı, başlangıç, bitiş: yazı[max(int(başlangıç), 0):max(int(bitiş), 0)],
+        "sayı": _yazı_sayı,
+        "levenshtein": _levenshtein
+    },
+    "sayı": {
+        "hex": _sayı_hex,
+        "yazı": lambda sayı: _ondalık(float(sayı)),
+        "yuvarla": lambda sayı: math.copysign(math.floor(abs(sayı) + 0.5), sayı),
+        "tavan": math.ceil,
+        "taban": math.floor,
+        "tamsayı": math.trunc,
+        "kesir": lambda sayı: sayı - math.trunc(sayı),
+        "üst": lambda sayı, kuvvet: math.pow(sayı, kuvvet)
+    },
+    "sözlük": {
+        "getir": lambda sözlük, anahtar: sözlük.get(anahtar),
+        "güncelle": lambda sözlük, anahtar, değer: sözlük.__setitem__(anahtar, değer),
+        "ekle": lambda sözlük, anahtar, değer: sözlük.__setitem__(anahtar, değer),
+        "içeriyormu": lambda sözlük, anahtar: anahtar in sözlük,
+        "uzunluk": len,
+        "temizle": lambda sözlük: sözlük.clear(),
+        "sil": _sözlük_sil,
+        "anahtarlar": lambda sözlük: list(sözlük),
+        "kopyala": copy.deepcopy
+    },
+    "hata": {
+        "kod": lambda hata: hata.kod,
+        "mesaj": lambda hata: hata.mesaj,
+        "veri": lambda hata: hata.veri
+    }
+}
+
+_eşadlar = {
+    "liste": {"guncelle": "güncelle", "sirala": "sırala"},
+    "yazı": {"harflerikucult": "harfleriküçült", "harfleribuyult": "harfleribüyült", "iceriyormu": "içeriyormu", "satirlar": "satırlar",
+             "parcala": "parçala", "degistir": "değiştir", "kirp": "kırp", "sonukirp": "sonukırp", "basikirp": "başıkırp",
+             "parcagetir": "parçagetir", "sayi": "sayı"},
+    "sayı": {"yazi": "yazı"},
+    "sözlük": {"guncelle": "güncelle", "iceriyormu": "içeriyormu"}
+}
+for _tip, _isimler in _eşadlar.items():
+    for _isim, _asıl in _isimler.items():
+        _yöntemler[_tip][_isim] = _yöntemler[_tip][_asıl]
+
+
+# Python'un yazı, sayı ve listelerine yeni fonksiyon eklenemediği için sınıf fonksiyonları bu fonksiyon ile çağrılır
+def _çağır(nesne, isim, *argümanlar):
+    yöntem = _yöntemler.get(_tür(nesne), {}).get(isim)
+    if yöntem is None and isinstance(nesne, dict) and callable(nesne.get(isim)):
+        return nesne[isim](*argümanlar)
+    if yöntem is None:
+        raise Hata(None, "'" + isim + "' fonksiyonu bulunamadı", None)
+    return yöntem(nesne, *argümanlar)
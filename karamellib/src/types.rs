@@ -1,8 +1,6 @@
 use std::borrow::Borrow;
 use std::mem::{self, discriminant};
 use std::vec::Vec;
-use std::str::Chars;
-use std::iter::Peekable;
 use std::result::Result;
 use std::hash::Hash;
 use std::rc::Rc;
@@ -57,7 +55,18 @@ pub enum KaramelKeywordType {
     Break,
     Continue,
     While,
-    Load
+    Load,
+    Try,
+    Catch,
+    Throw,
+    Defer,
+    Assume,
+    Constant,
+    Local,
+    Enum,
+    Private,
+    As,
+    With
 }
 
 impl KaramelKeywordType {
@@ -101,7 +110,22 @@ pub static KEYWORDS: &[(&str, KaramelKeywordType)] = &[
     ("döngü",         KaramelKeywordType::While),
     ("dongu",         KaramelKeywordType::While),
     ("yükle",          KaramelKeywordType::Load),
-    ("yukle",          KaramelKeywordType::Load)
+    ("yukle",          KaramelKeywordType::Load),
+    ("dene",           KaramelKeywordType::Try),
+    ("yakala",         KaramelKeywordType::Catch),
+    ("fırlat",         KaramelKeywordType::Throw),
+    ("firlat",         KaramelKeywordType::Throw),
+    ("ertele",         KaramelKeywordType::Defer),
+    ("varsay",         KaramelKeywordType::Assume),
+    ("hata_ayıklama_doğrula", KaramelKeywordType::Assume),
+    ("hata_ayiklama_dogrula", KaramelKeywordType::Assume),
+    ("sabit",          KaramelKeywordType::Constant),
+    ("yerel",          KaramelKeywordType::Local),
+    ("seçenekler",     KaramelKeywordType::Enum),
+    ("secenekler",     KaramelKeywordType::Enum),
+    ("gizli",          KaramelKeywordType::Private),
+    ("olarak",         KaramelKeywordType::As),
+    ("birlikte",       KaramelKeywordType::With)
 ];
 
 #[derive(Clone, Copy)]
@@ -143,7 +167,8 @@ pub enum KaramelOperatorType {
     CommentMultilineStart,
     CommentMultilineEnd,
     CurveBracketStart,
-    CurveBracketEnd
+    CurveBracketEnd,
+    At
 }
 
  impl KaramelOperatorType {
@@ -162,9 +187,14 @@ pub enum KaramelTokenType {
     Symbol(Rc<String>),
     Operator(KaramelOperatorType),
     Text(Rc<String>),
+    Bytes(Rc<Vec<u8>>),
     Keyword(KaramelKeywordType),
     WhiteSpace(u8),
-    NewLine(u8)
+    NewLine(u8),
+    Comment(Rc<String>),
+
+    /// '##' line comment. Content is kept without the marker, syntax parser attaches it to the next function.
+    DocComment(Rc<String>)
 }
 
 #[repr(C)]
@@ -178,6 +208,39 @@ pub enum KaramelNumberSystem {
     Hexadecimal = 3
 }
 
+impl KaramelNumberSystem {
+    pub fn radix(&self) -> u32 {
+        match self {
+            KaramelNumberSystem::Binary      => 2,
+            KaramelNumberSystem::Octal       => 8,
+            KaramelNumberSystem::Decimal     => 10,
+            KaramelNumberSystem::Hexadecimal => 16
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            KaramelNumberSystem::Binary      => "İkilik",
+            KaramelNumberSystem::Octal       => "Sekizlik",
+            KaramelNumberSystem::Decimal     => "Onluk",
+            KaramelNumberSystem::Hexadecimal => "Onaltılık"
+        }
+    }
+}
+
+/// Place of a statement in the source. Line and column are zero based.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SourcePosition {
+    pub line: u32,
+    pub column: u32
+}
+
+impl SourcePosition {
+    pub fn new(line: u32, column: u32) -> Self {
+        SourcePosition { line, column }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct Token {
     pub line      : u32,
@@ -186,37 +249,67 @@ pub struct Token {
     pub token_type: KaramelTokenType
 }
 
+/// Cursor over the source. The current character and the next two are kept decoded, so the lookahead does not walk the source again.
 pub struct Tokinizer<'a> {
     pub line  : u32,
     pub column: u32,
     pub tokens: Vec<Token>,
-    pub iter: Peekable<Chars<'a>>,
-    pub iter_second: Peekable<Chars<'a>>,
-    pub iter_third: Peekable<Chars<'a>>,
-    pub data: String,
-    pub index: u32
+    pub data: &'a str,
+
+    /// Byte index of the current character
+    pub index: u32,
+    window: [char; 3],
+    window_end: usize
 }
 
-impl Tokinizer<'_> {
-    pub fn is_end(&mut self) -> bool {
-        return match self.iter.peek() {
-            Some(_) => false,
-            None => true
-        };
+impl<'a> Tokinizer<'a> {
+    pub fn new(data: &'a str) -> Tokinizer<'a> {
+        Tokinizer::with_position(data, 0, 0, 0)
     }
 
-    pub fn get_char(&mut self) -> char {
-        return match self.iter.peek() {
-            Some(&c) => c,
-            None => '\0'
+    /// Starts from the byte `index` of the data. `line` and `column` are the position of that byte.
+    pub fn with_position(data: &'a str, index: u32, line: u32, column: u32) -> Tokinizer<'a> {
+        let mut tokinizer = Tokinizer {
+            column,
+            line,
+            tokens: Vec::new(),
+            data,
+            index,
+            window: ['\0'; 3],
+            window_end: index as usize
         };
+
+        for position in 0..3 {
+            tokinizer.window[position] = tokinizer.decode_next();
+        }
+        tokinizer
     }
 
-    pub fn get_next_char(&mut self) -> char {
-        return match self.iter_second.peek() {
-            Some(&c) => c,
+    /* Decodes the character after the window, '\0' after the end of the data */
+    fn decode_next(&mut self) -> char {
+        match self.data[self.window_end..].chars().next() {
+            Some(ch) => {
+                self.window_end += ch.len_utf8();
+                ch
+            },
             None => '\0'
-        };
+        }
+    }
+
+    pub fn is_end(&self) -> bool {
+        self.index as usize >= self.data.len()
+    }
+
+    pub fn get_char(&self) -> char {
+        self.window[0]
+    }
+
+    pub fn get_next_char(&self) -> char {
+        self.window[1]
+    }
+
+    pub fn get_third_char(&self) -> char {
+        self.window[2]
     }
 
     pub fn add_token(&mut self, start: u32, token_type: KaramelTokenType) {
@@ -230,11 +323,13 @@ impl Tokinizer<'_> {
     }
 
     pub fn increase_index(&mut self) {
-        self.index  += self.get_char().len_utf8() as u32;
         self.column += 1;
-        self.iter.next();
-        self.iter_second.next();
-        self.iter_third.next();
+        if self.is_end() {
+            return;
+        }
+
+        self.index += self.window[0].len_utf8() as u32;
+        self.window = [self.window[1], self.window[2], self.decode_next()];
     }
 
     pub fn increate_line(& mut self) {
@@ -257,6 +352,7 @@ pub trait CharTraits {
     fn is_whitespace(&self) -> bool;
     fn is_symbol(&self) -> bool;
     fn is_integer(&self) -> bool;
+    fn is_combining_mark(&self) -> bool;
 }
 
 impl CharTraits for char {
@@ -281,6 +377,11 @@ impl CharTraits for char {
             _ => false,
         }
     }
+
+    /* Decomposed letters (i + ◌̇, s + ◌̧ ...) use the combining diacritical marks block */
+    fn is_combining_mark(&self) -> bool {
+        matches!(*self, '\u{0300}'..='\u{036F}')
+    }
 }
 
 impl KaramelTokenType {
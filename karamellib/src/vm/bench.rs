@@ -0,0 +1,180 @@
+use std::borrow::Borrow;
+use std::cell::RefCell;
+use std::time::{Duration, Instant};
+
+use crate::buildin::json;
+use crate::compiler::{KaramelPrimative, DictKey};
+use crate::compiler::context::KaramelCompilerContext;
+use crate::compiler::value::set_print_precision;
+use crate::constants::KARAMEL_GC_THRESHOLD;
+use crate::error::{KaramelError, KaramelErrorType, KaramelRuntimeError};
+use crate::error::diagnostic::encode_text;
+use crate::gc;
+use crate::interner::release_unused;
+use crate::messages;
+use crate::vm::executer::code_program;
+use crate::vm::interpreter::{run_function, run_vm};
+use crate::vm::program::CompiledProgram;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct BenchOptions {
+    /// Measured runs
+    pub iterations: usize,
+
+    /// Runs before the measurement, their times are not used
+    pub warmup: usize,
+
+    /// Function of the main code that is measured. Main code is measured when it is empty.
+    pub function: Option<String>
+}
+
+impl Default for BenchOptions {
+    fn default() -> Self {
+        BenchOptions {
+            iterations: 10,
+            warmup: 2,
+            function: None
+        }
+    }
+}
+
+/// Wall times of the measured runs. Instruction count is taken from the last run.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BenchReport {
+    pub name: String,
+    pub iterations: usize,
+    pub min: Duration,
+    pub average: Duration,
+    pub p95: Duration,
+    pub instructions: usize
+}
+
+impl BenchReport {
+    fn new(name: String, mut durations: Vec<Duration>, instructions: usize) -> BenchReport {
+        durations.sort();
+        let total: Duration = durations.iter().sum();
+        let p95_index = ((durations.len() * 95 + 99) / 100).max(1) - 1;
+
+        BenchReport {
+            name,
+            iterations: durations.len(),
+            min: durations.first().copied().unwrap_or_default(),
+            average: total / durations.len().max(1) as u32,
+            p95: durations.get(p95_index).copied().unwrap_or_default(),
+            instructions
+        }
+    }
+
+    /// Single line JSON object. Times are written in milliseconds.
+    pub fn to_json(&self) -> String {
+        let mut buffer = String::from("{\"name\": ");
+        encode_text(&self.name, &mut buffer);
+        buffer.push_str(&format!(", \"iterations\": {}, \"min_ms\": {}, \"average_ms\": {}, \"p95_ms\": {}, \"instructions\": {}}}",
+            self.iterations, milliseconds(self.min), milliseconds(self.average), milliseconds(self.p95), self.instructions));
+        buffer
+    }
+
+    /// Reads the report that is written with 'to_json'. Used as the baseline of the comparison.
+    pub fn from_json(text: &str) -> Option<BenchReport> {
+        let value = json::decode(&mut text.chars().peekable())?.deref();
+        let dict = match &*value {
+            KaramelPrimative::Dict(dict) => dict.borrow().clone(),
+            _ => return None
+        };
+
+        let number = |key: &str| dict.get(&DictKey::from(key)).and_then(|value| value.as_number());
+        Some(BenchReport {
+            name: dict.get(&DictKey::from("name")).map(|name| name.deref().get_text()).unwrap_or_default(),
+            iterations: number("iterations")? as usize,
+            min: Duration::from_secs_f64(number("min_ms")? / 1000.0),
+            average: Duration::from_secs_f64(number("average_ms")? / 1000.0),
+            p95: Duration::from_secs_f64(number("p95_ms")? / 1000.0),
+            instructions: number("instructions")? as usize
+        })
+    }
+
+    /// Measurement as a table. Changes are written next to the values when a baseline is given.
+    pub fn render(&self, baseline: Option<&BenchReport>) -> String {
+        let rows = [
+            ("bench.min", format!("{:.3} ms", milliseconds(self.min)), baseline.map(|baseline| change(milliseconds(self.min), milliseconds(baseline.min)))),
+            ("bench.average", format!("{:.3} ms", milliseconds(self.average)), baseline.map(|baseline| change(milliseconds(self.average), milliseconds(baseline.average)))),
+            ("bench.p95", format!("{:.3} ms", milliseconds(self.p95)), baseline.map(|baseline| change(milliseconds(self.p95), milliseconds(baseline.p95)))),
+            ("bench.instructions", self.instructions.to_string(), baseline.map(|baseline| change(self.instructions as f64, baseline.instructions as f64)))
+        ];
+
+        let mut buffer = messages::format("bench.title", &[("0", &self.name), ("1", &self.iterations)]);
+        for (key, value, change) in rows.iter() {
+            buffer.push_str(&format!("\n  {:<10} {:>14}", messages::text(key), value));
+            if let Some(change) = change {
+                buffer.push_str(&format!("  {}", change));
+            }
+        }
+        buffer
+    }
+}
+
+fn milliseconds(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1000.0
+}
+
+fn change(value: f64, baseline: f64) -> String {
+    match baseline == 0.0 {
+        true => "-".to_string(),
+        false => format!("{:+.1}%", (value - baseline) / baseline * 100.0)
+    }
+}
+
+/// Compiles the code once and runs it again and again on new vm instances.
+pub fn code_bench<T: Borrow<str>>(data: T, name: &str, options: &BenchOptions) -> Result<BenchReport, KaramelError> {
+    let program = code_program(data)?;
+    program_bench(&program, name, options).map_err(KaramelError::from)
+}
+
+pub fn program_bench(program: &CompiledProgram, name: &str, options: &BenchOptions) -> Result<BenchReport, KaramelRuntimeError> {
+    let mut durations = Vec::with_capacity(options.iterations);
+    let mut instructions = 0;
+
+    for iteration in 0..options.warmup + options.iterations.max(1) {
+        let (duration, count) = run_once(program, options.function.as_deref())?;
+        if iteration >= options.warmup {
+            durations.push(duration);
+            instructions = count;
+        }
+    }
+
+    let name = match &options.function {
+        Some(function) => format!("{}::{}", name, function),
+        None => name.to_string()
+    };
+    Ok(BenchReport::new(name, durations, instructions))
+}
+
+/* Output of the program is not written to the screen, writing would change the measurement */
+fn run_once(program: &CompiledProgram, function: Option<&str>) -> Result<(Duration, usize), KaramelRuntimeError> {
+    release_unused();
+    gc::reset(KARAMEL_GC_THRESHOLD);
+
+    let mut context = KaramelCompilerContext::new();
+    program.load(&mut context);
+    set_print_precision(context.print_precision);
+    context.stdout = Some(RefCell::new(String::new()));
+    context.stderr = Some(RefCell::new(String::new()));
+    context.count_instructions = true;
+
+    let function = match function {
+        Some(name) => {
+            /* Main code prepares the function, only the call is measured */
+            unsafe { run_vm(&mut context, false, false) }?;
+            context.instruction_count = 0;
+            Some(context.get_main_function(name).ok_or_else(|| KaramelErrorType::FunctionNotFound(name.to_string()))?)
+        },
+        None => None
+    };
+
+    let start = Instant::now();
+    match function {
+        Some(function) => unsafe { run_function(&mut context, function, &[]) }.map(|_| ())?,
+        None => unsafe { run_vm(&mut context, false, false) }.map(|_| ())?
+    };
+    Ok((start.elapsed(), context.instruction_count))
+}
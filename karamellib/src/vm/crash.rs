@@ -0,0 +1,104 @@
+use std::any::Any;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use std::rc::Rc;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::compiler::ast::KaramelAstType;
+use crate::constants::KARAMEL_VERSION;
+use crate::types::Token;
+
+/// The stages that the interpreter completed before the panic. Written to a file to reproduce the internal errors.
+#[derive(Default)]
+pub struct CrashReport {
+    pub source: Option<String>,
+    pub tokens: Option<Vec<Token>>,
+    pub ast: Option<Rc<KaramelAstType>>,
+    pub opcodes: Option<String>
+}
+
+/// Message of the panic payload
+pub fn panic_message(payload: &(dyn Any + Send)) -> String {
+    match (payload.downcast_ref::<&str>(), payload.downcast_ref::<String>()) {
+        (Some(message), _) => message.to_string(),
+        (_, Some(message)) => message.to_string(),
+        _ => "Bilinmeyen hata".to_string()
+    }
+}
+
+impl CrashReport {
+    pub fn to_text(&self, message: &str) -> String {
+        let mut buffer = String::new();
+        buffer.push_str(&format!("== Sürüm ==\n{}\n\n", KARAMEL_VERSION));
+        buffer.push_str(&format!("== Hata ==\n{}\n\n", message));
+
+        buffer.push_str("== Kaynak Kod ==\n");
+        match &self.source {
+            Some(source) => buffer.push_str(source),
+            None => buffer.push_str("Okunamadı")
+        };
+
+        buffer.push_str("\n\n== Tokenlar ==\n");
+        match &self.tokens {
+            Some(tokens) => tokens.iter().for_each(|token| buffer.push_str(&format!("{}:{} {:?}\n", token.line, token.start, token.token_type))),
+            None => buffer.push_str("Oluşturulamadı\n")
+        };
+
+        buffer.push_str("\n== Sözdizimi Ağacı ==\n");
+        match &self.ast {
+            Some(ast) => buffer.push_str(&format!("{:#?}\n", ast)),
+            None => buffer.push_str("Oluşturulamadı\n")
+        };
+
+        buffer.push_str("\n== Komutlar ==\n");
+        match &self.opcodes {
+            Some(opcodes) => buffer.push_str(opcodes),
+            None => buffer.push_str("Oluşturulamadı\n")
+        };
+        buffer
+    }
+
+    /// Writes the report into the temporary folder and returns the path of the file.
+    pub fn write(&self, message: &str) -> io::Result<PathBuf> {
+        let time = SystemTime::now().duration_since(UNIX_EPOCH).map(|duration| duration.as_millis()).unwrap_or_default();
+        let path = std::env::temp_dir().join(format!("karamel_hata_raporu_{}_{}.txt", std::process::id(), time));
+        fs::write(&path, self.to_text(message))?;
+        Ok(path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::{panic_message, CrashReport};
+    use crate::parser::Parser;
+
+    #[test]
+    fn report_test() {
+        let mut parser = Parser::new("a = 1");
+        parser.parse().unwrap();
+
+        let report = CrashReport {
+            source: Some("a = 1".to_string()),
+            tokens: Some(parser.tokens()),
+            ..CrashReport::default()
+        };
+
+        let path = report.write("test").unwrap();
+        let content = fs::read_to_string(&path).unwrap();
+        fs::remove_file(path).unwrap();
+
+        assert!(content.contains("== Hata ==\ntest"));
+        assert!(content.contains("a = 1"));
+        assert!(content.contains("0:0 Symbol(\"a\")"));
+        assert!(content.contains("== Sözdizimi Ağacı ==\nOluşturulamadı"));
+    }
+
+    #[test]
+    fn panic_message_test() {
+        let payload = std::panic::catch_unwind(|| panic!("{} hata", 1)).unwrap_err();
+        assert_eq!(panic_message(&*payload), "1 hata");
+    }
+}
@@ -0,0 +1,315 @@
+use std::collections::HashMap;
+use std::io::Write;
+use std::rc::Rc;
+
+use crate::buildin::json::encode;
+use crate::error::diagnostic::encode_text;
+use crate::compiler::{GetType, KaramelPrimative};
+use crate::compiler::dict_key::DictKey;
+use crate::constants::KARAMEL_EVENT_VALUE_DEPTH;
+use crate::types::VmObject;
+use crate::messages;
+
+/// Events that are generated by the virtual machine while the code is executing. Lines are zero based.
+#[derive(Clone, Debug, PartialEq)]
+pub enum VmEvent {
+    LineEntered(u32),
+    VariableChanged {
+        name: String,
+        old_value: Option<Rc<KaramelPrimative>>,
+        new_value: Rc<KaramelPrimative>
+    },
+    FunctionCalled {
+        name: String,
+        arguments: Vec<(String, Rc<KaramelPrimative>)>
+    },
+    FunctionReturned {
+        name: String,
+        value: Rc<KaramelPrimative>
+    }
+}
+
+fn encode_value(value: &Rc<KaramelPrimative>, buffer: &mut String) {
+    encode_item(value, buffer, &mut Vec::new());
+}
+
+/* The event stream must not stop the observed program. Values that contain themselves and too deep values are written as
+   placeholders, the values that JSON can not keep are written as text. */
+fn encode_item(value: &Rc<KaramelPrimative>, buffer: &mut String, parents: &mut Vec<*const KaramelPrimative>) {
+    if parents.contains(&Rc::as_ptr(value)) {
+        return encode_text("<döngü>", buffer);
+    }
+
+    if parents.len() >= KARAMEL_EVENT_VALUE_DEPTH {
+        return encode_text("<...>", buffer);
+    }
+
+    parents.push(Rc::as_ptr(value));
+    match &**value {
+        KaramelPrimative::List(items) | KaramelPrimative::Stack(items) => {
+            buffer.push('[');
+            for (index, item) in items.borrow().iter().enumerate() {
+                if index > 0 {
+                    buffer.push_str(", ");
+                }
+                encode_item(&item.deref(), buffer, parents);
+            }
+            buffer.push(']');
+        },
+        KaramelPrimative::Dict(items) => {
+            let items = items.borrow();
+            let mut keys = items.keys().collect::<Vec<&DictKey>>();
+            keys.sort();
+            encode_pairs(keys.into_iter().map(|key| (key, items[key])), buffer, parents);
+        },
+        KaramelPrimative::SortedDict(items) => encode_pairs(items.borrow().iter().map(|(key, item)| (key, *item)), buffer, parents),
+        KaramelPrimative::Frozen(inner) => encode_item(&inner.deref(), buffer, parents),
+        KaramelPrimative::Queue(_) | KaramelPrimative::PriorityQueue(_) => encode_text(&format!("<{}>", value.get_type()), buffer),
+        KaramelPrimative::Number(number) if !number.is_finite() => encode_text(&number.to_string(), buffer),
+        _ => {
+            let mut encoded = String::new();
+            match encode(VmObject::from(value.clone()), &mut encoded) {
+                Ok(_) => buffer.push_str(&encoded),
+                Err(_) => encode_text(&format!("{}", value), buffer)
+            };
+        }
+    };
+    parents.pop();
+}
+
+fn encode_pairs<'a, I>(items: I, buffer: &mut String, parents: &mut Vec<*const KaramelPrimative>) where I: Iterator<Item = (&'a DictKey, VmObject)> {
+    buffer.push('{');
+    for (index, (key, item)) in items.enumerate() {
+        if index > 0 {
+            buffer.push_str(", ");
+        }
+        match key.as_text() {
+            Some(text) => encode_text(text, buffer),
+            None => encode_text(&format!("{:?}", key), buffer)
+        };
+        buffer.push_str(": ");
+        encode_item(&item.deref(), buffer, parents);
+    }
+    buffer.push('}');
+}
+
+impl VmEvent {
+    /// Single line JSON object. Lines are one based at the JSON output.
+    pub fn to_json(&self) -> String {
+        let mut buffer = String::new();
+        match self {
+            VmEvent::LineEntered(line) => buffer.push_str(&format!("{{\"event\": \"line\", \"line\": {}}}", line + 1)),
+            VmEvent::VariableChanged { name, old_value, new_value } => {
+                buffer.push_str("{\"event\": \"variable\", \"name\": ");
+                encode_text(name, &mut buffer);
+                buffer.push_str(", \"old\": ");
+                match old_value {
+                    Some(old_value) => encode_value(old_value, &mut buffer),
+                    None => buffer.push_str("null")
+                };
+                buffer.push_str(", \"new\": ");
+                encode_value(new_value, &mut buffer);
+                buffer.push('}');
+            },
+            VmEvent::FunctionCalled { name, arguments } => {
+                buffer.push_str("{\"event\": \"call\", \"name\": ");
+                encode_text(name, &mut buffer);
+                buffer.push_str(", \"arguments\": {");
+                for (index, (argument, value)) in arguments.iter().enumerate() {
+                    if index > 0 {
+                        buffer.push_str(", ");
+                    }
+                    encode_text(argument, &mut buffer);
+                    buffer.push_str(": ");
+                    encode_value(value, &mut buffer);
+                }
+                buffer.push_str("}}");
+            },
+            VmEvent::FunctionReturned { name, value } => {
+                buffer.push_str("{\"event\": \"return\", \"name\": ");
+                encode_text(name, &mut buffer);
+                buffer.push_str(", \"value\": ");
+                encode_value(value, &mut buffer);
+                buffer.push('}');
+            }
+        };
+        buffer
+    }
+}
+
+pub trait VmEventHook {
+    fn on_event(&mut self, event: VmEvent);
+
+    /// Called after the execution finished, even if the execution failed.
+    fn finish(&mut self) {}
+}
+
+/// Writes the events as JSON lines. Events are kept in the memory until the buffer is full.
+pub struct JsonLinesEventStream<W: Write> {
+    writer: W,
+    buffer: Vec<String>,
+    capacity: usize
+}
+
+impl<W: Write> JsonLinesEventStream<W> {
+    pub fn new(writer: W, capacity: usize) -> Self {
+        JsonLinesEventStream {
+            writer,
+            buffer: Vec::with_capacity(capacity),
+            capacity: capacity.max(1)
+        }
+    }
+
+    pub fn into_inner(mut self) -> W {
+        self.flush();
+        self.writer
+    }
+
+    fn flush(&mut self) {
+        for line in self.buffer.drain(..) {
+            if let Err(error) = writeln!(self.writer, "{}", line) {
+                log::error!("{}", messages::format("events.write_failed", &[("0", &error)]));
+                break;
+            }
+        }
+
+        self.buffer.clear();
+        let _ = self.writer.flush();
+    }
+}
+
+impl<W: Write> VmEventHook for JsonLinesEventStream<W> {
+    fn on_event(&mut self, event: VmEvent) {
+        self.buffer.push(event.to_json());
+        if self.buffer.len() >= self.capacity {
+            self.flush();
+        }
+    }
+
+    fn finish(&mut self) {
+        self.flush();
+    }
+}
+
+struct CallFrame {
+    name: String,
+    variables: HashMap<String, Rc<KaramelPrimative>>
+}
+
+/// Converts the raw virtual machine changes to events. Keeps the last values of the variables to find the old values.
+pub struct VmEventTracker {
+    hook: Box<dyn VmEventHook>,
+    frames: Vec<CallFrame>
+}
+
+impl VmEventTracker {
+    pub fn new(hook: Box<dyn VmEventHook>) -> Self {
+        VmEventTracker {
+            hook,
+            frames: vec![CallFrame { name: String::new(), variables: HashMap::new() }]
+        }
+    }
+
+    pub fn line_entered(&mut self, line: u32) {
+        self.hook.on_event(VmEvent::LineEntered(line));
+    }
+
+    pub fn variable_changed(&mut self, name: &str, value: VmObject) {
+        let new_value = value.deref();
+        let frame = self.frames.last_mut().unwrap();
+        let old_value = frame.variables.insert(name.to_string(), new_value.clone());
+
+        if old_value.as_ref() != Some(&new_value) {
+            self.hook.on_event(VmEvent::VariableChanged { name: name.to_string(), old_value, new_value });
+        }
+    }
+
+    pub fn function_called(&mut self, name: &str, arguments: Vec<(String, VmObject)>) {
+        let arguments: Vec<(String, Rc<KaramelPrimative>)> = arguments.into_iter().map(|(argument, value)| (argument, value.deref())).collect();
+        self.frames.push(CallFrame {
+            name: name.to_string(),
+            variables: arguments.iter().cloned().collect()
+        });
+        self.hook.on_event(VmEvent::FunctionCalled { name: name.to_string(), arguments });
+    }
+
+    pub fn function_returned(&mut self, value: VmObject) {
+        if self.frames.len() > 1 {
+            let frame = self.frames.pop().unwrap();
+            self.hook.on_event(VmEvent::FunctionReturned { name: frame.name, value: value.deref() });
+        }
+    }
+
+    pub fn finish(&mut self) {
+        self.hook.finish();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::rc::Rc;
+
+    use super::{JsonLinesEventStream, VmEvent, VmEventHook};
+    use crate::compiler::KaramelPrimative;
+    use crate::types::VmObject;
+
+    #[test]
+    fn json_lines_test() {
+        let mut stream = JsonLinesEventStream::new(Vec::new(), 2);
+        stream.on_event(VmEvent::LineEntered(0));
+        stream.on_event(VmEvent::VariableChanged {
+            name: "ad".to_string(),
+            old_value: None,
+            new_value: Rc::new(KaramelPrimative::Text(Rc::new("\"Ali\"".to_string())))
+        });
+        stream.on_event(VmEvent::FunctionCalled {
+            name: "topla".to_string(),
+            arguments: vec![("a".to_string(), Rc::new(KaramelPrimative::Number(1.0)))]
+        });
+        stream.on_event(VmEvent::FunctionReturned { name: "topla".to_string(), value: Rc::new(KaramelPrimative::Bool(true)) });
+
+        let output = String::from_utf8(stream.into_inner()).unwrap();
+        assert_eq!(output.lines().collect::<Vec<_>>(), vec![
+            r#"{"event": "line", "line": 1}"#,
+            r#"{"event": "variable", "name": "ad", "old": null, "new": "\"Ali\""}"#,
+            r#"{"event": "call", "name": "topla", "arguments": {"a": 1}}"#,
+            r#"{"event": "return", "name": "topla", "value": true}"#
+        ]);
+    }
+
+    #[test]
+    fn recursive_value_test() {
+        let list = VmObject::from(vec![VmObject::from(1.0)]);
+        if let KaramelPrimative::List(items) = &*list.deref() {
+            items.borrow_mut().push(list);
+        }
+
+        let mut nested = VmObject::from(Vec::new());
+        for _ in 0..100 {
+            nested = VmObject::from(vec![nested]);
+        }
+
+        let mut stream = JsonLinesEventStream::new(Vec::new(), 1);
+        for value in [list, nested, VmObject::from(f64::INFINITY), VmObject::from(f64::NAN)].iter() {
+            stream.on_event(VmEvent::FunctionReturned { name: "f".to_string(), value: value.deref() });
+        }
+
+        let output = String::from_utf8(stream.into_inner()).unwrap();
+        let lines = output.lines().collect::<Vec<_>>();
+        assert_eq!(lines[0], r#"{"event": "return", "name": "f", "value": [1, "<döngü>"]}"#);
+        assert!(lines[1].ends_with(&format!("{}\"<...>\"{}}}", "[".repeat(32), "]".repeat(32))), "{}", lines[1]);
+        assert_eq!(lines[2], r#"{"event": "return", "name": "f", "value": "inf"}"#);
+        assert_eq!(lines[3], r#"{"event": "return", "name": "f", "value": "NaN"}"#);
+    }
+
+    #[test]
+    fn bounded_buffer_test() {
+        let mut stream = JsonLinesEventStream::new(Vec::new(), 2);
+        stream.on_event(VmEvent::LineEntered(0));
+        assert!(stream.writer.is_empty());
+
+        stream.on_event(VmEvent::LineEntered(1));
+        assert_eq!(stream.writer.iter().filter(|&&byte| byte == b'\n').count(), 2);
+        assert!(stream.buffer.is_empty());
+    }
+}
@@ -1,17 +1,33 @@
 use std::borrow::Borrow;
 use std::cell::RefCell;
+use std::rc::Rc;
+use std::sync::Arc;
+use std::sync::atomic::AtomicBool;
+use std::panic::{self, AssertUnwindSafe};
 
-use crate::compiler::context::{ExecutionPathInfo, KaramelCompilerContext};
-use crate::file::read_module_or_script;
+use crate::compiler::context::{ExecutionPathInfo, KaramelCompilerContext, LoopCounter};
+use crate::compiler::resolver::resolve;
+use crate::file::{read_module_or_script, FileSystem};
 use crate::{types::Token, vm::interpreter::run_vm};
 use crate::parser::*;
 use crate::compiler::*;
 use crate::syntax::SyntaxParser;
 use crate::logger::{CONSOLE_LOGGER, write_stderr};
-use crate::error::generate_error_message;
+use crate::vm::crash::{panic_message, CrashReport};
+use crate::vm::events::{VmEventHook, VmEventTracker};
+use crate::vm::program::CompiledProgram;
+use crate::vm::trace::{TraceOutput, TraceRecord, VmTracer};
+use crate::error::{CompilerWarning, KaramelError, KaramelErrorType, KaramelRuntimeError, generate_error_message, generate_warning_message};
+use crate::error::diagnostic::Diagnostic;
+use crate::compiler::value::set_print_precision;
+use crate::buildin::Permission;
+use crate::messages;
 
 use log;
 use crate::types::VmObject;
+use crate::interner::release_unused;
+use crate::gc;
+use crate::constants::KARAMEL_GC_THRESHOLD;
 
 
 pub enum ExecutionSource {
@@ -24,7 +40,72 @@ pub struct ExecutionParameters {
     pub return_opcode: bool,
     pub return_output: bool,
     pub dump_opcode: bool,
-    pub dump_memory: bool
+    pub dump_memory: bool,
+    pub teaching_mode: bool,
+
+    /// Compiles the 'varsay' statements
+    pub debug_assertions: bool,
+
+    /// Checks the annotated argument types when the functions are called
+    pub type_guards: bool,
+
+    /// Receives the line, variable and function events while the code is executing
+    pub event_hook: Option<Box<dyn VmEventHook>>,
+
+    /// Files for 'yükle' and the 'dosya' module. Files of the operating system are used when it is not given.
+    pub file_system: Option<Rc<dyn FileSystem>>,
+
+    /// Heap values that are allocated between the garbage collections. Default threshold is used when it is not given.
+    pub gc_threshold: Option<usize>,
+
+    /// Function calls that can be nested. Default depth is used when it is not given.
+    pub max_call_depth: Option<usize>,
+
+    /// Execution stops with the 'InstructionLimitExceeded' error after this many instructions. There is no limit when it is not given.
+    pub max_instructions: Option<usize>,
+
+    /// Execution stops with the 'MemoryLimitExceeded' error after the texts, bytes and copies that are created reach this size in bytes.
+    /// There is no limit when it is not given.
+    pub max_memory: Option<usize>,
+
+    /// Permissions of the built-in modules that the code can use. Every module can be used when it is not given.
+    pub permissions: Option<Vec<Permission>>,
+
+    /// Lines for 'gç::satıroku'. Standard input is read when it is not given.
+    pub stdin: Option<String>,
+
+    /// Execution stops with the 'Interrupted' error after the flag is set. Can be set from another thread or a signal handler.
+    pub interrupt: Option<Arc<AtomicBool>>,
+
+    /// Records every executed instruction with its operands and stack change
+    pub trace: Option<TraceOutput>
+}
+
+impl ExecutionParameters {
+    /// Parameters that only run the source. Options are turned off and the default limits are used, other fields can be
+    /// given with the '..ExecutionParameters::new(source)' syntax.
+    pub fn new(source: ExecutionSource) -> ExecutionParameters {
+        ExecutionParameters {
+            source,
+            return_opcode: false,
+            return_output: false,
+            dump_opcode: false,
+            dump_memory: false,
+            teaching_mode: false,
+            debug_assertions: false,
+            type_guards: false,
+            event_hook: None,
+            file_system: None,
+            gc_threshold: None,
+            max_call_depth: None,
+            max_instructions: None,
+            max_memory: None,
+            permissions: None,
+            stdin: None,
+            interrupt: None,
+            trace: None
+        }
+    }
 }
 
 #[derive(Default)]
@@ -36,7 +117,38 @@ pub struct ExecutionStatus {
     pub stderr: Option<RefCell<String>>,
     pub opcodes: Option<Vec<Token>>,
     pub memory_dump: Option<String>,
-    pub opcode_dump: Option<String>
+    pub opcode_dump: Option<String>,
+    pub step_report: Option<StepReport>,
+    pub warnings: Vec<CompilerWarning>,
+    pub error: Option<KaramelError>,
+
+    /// All syntax errors of the code in the source order. 'error' keeps the first one.
+    pub syntax_errors: Vec<KaramelError>,
+
+    /// Error of the running code with its line and call stack
+    pub runtime_error: Option<KaramelRuntimeError>,
+
+    /// Last executed instructions when the trace is kept in a buffer
+    pub trace: Option<Vec<TraceRecord>>
+}
+
+impl ExecutionStatus {
+    /// Warnings and the error of the execution in the source order of the stages.
+    pub fn diagnostics(&self) -> Vec<Diagnostic> {
+        let mut diagnostics: Vec<Diagnostic> = self.warnings.iter().map(Diagnostic::from).collect();
+        match self.syntax_errors.is_empty() {
+            true => diagnostics.extend(self.error.iter().map(Diagnostic::from)),
+            false => diagnostics.extend(self.syntax_errors.iter().map(Diagnostic::from))
+        };
+        diagnostics
+    }
+}
+
+/// Executed instruction and loop iteration counts. Only created at teaching mode.
+#[derive(Clone, Debug, PartialEq)]
+pub struct StepReport {
+    pub instruction_count: usize,
+    pub loops: Vec<LoopCounter>
 }
 
 pub fn get_execution_path<T: Borrow<ExecutionSource>>(source: T) -> ExecutionPathInfo {
@@ -55,8 +167,56 @@ pub fn get_execution_path<T: Borrow<ExecutionSource>>(source: T) -> ExecutionPat
     }
 }
 
+fn write_teaching_hint(context: &KaramelCompilerContext, error: &KaramelErrorType) {
+    if context.teaching_mode {
+        if let Some(hint) = error.teaching_hint() {
+            let hint = messages::format("execution.hint", &[("0", &hint)]);
+            write_stderr(context, hint.clone());
+            log::info!("{}", hint);
+        }
+    }
+}
+
+fn write_warnings(context: &KaramelCompilerContext, data: &str, status: &mut ExecutionStatus) {
+    for warning in context.warnings.iter() {
+        write_stderr(context, generate_warning_message(data, warning));
+        log::warn!("{}", generate_warning_message(data, warning));
+
+        if context.teaching_mode {
+            if let Some(hint) = warning.warning_type.teaching_hint() {
+                let hint = messages::format("execution.hint", &[("0", &hint)]);
+                write_stderr(context, hint.clone());
+                log::info!("{}", hint);
+            }
+        }
+    }
+    status.warnings = context.warnings.clone();
+}
+
+fn write_step_report(context: &KaramelCompilerContext, status: &mut ExecutionStatus) {
+    if !context.teaching_mode {
+        return;
+    }
+
+    let mut lines = vec![messages::format("execution.step_report", &[("0", &context.instruction_count)])];
+    for counter in context.loop_counters.iter() {
+        lines.push(messages::format("execution.loop_report", &[("line", &(counter.line + 1)), ("count", &counter.iterations)]));
+    }
+
+    for line in lines.iter() {
+        write_stderr(context, line.to_string());
+        log::info!("{}", line);
+    }
+
+    status.step_report = Some(StepReport {
+        instruction_count: context.instruction_count,
+        loops: context.loop_counters.clone()
+    });
+}
+
+/// Runs the code and converts the internal panics to an error. The collected information is written to a crash report file
+/// so the error can be reproduced.
 pub fn code_executer(parameters: ExecutionParameters) -> ExecutionStatus {
-    let mut status = ExecutionStatus::default();
     match log::set_logger(&CONSOLE_LOGGER) {
         Ok(_) => {
             if cfg!(debug_assertions) {
@@ -68,8 +228,76 @@ pub fn code_executer(parameters: ExecutionParameters) -> ExecutionStatus {
         _ => ()
     };
 
+    /* Texts of the previous executions are not needed anymore */
+    release_unused();
+    gc::reset(parameters.gc_threshold.unwrap_or(KARAMEL_GC_THRESHOLD));
+
     let mut context: KaramelCompilerContext = KaramelCompilerContext::new();
+    let mut report = CrashReport::default();
+
+    let payload = match panic::catch_unwind(AssertUnwindSafe(|| execute(parameters, &mut context, &mut report))) {
+        Ok(status) => return status,
+        Err(payload) => payload
+    };
+
+    let message = panic_message(&*payload);
+    if report.ast.is_some() {
+        report.opcodes = panic::catch_unwind(AssertUnwindSafe(|| context.opcode_generator.dump(&context.opcodes))).ok();
+    }
+
+    let error = KaramelErrorType::InternalError(message.to_string());
+    let text = messages::format("execution.failed", &[("0", &error)]);
+    write_stderr(&context, text.clone());
+    log::error!("{}", text);
+    write_teaching_hint(&context, &error);
+
+    match report.write(&message) {
+        Ok(path) => {
+            let text = messages::format("execution.error_report", &[("0", &path.display())]);
+            write_stderr(&context, text.clone());
+            log::error!("{}", text);
+        },
+        Err(error) => log::error!("{}", messages::format("execution.error_report_failed", &[("0", &error)]))
+    };
+
+    ExecutionStatus {
+        stdout: context.stdout.take(),
+        stderr: context.stderr.take(),
+        error: Some(error.into()),
+        ..ExecutionStatus::default()
+    }
+}
+
+fn execute(parameters: ExecutionParameters, context: &mut KaramelCompilerContext, report: &mut CrashReport) -> ExecutionStatus {
+    let mut status = ExecutionStatus::default();
     context.execution_path = get_execution_path(&parameters.source);
+    context.teaching_mode  = parameters.teaching_mode;
+    context.count_instructions = parameters.teaching_mode || parameters.max_instructions.is_some();
+    context.instruction_limit = parameters.max_instructions;
+    context.allocation_limit = parameters.max_memory;
+    context.stdin = parameters.stdin.map(|input| Rc::new(RefCell::new(input)));
+    if let Some(permissions) = parameters.permissions {
+        context.allow_permissions(&permissions);
+    }
+    context.debug_assertions = parameters.debug_assertions;
+    context.type_guards    = parameters.type_guards;
+    if let Some(depth) = parameters.max_call_depth {
+        context.max_call_depth = depth;
+    }
+    if let Some(file_system) = parameters.file_system {
+        context.file_system = file_system;
+
+        /* Code is placed at the root of the given file system */
+        if let ExecutionSource::Code(_) = parameters.source {
+            context.execution_path.path = String::from(".");
+        }
+    }
+    context.events         = parameters.event_hook.map(VmEventTracker::new);
+    if let Some(interrupt) = parameters.interrupt {
+        context.interrupt = interrupt;
+    }
+    context.tracer         = parameters.trace.map(VmTracer::new);
+    set_print_precision(context.print_precision);
     log::debug!("Execution path: {}", context.execution_path.path);
 
     if parameters.return_output {
@@ -77,16 +305,20 @@ pub fn code_executer(parameters: ExecutionParameters) -> ExecutionStatus {
         context.stderr = Some(RefCell::new(String::new()));
     }
 
+    let is_file = matches!(parameters.source, ExecutionSource::File(_));
     let data = match parameters.source {
         ExecutionSource::Code(code) => code,
         ExecutionSource::File(filename) => {
-            match read_module_or_script(filename, &context) {
+            match read_module_or_script(filename, context) {
                 Ok(content) => content,
                 Err(error) => {
-                    write_stderr(&context, format!("Program hata ile sonlandırıldı: {}", error));
-                    log::error!("Program hata ile sonlandırıldı: {}", error);
-                    status.stdout = context.stdout;
-                    status.stderr = context.stderr;
+                    let text = messages::format("execution.failed", &[("0", &error)]);
+                    write_stderr(context, text.clone());
+                    log::error!("{}", text);
+                    write_teaching_hint(context, &error);
+                    status.stdout = context.stdout.take();
+                    status.stderr = context.stderr.take();
+                    status.error  = Some(error.into());
                     
                     status.executed = false;
                     return status
@@ -95,40 +327,92 @@ pub fn code_executer(parameters: ExecutionParameters) -> ExecutionStatus {
         }
     };
 
+    /* Dependencies of the küfe manifest next to the script */
+    if is_file {
+        if let Err(error) = crate::package::load_packages(context) {
+            let text = messages::format("execution.failed", &[("0", &error)]);
+            write_stderr(context, text.clone());
+            log::error!("{}", text);
+            write_teaching_hint(context, &error);
+            status.stdout = context.stdout.take();
+            status.stderr = context.stderr.take();
+            status.error  = Some(error.into());
+            return status
+        }
+    }
+
+    report.source = Some(data.clone());
     let mut parser = Parser::new(&data);
     match parser.parse() {
         Err(error) => {
-            write_stderr(&context, generate_error_message(&data, &error));
+            write_stderr(context, generate_error_message(&data, &error));
             log::error!("{}", generate_error_message(&data, &error));
-            status.stdout = context.stdout;
-            status.stderr = context.stderr;
+            write_teaching_hint(context, &error.error_type);
+            status.stdout = context.stdout.take();
+            status.stderr = context.stderr.take();
+            status.error  = Some(error);
 
             return status;
         },
         _ => ()
     };
 
-    let syntax = SyntaxParser::new(parser.tokens().to_vec());
-    let ast = match syntax.parse() {
+    report.tokens = Some(parser.tokens());
+    let mut syntax = SyntaxParser::new(parser.tokens().to_vec());
+    syntax.teaching_mode = context.teaching_mode;
+    let ast = match syntax.parse_with_recovery() {
         Ok(ast) => ast,
-        Err(error) => {
-            write_stderr(&context, generate_error_message(&data, &error));
-            log::error!("{}", generate_error_message(&data, &error));
-            status.stdout = context.stdout;
-            status.stderr = context.stderr;
+        Err(errors) => {
+            for error in errors.iter() {
+                write_stderr(context, generate_error_message(&data, error));
+                log::error!("{}", generate_error_message(&data, error));
+            }
+            write_teaching_hint(context, &errors[0].error_type);
+            status.stdout = context.stdout.take();
+            status.stderr = context.stderr.take();
+            status.error  = errors.first().cloned();
+            status.syntax_errors = errors;
 
             return status;
         }
     };
 
+    report.ast = Some(ast.clone());
+    let resolver_errors = resolve(&ast, &syntax.tokens, context);
+    write_warnings(context, &data, &mut status);
+
+    if let Some(error) = resolver_errors.into_iter().next() {
+        write_stderr(context, generate_error_message(&data, &error));
+        log::error!("{}", generate_error_message(&data, &error));
+        write_teaching_hint(context, &error.error_type);
+        status.stdout = context.stdout.take();
+        status.stderr = context.stderr.take();
+        status.error  = Some(error);
+
+        return status;
+    }
+
+    context.register_statement_positions(&ast, &syntax.statement_positions.borrow());
+    context.register_assert_expressions(&data, &syntax.assert_expressions.borrow());
+
     let opcode_compiler = InterpreterCompiler {};
-    let execution_status = match opcode_compiler.compile(ast.clone(), &mut context) {
-        Ok(_) => unsafe { run_vm(&mut context, parameters.dump_opcode, parameters.dump_memory) },
+    let execution_status = match opcode_compiler.compile(ast.clone(), context) {
+        Ok(_) => {
+            let result = unsafe { run_vm(context, parameters.dump_opcode, parameters.dump_memory) };
+            if let Some(events) = context.events.as_mut() {
+                events.finish();
+            }
+            status.trace = context.tracer.as_mut().map(VmTracer::finish);
+            result
+        },
         Err(message) => {
-            write_stderr(&context, format!("Program hata ile sonlandırıldı: {}", message));
-            log::error!("Program hata ile sonlandırıldı: {}", message);
-            status.stdout = context.stdout;
-            status.stderr = context.stderr;
+            let text = messages::format("execution.failed", &[("0", &message)]);
+            write_stderr(context, text.clone());
+            log::error!("{}", text);
+            write_teaching_hint(context, &message);
+            status.stdout = context.stdout.take();
+            status.stderr = context.stderr.take();
+            status.error  = Some(message.into());
 
             return status;
         }
@@ -141,24 +425,111 @@ pub fn code_executer(parameters: ExecutionParameters) -> ExecutionStatus {
             status.memory_output = Some(memory)
         },
         Err(error) => {
-            write_stderr(&context, format!("Program hata ile sonlandırıldı: {}", error));
-            log::error!("Program hata ile sonlandırıldı: {}", error);
-            status.stdout = context.stdout;
-            status.stderr = context.stderr;
+            let text = messages::format("execution.failed", &[("0", &error)]);
+            write_stderr(context, text.clone());
+            log::error!("{}", text);
+            write_teaching_hint(context, &error.error_type);
+            write_step_report(context, &mut status);
+            status.compiled = true;
+            status.stdout = context.stdout.take();
+            status.stderr = context.stderr.take();
+            status.error  = Some(error.clone().into());
+            status.runtime_error = Some(error);
 
             return status;
         }
     };
 
-    log::info!("Program başarıyla çalıştırıldı");
+    log::info!("{}", messages::text("execution.succeeded"));
+    write_step_report(context, &mut status);
     if parameters.return_opcode {
         status.opcodes = Some(parser.tokens());
     }
 
-    status.stdout      = context.stdout;
-    status.stderr      = context.stderr;
-    status.memory_dump = context.memory_dump;
-    status.opcode_dump = context.opcode_dump;
+    status.stdout      = context.stdout.take();
+    status.stderr      = context.stderr.take();
+    status.memory_dump = context.memory_dump.take();
+    status.opcode_dump = context.opcode_dump.take();
+
+    status
+}
+
+/// Compile the source code without executing it. Used by the tooling that only needs to validate the script.
+pub fn code_compiler<T: Borrow<str>>(data: T) -> Result<(), KaramelError> {
+    compile_only(data, |_| ())
+}
+
+/// Opcode table of the compiled source code.
+pub fn code_bytecode<T: Borrow<str>>(data: T) -> Result<String, KaramelError> {
+    compile_only(data, |context| context.opcode_generator.dump(&context.opcodes))
+}
+
+/// Source lines of the code, each followed by the opcodes generated for it.
+pub fn code_listing<T: Borrow<str>>(data: T) -> Result<String, KaramelError> {
+    let source = data.borrow().to_string();
+    compile_only(data, |context| context.opcode_generator.listing(&context.opcodes, &source))
+}
+
+/// Compiles the source code once, the result can be executed many times and from different threads with 'program_executer'.
+pub fn code_program<T: Borrow<str>>(data: T) -> Result<CompiledProgram, KaramelError> {
+    compile_only(data, CompiledProgram::new)?.map_err(KaramelError::from)
+}
+
+/// Executes the compiled program with a new vm on the current thread.
+pub fn program_executer(program: &CompiledProgram, return_output: bool) -> ExecutionStatus {
+    release_unused();
+    gc::reset(KARAMEL_GC_THRESHOLD);
+
+    let mut status = ExecutionStatus::default();
+    let mut context: KaramelCompilerContext = KaramelCompilerContext::new();
+    program.load(&mut context);
+    set_print_precision(context.print_precision);
+
+    if return_output {
+        context.stdout = Some(RefCell::new(String::new()));
+        context.stderr = Some(RefCell::new(String::new()));
+    }
+
+    status.compiled = true;
+    match unsafe { run_vm(&mut context, false, false) } {
+        Ok(memory) => {
+            status.executed = true;
+            status.memory_output = Some(memory);
+        },
+        Err(error) => {
+            let text = messages::format("execution.failed", &[("0", &error)]);
+            write_stderr(&context, text.clone());
+            log::error!("{}", text);
+            write_teaching_hint(&context, &error.error_type);
+            status.error = Some(error.clone().into());
+            status.runtime_error = Some(error);
+        }
+    };
 
+    write_step_report(&context, &mut status);
+    status.stdout = context.stdout.take();
+    status.stderr = context.stderr.take();
     status
 }
+
+fn compile_only<T: Borrow<str>, R, F: FnOnce(&KaramelCompilerContext) -> R>(data: T, output: F) -> Result<R, KaramelError> {
+    let mut context: KaramelCompilerContext = KaramelCompilerContext::new();
+    context.execution_path = get_execution_path(ExecutionSource::Code(data.borrow().to_string()));
+
+    let mut parser = Parser::new(data.borrow());
+    parser.parse()?;
+
+    let syntax = SyntaxParser::new(parser.tokens().to_vec());
+    let ast = syntax.parse()?;
+
+    if let Some(error) = resolve(&ast, &syntax.tokens, &mut context).into_iter().next() {
+        return Err(error);
+    }
+
+    context.register_statement_positions(&ast, &syntax.statement_positions.borrow());
+    context.register_assert_expressions(data.borrow(), &syntax.assert_expressions.borrow());
+
+    let opcode_compiler = InterpreterCompiler {};
+    opcode_compiler.compile(ast.clone(), &mut context)?;
+    Ok(output(&context))
+}
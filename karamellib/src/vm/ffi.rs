@@ -0,0 +1,312 @@
+use std::ffi::{CStr, CString, c_void};
+use std::mem;
+use std::os::raw::c_char;
+use std::rc::Rc;
+
+use crate::compiler::KaramelPrimative;
+use crate::compiler::context::KaramelCompilerContext;
+use crate::compiler::value::EMPTY_OBJECT;
+use crate::error::KaramelErrorType;
+use crate::types::VmObject;
+
+/// Functions with more arguments can not be declared. Each argument count and type combination is a separate call.
+pub const FFI_MAX_ARGUMENTS: usize = 4;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ForeignType {
+    /// 'void', only used as return type
+    Empty,
+
+    /// 'double'
+    Number,
+
+    /// 'int64_t'. 'int', 'long' and 'size_t' values fit too.
+    Integer,
+
+    /// 'const char *'. Texts are copied, the library should not keep the pointer after the call.
+    Text,
+
+    /// 'void *'. Addresses are kept as numbers.
+    Pointer
+}
+
+impl ForeignType {
+    pub fn from_name(name: &str) -> Option<ForeignType> {
+        match name {
+            "boş" | "bos" => Some(ForeignType::Empty),
+            "sayı" | "sayi" => Some(ForeignType::Number),
+            "tamsayı" | "tamsayi" => Some(ForeignType::Integer),
+            "yazı" | "yazi" => Some(ForeignType::Text),
+            "işaretçi" | "isaretci" => Some(ForeignType::Pointer),
+            _ => None
+        }
+    }
+}
+
+pub struct ForeignFunction {
+    pub name: String,
+    pub arguments: Vec<ForeignType>,
+    pub return_type: ForeignType,
+    pointer: *const c_void,
+
+    /* Library is not closed while its functions can be called */
+    _library: Rc<ForeignLibrary>
+}
+
+/// Libraries and functions that are declared by the 'ffi' module. Handles of the scripts are the indexes.
+#[derive(Default)]
+pub struct ForeignInterface {
+    libraries: Vec<Rc<ForeignLibrary>>,
+    functions: Vec<ForeignFunction>
+}
+
+/* Values that are passed with the registers. Integers, texts and pointers use the same registers. */
+#[derive(Clone, Copy)]
+enum ForeignValue {
+    Integer(i64),
+    Number(f64)
+}
+
+pub fn load_library(context: &KaramelCompilerContext, path: &str) -> Result<usize, KaramelErrorType> {
+    let library = ForeignLibrary::open(path).map_err(|error| KaramelErrorType::ForeignLibraryNotLoaded { path: path.to_string(), error })?;
+    let mut interface = context.foreign.borrow_mut();
+    interface.libraries.push(Rc::new(library));
+    Ok(interface.libraries.len() - 1)
+}
+
+pub fn declare_function(context: &KaramelCompilerContext, library: usize, name: &str, arguments: Vec<ForeignType>, return_type: ForeignType) -> Result<usize, KaramelErrorType> {
+    if arguments.len() > FFI_MAX_ARGUMENTS {
+        return Err(KaramelErrorType::ForeignTypeNotSupported(format!("{} parametre", arguments.len())));
+    }
+
+    if let Some(argument) = arguments.iter().find(|argument| **argument == ForeignType::Empty) {
+        return Err(KaramelErrorType::ForeignTypeNotSupported(format!("{:?}", argument)));
+    }
+
+    let mut interface = context.foreign.borrow_mut();
+    let library = interface.libraries.get(library).cloned().ok_or_else(|| KaramelErrorType::ForeignFunctionNotFound(name.to_string()))?;
+    let pointer = library.symbol(name).ok_or_else(|| KaramelErrorType::ForeignFunctionNotFound(name.to_string()))?;
+
+    interface.functions.push(ForeignFunction {
+        name: name.to_string(),
+        arguments,
+        return_type,
+        pointer,
+        _library: library
+    });
+    Ok(interface.functions.len() - 1)
+}
+
+fn expected_type(function: &ForeignFunction, expected: &str) -> KaramelErrorType {
+    KaramelErrorType::FunctionExpectedThatParameterType {
+        function: function.name.to_string(),
+        expected: expected.to_string()
+    }
+}
+
+/// Converts the values to the declared types, calls the function and converts the result back.
+pub fn call_function(context: &KaramelCompilerContext, function: usize, arguments: &[VmObject]) -> Result<VmObject, KaramelErrorType> {
+    let interface = context.foreign.borrow();
+    let function = interface.functions.get(function).ok_or_else(|| KaramelErrorType::ForeignFunctionNotFound(function.to_string()))?;
+
+    if function.arguments.len() != arguments.len() {
+        return Err(KaramelErrorType::FunctionArgumentNotMatching {
+            function: function.name.to_string(),
+            expected: function.arguments.len() as u8,
+            found: arguments.len() as u8
+        });
+    }
+
+    /* Texts should live until the call returns */
+    let mut texts = Vec::new();
+    let mut values = Vec::with_capacity(arguments.len());
+    for (argument_type, argument) in function.arguments.iter().zip(arguments.iter()) {
+        let argument = argument.deref();
+        values.push(match (argument_type, &*argument) {
+            (ForeignType::Number, KaramelPrimative::Number(number)) => ForeignValue::Number(*number),
+            (ForeignType::Integer, KaramelPrimative::Number(number)) if number.fract() == 0.0 => ForeignValue::Integer(*number as i64),
+            (ForeignType::Pointer, KaramelPrimative::Number(number)) if number.fract() == 0.0 && *number >= 0.0 => ForeignValue::Integer(*number as i64),
+            (ForeignType::Pointer, KaramelPrimative::Empty) | (ForeignType::Text, KaramelPrimative::Empty) => ForeignValue::Integer(0),
+            (ForeignType::Text, KaramelPrimative::Text(text)) => {
+                let text = CString::new(text.as_bytes()).map_err(|_| expected_type(function, "Yazı"))?;
+                let pointer = text.as_ptr() as i64;
+                texts.push(text);
+                ForeignValue::Integer(pointer)
+            },
+            (ForeignType::Number, _) => return Err(expected_type(function, "Sayı")),
+            (ForeignType::Integer, _) | (ForeignType::Pointer, _) => return Err(expected_type(function, "Tam sayı")),
+            (ForeignType::Text, _) => return Err(expected_type(function, "Yazı")),
+            (ForeignType::Empty, _) => return Err(KaramelErrorType::ForeignTypeNotSupported(format!("{:?}", argument_type)))
+        });
+    }
+
+    let result = unsafe {
+        match function.return_type {
+            ForeignType::Empty => invoke::<()>(function.pointer, &values).map(|_| EMPTY_OBJECT),
+            ForeignType::Number => invoke::<f64>(function.pointer, &values).map(VmObject::from),
+            ForeignType::Integer | ForeignType::Pointer => invoke::<i64>(function.pointer, &values).map(|value| VmObject::from(value as f64)),
+            ForeignType::Text => invoke::<*const c_char>(function.pointer, &values).map(|value| match value.is_null() {
+                true => EMPTY_OBJECT,
+                false => VmObject::from(CStr::from_ptr(value).to_string_lossy().into_owned())
+            })
+        }
+    };
+
+    drop(texts);
+    result.ok_or_else(|| KaramelErrorType::ForeignTypeNotSupported(format!("{} parametre", arguments.len())))
+}
+
+/* Function is called with the C calling convention of the platform. Signature is written for each type combination, the compiler places the values to the right registers. */
+unsafe fn invoke<R>(pointer: *const c_void, values: &[ForeignValue]) -> Option<R> {
+    use ForeignValue::{Integer as I, Number as N};
+
+    macro_rules! call {
+        ($($value:ident: $type:ty),*) => { mem::transmute::<*const c_void, extern "C" fn($($type),*) -> R>(pointer)($(*$value),*) };
+    }
+
+    Some(match values {
+        [] => call!(),
+        [I(a)] => call!(a: i64),
+        [N(a)] => call!(a: f64),
+        [I(a), I(b)] => call!(a: i64, b: i64),
+        [I(a), N(b)] => call!(a: i64, b: f64),
+        [N(a), I(b)] => call!(a: f64, b: i64),
+        [N(a), N(b)] => call!(a: f64, b: f64),
+        [I(a), I(b), I(c)] => call!(a: i64, b: i64, c: i64),
+        [I(a), I(b), N(c)] => call!(a: i64, b: i64, c: f64),
+        [I(a), N(b), I(c)] => call!(a: i64, b: f64, c: i64),
+        [I(a), N(b), N(c)] => call!(a: i64, b: f64, c: f64),
+        [N(a), I(b), I(c)] => call!(a: f64, b: i64, c: i64),
+        [N(a), I(b), N(c)] => call!(a: f64, b: i64, c: f64),
+        [N(a), N(b), I(c)] => call!(a: f64, b: f64, c: i64),
+        [N(a), N(b), N(c)] => call!(a: f64, b: f64, c: f64),
+        [I(a), I(b), I(c), I(d)] => call!(a: i64, b: i64, c: i64, d: i64),
+        [I(a), I(b), I(c), N(d)] => call!(a: i64, b: i64, c: i64, d: f64),
+        [I(a), I(b), N(c), I(d)] => call!(a: i64, b: i64, c: f64, d: i64),
+        [I(a), I(b), N(c), N(d)] => call!(a: i64, b: i64, c: f64, d: f64),
+        [I(a), N(b), I(c), I(d)] => call!(a: i64, b: f64, c: i64, d: i64),
+        [I(a), N(b), I(c), N(d)] => call!(a: i64, b: f64, c: i64, d: f64),
+        [I(a), N(b), N(c), I(d)] => call!(a: i64, b: f64, c: f64, d: i64),
+        [I(a), N(b), N(c), N(d)] => call!(a: i64, b: f64, c: f64, d: f64),
+        [N(a), I(b), I(c), I(d)] => call!(a: f64, b: i64, c: i64, d: i64),
+        [N(a), I(b), I(c), N(d)] => call!(a: f64, b: i64, c: i64, d: f64),
+        [N(a), I(b), N(c), I(d)] => call!(a: f64, b: i64, c: f64, d: i64),
+        [N(a), I(b), N(c), N(d)] => call!(a: f64, b: i64, c: f64, d: f64),
+        [N(a), N(b), I(c), I(d)] => call!(a: f64, b: f64, c: i64, d: i64),
+        [N(a), N(b), I(c), N(d)] => call!(a: f64, b: f64, c: i64, d: f64),
+        [N(a), N(b), N(c), I(d)] => call!(a: f64, b: f64, c: f64, d: i64),
+        [N(a), N(b), N(c), N(d)] => call!(a: f64, b: f64, c: f64, d: f64),
+        _ => return None
+    })
+}
+
+struct ForeignLibrary {
+    handle: *mut c_void
+}
+
+#[cfg(unix)]
+impl ForeignLibrary {
+    fn open(path: &str) -> Result<ForeignLibrary, String> {
+        let name = CString::new(path).map_err(|error| error.to_string())?;
+        let handle = unsafe { libc::dlopen(name.as_ptr(), libc::RTLD_NOW | libc::RTLD_LOCAL) };
+        match handle.is_null() {
+            true => Err(unsafe { Self::last_error() }),
+            false => Ok(ForeignLibrary { handle })
+        }
+    }
+
+    fn symbol(&self, name: &str) -> Option<*const c_void> {
+        let name = CString::new(name).ok()?;
+        let pointer = unsafe { libc::dlsym(self.handle, name.as_ptr()) };
+        match pointer.is_null() {
+            true => None,
+            false => Some(pointer as *const c_void)
+        }
+    }
+
+    unsafe fn last_error() -> String {
+        let error = libc::dlerror();
+        match error.is_null() {
+            true => String::new(),
+            false => CStr::from_ptr(error).to_string_lossy().into_owned()
+        }
+    }
+}
+
+#[cfg(unix)]
+impl Drop for ForeignLibrary {
+    fn drop(&mut self) {
+        unsafe { libc::dlclose(self.handle) };
+    }
+}
+
+#[cfg(windows)]
+extern "system" {
+    fn LoadLibraryW(name: *const u16) -> *mut c_void;
+    fn GetProcAddress(module: *mut c_void, name: *const c_char) -> *const c_void;
+    fn FreeLibrary(module: *mut c_void) -> i32;
+    fn GetLastError() -> u32;
+}
+
+#[cfg(windows)]
+impl ForeignLibrary {
+    fn open(path: &str) -> Result<ForeignLibrary, String> {
+        let name: Vec<u16> = path.encode_utf16().chain(std::iter::once(0)).collect();
+        let handle = unsafe { LoadLibraryW(name.as_ptr()) };
+        match handle.is_null() {
+            true => Err(format!("Windows hata kodu {}", unsafe { GetLastError() })),
+            false => Ok(ForeignLibrary { handle })
+        }
+    }
+
+    fn symbol(&self, name: &str) -> Option<*const c_void> {
+        let name = CString::new(name).ok()?;
+        let pointer = unsafe { GetProcAddress(self.handle, name.as_ptr()) };
+        match pointer.is_null() {
+            true => None,
+            false => Some(pointer)
+        }
+    }
+}
+
+#[cfg(windows)]
+impl Drop for ForeignLibrary {
+    fn drop(&mut self) {
+        unsafe { FreeLibrary(self.handle) };
+    }
+}
+
+#[cfg(all(test, target_os = "linux"))]
+mod tests {
+    use crate::compiler::context::KaramelCompilerContext;
+    use crate::error::KaramelErrorType;
+    use crate::types::VmObject;
+    use super::{ForeignType, call_function, declare_function, load_library};
+
+    #[test]
+    fn call_math_functions() {
+        let context = KaramelCompilerContext::new();
+        let library = load_library(&context, "libm.so.6").unwrap();
+
+        let pow = declare_function(&context, library, "pow", vec![ForeignType::Number, ForeignType::Number], ForeignType::Number).unwrap();
+        let result = call_function(&context, pow, &[VmObject::from(2.0), VmObject::from(10.0)]).unwrap();
+        assert_eq!(result.as_number(), Some(1024.0));
+
+        let ldexp = declare_function(&context, library, "ldexp", vec![ForeignType::Number, ForeignType::Integer], ForeignType::Number).unwrap();
+        let result = call_function(&context, ldexp, &[VmObject::from(3.0), VmObject::from(2.0)]).unwrap();
+        assert_eq!(result.as_number(), Some(12.0));
+    }
+
+    #[test]
+    fn errors() {
+        let context = KaramelCompilerContext::new();
+        assert!(matches!(load_library(&context, "olmayan_kutuphane.so"), Err(KaramelErrorType::ForeignLibraryNotLoaded { .. })));
+
+        let library = load_library(&context, "libm.so.6").unwrap();
+        assert_eq!(declare_function(&context, library, "olmayan", Vec::new(), ForeignType::Empty), Err(KaramelErrorType::ForeignFunctionNotFound("olmayan".to_string())));
+
+        let cos = declare_function(&context, library, "cos", vec![ForeignType::Number], ForeignType::Number).unwrap();
+        assert!(call_function(&context, cos, &[VmObject::from("bir".to_string())]).is_err());
+        assert!(call_function(&context, cos, &[]).is_err());
+    }
+}
@@ -1,18 +1,24 @@
 use crate::compiler::context::KaramelCompilerContext;
-use crate::compiler::scope::Scope;
-use crate::error::KaramelErrorType;
+use crate::compiler::scope::{Scope, ErrorHandler};
+use crate::buildin::class::error::ErrorObject;
+use crate::error::{KaramelErrorType, KaramelRuntimeError};
+use crate::constants::{KARAMEL_TEACHING_LOOP_LIMIT, KARAMEL_HIDDEN_VARIABLE_PREFIX, KARAMEL_MAX_TEXT_SIZE};
 use crate::logger::write_stdout;
 use crate::{pop, inc_memory_index, dec_memory_index, get_memory_index, karamel_dbg};
 use crate::types::{VmObject};
+use crate::gc;
+use crate::vm::trace::TraceRecord;
 use crate::compiler::*;
+use crate::compiler::semantics::deep_equal;
 use std::rc::Rc;
-use std::mem;
 use std::collections::HashMap;
 use std::io::stdout;
 use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::{AtomicBool, Ordering as AtomicOrdering};
 use log_update::LogUpdate;
 use std::io::{self, Write};
 use std::ptr;
+use std::cmp::Ordering;
 use colored::*;
 use crate::buildin::ClassProperty;
 
@@ -41,7 +47,79 @@ pub unsafe fn dump_opcode<W: Write>(index: usize, context: &mut KaramelCompilerC
     }
 }
 
-pub unsafe fn run_vm(context: &mut KaramelCompilerContext, dump_code: bool, dump_memory: bool) -> Result<Vec<VmObject>, KaramelErrorType>
+unsafe fn notify_variable_change(context: &mut KaramelCompilerContext, location: usize) {
+    let scope = &*context.current_scope;
+    let value = *scope.top_stack.add(location);
+
+    if let (Some(events), Some(name)) = (context.events.as_mut(), context.storages[scope.storage_index].variables.get(location)) {
+        if !name.starts_with(KARAMEL_HIDDEN_VARIABLE_PREFIX) {
+            events.variable_changed(name, value);
+        }
+    }
+}
+
+/* Collections run between the opcodes, every value in use is at the stack or at the constants */
+#[inline]
+unsafe fn collect_garbage(context: &KaramelCompilerContext) {
+    if gc::should_collect() {
+        context.collect_garbage();
+    }
+}
+
+/* Index of the current opcode */
+#[inline]
+unsafe fn opcode_index(context: &KaramelCompilerContext) -> usize {
+    context.opcodes_ptr.offset_from(context.opcodes.as_ptr()) as usize
+}
+
+/* 16 bit value after the opcode, low byte first */
+#[inline]
+unsafe fn read_u16(context: &KaramelCompilerContext) -> usize {
+    (*context.opcodes_ptr.offset(2) as usize * 256) + *context.opcodes_ptr.offset(1) as usize
+}
+
+/* 32 bit signed offset after the wide jump opcodes */
+#[inline]
+unsafe fn read_offset(context: &KaramelCompilerContext) -> isize {
+    i32::from_le_bytes([*context.opcodes_ptr.offset(1), *context.opcodes_ptr.offset(2), *context.opcodes_ptr.offset(3), *context.opcodes_ptr.offset(4)]) as isize
+}
+
+/* Jump location of the opcode. Wide opcodes keep the distance from the opcode. */
+#[inline]
+unsafe fn jump_location(context: &KaramelCompilerContext, wide: bool) -> usize {
+    match wide {
+        true => (opcode_index(context) as isize + read_offset(context)) as usize,
+        false => read_u16(context)
+    }
+}
+
+/* Size of the jump location after the opcode */
+#[inline]
+fn jump_size(wide: bool) -> usize {
+    match wide {
+        true => 4,
+        false => 2
+    }
+}
+
+/* 8 bit location of the opcode or 16 bit location of the wide opcode. Opcode pointer is moved to the last byte of the location. */
+#[inline]
+unsafe fn read_location(context: &mut KaramelCompilerContext, wide: bool) -> usize {
+    match wide {
+        true => {
+            let location = (*context.opcodes_ptr.offset(2) as usize * 256) + *context.opcodes_ptr.offset(1) as usize;
+            context.opcodes_ptr = context.opcodes_ptr.offset(2);
+            location
+        },
+        false => {
+            let location = *context.opcodes_ptr.offset(1) as usize;
+            context.opcodes_ptr = context.opcodes_ptr.offset(1);
+            location
+        }
+    }
+}
+
+pub unsafe fn run_vm(context: &mut KaramelCompilerContext, dump_code: bool, dump_memory: bool) -> Result<Vec<VmObject>, KaramelRuntimeError>
 {
     #[cfg(any(feature = "liveOpcodeView", feature = "dumpOpcodes"))]
     let mut log_update = LogUpdate::new(stdout()).unwrap();
@@ -56,439 +134,1050 @@ pub unsafe fn run_vm(context: &mut KaramelCompilerContext, dump_code: bool, dump
         //log_update.render(&generated[..]);
     }
 
+    let top_stack = start_main_scope(context);
+    {
+        run_until_halt(context)?;
+        
+        if dump_memory {
+            let dump = context.storages[0].dump();
+            context.memory_dump = Some(dump);
+        }
+    }
+    
+    let mut result = Vec::with_capacity(get_memory_index!(context) as usize);
+    for index in 0..get_memory_index!(context) {
+        result.push(*top_stack.add(context.storages[0].variables.len() + index as usize));
+    }
+
+    Ok(result)
+}
+
+/* Errors are sent to the nearest 'dene' block and the execution continues from its catch block.
+   Call stack is kept while the deferred blocks are running, the error comes back from a closer scope after them. */
+unsafe fn run_until_halt(context: &mut KaramelCompilerContext) -> Result<(), KaramelRuntimeError> {
+    let mut error_frames = None;
+    while let Err(error) = execute_opcodes(context) {
+        let frames = error_frames.take().unwrap_or_else(|| context.call_stack());
+        match catch_error(context, error) {
+            Ok(()) => if (*context.current_scope).defer_error.is_some() {
+                error_frames = Some(frames);
+            },
+            Err(error) => return Err(KaramelRuntimeError::new(error, frames))
+        };
+    }
+    Ok(())
+}
+
+/* Main code scope at the start of the stack. Returns the top of the stack. */
+unsafe fn start_main_scope(context: &mut KaramelCompilerContext) -> *mut VmObject {
     // Save top stack for main storage
     let top_stack = context.stack.as_mut_ptr();
 
     // Move stack pointer to forward. First slots are reserved for variable memories.
     context.stack_ptr = top_stack.add(context.storages[0].variables.len());
     context.storages_ptr = context.storages.as_mut_ptr();
-    {
-        context.scopes[context.scope_index] = Scope {
-            location: ptr::null_mut(),
-            call_return_assign_to_temp: false,
-            top_stack: top_stack,
-            constant_ptr: context.storages[0].constants.as_ptr()
+
+    /* Teaching mode stops the endless loops with a friendly message */
+    context.loop_iterations = 0;
+    context.scopes[context.scope_index] = Scope {
+        location: ptr::null_mut(),
+        call_return_assign_to_temp: false,
+        top_stack: top_stack,
+        constant_ptr: context.storages[0].constants.as_ptr(),
+        storage_index: 0,
+        function: ptr::null(),
+        deferred: Vec::new(),
+        defer_return: ptr::null_mut(),
+        defer_stack: ptr::null_mut(),
+        defer_error: None
+    };
+
+    context.error_handlers.clear();
+    top_stack
+}
+
+/// Calls the function with the arguments instead of running the main code. Context should be loaded with the program of the function.
+/// Call opcodes are added after the program, so the function returns to them and the execution stops.
+pub unsafe fn run_function(context: &mut KaramelCompilerContext, function: VmObject, arguments: &[VmObject]) -> Result<VmObject, KaramelRuntimeError> {
+    if arguments.len() > u8::MAX as usize {
+        return Err(KaramelErrorType::TooManyArguments(u8::MAX as usize).into());
+    }
+
+    let start = context.opcodes.len();
+    context.opcodes.extend_from_slice(&[VmOpCode::CallStack.into(), arguments.len() as u8, 1, VmOpCode::Halt.into()]);
+    context.opcodes_top_ptr = context.opcodes.as_mut_ptr();
+    context.opcodes_ptr = context.opcodes_top_ptr.add(start);
+
+    start_main_scope(context);
+    for argument in arguments.iter() {
+        *context.stack_ptr = *argument;
+        inc_memory_index!(context, 1);
+    }
+    *context.stack_ptr = function;
+    inc_memory_index!(context, 1);
+
+    run_until_halt(context)?;
+    Ok(*context.stack_ptr.sub(1))
+}
+
+/* What the dispatch loop does after the opcode handler */
+enum Flow {
+    /* Opcode pointer is at the last byte of the opcode, execution continues with the next byte */
+    Next,
+
+    /* Opcode pointer is already moved to the next opcode */
+    Jumped,
+    Halt
+}
+
+type OpcodeHandler = unsafe fn(&mut KaramelCompilerContext) -> Result<Flow, KaramelErrorType>;
+
+/* Handlers are indexed with the opcode byte. Unused bytes are reported as invalid opcode instead of being decoded. */
+static DISPATCH_TABLE: [OpcodeHandler; 256] = dispatch_table();
+
+const fn dispatch_table() -> [OpcodeHandler; 256] {
+    let mut table = [invalid_opcode as OpcodeHandler; 256];
+    table[VmOpCode::Addition as usize]          = addition;
+    table[VmOpCode::Subraction as usize]        = subraction;
+    table[VmOpCode::Multiply as usize]          = multiply;
+    table[VmOpCode::Division as usize]          = division;
+    table[VmOpCode::Module as usize]            = module;
+    table[VmOpCode::And as usize]               = and;
+    table[VmOpCode::Or as usize]                = or;
+    table[VmOpCode::Equal as usize]             = equal;
+    table[VmOpCode::NotEqual as usize]          = not_equal;
+    table[VmOpCode::GreaterThan as usize]       = greater_than;
+    table[VmOpCode::GreaterEqualThan as usize]  = greater_equal_than;
+    table[VmOpCode::Call as usize]              = call;
+    table[VmOpCode::CallStack as usize]         = call_stack;
+    table[VmOpCode::Return as usize]            = function_return;
+    table[VmOpCode::Increment as usize]         = increment;
+    table[VmOpCode::Decrement as usize]         = decrement;
+    table[VmOpCode::Not as usize]               = not;
+    table[VmOpCode::Compare as usize]           = |context| unsafe { compare(context, false) };
+    table[VmOpCode::CompareWide as usize]       = |context| unsafe { compare(context, true) };
+    table[VmOpCode::Jump as usize]              = |context| unsafe { jump(context, false) };
+    table[VmOpCode::JumpWide as usize]          = |context| unsafe { jump(context, true) };
+    table[VmOpCode::Init as usize]              = init;
+    table[VmOpCode::Load as usize]              = |context| unsafe { load(context, false) };
+    table[VmOpCode::LoadWide as usize]          = |context| unsafe { load(context, true) };
+    table[VmOpCode::Store as usize]             = |context| unsafe { store(context, false) };
+    table[VmOpCode::StoreWide as usize]         = |context| unsafe { store(context, true) };
+    table[VmOpCode::FastStore as usize]         = fast_store;
+    table[VmOpCode::CopyToStore as usize]       = |context| unsafe { copy_to_store(context, false) };
+    table[VmOpCode::CopyToStoreWide as usize]   = |context| unsafe { copy_to_store(context, true) };
+    table[VmOpCode::Dublicate as usize]         = dublicate;
+    table[VmOpCode::DublicatePair as usize]     = dublicate_pair;
+    table[VmOpCode::GetItem as usize]           = get_item;
+    table[VmOpCode::GetMember as usize]         = get_member;
+    table[VmOpCode::SetItem as usize]           = set_item;
+    table[VmOpCode::Constant as usize]          = |context| unsafe { constant(context, false) };
+    table[VmOpCode::ConstantWide as usize]      = |context| unsafe { constant(context, true) };
+    table[VmOpCode::Halt as usize]              = halt;
+    table[VmOpCode::LoopCounter as usize]       = loop_counter;
+    table[VmOpCode::Line as usize]              = line;
+    table[VmOpCode::TryStart as usize]          = |context| unsafe { try_start(context, false) };
+    table[VmOpCode::TryStartWide as usize]      = |context| unsafe { try_start(context, true) };
+    table[VmOpCode::TryEnd as usize]            = |context| unsafe { try_end(context, false) };
+    table[VmOpCode::TryEndWide as usize]        = |context| unsafe { try_end(context, true) };
+    table[VmOpCode::Throw as usize]             = throw;
+    table[VmOpCode::Defer as usize]             = |context| unsafe { defer(context, false) };
+    table[VmOpCode::DeferWide as usize]         = |context| unsafe { defer(context, true) };
+    table[VmOpCode::DeferEnd as usize]          = defer_end;
+    table[VmOpCode::WithStart as usize]         = with_start;
+    table[VmOpCode::WithEnd as usize]           = |context| unsafe { with_end(context, false) };
+    table[VmOpCode::WithEndWide as usize]       = |context| unsafe { with_end(context, true) };
+    table[VmOpCode::Assume as usize]            = assume;
+    table[VmOpCode::TypeGuard as usize]         = type_guard;
+    table[VmOpCode::JumpIfFalse as usize]       = |context| unsafe { conditional_jump(context, false, false) };
+    table[VmOpCode::JumpIfTrue as usize]        = |context| unsafe { conditional_jump(context, true, false) };
+    table[VmOpCode::JumpIfFalseWide as usize]   = |context| unsafe { conditional_jump(context, false, true) };
+    table[VmOpCode::JumpIfTrueWide as usize]    = |context| unsafe { conditional_jump(context, true, true) };
+    table
+}
+
+/* Limit is only given to the sandboxed executions, they count the instructions for it */
+#[inline(always)]
+fn count_instruction(context: &mut KaramelCompilerContext) -> Result<(), KaramelErrorType> {
+    context.instruction_count += 1;
+    match context.instruction_limit {
+        Some(limit) if context.instruction_count > limit => Err(KaramelErrorType::InstructionLimitExceeded(limit)),
+        _ => Ok(())
+    }
+}
+
+unsafe fn execute_opcodes(context: &mut KaramelCompilerContext) -> Result<(), KaramelErrorType> {
+    /* Tracing has its own loop, so the normal execution does not check it for each instruction */
+    if context.tracer.is_some() {
+        return execute_traced_opcodes(context);
+    }
+
+    #[cfg(all(feature = "liveOpcodeView"))]
+    let mut log_update = LogUpdate::new(stdout()).unwrap();
+
+    /* Flag stays alive with the context, so the pointer is read once */
+    let interrupt: *const AtomicBool = &*context.interrupt;
+    loop {
+        if (*interrupt).load(AtomicOrdering::Relaxed) {
+            return Err(KaramelErrorType::Interrupted);
+        }
+        if context.count_instructions {
+            count_instruction(context)?;
+        }
+        #[cfg(all(feature = "liveOpcodeView"))] {
+            dump_opcode(context.opcode_index, context, &mut log_update);
+        }
+
+        match DISPATCH_TABLE[*context.opcodes_ptr as usize](context)? {
+            Flow::Next => context.opcodes_ptr = context.opcodes_ptr.offset(1),
+            Flow::Jumped => (),
+            Flow::Halt => return Ok(())
         };
+    }
+}
 
-        loop {
-            let opcode = mem::transmute::<u8, VmOpCode>(*context.opcodes_ptr);
-            #[cfg(all(feature = "liveOpcodeView"))] {
-                dump_opcode(context.opcode_index, context, &mut log_update);
-            }
-            
-            match karamel_dbg_any!(opcode) {
-                VmOpCode::Subraction => {
-                    let right = pop_raw!(context, "right");
-                    let left = pop_raw!(context, "left");
-
-                    karamel_print_level2!("Subraction: {:?} - {:?}", left, right);
-
-                    *context.stack_ptr = match (left.as_number(), right.as_number()) {
-                        (Some(l_value),  Some(r_value))   => VmObject::from(karamel_dbg!(l_value) - karamel_dbg!(r_value)),
-                        _ => EMPTY_OBJECT
-                    };
-                    inc_memory_index!(context, 1);
-                    dump_data!(context, "result");
-                },
+unsafe fn execute_traced_opcodes(context: &mut KaramelCompilerContext) -> Result<(), KaramelErrorType> {
+    loop {
+        if context.interrupt.load(AtomicOrdering::Relaxed) {
+            return Err(KaramelErrorType::Interrupted);
+        }
+        if context.count_instructions {
+            count_instruction(context)?;
+        }
 
-                VmOpCode::Addition => {
-                    let right = pop_raw!(context, "right");
-                    let left = pop_raw!(context, "left");
-                    karamel_print_level2!("Addition: {:?} + {:?}", left, right);
-
-                    *context.stack_ptr = match (&left.deref_clean(), &right.deref_clean()) {
-                        (KaramelPrimative::Number(l_value),  KaramelPrimative::Number(r_value)) => VmObject::from(karamel_dbg!(l_value) + karamel_dbg!(r_value)),
-                        (KaramelPrimative::Text(l_value),    KaramelPrimative::Text(r_value))   => VmObject::from(Rc::new((&**l_value).to_owned() + &**r_value)),
-                        _ => EMPTY_OBJECT
-                    };
-                    dump_data!(context, "result");
-                    inc_memory_index!(context, 1);
-                },
+        let start = context.opcodes_ptr;
+        let stack_before = get_memory_index!(context) as usize;
+        let flow = DISPATCH_TABLE[*start as usize](context)?;
 
-                VmOpCode::Load => {
-                    let tmp   = *context.opcodes_ptr.offset(1) as usize;
-                    let scope = &mut *context.current_scope;
-                    *context.stack_ptr = karamel_dbg!(*scope.top_stack.offset(tmp as isize));
-                    context.opcodes_ptr = context.opcodes_ptr.offset(1);
-                    karamel_print_level2!("Load: [{:?}]: {:?}", tmp, *context.stack_ptr);
-                    dump_data!(context, "loaded");
-                    inc_memory_index!(context, 1);
-                },
+        let opcode = VmByte(*start).decode_opcode();
+        let operands = std::slice::from_raw_parts(start.add(1), opcode.operand_size()).to_vec();
 
-                VmOpCode::Constant => {
-                    let tmp   = *context.opcodes_ptr.offset(1) as usize;
-                    let scope = &mut *context.current_scope;        
-                    *context.stack_ptr = karamel_dbg!(*scope.constant_ptr.offset(tmp as isize));        
-                    context.opcodes_ptr = context.opcodes_ptr.offset(1);
-                    karamel_print_level2!("Constant: [{:?}]: {:?}", tmp, *context.stack_ptr);
-                    dump_data!(context, "constant loaded");
-                    inc_memory_index!(context, 1);
-                },
+        if let Flow::Next = flow {
+            context.opcodes_ptr = context.opcodes_ptr.offset(1);
+        }
 
-                VmOpCode::Store => {
-                    let tmp = *context.opcodes_ptr.offset(1) as usize;
-                    dec_memory_index!(context, 1);
-                    *(*context.current_scope).top_stack.offset(tmp as isize) = karamel_dbg!(*context.stack_ptr);
-                    context.opcodes_ptr = context.opcodes_ptr.offset(1);
-                    karamel_print_level2!("Store: [{:?}]: {:?}", tmp, *context.stack_ptr);
-                },
+        let stack_after = get_memory_index!(context) as usize;
+        let location = start.offset_from(context.opcodes.as_ptr()) as usize;
+        let record = TraceRecord {
+            location,
+            line: context.opcode_generator.find_line(location),
+            opcode: opcode.to_string(),
+            operands,
+            stack_before,
+            stack_after,
+            top: match stack_after {
+                0 => None,
+                _ => Some(format!("{:?}", (*context.stack_ptr.sub(1)).deref()))
+            },
+            next: context.opcodes_ptr.offset_from(context.opcodes.as_ptr()) as usize
+        };
 
-                VmOpCode::CopyToStore => {
-                    let tmp = *context.opcodes_ptr.offset(1) as usize;
-                    *(*context.current_scope).top_stack.offset(tmp as isize) = karamel_dbg!(*context.stack_ptr.sub(1));
-                    context.opcodes_ptr = context.opcodes_ptr.offset(1);
-                    karamel_print_level2!("CopyToStore: [{:?}]: {:?}", tmp, *context.stack_ptr);
-                },
+        if let Some(tracer) = context.tracer.as_mut() {
+            tracer.record(record);
+        }
 
-                VmOpCode::FastStore => {
-                    let destination = *context.opcodes_ptr.offset(1) as usize;
-                    let source      = *context.opcodes_ptr.offset(2) as usize;
-                    *(*context.current_scope).top_stack.offset(destination as isize) = karamel_dbg!(*(*context.current_scope).constant_ptr.offset(source as isize));
-                    context.opcodes_ptr = context.opcodes_ptr.offset(2);
-                    karamel_print_level2!("FastStore: {:?}: {:?} => {:?}", *(*context.current_scope).top_stack.offset(destination as isize), source, destination);
-                },
+        if let Flow::Halt = flow {
+            return Ok(());
+        }
+    }
+}
 
-                VmOpCode::Not => {
-                    *context.stack_ptr.sub(1) = VmObject::from(!(*context.stack_ptr.sub(1)).deref_clean().is_true());
-                    dump_data!(context, "result");
-                    karamel_print_level2!("Not: {:?}", *context.stack_ptr.sub(1));
-                },
+unsafe fn invalid_opcode(context: &mut KaramelCompilerContext) -> Result<Flow, KaramelErrorType> {
+    Err(KaramelErrorType::GeneralError(format!("Geçersiz komut: {}", *context.opcodes_ptr)))
+}
 
-                VmOpCode::Dublicate => {
-                    *context.stack_ptr = karamel_dbg!(*context.stack_ptr.sub(1));
-                    karamel_print_level2!("Dublicate: {:?}", *context.stack_ptr);
-                    inc_memory_index!(context, 1);
-                },
+unsafe fn subraction(context: &mut KaramelCompilerContext) -> Result<Flow, KaramelErrorType> {
+    let right = pop_raw!(context, "right");
+    let left = pop_raw!(context, "left");
 
-                VmOpCode::And => {
-                    let left  = pop!(context, "left");
-                    let right = pop!(context, "right");
-                    karamel_print_level2!("And: {:?} && {:?}", left, right);
+    karamel_print_level2!("Subraction: {:?} - {:?}", left, right);
 
-                    *context.stack_ptr = VmObject::from(karamel_dbg!(left.is_true()) && karamel_dbg!(right.is_true()));
-                    dump_data!(context, "result");
-                    inc_memory_index!(context, 1);
-                },
+    *context.stack_ptr = match (left.as_number(), right.as_number()) {
+        (Some(l_value),  Some(r_value))   => VmObject::from(karamel_dbg!(l_value) - karamel_dbg!(r_value)),
+        _ => EMPTY_OBJECT
+    };
+    inc_memory_index!(context, 1);
+    dump_data!(context, "result");
+    Ok(Flow::Next)
+}
 
-                VmOpCode::Or => {
-                    let left  = pop!(context, "left");
-                    let right = pop!(context, "right");
-                    karamel_print_level2!("Or: {:?} || {:?}", left, right);
+unsafe fn addition(context: &mut KaramelCompilerContext) -> Result<Flow, KaramelErrorType> {
+    let right = pop_raw!(context, "right");
+    let left = pop_raw!(context, "left");
+    karamel_print_level2!("Addition: {:?} + {:?}", left, right);
 
-                    *context.stack_ptr = VmObject::from(karamel_dbg!(left.is_true()) || karamel_dbg!(right.is_true()));
-                    dump_data!(context, "result");
-                    inc_memory_index!(context, 1);
-                },
+    /* Numbers do not need a primative */
+    if let (Some(l_value), Some(r_value)) = (left.as_number(), right.as_number()) {
+        *context.stack_ptr = VmObject::from(karamel_dbg!(l_value) + karamel_dbg!(r_value));
+        inc_memory_index!(context, 1);
+        return Ok(Flow::Next);
+    }
 
-                VmOpCode::Multiply => {
-                    let right = pop!(context, "right");
-                    let left  = pop!(context, "left");
-                    karamel_print_level2!("Multiply: {:?} * {:?}", left, right);
-
-                    *context.stack_ptr = match (&*left, &*right) {
-                        (KaramelPrimative::Number(l_value),  KaramelPrimative::Number(r_value))   => VmObject::from(*l_value * *r_value),
-                        (KaramelPrimative::Text(l_value),    KaramelPrimative::Number(r_value))   => VmObject::from((*l_value).repeat((*r_value) as usize)),
-                        _ => EMPTY_OBJECT
-                    };
-                    dump_data!(context, "result");
-                    inc_memory_index!(context, 1);
-                },
+    *context.stack_ptr = match (&left.deref_clean(), &right.deref_clean()) {
+        (KaramelPrimative::Text(l_value),    KaramelPrimative::Text(r_value))   => join_text(context, l_value, r_value)?,
+        (KaramelPrimative::Text(l_value),    KaramelPrimative::Number(r_value)) => join_text(context, l_value, &number_to_text(*r_value))?,
+        (KaramelPrimative::Number(l_value),  KaramelPrimative::Text(r_value))   => join_text(context, &number_to_text(*l_value), r_value)?,
+        (KaramelPrimative::Bytes(l_value),   KaramelPrimative::Bytes(r_value))  => {
+            allocate_value(context, l_value.len().checked_add(r_value.len()))?;
+            VmObject::native_convert(KaramelPrimative::Bytes(Rc::new([l_value.as_slice(), r_value.as_slice()].concat())))
+        },
+        _ => EMPTY_OBJECT
+    };
+    dump_data!(context, "result");
+    inc_memory_index!(context, 1);
+    Ok(Flow::Next)
+}
 
-                VmOpCode::Division => {
-                    let right = pop_raw!(context, "right");
-                    let left = pop_raw!(context, "left");
-                    karamel_print_level2!("Division: {:?} / {:?}", left, right);
+/* Size of the new text or bytes is checked before the memory is allocated, too big values would abort the process */
+fn allocate_value(context: &KaramelCompilerContext, size: Option<usize>) -> Result<(), KaramelErrorType> {
+    match size {
+        Some(size) if size <= KARAMEL_MAX_TEXT_SIZE => context.allocate(size),
+        _ => Err(KaramelErrorType::ValueTooLarge(KARAMEL_MAX_TEXT_SIZE))
+    }
+}
 
-                    let calculation = match (left.as_number(), right.as_number()) {
-                        (Some(l_value),  Some(r_value))   => (l_value / r_value),
-                        _ => std::f64::NAN
-                    };
+fn join_text(context: &KaramelCompilerContext, left: &str, right: &str) -> Result<VmObject, KaramelErrorType> {
+    allocate_value(context, left.len().checked_add(right.len()))?;
+    let mut text = String::with_capacity(left.len() + right.len());
+    text.push_str(left);
+    text.push_str(right);
+    Ok(VmObject::from(Rc::new(text)))
+}
 
-                    *context.stack_ptr = if calculation.is_nan() {
-                        EMPTY_OBJECT
-                    }
-                    else {
-                        VmObject::from(calculation)
-                    };
+unsafe fn load(context: &mut KaramelCompilerContext, wide: bool) -> Result<Flow, KaramelErrorType> {
+    let tmp   = read_location(context, wide);
+    let scope = &mut *context.current_scope;
+    *context.stack_ptr = karamel_dbg!(*scope.top_stack.offset(tmp as isize));
+    karamel_print_level2!("Load: [{:?}]: {:?}", tmp, *context.stack_ptr);
+    dump_data!(context, "loaded");
+    inc_memory_index!(context, 1);
+    Ok(Flow::Next)
+}
 
-                    inc_memory_index!(context, 1);
-                },
+unsafe fn constant(context: &mut KaramelCompilerContext, wide: bool) -> Result<Flow, KaramelErrorType> {
+    let tmp   = read_location(context, wide);
+    let scope = &mut *context.current_scope;
+    *context.stack_ptr = karamel_dbg!(*scope.constant_ptr.offset(tmp as isize));
+    karamel_print_level2!("Constant: [{:?}]: {:?}", tmp, *context.stack_ptr);
+    dump_data!(context, "constant loaded");
+    inc_memory_index!(context, 1);
+    Ok(Flow::Next)
+}
 
-                VmOpCode::Module => {
-                    let right = pop_raw!(context, "right");
-                    let left = pop_raw!(context, "left");
-                    karamel_print_level2!("Module: {:?} / {:?}", left, right);
-
-                    *context.stack_ptr = match (left.as_number(), right.as_number()) {
-                        (Some(l_value),  Some(r_value))   => VmObject::from(karamel_dbg!(l_value) % karamel_dbg!(r_value)),
-                        _ => EMPTY_OBJECT
-                    };
-                    dump_data!(context, "result");
-                    inc_memory_index!(context, 1);
-                },
+unsafe fn store(context: &mut KaramelCompilerContext, wide: bool) -> Result<Flow, KaramelErrorType> {
+    let tmp = read_location(context, wide);
+    dec_memory_index!(context, 1);
+    *(*context.current_scope).top_stack.offset(tmp as isize) = karamel_dbg!(*context.stack_ptr);
+    karamel_print_level2!("Store: [{:?}]: {:?}", tmp, *context.stack_ptr);
 
-                VmOpCode::Equal => {
-                    let right = pop!(context, "right");
-                    let left  = pop!(context, "left");
-                    karamel_print_level2!("Equal: {:?} == {:?}", left, right);
-                    
-                    *context.stack_ptr = VmObject::from(karamel_dbg!(left) == karamel_dbg!(right));
-                    dump_data!(context, "result");
-                    inc_memory_index!(context, 1);
-                },
+    if context.events.is_some() {
+        notify_variable_change(context, tmp);
+    }
+    Ok(Flow::Next)
+}
 
+unsafe fn copy_to_store(context: &mut KaramelCompilerContext, wide: bool) -> Result<Flow, KaramelErrorType> {
+    let tmp = read_location(context, wide);
+    *(*context.current_scope).top_stack.offset(tmp as isize) = karamel_dbg!(*context.stack_ptr.sub(1));
+    karamel_print_level2!("CopyToStore: [{:?}]: {:?}", tmp, *context.stack_ptr);
 
-                VmOpCode::NotEqual => {
-                    let right = pop!(context, "right");
-                    let left  = pop!(context, "left");
-                    karamel_print_level2!("NotEqual: {:?} != {:?}", left, right);
-                    
-                    *context.stack_ptr = VmObject::from(karamel_dbg!(left) != karamel_dbg!(right));
-                    dump_data!(context, "result");
-                    inc_memory_index!(context, 1);
-                },
+    if context.events.is_some() {
+        notify_variable_change(context, tmp);
+    }
+    Ok(Flow::Next)
+}
 
-                VmOpCode::GreaterThan => {
-                    let right = pop_raw!(context, "right");
-                    let left = pop_raw!(context, "left");
-                    karamel_print_level2!("GreaterThan: {:?} > {:?}", left, right);
-                    
-                    *context.stack_ptr = match (left.as_number(), right.as_number()) {
-                        (Some(l_value),  Some(r_value))   => VmObject::from(karamel_dbg!(l_value) > karamel_dbg!(r_value)),
-                        _ => EMPTY_OBJECT
-                    };
-                    dump_data!(context, "result");
-                    inc_memory_index!(context, 1);
-                },
+unsafe fn fast_store(context: &mut KaramelCompilerContext) -> Result<Flow, KaramelErrorType> {
+    let destination = *context.opcodes_ptr.offset(1) as usize;
+    let source      = *context.opcodes_ptr.offset(2) as usize;
+    *(*context.current_scope).top_stack.offset(destination as isize) = karamel_dbg!(*(*context.current_scope).constant_ptr.offset(source as isize));
+    context.opcodes_ptr = context.opcodes_ptr.offset(2);
+    karamel_print_level2!("FastStore: {:?}: {:?} => {:?}", *(*context.current_scope).top_stack.offset(destination as isize), source, destination);
 
-                VmOpCode::GreaterEqualThan => {
-                    let right = pop_raw!(context, "right");
-                    let left = pop_raw!(context, "left");
-                    karamel_print_level2!("GreaterEqualThan {:?} >= {:?}", left, right);
-                    
-                    *context.stack_ptr = match (left.as_number(), right.as_number()) {
-                        (Some(l_value),  Some(r_value))   => VmObject::from(karamel_dbg!(l_value) >= karamel_dbg!(r_value)),
-                        _ => EMPTY_OBJECT
-                    };
-                    dump_data!(context, "result");
-                    inc_memory_index!(context, 1);
-                },
+    if context.events.is_some() {
+        notify_variable_change(context, destination);
+    }
+    Ok(Flow::Next)
+}
 
-                VmOpCode::Call => {
-                    let func_location   = *context.opcodes_ptr.offset(1) as usize;
-                    context.opcodes_ptr = context.opcodes_ptr.offset(1);
-                    
-                    let value = (*(*context.current_scope).constant_ptr.offset(func_location as isize)).deref();
-
-                    karamel_print_level2!("Call: {:?}", value);
-                    if let KaramelPrimative::Function(reference, _) = karamel_dbg!(&*value) {
-                        reference.execute(context, None)?;
-                    }
-                    else {
-                        return Err(KaramelErrorType::NotCallable(value.clone()));
-                    }
-                },
+unsafe fn not(context: &mut KaramelCompilerContext) -> Result<Flow, KaramelErrorType> {
+    *context.stack_ptr.sub(1) = VmObject::from(!(*context.stack_ptr.sub(1)).deref_clean().is_true());
+    dump_data!(context, "result");
+    karamel_print_level2!("Not: {:?}", *context.stack_ptr.sub(1));
+    Ok(Flow::Next)
+}
 
-                VmOpCode::CallStack => {
-                    let function = pop_raw!(context, "function");
-                    let value =  function.deref();
-                    karamel_print_level2!("CallStack {:?}", value);
-                    
-                    match &*value {
-                        KaramelPrimative::Function(reference, base) => reference.execute(context, *base)?,
-                        _ => {
-                            log::debug!("{:?} not callable", &*function.deref());
-                        return Err(KaramelErrorType::NotCallable(value.clone()));
-                        }
-                    };
-                },
+unsafe fn dublicate(context: &mut KaramelCompilerContext) -> Result<Flow, KaramelErrorType> {
+    *context.stack_ptr = karamel_dbg!(*context.stack_ptr.sub(1));
+    karamel_print_level2!("Dublicate: {:?}", *context.stack_ptr);
+    inc_memory_index!(context, 1);
+    Ok(Flow::Next)
+}
 
-                VmOpCode::Return => {
-                    let return_value               = *context.stack_ptr.sub(1);
-                    context.opcodes_ptr            = (*context.current_scope).location;
-                    let call_return_assign_to_temp = (*context.current_scope).call_return_assign_to_temp;
-                    context.scope_index           -= 1;
-
-                    context.stack_ptr = (*context.current_scope).top_stack;
-                    context.current_scope          = context.scopes_ptr.add(context.scope_index);              
-
-                    if call_return_assign_to_temp {
-                        *context.stack_ptr = return_value;
-                        karamel_print_level2!("Return [{:?}] {:?}", get_memory_index!(context), *context.stack_ptr);
-                        inc_memory_index!(context, 1);
-                    } else {
-                        karamel_print_level2!("Return");
-                    }
-                },
+unsafe fn dublicate_pair(context: &mut KaramelCompilerContext) -> Result<Flow, KaramelErrorType> {
+    *context.stack_ptr = *context.stack_ptr.sub(2);
+    *context.stack_ptr.add(1) = *context.stack_ptr.sub(1);
+    karamel_print_level2!("DublicatePair: {:?} {:?}", *context.stack_ptr, *context.stack_ptr.add(1));
+    inc_memory_index!(context, 2);
+    Ok(Flow::Next)
+}
 
-                VmOpCode::Increment => {
-                    karamel_print_level2!("Increment");
-                    *context.stack_ptr.sub(1) = match (*context.stack_ptr.sub(1)).as_number() {
-                        Some(value) => VmObject::from(karamel_dbg!(value + 1 as f64)),
-                        _ => EMPTY_OBJECT
-                    };
-                },
+unsafe fn and(context: &mut KaramelCompilerContext) -> Result<Flow, KaramelErrorType> {
+    let left  = pop!(context, "left");
+    let right = pop!(context, "right");
+    karamel_print_level2!("And: {:?} && {:?}", left, right);
 
-                VmOpCode::Decrement => {
-                    karamel_print_level2!("Increment");
-                    *context.stack_ptr.sub(1) = match (*context.stack_ptr.sub(1)).as_number() {
-                        Some(value) => VmObject::from(value - 1 as f64),
-                        _ => EMPTY_OBJECT
-                    };
-                },
+    *context.stack_ptr = VmObject::from(karamel_dbg!(left.is_true()) && karamel_dbg!(right.is_true()));
+    dump_data!(context, "result");
+    inc_memory_index!(context, 1);
+    Ok(Flow::Next)
+}
 
-                VmOpCode::Init => {
-                    let init_type = *context.opcodes_ptr.offset(1) as usize;
-                    let total_item = *context.opcodes_ptr.offset(2) as usize;
-                    karamel_print_level2!("Init: {:?} {:?}", init_type, total_item);
+unsafe fn or(context: &mut KaramelCompilerContext) -> Result<Flow, KaramelErrorType> {
+    let left  = pop!(context, "left");
+    let right = pop!(context, "right");
+    karamel_print_level2!("Or: {:?} || {:?}", left, right);
 
-                    *context.stack_ptr = match init_type {
-                        // Dict
-                        0 => {
-                            let mut dict   = HashMap::new();
-        
-                            for _ in 0..total_item {
-                                let value = pop_raw!(context, "value");
-                                let key   = pop!(context, "key");
-                                
-                                dict.insert(key.get_text(), value);
-                            }
-
-                            VmObject::from(dict)
-                        },
-
-                        // List
-                        1 => {
-                            let mut list = Vec::with_capacity(total_item.into());
-
-                            for i in 0..total_item {
-                                list.push(pop_raw!(context, i));
-                            }
-                            
-                            VmObject::from(list)
-                        },
-                         _ => return Err(KaramelErrorType::GeneralError("Geçersiz yükleme tipi".to_string()))
-                    };
-                    
-                    inc_memory_index!(context, 1);
-                    context.opcodes_ptr = context.opcodes_ptr.offset(2);
-                },
+    *context.stack_ptr = VmObject::from(karamel_dbg!(left.is_true()) || karamel_dbg!(right.is_true()));
+    dump_data!(context, "result");
+    inc_memory_index!(context, 1);
+    Ok(Flow::Next)
+}
 
-                VmOpCode::Compare => {
-                    let condition = pop_raw!(context, "condition");
-                    karamel_print_level2!("Compare: {:?}", condition);
-
-                    let status = match &condition.deref_clean() {
-                        KaramelPrimative::Empty => false,
-                        KaramelPrimative::Bool(l_value) => *l_value,
-                        KaramelPrimative::Number(l_value) => *l_value > 0.0,
-                        KaramelPrimative::Text(l_value) => !(*l_value).is_empty(),
-                        _ => false
-                    };
-
-                    if status {
-                        context.opcodes_ptr = context.opcodes_ptr.offset(2);
-                    }
-                    else {
-                        let location = ((*context.opcodes_ptr.offset(2) as u16 * 256) + *context.opcodes_ptr.offset(1) as u16) as usize;
-                        context.opcodes_ptr = context.opcodes_ptr.offset(location as isize);
-                        continue;
-                    }
-                },
+/* JumpIfFalse, JumpIfTrue and their wide versions. Jumps when the condition is same with 'jump_when' */
+unsafe fn conditional_jump(context: &mut KaramelCompilerContext, jump_when: bool, wide: bool) -> Result<Flow, KaramelErrorType> {
+    let condition = (*context.stack_ptr.sub(1)).deref().is_true();
+    karamel_print_level2!("JumpIf{:?}: {:?}", jump_when, condition);
 
-                VmOpCode::Jump => {
-                    let location = ((*context.opcodes_ptr.offset(2)  as u16 * 256) + *context.opcodes_ptr.offset(1)  as u16) as usize;
-                    karamel_print_level2!("Jump: {:?}", location);
-                    context.opcodes_ptr = context.opcodes.as_mut_ptr().offset(location as isize);
-                    continue;
-                },
-                
-                VmOpCode::SetItem => {
-                    let assign_item  = pop_raw!(context, "assign_item");
-                    let indexer = pop!(context, "indexer");
-                    let raw_object = pop_raw!(context, "raw_object");
-                    let object  = raw_object.deref();
-                    karamel_print_level2!("GetItem: object={:?}, indexer={:?}, item={:?}", object, indexer, assign_item);
-
-                    // todo: change all those codes with setter implementation
-                    match &*object {
-                        KaramelPrimative::List(value) => {
-                            let indexer_value = match &*indexer {
-                                KaramelPrimative::Number(number) => *number as usize,
-                                _ => return Err(KaramelErrorType::IndexerMustBeNumber(indexer.clone()))
-                            };
-
-                            value.borrow_mut()[indexer_value] = assign_item;
-                        },
-                        KaramelPrimative::Dict(value) => {
-                            let indexer_value = match &*indexer {
-                                KaramelPrimative::Text(text) => &*text,
-                                _ => return Err(KaramelErrorType::IndexerMustBeString(indexer.clone()))
-                            };
-
-                            value.borrow_mut().insert(indexer_value.to_string(), assign_item);
-                        },
-                        KaramelPrimative::Text(_) => {
-                            let indexer_value = match &*indexer {
-                                KaramelPrimative::Number(number) => *number,
-                                _ => return Err(KaramelErrorType::IndexerMustBeNumber(indexer.clone()))
-                            };
-
-                            match context.get_class(&object).get_setter() {
-                                Some(function) => function(raw_object, indexer_value, assign_item)?,
-                                _ => EMPTY_OBJECT
-                            };
-                        },
-                        
-                        _ => ()
-                    };
-                },
+    *context.stack_ptr.sub(1) = VmObject::from(condition);
+    if condition == jump_when {
+        let location = jump_location(context, wide);
+        context.opcodes_ptr = context.opcodes.as_mut_ptr().add(location);
+        return Ok(Flow::Jumped);
+    }
+    context.opcodes_ptr = context.opcodes_ptr.add(jump_size(wide));
+    Ok(Flow::Next)
+}
 
-                VmOpCode::GetItem => {
-                    let indexer = pop!(context, "indexer");
-                    let raw_object  = pop_raw!(context, "raw_object");
-                    let object = &*raw_object.deref();
-                    karamel_print_level2!("GetItem: object={:?}, indexer={:?}", object, indexer);
-
-                    *context.stack_ptr = match &*indexer {
-                        KaramelPrimative::Text(text) => {
-                             match context.get_class(object).get_element(Some(raw_object), text.clone()) {
-                                Some(element) => match element {
-                                    ClassProperty::Function(function) => VmObject::from(Rc::new(KaramelPrimative::Function(function.clone(), Some(raw_object)))),
-                                    ClassProperty::Field(field) => VmObject::from(field.clone())
-                                },
-                                _ => EMPTY_OBJECT
-                            }
-                        },
-                        KaramelPrimative::Number(index) => match context.get_class(object).get_getter() {
-                            Some(function) => function(raw_object, *index)?,
-                            _ => EMPTY_OBJECT
-                        }
-                        _ => EMPTY_OBJECT
-                    };
-
-                    inc_memory_index!(context, 1);
-                },
+unsafe fn multiply(context: &mut KaramelCompilerContext) -> Result<Flow, KaramelErrorType> {
+    let right = pop_raw!(context, "right");
+    let left  = pop_raw!(context, "left");
+    karamel_print_level2!("Multiply: {:?} * {:?}", left, right);
+
+    /* Numbers do not need a primative */
+    if let (Some(l_value), Some(r_value)) = (left.as_number(), right.as_number()) {
+        *context.stack_ptr = VmObject::from(l_value * r_value);
+        inc_memory_index!(context, 1);
+        return Ok(Flow::Next);
+    }
+
+    *context.stack_ptr = match (&*left.deref(), &*right.deref()) {
+        (KaramelPrimative::Text(text), KaramelPrimative::Number(count)) |
+        (KaramelPrimative::Number(count), KaramelPrimative::Text(text)) => match repeat_count(*count) {
+            /* Size is checked before 'repeat', big counts would panic or abort the process while allocating */
+            Some(count) => {
+                allocate_value(context, text.len().checked_mul(count))?;
+                VmObject::from(text.repeat(count))
+            },
+            None => EMPTY_OBJECT
+        },
+        _ => EMPTY_OBJECT
+    };
+    dump_data!(context, "result");
+    inc_memory_index!(context, 1);
+    Ok(Flow::Next)
+}
+
+unsafe fn division(context: &mut KaramelCompilerContext) -> Result<Flow, KaramelErrorType> {
+    let right = pop_raw!(context, "right");
+    let left = pop_raw!(context, "left");
+    karamel_print_level2!("Division: {:?} / {:?}", left, right);
+
+    let calculation = match (left.as_number(), right.as_number()) {
+        (Some(l_value),  Some(r_value))   => (l_value / r_value),
+        _ => std::f64::NAN
+    };
 
-                VmOpCode::Halt => {
-                    karamel_print_level2!("Halt");
-                    break;
+    *context.stack_ptr = if calculation.is_nan() {
+        EMPTY_OBJECT
+    }
+    else {
+        VmObject::from(calculation)
+    };
+
+    inc_memory_index!(context, 1);
+    Ok(Flow::Next)
+}
+
+unsafe fn module(context: &mut KaramelCompilerContext) -> Result<Flow, KaramelErrorType> {
+    let right = pop_raw!(context, "right");
+    let left = pop_raw!(context, "left");
+    karamel_print_level2!("Module: {:?} / {:?}", left, right);
+
+    *context.stack_ptr = match (left.as_number(), right.as_number()) {
+        (Some(l_value),  Some(r_value))   => VmObject::from(karamel_dbg!(l_value) % karamel_dbg!(r_value)),
+        _ => EMPTY_OBJECT
+    };
+    dump_data!(context, "result");
+    inc_memory_index!(context, 1);
+    Ok(Flow::Next)
+}
+
+unsafe fn equal(context: &mut KaramelCompilerContext) -> Result<Flow, KaramelErrorType> {
+    let right = pop!(context, "right");
+    let left  = pop!(context, "left");
+    karamel_print_level2!("Equal: {:?} == {:?}", left, right);
+
+    *context.stack_ptr = VmObject::from(deep_equal(karamel_dbg!(&left), karamel_dbg!(&right)));
+    dump_data!(context, "result");
+    inc_memory_index!(context, 1);
+    Ok(Flow::Next)
+}
+
+unsafe fn not_equal(context: &mut KaramelCompilerContext) -> Result<Flow, KaramelErrorType> {
+    let right = pop!(context, "right");
+    let left  = pop!(context, "left");
+    karamel_print_level2!("NotEqual: {:?} != {:?}", left, right);
+
+    *context.stack_ptr = VmObject::from(!deep_equal(karamel_dbg!(&left), karamel_dbg!(&right)));
+    dump_data!(context, "result");
+    inc_memory_index!(context, 1);
+    Ok(Flow::Next)
+}
+
+unsafe fn greater_than(context: &mut KaramelCompilerContext) -> Result<Flow, KaramelErrorType> {
+    let right = pop_raw!(context, "right");
+    let left = pop_raw!(context, "left");
+    karamel_print_level2!("GreaterThan: {:?} > {:?}", left, right);
+
+    *context.stack_ptr = match (left.as_number(), right.as_number()) {
+        (Some(l_value),  Some(r_value))   => VmObject::from(karamel_dbg!(l_value) > karamel_dbg!(r_value)),
+        _ => EMPTY_OBJECT
+    };
+    dump_data!(context, "result");
+    inc_memory_index!(context, 1);
+    Ok(Flow::Next)
+}
+
+unsafe fn greater_equal_than(context: &mut KaramelCompilerContext) -> Result<Flow, KaramelErrorType> {
+    let right = pop_raw!(context, "right");
+    let left = pop_raw!(context, "left");
+    karamel_print_level2!("GreaterEqualThan {:?} >= {:?}", left, right);
+
+    *context.stack_ptr = match (left.as_number(), right.as_number()) {
+        (Some(l_value),  Some(r_value))   => VmObject::from(karamel_dbg!(l_value) >= karamel_dbg!(r_value)),
+        _ => EMPTY_OBJECT
+    };
+    dump_data!(context, "result");
+    inc_memory_index!(context, 1);
+    Ok(Flow::Next)
+}
+
+unsafe fn call(context: &mut KaramelCompilerContext) -> Result<Flow, KaramelErrorType> {
+    collect_garbage(context);
+    let func_location   = *context.opcodes_ptr.offset(1) as usize;
+    context.opcodes_ptr = context.opcodes_ptr.offset(1);
+
+    let value = (*(*context.current_scope).constant_ptr.offset(func_location as isize)).deref();
+
+    karamel_print_level2!("Call: {:?}", value);
+    if let KaramelPrimative::Function(reference, _) = karamel_dbg!(&*value) {
+        reference.execute(context, None)?;
+    }
+    else {
+        return Err(KaramelErrorType::NotCallable(value.clone()));
+    }
+    Ok(Flow::Next)
+}
+
+unsafe fn call_stack(context: &mut KaramelCompilerContext) -> Result<Flow, KaramelErrorType> {
+    collect_garbage(context);
+    let function = pop_raw!(context, "function");
+    let value =  function.deref();
+    karamel_print_level2!("CallStack {:?}", value);
+
+    match &*value {
+        KaramelPrimative::Function(reference, base) => reference.execute(context, *base)?,
+        KaramelPrimative::Bound(bound) => call_bound(context, bound)?,
+        _ => {
+            log::debug!("{:?} not callable", &*function.deref());
+            return Err(KaramelErrorType::NotCallable(value.clone()));
+        }
+    };
+    Ok(Flow::Next)
+}
+
+/* Captured arguments are placed before the arguments of the call, the function sees all of them as its arguments */
+unsafe fn call_bound(context: &mut KaramelCompilerContext, bound: &BoundFunction) -> Result<(), KaramelErrorType> {
+    let call_size     = *context.opcodes_ptr.offset(1) as usize;
+    let captured_size = bound.arguments.len();
+    if call_size + captured_size > u8::MAX as usize {
+        return Err(KaramelErrorType::TooManyArguments(u8::MAX as usize));
+    }
+
+    let arguments = context.stack_ptr.sub(call_size);
+    ptr::copy(arguments, arguments.add(captured_size), call_size);
+    ptr::copy_nonoverlapping(bound.arguments.as_ptr(), arguments, captured_size);
+    inc_memory_index!(context, captured_size);
+
+    bound.function.execute_with_arguments(context, bound.base, (call_size + captured_size) as u8)
+}
+
+unsafe fn function_return(context: &mut KaramelCompilerContext) -> Result<Flow, KaramelErrorType> {
+    /* Deferred blocks run before leaving the function, Return opcode is executed again after each block */
+    if let Some(location) = (*context.current_scope).deferred.pop() {
+        (*context.current_scope).defer_return = context.opcodes_ptr;
+        (*context.current_scope).defer_stack  = context.stack_ptr;
+        context.opcodes_ptr = context.opcodes.as_mut_ptr().add(location);
+        return Ok(Flow::Jumped);
+    }
+
+    let return_value               = *context.stack_ptr.sub(1);
+    context.opcodes_ptr            = (*context.current_scope).location;
+    let call_return_assign_to_temp = (*context.current_scope).call_return_assign_to_temp;
+    context.scope_index           -= 1;
+
+    if let Some(events) = context.events.as_mut() {
+        events.function_returned(return_value);
+    }
+
+    context.stack_ptr = (*context.current_scope).top_stack;
+    context.current_scope          = context.scopes_ptr.add(context.scope_index);
+
+    if call_return_assign_to_temp {
+        *context.stack_ptr = return_value;
+        karamel_print_level2!("Return [{:?}] {:?}", get_memory_index!(context), *context.stack_ptr);
+        inc_memory_index!(context, 1);
+    } else {
+        karamel_print_level2!("Return");
+    }
+    Ok(Flow::Next)
+}
+
+unsafe fn increment(context: &mut KaramelCompilerContext) -> Result<Flow, KaramelErrorType> {
+    karamel_print_level2!("Increment");
+    *context.stack_ptr.sub(1) = match (*context.stack_ptr.sub(1)).as_number() {
+        Some(value) => VmObject::from(karamel_dbg!(value + 1 as f64)),
+        _ => EMPTY_OBJECT
+    };
+    Ok(Flow::Next)
+}
+
+unsafe fn decrement(context: &mut KaramelCompilerContext) -> Result<Flow, KaramelErrorType> {
+    karamel_print_level2!("Increment");
+    *context.stack_ptr.sub(1) = match (*context.stack_ptr.sub(1)).as_number() {
+        Some(value) => VmObject::from(value - 1 as f64),
+        _ => EMPTY_OBJECT
+    };
+    Ok(Flow::Next)
+}
+
+unsafe fn init(context: &mut KaramelCompilerContext) -> Result<Flow, KaramelErrorType> {
+    let init_type = *context.opcodes_ptr.offset(1) as usize;
+    let total_item = (*context.opcodes_ptr.offset(3) as usize * 256) + *context.opcodes_ptr.offset(2) as usize;
+    karamel_print_level2!("Init: {:?} {:?}", init_type, total_item);
+
+    *context.stack_ptr = match init_type {
+        // Dict
+        0 => {
+            let mut dict   = HashMap::new();
+
+            for _ in 0..total_item {
+                let value = pop_raw!(context, "value");
+                let key   = pop!(context, "key");
+
+                dict.insert(DictKey::from(key.get_text()), value);
+            }
+
+            VmObject::from(dict)
+        },
+
+        // List
+        1 => {
+            let mut list = Vec::with_capacity(total_item);
+
+            for i in 0..total_item {
+                list.push(pop_raw!(context, i));
+            }
+
+            VmObject::from(list)
+        },
+         _ => return Err(KaramelErrorType::GeneralError("Geçersiz yükleme tipi".to_string()))
+    };
+
+    inc_memory_index!(context, 1);
+    context.opcodes_ptr = context.opcodes_ptr.offset(3);
+    Ok(Flow::Next)
+}
+
+unsafe fn compare(context: &mut KaramelCompilerContext, wide: bool) -> Result<Flow, KaramelErrorType> {
+    let condition = pop_raw!(context, "condition");
+    karamel_print_level2!("Compare: {:?}", condition);
+
+    let status = match &condition.deref_clean() {
+        KaramelPrimative::Empty => false,
+        KaramelPrimative::Bool(l_value) => *l_value,
+        KaramelPrimative::Number(l_value) => *l_value > 0.0,
+        KaramelPrimative::Text(l_value) => !(*l_value).is_empty(),
+        _ => false
+    };
+
+    if status {
+        context.opcodes_ptr = context.opcodes_ptr.add(jump_size(wide));
+        return Ok(Flow::Next);
+    }
+
+    /* Compare location is the distance from the opcode for both sizes */
+    let location = match wide {
+        true => read_offset(context),
+        false => read_u16(context) as isize
+    };
+    context.opcodes_ptr = context.opcodes_ptr.offset(location);
+    Ok(Flow::Jumped)
+}
+
+unsafe fn jump(context: &mut KaramelCompilerContext, wide: bool) -> Result<Flow, KaramelErrorType> {
+    collect_garbage(context);
+    let location = jump_location(context, wide);
+    karamel_print_level2!("Jump: {:?}", location);
+
+    if context.teaching_mode && location < opcode_index(context) {
+        context.loop_iterations += 1;
+        if context.loop_iterations > KARAMEL_TEACHING_LOOP_LIMIT {
+            return Err(KaramelErrorType::LoopIterationLimitExceeded(KARAMEL_TEACHING_LOOP_LIMIT));
+        }
+    }
+
+    context.opcodes_ptr = context.opcodes.as_mut_ptr().add(location);
+    Ok(Flow::Jumped)
+}
+
+unsafe fn set_item(context: &mut KaramelCompilerContext) -> Result<Flow, KaramelErrorType> {
+    let assign_item  = pop_raw!(context, "assign_item");
+    let indexer = pop!(context, "indexer");
+    let raw_object = pop_raw!(context, "raw_object");
+    let object  = raw_object.deref();
+    karamel_print_level2!("SetItem: object={:?}, indexer={:?}, item={:?}", object, indexer, assign_item);
+
+    match (&*object, &*indexer) {
+        (KaramelPrimative::Frozen(_), _) => return Err(KaramelErrorType::FrozenValueCannotBeChanged),
+        (KaramelPrimative::Dict(value), _) => {
+            value.borrow_mut().insert(DictKey::try_from_value(&indexer)?, assign_item);
+        },
+        (KaramelPrimative::SortedDict(value), _) => {
+            value.borrow_mut().insert(DictKey::try_from_value(&indexer)?, assign_item);
+        },
+        (_, KaramelPrimative::Number(index)) => match context.get_class(&object).get_setter() {
+            Some(function) => {
+                function(raw_object, *index, assign_item)?;
+            },
+            None => return Err(KaramelErrorType::IndexerAssignmentNotSupported(object.clone()))
+        },
+        _ => return Err(KaramelErrorType::IndexerMustBeNumber(indexer.clone()))
+    };
+    Ok(Flow::Next)
+}
+
+unsafe fn get_item(context: &mut KaramelCompilerContext) -> Result<Flow, KaramelErrorType> {
+    let indexer = pop!(context, "indexer");
+    let raw_object  = pop_raw!(context, "raw_object");
+    let object = &*raw_object.deref();
+    karamel_print_level2!("GetItem: object={:?}, indexer={:?}", object, indexer);
+
+    *context.stack_ptr = match &*indexer {
+        KaramelPrimative::Text(text) => {
+             match context.get_class(object).get_element(Some(raw_object), text.clone()) {
+                Some(element) => match element {
+                    ClassProperty::Function(function) => VmObject::from(Rc::new(KaramelPrimative::Function(function.clone(), Some(raw_object)))),
+                    ClassProperty::Field(field) => VmObject::from(field.clone())
                 },
+                _ => EMPTY_OBJECT
             }
+        },
+        KaramelPrimative::Number(index) => match context.get_class(object).get_getter() {
+            Some(function) => function(raw_object, *index)?,
+            _ => EMPTY_OBJECT
+        }
+        _ => match object {
+            KaramelPrimative::Frozen(inner) => get_dict_item(&inner.deref(), &indexer)?,
+            object => get_dict_item(object, &indexer)?
+        }
+    };
 
-            context.opcodes_ptr = context.opcodes_ptr.offset(1);
+    inc_memory_index!(context, 1);
+    Ok(Flow::Next)
+}
+
+/* Dict keys that are not text or number, like the frozen lists */
+fn get_dict_item(object: &KaramelPrimative, key: &Rc<KaramelPrimative>) -> Result<VmObject, KaramelErrorType> {
+    match object {
+        KaramelPrimative::Dict(dict) => Ok(dict.borrow().get(&DictKey::try_from_value(key)?).copied().unwrap_or(EMPTY_OBJECT)),
+        KaramelPrimative::SortedDict(dict) => Ok(dict.borrow().get(&DictKey::try_from_value(key)?).copied().unwrap_or(EMPTY_OBJECT)),
+        _ => Ok(EMPTY_OBJECT)
+    }
+}
+
+/* Classes with a fixed property give the same property for every object, so it is kept at the call site.
+   Dictionary items and the user class properties belong to the object, they are searched at every call. */
+unsafe fn get_member(context: &mut KaramelCompilerContext) -> Result<Flow, KaramelErrorType> {
+    let index = read_u16(context);
+    let indexer = pop!(context, "indexer");
+    let raw_object  = pop_raw!(context, "raw_object");
+    let class = context.get_class(&raw_object.deref());
+    let class_id = Rc::as_ptr(&class) as *const u8 as usize;
+    karamel_print_level2!("GetMember: cache={:?}, indexer={:?}", index, indexer);
+
+    let cache = context.inline_caches.get_unchecked_mut(index);
+    let property = match (cache.class == class_id, &*indexer) {
+        (true, _) => cache.property.clone(),
+        (false, KaramelPrimative::Text(text)) => {
+            let property = class.get_element(Some(raw_object), text.clone());
+            if class.has_element(None, text.clone()) {
+                cache.class = class_id;
+                cache.property = property.clone();
+            }
+            property
+        },
+        _ => None
+    };
+
+    *context.stack_ptr = match property {
+        Some(ClassProperty::Function(function)) => VmObject::from(Rc::new(KaramelPrimative::Function(function, Some(raw_object)))),
+        Some(ClassProperty::Field(field)) => VmObject::from(field),
+        None => EMPTY_OBJECT
+    };
+
+    inc_memory_index!(context, 1);
+    context.opcodes_ptr = context.opcodes_ptr.offset(2);
+    Ok(Flow::Next)
+}
+
+unsafe fn loop_counter(context: &mut KaramelCompilerContext) -> Result<Flow, KaramelErrorType> {
+    let index = read_u16(context);
+    karamel_print_level2!("LoopCounter: {:?}", index);
+    context.loop_counters.get_unchecked_mut(index).iterations += 1;
+    context.opcodes_ptr = context.opcodes_ptr.offset(2);
+    Ok(Flow::Next)
+}
+
+unsafe fn line(context: &mut KaramelCompilerContext) -> Result<Flow, KaramelErrorType> {
+    let line = read_u16(context) as u32;
+    karamel_print_level2!("Line: {:?}", line);
+    if let Some(events) = context.events.as_mut() {
+        events.line_entered(line);
+    }
+    context.opcodes_ptr = context.opcodes_ptr.offset(2);
+    Ok(Flow::Next)
+}
+
+unsafe fn try_start(context: &mut KaramelCompilerContext, wide: bool) -> Result<Flow, KaramelErrorType> {
+    let catch_location = jump_location(context, wide);
+    let flag = context.opcodes_ptr.add(jump_size(wide) + 1);
+    let variable = match *flag {
+        0 => None,
+        _ => Some((*flag.offset(2) as usize * 256) + *flag.offset(1) as usize)
+    };
+    let start = opcode_index(context);
+    karamel_print_level2!("TryStart: {:?}", catch_location);
+
+    /* Blocks that are left with 'kır', 'devam' or 'döndür' do not reach TryEnd, their handlers are removed here */
+    while let Some(handler) = context.error_handlers.last() {
+        if is_handler_active(context, handler) {
+            break;
         }
-        
-        if dump_memory {
-            let dump = context.storages[0].dump();
-            context.memory_dump = Some(dump);
+        context.error_handlers.pop();
+    }
+
+    context.error_handlers.push(ErrorHandler {
+        start,
+        catch_location,
+        variable,
+        scope_index: context.scope_index,
+        stack_ptr: context.stack_ptr
+    });
+    context.opcodes_ptr = flag.offset(2);
+    Ok(Flow::Next)
+}
+
+unsafe fn try_end(context: &mut KaramelCompilerContext, wide: bool) -> Result<Flow, KaramelErrorType> {
+    let location = jump_location(context, wide);
+    let catch_location = opcode_index(context) + jump_size(wide) + 1;
+    karamel_print_level2!("TryEnd: {:?}", location);
+
+    while let Some(handler) = context.error_handlers.pop() {
+        if handler.scope_index == context.scope_index && handler.catch_location == catch_location {
+            break;
         }
     }
-    
-    let mut result = Vec::with_capacity(get_memory_index!(context) as usize);
-    for index in 0..get_memory_index!(context) {
-        result.push(*top_stack.add(context.storages[0].variables.len() + index as usize));
+
+    context.opcodes_ptr = context.opcodes.as_mut_ptr().add(location);
+    Ok(Flow::Jumped)
+}
+
+unsafe fn throw(context: &mut KaramelCompilerContext) -> Result<Flow, KaramelErrorType> {
+    let value = pop_raw!(context, "error");
+    karamel_print_level2!("Throw: {:?}", value);
+    Err(KaramelErrorType::UncaughtError(ErrorObject::from_value(value).deref()))
+}
+
+unsafe fn assume(context: &mut KaramelCompilerContext) -> Result<Flow, KaramelErrorType> {
+    let message = pop!(context, "message");
+    let condition = pop!(context, "condition");
+    karamel_print_level2!("Assume: {:?} {:?}", condition, message);
+
+    if !condition.is_true() {
+        return Err(KaramelErrorType::AssumptionFailed(match &*message {
+            KaramelPrimative::Empty => String::new(),
+            KaramelPrimative::Text(text) => text.to_string(),
+            message => format!("{}", message)
+        }));
     }
+    Ok(Flow::Next)
+}
 
-    Ok(result)
-}
\ No newline at end of file
+unsafe fn type_guard(context: &mut KaramelCompilerContext) -> Result<Flow, KaramelErrorType> {
+    let argument = pop!(context, "argument");
+    let expected = pop!(context, "expected");
+    let value = pop!(context, "value");
+    karamel_print_level2!("TypeGuard: {:?} {:?} {:?}", argument, expected, value);
+
+    let found = value.get_type();
+    if expected.get_text() != found {
+        return Err(KaramelErrorType::ArgumentTypeMismatch {
+            argument: argument.get_text(),
+            expected: expected.get_text(),
+            found
+        });
+    }
+    Ok(Flow::Next)
+}
+
+unsafe fn defer(context: &mut KaramelCompilerContext, wide: bool) -> Result<Flow, KaramelErrorType> {
+    let location = jump_location(context, wide);
+    let start = opcode_index(context) + jump_size(wide) + 1;
+    karamel_print_level2!("Defer: {:?}", start);
+
+    (*context.current_scope).deferred.push(start);
+    context.opcodes_ptr = context.opcodes.as_mut_ptr().add(location);
+    Ok(Flow::Jumped)
+}
+
+unsafe fn defer_end(context: &mut KaramelCompilerContext) -> Result<Flow, KaramelErrorType> {
+    let scope = &mut *context.current_scope;
+    karamel_print_level2!("DeferEnd");
+
+    if scope.defer_error.is_none() {
+        context.opcodes_ptr = scope.defer_return;
+        context.stack_ptr   = scope.defer_stack;
+        return Ok(Flow::Jumped);
+    }
+
+    /* Function is left with an error, the error moves to the caller after the last deferred block */
+    if let Some(location) = scope.deferred.pop() {
+        context.opcodes_ptr = context.opcodes.as_mut_ptr().add(location);
+        return Ok(Flow::Jumped);
+    }
+
+    let error = scope.defer_error.take().unwrap();
+    leave_scope(context);
+    Err(error)
+}
+
+unsafe fn with_start(context: &mut KaramelCompilerContext) -> Result<Flow, KaramelErrorType> {
+    let value = pop_raw!(context, "value");
+    karamel_print_level2!("WithStart: {:?}", value);
+
+    let class = context.get_class(&value.deref());
+    match class.get_protocol_method(Some(value), "kapat") {
+        Some(_) => Ok(Flow::Next),
+        None => Err(KaramelErrorType::CloseFunctionNotFound(value.deref().get_type()))
+    }
+}
+
+/* Body is finished without an error, 'kapat' is called by the next opcodes instead of the deferred block */
+unsafe fn with_end(context: &mut KaramelCompilerContext, wide: bool) -> Result<Flow, KaramelErrorType> {
+    let location = jump_location(context, wide);
+    karamel_print_level2!("WithEnd: {:?}", location);
+
+    let deferred = &mut (*context.current_scope).deferred;
+    if let Some(index) = deferred.iter().rposition(|block| *block == location) {
+        deferred.remove(index);
+    }
+    context.opcodes_ptr = context.opcodes_ptr.add(jump_size(wide));
+    Ok(Flow::Next)
+}
+
+unsafe fn halt(_: &mut KaramelCompilerContext) -> Result<Flow, KaramelErrorType> {
+    karamel_print_level2!("Halt");
+    Ok(Flow::Halt)
+}
+
+/* Moves the execution to the function call location without a return value */
+unsafe fn leave_scope(context: &mut KaramelCompilerContext) {
+    context.opcodes_ptr   = (*context.current_scope).location;
+    context.stack_ptr     = (*context.current_scope).top_stack;
+    context.scope_index  -= 1;
+    context.current_scope = context.scopes_ptr.add(context.scope_index);
+}
+
+/* Handler is active while the execution is in its protected range. Upper scopes are checked with their call location. */
+unsafe fn is_handler_active(context: &KaramelCompilerContext, handler: &ErrorHandler) -> bool {
+    let location = match handler.scope_index.cmp(&context.scope_index) {
+        Ordering::Equal => context.opcodes_ptr,
+        Ordering::Less => (*context.scopes_ptr.add(handler.scope_index + 1)).location,
+        Ordering::Greater => return false
+    };
+
+    let position = location.offset_from(context.opcodes.as_ptr()) as usize;
+    handler.start < position && position < handler.catch_location
+}
+
+/* Moves the execution to the catch block of the nearest active 'dene' block. Returns the error back if there is no handler. */
+unsafe fn catch_error(context: &mut KaramelCompilerContext, error: KaramelErrorType) -> Result<(), KaramelErrorType> {
+    /* Limits protect from the endless loops and the interrupt is sent by the user, so they can not be caught */
+    if let KaramelErrorType::LoopIterationLimitExceeded(_) | KaramelErrorType::InstructionLimitExceeded(_) | KaramelErrorType::MemoryLimitExceeded(_) | KaramelErrorType::Interrupted = error {
+        return Err(error);
+    }
+
+    while let Some(handler) = context.error_handlers.last() {
+        if is_handler_active(context, handler) {
+            break;
+        }
+        context.error_handlers.pop();
+    }
+
+    /* Functions between the error and the handler are closed. Their deferred blocks run first, DeferEnd sends the error back here */
+    let handler_scope = context.error_handlers.last().map_or(0, |handler| handler.scope_index);
+    while context.scope_index > handler_scope {
+        let scope = &mut *context.current_scope;
+        if let Some(location) = scope.deferred.pop() {
+            scope.defer_error = Some(error);
+            context.opcodes_ptr = context.opcodes.as_mut_ptr().add(location);
+            return Ok(());
+        }
+        leave_scope(context);
+    }
+
+    if let Some(handler) = context.error_handlers.pop() {
+        let value = match &error {
+            KaramelErrorType::UncaughtError(value) => VmObject::native_convert_by_ref(value.clone()),
+            _ => VmObject::native_convert(KaramelPrimative::Error(ErrorObject::from_error(&error)))
+        };
+        karamel_print_level2!("Catch: {:?}", value);
+
+        context.scope_index   = handler.scope_index;
+        context.current_scope = context.scopes_ptr.add(handler.scope_index);
+        context.stack_ptr     = handler.stack_ptr;
+        context.opcodes_ptr   = context.opcodes.as_mut_ptr().add(handler.catch_location);
+
+        if let Some(variable) = handler.variable {
+            *(*context.current_scope).top_stack.add(variable) = value;
+            if context.events.is_some() {
+                notify_variable_change(context, variable);
+            }
+        }
+        return Ok(());
+    }
+
+    Err(error)
+}
@@ -1,2 +1,12 @@
 pub mod interpreter;
-pub mod executer;
\ No newline at end of file
+pub mod executer;
+pub mod events;
+pub mod crash;
+pub mod program;
+pub mod task;
+pub mod testing;
+pub mod bench;
+pub mod trace;
+pub mod sandbox;
+#[cfg(feature = "ffi")]
+pub mod ffi;
\ No newline at end of file
@@ -0,0 +1,200 @@
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::rc::Rc;
+use std::sync::Arc;
+
+use crate::buildin::DummyModule;
+use crate::compiler::{GetType, KaramelPrimative, StaticStorage};
+use crate::compiler::context::{InlineCache, KaramelCompilerContext, LoopCounter};
+use crate::compiler::function::{FunctionFlag, FunctionReference, FunctionType};
+use crate::error::KaramelErrorType;
+use crate::interner::intern;
+use crate::types::{SourcePosition, VmObject};
+
+/* Constants are kept without 'Rc' so the program can be moved to the other threads. Every execution converts them again. */
+enum ConstantImage {
+    Empty,
+    Number(f64),
+    Bool(bool),
+    Text(Arc<str>),
+    Bytes(Arc<[u8]>),
+    Function(usize),
+    Class(String)
+}
+
+struct FunctionImage {
+    callback: FunctionType,
+    flags: FunctionFlag,
+    name: String,
+    module_path: Vec<String>,
+    arguments: Vec<String>,
+    defined_storage_index: usize,
+    storage_index: usize,
+    opcode_location: usize,
+    documentation: Option<Arc<str>>,
+    definition_position: Option<SourcePosition>,
+    argument_types: Vec<Option<String>>
+}
+
+struct StorageImage {
+    constants: Vec<ConstantImage>,
+    variables: Vec<Arc<str>>,
+    parent_location: Option<usize>
+}
+
+struct ProgramImage {
+    opcodes: Vec<u8>,
+    storages: Vec<StorageImage>,
+    functions: Vec<FunctionImage>,
+    opcode_positions: Vec<(usize, SourcePosition)>,
+    assert_expressions: HashMap<SourcePosition, String>,
+    loop_lines: Vec<u32>,
+    inline_cache_count: usize,
+    teaching_mode: bool,
+    type_guards: bool
+}
+
+/// Opcodes and constants of a compiled code. It is compiled once and executed by many vm instances, also at the same time from different threads.
+#[derive(Clone)]
+pub struct CompiledProgram {
+    image: Arc<ProgramImage>
+}
+
+impl CompiledProgram {
+    pub fn new(context: &KaramelCompilerContext) -> Result<CompiledProgram, KaramelErrorType> {
+        let mut functions: Vec<FunctionImage> = Vec::new();
+        let mut function_indexes: HashMap<*const FunctionReference, usize> = HashMap::new();
+        let mut storages = Vec::with_capacity(context.storages.len());
+
+        for storage in context.storages.iter() {
+            let mut constants = Vec::with_capacity(storage.constants.len());
+            for constant in storage.constants.iter() {
+                constants.push(match &*constant.deref() {
+                    KaramelPrimative::Empty => ConstantImage::Empty,
+                    KaramelPrimative::Number(number) => ConstantImage::Number(*number),
+                    KaramelPrimative::Bool(value) => ConstantImage::Bool(*value),
+                    KaramelPrimative::Text(text) => ConstantImage::Text(Arc::from(text.as_str())),
+                    KaramelPrimative::Bytes(bytes) => ConstantImage::Bytes(Arc::from(bytes.as_slice())),
+                    KaramelPrimative::Class(class) => ConstantImage::Class(class.get_class_name()),
+                    KaramelPrimative::Function(reference, None) => {
+                        let index = *function_indexes.entry(Rc::as_ptr(reference)).or_insert_with(|| {
+                            functions.push(FunctionImage {
+                                callback: reference.callback.clone(),
+                                flags: reference.flags,
+                                name: reference.name.clone(),
+                                module_path: reference.module.get_path().clone(),
+                                arguments: reference.arguments.clone(),
+                                defined_storage_index: reference.defined_storage_index,
+                                storage_index: reference.storage_index,
+                                opcode_location: reference.opcode_location.get(),
+                                documentation: reference.documentation.borrow().as_ref().map(|text| Arc::from(text.as_str())),
+                                definition_position: reference.definition_position.get(),
+                                argument_types: reference.argument_types.borrow().clone()
+                            });
+                            functions.len() - 1
+                        });
+                        ConstantImage::Function(index)
+                    },
+                    primative => return Err(KaramelErrorType::GeneralError(format!("Derlenmiş programa taşınamayan sabit: {}", primative.get_type())))
+                });
+            }
+
+            storages.push(StorageImage {
+                constants,
+                variables: storage.variables.iter().map(|variable| Arc::from(variable.as_str())).collect(),
+                parent_location: storage.parent_location
+            });
+        }
+
+        Ok(CompiledProgram {
+            image: Arc::new(ProgramImage {
+                opcodes: context.opcodes.clone(),
+                storages,
+                functions,
+                opcode_positions: context.opcode_generator.opcode_positions(),
+                assert_expressions: context.assert_expressions.clone(),
+                loop_lines: context.loop_counters.iter().map(|counter| counter.line).collect(),
+                inline_cache_count: context.inline_caches.len(),
+                teaching_mode: context.teaching_mode,
+                type_guards: context.type_guards
+            })
+        })
+    }
+
+    pub fn opcode_size(&self) -> usize {
+        self.image.opcodes.len()
+    }
+
+    /// Functions that are written in the main code with their argument counts, in the source order.
+    pub fn main_functions(&self) -> Vec<(String, usize)> {
+        let mut functions: Vec<&FunctionImage> = self.image.functions.iter()
+            .filter(|function| matches!(function.callback, FunctionType::Opcode) && function.defined_storage_index == 0)
+            .collect();
+        functions.sort_by_key(|function| function.opcode_location);
+        functions.into_iter().map(|function| (function.name.clone(), function.arguments.len())).collect()
+    }
+
+    /// Loads the program into a new context of the current thread. Native functions and classes are taken from the modules of the context.
+    pub fn load(&self, context: &mut KaramelCompilerContext) {
+        let image = &*self.image;
+        context.teaching_mode = image.teaching_mode;
+        context.count_instructions = image.teaching_mode;
+        context.type_guards = image.type_guards;
+
+        context.storages.clear();
+        for (index, storage) in image.storages.iter().enumerate() {
+            let mut static_storage = StaticStorage::new(index);
+            static_storage.variables = storage.variables.iter().map(|variable| intern(&**variable)).collect();
+            static_storage.parent_location = storage.parent_location;
+            context.storages.push(static_storage);
+        }
+
+        let functions: Vec<Rc<FunctionReference>> = image.functions.iter().map(|function| match function.callback {
+            FunctionType::Native(func) => context.get_function(function.name.clone(), &function.module_path, function.defined_storage_index)
+                .unwrap_or_else(|| FunctionReference::native_function(func, function.name.clone(), Rc::new(DummyModule::new()))),
+            FunctionType::Opcode => Rc::new(FunctionReference {
+                callback: FunctionType::Opcode,
+                flags: function.flags,
+                name: function.name.clone(),
+                arguments: function.arguments.clone(),
+                defined_storage_index: function.defined_storage_index,
+                storage_index: function.storage_index,
+                opcode_location: Cell::new(function.opcode_location),
+                used_locations: RefCell::new(Vec::new()),
+                opcode_body: None,
+                module: Rc::new(DummyModule::new()),
+                documentation: RefCell::new(function.documentation.as_ref().map(|text| Rc::new(text.to_string()))),
+                definition_position: Cell::new(function.definition_position),
+                argument_types: RefCell::new(function.argument_types.clone())
+            })
+        }).collect();
+
+        for (storage, storage_image) in context.storages.iter_mut().zip(image.storages.iter()) {
+            for constant in storage_image.constants.iter() {
+                let primative = match constant {
+                    ConstantImage::Empty => KaramelPrimative::Empty,
+                    ConstantImage::Number(number) => KaramelPrimative::Number(*number),
+                    ConstantImage::Bool(value) => KaramelPrimative::Bool(*value),
+                    ConstantImage::Text(text) => KaramelPrimative::Text(intern(&**text)),
+                    ConstantImage::Bytes(bytes) => KaramelPrimative::Bytes(Rc::new(bytes.to_vec())),
+                    ConstantImage::Function(index) => KaramelPrimative::Function(functions[*index].clone(), None),
+                    ConstantImage::Class(name) => match context.primative_classes.iter().find(|class| &class.get_class_name() == name) {
+                        Some(class) => KaramelPrimative::Class(class.clone()),
+                        None => KaramelPrimative::Empty
+                    }
+                };
+                storage.constants.push(VmObject::convert(Rc::new(primative)));
+            }
+        }
+
+        context.storages_ptr = context.storages.as_mut_ptr();
+        context.opcodes = image.opcodes.clone();
+        context.opcodes_ptr = context.opcodes.as_mut_ptr();
+        context.opcodes_top_ptr = context.opcodes_ptr;
+        context.opcode_generator.set_opcode_positions(image.opcode_positions.clone());
+        context.assert_expressions = image.assert_expressions.clone();
+        context.loop_counters = image.loop_lines.iter().map(|line| LoopCounter { line: *line, iterations: 0 }).collect();
+        context.inline_caches = vec![InlineCache::default(); image.inline_cache_count];
+        context.tasks.borrow_mut().set_program(self.clone());
+    }
+}
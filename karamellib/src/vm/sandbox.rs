@@ -0,0 +1,116 @@
+use std::rc::Rc;
+use std::sync::Arc;
+use std::sync::atomic::AtomicBool;
+
+use crate::buildin::Permission;
+use crate::constants::{KARAMEL_SANDBOX_INSTRUCTION_LIMIT, KARAMEL_SANDBOX_MEMORY_LIMIT};
+use crate::error::KaramelRuntimeError;
+use crate::error::diagnostic::{Diagnostic, encode_text};
+use crate::file::{FileSystem, MemoryFileSystem};
+use crate::vm::executer::{ExecutionParameters, ExecutionSource, code_executer};
+
+/// Runs the code of an untrusted user, for example the code of a web playground. The code can not reach the files and
+/// the libraries of the operating system unless their permissions are given, it stops after the instruction limit and
+/// its outputs are returned instead of being written to the console.
+pub struct Sandbox {
+    /// Executed instructions before the execution stops with the 'InstructionLimitExceeded' error
+    pub max_instructions: usize,
+
+    /// Bytes of the texts, bytes and copies that the code can create before the execution stops with the 'MemoryLimitExceeded' error
+    pub max_memory: usize,
+
+    /// Function calls that can be nested. Default depth is used when it is not given.
+    pub max_call_depth: Option<usize>,
+
+    /// Built-in modules of these permissions can be used, none of them are given by default
+    pub permissions: Vec<Permission>,
+
+    /// Files for 'yükle'. The embedded standard library can be loaded without them.
+    pub files: Rc<MemoryFileSystem>,
+
+    /// Lines for 'gç::satıroku'
+    pub stdin: String,
+
+    /// Execution stops with the 'Interrupted' error after the flag is set, a time limit can be applied from another thread with it
+    pub interrupt: Option<Arc<AtomicBool>>
+}
+
+/// Outputs and the diagnostics of a sandboxed execution
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SandboxResult {
+    /// Code is compiled and executed without an error
+    pub success: bool,
+    pub compiled: bool,
+    pub stdout: String,
+    pub stderr: String,
+
+    /// Warnings and the errors in the source order
+    pub diagnostics: Vec<Diagnostic>,
+
+    /// Error of the running code with its line and call stack
+    pub runtime_error: Option<KaramelRuntimeError>
+}
+
+impl Default for Sandbox {
+    fn default() -> Self {
+        Sandbox {
+            max_instructions: KARAMEL_SANDBOX_INSTRUCTION_LIMIT,
+            max_memory: KARAMEL_SANDBOX_MEMORY_LIMIT,
+            max_call_depth: None,
+            permissions: Vec::new(),
+            files: Rc::new(MemoryFileSystem::new()),
+            stdin: String::new(),
+            interrupt: None
+        }
+    }
+}
+
+impl Sandbox {
+    pub fn new() -> Self {
+        Sandbox::default()
+    }
+
+    pub fn run(&self, code: &str) -> SandboxResult {
+        let status = code_executer(ExecutionParameters {
+            return_output: true,
+            file_system: Some(self.files.clone() as Rc<dyn FileSystem>),
+            max_call_depth: self.max_call_depth,
+            max_instructions: Some(self.max_instructions),
+            max_memory: Some(self.max_memory),
+            permissions: Some(self.permissions.clone()),
+            stdin: Some(self.stdin.clone()),
+            interrupt: self.interrupt.clone(),
+            ..ExecutionParameters::new(ExecutionSource::Code(code.to_string()))
+        });
+
+        let diagnostics = status.diagnostics();
+        SandboxResult {
+            success: status.executed,
+            compiled: status.compiled,
+            stdout: status.stdout.map(|output| output.into_inner()).unwrap_or_default(),
+            stderr: status.stderr.map(|output| output.into_inner()).unwrap_or_default(),
+            diagnostics,
+            runtime_error: status.runtime_error
+        }
+    }
+}
+
+impl SandboxResult {
+    /// Single line JSON object with the outputs and the diagnostics
+    pub fn to_json(&self) -> String {
+        let mut buffer = format!("{{\"success\": {}, \"compiled\": {}, \"stdout\": ", self.success, self.compiled);
+        encode_text(&self.stdout, &mut buffer);
+        buffer.push_str(", \"stderr\": ");
+        encode_text(&self.stderr, &mut buffer);
+        buffer.push_str(", \"diagnostics\": [");
+
+        for (index, diagnostic) in self.diagnostics.iter().enumerate() {
+            if index > 0 {
+                buffer.push_str(", ");
+            }
+            buffer.push_str(&diagnostic.to_json());
+        }
+        buffer.push_str("]}");
+        buffer
+    }
+}
@@ -0,0 +1,159 @@
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::mem;
+use std::rc::Rc;
+
+use crate::compiler::KaramelPrimative;
+use crate::compiler::context::KaramelCompilerContext;
+use crate::error::KaramelErrorType;
+use crate::gc;
+use crate::types::VmObject;
+use crate::vm::interpreter::run_function;
+use crate::vm::program::CompiledProgram;
+
+/* Values are kept with 'Rc', so the garbage collector sees them as the roots while they are waiting in the scheduler */
+enum TaskState {
+    Waiting {
+        function: Rc<KaramelPrimative>,
+        arguments: Vec<Rc<KaramelPrimative>>
+    },
+    Running,
+    Finished(Rc<KaramelPrimative>),
+    Failed(KaramelErrorType)
+}
+
+/// Tasks and channels of the 'görev' module. Tasks are cooperative, a task runs until its function returns.
+/// Waiting tasks are started when their result or a value of an empty channel is needed.
+/// Each task runs on its own vm instance that is loaded with the compiled program of the main code.
+#[derive(Default)]
+pub struct TaskScheduler {
+    program: Option<CompiledProgram>,
+    tasks: Vec<TaskState>,
+    channels: Vec<VecDeque<Rc<KaramelPrimative>>>
+}
+
+impl TaskScheduler {
+    pub fn set_program(&mut self, program: CompiledProgram) {
+        self.program = Some(program);
+    }
+}
+
+/// Adds a waiting task that calls the function with the arguments. Returns the number of the task.
+pub fn spawn(context: &KaramelCompilerContext, function: VmObject, arguments: &[VmObject]) -> Result<usize, KaramelErrorType> {
+    let function = function.deref();
+    if !matches!(&*function, KaramelPrimative::Function(..) | KaramelPrimative::Bound(_)) {
+        return Err(KaramelErrorType::NotCallable(function));
+    }
+
+    let mut scheduler = context.tasks.borrow_mut();
+    if scheduler.program.is_none() {
+        scheduler.program = Some(CompiledProgram::new(context)?);
+    }
+
+    scheduler.tasks.push(TaskState::Waiting {
+        function,
+        arguments: arguments.iter().map(|argument| argument.deref()).collect()
+    });
+    Ok(scheduler.tasks.len() - 1)
+}
+
+/// Result of the task. Task is started first if it is still waiting.
+pub fn wait(context: &KaramelCompilerContext, task: usize) -> Result<VmObject, KaramelErrorType> {
+    loop {
+        match context.tasks.borrow().tasks.get(task) {
+            Some(TaskState::Finished(result)) => return Ok(VmObject::from(result.clone())),
+            Some(TaskState::Failed(error)) => return Err(error.clone()),
+            Some(TaskState::Running) => return Err(KaramelErrorType::TaskDeadlock(task)),
+            Some(TaskState::Waiting { .. }) => (),
+            None => return Err(KaramelErrorType::TaskNotFound(task))
+        };
+        run_task(context, task);
+    }
+}
+
+pub fn create_channel(context: &KaramelCompilerContext) -> usize {
+    let mut scheduler = context.tasks.borrow_mut();
+    scheduler.channels.push(VecDeque::new());
+    scheduler.channels.len() - 1
+}
+
+pub fn send(context: &KaramelCompilerContext, channel: usize, value: VmObject) -> Result<(), KaramelErrorType> {
+    match context.tasks.borrow_mut().channels.get_mut(channel) {
+        Some(values) => {
+            values.push_back(value.deref());
+            Ok(())
+        },
+        None => Err(KaramelErrorType::ChannelNotFound(channel))
+    }
+}
+
+/// First value of the channel. Waiting tasks are started one by one while the channel is empty.
+pub fn receive(context: &KaramelCompilerContext, channel: usize) -> Result<VmObject, KaramelErrorType> {
+    loop {
+        let waiting = {
+            let mut scheduler = context.tasks.borrow_mut();
+            match scheduler.channels.get_mut(channel) {
+                Some(values) => if let Some(value) = values.pop_front() {
+                    return Ok(VmObject::from(value));
+                },
+                None => return Err(KaramelErrorType::ChannelNotFound(channel))
+            };
+            scheduler.tasks.iter().position(|task| matches!(task, TaskState::Waiting { .. }))
+        };
+
+        match waiting {
+            Some(task) => run_task(context, task),
+            None => return Err(KaramelErrorType::ChannelEmpty(channel))
+        };
+    }
+}
+
+fn run_task(context: &KaramelCompilerContext, task: usize) {
+    let (program, function, arguments) = {
+        let mut scheduler = context.tasks.borrow_mut();
+        let program = scheduler.program.clone();
+        match (program, mem::replace(&mut scheduler.tasks[task], TaskState::Running)) {
+            (Some(program), TaskState::Waiting { function, arguments }) => (program, function, arguments),
+            (_, state) => {
+                scheduler.tasks[task] = state;
+                return;
+            }
+        }
+    };
+
+    let mut task_context = KaramelCompilerContext::new();
+    task_context.tasks = context.tasks.clone();
+    task_context.file_system = context.file_system.clone();
+    #[cfg(feature = "ffi")]
+    {
+        task_context.foreign = context.foreign.clone();
+    }
+    task_context.interrupt = context.interrupt.clone();
+    task_context.stdin = context.stdin.clone();
+    task_context.count_instructions = context.instruction_limit.is_some();
+    task_context.instruction_limit = context.instruction_limit;
+    task_context.allocation_limit = context.allocation_limit;
+    program.load(&mut task_context);
+
+    if context.stdout.is_some() {
+        task_context.stdout = Some(RefCell::new(String::new()));
+        task_context.stderr = Some(RefCell::new(String::new()));
+    }
+
+    /* Values of the caller vm are not on the stack of the task */
+    gc::pause();
+    let arguments: Vec<VmObject> = arguments.into_iter().map(VmObject::from).collect();
+    let result = unsafe { run_function(&mut task_context, VmObject::from(function), &arguments) };
+    gc::resume();
+
+    for (output, task_output) in [(&context.stdout, &task_context.stdout), (&context.stderr, &task_context.stderr)] {
+        if let (Some(output), Some(task_output)) = (output, task_output) {
+            output.borrow_mut().push_str(&task_output.borrow());
+        }
+    }
+
+    context.tasks.borrow_mut().tasks[task] = match result {
+        Ok(result) => TaskState::Finished(result.deref()),
+        Err(error) => TaskState::Failed(error.error_type)
+    };
+}
@@ -0,0 +1,127 @@
+use std::borrow::Borrow;
+use std::cell::RefCell;
+use std::time::{Duration, Instant};
+
+use crate::compiler::context::KaramelCompilerContext;
+use crate::compiler::value::set_print_precision;
+use crate::constants::{KARAMEL_GC_THRESHOLD, KARAMEL_TEST_PREFIX};
+use crate::error::{KaramelError, KaramelErrorType, KaramelRuntimeError};
+use crate::gc;
+use crate::interner::release_unused;
+use crate::messages;
+use crate::vm::executer::code_program;
+use crate::vm::interpreter::{run_function, run_vm};
+use crate::vm::program::CompiledProgram;
+
+/// Result of a single test function.
+#[derive(Clone, Debug)]
+pub struct TestResult {
+    pub name: String,
+    pub error: Option<KaramelRuntimeError>,
+
+    /// Text that the test function wrote, the output of the main code is not included
+    pub stdout: String,
+    pub duration: Duration
+}
+
+impl TestResult {
+    pub fn passed(&self) -> bool {
+        self.error.is_none()
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct TestReport {
+    pub results: Vec<TestResult>
+}
+
+impl TestReport {
+    pub fn passed(&self) -> usize {
+        self.results.iter().filter(|result| result.passed()).count()
+    }
+
+    pub fn failed(&self) -> usize {
+        self.results.len() - self.passed()
+    }
+
+    /// Table of the tests. Failed tests are followed by their errors, compared values and outputs.
+    pub fn render(&self) -> String {
+        let width = self.results.iter().map(|result| result.name.chars().count()).max().unwrap_or_default();
+        let mut buffer = String::new();
+
+        for result in self.results.iter() {
+            let state = match result.passed() {
+                true => messages::text("test.passed"),
+                false => messages::text("test.failed")
+            };
+            buffer.push_str(&format!("{:<6} {:<width$} {:>8.2} ms\n", state, result.name, result.duration.as_secs_f64() * 1000.0, width = width));
+
+            if let Some(error) = &result.error {
+                /* Failed conditions already have the line in their message */
+                match error.line {
+                    Some(line) if !matches!(error.error_type, KaramelErrorType::AssertFailedWithExpression { .. }) => buffer.push_str(&format!("       {}\n", messages::format("test.error", &[("0", &(line + 1)), ("1", &error.error_type)]))),
+                    _ => buffer.push_str(&format!("       {}\n", error.error_type))
+                };
+
+                if let KaramelErrorType::AssertFailedWithArgument { left, right } = &error.error_type {
+                    buffer.push_str(&format!("       {}\n", messages::format("test.found", &[("0", &format!("{:?}", left))])));
+                    buffer.push_str(&format!("       {}\n", messages::format("test.expected", &[("0", &format!("{:?}", right))])));
+                }
+
+                if !result.stdout.is_empty() {
+                    buffer.push_str(&format!("       {}\n", messages::text("test.output")));
+                    for line in result.stdout.lines() {
+                        buffer.push_str(&format!("         {}\n", line));
+                    }
+                }
+            }
+        }
+
+        buffer.push_str(&messages::format("test.summary", &[("0", &self.results.len()), ("1", &self.passed()), ("2", &self.failed())]));
+        buffer
+    }
+}
+
+/// Finds the functions of the main code that start with 'test_' and runs each of them on a new vm.
+/// Main code runs again before every test, so the tests do not affect each other. Output of the main code is not kept.
+pub fn code_tests<T: Borrow<str>>(data: T) -> Result<TestReport, KaramelError> {
+    Ok(program_tests(&code_program(data)?))
+}
+
+pub fn program_tests(program: &CompiledProgram) -> TestReport {
+    let mut report = TestReport::default();
+    for (name, argument_count) in program.main_functions() {
+        if name.starts_with(KARAMEL_TEST_PREFIX) && argument_count == 0 {
+            report.results.push(run_test(program, name));
+        }
+    }
+    report
+}
+
+fn run_test(program: &CompiledProgram, name: String) -> TestResult {
+    release_unused();
+    gc::reset(KARAMEL_GC_THRESHOLD);
+
+    let mut context = KaramelCompilerContext::new();
+    program.load(&mut context);
+    set_print_precision(context.print_precision);
+    context.stdout = Some(RefCell::new(String::new()));
+    context.stderr = Some(RefCell::new(String::new()));
+
+    let start = Instant::now();
+    let result = unsafe { run_vm(&mut context, false, false) }.and_then(|_| {
+        /* Only the output of the test is kept */
+        context.stdout = Some(RefCell::new(String::new()));
+        match context.get_main_function(&name) {
+            Some(function) => unsafe { run_function(&mut context, function, &[]) },
+            None => Err(KaramelErrorType::FunctionNotFound(name.clone()).into())
+        }
+    });
+
+    TestResult {
+        name,
+        error: result.err(),
+        stdout: context.stdout.take().map(RefCell::into_inner).unwrap_or_default(),
+        duration: start.elapsed()
+    }
+}
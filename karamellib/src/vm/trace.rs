@@ -0,0 +1,95 @@
+use std::collections::VecDeque;
+use std::io::Write;
+
+use crate::error::diagnostic::encode_text;
+use crate::messages;
+
+/// Single executed instruction. Stack sizes are counted from the start of the vm stack, values are written with their debug text.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TraceRecord {
+    /// Index of the instruction in the opcodes
+    pub location: usize,
+
+    /// Source line of the instruction, zero based
+    pub line: Option<u32>,
+    pub opcode: String,
+
+    /// Bytes after the opcode
+    pub operands: Vec<u8>,
+    pub stack_before: usize,
+    pub stack_after: usize,
+
+    /// Last stack value after the instruction
+    pub top: Option<String>,
+
+    /// Location of the next instruction
+    pub next: usize
+}
+
+impl TraceRecord {
+    /// Single line JSON object. Lines are one based at the JSON output.
+    pub fn to_json(&self) -> String {
+        let mut buffer = format!("{{\"location\": {}, \"line\": ", self.location);
+        match self.line {
+            Some(line) => buffer.push_str(&(line + 1).to_string()),
+            None => buffer.push_str("null")
+        };
+        buffer.push_str(", \"opcode\": ");
+        encode_text(&self.opcode, &mut buffer);
+        buffer.push_str(&format!(", \"operands\": {:?}, \"stack\": [{}, {}], \"top\": ", self.operands, self.stack_before, self.stack_after));
+        match &self.top {
+            Some(top) => encode_text(top, &mut buffer),
+            None => buffer.push_str("null")
+        };
+        buffer.push_str(&format!(", \"next\": {}}}", self.next));
+        buffer
+    }
+}
+
+/// Where the executed instructions are kept.
+pub enum TraceOutput {
+    /// Only the last instructions are kept, older ones are dropped. Records are given back with the execution status.
+    Buffer(usize),
+
+    /// Every instruction is written as a JSON line
+    Writer(Box<dyn Write>)
+}
+
+pub struct VmTracer {
+    output: TraceOutput,
+    records: VecDeque<TraceRecord>
+}
+
+impl VmTracer {
+    pub fn new(output: TraceOutput) -> Self {
+        VmTracer {
+            records: match output {
+                TraceOutput::Buffer(capacity) => VecDeque::with_capacity(capacity.max(1)),
+                TraceOutput::Writer(_) => VecDeque::new()
+            },
+            output
+        }
+    }
+
+    pub fn record(&mut self, record: TraceRecord) {
+        match &mut self.output {
+            TraceOutput::Buffer(capacity) => {
+                if self.records.len() >= (*capacity).max(1) {
+                    self.records.pop_front();
+                }
+                self.records.push_back(record);
+            },
+            TraceOutput::Writer(writer) => if let Err(error) = writeln!(writer, "{}", record.to_json()) {
+                log::error!("{}", messages::format("trace.write_failed", &[("0", &error)]));
+            }
+        };
+    }
+
+    /// Writes the remaining output and gives back the kept records.
+    pub fn finish(&mut self) -> Vec<TraceRecord> {
+        if let TraceOutput::Writer(writer) = &mut self.output {
+            let _ = writer.flush();
+        }
+        self.records.drain(..).collect()
+    }
+}
@@ -0,0 +1,102 @@
+use std::rc::Rc;
+
+use js_sys::{Array, Object, Reflect};
+use strum::EnumMessage;
+use wasm_bindgen::prelude::*;
+
+use crate::compiler::KaramelPrimative;
+use crate::error::KaramelError;
+use crate::file::{FileSystem, MemoryFileSystem};
+use crate::vm::executer::{ExecutionParameters, ExecutionSource, code_compiler, code_executer};
+
+fn set_field<T: Into<JsValue>>(object: &Object, name: &str, value: T) {
+    Reflect::set(object.as_ref(), &JsValue::from_str(name), &value.into()).unwrap();
+}
+
+fn build_error(error: &KaramelError) -> Object {
+    let item = Object::new();
+    set_field(&item, "line", error.line);
+    set_field(&item, "column", error.column);
+    set_field(&item, "code", error.error_type.get_message().unwrap_or_default());
+    set_field(&item, "message", format!("{}", error.error_type));
+    item
+}
+
+fn build_errors(error: Option<&KaramelError>) -> Array {
+    let errors = Array::new();
+    if let Some(error) = error {
+        errors.push(&build_error(error));
+    }
+    errors
+}
+
+/// Compiles the source code and returns `{ status, errors }` without executing it.
+#[wasm_bindgen]
+pub fn compile(source: &str) -> Object {
+    let response = Object::new();
+    let result = code_compiler(source);
+
+    set_field(&response, "status", result.is_ok());
+    set_field(&response, "errors", build_errors(result.as_ref().err()));
+    response
+}
+
+/// Compiles and executes the source code. Returns `{ status, stdout, stderr, results, errors }`.
+#[wasm_bindgen]
+pub fn run(source: &str) -> Object {
+    execute(source, None)
+}
+
+/// Same as `run`, but `files` (`{ "path": "content" }`) is used as the file system for `yükle` and the `dosya` module.
+#[wasm_bindgen]
+pub fn run_with_files(source: &str, files: &Object) -> Object {
+    let file_system = MemoryFileSystem::new();
+    for entry in Object::entries(files).iter() {
+        let entry = Array::from(&entry);
+        if let (Some(path), Some(content)) = (entry.get(0).as_string(), entry.get(1).as_string()) {
+            file_system.add_file(path, content);
+        }
+    }
+
+    execute(source, Some(Rc::new(file_system)))
+}
+
+fn execute(source: &str, file_system: Option<Rc<dyn FileSystem>>) -> Object {
+    let response = Object::new();
+    let parameters = ExecutionParameters {
+        return_output: true,
+        file_system,
+        ..ExecutionParameters::new(ExecutionSource::Code(source.to_string()))
+    };
+
+    let result = code_executer(parameters);
+    let results = Array::new();
+
+    if let Some(objects) = &result.memory_output {
+        for object in objects.iter() {
+            match &*object.deref() {
+                KaramelPrimative::Text(text) => results.push(&JsValue::from_str(text)),
+                KaramelPrimative::Number(number) => results.push(&JsValue::from_f64(*number)),
+                KaramelPrimative::Bool(bool) => results.push(&JsValue::from_bool(*bool)),
+                _ => results.push(&JsValue::undefined())
+            };
+        }
+    }
+
+    let stdout = match &result.stdout {
+        Some(stdout) => stdout.borrow().clone(),
+        None => String::new()
+    };
+
+    let stderr = match &result.stderr {
+        Some(stderr) => stderr.borrow().clone(),
+        None => String::new()
+    };
+
+    set_field(&response, "status", result.compiled && result.executed);
+    set_field(&response, "stdout", stdout);
+    set_field(&response, "stderr", stderr);
+    set_field(&response, "results", results);
+    set_field(&response, "errors", build_errors(result.error.as_ref()));
+    response
+}
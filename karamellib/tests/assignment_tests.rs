@@ -41,4 +41,25 @@ mod tests {
             right: Rc::new(KaramelAstType::Primative(Rc::new(KaramelPrimative::Number(2.0))))
         })
     })));
-}
\ No newline at end of file
+
+    test_compare!(assignment_3, "liste_[0] = 5", Ok(Rc::new(KaramelAstType::Assignment {
+        variable: Rc::new(KaramelAstType::Indexer {
+            body: Rc::new(KaramelAstType::Symbol("liste_".to_string())),
+            indexer: Rc::new(KaramelAstType::Primative(Rc::new(KaramelPrimative::Number(0.0))))
+        }),
+        operator: KaramelOperatorType::Assign,
+        expression: Rc::new(KaramelAstType::Primative(Rc::new(KaramelPrimative::Number(5.0))))
+    })));
+
+    test_compare!(assignment_4, "sözlük['a']['b'] = 1", Ok(Rc::new(KaramelAstType::Assignment {
+        variable: Rc::new(KaramelAstType::Indexer {
+            body: Rc::new(KaramelAstType::Indexer {
+                body: Rc::new(KaramelAstType::Symbol("sözlük".to_string())),
+                indexer: Rc::new(KaramelAstType::Primative(Rc::new(KaramelPrimative::Text(Rc::new("a".to_string())))))
+            }),
+            indexer: Rc::new(KaramelAstType::Primative(Rc::new(KaramelPrimative::Text(Rc::new("b".to_string())))))
+        }),
+        operator: KaramelOperatorType::Assign,
+        expression: Rc::new(KaramelAstType::Primative(Rc::new(KaramelPrimative::Number(1.0))))
+    })));
+}
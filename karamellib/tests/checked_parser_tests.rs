@@ -0,0 +1,44 @@
+extern crate karamellib;
+
+#[cfg(test)]
+mod tests {
+    use crate::karamellib::syntax::*;
+    use crate::karamellib::error::*;
+
+    #[warn(unused_macros)]
+    macro_rules! test_error {
+        ($name:ident, $text:expr, $error:pat) => {
+            #[test]
+            fn $name () {
+                let result = parse_checked($text);
+                assert!(matches!(result.map_err(|error| error.error_type), Err($error)));
+            }
+        };
+    }
+
+    #[warn(unused_macros)]
+    macro_rules! test_success {
+        ($name:ident, $text:expr) => {
+            #[test]
+            fn $name () {
+                assert!(parse_checked($text).is_ok());
+            }
+        };
+    }
+
+    test_error!(checked_1, "4[", KaramelErrorType::IndexerNotClosed);
+    test_error!(checked_2, "a[1", KaramelErrorType::IndexerNotClosed);
+    test_error!(checked_3, "a = -", KaramelErrorType::InvalidUnaryOperation);
+    test_error!(checked_4, "a = !", KaramelErrorType::InvalidUnaryOperation);
+    test_error!(checked_5, "gç::", _);
+    test_error!(checked_6, "a = [1, ", _);
+    test_error!(checked_7, "a = {'a': ", _);
+    test_error!(checked_8, "doğru ise:\n", _);
+    test_error!(checked_9, &format!("a = {}1{}", "(".repeat(100), ")".repeat(100)), KaramelErrorType::NestingTooDeep(_));
+    test_error!(checked_10, &format!("a = {}1{}", "[".repeat(100), "]".repeat(100)), KaramelErrorType::NestingTooDeep(_));
+    test_error!(checked_11, &format!("a = {}doğru", "!".repeat(100)), KaramelErrorType::NestingTooDeep(_));
+    test_error!(checked_12, &(0..100).map(|index| format!("{}doğru ise:\n", "    ".repeat(index))).collect::<String>(), KaramelErrorType::NestingTooDeep(_));
+    test_success!(checked_13, "a = [1, 2][0]\n\n");
+    test_success!(checked_14, &format!("a = {}1{}", "(".repeat(30), ")".repeat(30)));
+    test_success!(checked_15, "döngü doğru:\n    kır\n");
+}
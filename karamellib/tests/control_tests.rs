@@ -69,4 +69,28 @@ mod tests {
         operator: KaramelOperatorType::Or, 
         right: Rc::new(KaramelAstType::Primative(Rc::new(KaramelPrimative::Number(10.0))))
     })));
-}
\ No newline at end of file
+
+    test_compare!(chained_1, "1 < x <= 10", Ok(Rc::new(KaramelAstType::Control {
+        left: Rc::new(KaramelAstType::Control {
+            left: Rc::new(KaramelAstType::Symbol("x".to_string())),
+            operator: KaramelOperatorType::GreaterThan,
+            right: Rc::new(KaramelAstType::Primative(Rc::new(KaramelPrimative::Number(1.0))))
+        }),
+        operator: KaramelOperatorType::And,
+        right: Rc::new(KaramelAstType::Control {
+            left: Rc::new(KaramelAstType::Primative(Rc::new(KaramelPrimative::Number(10.0)))),
+            operator: KaramelOperatorType::GreaterEqualThan,
+            right: Rc::new(KaramelAstType::Symbol("x".to_string()))
+        })
+    })));
+
+    test_compare!(chained_2, "1 == 1 != 2", Ok(Rc::new(KaramelAstType::Control {
+        left: Rc::new(KaramelAstType::Control {
+            left: Rc::new(KaramelAstType::Primative(Rc::new(KaramelPrimative::Number(1.0)))),
+            operator: KaramelOperatorType::Equal,
+            right: Rc::new(KaramelAstType::Primative(Rc::new(KaramelPrimative::Number(1.0))))
+        }),
+        operator: KaramelOperatorType::NotEqual,
+        right: Rc::new(KaramelAstType::Primative(Rc::new(KaramelPrimative::Number(2.0))))
+    })));
+}
@@ -33,6 +33,10 @@ fonk test():
     erhan=123"#, Ok(Rc::new(KaramelAstType::FunctionDefination {
         name: "test".to_string(),
         arguments: Vec::new(),
+        argument_types: Vec::new(),
+        decorators: Vec::new(),
+        private: false,
+        documentation: None,
         body: Rc::new(KaramelAstType::Block([Rc::new(KaramelAstType::Assignment {
             variable: Rc::new(KaramelAstType::Symbol("erhan".to_string())),
             operator: KaramelOperatorType::Assign,
@@ -45,6 +49,10 @@ fonk test(a):
     erhan=123"#, Ok(Rc::new(KaramelAstType::FunctionDefination {
         name: "test".to_string(),
         arguments: ["a".to_string()].to_vec(),
+        argument_types: [None].to_vec(),
+        decorators: Vec::new(),
+        private: false,
+        documentation: None,
         body: Rc::new(KaramelAstType::Block([Rc::new(KaramelAstType::Assignment {
             variable: Rc::new(KaramelAstType::Symbol("erhan".to_string())),
             operator: KaramelOperatorType::Assign,
@@ -57,6 +65,10 @@ fonk test(a, b    ,   c):
     erhan=123"#, Ok(Rc::new(KaramelAstType::FunctionDefination {
         name: "test".to_string(),
         arguments: ["a".to_string(), "b".to_string(), "c".to_string()].to_vec(),
+        argument_types: [None, None, None].to_vec(),
+        decorators: Vec::new(),
+        private: false,
+        documentation: None,
         body: Rc::new(KaramelAstType::Block([Rc::new(KaramelAstType::Assignment {
             variable: Rc::new(KaramelAstType::Symbol("erhan".to_string())),
             operator: KaramelOperatorType::Assign,
@@ -69,6 +81,10 @@ fonk test:
     erhan=123"#, Ok(Rc::new(KaramelAstType::FunctionDefination {
             name: "test".to_string(),
             arguments: Vec::new(),
+            argument_types: Vec::new(),
+            decorators: Vec::new(),
+            private: false,
+            documentation: None,
             body: Rc::new(KaramelAstType::Block([Rc::new(KaramelAstType::Assignment {
                 variable: Rc::new(KaramelAstType::Symbol("erhan".to_string())),
                 operator: KaramelOperatorType::Assign,
@@ -84,6 +100,10 @@ fonk test   :
         erhan=123"#, Ok(Rc::new(KaramelAstType::FunctionDefination {
                 name: "test".to_string(),
                 arguments: Vec::new(),
+                argument_types: Vec::new(),
+                decorators: Vec::new(),
+                private: false,
+                documentation: None,
                 body: Rc::new(KaramelAstType::Block([Rc::new(KaramelAstType::Assignment {
                     variable: Rc::new(KaramelAstType::Symbol("erhan".to_string())),
                     operator: KaramelOperatorType::Assign,
@@ -157,6 +177,10 @@ fonk test():
     döndür erhan"#, Ok(Rc::new(KaramelAstType::FunctionDefination {
     name: "test".to_string(),
     arguments: Vec::new(),
+    argument_types: Vec::new(),
+    decorators: Vec::new(),
+    private: false,
+    documentation: None,
     body: Rc::new(KaramelAstType::Block([Rc::new(KaramelAstType::Assignment {
         variable: Rc::new(KaramelAstType::Symbol("erhan".to_string())),
         operator: KaramelOperatorType::Assign,
@@ -170,6 +194,10 @@ fonk test():
     döndür"#, Ok(Rc::new(KaramelAstType::FunctionDefination {
     name: "test".to_string(),
     arguments: Vec::new(),
+    argument_types: Vec::new(),
+    decorators: Vec::new(),
+    private: false,
+    documentation: None,
     body: Rc::new(KaramelAstType::Block([Rc::new(KaramelAstType::Assignment {
         variable: Rc::new(KaramelAstType::Symbol("erhan".to_string())),
         operator: KaramelOperatorType::Assign,
@@ -177,4 +205,74 @@ fonk test():
     }),
     Rc::new(KaramelAstType::Return(Rc::new(KaramelAstType::None)))].to_vec()))
 })));
-}
\ No newline at end of file
+test_compare!(func_def_17, r#"
+## İki sayıyı toplar.
+##   Sonucu döndürür.
+fonk test():
+    döndür"#, Ok(Rc::new(KaramelAstType::FunctionDefination {
+    name: "test".to_string(),
+    arguments: Vec::new(),
+    argument_types: Vec::new(),
+    decorators: Vec::new(),
+    private: false,
+    documentation: Some(Rc::new("İki sayıyı toplar.\nSonucu döndürür.".to_string())),
+    body: Rc::new(KaramelAstType::Return(Rc::new(KaramelAstType::None)))
+})));
+test_compare!(func_def_18, r#"
+fonk test(a: sayı, b, c: boş):
+    döndür"#, Ok(Rc::new(KaramelAstType::FunctionDefination {
+    name: "test".to_string(),
+    arguments: ["a".to_string(), "b".to_string(), "c".to_string()].to_vec(),
+    argument_types: [Some("sayı".to_string()), None, Some("boş".to_string())].to_vec(),
+    decorators: Vec::new(),
+    private: false,
+    documentation: None,
+    body: Rc::new(KaramelAstType::Return(Rc::new(KaramelAstType::None)))
+})));
+test_compare!(func_def_19, r#"
+fonk test(a: tamsayı):
+    döndür"#, Err(KaramelError {
+    error_type: KaramelErrorType::UnknownTypeName("tamsayı".to_string()),
+    column: 20,
+    line: 1
+}));
+test_compare!(func_def_20, r#"
+## Bir ekler.
+@duyur
+@kayıt::ekle
+fonk test():
+    döndür"#, Ok(Rc::new(KaramelAstType::FunctionDefination {
+    name: "test".to_string(),
+    arguments: Vec::new(),
+    argument_types: Vec::new(),
+    decorators: [Rc::new(KaramelAstType::Symbol("duyur".to_string())), Rc::new(KaramelAstType::ModulePath(["kayıt".to_string(), "ekle".to_string()].to_vec()))].to_vec(),
+    private: false,
+    documentation: Some(Rc::new("Bir ekler.".to_string())),
+    body: Rc::new(KaramelAstType::Return(Rc::new(KaramelAstType::None)))
+})));
+test_compare!(func_def_21, r#"
+@duyur
+a = 1"#, Err(KaramelError {
+    error_type: KaramelErrorType::DecoratorWithoutFunction,
+    column: 1,
+    line: 2
+}));
+test_compare!(func_def_22, r#"
+@duyur
+gizli fonk test():
+    döndür"#, Ok(Rc::new(KaramelAstType::FunctionDefination {
+    name: "test".to_string(),
+    arguments: Vec::new(),
+    argument_types: Vec::new(),
+    decorators: [Rc::new(KaramelAstType::Symbol("duyur".to_string()))].to_vec(),
+    private: true,
+    documentation: None,
+    body: Rc::new(KaramelAstType::Return(Rc::new(KaramelAstType::None)))
+})));
+test_compare!(func_def_23, r#"
+gizli a = 1"#, Err(KaramelError {
+    error_type: KaramelErrorType::PrivateWithoutFunction,
+    column: 7,
+    line: 1
+}));
+}
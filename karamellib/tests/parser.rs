@@ -4,6 +4,7 @@ extern crate karamellib;
 mod tests {
     use crate::karamellib::parser::*;
     use crate::karamellib::types::*;
+    use crate::karamellib::error::diagnostic::Span;
 
     #[warn(unused_macros)]
     macro_rules! test_number {
@@ -135,6 +136,86 @@ mod tests {
         }
     }
 
+    #[test]
+    fn text_escapes() {
+        let mut parser = Parser::new(r#"'a\nb\t\'c\'' "\"\\\u{11F}\u{1F600}""#);
+        assert!(parser.parse().is_ok());
+        let tokens = parser.tokens();
+        match (&tokens[0].token_type, &tokens[2].token_type) {
+            (KaramelTokenType::Text(first), KaramelTokenType::Text(second)) => {
+                assert_eq!(**first, "a\nb\t'c'");
+                assert_eq!(**second, "\"\\ğ😀");
+            },
+            _ => assert!(false)
+        };
+    }
+
+    #[test]
+    fn multi_line_text() {
+        let mut parser = Parser::new("a = \"\"\"{\n  \"ad\": 'Ali'\\n\n}\"\"\"\nb = '''tek'''");
+        assert!(parser.parse().is_ok());
+        let tokens = parser.tokens();
+        match &tokens[4].token_type {
+            KaramelTokenType::Text(text) => assert_eq!(**text, "{\n  \"ad\": 'Ali'\n\n}"),
+            _ => assert!(false)
+        };
+        assert_eq!((tokens[4].line, tokens[4].start, tokens[4].end), (0, 4, 4));
+        assert_eq!(tokens[6].line, 3);
+
+        match &tokens[10].token_type {
+            KaramelTokenType::Text(text) => assert_eq!(**text, "tek"),
+            _ => assert!(false)
+        };
+
+        let error = Parser::new("a = '''\nkapanmadı''").parse().unwrap_err();
+        assert_eq!(error.error_type, karamellib::error::KaramelErrorType::MissingStringDeliminator);
+    }
+
+    #[test]
+    fn text_invalid_escapes() {
+        for (code, escape, column) in [("'ab\\q'", "\\q", 3), ("a = '\\u{110000}'", "\\u{110000}", 5), ("'\\u{zz}'", "\\u{zz}", 1), ("'\\u41'", "\\u4", 1)] {
+            let error = Parser::new(code).parse().unwrap_err();
+            assert_eq!(error.error_type, karamellib::error::KaramelErrorType::InvalidEscape(escape.to_string()), "{}", code);
+            assert_eq!((error.line, error.column), (0, column), "{}", code);
+        }
+    }
+
+    #[test]
+    fn token_limits() {
+        use karamellib::error::KaramelErrorType;
+
+        let limits = TokenLimits { symbol_length: 5, text_length: 5, line_length: 20 };
+        for (code, error_type, position) in [
+            ("a = çokuzun", KaramelErrorType::SymbolTooLong(5), (0, 4)),
+            ("a = 1\nb = 'merhaba'", KaramelErrorType::TextTooLong(5), (1, 4)),
+            ("a = 1 + 2 + 3 + 4 + 5 + 6", KaramelErrorType::LineTooLong(20), (0, 20))] {
+            let mut parser = Parser::new(code);
+            parser.set_limits(limits);
+            let error = parser.parse().unwrap_err();
+            assert_eq!(error.error_type, error_type, "{}", code);
+            assert_eq!((error.line, error.column), position, "{}", code);
+        }
+
+        /* Limits are inclusive */
+        let mut parser = Parser::new("abcde = 'abcde'\nb = '''12\n34'''");
+        parser.set_limits(limits);
+        assert!(parser.parse().is_ok());
+    }
+
+    #[test]
+    fn unterminated_text_in_big_file() {
+        use karamellib::error::KaramelErrorType;
+
+        /* Reading stops at the text limit instead of the end of the file */
+        let code = format!("a = 1\nb = '{}", "x".repeat(10_000_000));
+        let error = Parser::new(&code).parse().unwrap_err();
+        assert_eq!(error.error_type, KaramelErrorType::TextTooLong(TokenLimits::default().text_length));
+        assert_eq!((error.line, error.column), (1, 4));
+
+        let code = format!("a = '{}'", "\\u{41".repeat(200_000));
+        assert!(matches!(Parser::new(&code).parse().unwrap_err().error_type, KaramelErrorType::InvalidEscape(_)));
+    }
+
     #[test]
     fn keywords() {
         let mut parser = Parser::new("_test_");
@@ -177,6 +258,32 @@ mod tests {
         }
     }
 
+    #[test]
+    fn turkish_symbol() {
+        for (code, expected) in [("değer2", "değer2"), ("ışık_hızı_2x", "ışık_hızı_2x"), ("istanbul'un_nüfusu", "istanbul'un_nüfusu"), ("I\u{0307}stanbul", "İstanbul"), ("s\u{0327}eker", "şeker")] {
+            let mut parser = Parser::new(code);
+            assert!(parser.parse().is_ok());
+            let tokens = parser.tokens();
+
+            assert_eq!(1, tokens.len());
+            match &tokens[0].token_type {
+                KaramelTokenType::Symbol(symbol) => assert_eq!(expected, **symbol),
+                _ => assert_eq!(true, false)
+            }
+        }
+
+        let mut parser = Parser::new("ad'");
+        assert!(parser.parse().is_err());
+
+        let mut parser = Parser::new("a='b'");
+        assert!(parser.parse().is_ok());
+        assert_eq!(parser.tokens().len(), 3);
+
+        let mut parser = Parser::new("dog\u{0306}ru");
+        assert!(parser.parse().is_ok());
+        assert_eq!(parser.tokens()[0].token_type, KaramelTokenType::Keyword(KaramelKeywordType::True));
+    }
+
     #[test]
     fn new_line_1() {
         let mut parser = Parser::new("\n");
@@ -246,6 +353,35 @@ mod tests {
     test_comment!(comment_5, "/* // */");
     parse_failed!(comment_6, "/*");
 
+    #[test]
+    fn comment_with_tokens() {
+        let mut parser = Parser::with_comments("a = 1 // merhaba\n/* dünya */");
+        match parser.parse() {
+            Err(_) => assert_eq!(true, false),
+            _ => ()
+        };
+        let tokens = parser.tokens();
+        let comments: Vec<String> = tokens.iter().filter_map(|token| match &token.token_type {
+            KaramelTokenType::Comment(comment) => Some(comment.to_string()),
+            _ => None
+        }).collect();
+
+        assert_eq!(comments, vec!["// merhaba".to_string(), "/* dünya */".to_string()]);
+        assert_eq!(tokens.last().unwrap().line, 1);
+    }
+
+    #[test]
+    fn doc_comment() {
+        let mut parser = Parser::new("## merhaba dünya \nfonk");
+        match parser.parse() {
+            Err(_) => assert_eq!(true, false),
+            _ => ()
+        };
+        let tokens = parser.tokens();
+        assert_eq!(tokens[0].token_type, KaramelTokenType::DocComment(std::rc::Rc::new("merhaba dünya".to_string())));
+        assert_eq!(tokens.last().unwrap().token_type, KaramelTokenType::Keyword(KaramelKeywordType::Fn));
+    }
+
     parse_failed!(operator_1, "#");
 
     test_number!(integer_1, Integer, "1024", 1024);
@@ -257,18 +393,35 @@ mod tests {
     test_number!(integer_7, Integer, "1_234_5_6_7", 1234567);
     test_number!(integer_8, Integer, "1_234_5_6_7_", 1234567);
     parse_failed!(integer_9, "1024erhan");
+    test_number!(integer_10, Integer, "1_000_000", 1000000);
+    test_number!(integer_11, Double, "100_000_000_000_000_000_000", 1e20);
 
     test_number!(hex_1, Integer, "0x12", 18);
     test_number!(hex_2, Integer, "0xffffff", 16777215);
     test_number!(hex_3, Integer, "0x1FFFFFFFFFFFFF", 9007199254740991);
+    test_number!(hex_4, Integer, "0xFF_FF", 65535);
+    test_number!(hex_5, Integer, "0X7fffffffffffffff", 9223372036854775807);
+    parse_failed!(hex_6, "0x");
+    parse_failed!(hex_7, "0xFG");
+    parse_failed!(hex_8, "0xFFFFFFFFFFFFFFFF");
+    parse_failed!(hex_9, "0x_1");
 
     test_number!(oct_1, Integer, "062", 50);
     test_number!(oct_2, Integer, "06211111111111", 430723863113);
+    test_number!(oct_3, Integer, "0o17", 15);
+    test_number!(oct_4, Integer, "0O1_7", 15);
+    parse_failed!(oct_5, "0o18");
+    test_number!(oct_6, Integer, "0128", 128);
+    test_number!(oct_7, Integer, "0123456789", 123456789);
+    test_number!(oct_8, Double, "0777.5", 777.5);
 
     test_number!(binary_1, Integer, "0b10000000000000000000000000000000", 2147483648);
     test_number!(binary_2, Integer, "0b01111111100000000000000000000000", 2139095040);
     test_number!(binary_3, Integer, "0b01", 1);
     test_number!(binary_4, Integer, "0B00000000011111111111111111111111", 8388607);
+    test_number!(binary_5, Integer, "0b1010_1010", 170);
+    parse_failed!(binary_6, "0b102");
+    parse_failed!(binary_7, "0b");
 
 
     test_number!(double_1, Double, "1024.0", 1024.0);
@@ -292,7 +445,176 @@ mod tests {
     test_number!(double_5, Double, "1_23.4e+4", 1234000.0);
     test_number!(double_6, Double, "1_23.4_e+4_", 1234000.0);
     test_number!(double_7, Double, "09__9_999.9_", 99999.9);
+    test_number!(double_8, Double, "3.14159265358979323846", std::f64::consts::PI);
+    parse_failed!(double_9, "1.5e");
+    test_number!(double_10, Double, "1.5e10", 15000000000.0);
+    test_number!(double_11, Double, "1e3", 1000.0);
+    test_number!(double_12, Double, "2E-3", 0.002);
+    test_number!(double_13, Double, "1_0e+1_0", 100000000000.0);
+    parse_failed!(double_14, "1e");
+    parse_failed!(double_15, "3e+a");
 
     test_keyword!(keyword_2, "doğru", KaramelKeywordType::True);
     test_keyword!(keyword_4, "yanlış", KaramelKeywordType::False);
-}
\ No newline at end of file
+
+    #[test]
+    fn token_stream() {
+        let code = "fonk topla(a, b):\n    döndür a + b\ngç::satıryaz(topla(1, 2.5e1))";
+        let mut parser = Parser::new(code);
+        parser.parse().unwrap();
+
+        let tokens: Vec<Token> = Parser::new(code).into_iter().map(|token| token.unwrap()).collect();
+        assert_eq!(format!("{:?}", tokens), format!("{:?}", parser.tokens()));
+    }
+
+    #[test]
+    fn token_stream_error() {
+        let mut stream = Parser::new("a = 0b102").into_iter();
+        assert!(stream.next().unwrap().is_ok());
+        assert!(stream.next().unwrap().is_ok());
+        assert!(stream.next().unwrap().is_ok());
+        assert!(stream.next().unwrap().is_ok());
+
+        let error = stream.next().unwrap().unwrap_err();
+        assert_eq!(error.code, "160");
+        assert!(stream.next().is_none());
+    }
+
+    fn retokenize(old: &str, new: &str, edit: LineEdit) -> TokenDelta {
+        let mut parser = Parser::new(old);
+        parser.parse().unwrap();
+        let mut tokens = parser.tokens();
+
+        let mut parser = Parser::new(new);
+        let delta = parser.retokenize(&tokens, &edit).unwrap();
+        delta.apply(&mut tokens);
+
+        parser.parse().unwrap();
+        assert_eq!(format!("{:?}", tokens), format!("{:?}", parser.tokens()));
+        delta
+    }
+
+    #[test]
+    fn retokenize_changed_line() {
+        let old = "a = 1\nb = 2\nc = 3\nd = 4";
+        let new = "a = 1\nb = 22 + 1\nc = 3\nd = 4";
+        let delta = retokenize(old, new, LineEdit { start_line: 1, old_end_line: 2, new_end_line: 2 });
+        assert_eq!(delta.start, 5);
+        assert_eq!(delta.removed, 7);
+        assert_eq!(delta.inserted.len(), 11);
+    }
+
+    #[test]
+    fn retokenize_inserted_lines() {
+        let old = "fonk topla(a, b):\n    döndür a + b\ngç::satıryaz(topla(1, 2))";
+        let new = "fonk topla(a, b):\n    c = a\n    c += b\n    döndür c\ngç::satıryaz(topla(1, 2))";
+        let delta = retokenize(old, new, LineEdit { start_line: 1, old_end_line: 2, new_end_line: 4 });
+        assert_eq!(delta.line_shift, 2);
+    }
+
+    #[test]
+    fn retokenize_removed_lines() {
+        let old = "a = 1\nb = 2\nc = 3\nd = 4";
+        let new = "a = 1\nd = 4";
+        retokenize(old, new, LineEdit { start_line: 1, old_end_line: 3, new_end_line: 1 });
+    }
+
+    #[test]
+    fn retokenize_indentation() {
+        let old = "eğer a:\n    b = 1\nc = 2";
+        let new = "eğer a:\n    b = 1\n    c = 2";
+        retokenize(old, new, LineEdit { start_line: 2, old_end_line: 3, new_end_line: 3 });
+    }
+
+    #[test]
+    fn retokenize_multiline_text() {
+        /* The edited line is inside a text, so the whole source is tokenized again */
+        let old = "a = 'bir\niki'\nb = 2";
+        let new = "a = 'bir\nüç'\nb = 2";
+        let delta = retokenize(old, new, LineEdit { start_line: 1, old_end_line: 2, new_end_line: 2 });
+        assert_eq!(delta.start, 0);
+
+        let old = "a = 1\nb = 2";
+        let new = "a = 1\nb = 'iki\nüç'";
+        retokenize(old, new, LineEdit { start_line: 1, old_end_line: 2, new_end_line: 3 });
+    }
+
+    #[test]
+    fn retokenize_error() {
+        let mut parser = Parser::new("a = 1");
+        parser.parse().unwrap();
+
+        let parser = Parser::new("a = 0b102");
+        assert!(parser.retokenize(&parser.tokens(), &LineEdit { start_line: 0, old_end_line: 1, new_end_line: 1 }).is_err());
+    }
+
+    #[test]
+    fn reader_token_stream() {
+        let sources = [
+            "fonk topla(a, b):\n    döndür a + b\ngç::satıryaz(topla(1, 2.5e1))\n",
+            "a = 'bir\niki\n    üç'\nb = \"dört\"",
+            "/* bir\n   iki */\n    a = 1 // yorum\n\n## belge\nb = 2",
+            "",
+            "\n\n",
+            "#!/usr/bin/env karamelapp\na = 1",
+            "\u{feff}a = 1"
+        ];
+
+        for source in sources.iter() {
+            let mut parser = Parser::new(source);
+            parser.parse().unwrap();
+
+            let tokens: Vec<Token> = Parser::from_reader(source.as_bytes()).map(|token| token.unwrap()).collect();
+            assert_eq!(format!("{:?}", tokens), format!("{:?}", parser.tokens()));
+        }
+    }
+
+    #[test]
+    fn reader_token_stream_error() {
+        let source = "a = 1\nb = 'iki\nc = 3";
+        let error = Parser::new(source).parse().unwrap_err();
+
+        let mut stream = Parser::from_reader(source.as_bytes());
+        let diagnostic = stream.find(|token| token.is_err()).unwrap().unwrap_err();
+        assert_eq!(diagnostic.code, "106");
+        assert_eq!(diagnostic.span, Span::point(error.line, error.column));
+        assert!(stream.next().is_none());
+    }
+
+    #[test]
+    fn shebang_and_bom() {
+        let mut parser = Parser::new("\u{feff}#!/usr/bin/env karamelapp\na = 1");
+        parser.parse().unwrap();
+        let tokens = parser.tokens();
+
+        /* New line of the '#!' line is kept, so the line numbers are the same with the file */
+        assert_eq!(tokens.len(), 6);
+        assert!(matches!(tokens[0].token_type, KaramelTokenType::NewLine(0)));
+        assert_eq!(tokens[1].line, 1);
+        assert_eq!(tokens[1].start, 0);
+
+        let mut parser = Parser::new("\u{feff}a = 1");
+        parser.parse().unwrap();
+        assert_eq!(parser.tokens().len(), 5);
+        assert_eq!(parser.tokens()[0].start, 0);
+
+        /* Only the first line can be a '#!' line */
+        assert!(Parser::new("a = 1\n#!/usr/bin/env karamelapp").parse().is_err());
+        assert_eq!(script_start("#!karamelapp"), 12);
+    }
+
+    #[test]
+    fn interned_symbols() {
+        let mut parser = Parser::new("sayaç = 'sayaç'\nsayaç += 1");
+        parser.parse().unwrap();
+        let tokens = parser.tokens();
+
+        match (&tokens[0].token_type, &tokens[4].token_type, &tokens[6].token_type) {
+            (KaramelTokenType::Symbol(first), KaramelTokenType::Text(text), KaramelTokenType::Symbol(second)) => {
+                assert!(std::rc::Rc::ptr_eq(first, second));
+                assert!(std::rc::Rc::ptr_eq(first, text));
+            },
+            _ => assert!(false)
+        };
+    }
+}
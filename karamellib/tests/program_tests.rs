@@ -0,0 +1,112 @@
+extern crate karamellib;
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+    use std::fs;
+    use std::path::{Path, PathBuf};
+    use std::process::Command;
+
+    use crate::karamellib::transpiler::{transpile_code, TranspileTarget};
+    use crate::karamellib::vm::executer::{self, ExecutionParameters, ExecutionSource};
+
+    /* Expected outputs are rewritten when this variable is set */
+    const UPDATE_VARIABLE: &str = "KARAMEL_GOLDEN_UPDATE";
+
+    fn programs() -> Vec<PathBuf> {
+        let folder = Path::new(&env::current_dir().unwrap()).join("tests").join("programs");
+        let mut programs: Vec<PathBuf> = fs::read_dir(folder).unwrap()
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| path.extension().map_or(false, |extension| extension == "k"))
+            .collect();
+        programs.sort();
+        programs
+    }
+
+    fn run(program: &Path) -> String {
+        let parameters = ExecutionParameters {
+            return_output: true,
+            ..ExecutionParameters::new(ExecutionSource::File(program.to_str().unwrap().to_string()))
+        };
+
+        let result = executer::code_executer(parameters);
+        let mut output = result.stdout.map(|stdout| stdout.into_inner()).unwrap_or_default();
+        if let Some(error) = result.error {
+            output.push_str(&format!("Hata: {}\n", error.error_type));
+        }
+        output.replace("\r\n", "\n")
+    }
+
+    fn difference(expected: &str, output: &str) -> String {
+        let expected_lines: Vec<&str> = expected.lines().collect();
+        let output_lines: Vec<&str> = output.lines().collect();
+
+        for index in 0..expected_lines.len().max(output_lines.len()) {
+            let (expected_line, output_line) = (expected_lines.get(index), output_lines.get(index));
+            if expected_line != output_line {
+                return format!("{}. satır\n  beklenen: {:?}\n  üretilen: {:?}", index + 1, expected_line, output_line);
+            }
+        }
+        String::new()
+    }
+
+    #[test]
+    fn golden_programs() {
+        let update = env::var(UPDATE_VARIABLE).is_ok();
+        let mut failed_cases = Vec::new();
+        let programs = programs();
+        assert!(!programs.is_empty());
+
+        for program in programs.iter() {
+            let output = run(program);
+            let expected_file = program.with_extension("out");
+
+            if update {
+                fs::write(&expected_file, &output).unwrap();
+                continue;
+            }
+
+            match fs::read_to_string(&expected_file) {
+                Ok(expected) if expected.replace("\r\n", "\n") == output => (),
+                Ok(expected) => failed_cases.push(format!("{}\n{}", program.display(), difference(&expected.replace("\r\n", "\n"), &output))),
+                Err(_) => failed_cases.push(format!("{} için beklenen çıktı dosyası bulunamadı. {}=1 ile oluşturulabilir.", program.display(), UPDATE_VARIABLE))
+            };
+        }
+
+        assert!(failed_cases.is_empty(), "\n{}", failed_cases.join("\n\n"));
+    }
+
+    /* Transpiled programs should write the same output. Test is skipped when the interpreter is not installed */
+    fn transpiled_programs(target: TranspileTarget, interpreter: &str, extension: &str) {
+        if Command::new(interpreter).arg("--version").output().is_err() {
+            return;
+        }
+
+        let mut failed_cases = Vec::new();
+        for program in programs().iter() {
+            let code = transpile_code(fs::read_to_string(program).unwrap(), target).unwrap();
+            let script = env::temp_dir().join(format!("karamel_{}.{}", program.file_stem().unwrap().to_str().unwrap(), extension));
+            fs::write(&script, code).unwrap();
+
+            let output = Command::new(interpreter).arg(&script).env("PYTHONIOENCODING", "utf-8").output().unwrap();
+            let output = String::from_utf8_lossy(&output.stdout).replace("\r\n", "\n");
+            let expected = fs::read_to_string(program.with_extension("out")).unwrap().replace("\r\n", "\n");
+            if expected != output {
+                failed_cases.push(format!("{}\n{}", program.display(), difference(&expected, &output)));
+            }
+            let _ = fs::remove_file(&script);
+        }
+
+        assert!(failed_cases.is_empty(), "\n{}", failed_cases.join("\n\n"));
+    }
+
+    #[test]
+    fn javascript_programs() {
+        transpiled_programs(TranspileTarget::JavaScript, "node", "js");
+    }
+
+    #[test]
+    fn python_programs() {
+        transpiled_programs(TranspileTarget::Python, "python3", "py");
+    }
+}
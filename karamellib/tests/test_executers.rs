@@ -6,6 +6,17 @@ mod tests {
 
     use crate::karamellib::vm::*;
     use crate::karamellib::{vm::executer::{ExecutionParameters, ExecutionSource}};
+    use crate::karamellib::constants::{KARAMEL_TEACHING_LOOP_LIMIT, KARAMEL_MAX_TEXT_SIZE, KARAMEL_SANDBOX_MEMORY_LIMIT};
+    use crate::karamellib::gc;
+    use crate::karamellib::error::{KaramelErrorType, KaramelWarningType};
+    use crate::karamellib::vm::events::{VmEvent, VmEventHook};
+    use crate::karamellib::vm::trace::TraceOutput;
+    use crate::karamellib::file::{FileSystem, MemoryFileSystem};
+    use crate::karamellib::buildin::Permission;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicBool, Ordering};
 
     enum ExecuterType {
         File,
@@ -51,13 +62,7 @@ mod tests {
 
                     match path.path().to_str() {
                         Some(path_str) => {
-                            let parameters = ExecutionParameters {
-                                source: ExecutionSource::File(path_str.to_string()),
-                                return_opcode: false,
-                                return_output: false,
-                                dump_opcode: false,
-                                dump_memory: false
-                            };
+                            let parameters = ExecutionParameters::new(ExecutionSource::File(path_str.to_string()));
 
                             let result = executer::code_executer(parameters);
                             match result.compiled && result.executed {
@@ -98,4 +103,808 @@ mod tests {
     fn test_module_executer() -> Result<(), String> {
         executer(ExecuterType::Module)
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_execution_error_info() {
+        let parameters = ExecutionParameters {
+            return_output: true,
+            ..ExecutionParameters::new(ExecutionSource::Code("a = 1\nb = (1 + 2".to_string()))
+        };
+
+        let result = executer::code_executer(parameters);
+        assert_eq!(result.executed, false);
+
+        let error = result.error.unwrap();
+        assert_eq!(error.line, 1);
+    }
+
+    #[test]
+    fn test_code_compiler() {
+        assert!(executer::code_compiler("a = 1 + 2").is_ok());
+        assert!(executer::code_compiler("a = (1 + 2").is_err());
+    }
+
+    #[test]
+    fn test_code_listing() {
+        let listing = executer::code_listing("fonk kare(a):\n    döndür a * a\n\nb = kare(2)").unwrap();
+        let lines: Vec<&str> = listing.lines().collect();
+
+        let function = lines.iter().position(|line| line.ends_with("│ fonk kare(a):")).unwrap();
+        let body = lines.iter().position(|line| line.ends_with("│     döndür a * a")).unwrap();
+        let call = lines.iter().position(|line| line.ends_with("│ b = kare(2)")).unwrap();
+        assert!(function < body && body < call);
+        assert!(lines[body + 1..call].iter().any(|line| line.contains("Multiply")));
+        assert!(lines[call + 1..].iter().any(|line| line.contains("Call")));
+
+        assert!(executer::code_bytecode("a = 1").unwrap().contains("OPCODE DUMP"));
+        assert!(executer::code_listing("a = (1 + 2").is_err());
+    }
+
+    #[test]
+    fn test_resolver_errors() {
+        let parameters = ExecutionParameters {
+            return_output: true,
+            ..ExecutionParameters::new(ExecutionSource::Code("a = 1\ngç::satıryaz(a)\nb = a + c".to_string()))
+        };
+
+        let result = executer::code_executer(parameters);
+        assert_eq!(result.executed, false);
+        assert_eq!(result.stdout.unwrap().borrow().as_str(), "");
+
+        let error = result.error.unwrap();
+        assert_eq!((error.line, error.column), (2, 8));
+        assert_eq!(error.error_type, KaramelErrorType::VariableNotDefined("c".to_string()));
+        assert!(executer::code_compiler("b = a\na = 1").is_err());
+    }
+
+    fn assumption_executer(code: &str, debug_assertions: bool) -> executer::ExecutionStatus {
+        let parameters = ExecutionParameters {
+            return_output: true,
+            debug_assertions,
+            ..ExecutionParameters::new(ExecutionSource::Code(code.to_string()))
+        };
+
+        executer::code_executer(parameters)
+    }
+
+    #[test]
+    fn test_debug_assertions() {
+        let code = "a = -1\nvarsay a > 0, 'a pozitif olmalı'\ngç::satıryaz('bitti')";
+        let result = assumption_executer(code, false);
+        assert_eq!(result.executed, true);
+        assert!(result.stdout.unwrap().borrow().contains("bitti"));
+
+        let result = assumption_executer(code, true);
+        assert_eq!(result.executed, false);
+        assert_eq!(result.error.unwrap().error_type, KaramelErrorType::AssumptionFailed("a pozitif olmalı".to_string()));
+
+        assert_eq!(assumption_executer("varsay 1 > 0\nvarsay doğru", true).executed, true);
+        assert_eq!(assumption_executer("varsay 1 < 0", true).error.unwrap().error_type.to_string(), "Varsayım sağlanmadı");
+
+        let result = assumption_executer("dene:\n    varsay yanlış, 'yok'\nyakala hata:\n    gç::satıryaz(hata.kod(), hata.mesaj())", true);
+        let output = result.stdout.unwrap().borrow().to_string();
+        assert!(output.contains("166") && output.contains("Varsayım sağlanmadı: yok"));
+    }
+
+    #[test]
+    fn test_constants() {
+        let result = assumption_executer("sabit PI = 3.14\ngç::satıryaz(PI * 2)", false);
+        assert_eq!(result.executed, true);
+
+        for code in ["sabit PI = 3.14\nPI = 3", "sabit PI = 3.14\nPI += 1", "sabit a = 1\n++a", "sabit a = 1\na--", "a = 1\nsabit a = 2", "sabit a = 1\nsabit a = 2", "sabit hata = 1\ndene:\n    fırlat 'x'\nyakala hata:\n    gç::satıryaz(hata)"] {
+            let result = assumption_executer(code, false);
+            assert_eq!(result.compiled, false, "{}", code);
+        }
+
+        let result = assumption_executer("sabit PI = 3.14\nPI = 3", false);
+        assert_eq!(result.error.unwrap().error_type, KaramelErrorType::ConstantReassigned("PI".to_string()));
+        assert_eq!(KaramelErrorType::ConstantReassigned("PI".to_string()).to_string(), "'PI' sabit olarak tanımlanmış, değeri değiştirilemez");
+    }
+
+    #[test]
+    fn test_local_variables() {
+        let result = assumption_executer("doğru ise:\n    yerel y = 1\n    gç::satıryaz(y)", false);
+        assert_eq!(result.executed, true);
+
+        let result = assumption_executer("doğru ise:\n    yerel y = 1\ngç::satıryaz(y)", false);
+        assert_eq!(result.compiled, false);
+        assert_eq!(result.error.unwrap().error_type, KaramelErrorType::VariableNotDefined("y".to_string()));
+
+        let result = assumption_executer("doğru ise:\n    yerel y = 1\n    fonk oku():\n        döndür y\n    gç::satıryaz(oku())", false);
+        assert_eq!(result.compiled, false);
+    }
+
+    #[test]
+    fn test_enums() {
+        let result = assumption_executer("seçenekler Renk: Kırmızı, Yeşil\ngç::satıryaz(Renk::Yeşil)", false);
+        assert_eq!(result.executed, true);
+        assert_eq!(result.stdout.unwrap().borrow().to_string(), "Renk::Yeşil\r\n");
+
+        let result = assumption_executer("seçenekler Renk: Kırmızı, Yeşil\ngç::satıryaz(Renk::Mor)", false);
+        assert_eq!(result.error.unwrap().error_type, KaramelErrorType::EnumVariantNotFound("Renk::Mor".to_string()));
+
+        let result = assumption_executer("seçenekler Renk: Kırmızı\nseçenekler Renk: Yeşil", false);
+        assert_eq!(result.error.unwrap().error_type, KaramelErrorType::EnumAlreadyDefined("Renk".to_string()));
+    }
+
+    #[test]
+    fn test_help() {
+        let code = "## İki sayıyı toplar.\nfonk topla(a: sayı, b):\n    döndür a + b\nbaz::yardım(topla)\nbaz::yardım([1, 2])\nbaz::yardım(baz::türü)";
+        let result = assumption_executer(code, false);
+        assert_eq!(result.executed, true);
+
+        let output = result.stdout.unwrap().borrow().to_string();
+        assert!(output.contains("Tür: fonksiyon\r\nFonksiyon: topla(a: sayı, b)\r\nİki sayıyı toplar."));
+        assert!(output.contains("Tür: liste\r\nFonksiyonlar: ") && output.contains("uzunluk"));
+        assert!(output.contains("Fonksiyon: türü(...)"));
+    }
+
+    fn type_guard_executer(code: &str, type_guards: bool) -> executer::ExecutionStatus {
+        let parameters = ExecutionParameters {
+            return_output: true,
+            type_guards,
+            ..ExecutionParameters::new(ExecutionSource::Code(code.to_string()))
+        };
+
+        executer::code_executer(parameters)
+    }
+
+    #[test]
+    fn test_type_guards() {
+        let code = "fonk topla(a: sayı, b: sayı):\n    döndür a + b\ngç::satıryaz(topla(1, '2'))";
+        let result = type_guard_executer(code, false);
+        assert_eq!(result.executed, true);
+        assert!(result.stdout.unwrap().borrow().contains("12"));
+
+        let result = type_guard_executer(code, true);
+        assert_eq!(result.executed, false);
+        let error = result.error.unwrap().error_type;
+        assert_eq!(error, KaramelErrorType::ArgumentTypeMismatch { argument: "b".to_string(), expected: "sayı".to_string(), found: "yazı".to_string() });
+        assert_eq!(error.to_string(), "'b' parametresi sayı olmalı fakat yazı verildi");
+
+        let result = type_guard_executer("fonk boyut(a: liste, b):\n    döndür a.uzunluk() + b\ngç::satıryaz(boyut([1, 2], 3))", true);
+        assert_eq!(result.executed, true);
+        assert!(result.stdout.unwrap().borrow().contains("5"));
+
+        let result = type_guard_executer("fonk test(a: sözlük):\n    döndür a\ndene:\n    test(boş)\nyakala hata:\n    gç::satıryaz(hata.kod())", true);
+        assert!(result.stdout.unwrap().borrow().contains("173"));
+    }
+
+    #[test]
+    fn test_indexer_assignment_errors() {
+        let result = assumption_executer("a = [1, 2]\na[2] = 3", false);
+        assert_eq!(result.error.unwrap().error_type, KaramelErrorType::IndexOutOfRange { index: "2".to_string(), length: 2 });
+
+        let result = assumption_executer("a = [1, 2]\na[-1] = 3", false);
+        assert_eq!(result.error.unwrap().error_type.to_string(), "-1 sırası geçersiz, uzunluk 2");
+
+        let result = assumption_executer("a = {'x': 1}\na[[0]] = 3", false);
+        assert!(matches!(result.error.unwrap().error_type, KaramelErrorType::DictionaryKeyNotHashable(_)));
+
+        let result = assumption_executer("a = 5\na[0] = 3", false);
+        assert!(matches!(result.error.unwrap().error_type, KaramelErrorType::IndexerAssignmentNotSupported(_)));
+    }
+
+    #[test]
+    fn test_compiler_warnings() {
+        let result = teaching_executer("fonk yaz(not):\n    döndür 'Not: ' + 50\ngç::satıryaz('Merhaba')");
+        assert_eq!(result.executed, true);
+
+        let warnings: Vec<_> = result.warnings.iter().map(|warning| warning.warning_type.clone()).collect();
+        assert_eq!(warnings, vec![KaramelWarningType::UnusedFunction("yaz".to_string()), KaramelWarningType::ImplicitNumberToText]);
+        assert!(result.stderr.unwrap().borrow().contains("İpucu: Sayı ile yazı toplandığında"));
+    }
+
+    #[test]
+    fn test_execution_diagnostics() {
+        let result = teaching_executer("fonk yaz():\n    döndür 1\ngç::satıryaz(a)");
+        assert_eq!(result.executed, false);
+
+        let codes: Vec<_> = result.diagnostics().iter().map(|diagnostic| diagnostic.code.clone()).collect();
+        assert_eq!(codes, vec!["U001".to_string(), "157".to_string()]);
+    }
+
+    fn teaching_executer(code: &str) -> executer::ExecutionStatus {
+        let parameters = ExecutionParameters {
+            return_output: true,
+            teaching_mode: true,
+            ..ExecutionParameters::new(ExecutionSource::Code(code.to_string()))
+        };
+
+        executer::code_executer(parameters)
+    }
+
+    #[test]
+    fn test_teaching_mode_constants() {
+        let result = teaching_executer("hataayıklama::doğrula(pi, sabitler::pi)\nhataayıklama::doğrula(geçme_notu, 50)");
+        assert_eq!(result.executed, true);
+
+        let result = teaching_executer("pi = 3");
+        assert_eq!(result.error.unwrap().error_type, KaramelErrorType::ReservedName("pi".to_string()));
+    }
+
+    #[test]
+    fn test_teaching_mode_loop_limit() {
+        let result = teaching_executer("döngü doğru:\n    a = 1");
+        assert_eq!(result.executed, false);
+        assert_eq!(result.error.unwrap().error_type, KaramelErrorType::LoopIterationLimitExceeded(KARAMEL_TEACHING_LOOP_LIMIT));
+        assert!(result.stderr.unwrap().borrow().contains("İpucu:"));
+    }
+
+    #[test]
+    fn test_teaching_mode_nested_function() {
+        let code = "fonk dış():\n    fonk iç():\n        döndür 1\n    döndür iç()";
+        let result = teaching_executer(code);
+        assert_eq!(result.error.unwrap().error_type, KaramelErrorType::DisabledInTeachingMode("İç içe fonksiyon".to_string()));
+    }
+
+    #[test]
+    fn test_teaching_mode_step_report() {
+        let code = r#"toplam = 0
+döngü i = 0, i < 3, ++i:
+    döngü j = 0, j < 4, ++j:
+        toplam += j
+
+sonsuz:
+    kır
+"#;
+        let result = teaching_executer(code);
+        assert_eq!(result.executed, true);
+
+        let report = result.step_report.unwrap();
+        assert!(report.instruction_count > 0);
+        assert_eq!(report.loops.iter().map(|counter| (counter.line, counter.iterations)).collect::<Vec<_>>(), vec![(1, 3), (2, 12), (5, 1)]);
+        assert!(result.stderr.unwrap().borrow().contains("2. satırdaki döngü 3 kez döndü"));
+    }
+
+    struct EventCollector(Rc<RefCell<Vec<String>>>);
+
+    impl VmEventHook for EventCollector {
+        fn on_event(&mut self, event: VmEvent) {
+            self.0.borrow_mut().push(event.to_json());
+        }
+    }
+
+    struct PanickingHook;
+
+    impl VmEventHook for PanickingHook {
+        fn on_event(&mut self, event: VmEvent) {
+            if let VmEvent::FunctionCalled { .. } = event {
+                panic!("olay dinleyicisi çöktü");
+            }
+        }
+    }
+
+    #[test]
+    fn test_crash_report() {
+        let parameters = ExecutionParameters {
+            return_output: true,
+            event_hook: Some(Box::new(PanickingHook)),
+            ..ExecutionParameters::new(ExecutionSource::Code("fonk f():\n    döndür 1\na = f()".to_string()))
+        };
+
+        let result = executer::code_executer(parameters);
+        assert_eq!(result.executed, false);
+        assert_eq!(result.error.unwrap().error_type, KaramelErrorType::InternalError("olay dinleyicisi çöktü".to_string()));
+
+        let stderr = result.stderr.unwrap().borrow().to_string();
+        let path = stderr.split("Hata raporu: ").nth(1).unwrap().trim();
+        let report = std::fs::read_to_string(path).unwrap();
+        std::fs::remove_file(path).unwrap();
+
+        assert!(report.contains("a = f()"));
+        assert!(report.contains("FunctionDefination"));
+        assert!(report.contains("== Komutlar ==\n\n"));
+    }
+
+    #[test]
+    fn test_event_hook() {
+        let events = Rc::new(RefCell::new(Vec::new()));
+        let parameters = ExecutionParameters {
+            return_output: true,
+            event_hook: Some(Box::new(EventCollector(events.clone()))),
+            ..ExecutionParameters::new(ExecutionSource::Code("fonk ikikatı(a):\n    döndür a * 2\n\nsonuç = 1\nsonuç = ikikatı(sonuç)".to_string()))
+        };
+
+        let result = executer::code_executer(parameters);
+        assert_eq!(result.executed, true);
+        assert_eq!(*events.borrow(), vec![
+            r#"{"event": "line", "line": 1}"#,
+            r#"{"event": "line", "line": 4}"#,
+            r#"{"event": "variable", "name": "sonuç", "old": null, "new": 1}"#,
+            r#"{"event": "line", "line": 5}"#,
+            r#"{"event": "call", "name": "ikikatı", "arguments": {"a": 1}}"#,
+            r#"{"event": "line", "line": 2}"#,
+            r#"{"event": "return", "name": "ikikatı", "value": 2}"#,
+            r#"{"event": "variable", "name": "sonuç", "old": 1, "new": 2}"#
+        ]);
+    }
+
+    #[test]
+    fn test_memory_file_system() {
+        let file_system = Rc::new(MemoryFileSystem::new());
+        file_system.add_file("topla.k", "fonk topla(bir, iki): dondur bir + iki");
+        file_system.add_file("veri/ad.txt", "karamel");
+
+        let code = r#"
+topla yükle
+hataayıklama::doğrula(topla::topla(1, 2), 3)
+hataayıklama::doğrula(dosya::oku('veri/ad.txt'), 'karamel')
+hataayıklama::doğrula(dosya::varmı('veri/yok.txt'), yanlış)
+dosya::yaz('veri/yeni.txt', 'merhaba')
+hataayıklama::doğrula(dosya::listele('veri'), ['ad.txt', 'yeni.txt'])
+"#;
+        let parameters = ExecutionParameters {
+            return_output: true,
+            file_system: Some(file_system.clone()),
+            ..ExecutionParameters::new(ExecutionSource::Code(code.to_string()))
+        };
+
+        let result = executer::code_executer(parameters);
+        assert!(result.executed, "{:?}", result.error);
+        assert_eq!(file_system.read("veri/yeni.txt"), Ok("merhaba".to_string()));
+    }
+
+    #[test]
+    fn test_wide_constants() {
+        /* More than 256 constants, the function constants are added after the numbers and the texts */
+        let mut code = String::from("toplam = 0\n");
+        for index in 0..300 {
+            code.push_str(&format!("toplam += {}\nad = 'ad {}'\n", index, index));
+        }
+        code.push_str("hataayıklama::doğrula(toplam, 44850)\nhataayıklama::doğrula(ad, 'ad 299')\n");
+
+        let parameters = ExecutionParameters {
+            return_output: true,
+            ..ExecutionParameters::new(ExecutionSource::Code(code))
+        };
+
+        let result = executer::code_executer(parameters);
+        assert!(result.executed, "{:?}", result.error);
+    }
+
+    #[test]
+    fn test_wide_variables() {
+        /* More than 256 variables, catch variable and list literal are placed after them */
+        let mut code = String::from("fonk hesapla():\n");
+        for index in 0..300 {
+            code.push_str(&format!("    d{} = {}\n", index, index));
+        }
+        code.push_str("    dene:\n        fırlat 'sorun'\n    yakala hata:\n        d0 = hata.mesaj()\n        d1 = d299 + d298\n");
+        code.push_str("    döndür [d0, d1, d299]\nsonuç = hesapla()\n");
+        code.push_str("hataayıklama::doğrula(sonuç.getir(0), 'sorun')\nhataayıklama::doğrula(sonuç.getir(1), 597)\nhataayıklama::doğrula(sonuç.getir(2), 299)\n");
+
+        let mut list = Vec::new();
+        for index in 0..300 {
+            list.push(index.to_string());
+        }
+        code.push_str(&format!("liste_değeri = [{}]\nhataayıklama::doğrula(liste_değeri.uzunluk(), 300)\nhataayıklama::doğrula(liste_değeri.getir(299), 299)\n", list.join(", ")));
+
+        let parameters = ExecutionParameters {
+            return_output: true,
+            ..ExecutionParameters::new(ExecutionSource::Code(code))
+        };
+        let result = executer::code_executer(parameters);
+        assert!(result.executed, "{:?}", result.error);
+    }
+
+    #[test]
+    fn test_wide_jumps() {
+        /* Each block is longer than the 16 bit jump locations */
+        let block = |indent: &str, name: &str| (0..12000).map(|_| format!("{}{} = {} + 1\n", indent, name, name)).collect::<String>();
+
+        let mut code = String::from("fonk hesapla(sıra):\n    y = 0\n    ertele:\n        sıra.ekle('ertele')\n");
+        code.push_str(&block("        ", "y"));
+        code.push_str("    sıra.ekle('gövde')\n    döndür 1\n");
+        code.push_str("t = 0\nyaş = 30\nyaş < 18 ise:\n");
+        code.push_str(&block("    ", "t"));
+        code.push_str("veya yaş < 40 ise:\n    t = t + 1000000\nveya:\n");
+        code.push_str(&block("    ", "t"));
+        code.push_str("hataayıklama::doğrula(t, 1000000)\n");
+
+        code.push_str("t = 0\nsayaç = 0\ndöngü sayaç < 2:\n    sayaç = sayaç + 1\n");
+        code.push_str(&block("    ", "t"));
+        code.push_str("hataayıklama::doğrula(t, 24000)\n");
+
+        code.push_str("t = 0\ndene:\n");
+        code.push_str(&block("    ", "t"));
+        code.push_str("    fırlat 'sorun'\nyakala hata:\n    t = t + 1\nhataayıklama::doğrula(t, 12001)\n");
+
+        code.push_str("fonk kapat(kapananlar):\n    kapananlar.ekle('kaynak')\nfonk kullan_kaynak(kapananlar):\n    t = 0\n    birlikte {'kapat': baz::bağla(kapat, kapananlar)} olarak kaynak:\n");
+        code.push_str(&block("        ", "t"));
+        code.push_str("    döndür t\nkapananlar = []\nhataayıklama::doğrula(kullan_kaynak(kapananlar), 12000)\nhataayıklama::doğrula(kapananlar, ['kaynak'])\n");
+
+        code.push_str("t = 0\nsonuç = doğru veya hesapla([])\nhataayıklama::doğrula(sonuç, doğru)\n");
+        code.push_str("sıra = []\nhataayıklama::doğrula(hesapla(sıra), 1)\nhataayıklama::doğrula(sıra, ['gövde', 'ertele'])\n");
+
+        let parameters = ExecutionParameters {
+            return_output: true,
+            ..ExecutionParameters::new(ExecutionSource::Code(code))
+        };
+        let result = executer::code_executer(parameters);
+        assert!(result.executed, "{:?}", result.error);
+    }
+
+    #[test]
+    fn test_too_many_arguments() {
+        let arguments: Vec<String> = (0..256).map(|index| format!("a{}", index)).collect();
+        let code = format!("fonk topla({}):\n    döndür a0\n", arguments.join(", "));
+
+        let parameters = ExecutionParameters {
+            return_output: true,
+            ..ExecutionParameters::new(ExecutionSource::Code(code))
+        };
+        let result = executer::code_executer(parameters);
+        assert!(!result.executed);
+        assert_eq!(result.error.unwrap().error_type, KaramelErrorType::TooManyArguments(255));
+    }
+
+    #[test]
+    fn test_garbage_collection() {
+        let code = r#"
+toplam = 0
+döngü i = 0, i < 500, ++i:
+    a = [i]
+    b = [a]
+    a.ekle(b)
+    toplam += a[0]
+a = boş
+b = boş
+hataayıklama::doğrula(toplam, 124750)
+hataayıklama::doğrula(hataayıklama::çöp_topla() > 0, doğru)
+hataayıklama::doğrula(hataayıklama::çöp_topla(), 0)
+"#;
+        let parameters = ExecutionParameters {
+            return_output: true,
+            gc_threshold: Some(50),
+            ..ExecutionParameters::new(ExecutionSource::Code(code.to_string()))
+        };
+        let result = executer::code_executer(parameters);
+        assert!(result.executed, "{:?}", result.error);
+        assert!(gc::collection_count() > 1);
+    }
+
+    #[test]
+    fn test_inline_cache() {
+        /* Same call site is used with different classes, cached property must belong to the class of the object */
+        let code = r#"
+fonk uzunluk_al(nesne):
+    döndür nesne.uzunluk()
+
+toplam = 0
+döngü i = 0, i < 100, ++i:
+    toplam += uzunluk_al([1, 2, 3])
+    toplam += uzunluk_al('karamel')
+    toplam += uzunluk_al({'a': 1})
+hataayıklama::doğrula(toplam, 1100)
+
+notlar = {'ali': 70}
+döngü i = 0, i < 3, ++i:
+    notlar.güncelle('ali', notlar.getir('ali') + 1)
+hataayıklama::doğrula(notlar.getir('ali'), 73)
+"#;
+        let parameters = ExecutionParameters {
+            return_output: true,
+            ..ExecutionParameters::new(ExecutionSource::Code(code.to_string()))
+        };
+        let result = executer::code_executer(parameters);
+        assert!(result.executed, "{:?}", result.error);
+    }
+
+    #[test]
+    fn test_parallel_module_parse() {
+        /* Modules are parsed at the worker threads, nested 'yükle' lines add new files while the others are parsed */
+        let file_system = Rc::new(MemoryFileSystem::new());
+        file_system.add_file("topla.k", "fonk topla(bir, iki): dondur bir + iki");
+        file_system.add_file("carp.k", "fonk carp(bir, iki): dondur bir * iki");
+        file_system.add_file("fark.k", "fonk fark(bir, iki): dondur bir - iki");
+        file_system.add_file("hesap.k", "topla yükle\ncarp yükle\nfonk kare(değer): dondur değer * değer\n");
+
+        let code = r#"
+hesap yükle
+fark yükle
+hataayıklama::doğrula(topla::topla(1, 2), 3)
+hataayıklama::doğrula(carp::carp(3, 4), 12)
+hataayıklama::doğrula(fark::fark(10, 4), 6)
+hataayıklama::doğrula(hesap::kare(5), 25)
+"#;
+        for _ in 0..10 {
+            let parameters = ExecutionParameters {
+                return_output: true,
+                file_system: Some(file_system.clone()),
+                ..ExecutionParameters::new(ExecutionSource::Code(code.to_string()))
+            };
+
+            let result = executer::code_executer(parameters);
+            assert!(result.executed, "{:?}", result.error);
+        }
+    }
+
+    #[test]
+    fn test_parallel_module_parse_error() {
+        let file_system = Rc::new(MemoryFileSystem::new());
+        file_system.add_file("topla.k", "fonk topla(bir, iki): dondur bir + iki");
+        file_system.add_file("bozuk.k", "fonk bozuk(:\n");
+
+        let parameters = ExecutionParameters {
+            return_output: true,
+            file_system: Some(file_system),
+            ..ExecutionParameters::new(ExecutionSource::Code("topla yükle\nbozuk yükle\nyok yükle\n".to_string()))
+        };
+
+        let result = executer::code_executer(parameters);
+        assert!(!result.executed);
+        assert_ne!(result.error.unwrap().error_type, KaramelErrorType::FileNotFound("yok".to_string()));
+    }
+
+    #[test]
+    fn test_compiled_program_threads() {
+        fn send_sync<T: Send + Sync>() {}
+        send_sync::<program::CompiledProgram>();
+
+        let code = "fonk fib(n):\n    n < 2 ise:\n        döndür n\n    döndür fib(n - 1) + fib(n - 2)\ndizi = [1, 2, 3]\ntoplam = 0\ndöngü i = 0, i < 100, ++i:\n    toplam += dizi.uzunluk()\nisim = 'sonuç'\ngç::satıryaz(isim.uzunluk(), fib(15), toplam)";
+        let program = executer::code_program(code).unwrap();
+
+        let workers: Vec<_> = (0..4).map(|_| {
+            let program = program.clone();
+            std::thread::spawn(move || {
+                let result = executer::program_executer(&program, true);
+                assert!(result.executed, "{:?}", result.error);
+                result.stdout.unwrap().borrow().to_string()
+            })
+        }).collect();
+
+        for worker in workers {
+            assert_eq!(worker.join().unwrap().trim(), "5610300");
+        }
+
+        let result = executer::program_executer(&program, true);
+        assert!(result.executed);
+    }
+
+    #[test]
+    fn test_tasks() {
+        let code = "fonk parça(n):\n    dizi = []\n    döngü i = 0, i < 200, ++i:\n        dizi.ekle([i, n])\n    gç::satıryaz('görev', n)\n    döndür dizi.uzunluk()\nkalan = [[1], [2], [3]]\nbir = görev::başlat(parça, 1)\niki = görev::başlat(parça, 2)\ngç::satıryaz(görev::bekle(iki) + görev::bekle(bir))\nhataayıklama::doğrula(kalan[2][0], 3)";
+        let parameters = ExecutionParameters {
+            return_output: true,
+            gc_threshold: Some(20),
+            ..ExecutionParameters::new(ExecutionSource::Code(code.to_string()))
+        };
+
+        let result = executer::code_executer(parameters);
+        assert!(result.executed, "{:?}", result.error);
+        let output: Vec<String> = result.stdout.unwrap().borrow().lines().map(|line| line.to_string()).collect();
+        assert_eq!(output, vec!["\"görev\"2", "\"görev\"1", "400"]);
+    }
+
+    #[test]
+    fn test_interrupt() {
+        let interrupt = Arc::new(AtomicBool::new(false));
+        let parameters = ExecutionParameters {
+            return_output: true,
+            interrupt: Some(interrupt.clone()),
+            ..ExecutionParameters::new(ExecutionSource::Code("dene:\n    döngü doğru:\n        a = 1\nyakala hata:\n    gç::satıryaz('yakalandı')".to_string()))
+        };
+
+        let flag = interrupt.clone();
+        let sender = std::thread::spawn(move || {
+            std::thread::sleep(std::time::Duration::from_millis(50));
+            flag.store(true, Ordering::SeqCst);
+        });
+
+        let result = executer::code_executer(parameters);
+        sender.join().unwrap();
+        assert!(!result.executed);
+        assert_eq!(result.error.unwrap().error_type, KaramelErrorType::Interrupted);
+        assert!(!result.stdout.unwrap().borrow().contains("yakalandı"));
+    }
+
+    #[test]
+    fn test_trace_buffer() {
+        let parameters = ExecutionParameters {
+            return_output: true,
+            trace: Some(TraceOutput::Buffer(4)),
+            ..ExecutionParameters::new(ExecutionSource::Code("fonk topla(a, b):\n    döndür a + b\nx = topla(1, 2)".to_string()))
+        };
+
+        let result = executer::code_executer(parameters);
+        assert!(result.executed, "{:?}", result.error);
+
+        let trace = result.trace.unwrap();
+        let opcodes: Vec<&str> = trace.iter().map(|record| record.opcode.as_str()).collect();
+        assert_eq!(opcodes, vec!["Addition", "Return", "Store", "Halt"]);
+        assert_eq!(trace[0].top, Some("3".to_string()));
+        assert_eq!(trace[0].stack_before, trace[0].stack_after + 1);
+        assert_eq!(trace[0].line, Some(1));
+        assert_eq!(trace[2].operands.len(), 1);
+        assert!(trace[3].to_json().starts_with("{\"location\": "));
+    }
+
+    #[test]
+    fn test_runtime_error_stack() {
+        let parameters = ExecutionParameters {
+            return_output: true,
+            ..ExecutionParameters::new(ExecutionSource::Code("fonk böl(a):\n    döndür a(2)\n\nfonk hesapla(a):\n    döndür böl(a) + 1\n\ngç::satıryaz(hesapla(4))".to_string()))
+        };
+
+        let result = executer::code_executer(parameters);
+        assert!(!result.executed);
+
+        let error = result.runtime_error.unwrap();
+        assert_eq!(error.line, Some(1));
+        assert_eq!(result.error.unwrap().line, 1);
+
+        let functions: Vec<Option<&str>> = error.call_stack.iter().map(|frame| frame.function.as_deref()).collect();
+        let lines: Vec<Option<u32>> = error.call_stack.iter().map(|frame| frame.line).collect();
+        assert_eq!(functions, vec![None, Some("hesapla"), Some("böl")]);
+        assert_eq!(lines, vec![Some(6), Some(4), Some(1)]);
+        assert_eq!(error.column, Some(4));
+        assert_eq!(error.call_stack[0].column, Some(0));
+
+        assert_eq!(error.message(), error.error_type.message_in("tr"));
+        assert_eq!(error.call_stack[2].arguments, vec![("a".to_string(), "4".to_string())]);
+        assert!(error.stack_trace().ends_with("böl(a=4), satır 2\n    hesapla(a=4), satır 5\n    ana kod, satır 7"), "{}", error.stack_trace());
+    }
+
+    #[test]
+    fn test_stack_overflow() {
+        let parameters = ExecutionParameters {
+            return_output: true,
+            max_call_depth: Some(50),
+            ..ExecutionParameters::new(ExecutionSource::Code("fonk say(sıra, değerler):
+    döndür say(sıra + 1, değerler)
+
+say(1, [1, 2, 3])".to_string()))
+        };
+
+        let result = executer::code_executer(parameters);
+        assert!(!result.executed);
+
+        let error = result.runtime_error.unwrap();
+        assert_eq!(error.error_type, KaramelErrorType::StackOverflow(50));
+        assert_eq!(error.call_stack.len(), 51);
+        assert!(error.stack_trace().ends_with("say(sıra=50, değerler=<liste(3)>), satır 2\n    ... aynı satırdan 49 çağrı daha\n    ana kod, satır 4"), "{}", error.stack_trace());
+    }
+
+    #[test]
+    fn test_repeated_text_limit() {
+        let sandbox = sandbox::Sandbox::new();
+        for code in ["a = 'ab' * 1e19", "a = 1e9 * 'ab'"] {
+            let result = sandbox.run(code);
+            assert!(!result.success, "{}", code);
+            assert_eq!(result.runtime_error.unwrap().error_type, KaramelErrorType::ValueTooLarge(KARAMEL_MAX_TEXT_SIZE), "{}", code);
+        }
+
+        let result = sandbox.run("dene:\n    a = 'ab' * 1e19\nyakala hata:\n    gç::satıryaz('yakalandı')\ngç::satıryaz(('ab' * 3).uzunluk())");
+        assert!(result.success, "{:?}", result.runtime_error);
+        assert_eq!(result.stdout, "\"yakalandı\"\r\n6\r\n");
+    }
+
+    #[test]
+    fn test_assert_expression() {
+        let run = |code: &str| executer::code_executer(ExecutionParameters {
+            return_output: true,
+            ..ExecutionParameters::new(ExecutionSource::Code(code.to_string()))
+        });
+
+        let result = run("a = 3\nfonk kontrol(x):\n    hataayıklama::doğrula(x > 5 ve x < 10, 'beşten büyük olmalı')\nkontrol(a)");
+        assert_eq!(result.error.unwrap().error_type, KaramelErrorType::AssertFailedWithExpression {
+            expression: "x > 5 ve x < 10".to_string(),
+            line: 2,
+            message: "beşten büyük olmalı".to_string()
+        });
+
+        let result = run("liste_1 = [1, 2]\nhataayıklama::doğrula(liste_1.uzunluk()  ==  3)");
+        let error = result.error.unwrap().error_type;
+        assert!(error.is_assertion());
+        assert_eq!(error.message_in("tr"), "Doğrulama başarısız, satır 2: liste_1.uzunluk()  ==  3");
+
+        let result = run("hataayıklama::doğrula(1 == 1, 'mesaj')\nhataayıklama::doğrula('a', 'a')");
+        assert!(result.executed, "{:?}", result.error);
+    }
+
+    #[test]
+    fn test_test_runner() {
+        let code = "fonk topla(a, b):\n    döndür a + b\n\nfonk test_topla():\n    hataayıklama::doğrula(topla(1, 2), 3)\n\nfonk test_hatalı():\n    gç::satıryaz('ara')\n    hataayıklama::doğrula(topla(2, 2), 5)\n\nfonk test_parametreli(a):\n    döndür a\n\ngç::satıryaz('ana kod')";
+        let report = testing::code_tests(code).unwrap();
+
+        let names: Vec<&str> = report.results.iter().map(|result| result.name.as_str()).collect();
+        assert_eq!(names, vec!["test_topla", "test_hatalı"]);
+        assert_eq!((report.passed(), report.failed()), (1, 1));
+        assert!(report.results[0].stdout.is_empty());
+
+        let failed = &report.results[1];
+        assert_eq!(failed.stdout.trim_end(), "\"ara\"");
+        assert_eq!(failed.error.as_ref().unwrap().line, Some(8));
+        assert!(matches!(failed.error.as_ref().unwrap().error_type, KaramelErrorType::AssertFailedWithArgument { .. }));
+        assert!(report.render().contains("test_hatalı"));
+    }
+
+    #[test]
+    fn test_bench() {
+        let code = "fonk topla(n):\n    toplam = 0\n    döngü i = 0, i < n, ++i:\n        toplam += i\n    döndür toplam\n\nfonk ölç():\n    döndür topla(100)\n\ngç::satıryaz(topla(10))";
+        let options = bench::BenchOptions { iterations: 3, warmup: 1, function: None };
+        let report = bench::code_bench(code, "ölçüm", &options).unwrap();
+        assert_eq!(report.iterations, 3);
+        assert!(report.min <= report.average && report.average <= report.p95);
+
+        let options = bench::BenchOptions { function: Some("ölç".to_string()), ..options };
+        let function_report = bench::code_bench(code, "ölçüm", &options).unwrap();
+        assert_eq!(function_report.name, "ölçüm::ölç");
+        assert!(function_report.instructions > report.instructions);
+
+        let saved = bench::BenchReport::from_json(&function_report.to_json()).unwrap();
+        assert_eq!(saved.name, function_report.name);
+        assert_eq!(saved.instructions, function_report.instructions);
+        assert!(function_report.render(Some(&saved)).contains("+0.0%"));
+
+        let options = bench::BenchOptions { function: Some("yok".to_string()), ..options };
+        assert!(bench::code_bench(code, "ölçüm", &options).is_err());
+    }
+
+    #[test]
+    fn test_multiple_syntax_errors() {
+        let result = teaching_executer("a = (1\nb = 2\nfonk f():\n    c = [1,\n    d = 4\ne = +");
+        assert_eq!(result.executed, false);
+
+        let positions: Vec<_> = result.syntax_errors.iter().map(|error| (error.line, error.error_type.clone())).collect();
+        assert_eq!(positions, vec![(0, KaramelErrorType::ParenthesesNotClosed), (3, KaramelErrorType::InvalidListItem), (5, KaramelErrorType::InvalidUnaryOperation)]);
+        assert_eq!(result.error, result.syntax_errors.first().cloned());
+        assert_eq!(result.diagnostics().len(), 3);
+    }
+
+    #[test]
+    fn test_sandbox() {
+        let sandbox = sandbox::Sandbox::new();
+        sandbox.files.add_file("hesap.k", "fonk kare(a):\n    döndür a * a");
+        let sandbox = sandbox::Sandbox { stdin: "ali\nveli\n".to_string(), ..sandbox };
+
+        let result = sandbox.run("hesap yükle\nkullan listeler\ngç::satıryaz(gç::satıroku(), gç::satıroku(), gç::satıroku())\ngç::satıryaz(hesap::kare(3), listeler::toplam([1, 2]))");
+        assert!(result.success, "{:?}", result.diagnostics);
+        assert_eq!(result.stdout, "\"ali\"\"veli\"\"\"\r\n93\r\n");
+        assert_eq!(sandbox.run("gç::satıryaz(1)").to_json(), "{\"success\": true, \"compiled\": true, \"stdout\": \"1\\r\\n\", \"stderr\": \"\", \"diagnostics\": []}");
+
+        let result = sandbox.run("dosya::oku('/etc/passwd')");
+        assert!(!result.compiled);
+        assert_eq!(result.diagnostics[0].message_key, "PermissionDenied");
+
+        let result = sandbox::Sandbox { max_instructions: 1_000, ..sandbox::Sandbox::new() }.run("gç::satıryaz('başla')\ndene:\n    döngü doğru:\n        a = 1\nyakala hata:\n    gç::satıryaz('yakalandı')");
+        assert!(!result.success);
+        assert_eq!(result.stdout, "\"başla\"\r\n");
+        assert_eq!(result.runtime_error.unwrap().error_type, KaramelErrorType::InstructionLimitExceeded(1_000));
+    }
+
+    #[test]
+    fn test_sandbox_memory_limit() {
+        let result = sandbox::Sandbox::new().run("s = 'a'\ndöngü 1:\n    s = s + s");
+        assert!(!result.success);
+        assert_eq!(result.runtime_error.unwrap().error_type, KaramelErrorType::MemoryLimitExceeded(KARAMEL_SANDBOX_MEMORY_LIMIT));
+
+        let sandbox = sandbox::Sandbox { max_memory: 100_000, ..sandbox::Sandbox::new() };
+        for code in ["b = b'a'\ndöngü 1:\n    b = b + b", "l = [1]\ndöngü 1:\n    l.ekle(l.kopyala())", "y = baz::yazı_oluşturucu('a')\ndöngü 1:\n    y.ekle(y.yazı())",
+                     "s = 'a'\ndene:\n    döngü 1:\n        s = s + s\nyakala hata:\n    gç::satıryaz('yakalandı')"] {
+            let result = sandbox.run(code);
+            assert!(!result.success, "{}", code);
+            assert_eq!(result.stdout, "", "{}", code);
+            assert_eq!(result.runtime_error.unwrap().error_type, KaramelErrorType::MemoryLimitExceeded(100_000), "{}", code);
+        }
+
+        assert!(sandbox.run("s = ''\ndöngü i = 0, i < 100, i++:\n    s = s + 'a'\nhataayıklama::doğrula(s.uzunluk(), 100)").success);
+    }
+
+    #[test]
+    fn test_permissions() {
+        let sandbox = sandbox::Sandbox::new();
+        for code in ["kullan dosya::{oku}\noku('a.txt')", "dosya yükle", "depo::oku('a')"] {
+            let error = sandbox.run(code).diagnostics.remove(0);
+            assert_eq!(error.message_key, "PermissionDenied", "{}", code);
+        }
+        assert_eq!(KaramelErrorType::PermissionDenied { module: "dosya".to_string(), permission: "dosya".to_string() }.message_in("tr"), "'dosya' modülü için 'dosya' izni verilmemiş");
+
+        let sandbox = sandbox::Sandbox { permissions: vec![Permission::File], ..sandbox::Sandbox::new() };
+        sandbox.files.add_file("a.txt", "karamel");
+        let result = sandbox.run("kullan dosya::{oku}\nhataayıklama::doğrula(oku('a.txt'), 'karamel')");
+        assert!(result.success, "{:?}", result.diagnostics);
+        assert_eq!(Permission::from_name("dosya"), Some(Permission::File));
+    }
+}
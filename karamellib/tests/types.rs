@@ -2,7 +2,7 @@ extern crate karamellib;
 
 #[cfg(test)]
 mod tests {
-    use crate::karamellib::types::CharTraits;
+    use crate::karamellib::types::{CharTraits, Tokinizer};
 
     #[test]
     fn is_new_line() {
@@ -39,4 +39,25 @@ mod tests {
         assert_eq!(true, '\t'.is_whitespace());
         assert_eq!(false, '2'.is_whitespace());
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn tokinizer_cursor() {
+        let mut tokinizer = Tokinizer::new("ğü1");
+        assert_eq!(('ğ', 'ü', '1'), (tokinizer.get_char(), tokinizer.get_next_char(), tokinizer.get_third_char()));
+
+        tokinizer.increase_index();
+        assert_eq!(('ü', '1', '\0'), (tokinizer.get_char(), tokinizer.get_next_char(), tokinizer.get_third_char()));
+        assert_eq!((tokinizer.index, tokinizer.column), (2, 1));
+
+        tokinizer.increase_index();
+        tokinizer.increase_index();
+        assert!(tokinizer.is_end());
+        assert_eq!(tokinizer.get_char(), '\0');
+
+        tokinizer.increase_index();
+        assert_eq!((tokinizer.index, tokinizer.column), (5, 4));
+
+        let tokinizer = Tokinizer::with_position("ab\ncd", 3, 1, 0);
+        assert_eq!(('c', 'd'), (tokinizer.get_char(), tokinizer.get_next_char()));
+    }
+}
@@ -168,6 +168,15 @@ mod tests {
     test_last_memory!(vm_52, "boş == boş", KaramelPrimative::Bool(true));
     test_last_memory!(vm_53, "boş != boş", KaramelPrimative::Bool(false));
     test_last_memory!(vm_55, "test_1 == test_2", KaramelPrimative::Bool(true));
+    test_last_memory!(vm_108, "'not: ' + 3.5", KaramelPrimative::Text(Rc::new("not: 3,5".to_string())));
+    test_last_memory!(vm_109, "2021 + ' yılı'", KaramelPrimative::Text(Rc::new("2021 yılı".to_string())));
+    test_last_memory!(vm_110, "'x' + 1 + 2", KaramelPrimative::Text(Rc::new("x12".to_string())));
+    test_last_memory!(vm_111, "'x' + (0.1 + 0.2)", KaramelPrimative::Text(Rc::new("x0,3".to_string())));
+    test_last_memory!(vm_112, "'sıcaklık ' + -4", KaramelPrimative::Text(Rc::new("sıcaklık -4".to_string())));
+    test_last_memory!(vm_113, "3 * 'ha'", KaramelPrimative::Text(Rc::new("hahaha".to_string())));
+    test_last_memory!(vm_114, "'ha' * 0", KaramelPrimative::Text(Rc::new("".to_string())));
+    test_last_memory!(vm_115, "'ha' * 1.5", KaramelPrimative::Empty);
+    test_last_memory!(vm_116, "'ha' * -1", KaramelPrimative::Empty);
     test_variable_value!(vm_56, "text", "text = 1024", KaramelPrimative::Number(1024.0));
     test_variable_value!(vm_57, "result", r#"text = 1024
 result = text *2"#, KaramelPrimative::Number(2048.0));
@@ -201,6 +210,13 @@ erhan-=1"#, KaramelPrimative::Number(10.0));
 erhan/=2"#, KaramelPrimative::Number(5.0));
     test_variable_value!(vm_71, "erhan", r#"erhan=5
 erhan*=2"#, KaramelPrimative::Number(10.0));
+    test_variable_value!(vm_117, "erhan", r#"erhan='not: '
+erhan+=90"#, KaramelPrimative::Text(Rc::new("not: 90".to_string())));
+    test_variable_value!(vm_118, "erhan", r#"erhan='-'
+erhan*=3"#, KaramelPrimative::Text(Rc::new("---".to_string())));
+    test_variable_value!(vm_119, "full_text", r#"ad = 'erhan'
+yaş = 36.5
+full_text = ad + ' ' + yaş"#, KaramelPrimative::Text(Rc::new("erhan 36,5".to_string())));
     test_variable_value!(vm_72, "erhan", r#"erhan=9-3"#, KaramelPrimative::Number(6.0));
     test_variable_value!(vm_73, "erhan", r#"erhan=9/3"#, KaramelPrimative::Number(3.0));
     test_variable_value!(vm_74, "erhan", r#"
@@ -408,4 +424,9 @@ fonk Fibonacci(n):
 hataayıklama::doğrula(Fibonacci(10), 55)
 hataayıklama::doğrula(Fibonacci(20), 6765)
 "#);
-}
\ No newline at end of file
+execute!(vm_120, r#"
+o = baz::yazı_oluşturucu()
+o.ekle('a').ekle(1).ekle('b')
+hataayıklama::doğrula(o.yazı(), 'a1b')
+"#);
+}
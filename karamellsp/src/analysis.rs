@@ -0,0 +1,166 @@
+use std::rc::Rc;
+
+use lsp_types::{CompletionItem, CompletionItemKind, Diagnostic, DiagnosticSeverity, NumberOrString, Position, Range};
+
+use karamellib::buildin::Module;
+use karamellib::buildin::{base_functions::BaseFunctionsModule, constants::ConstantsModule, debug::DebugModule, io::IoModule, num::NumModule, store::StoreModule};
+use karamellib::error::KaramelError;
+use karamellib::error::diagnostic::{Diagnostic as KaramelDiagnostic, DiagnosticSeverity as KaramelDiagnosticSeverity};
+use karamellib::parser::Parser;
+use karamellib::syntax::SyntaxParser;
+use karamellib::types::{KEYWORDS, KaramelKeywordType, KaramelOperatorType, KaramelTokenType, Token};
+
+const SOURCE_NAME: &str = "karamel";
+
+fn error_to_diagnostic(error: &KaramelError) -> Diagnostic {
+    let diagnostic = KaramelDiagnostic::from(error);
+    Diagnostic {
+        range: Range::new(Position::new(diagnostic.span.line, diagnostic.span.start), Position::new(diagnostic.span.line, diagnostic.span.end)),
+        severity: Some(match diagnostic.severity {
+            KaramelDiagnosticSeverity::Error => DiagnosticSeverity::ERROR,
+            KaramelDiagnosticSeverity::Warning | KaramelDiagnosticSeverity::Lint => DiagnosticSeverity::WARNING
+        }),
+        code: Some(NumberOrString::String(diagnostic.code)),
+        source: Some(SOURCE_NAME.to_string()),
+        message: diagnostic.message,
+        ..Diagnostic::default()
+    }
+}
+
+/// Tokens without whitespaces and new lines. Tokens are returned even if tokenizer failed.
+fn tokenize(text: &str) -> Vec<Token> {
+    Parser::new(text).into_iter()
+        .map_while(Result::ok)
+        .filter(|token| !matches!(token.token_type, KaramelTokenType::WhiteSpace(_) | KaramelTokenType::NewLine(_)))
+        .collect()
+}
+
+fn token_range(token: &Token) -> Range {
+    Range::new(Position::new(token.line, token.start), Position::new(token.line, token.end))
+}
+
+pub fn diagnostics(text: &str) -> Vec<Diagnostic> {
+    let mut parser = Parser::new(text);
+    if let Err(error) = parser.parse() {
+        return vec![error_to_diagnostic(&error)];
+    }
+
+    let syntax = SyntaxParser::new(parser.tokens().to_vec());
+    match syntax.parse_with_recovery() {
+        Ok(_) => Vec::new(),
+        Err(errors) => errors.iter().map(error_to_diagnostic).collect()
+    }
+}
+
+/// Find the function or variable definition for the symbol at given position.
+/// Function definitions have priority, otherwise the first assignment is accepted as the definition.
+pub fn find_definition(text: &str, position: Position) -> Option<Range> {
+    let tokens = tokenize(text);
+    let name = tokens.iter().find_map(|token| match &token.token_type {
+        KaramelTokenType::Symbol(symbol) if token.line == position.line && token.start <= position.character && position.character <= token.end => Some(symbol.clone()),
+        _ => None
+    })?;
+
+    let mut variable_definition = None;
+    for (index, token) in tokens.iter().enumerate() {
+        let is_same_symbol = match &token.token_type {
+            KaramelTokenType::Symbol(symbol) => symbol == &name,
+            _ => false
+        };
+
+        if !is_same_symbol {
+            continue;
+        }
+
+        let previous = match index {
+            0 => None,
+            _ => tokens.get(index - 1)
+        };
+
+        if let Some(Token { token_type: KaramelTokenType::Keyword(KaramelKeywordType::Fn), .. }) = previous {
+            return Some(token_range(token));
+        }
+
+        if variable_definition.is_none() {
+            if let Some(Token { token_type: KaramelTokenType::Operator(KaramelOperatorType::Assign), .. }) = tokens.get(index + 1) {
+                variable_definition = Some(token_range(token));
+            }
+        }
+    }
+
+    variable_definition
+}
+
+pub fn completions() -> Vec<CompletionItem> {
+    let mut items = Vec::new();
+
+    for (keyword, _) in KEYWORDS.iter() {
+        items.push(CompletionItem {
+            label: keyword.to_string(),
+            kind: Some(CompletionItemKind::KEYWORD),
+            ..CompletionItem::default()
+        });
+    }
+
+    let modules: Vec<Rc<dyn Module>> = vec![BaseFunctionsModule::new(), IoModule::new(), NumModule::new(), DebugModule::new(), StoreModule::new(), ConstantsModule::new()];
+    for module in modules.iter() {
+        for function in module.get_methods().iter() {
+            items.push(CompletionItem {
+                label: format!("{}::{}", module.get_module_name(), function.name),
+                kind: Some(CompletionItemKind::FUNCTION),
+                ..CompletionItem::default()
+            });
+        }
+
+        for (name, _) in module.get_constants().iter() {
+            items.push(CompletionItem {
+                label: format!("{}::{}", module.get_module_name(), name),
+                kind: Some(CompletionItemKind::CONSTANT),
+                ..CompletionItem::default()
+            });
+        }
+    }
+
+    items.sort_by(|left, right| left.label.cmp(&right.label));
+    items
+}
+
+#[cfg(test)]
+mod tests {
+    use lsp_types::{Position, Range};
+    use super::*;
+
+    #[test]
+    fn test_diagnostics() {
+        assert!(diagnostics("a = 1 + 2").is_empty());
+
+        let result = diagnostics("a = 1\nb = (1 + 2");
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].range.start.line, 1);
+
+        let result = diagnostics("a = (1\nb = 2\nc = [1,\nd = 3");
+        let lines: Vec<u32> = result.iter().map(|diagnostic| diagnostic.range.start.line).collect();
+        assert_eq!(lines, vec![0, 2]);
+    }
+
+    #[test]
+    fn test_function_definition() {
+        let code = "fonk topla(bir, iki):\n    dondur bir + iki\nsonuc = topla(1, 2)";
+        assert_eq!(find_definition(code, Position::new(2, 10)), Some(Range::new(Position::new(0, 5), Position::new(0, 10))));
+    }
+
+    #[test]
+    fn test_variable_definition() {
+        let code = "sonuç = 1\nsonuç = 2\ngç::satıryaz(sonuç)";
+        assert_eq!(find_definition(code, Position::new(2, 14)), Some(Range::new(Position::new(0, 0), Position::new(0, 5))));
+        assert_eq!(find_definition(code, Position::new(2, 0)), None);
+    }
+
+    #[test]
+    fn test_completions() {
+        let items = completions();
+        assert!(items.iter().any(|item| item.label == "döngü"));
+        assert!(items.iter().any(|item| item.label == "gç::satıryaz"));
+        assert!(items.iter().any(|item| item.label == "sabitler::pi"));
+    }
+}
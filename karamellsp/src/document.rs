@@ -0,0 +1,122 @@
+use std::collections::HashMap;
+
+use lsp_types::{Position, TextDocumentContentChangeEvent, Url};
+
+pub struct Document {
+    pub text: String,
+    pub version: i32
+}
+
+#[derive(Default)]
+pub struct DocumentStore {
+    documents: HashMap<Url, Document>
+}
+
+impl DocumentStore {
+    pub fn new() -> Self {
+        DocumentStore::default()
+    }
+
+    pub fn open(&mut self, uri: Url, text: String, version: i32) {
+        self.documents.insert(uri, Document { text, version });
+    }
+
+    pub fn close(&mut self, uri: &Url) {
+        self.documents.remove(uri);
+    }
+
+    pub fn get(&self, uri: &Url) -> Option<&Document> {
+        self.documents.get(uri)
+    }
+
+    /// Apply changes that client sent. Changes without range replace the whole document. Positions after the end of a line or
+    /// the document are moved to the end, changes that end before they start are ignored.
+    pub fn change(&mut self, uri: &Url, version: i32, changes: Vec<TextDocumentContentChangeEvent>) {
+        let document = match self.documents.get_mut(uri) {
+            Some(document) => document,
+            None => return
+        };
+
+        for change in changes {
+            match change.range {
+                Some(range) => {
+                    let start = position_to_offset(&document.text, range.start);
+                    let end   = position_to_offset(&document.text, range.end);
+                    if start <= end {
+                        document.text.replace_range(start..end, &change.text);
+                    }
+                },
+                None => document.text = change.text
+            };
+        }
+
+        document.version = version;
+    }
+}
+
+/// Convert LSP position (line and UTF-16 based character) to byte offset.
+pub fn position_to_offset(text: &str, position: Position) -> usize {
+    let mut offset = 0;
+    for (index, line) in text.split_inclusive('\n').enumerate() {
+        if index as u32 == position.line {
+            let mut character = 0;
+            for (char_offset, chr) in line.char_indices() {
+                if character >= position.character || chr == '\n' {
+                    return offset + char_offset;
+                }
+                character += chr.len_utf16() as u32;
+            }
+            return offset + line.len();
+        }
+        offset += line.len();
+    }
+    text.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use lsp_types::{Position, Range, TextDocumentContentChangeEvent, Url};
+    use super::*;
+
+    fn change(start: (u32, u32), end: (u32, u32), text: &str) -> TextDocumentContentChangeEvent {
+        TextDocumentContentChangeEvent {
+            range: Some(Range::new(Position::new(start.0, start.1), Position::new(end.0, end.1))),
+            range_length: None,
+            text: text.to_string()
+        }
+    }
+
+    #[test]
+    fn test_position_to_offset() {
+        assert_eq!(position_to_offset("abc\ndef", Position::new(0, 0)), 0);
+        assert_eq!(position_to_offset("abc\ndef", Position::new(1, 1)), 5);
+        assert_eq!(position_to_offset("abc\ndef", Position::new(0, 10)), 3);
+        assert_eq!(position_to_offset("çğü\nabc", Position::new(0, 2)), 4);
+        assert_eq!(position_to_offset("abc", Position::new(5, 0)), 3);
+        assert_eq!(position_to_offset("😀a", Position::new(0, 1)), 4);
+    }
+
+    #[test]
+    fn test_incremental_change() {
+        let uri = Url::parse("file:///test.k").unwrap();
+        let mut store = DocumentStore::new();
+        store.open(uri.clone(), "a = 1\nb = 2".to_string(), 1);
+        store.change(&uri, 2, vec![change((1, 4), (1, 5), "20"), change((0, 0), (0, 1), "ğ")]);
+
+        let document = store.get(&uri).unwrap();
+        assert_eq!(document.text, "ğ = 1\nb = 20");
+        assert_eq!(document.version, 2);
+    }
+
+    #[test]
+    fn test_invalid_change() {
+        let uri = Url::parse("file:///test.k").unwrap();
+        let mut store = DocumentStore::new();
+        store.open(uri.clone(), "a = 1\nb = 2".to_string(), 1);
+        store.change(&uri, 2, vec![change((1, 3), (0, 2), "x"), change((0, 4), (0, 40), "10"), change((9, 0), (12, 5), "\nc = 3")]);
+
+        let document = store.get(&uri).unwrap();
+        assert_eq!(document.text, "a = 10\nb = 2\nc = 3");
+        assert_eq!(document.version, 2);
+    }
+}
@@ -0,0 +1,142 @@
+mod analysis;
+mod document;
+
+use std::error::Error;
+
+use lsp_server::{Connection, ErrorCode, Message, Notification, Request, RequestId, Response};
+use lsp_types::notification::{DidChangeTextDocument, DidCloseTextDocument, DidOpenTextDocument, Notification as _, PublishDiagnostics};
+use lsp_types::request::{Completion, GotoDefinition, Request as _};
+use lsp_types::{CompletionOptions, CompletionResponse, GotoDefinitionResponse, Location, OneOf, PublishDiagnosticsParams, ServerCapabilities, TextDocumentSyncCapability, TextDocumentSyncKind, Url};
+
+use document::DocumentStore;
+
+type LspResult<T> = Result<T, Box<dyn Error + Sync + Send>>;
+
+fn main() -> LspResult<()> {
+    let (connection, io_threads) = Connection::stdio();
+
+    let capabilities = serde_json::to_value(ServerCapabilities {
+        text_document_sync: Some(TextDocumentSyncCapability::Kind(TextDocumentSyncKind::INCREMENTAL)),
+        definition_provider: Some(OneOf::Left(true)),
+        completion_provider: Some(CompletionOptions {
+            trigger_characters: Some(vec![":".to_string()]),
+            ..CompletionOptions::default()
+        }),
+        ..ServerCapabilities::default()
+    })?;
+
+    connection.initialize(capabilities)?;
+    main_loop(connection)?;
+    io_threads.join()?;
+    Ok(())
+}
+
+/* Connection should be dropped before joining io threads, otherwise writer thread never finishes */
+fn main_loop(connection: Connection) -> LspResult<()> {
+    let mut documents = DocumentStore::new();
+
+    for message in &connection.receiver {
+        match message {
+            Message::Request(request) => {
+                if connection.handle_shutdown(&request)? {
+                    return Ok(());
+                }
+                handle_request(&connection, &documents, request)?;
+            },
+            Message::Notification(notification) => handle_notification(&connection, &mut documents, notification)?,
+            Message::Response(_) => ()
+        };
+    }
+    Ok(())
+}
+
+fn handle_request(connection: &Connection, documents: &DocumentStore, request: Request) -> LspResult<()> {
+    let id = request.id.clone();
+    match request.method.as_str() {
+        GotoDefinition::METHOD => {
+            let params: lsp_types::GotoDefinitionParams = serde_json::from_value(request.params)?;
+            let uri = params.text_document_position_params.text_document.uri;
+            let position = params.text_document_position_params.position;
+
+            let result = documents.get(&uri)
+                .and_then(|document| analysis::find_definition(&document.text, position))
+                .map(|range| GotoDefinitionResponse::Scalar(Location::new(uri.clone(), range)));
+            send_response(connection, id, serde_json::to_value(result)?)
+        },
+        Completion::METHOD => {
+            let result = CompletionResponse::Array(analysis::completions());
+            send_response(connection, id, serde_json::to_value(result)?)
+        },
+
+        /* Every request needs a response, otherwise the client waits for it */
+        method => {
+            let response = Response::new_err(id, ErrorCode::MethodNotFound as i32, format!("'{}' method is not supported", method));
+            connection.sender.send(Message::Response(response))?;
+            Ok(())
+        }
+    }
+}
+
+fn handle_notification(connection: &Connection, documents: &mut DocumentStore, notification: Notification) -> LspResult<()> {
+    match notification.method.as_str() {
+        DidOpenTextDocument::METHOD => {
+            let params: lsp_types::DidOpenTextDocumentParams = serde_json::from_value(notification.params)?;
+            let uri = params.text_document.uri.clone();
+            documents.open(params.text_document.uri, params.text_document.text, params.text_document.version);
+            publish_diagnostics(connection, documents, uri)
+        },
+        DidChangeTextDocument::METHOD => {
+            let params: lsp_types::DidChangeTextDocumentParams = serde_json::from_value(notification.params)?;
+            documents.change(&params.text_document.uri, params.text_document.version, params.content_changes);
+            publish_diagnostics(connection, documents, params.text_document.uri)
+        },
+        DidCloseTextDocument::METHOD => {
+            let params: lsp_types::DidCloseTextDocumentParams = serde_json::from_value(notification.params)?;
+            documents.close(&params.text_document.uri);
+            Ok(())
+        },
+
+        /* Notifications do not have a response, unknown ones are ignored */
+        _ => Ok(())
+    }
+}
+
+fn publish_diagnostics(connection: &Connection, documents: &DocumentStore, uri: Url) -> LspResult<()> {
+    let document = match documents.get(&uri) {
+        Some(document) => document,
+        None => return Ok(())
+    };
+
+    let params = PublishDiagnosticsParams::new(uri.clone(), analysis::diagnostics(&document.text), Some(document.version));
+    let notification = Notification::new(PublishDiagnostics::METHOD.to_string(), params);
+    connection.sender.send(Message::Notification(notification))?;
+    Ok(())
+}
+
+fn send_response(connection: &Connection, id: RequestId, result: serde_json::Value) -> LspResult<()> {
+    connection.sender.send(Message::Response(Response { id, result: Some(result), error: None }))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use lsp_server::{Connection, ErrorCode, Message, Request, RequestId};
+
+    use super::handle_request;
+    use crate::document::DocumentStore;
+
+    #[test]
+    fn test_unknown_request() {
+        let (server, client) = Connection::memory();
+        let request = Request::new(RequestId::from(7), "textDocument/hover".to_string(), serde_json::Value::Null);
+        handle_request(&server, &DocumentStore::new(), request).unwrap();
+
+        match client.receiver.try_recv().unwrap() {
+            Message::Response(response) => {
+                assert_eq!(response.id, RequestId::from(7));
+                assert_eq!(response.error.unwrap().code, ErrorCode::MethodNotFound as i32);
+            },
+            message => panic!("{:?}", message)
+        };
+    }
+}
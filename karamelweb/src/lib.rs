@@ -4,6 +4,8 @@ use karamellib::{compiler::KaramelPrimative, vm::executer::{ExecutionParameters,
 use wasm_bindgen::prelude::*;
 use js_sys::*;
 
+pub use karamellib::wasm::{compile, run};
+
 #[wasm_bindgen]
 pub fn execute_code(name: &str) -> Object {
     let response = js_sys::Object::new();
@@ -17,11 +19,11 @@ pub fn execute_code(name: &str) -> Object {
     let memory_dump_ref = JsValue::from("memory_dump");
 
     let parameters = ExecutionParameters {
-        source: ExecutionSource::Code(name.to_string()),
         return_opcode: true,
         return_output: true,
         dump_opcode: true,
-        dump_memory: true
+        dump_memory: true,
+        ..ExecutionParameters::new(ExecutionSource::Code(name.to_string()))
     };
 
     let result = karamellib::vm::executer::code_executer(parameters);